@@ -7,9 +7,12 @@ use aries_vcx_core::wallet::agency_client_wallet::ToBaseAgencyClientWallet;
 use crate::agent::agent_config::AgentConfig;
 
 use crate::error::*;
+use crate::events::EventBus;
+#[cfg(feature = "http_server")]
+use crate::http_server::{HttpInboundServer, InboundListener};
 use crate::services::connection::ServiceConnections;
 use crate::services::{
-    credential_definition::ServiceCredentialDefinitions, holder::ServiceCredentialsHolder,
+    credential_definition::ServiceCredentialDefinitions, holder::ServiceCredentialsHolder, inbound::ServiceInbound,
     issuer::ServiceCredentialsIssuer, mediated_connection::ServiceMediatedConnections, prover::ServiceProver,
     revocation_registry::ServiceRevocationRegistries, schema::ServiceSchemas, verifier::ServiceVerifier,
 };
@@ -27,6 +30,8 @@ pub struct Agent {
     pub(super) issuer: Arc<ServiceCredentialsIssuer>,
     pub(super) verifier: Arc<ServiceVerifier>,
     pub(super) prover: Arc<ServiceProver>,
+    pub(super) inbound: Arc<ServiceInbound>,
+    pub(super) events: Arc<EventBus>,
 }
 
 impl Agent {
@@ -97,4 +102,19 @@ impl Agent {
     pub fn prover(&self) -> Arc<ServiceProver> {
         self.prover.clone()
     }
+
+    pub fn inbound(&self) -> Arc<ServiceInbound> {
+        self.inbound.clone()
+    }
+
+    pub fn events(&self) -> Arc<EventBus> {
+        self.events.clone()
+    }
+
+    /// Receives the messages sent to the agent's endpoint on the listener, which should be bound
+    /// to the address of the service endpoint the agent was initialized with.
+    #[cfg(feature = "http_server")]
+    pub fn serve_inbound(&self, listener: InboundListener) -> AgentResult<HttpInboundServer> {
+        HttpInboundServer::serve(listener, self.inbound.clone())
+    }
 }
@@ -15,10 +15,12 @@ use url::Url;
 use crate::{
     agent::{agent_config::AgentConfig, agent_struct::Agent},
     error::AgentResult,
+    events::EventBus,
     services::{
         connection::{ServiceConnections, ServiceEndpoint},
         credential_definition::ServiceCredentialDefinitions,
         holder::ServiceCredentialsHolder,
+        inbound::ServiceInbound,
         issuer::ServiceCredentialsIssuer,
         mediated_connection::ServiceMediatedConnections,
         prover::ServiceProver,
@@ -113,9 +115,11 @@ impl Agent {
             (None, None)
         };
 
+        let events = Arc::new(EventBus::new());
         let connections = Arc::new(ServiceConnections::new(
             Arc::clone(&profile),
             init_config.service_endpoint,
+            events.clone(),
         ));
         let schemas = Arc::new(ServiceSchemas::new(
             Arc::clone(&profile),
@@ -126,10 +130,34 @@ impl Agent {
             Arc::clone(&profile),
             config_issuer.institution_did.clone(),
         ));
-        let issuer = Arc::new(ServiceCredentialsIssuer::new(Arc::clone(&profile), connections.clone()));
-        let holder = Arc::new(ServiceCredentialsHolder::new(Arc::clone(&profile), connections.clone()));
-        let verifier = Arc::new(ServiceVerifier::new(Arc::clone(&profile), connections.clone()));
-        let prover = Arc::new(ServiceProver::new(Arc::clone(&profile), connections.clone()));
+        let issuer = Arc::new(ServiceCredentialsIssuer::new(
+            Arc::clone(&profile),
+            connections.clone(),
+            events.clone(),
+        ));
+        let holder = Arc::new(ServiceCredentialsHolder::new(
+            Arc::clone(&profile),
+            connections.clone(),
+            events.clone(),
+        ));
+        let verifier = Arc::new(ServiceVerifier::new(
+            Arc::clone(&profile),
+            connections.clone(),
+            events.clone(),
+        ));
+        let prover = Arc::new(ServiceProver::new(
+            Arc::clone(&profile),
+            connections.clone(),
+            events.clone(),
+        ));
+        let inbound = Arc::new(ServiceInbound::new(
+            Arc::clone(&profile),
+            connections.clone(),
+            issuer.clone(),
+            holder.clone(),
+            verifier.clone(),
+            prover.clone(),
+        ));
 
         Ok(Self {
             profile,
@@ -142,6 +170,8 @@ impl Agent {
             holder,
             verifier,
             prover,
+            inbound,
+            events,
             config: AgentConfig {
                 config_wallet,
                 config_issuer,
@@ -0,0 +1,380 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use aries_vcx::messages::AriesMessage;
+use aries_vcx::protocols::connection::ThinState;
+use aries_vcx::protocols::issuance::holder::state_machine::HolderState;
+use aries_vcx::protocols::issuance::issuer::state_machine::IssuerState;
+use aries_vcx::protocols::proof_presentation::prover::state_machine::ProverState;
+use aries_vcx::protocols::proof_presentation::verifier::state_machine::VerifierState;
+use url::Url;
+
+use crate::error::*;
+
+use self::webhook::{Webhook, WebhookClient};
+
+mod webhook;
+
+/// A protocol record moving from one state to another.
+#[derive(Clone, Debug)]
+pub struct StateChange<S> {
+    pub thread_id: String,
+    pub connection_id: String,
+    /// `None` when the record was just created.
+    pub old_state: Option<S>,
+    pub new_state: S,
+    /// The received message which made the state change, if any.
+    pub message: Option<AriesMessage>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ProtocolEvent {
+    Connection(StateChange<ThinState>),
+    Issuer(StateChange<IssuerState>),
+    Holder(StateChange<HolderState>),
+    Verifier(StateChange<VerifierState>),
+    Prover(StateChange<ProverState>),
+}
+
+impl ProtocolEvent {
+    pub fn thread_id(&self) -> &str {
+        match self {
+            Self::Connection(change) => &change.thread_id,
+            Self::Issuer(change) => &change.thread_id,
+            Self::Holder(change) => &change.thread_id,
+            Self::Verifier(change) => &change.thread_id,
+            Self::Prover(change) => &change.thread_id,
+        }
+    }
+
+    pub fn connection_id(&self) -> &str {
+        match self {
+            Self::Connection(change) => &change.connection_id,
+            Self::Issuer(change) => &change.connection_id,
+            Self::Holder(change) => &change.connection_id,
+            Self::Verifier(change) => &change.connection_id,
+            Self::Prover(change) => &change.connection_id,
+        }
+    }
+
+    pub fn message(&self) -> Option<&AriesMessage> {
+        match self {
+            Self::Connection(change) => change.message.as_ref(),
+            Self::Issuer(change) => change.message.as_ref(),
+            Self::Holder(change) => change.message.as_ref(),
+            Self::Verifier(change) => change.message.as_ref(),
+            Self::Prover(change) => change.message.as_ref(),
+        }
+    }
+
+    /// The protocol of the record, named as the topics of ACA-Py webhooks.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::Connection(_) => "connections",
+            Self::Issuer(_) | Self::Holder(_) => "issue_credential",
+            Self::Verifier(_) | Self::Prover(_) => "present_proof",
+        }
+    }
+
+    pub fn role(&self) -> &'static str {
+        match self {
+            Self::Connection(change) => match change.new_state {
+                ThinState::Invitee(_) => "invitee",
+                ThinState::Inviter(_) => "inviter",
+                ThinState::Requester(_) => "requester",
+                ThinState::Responder(_) => "responder",
+            },
+            Self::Issuer(_) => "issuer",
+            Self::Holder(_) => "holder",
+            Self::Verifier(_) => "verifier",
+            Self::Prover(_) => "prover",
+        }
+    }
+
+    /// The states as debug-formatted, such as `OfferReceived`.
+    pub fn states(&self) -> (Option<String>, String) {
+        fn format<S: std::fmt::Debug>(change: &StateChange<S>) -> (Option<String>, String) {
+            (
+                change.old_state.as_ref().map(|state| format!("{:?}", state)),
+                format!("{:?}", change.new_state),
+            )
+        }
+        match self {
+            Self::Connection(change) => format(change),
+            Self::Issuer(change) => format(change),
+            Self::Holder(change) => format(change),
+            Self::Verifier(change) => format(change),
+            Self::Prover(change) => format(change),
+        }
+    }
+}
+
+impl From<StateChange<ThinState>> for ProtocolEvent {
+    fn from(value: StateChange<ThinState>) -> Self {
+        Self::Connection(value)
+    }
+}
+
+impl From<StateChange<IssuerState>> for ProtocolEvent {
+    fn from(value: StateChange<IssuerState>) -> Self {
+        Self::Issuer(value)
+    }
+}
+
+impl From<StateChange<HolderState>> for ProtocolEvent {
+    fn from(value: StateChange<HolderState>) -> Self {
+        Self::Holder(value)
+    }
+}
+
+impl From<StateChange<VerifierState>> for ProtocolEvent {
+    fn from(value: StateChange<VerifierState>) -> Self {
+        Self::Verifier(value)
+    }
+}
+
+impl From<StateChange<ProverState>> for ProtocolEvent {
+    fn from(value: StateChange<ProverState>) -> Self {
+        Self::Prover(value)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+pub type Subscriber = Arc<dyn Fn(&ProtocolEvent) + Send + Sync>;
+
+/// Hands the state changes of the agent's protocol records to the subscribers, which are called
+/// on the task making the change, and posts them to the webhooks.
+///
+/// Subscribers are called without the bus being locked, so they may subscribe and unsubscribe.
+pub struct EventBus {
+    next_id: AtomicU64,
+    subscribers: RwLock<Vec<(SubscriptionId, Subscriber)>>,
+    webhooks: RwLock<Vec<Webhook>>,
+    webhook_client: WebhookClient,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            subscribers: RwLock::new(Vec::new()),
+            webhooks: RwLock::new(Vec::new()),
+            webhook_client: WebhookClient::new(),
+        }
+    }
+
+    pub fn subscribe<F>(&self, subscriber: F) -> AgentResult<SubscriptionId>
+    where
+        F: Fn(&ProtocolEvent) + Send + Sync + 'static,
+    {
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.subscribers
+            .write()
+            .map_err(|err| lock_error("subscribers", err))?
+            .push((id, Arc::new(subscriber)));
+        Ok(id)
+    }
+
+    /// Returns whether the subscriber was found.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> AgentResult<bool> {
+        let mut subscribers = self.subscribers.write().map_err(|err| lock_error("subscribers", err))?;
+        let count = subscribers.len();
+        subscribers.retain(|(subscriber_id, _)| *subscriber_id != id);
+        Ok(subscribers.len() != count)
+    }
+
+    /// Events are posted as JSON to `<url>/topic/<topic>/`, as ACA-Py does. Failed deliveries are
+    /// logged and not retried.
+    ///
+    /// The messages making the changes are left out of the events, as they can hold credentials,
+    /// see [`Self::add_webhook_with_messages`].
+    pub fn add_webhook(&self, url: Url) -> AgentResult<()> {
+        self.push_webhook(url, false)
+    }
+
+    /// Like [`Self::add_webhook`], with the received messages making the changes in the events.
+    pub fn add_webhook_with_messages(&self, url: Url) -> AgentResult<()> {
+        self.push_webhook(url, true)
+    }
+
+    fn push_webhook(&self, url: Url, include_message: bool) -> AgentResult<()> {
+        if url.cannot_be_a_base() {
+            return Err(AgentError::from_msg(
+                AgentErrorKind::InvalidArguments,
+                &format!("Webhook url {} cannot be a base", url),
+            ));
+        }
+        self.webhooks
+            .write()
+            .map_err(|err| lock_error("webhooks", err))?
+            .push(Webhook { url, include_message });
+        Ok(())
+    }
+
+    pub fn remove_webhook(&self, url: &Url) -> AgentResult<()> {
+        self.webhooks
+            .write()
+            .map_err(|err| lock_error("webhooks", err))?
+            .retain(|webhook| &webhook.url != url);
+        Ok(())
+    }
+
+    /// Publishes the change of state of a record, unless it stayed in the same state.
+    pub(crate) fn state_changed<S>(
+        &self,
+        thread_id: &str,
+        connection_id: &str,
+        old_state: Option<S>,
+        new_state: S,
+        message: Option<AriesMessage>,
+    ) where
+        S: PartialEq,
+        StateChange<S>: Into<ProtocolEvent>,
+    {
+        if old_state.as_ref() == Some(&new_state) {
+            return;
+        }
+        self.publish(
+            StateChange {
+                thread_id: thread_id.to_string(),
+                connection_id: connection_id.to_string(),
+                old_state,
+                new_state,
+                message,
+            }
+            .into(),
+        );
+    }
+
+    fn publish(&self, event: ProtocolEvent) {
+        let subscribers: Vec<Subscriber> = match self.subscribers.read() {
+            Ok(subscribers) => subscribers.iter().map(|(_, subscriber)| subscriber.clone()).collect(),
+            Err(err) => {
+                error!("Unable to notify subscribers of event {:?}: {:?}", event, err);
+                Vec::new()
+            }
+        };
+        subscribers.iter().for_each(|subscriber| subscriber(&event));
+
+        let webhooks = match self.webhooks.read() {
+            Ok(webhooks) => webhooks.clone(),
+            Err(err) => {
+                error!("Unable to post event {:?} to webhooks: {:?}", event, err);
+                Vec::new()
+            }
+        };
+        webhooks
+            .iter()
+            .for_each(|webhook| self.webhook_client.post(webhook, &event));
+    }
+}
+
+fn lock_error<E: std::fmt::Debug>(name: &str, err: E) -> AgentError {
+    AgentError::from_msg(
+        AgentErrorKind::LockError,
+        &format!("Unable to lock event {}: {:?}", name, err),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Mutex;
+
+    use aries_vcx::protocols::connection::State;
+
+    use super::*;
+
+    /// Subscribes a subscriber collecting the thread ids of the events.
+    fn collect(bus: &EventBus) -> (SubscriptionId, Arc<Mutex<Vec<String>>>) {
+        let collected = Arc::new(Mutex::new(Vec::new()));
+        let sink = collected.clone();
+        let id = bus
+            .subscribe(move |event| sink.lock().unwrap().push(event.thread_id().to_string()))
+            .unwrap();
+        (id, collected)
+    }
+
+    fn change_state(bus: &EventBus, thread_id: &str, old_state: Option<State>, new_state: State) {
+        bus.state_changed(
+            thread_id,
+            "connection_id",
+            old_state.map(ThinState::Invitee),
+            ThinState::Invitee(new_state),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_subscribe_unsubscribe() {
+        let bus = EventBus::new();
+        let (first, first_events) = collect(&bus);
+        let (_, second_events) = collect(&bus);
+
+        change_state(&bus, "thread_1", None, State::Invited);
+        assert!(bus.unsubscribe(first).unwrap());
+        assert!(!bus.unsubscribe(first).unwrap());
+        change_state(&bus, "thread_2", None, State::Invited);
+
+        assert_eq!(*first_events.lock().unwrap(), vec!["thread_1"]);
+        assert_eq!(*second_events.lock().unwrap(), vec!["thread_1", "thread_2"]);
+    }
+
+    #[test]
+    fn test_no_event_when_state_unchanged() {
+        let bus = EventBus::new();
+        let (_, events) = collect(&bus);
+
+        change_state(&bus, "thread_1", Some(State::Requested), State::Requested);
+        assert!(events.lock().unwrap().is_empty());
+
+        change_state(&bus, "thread_1", Some(State::Requested), State::Completed);
+        assert_eq!(*events.lock().unwrap(), vec!["thread_1"]);
+    }
+
+    #[test]
+    fn test_subscriber_unsubscribes_itself() {
+        let bus = Arc::new(EventBus::new());
+        let own_id = Arc::new(Mutex::new(None));
+        let calls = Arc::new(AtomicU64::new(0));
+
+        let (subscriber_bus, subscriber_id, subscriber_calls) = (bus.clone(), own_id.clone(), calls.clone());
+        let id = bus
+            .subscribe(move |_| {
+                subscriber_calls.fetch_add(1, Ordering::SeqCst);
+                if let Some(id) = subscriber_id.lock().unwrap().take() {
+                    subscriber_bus.unsubscribe(id).unwrap();
+                }
+            })
+            .unwrap();
+        *own_id.lock().unwrap() = Some(id);
+
+        change_state(&bus, "thread_1", None, State::Invited);
+        change_state(&bus, "thread_2", None, State::Invited);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_add_remove_webhook() {
+        let bus = EventBus::new();
+        let url = Url::parse("http://localhost:8022/webhooks").unwrap();
+
+        bus.add_webhook(url.clone()).unwrap();
+        bus.add_webhook_with_messages(Url::parse("http://localhost:8023/").unwrap())
+            .unwrap();
+        assert!(bus.add_webhook(Url::parse("mailto:admin@localhost").unwrap()).is_err());
+
+        bus.remove_webhook(&url).unwrap();
+        let webhooks = bus.webhooks.read().unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert!(webhooks[0].include_message);
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use url::Url;
+
+use super::ProtocolEvent;
+
+/// A URL the events are posted to.
+#[derive(Clone, Debug)]
+pub(super) struct Webhook {
+    pub(super) url: Url,
+    /// Whether the received message making the change is posted along.
+    pub(super) include_message: bool,
+}
+
+impl Webhook {
+    /// The URL the events of the topic are posted to.
+    fn topic_url(&self, topic: &str) -> Url {
+        let mut url = self.url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().extend(["topic", topic, ""]);
+        }
+        url
+    }
+
+    /// The body of a webhook request, after the records ACA-Py posts.
+    fn payload(&self, event: &ProtocolEvent) -> serde_json::Value {
+        let (old_state, state) = event.states();
+        let mut payload = json!({
+            "topic": event.topic(),
+            "role": event.role(),
+            "thread_id": event.thread_id(),
+            "connection_id": event.connection_id(),
+            "old_state": old_state,
+            "state": state,
+        });
+        if self.include_message {
+            payload["message"] = json!(event.message());
+        }
+        payload
+    }
+}
+
+pub(super) struct WebhookClient {
+    client: Client,
+}
+
+impl WebhookClient {
+    pub(super) fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+
+    /// Posts the event in the background, on the runtime of the caller.
+    pub(super) fn post(&self, webhook: &Webhook, event: &ProtocolEvent) {
+        let body = match serde_json::to_vec(&webhook.payload(event)) {
+            Ok(body) => body,
+            Err(err) => {
+                error!("Unable to serialize event {:?}: {}", event, err);
+                return;
+            }
+        };
+        let url = webhook.topic_url(event.topic());
+
+        let runtime = match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                warn!("Unable to post event to webhook {}: {}", url, err);
+                return;
+            }
+        };
+        let request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        runtime.spawn(async move {
+            match request.send().await.and_then(|response| response.error_for_status()) {
+                Ok(_) => debug!("Posted event to webhook {}", url),
+                Err(err) => warn!("Failed to post event to webhook {}: {}", url, err),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx::messages::msg_fields::protocols::cred_issuance::request_credential::{
+        RequestCredential, RequestCredentialContent, RequestCredentialDecorators,
+    };
+    use aries_vcx::messages::AriesMessage;
+    use aries_vcx::protocols::issuance::issuer::state_machine::IssuerState;
+
+    use super::*;
+    use crate::events::StateChange;
+
+    fn webhook(url: &str, include_message: bool) -> Webhook {
+        Webhook {
+            url: Url::parse(url).unwrap(),
+            include_message,
+        }
+    }
+
+    fn request_received() -> (ProtocolEvent, AriesMessage) {
+        let request: AriesMessage = RequestCredential::with_decorators(
+            "request_id".to_owned(),
+            RequestCredentialContent::new(vec![]),
+            RequestCredentialDecorators::default(),
+        )
+        .into();
+        let event = ProtocolEvent::Issuer(StateChange {
+            thread_id: "thread_id".to_owned(),
+            connection_id: "connection_id".to_owned(),
+            old_state: Some(IssuerState::OfferSent),
+            new_state: IssuerState::RequestReceived,
+            message: Some(request.clone()),
+        });
+        (event, request)
+    }
+
+    #[test]
+    fn test_topic_url() {
+        for url in ["http://localhost:8022/webhooks", "http://localhost:8022/webhooks/"] {
+            assert_eq!(
+                webhook(url, false).topic_url("connections").as_str(),
+                "http://localhost:8022/webhooks/topic/connections/"
+            );
+        }
+        assert_eq!(
+            webhook("http://localhost:8022", false)
+                .topic_url("present_proof")
+                .as_str(),
+            "http://localhost:8022/topic/present_proof/"
+        );
+    }
+
+    #[test]
+    fn test_payload_leaves_out_message() {
+        let (event, _) = request_received();
+        let expected = json!({
+            "topic": "issue_credential",
+            "role": "issuer",
+            "thread_id": "thread_id",
+            "connection_id": "connection_id",
+            "old_state": "OfferSent",
+            "state": "RequestReceived",
+        });
+        assert_eq!(webhook("http://localhost:8022/", false).payload(&event), expected);
+    }
+
+    #[test]
+    fn test_payload_with_message() {
+        let (event, request) = request_received();
+        let payload = webhook("http://localhost:8022/", true).payload(&event);
+        assert_eq!(payload["message"], serde_json::to_value(&request).unwrap());
+        assert_eq!(payload["state"], "RequestReceived");
+    }
+}
@@ -0,0 +1,299 @@
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http_body::{LengthLimitError, Limited};
+use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, HeaderMap, Method, Request, Response, Server, StatusCode};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use url::Url;
+
+use crate::error::*;
+use crate::services::inbound::{InboundEvent, ServiceInbound};
+
+pub const DIDCOMM_ENVELOPE_ENC: &str = "application/didcomm-envelope-enc";
+pub const SSI_AGENT_WIRE: &str = "application/ssi-agent-wire";
+/// Messages larger than this are refused, without their body being read past the limit.
+pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Takes the packed messages received on the endpoint, implemented by [`ServiceInbound`] and
+/// stubbed in tests.
+#[async_trait]
+trait InboundReceiver: Send + Sync + 'static {
+    async fn receive_packed(&self, payload: Vec<u8>) -> AgentResult<InboundEvent>;
+}
+
+#[async_trait]
+impl InboundReceiver for ServiceInbound {
+    async fn receive_packed(&self, payload: Vec<u8>) -> AgentResult<InboundEvent> {
+        self.receive_message(payload).await
+    }
+}
+
+/// The socket of the agent's inbound endpoint, bound before the agent is initialized so that its
+/// service endpoint is known.
+pub struct InboundListener {
+    listener: TcpListener,
+}
+
+impl InboundListener {
+    /// Binds to the address, for example `127.0.0.1:0` for any free port on localhost.
+    pub fn bind(addr: impl ToSocketAddrs) -> AgentResult<Self> {
+        let listener = TcpListener::bind(addr).map_err(io_error)?;
+        listener.set_nonblocking(true).map_err(io_error)?;
+        Ok(Self { listener })
+    }
+
+    pub fn local_addr(&self) -> AgentResult<SocketAddr> {
+        self.listener.local_addr().map_err(io_error)
+    }
+
+    /// The endpoint to advertise in invitations and DID documents.
+    pub fn endpoint(&self) -> AgentResult<Url> {
+        let endpoint = format!("http://{}/", self.local_addr()?);
+        Url::parse(&endpoint).map_err(|err| {
+            AgentError::from_msg(
+                AgentErrorKind::InvalidArguments,
+                &format!("Invalid endpoint {}: {}", endpoint, err),
+            )
+        })
+    }
+}
+
+/// Receives packed messages POSTed to the agent's endpoint and hands them to
+/// [`ServiceInbound::receive_message`]. Serves until shut down or dropped.
+pub struct HttpInboundServer {
+    local_addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl HttpInboundServer {
+    /// Serves on the listener, in a task of the current tokio runtime.
+    pub fn serve(listener: InboundListener, inbound: Arc<ServiceInbound>) -> AgentResult<Self> {
+        Self::serve_receiver(listener, inbound)
+    }
+
+    fn serve_receiver<I: InboundReceiver>(listener: InboundListener, inbound: Arc<I>) -> AgentResult<Self> {
+        let local_addr = listener.local_addr()?;
+        let make_service = make_service_fn(move |_| {
+            let inbound = inbound.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| receive(inbound.clone(), request))) }
+        });
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        let server = Server::from_tcp(listener.listener)
+            .map_err(|err| {
+                AgentError::from_msg(
+                    AgentErrorKind::InvalidArguments,
+                    &format!("Unable to serve on {}: {}", local_addr, err),
+                )
+            })?
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                shutdown_signal.await.ok();
+            });
+
+        info!("Serving inbound endpoint on {}", local_addr);
+        let task = tokio::spawn(async move {
+            if let Err(err) = server.await {
+                error!("Inbound endpoint on {} failed: {}", local_addr, err);
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            shutdown: Some(shutdown),
+            task,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stops accepting connections and waits for the requests in flight to be answered.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        (&mut self.task).await.ok();
+    }
+}
+
+impl Drop for HttpInboundServer {
+    fn drop(&mut self) {
+        if self.shutdown.is_some() {
+            self.task.abort();
+        }
+    }
+}
+
+async fn receive<I: InboundReceiver>(inbound: Arc<I>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST {
+        return Ok(respond(StatusCode::METHOD_NOT_ALLOWED));
+    }
+    if !is_didcomm_content_type(request.headers()) {
+        return Ok(respond(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    }
+    if matches!(content_length(request.headers()), Some(length) if length > MAX_MESSAGE_SIZE as u64) {
+        return Ok(respond(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+    let payload = match hyper::body::to_bytes(Limited::new(request.into_body(), MAX_MESSAGE_SIZE)).await {
+        Ok(payload) => payload,
+        Err(err) if err.is::<LengthLimitError>() => {
+            warn!("Inbound message exceeds {} bytes, refusing it", MAX_MESSAGE_SIZE);
+            return Ok(respond(StatusCode::PAYLOAD_TOO_LARGE));
+        }
+        Err(err) => {
+            warn!("Unable to read inbound message: {}", err);
+            return Ok(respond(StatusCode::BAD_REQUEST));
+        }
+    };
+
+    match inbound.receive_packed(payload.to_vec()).await {
+        Ok(InboundEvent::Handled {
+            connection_id,
+            thread_id,
+        }) => {
+            debug!(
+                "Handled inbound message of thread {} over connection {}",
+                thread_id, connection_id
+            );
+            Ok(respond(StatusCode::ACCEPTED))
+        }
+        Ok(InboundEvent::Unhandled(_)) => Ok(respond(StatusCode::ACCEPTED)),
+        Err(err) => {
+            warn!("Failed to receive inbound message: {}", err);
+            match err.kind {
+                AgentErrorKind::NotFound | AgentErrorKind::SerializationError => Ok(respond(StatusCode::BAD_REQUEST)),
+                _ => Ok(respond(StatusCode::INTERNAL_SERVER_ERROR)),
+            }
+        }
+    }
+}
+
+fn is_didcomm_content_type(headers: &HeaderMap) -> bool {
+    let content_type = match headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok()) {
+        Some(content_type) => content_type,
+        None => return false,
+    };
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    media_type.eq_ignore_ascii_case(DIDCOMM_ENVELOPE_ENC) || media_type.eq_ignore_ascii_case(SSI_AGENT_WIRE)
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+fn respond(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+fn io_error(err: std::io::Error) -> AgentError {
+    AgentError::from_msg(
+        AgentErrorKind::InvalidArguments,
+        &format!("Inbound endpoint socket error: {}", err),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Takes any payload, as if it was a message of the thread `thread_id` over `connection_id`.
+    #[derive(Default)]
+    struct StubReceiver {
+        received: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl InboundReceiver for StubReceiver {
+        async fn receive_packed(&self, payload: Vec<u8>) -> AgentResult<InboundEvent> {
+            self.received.lock().unwrap().push(payload);
+            Ok(InboundEvent::Handled {
+                connection_id: "connection_id".to_string(),
+                thread_id: "thread_id".to_string(),
+            })
+        }
+    }
+
+    fn request(method: Method, content_type: &str, body: impl Into<Body>) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .header(CONTENT_TYPE, content_type)
+            .body(body.into())
+            .unwrap()
+    }
+
+    async fn status_of(inbound: &Arc<StubReceiver>, request: Request<Body>) -> StatusCode {
+        receive(inbound.clone(), request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_receive_accepts_packed_message() {
+        let inbound = Arc::new(StubReceiver::default());
+        let packed = br#"{"protected":"e30","iv":"aXY","ciphertext":"Y3Q","tag":"dGFn"}"#.to_vec();
+
+        let status = status_of(&inbound, request(Method::POST, DIDCOMM_ENVELOPE_ENC, packed.clone())).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let status = status_of(
+            &inbound,
+            request(
+                Method::POST,
+                "Application/SSI-Agent-Wire; charset=utf-8",
+                packed.clone(),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(*inbound.received.lock().unwrap(), vec![packed.clone(), packed]);
+    }
+
+    #[tokio::test]
+    async fn test_receive_refuses_wrong_method() {
+        let inbound = Arc::new(StubReceiver::default());
+        let status = status_of(&inbound, request(Method::GET, DIDCOMM_ENVELOPE_ENC, Body::empty())).await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        assert!(inbound.received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_receive_refuses_wrong_content_type() {
+        let inbound = Arc::new(StubReceiver::default());
+        let status = status_of(&inbound, request(Method::POST, "application/json", "{}")).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let without_content_type = Request::builder().method(Method::POST).body(Body::from("{}")).unwrap();
+        assert_eq!(
+            status_of(&inbound, without_content_type).await,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+        assert!(inbound.received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_receive_refuses_oversized_message() {
+        let inbound = Arc::new(StubReceiver::default());
+
+        let mut declared = request(Method::POST, DIDCOMM_ENVELOPE_ENC, "{}");
+        declared
+            .headers_mut()
+            .insert(CONTENT_LENGTH, (MAX_MESSAGE_SIZE + 1).into());
+        assert_eq!(status_of(&inbound, declared).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let undeclared = request(Method::POST, DIDCOMM_ENVELOPE_ENC, vec![b'0'; MAX_MESSAGE_SIZE + 1]);
+        assert_eq!(status_of(&inbound, undeclared).await, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(inbound.received.lock().unwrap().is_empty());
+    }
+}
@@ -9,9 +9,16 @@ extern crate uuid;
 
 mod agent;
 mod error;
+mod events;
 mod http_client;
+#[cfg(feature = "http_server")]
+mod http_server;
 mod services;
 mod storage;
 
 pub use agent::*;
 pub use error::*;
+pub use events::{EventBus, ProtocolEvent, StateChange, Subscriber, SubscriptionId};
+#[cfg(feature = "http_server")]
+pub use http_server::{HttpInboundServer, InboundListener, DIDCOMM_ENVELOPE_ENC, SSI_AGENT_WIRE};
+pub use services::inbound::{InboundEvent, UnhandledMessage};
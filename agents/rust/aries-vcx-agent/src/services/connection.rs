@@ -1,35 +1,68 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use crate::error::*;
+use crate::events::EventBus;
 use crate::http_client::HttpClient;
-use crate::storage::object_cache::ObjectCache;
-use crate::storage::Storage;
+use crate::storage::wallet_storage::WalletStorage;
+use crate::storage::{RecordTags, Storage, WalletRecord};
 use aries_vcx::core::profile::profile::Profile;
 use aries_vcx::handlers::util::AnyInvitation;
 use aries_vcx::messages::msg_fields::protocols::connection::request::Request;
 use aries_vcx::messages::msg_fields::protocols::connection::response::Response;
 use aries_vcx::messages::msg_fields::protocols::notification::ack::Ack;
+use aries_vcx::messages::AriesMessage;
 use aries_vcx::protocols::connection::pairwise_info::PairwiseInfo;
 use aries_vcx::protocols::connection::{Connection, GenericConnection, State, ThinState};
 use url::Url;
 
 pub type ServiceEndpoint = Url;
 
+impl WalletRecord for GenericConnection {
+    fn tags(&self) -> RecordTags {
+        RecordTags::default()
+            .with_state(&self.state())
+            .with_thread_id(self.thread_id())
+            .with_our_vk(Some(&self.pairwise_info().pw_vk))
+            .with_their_vk(self.remote_vk().ok().as_deref())
+    }
+}
+
 pub struct ServiceConnections {
     profile: Arc<dyn Profile>,
     service_endpoint: ServiceEndpoint,
-    connections: Arc<ObjectCache<GenericConnection>>,
+    connections: Arc<WalletStorage<GenericConnection>>,
+    events: Arc<EventBus>,
 }
 
 impl ServiceConnections {
-    pub fn new(profile: Arc<dyn Profile>, service_endpoint: ServiceEndpoint) -> Self {
+    pub fn new(profile: Arc<dyn Profile>, service_endpoint: ServiceEndpoint, events: Arc<EventBus>) -> Self {
         Self {
+            connections: Arc::new(WalletStorage::new(profile.inject_wallet(), "agent-connections")),
             profile,
             service_endpoint,
-            connections: Arc::new(ObjectCache::new("connections")),
+            events,
         }
     }
 
+    async fn store(
+        &self,
+        thread_id: &str,
+        connection: GenericConnection,
+        message: Option<AriesMessage>,
+    ) -> AgentResult<String> {
+        let old_state = self
+            .connections
+            .get(thread_id)
+            .await
+            .ok()
+            .map(|connection| connection.state());
+        let new_state = connection.state();
+        self.connections.insert(thread_id, connection).await?;
+        self.events
+            .state_changed(thread_id, thread_id, old_state, new_state, message);
+        Ok(thread_id.to_string())
+    }
+
     pub async fn create_invitation(&self, pw_info: Option<PairwiseInfo>) -> AgentResult<AnyInvitation> {
         let pw_info = pw_info.unwrap_or(PairwiseInfo::create(&self.profile.inject_wallet()).await?);
         let inviter =
@@ -37,7 +70,7 @@ impl ServiceConnections {
         let invite = inviter.get_invitation().clone();
         let thread_id = inviter.thread_id().to_owned();
 
-        self.connections.insert(&thread_id, inviter.into())?;
+        self.store(&thread_id, inviter.into(), None).await?;
 
         Ok(invite)
     }
@@ -50,11 +83,11 @@ impl ServiceConnections {
 
         let thread_id = invitee.thread_id().to_owned();
 
-        self.connections.insert(&thread_id, invitee.into())
+        self.store(&thread_id, invitee.into(), None).await
     }
 
     pub async fn send_request(&self, thread_id: &str) -> AgentResult<()> {
-        let invitee: Connection<_, _> = self.connections.get(thread_id)?.try_into()?;
+        let invitee: Connection<_, _> = self.connections.get(thread_id).await?.try_into()?;
         let invitee = invitee
             .send_request(
                 &self.profile.inject_wallet(),
@@ -64,12 +97,12 @@ impl ServiceConnections {
             )
             .await?;
 
-        self.connections.insert(thread_id, invitee.into())?;
+        self.store(thread_id, invitee.into(), None).await?;
         Ok(())
     }
 
     pub async fn accept_request(&self, thread_id: &str, request: Request) -> AgentResult<()> {
-        let inviter = self.connections.get(thread_id)?;
+        let inviter = self.connections.get(thread_id).await?;
 
         let inviter = match inviter.state() {
             ThinState::Inviter(State::Initial) => Connection::try_from(inviter)
@@ -88,79 +121,84 @@ impl ServiceConnections {
         let inviter = inviter
             .handle_request(
                 &self.profile.inject_wallet(),
-                request,
+                request.clone(),
                 self.service_endpoint.clone(),
                 vec![],
                 &HttpClient,
             )
             .await?;
 
-        self.connections.insert(thread_id, inviter.into())?;
+        self.store(thread_id, inviter.into(), Some(request.into())).await?;
 
         Ok(())
     }
 
     pub async fn send_response(&self, thread_id: &str) -> AgentResult<()> {
-        let inviter: Connection<_, _> = self.connections.get(thread_id)?.try_into()?;
+        let inviter: Connection<_, _> = self.connections.get(thread_id).await?.try_into()?;
         let inviter = inviter
             .send_response(&self.profile.inject_wallet(), &HttpClient)
             .await?;
 
-        self.connections.insert(thread_id, inviter.into())?;
+        self.store(thread_id, inviter.into(), None).await?;
 
         Ok(())
     }
 
     pub async fn accept_response(&self, thread_id: &str, response: Response) -> AgentResult<()> {
-        let invitee: Connection<_, _> = self.connections.get(thread_id)?.try_into()?;
+        let invitee: Connection<_, _> = self.connections.get(thread_id).await?.try_into()?;
         let invitee = invitee
-            .handle_response(&self.profile.inject_wallet(), response, &HttpClient)
+            .handle_response(&self.profile.inject_wallet(), response.clone(), &HttpClient)
             .await?;
 
-        self.connections.insert(thread_id, invitee.into())?;
+        self.store(thread_id, invitee.into(), Some(response.into())).await?;
 
         Ok(())
     }
 
     pub async fn send_ack(&self, thread_id: &str) -> AgentResult<()> {
-        let invitee: Connection<_, _> = self.connections.get(thread_id)?.try_into()?;
+        let invitee: Connection<_, _> = self.connections.get(thread_id).await?.try_into()?;
         let invitee = invitee.send_ack(&self.profile.inject_wallet(), &HttpClient).await?;
 
-        self.connections.insert(thread_id, invitee.into())?;
+        self.store(thread_id, invitee.into(), None).await?;
 
         Ok(())
     }
 
     pub async fn process_ack(&self, thread_id: &str, ack: Ack) -> AgentResult<()> {
-        let inviter: Connection<_, _> = self.connections.get(thread_id)?.try_into()?;
-        let inviter = inviter.acknowledge_connection(&ack.into())?;
+        let inviter: Connection<_, _> = self.connections.get(thread_id).await?.try_into()?;
+        let ack = AriesMessage::from(ack);
+        let inviter = inviter.acknowledge_connection(&ack)?;
 
-        self.connections.insert(thread_id, inviter.into())?;
+        self.store(thread_id, inviter.into(), Some(ack)).await?;
 
         Ok(())
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<ThinState> {
-        Ok(self.connections.get(thread_id)?.state())
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<ThinState> {
+        Ok(self.connections.get(thread_id).await?.state())
+    }
+
+    pub(in crate::services) async fn get_by_id(&self, thread_id: &str) -> AgentResult<GenericConnection> {
+        self.connections.get(thread_id).await
+    }
+
+    pub async fn find_by_state(&self, state: ThinState) -> AgentResult<Vec<String>> {
+        self.connections.query(&RecordTags::default().with_state(&state)).await
     }
 
-    pub(in crate::services) fn get_by_id(&self, thread_id: &str) -> AgentResult<GenericConnection> {
-        self.connections.get(thread_id)
+    pub async fn get_by_their_vk(&self, their_vk: &str) -> AgentResult<Vec<String>> {
+        self.connections
+            .query(&RecordTags::default().with_their_vk(Some(their_vk)))
+            .await
     }
 
-    pub fn get_by_their_vk(&self, their_vk: &str) -> AgentResult<Vec<String>> {
-        let their_vk = their_vk.to_string();
-        let f = |(id, m): (&String, &Mutex<GenericConnection>)| -> Option<String> {
-            let connection = m.lock().unwrap();
-            match connection.remote_vk() {
-                Ok(remote_vk) if remote_vk == their_vk => Some(id.to_string()),
-                _ => None,
-            }
-        };
-        self.connections.find_by(f)
+    pub async fn get_by_our_vk(&self, our_vk: &str) -> AgentResult<Vec<String>> {
+        self.connections
+            .query(&RecordTags::default().with_our_vk(Some(our_vk)))
+            .await
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.connections.contains_key(thread_id)
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.connections.contains_key(thread_id).await
     }
 }
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
 use crate::error::*;
+use crate::events::EventBus;
 use crate::http_client::HttpClient;
 use crate::services::connection::ServiceConnections;
-use crate::storage::object_cache::ObjectCache;
-use crate::storage::Storage;
+use crate::storage::wallet_storage::WalletStorage;
+use crate::storage::{RecordTags, Storage, WalletRecord};
 use aries_vcx::core::profile::profile::Profile;
 use aries_vcx::handlers::issuance::holder::Holder;
 use aries_vcx::messages::msg_fields::protocols::cred_issuance::issue_credential::IssueCredential;
@@ -13,8 +14,9 @@ use aries_vcx::messages::msg_fields::protocols::cred_issuance::propose_credentia
 use aries_vcx::messages::AriesMessage;
 use aries_vcx::protocols::issuance::holder::state_machine::HolderState;
 use aries_vcx::protocols::SendClosure;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct HolderWrapper {
     holder: Holder,
     connection_id: String,
@@ -29,28 +31,51 @@ impl HolderWrapper {
     }
 }
 
+impl WalletRecord for HolderWrapper {
+    fn tags(&self) -> RecordTags {
+        RecordTags::default()
+            .with_state(&self.holder.get_state())
+            .with_thread_id(self.holder.get_thread_id().ok().as_deref())
+            .with_connection_id(Some(&self.connection_id))
+    }
+}
+
 pub struct ServiceCredentialsHolder {
     profile: Arc<dyn Profile>,
-    creds_holder: ObjectCache<HolderWrapper>,
+    creds_holder: WalletStorage<HolderWrapper>,
     service_connections: Arc<ServiceConnections>,
+    events: Arc<EventBus>,
 }
 
 impl ServiceCredentialsHolder {
-    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>) -> Self {
+    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>, events: Arc<EventBus>) -> Self {
         Self {
+            creds_holder: WalletStorage::new(profile.inject_wallet(), "agent-creds-holder"),
             profile,
             service_connections,
-            creds_holder: ObjectCache::new("creds-holder"),
+            events,
         }
     }
 
-    fn get_holder(&self, thread_id: &str) -> AgentResult<Holder> {
-        let HolderWrapper { holder, .. } = self.creds_holder.get(thread_id)?;
+    async fn store(&self, holder: Holder, connection_id: &str, message: Option<AriesMessage>) -> AgentResult<String> {
+        let thread_id = holder.get_thread_id()?;
+        let old_state = self.get_state(&thread_id).await.ok();
+        let new_state = holder.get_state();
+        self.creds_holder
+            .insert(&thread_id, HolderWrapper::new(holder, connection_id))
+            .await?;
+        self.events
+            .state_changed(&thread_id, connection_id, old_state, new_state, message);
+        Ok(thread_id)
+    }
+
+    async fn get_holder(&self, thread_id: &str) -> AgentResult<Holder> {
+        let HolderWrapper { holder, .. } = self.creds_holder.get(thread_id).await?;
         Ok(holder)
     }
 
-    pub fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
-        let HolderWrapper { connection_id, .. } = self.creds_holder.get(thread_id)?;
+    pub async fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
+        let HolderWrapper { connection_id, .. } = self.creds_holder.get(thread_id).await?;
         Ok(connection_id)
     }
 
@@ -59,7 +84,7 @@ impl ServiceCredentialsHolder {
         connection_id: &str,
         proposal_data: ProposeCredential,
     ) -> AgentResult<String> {
-        let connection = self.service_connections.get_by_id(connection_id)?;
+        let connection = self.service_connections.get_by_id(connection_id).await?;
         let wallet = self.profile.inject_wallet();
 
         let send_closure: SendClosure = Box::new(|msg: AriesMessage| {
@@ -69,15 +94,13 @@ impl ServiceCredentialsHolder {
         let mut holder = Holder::create("")?;
         holder.send_proposal(proposal_data, send_closure).await?;
 
-        self.creds_holder
-            .insert(&holder.get_thread_id()?, HolderWrapper::new(holder, connection_id))
+        self.store(holder, connection_id, None).await
     }
 
-    pub fn create_from_offer(&self, connection_id: &str, offer: OfferCredential) -> AgentResult<String> {
-        self.service_connections.get_by_id(connection_id)?;
-        let holder = Holder::create_from_offer("", offer)?;
-        self.creds_holder
-            .insert(&holder.get_thread_id()?, HolderWrapper::new(holder, connection_id))
+    pub async fn create_from_offer(&self, connection_id: &str, offer: OfferCredential) -> AgentResult<String> {
+        self.service_connections.get_by_id(connection_id).await?;
+        let holder = Holder::create_from_offer("", offer.clone())?;
+        self.store(holder, connection_id, Some(offer.into())).await
     }
 
     pub async fn send_credential_request(
@@ -86,12 +109,12 @@ impl ServiceCredentialsHolder {
         connection_id: Option<&str>,
     ) -> AgentResult<String> {
         let (mut holder, connection_id) = match (thread_id, connection_id) {
-            (Some(id), Some(connection_id)) => (self.get_holder(id)?, connection_id.to_string()),
-            (Some(id), None) => (self.get_holder(id)?, self.get_connection_id(id)?),
+            (Some(id), Some(connection_id)) => (self.get_holder(id).await?, connection_id.to_string()),
+            (Some(id), None) => (self.get_holder(id).await?, self.get_connection_id(id).await?),
             (None, Some(connection_id)) => (Holder::create("")?, connection_id.to_string()),
             (None, None) => return Err(AgentError::from_kind(AgentErrorKind::InvalidArguments)),
         };
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
         let wallet = self.profile.inject_wallet();
         let pw_did = connection.pairwise_info().pw_did.to_string();
 
@@ -100,14 +123,13 @@ impl ServiceCredentialsHolder {
         });
 
         holder.send_request(&self.profile, pw_did, send_closure).await?;
-        self.creds_holder
-            .insert(&holder.get_thread_id()?, HolderWrapper::new(holder, &connection_id))
+        self.store(holder, &connection_id, None).await
     }
 
     pub async fn process_credential(&self, thread_id: &str, credential: IssueCredential) -> AgentResult<String> {
-        let mut holder = self.get_holder(thread_id)?;
-        let connection_id = self.get_connection_id(thread_id)?;
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        let mut holder = self.get_holder(thread_id).await?;
+        let connection_id = self.get_connection_id(thread_id).await?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
         let wallet = self.profile.inject_wallet();
 
         let send_closure: SendClosure = Box::new(|msg: AriesMessage| {
@@ -115,38 +137,53 @@ impl ServiceCredentialsHolder {
         });
 
         holder
-            .process_credential(&self.profile, credential, send_closure)
+            .process_credential(&self.profile, credential.clone(), send_closure)
             .await?;
-        self.creds_holder
-            .insert(&holder.get_thread_id()?, HolderWrapper::new(holder, &connection_id))
+        self.store(holder, &connection_id, Some(credential.into())).await
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<HolderState> {
-        Ok(self.get_holder(thread_id)?.get_state())
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<HolderState> {
+        Ok(self.get_holder(thread_id).await?.get_state())
     }
 
     pub async fn is_revokable(&self, thread_id: &str) -> AgentResult<bool> {
-        self.get_holder(thread_id)?
+        self.get_holder(thread_id)
+            .await?
             .is_revokable(&self.profile)
             .await
             .map_err(|err| err.into())
     }
 
     pub async fn get_rev_reg_id(&self, thread_id: &str) -> AgentResult<String> {
-        self.get_holder(thread_id)?.get_rev_reg_id().map_err(|err| err.into())
+        self.get_holder(thread_id)
+            .await?
+            .get_rev_reg_id()
+            .map_err(|err| err.into())
     }
 
     pub async fn get_tails_hash(&self, thread_id: &str) -> AgentResult<String> {
-        self.get_holder(thread_id)?.get_tails_hash().map_err(|err| err.into())
+        self.get_holder(thread_id)
+            .await?
+            .get_tails_hash()
+            .map_err(|err| err.into())
     }
 
     pub async fn get_tails_location(&self, thread_id: &str) -> AgentResult<String> {
-        self.get_holder(thread_id)?
+        self.get_holder(thread_id)
+            .await?
             .get_tails_location()
             .map_err(|err| err.into())
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.creds_holder.contains_key(thread_id)
+    /// The thread ids of the holders in the state, over the connection if given.
+    pub async fn find_by_state(&self, state: HolderState, connection_id: Option<&str>) -> AgentResult<Vec<String>> {
+        let tags = RecordTags::default()
+            .with_state(&state)
+            .with_connection_id(connection_id);
+        self.creds_holder.query(&tags).await
+    }
+
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.creds_holder.contains_key(thread_id).await
     }
 }
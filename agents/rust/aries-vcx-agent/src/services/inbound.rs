@@ -0,0 +1,523 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::error::*;
+use crate::services::connection::ServiceConnections;
+use crate::services::holder::ServiceCredentialsHolder;
+use crate::services::issuer::ServiceCredentialsIssuer;
+use crate::services::prover::ServiceProver;
+use crate::services::verifier::ServiceVerifier;
+use aries_vcx::core::profile::profile::Profile;
+use aries_vcx::messages::decorators::thread::Thread;
+use aries_vcx::messages::msg_fields::protocols::connection::Connection;
+use aries_vcx::messages::msg_fields::protocols::cred_issuance::CredentialIssuance;
+use aries_vcx::messages::msg_fields::protocols::notification::Notification;
+use aries_vcx::messages::msg_fields::protocols::present_proof::PresentProof;
+use aries_vcx::messages::AriesMessage;
+use aries_vcx::protocols::connection::{State, ThinState};
+use aries_vcx::utils::encryption_envelope::EncryptionEnvelope;
+
+/// How many unhandled messages are kept until taken, the oldest are dropped beyond it.
+pub const MAX_UNHANDLED_MESSAGES: usize = 1000;
+
+/// What became of an inbound message.
+#[derive(Clone, Debug)]
+pub enum InboundEvent {
+    /// The message was handed to the protocol handler of its thread.
+    Handled { connection_id: String, thread_id: String },
+    /// No protocol handler takes the message, it is left to the agent's user.
+    Unhandled(UnhandledMessage),
+}
+
+#[derive(Clone, Debug)]
+pub struct UnhandledMessage {
+    pub connection_id: String,
+    pub message: AriesMessage,
+}
+
+/// The thread ids a message refers to, its own thread first.
+struct MessageThread {
+    thid: String,
+    pthid: Option<String>,
+}
+
+impl MessageThread {
+    /// Messages starting a thread carry no `~thread` decorator, their id is the thread id.
+    fn of(message: &AriesMessage) -> AgentResult<Self> {
+        let value = serde_json::to_value(message)?;
+        match value.get("~thread") {
+            Some(thread) => {
+                let Thread { thid, pthid, .. } = serde_json::from_value(thread.clone())?;
+                Ok(Self { thid, pthid })
+            }
+            None => {
+                let thid = value["@id"].as_str().unwrap_or_default().to_string();
+                Ok(Self { thid, pthid: None })
+            }
+        }
+    }
+
+    /// The first of the thread ids whose protocol record belongs to the connection.
+    async fn find<F, Fut>(&self, connection_id: &str, connection_of: F) -> Option<String>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = AgentResult<String>>,
+    {
+        for id in std::iter::once(&self.thid).chain(self.pthid.as_ref()) {
+            if matches!(connection_of(id.clone()).await, Ok(owner) if owner == connection_id) {
+                return Some(id.clone());
+            }
+        }
+        None
+    }
+}
+
+/// Takes packed messages as received on the agent's endpoint, unpacks them and feeds them to the
+/// protocol handlers of their threads.
+pub struct ServiceInbound {
+    profile: Arc<dyn Profile>,
+    service_connections: Arc<ServiceConnections>,
+    service_issuer: Arc<ServiceCredentialsIssuer>,
+    service_holder: Arc<ServiceCredentialsHolder>,
+    service_verifier: Arc<ServiceVerifier>,
+    service_prover: Arc<ServiceProver>,
+    unhandled: Mutex<VecDeque<UnhandledMessage>>,
+}
+
+impl ServiceInbound {
+    pub fn new(
+        profile: Arc<dyn Profile>,
+        service_connections: Arc<ServiceConnections>,
+        service_issuer: Arc<ServiceCredentialsIssuer>,
+        service_holder: Arc<ServiceCredentialsHolder>,
+        service_verifier: Arc<ServiceVerifier>,
+        service_prover: Arc<ServiceProver>,
+    ) -> Self {
+        Self {
+            profile,
+            service_connections,
+            service_issuer,
+            service_holder,
+            service_verifier,
+            service_prover,
+            unhandled: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Unpacks a message and hands it to the protocol handler of its thread. Messages no handler
+    /// takes are queued, see [`Self::take_unhandled`].
+    pub async fn receive_message(&self, payload: Vec<u8>) -> AgentResult<InboundEvent> {
+        let (connection_id, message) = self.unpack(payload).await?;
+        let event = self.dispatch(&connection_id, message).await?;
+
+        if let InboundEvent::Unhandled(unhandled) = &event {
+            info!(
+                "No handler for message {:?} received over connection {}",
+                unhandled.message, connection_id
+            );
+            push_unhandled(&mut self.lock_unhandled()?, unhandled.clone());
+        }
+        Ok(event)
+    }
+
+    /// Drains the messages received which no protocol handler took, oldest first. Only the last
+    /// [`MAX_UNHANDLED_MESSAGES`] are kept.
+    pub fn take_unhandled(&self) -> AgentResult<Vec<UnhandledMessage>> {
+        Ok(self.lock_unhandled()?.drain(..).collect())
+    }
+
+    fn lock_unhandled(&self) -> AgentResult<std::sync::MutexGuard<VecDeque<UnhandledMessage>>> {
+        self.unhandled.lock().map_err(|err| {
+            AgentError::from_msg(
+                AgentErrorKind::LockError,
+                &format!("Unable to lock unhandled messages: {:?}", err),
+            )
+        })
+    }
+
+    /// Finds the connection the message is addressed to by our verkey, and unpacks it checking it
+    /// was sent by the counterparty. The inviter only learns the invitee's verkey from its request.
+    async fn unpack(&self, payload: Vec<u8>) -> AgentResult<(String, AriesMessage)> {
+        let recipient_keys = EncryptionEnvelope::recipient_keys(&payload)?;
+        let mut connection_id = None;
+        for key in &recipient_keys {
+            connection_id = match self.service_connections.get_by_our_vk(key).await {
+                Ok(ids) => ids.into_iter().next(),
+                Err(_) => None,
+            };
+            if connection_id.is_some() {
+                break;
+            }
+        }
+        let connection_id = connection_id.ok_or_else(|| {
+            AgentError::from_msg(
+                AgentErrorKind::NotFound,
+                &format!("No connection found for recipient keys {:?}", recipient_keys),
+            )
+        })?;
+
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
+        let wallet = self.profile.inject_wallet();
+        let message = match connection.remote_vk() {
+            Ok(remote_vk) => EncryptionEnvelope::auth_unpack(&wallet, payload, &remote_vk).await?,
+            Err(_) => EncryptionEnvelope::anon_unpack(&wallet, payload).await?.0,
+        };
+
+        Ok((connection_id, message))
+    }
+
+    async fn dispatch(&self, connection_id: &str, message: AriesMessage) -> AgentResult<InboundEvent> {
+        let thread = MessageThread::of(&message)?;
+        let handled = |thread_id: String| InboundEvent::Handled {
+            connection_id: connection_id.to_string(),
+            thread_id,
+        };
+        let unhandled = |message: AriesMessage| {
+            InboundEvent::Unhandled(UnhandledMessage {
+                connection_id: connection_id.to_string(),
+                message,
+            })
+        };
+
+        let (issuer, holder) = (&self.service_issuer, &self.service_holder);
+        let (verifier, prover) = (&self.service_verifier, &self.service_prover);
+        let issuer_thread = || thread.find(connection_id, |id| async move { issuer.get_connection_id(&id).await });
+        let holder_thread = || thread.find(connection_id, |id| async move { holder.get_connection_id(&id).await });
+        let verifier_thread = || thread.find(connection_id, |id| async move { verifier.get_connection_id(&id).await });
+        let prover_thread = || thread.find(connection_id, |id| async move { prover.get_connection_id(&id).await });
+
+        let event = match message {
+            AriesMessage::Connection(Connection::Request(request)) => {
+                self.service_connections.accept_request(connection_id, request).await?;
+                handled(connection_id.to_string())
+            }
+            AriesMessage::Connection(Connection::Response(response)) => {
+                self.service_connections
+                    .accept_response(connection_id, response)
+                    .await?;
+                handled(connection_id.to_string())
+            }
+            AriesMessage::Notification(Notification::Ack(ack))
+                if matches!(
+                    self.service_connections.get_state(connection_id).await?,
+                    ThinState::Inviter(State::Responded)
+                ) =>
+            {
+                self.service_connections.process_ack(connection_id, ack).await?;
+                handled(connection_id.to_string())
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::ProposeCredential(proposal)) => {
+                handled(self.service_issuer.accept_proposal(connection_id, &proposal).await?)
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::OfferCredential(offer)) => {
+                handled(self.service_holder.create_from_offer(connection_id, offer).await?)
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::RequestCredential(request)) => {
+                match issuer_thread().await {
+                    Some(thread_id) => {
+                        self.service_issuer
+                            .process_credential_request(&thread_id, request)
+                            .await?;
+                        handled(thread_id)
+                    }
+                    None => unhandled(request.into()),
+                }
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::IssueCredential(credential)) => {
+                match holder_thread().await {
+                    Some(thread_id) => {
+                        self.service_holder.process_credential(&thread_id, credential).await?;
+                        handled(thread_id)
+                    }
+                    None => unhandled(credential.into()),
+                }
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::Ack(ack)) => match issuer_thread().await {
+                Some(thread_id) => {
+                    self.service_issuer.process_credential_ack(&thread_id, ack).await?;
+                    handled(thread_id)
+                }
+                None => unhandled(ack.into()),
+            },
+            AriesMessage::PresentProof(PresentProof::RequestPresentation(request)) => {
+                handled(self.service_prover.create_from_request(connection_id, request).await?)
+            }
+            AriesMessage::PresentProof(PresentProof::Presentation(presentation)) => match verifier_thread().await {
+                Some(thread_id) => {
+                    self.service_verifier
+                        .verify_presentation(&thread_id, presentation)
+                        .await?;
+                    handled(thread_id)
+                }
+                None => unhandled(presentation.into()),
+            },
+            AriesMessage::PresentProof(PresentProof::Ack(ack)) => match prover_thread().await {
+                Some(thread_id) => {
+                    self.service_prover.process_presentation_ack(&thread_id, ack).await?;
+                    handled(thread_id)
+                }
+                None => unhandled(ack.into()),
+            },
+            message => unhandled(message),
+        };
+
+        Ok(event)
+    }
+}
+
+fn push_unhandled(unhandled: &mut VecDeque<UnhandledMessage>, message: UnhandledMessage) {
+    if unhandled.len() >= MAX_UNHANDLED_MESSAGES {
+        if let Some(dropped) = unhandled.pop_front() {
+            warn!(
+                "Unhandled messages exceed {}, dropping message {:?} received over connection {}",
+                MAX_UNHANDLED_MESSAGES, dropped.message, dropped.connection_id
+            );
+        }
+    }
+    unhandled.push_back(message);
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::collections::HashMap;
+
+    use aries_vcx::messages::msg_fields::protocols::cred_issuance::propose_credential::{
+        ProposeCredential, ProposeCredentialContent, ProposeCredentialDecorators,
+    };
+    use aries_vcx::messages::msg_fields::protocols::cred_issuance::request_credential::{
+        RequestCredential, RequestCredentialContent, RequestCredentialDecorators,
+    };
+    use aries_vcx::messages::msg_fields::protocols::cred_issuance::CredentialPreview;
+    use aries_vcx::messages::msg_fields::protocols::trust_ping::ping::{Ping, PingContent, PingDecorators};
+    use aries_vcx::protocols::issuance::issuer::state_machine::IssuerState;
+    use aries_vcx_core::anoncreds::base_anoncreds::BaseAnonCreds;
+    use aries_vcx_core::ledger::base_ledger::{
+        AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite,
+    };
+    use aries_vcx_core::wallet::base_wallet::BaseWallet;
+    use aries_vcx_core::wallet::in_memory_wallet::InMemoryWallet;
+
+    use super::*;
+    use crate::events::EventBus;
+
+    /// Profile of a wallet alone, the messages received in these tests needing no ledger nor anoncreds.
+    #[derive(Debug)]
+    struct WalletProfile(Arc<dyn BaseWallet>);
+
+    impl Profile for WalletProfile {
+        fn inject_indy_ledger_read(self: Arc<Self>) -> Arc<dyn IndyLedgerRead> {
+            unimplemented!("no ledger")
+        }
+
+        fn inject_indy_ledger_write(self: Arc<Self>) -> Arc<dyn IndyLedgerWrite> {
+            unimplemented!("no ledger")
+        }
+
+        fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
+            unimplemented!("no anoncreds")
+        }
+
+        fn inject_anoncreds_ledger_read(self: Arc<Self>) -> Arc<dyn AnoncredsLedgerRead> {
+            unimplemented!("no ledger")
+        }
+
+        fn inject_anoncreds_ledger_write(self: Arc<Self>) -> Arc<dyn AnoncredsLedgerWrite> {
+            unimplemented!("no ledger")
+        }
+
+        fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
+            Arc::clone(&self.0)
+        }
+    }
+
+    fn _service_inbound() -> ServiceInbound {
+        let profile: Arc<dyn Profile> = Arc::new(WalletProfile(Arc::new(InMemoryWallet::new())));
+        let events = Arc::new(EventBus::new());
+        let endpoint = "http://127.0.0.1:8080/".parse().unwrap();
+        let connections = Arc::new(ServiceConnections::new(profile.clone(), endpoint, events.clone()));
+        ServiceInbound::new(
+            profile.clone(),
+            connections.clone(),
+            Arc::new(ServiceCredentialsIssuer::new(
+                profile.clone(),
+                connections.clone(),
+                events.clone(),
+            )),
+            Arc::new(ServiceCredentialsHolder::new(
+                profile.clone(),
+                connections.clone(),
+                events.clone(),
+            )),
+            Arc::new(ServiceVerifier::new(
+                profile.clone(),
+                connections.clone(),
+                events.clone(),
+            )),
+            Arc::new(ServiceProver::new(profile, connections, events)),
+        )
+    }
+
+    fn _ping(id: &str) -> AriesMessage {
+        Ping::with_decorators(id.to_string(), PingContent::default(), PingDecorators::default()).into()
+    }
+
+    fn _proposal() -> AriesMessage {
+        let content = ProposeCredentialContent::new(
+            CredentialPreview::new(vec![]),
+            "schema_id".to_string(),
+            "cred_def_id".to_string(),
+        );
+        ProposeCredential::with_decorators(
+            "proposal_id".to_string(),
+            content,
+            ProposeCredentialDecorators::default(),
+        )
+        .into()
+    }
+
+    fn _request(thread: Thread) -> AriesMessage {
+        let mut decorators = RequestCredentialDecorators::default();
+        decorators.thread = Some(thread);
+        RequestCredential::with_decorators(
+            "request_id".to_string(),
+            RequestCredentialContent::new(vec![]),
+            decorators,
+        )
+        .into()
+    }
+
+    fn _unhandled(id: usize) -> UnhandledMessage {
+        UnhandledMessage {
+            connection_id: "connection_id".to_string(),
+            message: _ping(&id.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_push_unhandled_drops_oldest() {
+        let mut unhandled = VecDeque::new();
+        for id in 0..=MAX_UNHANDLED_MESSAGES {
+            push_unhandled(&mut unhandled, _unhandled(id));
+        }
+
+        assert_eq!(unhandled.len(), MAX_UNHANDLED_MESSAGES);
+        let ids = unhandled
+            .iter()
+            .map(|unhandled| serde_json::to_value(&unhandled.message).unwrap()["@id"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids.first(), Some(&json!("1")));
+        assert_eq!(ids.last(), Some(&json!(MAX_UNHANDLED_MESSAGES.to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_message_thread_found_by_thid_then_pthid() {
+        let owners = HashMap::from([
+            ("thread_a".to_string(), "connection_a".to_string()),
+            ("thread_b".to_string(), "connection_b".to_string()),
+        ]);
+        let owner_of = |id: String| {
+            let owner = owners.get(&id).cloned();
+            async move { owner.ok_or_else(|| AgentError::from_kind(AgentErrorKind::NotFound)) }
+        };
+
+        let mut thread = Thread::new("thread_a".to_string());
+        thread.pthid = Some("thread_b".to_string());
+        let thread = MessageThread::of(&_request(thread)).unwrap();
+        assert_eq!(
+            thread.find("connection_a", owner_of).await,
+            Some("thread_a".to_string())
+        );
+        assert_eq!(
+            thread.find("connection_b", owner_of).await,
+            Some("thread_b".to_string())
+        );
+        assert_eq!(thread.find("connection_c", owner_of).await, None);
+
+        let thread = MessageThread::of(&_ping("thread_a")).unwrap();
+        assert_eq!(
+            thread.find("connection_a", owner_of).await,
+            Some("thread_a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_to_thread_of_connection() {
+        let inbound = _service_inbound();
+        let InboundEvent::Handled { thread_id, .. } = inbound.dispatch("connection_b", _proposal()).await.unwrap()
+        else {
+            panic!("proposal was not handled");
+        };
+
+        let event = inbound
+            .dispatch("connection_b", _request(Thread::new(thread_id.clone())))
+            .await
+            .unwrap();
+        assert!(matches!(event, InboundEvent::Handled { thread_id: handled, .. } if handled == thread_id));
+
+        let mut thread = Thread::new("unknown_thread".to_string());
+        thread.pthid = Some(thread_id.clone());
+        let event = inbound.dispatch("connection_b", _request(thread)).await.unwrap();
+        assert!(matches!(event, InboundEvent::Handled { thread_id: handled, .. } if handled == thread_id));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_leaves_thread_of_other_connection_unhandled() {
+        let inbound = _service_inbound();
+        let InboundEvent::Handled { thread_id, .. } = inbound.dispatch("connection_b", _proposal()).await.unwrap()
+        else {
+            panic!("proposal was not handled");
+        };
+
+        let event = inbound
+            .dispatch("connection_a", _request(Thread::new(thread_id.clone())))
+            .await
+            .unwrap();
+        assert!(matches!(event, InboundEvent::Unhandled(unhandled) if unhandled.connection_id == "connection_a"));
+        assert!(matches!(
+            inbound.service_issuer.get_state(&thread_id).await.unwrap(),
+            IssuerState::ProposalReceived
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unpack_checks_recipient_and_sender() {
+        let inbound = _service_inbound();
+        let invitation = inbound.service_connections.create_invitation(None).await.unwrap();
+        let connection_id = inbound
+            .service_connections
+            .receive_invitation(invitation)
+            .await
+            .unwrap();
+        let connection = inbound.service_connections.get_by_id(&connection_id).await.unwrap();
+        let our_vk = connection.pairwise_info().pw_vk.clone();
+        let their_vk = connection.remote_vk().unwrap();
+
+        let wallet = inbound.profile.inject_wallet();
+        let (_, other_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let message = serde_json::to_vec(&_ping("ping_id")).unwrap();
+        let pack = |sender_vk: String, recipient_vk: String| {
+            let (wallet, message) = (wallet.clone(), message.clone());
+            async move {
+                wallet
+                    .pack_message(Some(&sender_vk), &json!([recipient_vk]).to_string(), &message)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let (unpacked_id, unpacked) = inbound.unpack(pack(their_vk, our_vk.clone()).await).await.unwrap();
+        assert_eq!(unpacked_id, connection_id);
+        assert_eq!(unpacked, _ping("ping_id"));
+
+        let err = inbound
+            .unpack(pack(our_vk.clone(), other_vk.clone()).await)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, AgentErrorKind::NotFound);
+
+        let err = inbound.unpack(pack(other_vk, our_vk).await).await.unwrap_err();
+        assert_ne!(err.kind, AgentErrorKind::NotFound);
+    }
+}
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
 use crate::error::*;
+use crate::events::EventBus;
 use crate::http_client::HttpClient;
 use crate::services::connection::ServiceConnections;
-use crate::storage::object_cache::ObjectCache;
-use crate::storage::Storage;
+use crate::storage::wallet_storage::WalletStorage;
+use crate::storage::{RecordTags, Storage, WalletRecord};
 use aries_vcx::core::profile::profile::Profile;
 use aries_vcx::handlers::issuance::issuer::Issuer;
 use aries_vcx::handlers::util::OfferInfo;
@@ -14,8 +15,9 @@ use aries_vcx::messages::msg_fields::protocols::cred_issuance::request_credentia
 use aries_vcx::messages::AriesMessage;
 use aries_vcx::protocols::issuance::issuer::state_machine::IssuerState;
 use aries_vcx::protocols::SendClosure;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct IssuerWrapper {
     issuer: Issuer,
     connection_id: String,
@@ -30,35 +32,57 @@ impl IssuerWrapper {
     }
 }
 
+impl WalletRecord for IssuerWrapper {
+    fn tags(&self) -> RecordTags {
+        RecordTags::default()
+            .with_state(&self.issuer.get_state())
+            .with_thread_id(self.issuer.get_thread_id().ok().as_deref())
+            .with_connection_id(Some(&self.connection_id))
+    }
+}
+
 pub struct ServiceCredentialsIssuer {
     profile: Arc<dyn Profile>,
-    creds_issuer: ObjectCache<IssuerWrapper>,
+    creds_issuer: WalletStorage<IssuerWrapper>,
     service_connections: Arc<ServiceConnections>,
+    events: Arc<EventBus>,
 }
 
 impl ServiceCredentialsIssuer {
-    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>) -> Self {
+    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>, events: Arc<EventBus>) -> Self {
         Self {
+            creds_issuer: WalletStorage::new(profile.inject_wallet(), "agent-creds-issuer"),
             profile,
             service_connections,
-            creds_issuer: ObjectCache::new("creds-issuer"),
+            events,
         }
     }
 
-    fn get_issuer(&self, thread_id: &str) -> AgentResult<Issuer> {
-        let IssuerWrapper { issuer, .. } = self.creds_issuer.get(thread_id)?;
+    async fn store(&self, issuer: Issuer, connection_id: &str, message: Option<AriesMessage>) -> AgentResult<String> {
+        let thread_id = issuer.get_thread_id()?;
+        let old_state = self.get_state(&thread_id).await.ok();
+        let new_state = issuer.get_state();
+        self.creds_issuer
+            .insert(&thread_id, IssuerWrapper::new(issuer, connection_id))
+            .await?;
+        self.events
+            .state_changed(&thread_id, connection_id, old_state, new_state, message);
+        Ok(thread_id)
+    }
+
+    async fn get_issuer(&self, thread_id: &str) -> AgentResult<Issuer> {
+        let IssuerWrapper { issuer, .. } = self.creds_issuer.get(thread_id).await?;
         Ok(issuer)
     }
 
-    pub fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
-        let IssuerWrapper { connection_id, .. } = self.creds_issuer.get(thread_id)?;
+    pub async fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
+        let IssuerWrapper { connection_id, .. } = self.creds_issuer.get(thread_id).await?;
         Ok(connection_id)
     }
 
     pub async fn accept_proposal(&self, connection_id: &str, proposal: &ProposeCredential) -> AgentResult<String> {
         let issuer = Issuer::create_from_proposal("", proposal)?;
-        self.creds_issuer
-            .insert(&issuer.get_thread_id()?, IssuerWrapper::new(issuer, connection_id))
+        self.store(issuer, connection_id, Some(proposal.clone().into())).await
     }
 
     pub async fn send_credential_offer(
@@ -68,12 +92,12 @@ impl ServiceCredentialsIssuer {
         offer_info: OfferInfo,
     ) -> AgentResult<String> {
         let (mut issuer, connection_id) = match (thread_id, connection_id) {
-            (Some(id), Some(connection_id)) => (self.get_issuer(id)?, connection_id.to_string()),
-            (Some(id), None) => (self.get_issuer(id)?, self.get_connection_id(id)?),
+            (Some(id), Some(connection_id)) => (self.get_issuer(id).await?, connection_id.to_string()),
+            (Some(id), None) => (self.get_issuer(id).await?, self.get_connection_id(id).await?),
             (None, Some(connection_id)) => (Issuer::create("")?, connection_id.to_string()),
             (None, None) => return Err(AgentError::from_kind(AgentErrorKind::InvalidArguments)),
         };
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
         issuer
             .build_credential_offer_msg(&self.profile, offer_info, None)
             .await?;
@@ -85,29 +109,26 @@ impl ServiceCredentialsIssuer {
         });
 
         issuer.send_credential_offer(send_closure).await?;
-        self.creds_issuer
-            .insert(&issuer.get_thread_id()?, IssuerWrapper::new(issuer, &connection_id))
+        self.store(issuer, &connection_id, None).await
     }
 
-    pub fn process_credential_request(&self, thread_id: &str, request: RequestCredential) -> AgentResult<()> {
+    pub async fn process_credential_request(&self, thread_id: &str, request: RequestCredential) -> AgentResult<()> {
         let IssuerWrapper {
             mut issuer,
             connection_id,
-        } = self.creds_issuer.get(thread_id)?;
-        issuer.process_credential_request(request)?;
-        self.creds_issuer
-            .insert(&issuer.get_thread_id()?, IssuerWrapper::new(issuer, &connection_id))?;
+        } = self.creds_issuer.get(thread_id).await?;
+        issuer.process_credential_request(request.clone())?;
+        self.store(issuer, &connection_id, Some(request.into())).await?;
         Ok(())
     }
 
-    pub fn process_credential_ack(&self, thread_id: &str, ack: AckCredential) -> AgentResult<()> {
+    pub async fn process_credential_ack(&self, thread_id: &str, ack: AckCredential) -> AgentResult<()> {
         let IssuerWrapper {
             mut issuer,
             connection_id,
-        } = self.creds_issuer.get(thread_id)?;
-        issuer.process_credential_ack(ack)?;
-        self.creds_issuer
-            .insert(&issuer.get_thread_id()?, IssuerWrapper::new(issuer, &connection_id))?;
+        } = self.creds_issuer.get(thread_id).await?;
+        issuer.process_credential_ack(ack.clone())?;
+        self.store(issuer, &connection_id, Some(ack.into())).await?;
         Ok(())
     }
 
@@ -115,8 +136,8 @@ impl ServiceCredentialsIssuer {
         let IssuerWrapper {
             mut issuer,
             connection_id,
-        } = self.creds_issuer.get(thread_id)?;
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        } = self.creds_issuer.get(thread_id).await?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
 
         let wallet = self.profile.inject_wallet();
 
@@ -125,31 +146,38 @@ impl ServiceCredentialsIssuer {
         });
 
         issuer.send_credential(&self.profile, send_closure).await?;
-        self.creds_issuer
-            .insert(&issuer.get_thread_id()?, IssuerWrapper::new(issuer, &connection_id))?;
+        self.store(issuer, &connection_id, None).await?;
         Ok(())
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<IssuerState> {
-        Ok(self.get_issuer(thread_id)?.get_state())
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<IssuerState> {
+        Ok(self.get_issuer(thread_id).await?.get_state())
     }
 
-    pub fn get_rev_reg_id(&self, thread_id: &str) -> AgentResult<String> {
-        let issuer = self.get_issuer(thread_id)?;
+    pub async fn get_rev_reg_id(&self, thread_id: &str) -> AgentResult<String> {
+        let issuer = self.get_issuer(thread_id).await?;
         issuer.get_rev_reg_id().map_err(|err| err.into())
     }
 
-    pub fn get_rev_id(&self, thread_id: &str) -> AgentResult<String> {
-        let issuer = self.get_issuer(thread_id)?;
+    pub async fn get_rev_id(&self, thread_id: &str) -> AgentResult<String> {
+        let issuer = self.get_issuer(thread_id).await?;
         issuer.get_rev_id().map_err(|err| err.into())
     }
 
-    pub fn get_proposal(&self, thread_id: &str) -> AgentResult<ProposeCredential> {
-        let issuer = self.get_issuer(thread_id)?;
+    pub async fn get_proposal(&self, thread_id: &str) -> AgentResult<ProposeCredential> {
+        let issuer = self.get_issuer(thread_id).await?;
         issuer.get_proposal().map_err(|err| err.into())
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.creds_issuer.contains_key(thread_id)
+    /// The thread ids of the issuers in the state, over the connection if given.
+    pub async fn find_by_state(&self, state: IssuerState, connection_id: Option<&str>) -> AgentResult<Vec<String>> {
+        let tags = RecordTags::default()
+            .with_state(&state)
+            .with_connection_id(connection_id);
+        self.creds_issuer.query(&tags).await
+    }
+
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.creds_issuer.contains_key(thread_id).await
     }
 }
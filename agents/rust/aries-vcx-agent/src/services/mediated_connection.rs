@@ -56,7 +56,8 @@ impl ServiceMediatedConnections {
             .ok_or_else(|| AgentError::from_kind(AgentErrorKind::InviteDetails))?
             .clone();
         self.mediated_connections
-            .insert(&connection.get_thread_id(), connection)?;
+            .insert(&connection.get_thread_id(), connection)
+            .await?;
         Ok(invite)
     }
 
@@ -67,57 +68,58 @@ impl ServiceMediatedConnections {
                 .await?;
         self.mediated_connections
             .insert(&connection.get_thread_id(), connection)
+            .await
     }
 
     pub async fn send_request(&self, thread_id: &str) -> AgentResult<()> {
-        let mut connection = self.mediated_connections.get(thread_id)?;
+        let mut connection = self.mediated_connections.get(thread_id).await?;
         connection.connect(&self.profile, &self.agency_client()?, None).await?;
         connection
             .find_message_and_update_state(&self.profile, &self.agency_client()?)
             .await?;
-        self.mediated_connections.insert(thread_id, connection)?;
+        self.mediated_connections.insert(thread_id, connection).await?;
         Ok(())
     }
 
     pub async fn accept_request(&self, thread_id: &str, request: Request) -> AgentResult<()> {
-        let mut connection = self.mediated_connections.get(thread_id)?;
+        let mut connection = self.mediated_connections.get(thread_id).await?;
         connection
             .process_request(&self.profile, &self.agency_client()?, request)
             .await?;
         connection.send_response(&self.profile).await?;
-        self.mediated_connections.insert(thread_id, connection)?;
+        self.mediated_connections.insert(thread_id, connection).await?;
         Ok(())
     }
 
     pub async fn send_ping(&self, thread_id: &str) -> AgentResult<()> {
-        let mut connection = self.mediated_connections.get(thread_id)?;
+        let mut connection = self.mediated_connections.get(thread_id).await?;
         connection.send_ping(&self.profile, None).await?;
-        self.mediated_connections.insert(thread_id, connection)?;
+        self.mediated_connections.insert(thread_id, connection).await?;
         Ok(())
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<ConnectionState> {
-        Ok(self.mediated_connections.get(thread_id)?.get_state())
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<ConnectionState> {
+        Ok(self.mediated_connections.get(thread_id).await?.get_state())
     }
 
     pub async fn update_state(&self, thread_id: &str) -> AgentResult<ConnectionState> {
-        let mut connection = self.mediated_connections.get(thread_id)?;
+        let mut connection = self.mediated_connections.get(thread_id).await?;
         connection
             .find_message_and_update_state(&self.profile, &self.agency_client()?)
             .await?;
-        self.mediated_connections.insert(thread_id, connection)?;
-        Ok(self.mediated_connections.get(thread_id)?.get_state())
+        self.mediated_connections.insert(thread_id, connection).await?;
+        Ok(self.mediated_connections.get(thread_id).await?.get_state())
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.mediated_connections.contains_key(thread_id)
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.mediated_connections.contains_key(thread_id).await
     }
 }
 
 macro_rules! get_messages (($msg_type:ty, $a2a_msg:ident, $var:ident, $name:ident) => (
     impl ServiceMediatedConnections {
         pub async fn $name(&self, thread_id: &str) -> AgentResult<Vec<$msg_type>> {
-            let connection = self.mediated_connections.get(thread_id)?;
+            let connection = self.mediated_connections.get(thread_id).await?;
             let agency_client = self.agency_client()?;
             let mut messages = Vec::<$msg_type>::new();
             for (uid, message) in connection.get_messages_noauth(&agency_client).await?.into_iter() {
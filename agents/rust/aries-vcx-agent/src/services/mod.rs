@@ -1,6 +1,7 @@
 pub(crate) mod connection;
 pub(crate) mod credential_definition;
 pub(crate) mod holder;
+pub(crate) mod inbound;
 pub(crate) mod issuer;
 pub(crate) mod mediated_connection;
 pub(crate) mod prover;
@@ -2,9 +2,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::error::*;
+use crate::events::EventBus;
 use crate::http_client::HttpClient;
-use crate::storage::object_cache::ObjectCache;
-use crate::storage::Storage;
+use crate::storage::wallet_storage::WalletStorage;
+use crate::storage::{RecordTags, Storage, WalletRecord};
 use aries_vcx::core::profile::profile::Profile;
 use aries_vcx::handlers::proof_presentation::prover::Prover;
 use aries_vcx::handlers::proof_presentation::types::SelectedCredentials;
@@ -14,11 +15,12 @@ use aries_vcx::messages::msg_fields::protocols::present_proof::request::RequestP
 use aries_vcx::messages::AriesMessage;
 use aries_vcx::protocols::proof_presentation::prover::state_machine::ProverState;
 use aries_vcx::protocols::SendClosure;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::connection::ServiceConnections;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct ProverWrapper {
     prover: Prover,
     connection_id: String,
@@ -33,28 +35,51 @@ impl ProverWrapper {
     }
 }
 
+impl WalletRecord for ProverWrapper {
+    fn tags(&self) -> RecordTags {
+        RecordTags::default()
+            .with_state(&self.prover.get_state())
+            .with_thread_id(self.prover.get_thread_id().ok().as_deref())
+            .with_connection_id(Some(&self.connection_id))
+    }
+}
+
 pub struct ServiceProver {
     profile: Arc<dyn Profile>,
-    provers: ObjectCache<ProverWrapper>,
+    provers: WalletStorage<ProverWrapper>,
     service_connections: Arc<ServiceConnections>,
+    events: Arc<EventBus>,
 }
 
 impl ServiceProver {
-    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>) -> Self {
+    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>, events: Arc<EventBus>) -> Self {
         Self {
+            provers: WalletStorage::new(profile.inject_wallet(), "agent-provers"),
             profile,
             service_connections,
-            provers: ObjectCache::new("provers"),
+            events,
         }
     }
 
-    pub fn get_prover(&self, thread_id: &str) -> AgentResult<Prover> {
-        let ProverWrapper { prover, .. } = self.provers.get(thread_id)?;
+    async fn store(&self, prover: Prover, connection_id: &str, message: Option<AriesMessage>) -> AgentResult<String> {
+        let thread_id = prover.get_thread_id()?;
+        let old_state = self.get_state(&thread_id).await.ok();
+        let new_state = prover.get_state();
+        self.provers
+            .insert(&thread_id, ProverWrapper::new(prover, connection_id))
+            .await?;
+        self.events
+            .state_changed(&thread_id, connection_id, old_state, new_state, message);
+        Ok(thread_id)
+    }
+
+    pub async fn get_prover(&self, thread_id: &str) -> AgentResult<Prover> {
+        let ProverWrapper { prover, .. } = self.provers.get(thread_id).await?;
         Ok(prover)
     }
 
-    pub fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
-        let ProverWrapper { connection_id, .. } = self.provers.get(thread_id)?;
+    pub async fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
+        let ProverWrapper { connection_id, .. } = self.provers.get(thread_id).await?;
         Ok(connection_id)
     }
 
@@ -77,11 +102,10 @@ impl ServiceProver {
         Ok(res_credentials)
     }
 
-    pub fn create_from_request(&self, connection_id: &str, request: RequestPresentation) -> AgentResult<String> {
-        self.service_connections.get_by_id(connection_id)?;
-        let prover = Prover::create_from_request("", request)?;
-        self.provers
-            .insert(&prover.get_thread_id()?, ProverWrapper::new(prover, connection_id))
+    pub async fn create_from_request(&self, connection_id: &str, request: RequestPresentation) -> AgentResult<String> {
+        self.service_connections.get_by_id(connection_id).await?;
+        let prover = Prover::create_from_request("", request.clone())?;
+        self.store(prover, connection_id, Some(request.into())).await
     }
 
     pub async fn send_proof_proposal(
@@ -89,7 +113,7 @@ impl ServiceProver {
         connection_id: &str,
         proposal: PresentationProposalData,
     ) -> AgentResult<String> {
-        let connection = self.service_connections.get_by_id(connection_id)?;
+        let connection = self.service_connections.get_by_id(connection_id).await?;
         let mut prover = Prover::create("")?;
 
         let wallet = self.profile.inject_wallet();
@@ -99,12 +123,11 @@ impl ServiceProver {
         });
 
         prover.send_proposal(proposal, send_closure).await?;
-        self.provers
-            .insert(&prover.get_thread_id()?, ProverWrapper::new(prover, connection_id))
+        self.store(prover, connection_id, None).await
     }
 
-    pub fn is_secondary_proof_requested(&self, thread_id: &str) -> AgentResult<bool> {
-        let prover = self.get_prover(thread_id)?;
+    pub async fn is_secondary_proof_requested(&self, thread_id: &str) -> AgentResult<bool> {
+        let prover = self.get_prover(thread_id).await?;
         let attach = prover.get_proof_request_attachment()?;
         let attach: Value = serde_json::from_str(&attach)?;
         Ok(!attach["non_revoked"].is_null())
@@ -114,8 +137,8 @@ impl ServiceProver {
         let ProverWrapper {
             mut prover,
             connection_id,
-        } = self.provers.get(thread_id)?;
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        } = self.provers.get(thread_id).await?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
         let credentials = self.get_credentials_for_presentation(&prover, tails_dir).await?;
         prover
             .generate_presentation(&self.profile, credentials, HashMap::new())
@@ -128,27 +151,33 @@ impl ServiceProver {
         });
 
         prover.send_presentation(send_closure).await?;
-        self.provers
-            .insert(&prover.get_thread_id()?, ProverWrapper::new(prover, &connection_id))?;
+        self.store(prover, &connection_id, None).await?;
         Ok(())
     }
 
-    pub fn process_presentation_ack(&self, thread_id: &str, ack: AckPresentation) -> AgentResult<String> {
+    pub async fn process_presentation_ack(&self, thread_id: &str, ack: AckPresentation) -> AgentResult<String> {
         let ProverWrapper {
             mut prover,
             connection_id,
-        } = self.provers.get(thread_id)?;
-        prover.process_presentation_ack(ack)?;
-        self.provers
-            .insert(&prover.get_thread_id()?, ProverWrapper::new(prover, &connection_id))
+        } = self.provers.get(thread_id).await?;
+        prover.process_presentation_ack(ack.clone())?;
+        self.store(prover, &connection_id, Some(ack.into())).await
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<ProverState> {
-        let ProverWrapper { prover, .. } = self.provers.get(thread_id)?;
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<ProverState> {
+        let ProverWrapper { prover, .. } = self.provers.get(thread_id).await?;
         Ok(prover.get_state())
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.provers.contains_key(thread_id)
+    /// The thread ids of the provers in the state, over the connection if given.
+    pub async fn find_by_state(&self, state: ProverState, connection_id: Option<&str>) -> AgentResult<Vec<String>> {
+        let tags = RecordTags::default()
+            .with_state(&state)
+            .with_connection_id(connection_id);
+        self.provers.query(&tags).await
+    }
+
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.provers.contains_key(thread_id).await
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use crate::error::*;
 use crate::storage::object_cache::ObjectCache;
@@ -23,13 +23,13 @@ impl ServiceSchemas {
 
     pub async fn create_schema(&self, name: &str, version: &str, attributes: &Vec<String>) -> AgentResult<String> {
         let schema = Schema::create(&self.profile, "", &self.issuer_did, name, version, attributes).await?;
-        self.schemas.insert(&schema.get_schema_id(), schema)
+        self.schemas.insert(&schema.get_schema_id(), schema).await
     }
 
     pub async fn publish_schema(&self, thread_id: &str) -> AgentResult<()> {
-        let schema = self.schemas.get(thread_id)?;
+        let schema = self.schemas.get(thread_id).await?;
         let schema = schema.publish(&self.profile, None).await?;
-        self.schemas.insert(thread_id, schema)?;
+        self.schemas.insert(thread_id, schema).await?;
         Ok(())
     }
 
@@ -38,21 +38,20 @@ impl ServiceSchemas {
         Ok(ledger.get_schema(thread_id, None).await?)
     }
 
-    pub fn find_by_name_and_version(&self, name: &str, version: &str) -> AgentResult<Vec<String>> {
+    pub async fn find_by_name_and_version(&self, name: &str, version: &str) -> AgentResult<Vec<String>> {
         let name = name.to_string();
         let version = version.to_string();
-        let f = |(id, m): (&String, &Mutex<Schema>)| -> Option<String> {
-            let schema = m.lock().unwrap();
+        let f = |(id, schema): (&String, &Schema)| -> Option<String> {
             if schema.name == name && schema.version == version {
                 Some(id.to_string())
             } else {
                 None
             }
         };
-        self.schemas.find_by(f)
+        self.schemas.find_by(f).await
     }
 
-    pub fn get_by_id(&self, thread_id: &str) -> AgentResult<Schema> {
-        self.schemas.get(thread_id)
+    pub async fn get_by_id(&self, thread_id: &str) -> AgentResult<Schema> {
+        self.schemas.get(thread_id).await
     }
 }
@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
 use crate::error::*;
+use crate::events::EventBus;
 use crate::http_client::HttpClient;
-use crate::storage::object_cache::ObjectCache;
-use crate::storage::Storage;
+use crate::storage::wallet_storage::WalletStorage;
+use crate::storage::{RecordTags, Storage, WalletRecord};
 use aries_vcx::common::proofs::proof_request::PresentationRequestData;
 use aries_vcx::core::profile::profile::Profile;
 use aries_vcx::handlers::proof_presentation::verifier::Verifier;
@@ -13,10 +14,11 @@ use aries_vcx::messages::AriesMessage;
 use aries_vcx::protocols::proof_presentation::verifier::state_machine::VerifierState;
 use aries_vcx::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
 use aries_vcx::protocols::SendClosure;
+use serde::{Deserialize, Serialize};
 
 use super::connection::ServiceConnections;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct VerifierWrapper {
     verifier: Verifier,
     connection_id: String,
@@ -31,28 +33,56 @@ impl VerifierWrapper {
     }
 }
 
+impl WalletRecord for VerifierWrapper {
+    fn tags(&self) -> RecordTags {
+        RecordTags::default()
+            .with_state(&self.verifier.get_state())
+            .with_thread_id(self.verifier.get_thread_id().ok().as_deref())
+            .with_connection_id(Some(&self.connection_id))
+    }
+}
+
 pub struct ServiceVerifier {
     profile: Arc<dyn Profile>,
-    verifiers: ObjectCache<VerifierWrapper>,
+    verifiers: WalletStorage<VerifierWrapper>,
     service_connections: Arc<ServiceConnections>,
+    events: Arc<EventBus>,
 }
 
 impl ServiceVerifier {
-    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>) -> Self {
+    pub fn new(profile: Arc<dyn Profile>, service_connections: Arc<ServiceConnections>, events: Arc<EventBus>) -> Self {
         Self {
+            verifiers: WalletStorage::new(profile.inject_wallet(), "agent-verifiers"),
             profile,
             service_connections,
-            verifiers: ObjectCache::new("verifiers"),
+            events,
         }
     }
 
+    async fn store(
+        &self,
+        verifier: Verifier,
+        connection_id: &str,
+        message: Option<AriesMessage>,
+    ) -> AgentResult<String> {
+        let thread_id = verifier.get_thread_id()?;
+        let old_state = self.get_state(&thread_id).await.ok();
+        let new_state = verifier.get_state();
+        self.verifiers
+            .insert(&thread_id, VerifierWrapper::new(verifier, connection_id))
+            .await?;
+        self.events
+            .state_changed(&thread_id, connection_id, old_state, new_state, message);
+        Ok(thread_id)
+    }
+
     pub async fn send_proof_request(
         &self,
         connection_id: &str,
         request: PresentationRequestData,
         proposal: Option<ProposePresentation>,
     ) -> AgentResult<String> {
-        let connection = self.service_connections.get_by_id(connection_id)?;
+        let connection = self.service_connections.get_by_id(connection_id).await?;
         let mut verifier = if let Some(proposal) = proposal {
             Verifier::create_from_proposal("", &proposal)?
         } else {
@@ -66,14 +96,16 @@ impl ServiceVerifier {
         });
 
         verifier.send_presentation_request(send_closure).await?;
-        self.verifiers.insert(
-            &verifier.get_thread_id()?,
-            VerifierWrapper::new(verifier, connection_id),
-        )
+        self.store(verifier, connection_id, None).await
+    }
+
+    pub async fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
+        let VerifierWrapper { connection_id, .. } = self.verifiers.get(thread_id).await?;
+        Ok(connection_id)
     }
 
-    pub fn get_presentation_status(&self, thread_id: &str) -> AgentResult<PresentationVerificationStatus> {
-        let VerifierWrapper { verifier, .. } = self.verifiers.get(thread_id)?;
+    pub async fn get_presentation_status(&self, thread_id: &str) -> AgentResult<PresentationVerificationStatus> {
+        let VerifierWrapper { verifier, .. } = self.verifiers.get(thread_id).await?;
         Ok(verifier.get_verification_status())
     }
 
@@ -81,8 +113,8 @@ impl ServiceVerifier {
         let VerifierWrapper {
             mut verifier,
             connection_id,
-        } = self.verifiers.get(thread_id)?;
-        let connection = self.service_connections.get_by_id(&connection_id)?;
+        } = self.verifiers.get(thread_id).await?;
+        let connection = self.service_connections.get_by_id(&connection_id).await?;
         let wallet = self.profile.inject_wallet();
 
         let send_closure: SendClosure = Box::new(|msg: AriesMessage| {
@@ -90,19 +122,26 @@ impl ServiceVerifier {
         });
 
         verifier
-            .verify_presentation(&self.profile, presentation, send_closure)
+            .verify_presentation(&self.profile, presentation.clone(), send_closure)
             .await?;
-        self.verifiers
-            .insert(thread_id, VerifierWrapper::new(verifier, &connection_id))?;
+        self.store(verifier, &connection_id, Some(presentation.into())).await?;
         Ok(())
     }
 
-    pub fn get_state(&self, thread_id: &str) -> AgentResult<VerifierState> {
-        let VerifierWrapper { verifier, .. } = self.verifiers.get(thread_id)?;
+    pub async fn get_state(&self, thread_id: &str) -> AgentResult<VerifierState> {
+        let VerifierWrapper { verifier, .. } = self.verifiers.get(thread_id).await?;
         Ok(verifier.get_state())
     }
 
-    pub fn exists_by_id(&self, thread_id: &str) -> bool {
-        self.verifiers.contains_key(thread_id)
+    /// The thread ids of the verifiers in the state, over the connection if given.
+    pub async fn find_by_state(&self, state: VerifierState, connection_id: Option<&str>) -> AgentResult<Vec<String>> {
+        let tags = RecordTags::default()
+            .with_state(&state)
+            .with_connection_id(connection_id);
+        self.verifiers.query(&tags).await
+    }
+
+    pub async fn exists_by_id(&self, thread_id: &str) -> bool {
+        self.verifiers.contains_key(thread_id).await
     }
 }
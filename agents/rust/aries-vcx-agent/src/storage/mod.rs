@@ -1,13 +1,87 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
 use crate::AgentResult;
 
 pub(crate) mod object_cache;
+pub(crate) mod wallet_storage;
 
-pub trait Storage<T> {
-    type Value;
-    fn get(&self, id: &str) -> AgentResult<T>;
-    fn insert(&self, id: &str, obj: T) -> AgentResult<String>;
-    fn contains_key(&self, id: &str) -> bool;
-    fn find_by<F>(&self, closure: F) -> AgentResult<Vec<String>>
+#[async_trait]
+pub trait Storage<T>: Send + Sync
+where
+    T: Send + Sync,
+{
+    async fn get(&self, id: &str) -> AgentResult<T>;
+    async fn insert(&self, id: &str, obj: T) -> AgentResult<String>;
+    async fn contains_key(&self, id: &str) -> bool;
+    async fn find_by<F>(&self, closure: F) -> AgentResult<Vec<String>>
     where
-        F: FnMut((&String, &Self::Value)) -> Option<String>;
+        F: FnMut((&String, &T)) -> Option<String> + Send;
+}
+
+/// An object kept in a [`wallet_storage::WalletStorage`], as JSON along with the tags it can be
+/// queried by.
+pub trait WalletRecord: Serialize + DeserializeOwned {
+    fn tags(&self) -> RecordTags;
+}
+
+/// The tags of a protocol record, or the ones records are queried by, the unset ones matching any
+/// record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordTags {
+    pub state: Option<String>,
+    pub thread_id: Option<String>,
+    pub connection_id: Option<String>,
+    pub our_vk: Option<String>,
+    pub their_vk: Option<String>,
+}
+
+impl RecordTags {
+    /// States are tagged by their debug format, such as `OfferReceived`.
+    pub fn with_state<S: Debug>(mut self, state: &S) -> Self {
+        self.state = Some(format!("{:?}", state));
+        self
+    }
+
+    pub fn with_thread_id(mut self, thread_id: Option<&str>) -> Self {
+        self.thread_id = thread_id.map(ToOwned::to_owned);
+        self
+    }
+
+    pub fn with_connection_id(mut self, connection_id: Option<&str>) -> Self {
+        self.connection_id = connection_id.map(ToOwned::to_owned);
+        self
+    }
+
+    /// The verkey of our pairwise DID, by which the connection an inbound message is for is found.
+    pub fn with_our_vk(mut self, our_vk: Option<&str>) -> Self {
+        self.our_vk = our_vk.map(ToOwned::to_owned);
+        self
+    }
+
+    pub fn with_their_vk(mut self, their_vk: Option<&str>) -> Self {
+        self.their_vk = their_vk.map(ToOwned::to_owned);
+        self
+    }
+
+    /// The tags as the JSON object wallet records are tagged with, which is also the WQL query
+    /// matching them.
+    pub(crate) fn to_json(&self) -> Value {
+        let tags = [
+            ("state", &self.state),
+            ("thread_id", &self.thread_id),
+            ("connection_id", &self.connection_id),
+            ("our_vk", &self.our_vk),
+            ("their_vk", &self.their_vk),
+        ];
+        let tags: Map<String, Value> = tags
+            .into_iter()
+            .filter_map(|(name, value)| Some((name.to_string(), Value::String(value.clone()?))))
+            .collect();
+        Value::Object(tags)
+    }
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use async_trait::async_trait;
+
 use crate::error::*;
 
 use super::Storage;
@@ -58,13 +60,12 @@ where
     }
 }
 
+#[async_trait]
 impl<T> Storage<T> for ObjectCache<T>
 where
-    T: Clone,
+    T: Clone + Send + Sync,
 {
-    type Value = Mutex<T>;
-
-    fn get(&self, id: &str) -> AgentResult<T> {
+    async fn get(&self, id: &str) -> AgentResult<T> {
         let store = self._lock_store_read()?;
         match store.get(id) {
             Some(m) => match m.lock() {
@@ -81,7 +82,7 @@ where
         }
     }
 
-    fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
+    async fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
         let mut store = self._lock_store_write()?;
 
         match store.insert(id.to_string(), Mutex::new(obj)) {
@@ -90,7 +91,7 @@ where
         }
     }
 
-    fn contains_key(&self, id: &str) -> bool {
+    async fn contains_key(&self, id: &str) -> bool {
         let store = match self._lock_store_read() {
             Ok(g) => g,
             Err(_) => return false,
@@ -98,11 +99,14 @@ where
         store.contains_key(id)
     }
 
-    fn find_by<F>(&self, closure: F) -> AgentResult<Vec<String>>
+    async fn find_by<F>(&self, mut closure: F) -> AgentResult<Vec<String>>
     where
-        F: FnMut((&String, &Self::Value)) -> Option<String>,
+        F: FnMut((&String, &T)) -> Option<String> + Send,
     {
         let store = self._lock_store_read()?;
-        Ok(store.iter().filter_map(closure).collect())
+        Ok(store
+            .iter()
+            .filter_map(|(id, m)| closure((id, m.lock().ok()?.deref())))
+            .collect())
     }
 }
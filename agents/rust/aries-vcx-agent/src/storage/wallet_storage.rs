@@ -0,0 +1,310 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::*;
+
+use super::{RecordTags, Storage, WalletRecord};
+
+const RECORD_OPTIONS: &str = r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"#;
+
+/// Keeps objects as non-secret records of the wallet, so that they outlive the agent's process.
+pub struct WalletStorage<T> {
+    wallet: Arc<dyn BaseWallet>,
+    category: String,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<T> WalletStorage<T>
+where
+    T: WalletRecord + Send + Sync,
+{
+    pub fn new(wallet: Arc<dyn BaseWallet>, category: &str) -> Self {
+        Self {
+            wallet,
+            category: category.to_string(),
+            record: PhantomData,
+        }
+    }
+
+    /// The ids of the objects whose tags match the ones set in `tags`.
+    pub async fn query(&self, tags: &RecordTags) -> AgentResult<Vec<String>> {
+        Ok(self
+            .records(&tags.to_json().to_string())
+            .await?
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    async fn records(&self, query: &str) -> AgentResult<Vec<(String, T)>> {
+        let records = self
+            .wallet
+            .iterate_wallet_records(&self.category, query, RECORD_OPTIONS)
+            .await?
+            .collect()
+            .await?;
+
+        records
+            .iter()
+            .map(|record| {
+                let record: Value = serde_json::from_str(record)?;
+                let (id, value) = match (record["id"].as_str(), record["value"].as_str()) {
+                    (Some(id), Some(value)) => (id, value),
+                    _ => {
+                        return Err(AgentError::from_msg(
+                            AgentErrorKind::SerializationError,
+                            &format!("[WalletStorage: {}] Malformed wallet record", self.category),
+                        ))
+                    }
+                };
+                Ok((id.to_string(), serde_json::from_str(value)?))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<T> Storage<T> for WalletStorage<T>
+where
+    T: WalletRecord + Send + Sync,
+{
+    async fn get(&self, id: &str) -> AgentResult<T> {
+        match self.wallet.get_wallet_record_value(&self.category, id).await {
+            Ok(value) => Ok(serde_json::from_str(&value)?),
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Err(AgentError::from_msg(
+                AgentErrorKind::NotFound,
+                &format!("[WalletStorage: {}] Object not found for id: {}", self.category, id),
+            )),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
+        let value = serde_json::to_string(&obj)?;
+        let tags = obj.tags().to_json().to_string();
+
+        if self.contains_key(id).await {
+            self.wallet
+                .update_wallet_record_value(&self.category, id, &value)
+                .await?;
+            self.wallet.update_wallet_record_tags(&self.category, id, &tags).await?;
+        } else {
+            self.wallet
+                .add_wallet_record(&self.category, id, &value, Some(&tags))
+                .await?;
+        }
+        Ok(id.to_string())
+    }
+
+    async fn contains_key(&self, id: &str) -> bool {
+        self.wallet
+            .get_wallet_record(&self.category, id, RECORD_OPTIONS)
+            .await
+            .is_ok()
+    }
+
+    async fn find_by<F>(&self, mut closure: F) -> AgentResult<Vec<String>>
+    where
+        F: FnMut((&String, &T)) -> Option<String> + Send,
+    {
+        Ok(self
+            .records("{}")
+            .await?
+            .iter()
+            .filter_map(|(id, obj)| closure((id, obj)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx_core::wallet::in_memory_wallet::InMemoryWallet;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    const CATEGORY: &str = "test_records";
+
+    #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+    enum TestState {
+        Offered,
+        Accepted,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct TestRecord {
+        state: TestState,
+        thread_id: String,
+        connection_id: String,
+    }
+
+    impl TestRecord {
+        fn new(state: TestState, thread_id: &str, connection_id: &str) -> Self {
+            Self {
+                state,
+                thread_id: thread_id.to_string(),
+                connection_id: connection_id.to_string(),
+            }
+        }
+    }
+
+    impl WalletRecord for TestRecord {
+        fn tags(&self) -> RecordTags {
+            RecordTags::default()
+                .with_state(&self.state)
+                .with_thread_id(Some(self.thread_id.as_str()))
+                .with_connection_id(Some(self.connection_id.as_str()))
+        }
+    }
+
+    fn storage(wallet: &Arc<dyn BaseWallet>) -> WalletStorage<TestRecord> {
+        WalletStorage::new(wallet.clone(), CATEGORY)
+    }
+
+    fn sorted(mut ids: Vec<String>) -> Vec<String> {
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_records_outlive_storage() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let record = TestRecord::new(TestState::Offered, "thread_1", "connection_1");
+
+        storage(&wallet).insert("thread_1", record.clone()).await.unwrap();
+        let reopened = storage(&wallet);
+        assert!(reopened.contains_key("thread_1").await);
+        assert_eq!(reopened.get("thread_1").await.unwrap(), record);
+
+        let accepted = TestRecord::new(TestState::Accepted, "thread_1", "connection_1");
+        reopened.insert("thread_1", accepted.clone()).await.unwrap();
+        assert_eq!(storage(&wallet).get("thread_1").await.unwrap(), accepted);
+
+        let err = storage(&wallet).get("thread_2").await.unwrap_err();
+        assert_eq!(err.kind, AgentErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_tags_follow_state() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let storage = storage(&wallet);
+        let offered = RecordTags::default().with_state(&TestState::Offered);
+        let accepted = RecordTags::default().with_state(&TestState::Accepted);
+
+        storage
+            .insert(
+                "thread_1",
+                TestRecord::new(TestState::Offered, "thread_1", "connection_1"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(storage.query(&offered).await.unwrap(), vec!["thread_1"]);
+        assert!(storage.query(&accepted).await.unwrap().is_empty());
+
+        storage
+            .insert(
+                "thread_1",
+                TestRecord::new(TestState::Accepted, "thread_1", "connection_1"),
+            )
+            .await
+            .unwrap();
+        assert!(storage.query(&offered).await.unwrap().is_empty());
+        assert_eq!(storage.query(&accepted).await.unwrap(), vec!["thread_1"]);
+    }
+
+    #[tokio::test]
+    async fn test_query_by_thread_and_connection() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let storage = storage(&wallet);
+        let records = [
+            TestRecord::new(TestState::Offered, "thread_1", "connection_1"),
+            TestRecord::new(TestState::Offered, "thread_2", "connection_1"),
+            TestRecord::new(TestState::Accepted, "thread_3", "connection_1"),
+            TestRecord::new(TestState::Offered, "thread_4", "connection_2"),
+        ];
+        for record in records {
+            let id = record.thread_id.clone();
+            storage.insert(&id, record).await.unwrap();
+        }
+
+        let offered = RecordTags::default().with_state(&TestState::Offered);
+        assert_eq!(
+            sorted(
+                storage
+                    .query(&offered.clone().with_connection_id(Some("connection_1")))
+                    .await
+                    .unwrap()
+            ),
+            vec!["thread_1", "thread_2"]
+        );
+        assert_eq!(
+            sorted(storage.query(&offered).await.unwrap()),
+            vec!["thread_1", "thread_2", "thread_4"]
+        );
+        assert_eq!(
+            storage
+                .query(&RecordTags::default().with_thread_id(Some("thread_3")))
+                .await
+                .unwrap(),
+            vec!["thread_3"]
+        );
+        assert!(storage
+            .query(&offered.with_thread_id(Some("thread_3")))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            sorted(storage.query(&RecordTags::default()).await.unwrap()),
+            vec!["thread_1", "thread_2", "thread_3", "thread_4"]
+        );
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct KeysRecord {
+        our_vk: String,
+        their_vk: Option<String>,
+    }
+
+    impl WalletRecord for KeysRecord {
+        fn tags(&self) -> RecordTags {
+            RecordTags::default()
+                .with_our_vk(Some(self.our_vk.as_str()))
+                .with_their_vk(self.their_vk.as_deref())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_by_keys() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let storage = WalletStorage::new(wallet, CATEGORY);
+        let records = [
+            ("connection_1", "our_vk_1", Some("their_vk_1")),
+            ("connection_2", "our_vk_2", None),
+        ];
+        for (id, our_vk, their_vk) in records {
+            let record = KeysRecord {
+                our_vk: our_vk.to_string(),
+                their_vk: their_vk.map(ToOwned::to_owned),
+            };
+            storage.insert(id, record).await.unwrap();
+        }
+
+        let by_our_vk = |our_vk| RecordTags::default().with_our_vk(Some(our_vk));
+        let by_their_vk = |their_vk| RecordTags::default().with_their_vk(Some(their_vk));
+        assert_eq!(
+            storage.query(&by_our_vk("our_vk_2")).await.unwrap(),
+            vec!["connection_2"]
+        );
+        assert_eq!(
+            storage.query(&by_their_vk("their_vk_1")).await.unwrap(),
+            vec!["connection_1"]
+        );
+        assert!(storage.query(&by_their_vk("our_vk_1")).await.unwrap().is_empty());
+    }
+}
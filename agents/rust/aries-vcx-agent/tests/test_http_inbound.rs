@@ -0,0 +1,95 @@
+#![cfg(feature = "http_server")]
+
+use std::time::Duration;
+
+use aries_vcx::protocols::connection::{State, ThinState};
+use aries_vcx_agent::{
+    Agent, HttpInboundServer, InboundListener, InitConfig, PoolInitConfig, ProtocolEvent, WalletInitConfig,
+};
+use aries_vcx_core::indy::ledger::pool::test_utils::create_tmp_genesis_txn_file;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::time::timeout;
+
+const WALLET_KEY: &str = "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY";
+const TRUSTEE_SEED: &str = "000000000000000000000000Trustee1";
+
+struct TestAgent {
+    agent: Agent,
+    server: HttpInboundServer,
+    events: UnboundedReceiver<ProtocolEvent>,
+}
+
+impl TestAgent {
+    async fn start(name: &str) -> Self {
+        let listener = InboundListener::bind("127.0.0.1:0").unwrap();
+        let suffix = uuid::Uuid::new_v4();
+        let agent = Agent::initialize(InitConfig {
+            enterprise_seed: TRUSTEE_SEED.to_string(),
+            pool_config: PoolInitConfig {
+                genesis_path: create_tmp_genesis_txn_file(),
+                pool_name: format!("pool_{}_{}", name, suffix),
+            },
+            agency_config: None,
+            wallet_config: WalletInitConfig {
+                wallet_name: format!("wallet_{}_{}", name, suffix),
+                wallet_key: WALLET_KEY.to_string(),
+                wallet_kdf: "RAW".to_string(),
+            },
+            service_endpoint: listener.endpoint().unwrap(),
+        })
+        .await
+        .unwrap();
+
+        let (sender, events) = unbounded_channel();
+        agent
+            .events()
+            .subscribe(move |event| {
+                sender.send(event.clone()).ok();
+            })
+            .unwrap();
+        let server = agent.serve_inbound(listener).unwrap();
+
+        Self { agent, server, events }
+    }
+
+    /// Waits for a connection of the agent to reach the state, as messages are received in the
+    /// background, and returns its thread id.
+    async fn connection_reaches(&mut self, state: ThinState) -> String {
+        let wait = async {
+            loop {
+                match self.events.recv().await {
+                    Some(ProtocolEvent::Connection(change)) if change.new_state == state => return change.thread_id,
+                    Some(_) => continue,
+                    None => panic!("Event bus of the agent was dropped"),
+                }
+            }
+        };
+        timeout(Duration::from_secs(30), wait)
+            .await
+            .unwrap_or_else(|_| panic!("No connection reached state {:?}", state))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore]
+async fn test_agents_connect_over_http() {
+    let mut faber = TestAgent::start("faber").await;
+    let mut alice = TestAgent::start("alice").await;
+
+    let invitation = faber.agent.connections().create_invitation(None).await.unwrap();
+    let alice_thread_id = alice.agent.connections().receive_invitation(invitation).await.unwrap();
+    alice.agent.connections().send_request(&alice_thread_id).await.unwrap();
+
+    let faber_thread_id = faber.connection_reaches(ThinState::Inviter(State::Requested)).await;
+    faber.agent.connections().send_response(&faber_thread_id).await.unwrap();
+
+    let thread_id = alice.connection_reaches(ThinState::Invitee(State::Responded)).await;
+    assert_eq!(thread_id, alice_thread_id);
+    alice.agent.connections().send_ack(&alice_thread_id).await.unwrap();
+
+    let thread_id = faber.connection_reaches(ThinState::Inviter(State::Completed)).await;
+    assert_eq!(thread_id, faber_thread_id);
+
+    faber.server.shutdown().await;
+    alice.server.shutdown().await;
+}
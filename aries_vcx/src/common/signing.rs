@@ -2,12 +2,21 @@ use std::sync::Arc;
 
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use base64;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::decorators::attachment::{
+    Attachment, AttachmentData, AttachmentJws, AttachmentJwsHeader, AttachmentType,
+};
+use messages::misc::MimeType;
 use messages::msg_fields::protocols::connection::response::{ConnectionSignature, ResponseContent};
 use messages::msg_fields::protocols::connection::ConnectionData;
 use time;
+use uuid::Uuid;
 
 use crate::errors::error::prelude::*;
 use crate::global::settings;
+use crate::utils::didcomm_v2::{crypto, keys};
+
+const ATTACHMENT_JWS_ALG: &str = "EdDSA";
 
 async fn get_signature_data(wallet: &Arc<dyn BaseWallet>, data: String, key: &str) -> VcxResult<(Vec<u8>, Vec<u8>)> {
     let now: u64 = time::OffsetDateTime::now_utc().unix_timestamp() as u64;
@@ -78,6 +87,179 @@ pub async fn decode_signed_connection_response(
     Ok(connection)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct AttachmentJwsProtected {
+    alg: String,
+    kid: String,
+    jwk: AttachmentJwk,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AttachmentJwk {
+    kty: String,
+    crv: String,
+    x: String,
+    kid: String,
+}
+
+fn attachment_signing_input(protected: &str, content: &[u8]) -> Vec<u8> {
+    format!("{protected}.{}", crypto::b64_encode(content)).into_bytes()
+}
+
+fn decode_attachment_content(data: &AttachmentData) -> VcxResult<Vec<u8>> {
+    match &data.content {
+        AttachmentType::Base64(encoded) => base64::decode_config(encoded, base64::STANDARD)
+            .or_else(|_| base64::decode_config(encoded, base64::URL_SAFE))
+            .or_else(|_| base64::decode_config(encoded, base64::URL_SAFE_NO_PAD))
+            .map_err(|err| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Cannot decode base64 attachment: {err}"),
+                )
+            }),
+        AttachmentType::Json(value) if data.jws.is_none() => Ok(serde_json::to_vec(value)?),
+        _ => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Only base64 encoded attachments can be signed",
+        )),
+    }
+}
+
+/// Verifies the JWS of a signed attachment, returning the attachment content
+/// and the base58 verkey of the signer.
+async fn verify_attachment_jws(wallet: &Arc<dyn BaseWallet>, data: &AttachmentData) -> VcxResult<(Vec<u8>, String)> {
+    let jws = data.jws.as_ref().ok_or_else(|| {
+        AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, "Attachment is not signed")
+    })?;
+
+    let protected: AttachmentJwsProtected = serde_json::from_slice(&crypto::b64_decode(&jws.protected)?)
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize attachment JWS protected header: {err}"),
+            )
+        })?;
+
+    if protected.alg != ATTACHMENT_JWS_ALG {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::UnimplementedFeature,
+            format!("Unsupported attachment JWS algorithm: {}", protected.alg),
+        ));
+    }
+
+    let signer_vk = bs58::encode(keys::ed25519_from_kid(&protected.kid)?).into_string();
+    let content = decode_attachment_content(data)?;
+    let signature = crypto::b64_decode(&jws.signature)?;
+
+    let input = attachment_signing_input(&jws.protected, &content);
+
+    if !wallet.verify(&signer_vk, &input, &signature).await? {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Attachment JWS signature is invalid",
+        ));
+    }
+
+    Ok((content, signer_vk))
+}
+
+fn deserialize_did_doc(content: &[u8]) -> VcxResult<AriesDidDoc> {
+    serde_json::from_slice(content).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize attached DIDDoc: {err}"),
+        )
+    })
+}
+
+/// Creates a base64 attachment of the [`AriesDidDoc`], signed with `key`,
+/// as used by the `did_doc~attach` field of the DID exchange messages.
+pub async fn sign_did_doc_attachment(
+    wallet: &Arc<dyn BaseWallet>,
+    key: &str,
+    did_doc: &AriesDidDoc,
+) -> VcxResult<Attachment> {
+    let content = serde_json::to_vec(did_doc)?;
+    let did_key = keys::ed25519_did_key(&keys::verkey_to_bytes(key)?);
+
+    let protected = AttachmentJwsProtected {
+        alg: ATTACHMENT_JWS_ALG.to_owned(),
+        kid: did_key.clone(),
+        jwk: AttachmentJwk {
+            kty: "OKP".to_owned(),
+            crv: "Ed25519".to_owned(),
+            x: crypto::b64_encode(&keys::verkey_to_bytes(key)?),
+            kid: did_key.clone(),
+        },
+    };
+
+    let protected = crypto::b64_encode(&serde_json::to_vec(&protected)?);
+    let signature = wallet
+        .sign(key, &attachment_signing_input(&protected, &content))
+        .await?;
+
+    let jws = AttachmentJws::new(
+        AttachmentJwsHeader::new(did_key),
+        protected,
+        crypto::b64_encode(&signature),
+    );
+
+    let mut data = AttachmentData::new(AttachmentType::Base64(base64::encode_config(
+        &content,
+        base64::STANDARD,
+    )));
+    data.jws = Some(jws);
+
+    let mut attachment = Attachment::new(data);
+    attachment.id = Some(Uuid::new_v4().to_string());
+    attachment.mime_type = Some(MimeType::Json);
+
+    Ok(attachment)
+}
+
+/// Extracts the [`AriesDidDoc`] from an attachment that must have been signed with `their_vk`.
+pub async fn decode_signed_did_doc_attachment(
+    wallet: &Arc<dyn BaseWallet>,
+    attachment: &Attachment,
+    their_vk: &str,
+) -> VcxResult<AriesDidDoc> {
+    let (content, signer_vk) = verify_attachment_jws(wallet, &attachment.data).await?;
+
+    if signer_vk != their_vk {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Attached DIDDoc was signed by {signer_vk}, expected signer {their_vk}"),
+        ));
+    }
+
+    deserialize_did_doc(&content)
+}
+
+/// Extracts the [`AriesDidDoc`] from an attachment.
+///
+/// If the attachment is signed, the signature is verified and the signer must be
+/// one of the recipient keys of the attached [`AriesDidDoc`].
+pub async fn decode_did_doc_attachment(
+    wallet: &Arc<dyn BaseWallet>,
+    attachment: &Attachment,
+) -> VcxResult<AriesDidDoc> {
+    if attachment.data.jws.is_none() {
+        return deserialize_did_doc(&decode_attachment_content(&attachment.data)?);
+    }
+
+    let (content, signer_vk) = verify_attachment_jws(wallet, &attachment.data).await?;
+    let did_doc = deserialize_did_doc(&content)?;
+
+    if !did_doc.recipient_keys()?.contains(&signer_vk) {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Attached DIDDoc was signed by {signer_vk}, which is not one of its recipient keys"),
+        ));
+    }
+
+    Ok(did_doc)
+}
+
 pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8]) -> VcxResult<String> {
     if settings::indy_mocks_enabled() {
         return Ok(String::new());
@@ -158,3 +340,55 @@ pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8])
 //         .await;
 //     }
 // }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+    use crate::core::profile::profile::Profile;
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    const SIGNER_VK: &str = "7Z9ZajGKvb6BMsZ9TBEqxMHktxGdts3FvAbKSJT5XgzK";
+    const OTHER_VK: &str = "91qMFrZjXDoi2Vc8Mm14Ys112tEZdDegBZZoembFEATE";
+
+    fn make_did_doc() -> AriesDidDoc {
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id("test_did".to_owned());
+        did_doc.set_service_endpoint("https://dummy.dummy/dummy".parse().unwrap());
+        did_doc.set_recipient_keys(vec![SIGNER_VK.to_owned()]);
+        did_doc.set_routing_keys(Vec::new());
+        did_doc
+    }
+
+    #[tokio::test]
+    async fn test_signed_did_doc_attachment_roundtrip() {
+        let wallet = Arc::new(MockProfile).inject_wallet();
+        let did_doc = make_did_doc();
+
+        let attachment = sign_did_doc_attachment(&wallet, SIGNER_VK, &did_doc).await.unwrap();
+        assert!(attachment.data.jws.is_some());
+
+        let decoded = decode_signed_did_doc_attachment(&wallet, &attachment, SIGNER_VK)
+            .await
+            .unwrap();
+        assert_eq!(decoded, did_doc);
+
+        let decoded = decode_did_doc_attachment(&wallet, &attachment).await.unwrap();
+        assert_eq!(decoded, did_doc);
+    }
+
+    #[tokio::test]
+    async fn test_signed_did_doc_attachment_rejects_other_signer() {
+        let wallet = Arc::new(MockProfile).inject_wallet();
+        let did_doc = make_did_doc();
+
+        let attachment = sign_did_doc_attachment(&wallet, OTHER_VK, &did_doc).await.unwrap();
+
+        decode_signed_did_doc_attachment(&wallet, &attachment, SIGNER_VK)
+            .await
+            .unwrap_err();
+
+        // The signer is not one of the recipient keys of the attached DIDDoc.
+        decode_did_doc_attachment(&wallet, &attachment).await.unwrap_err();
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use aries_vcx_core::{
+    anoncreds::base_anoncreds::BaseAnonCreds,
+    ledger::{
+        base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite},
+        in_memory_ledger::{InMemoryLedger, InMemoryLedgerWrite},
+    },
+    wallet::base_wallet::BaseWallet,
+};
+
+use super::profile::Profile;
+
+/// Profile writing to a ledger kept in memory, for tests and demos which should not depend on a
+/// running pool. The ledger may be shared by the profiles of several agents.
+#[derive(Debug)]
+pub struct InMemoryLedgerProfile {
+    wallet: Arc<dyn BaseWallet>,
+    anoncreds: Arc<dyn BaseAnonCreds>,
+    ledger_read: Arc<InMemoryLedger>,
+    ledger_write: Arc<InMemoryLedgerWrite>,
+}
+
+impl InMemoryLedgerProfile {
+    pub fn new(wallet: Arc<dyn BaseWallet>, anoncreds: Arc<dyn BaseAnonCreds>, ledger: Arc<InMemoryLedger>) -> Self {
+        let ledger_write = Arc::new(InMemoryLedgerWrite::new(Arc::clone(&ledger), Arc::clone(&wallet)));
+        InMemoryLedgerProfile {
+            wallet,
+            anoncreds,
+            ledger_read: ledger,
+            ledger_write,
+        }
+    }
+}
+
+impl Profile for InMemoryLedgerProfile {
+    fn inject_indy_ledger_read(self: Arc<Self>) -> Arc<dyn IndyLedgerRead> {
+        self.ledger_read.clone()
+    }
+
+    fn inject_indy_ledger_write(self: Arc<Self>) -> Arc<dyn IndyLedgerWrite> {
+        self.ledger_write.clone()
+    }
+
+    fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
+        Arc::clone(&self.anoncreds)
+    }
+
+    fn inject_anoncreds_ledger_read(self: Arc<Self>) -> Arc<dyn AnoncredsLedgerRead> {
+        self.ledger_read.clone()
+    }
+
+    fn inject_anoncreds_ledger_write(self: Arc<Self>) -> Arc<dyn AnoncredsLedgerWrite> {
+        self.ledger_write.clone()
+    }
+
+    fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
+        Arc::clone(&self.wallet)
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "in_memory_ledger")]
+pub mod in_memory_ledger_profile;
 #[cfg(feature = "mixed_breed")]
 pub mod mixed_breed_profile;
 #[cfg(feature = "modular_libs")]
@@ -7,9 +7,11 @@ use aries_vcx_core::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWr
 use aries_vcx_core::ledger::indy_vdr_ledger::{
     IndyVdrLedgerRead, IndyVdrLedgerReadConfig, IndyVdrLedgerWrite, IndyVdrLedgerWriteConfig,
 };
+use aries_vcx_core::ledger::multi_ledger::{MultiLedgerRead, MultiLedgerWrite, NamespacedLedger};
 use aries_vcx_core::ledger::request_signer::base_wallet::BaseWalletRequestSigner;
 use aries_vcx_core::ledger::request_submitter::vdr_ledger::{IndyVdrLedgerPool, IndyVdrSubmitter, LedgerPoolConfig};
 use aries_vcx_core::ledger::response_cacher::in_memory::{InMemoryResponseCacher, InMemoryResponseCacherConfig};
+use aries_vcx_core::ledger::response_cacher::persistent::{PersistentResponseCacher, PersistentResponseCacherConfig};
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use aries_vcx_core::ResponseParser;
 
@@ -28,12 +30,88 @@ pub struct ModularLibsProfile {
     indy_ledger_write: Arc<dyn IndyLedgerWrite>,
 }
 
+type VdrLedgerRead = IndyVdrLedgerRead<IndyVdrSubmitter, InMemoryResponseCacher>;
+
 impl ModularLibsProfile {
     pub fn new(wallet: Arc<dyn BaseWallet>, ledger_pool_config: LedgerPoolConfig) -> VcxResult<Self> {
         let anoncreds = Arc::new(IndyCredxAnonCreds::new(Arc::clone(&wallet)));
         let ledger_pool = Arc::new(IndyVdrLedgerPool::new(ledger_pool_config)?);
-        let request_signer = Arc::new(BaseWalletRequestSigner::new(wallet.clone()));
         let request_submitter = Arc::new(IndyVdrSubmitter::new(ledger_pool));
+        let ledger_read = Arc::new(Self::ledger_read(request_submitter.clone())?);
+        let ledger_write = Arc::new(Self::ledger_write(&wallet, request_submitter));
+        Ok(ModularLibsProfile {
+            wallet,
+            anoncreds,
+            anoncreds_ledger_read: ledger_read.clone(),
+            anoncreds_ledger_write: ledger_write.clone(),
+            indy_ledger_read: ledger_read.clone(),
+            indy_ledger_write: ledger_write,
+        })
+    }
+
+    /// Profile caching the ledger responses in its wallet, so they are kept across restarts. As the
+    /// cached responses do not tell pools apart, the wallet should only ever be used with one pool.
+    pub fn new_with_persistent_cache(
+        wallet: Arc<dyn BaseWallet>,
+        ledger_pool_config: LedgerPoolConfig,
+        cacher_config: PersistentResponseCacherConfig,
+    ) -> VcxResult<Self> {
+        let anoncreds = Arc::new(IndyCredxAnonCreds::new(Arc::clone(&wallet)));
+        let ledger_pool = Arc::new(IndyVdrLedgerPool::new(ledger_pool_config)?);
+        let request_submitter = Arc::new(IndyVdrSubmitter::new(ledger_pool));
+        let config_read = IndyVdrLedgerReadConfig {
+            request_submitter: request_submitter.clone(),
+            response_parser: Arc::new(ResponseParser::new()),
+            response_cacher: Arc::new(PersistentResponseCacher::new(wallet.clone(), cacher_config)),
+        };
+        let ledger_read = Arc::new(IndyVdrLedgerRead::new(config_read));
+        let ledger_write = Arc::new(Self::ledger_write(&wallet, request_submitter));
+        Ok(ModularLibsProfile {
+            wallet,
+            anoncreds,
+            anoncreds_ledger_read: ledger_read.clone(),
+            anoncreds_ledger_write: ledger_write.clone(),
+            indy_ledger_read: ledger_read,
+            indy_ledger_write: ledger_write,
+        })
+    }
+
+    /// Profile reading from the pools of several `did:indy` namespaces, such as `sovrin` or
+    /// `bcovrin:test`, given in the order unqualified IDs are looked up in. Writes go to the pool
+    /// of the first namespace, `did:indy` DIDs and IDs of the other namespaces being rejected.
+    pub fn new_multi_ledger(
+        wallet: Arc<dyn BaseWallet>,
+        ledger_pool_configs: Vec<(String, LedgerPoolConfig)>,
+    ) -> VcxResult<Self> {
+        let anoncreds = Arc::new(IndyCredxAnonCreds::new(Arc::clone(&wallet)));
+        let mut ledger_write = None;
+        let mut ledgers = Vec::new();
+        for (namespace, ledger_pool_config) in ledger_pool_configs {
+            let ledger_pool = Arc::new(IndyVdrLedgerPool::new(ledger_pool_config)?);
+            let request_submitter = Arc::new(IndyVdrSubmitter::new(ledger_pool));
+            ledger_write.get_or_insert_with(|| {
+                let write = Arc::new(Self::ledger_write(&wallet, request_submitter.clone()));
+                MultiLedgerWrite::new(&namespace, write)
+            });
+            let ledger_read = Arc::new(Self::ledger_read(request_submitter)?);
+            ledgers.push(NamespacedLedger::new(&namespace, ledger_read));
+        }
+        let ledger_read = Arc::new(MultiLedgerRead::new(ledgers)?);
+        // `MultiLedgerRead::new` rejects an empty list of pools, so the first one is there to write to
+        let ledger_write = Arc::new(ledger_write.expect("writer of the first pool"));
+        Ok(ModularLibsProfile {
+            wallet,
+            anoncreds,
+            anoncreds_ledger_read: ledger_read.clone(),
+            anoncreds_ledger_write: ledger_write.clone(),
+            indy_ledger_read: ledger_read,
+            indy_ledger_write: ledger_write,
+        })
+    }
+
+    /// Reads of a pool have their own cache, as unqualified IDs may name different objects on
+    /// different pools.
+    fn ledger_read(request_submitter: Arc<IndyVdrSubmitter>) -> VcxResult<VdrLedgerRead> {
         let response_parser = Arc::new(ResponseParser::new());
         let cacher_config = InMemoryResponseCacherConfig::builder()
             .ttl(Duration::from_secs(60))
@@ -41,24 +119,23 @@ impl ModularLibsProfile {
             .build();
         let response_cacher = Arc::new(InMemoryResponseCacher::new(cacher_config));
         let config_read = IndyVdrLedgerReadConfig {
-            request_submitter: request_submitter.clone(),
+            request_submitter,
             response_parser,
             response_cacher,
         };
+        Ok(IndyVdrLedgerRead::new(config_read))
+    }
+
+    fn ledger_write(
+        wallet: &Arc<dyn BaseWallet>,
+        request_submitter: Arc<IndyVdrSubmitter>,
+    ) -> IndyVdrLedgerWrite<IndyVdrSubmitter, BaseWalletRequestSigner> {
+        let request_signer = Arc::new(BaseWalletRequestSigner::new(wallet.clone()));
         let config_write = IndyVdrLedgerWriteConfig {
             request_signer,
             request_submitter,
         };
-        let ledger_read = Arc::new(IndyVdrLedgerRead::new(config_read));
-        let ledger_write = Arc::new(IndyVdrLedgerWrite::new(config_write));
-        Ok(ModularLibsProfile {
-            wallet,
-            anoncreds,
-            anoncreds_ledger_read: ledger_read.clone(),
-            anoncreds_ledger_write: ledger_write.clone(),
-            indy_ledger_read: ledger_read.clone(),
-            indy_ledger_write: ledger_write,
-        })
+        IndyVdrLedgerWrite::new(config_write)
     }
 }
 
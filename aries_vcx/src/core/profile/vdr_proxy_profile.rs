@@ -6,8 +6,13 @@ use aries_vcx_core::{
         base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite},
         indy_vdr_ledger::{IndyVdrLedgerRead, IndyVdrLedgerReadConfig, IndyVdrLedgerWrite, IndyVdrLedgerWriteConfig},
         request_signer::base_wallet::BaseWalletRequestSigner,
-        request_submitter::vdr_proxy::VdrProxySubmitter,
+        request_submitter::{
+            state_proof::{StateProofMode, StateProofSubmitter},
+            vdr_proxy::VdrProxySubmitter,
+            RequestSubmitter,
+        },
         response_cacher::in_memory::{InMemoryResponseCacher, InMemoryResponseCacherConfig},
+        state_proof::StateProofVerifier,
     },
     wallet::{base_wallet::BaseWallet, indy_wallet::IndySdkWallet},
     ResponseParser, VdrProxyClient, WalletHandle,
@@ -29,10 +34,30 @@ pub struct VdrProxyProfile {
 
 impl VdrProxyProfile {
     pub fn new(wallet_handle: WalletHandle, client: VdrProxyClient) -> VcxResult<Self> {
+        let request_submitter = Arc::new(VdrProxySubmitter::new(Arc::new(client)));
+        Self::new_with_submitter(wallet_handle, request_submitter)
+    }
+
+    /// Profile verifying the state proofs of the replies the proxy passes on, with the BLS keys
+    /// of the pool nodes known to the verifier, so the proxy need not be trusted.
+    pub fn new_with_state_proofs(
+        wallet_handle: WalletHandle,
+        client: VdrProxyClient,
+        verifier: StateProofVerifier,
+        mode: StateProofMode,
+    ) -> VcxResult<Self> {
+        let proxy_submitter = Arc::new(VdrProxySubmitter::new(Arc::new(client)));
+        let request_submitter = Arc::new(StateProofSubmitter::new(proxy_submitter, verifier, mode));
+        Self::new_with_submitter(wallet_handle, request_submitter)
+    }
+
+    fn new_with_submitter<T>(wallet_handle: WalletHandle, request_submitter: Arc<T>) -> VcxResult<Self>
+    where
+        T: RequestSubmitter + Send + Sync + 'static,
+    {
         let wallet = Arc::new(IndySdkWallet::new(wallet_handle));
         let anoncreds = Arc::new(IndySdkAnonCreds::new(wallet_handle));
         let request_signer = Arc::new(BaseWalletRequestSigner::new(wallet.clone()));
-        let request_submitter = Arc::new(VdrProxySubmitter::new(Arc::new(client)));
         let response_parser = Arc::new(ResponseParser::new());
         let cacher_config = InMemoryResponseCacherConfig::builder()
             .ttl(Duration::from_secs(60))
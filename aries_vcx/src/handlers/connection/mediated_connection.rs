@@ -14,6 +14,7 @@ use messages::msg_fields::protocols::connection::request::Request;
 use messages::msg_fields::protocols::connection::Connection;
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
 use messages::msg_fields::protocols::discover_features::{DiscoverFeatures, ProtocolDescriptor};
+use messages::msg_fields::protocols::discover_features_v2::DiscoverFeaturesV2;
 use messages::msg_fields::protocols::out_of_band::OutOfBand;
 use messages::msg_fields::protocols::trust_ping::TrustPing;
 use messages::AriesMessage;
@@ -31,7 +32,9 @@ use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::cloud_agent::CloudAgentInfo;
 use crate::handlers::connection::legacy_agent_info::LegacyAgentInfo;
-use crate::handlers::discovery::{respond_discovery_query, send_discovery_query};
+use crate::handlers::discovery::{
+    respond_discovery_queries, respond_discovery_query, send_discovery_query, SupportedFeatures,
+};
 use crate::handlers::trust_ping::TrustPingSender;
 use crate::handlers::util::AnyInvitation;
 use crate::protocols::mediated_connection::invitee::state_machine::{
@@ -425,7 +428,8 @@ impl MediatedConnection {
                 | AriesMessage::OutOfBand(OutOfBand::HandshakeReuse(_))
                 | AriesMessage::OutOfBand(OutOfBand::HandshakeReuseAccepted(_))
                 | AriesMessage::DiscoverFeatures(DiscoverFeatures::Query(_))
-                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(_)) => return Some((uid, message)),
+                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(_))
+                | AriesMessage::DiscoverFeaturesV2(DiscoverFeaturesV2::Queries(_)) => return Some((uid, message)),
                 _ => {}
             }
         }
@@ -468,7 +472,7 @@ impl MediatedConnection {
                 send_message(profile.inject_wallet(), pw_vk.to_string(), did_doc.clone(), msg.into()).await?;
             }
             AriesMessage::DiscoverFeatures(DiscoverFeatures::Query(query)) => {
-                let supported_protocols = query.content.lookup();
+                let supported_protocols = SupportedFeatures::default().protocols(&query.content.query);
 
                 info!(
                     "Answering discovery protocol query, @id: {}, with supported protocols: {:?}",
@@ -483,6 +487,18 @@ impl MediatedConnection {
 
                 self.connection_sm = self.handle_disclose(disclose).await?;
             }
+            AriesMessage::DiscoverFeaturesV2(DiscoverFeaturesV2::Queries(queries)) => {
+                info!("Answering discovery protocol queries, @id: {}", &queries.id);
+
+                respond_discovery_queries(
+                    &profile.inject_wallet(),
+                    queries,
+                    &did_doc,
+                    pw_vk,
+                    &SupportedFeatures::default(),
+                )
+                .await?;
+            }
             _ => {
                 // todo: implement to_string for A2AMessage, printing only type of the message, not entire payload
                 // todo: attempt to print @id / thread_id of the message
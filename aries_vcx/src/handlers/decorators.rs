@@ -0,0 +1,534 @@
+//! Enforcement of the `~timing` and `~please_ack` decorators, for the messages of all protocols.
+//!
+//! Inbound messages are admitted through [`admit_message`] before being handled: expired messages are
+//! rejected with a problem report, messages that are not to be handled yet are deferred and the
+//! `notification/1.0` acks asked for are built so they can be sent on receipt and on outcome.
+//! Deferred messages are left aside by [`without_deferred`] so they don't hold up the rest of their
+//! thread.
+//!
+//! Outbound messages declare their expiry with [`set_expiry`], and state machines implementing
+//! [`ExpiringThread`] give up on their thread through [`time_out`] once the message awaiting an
+//! answer expired, unless an answer sent before the expiry is waiting to be handled, see
+//! [`answered_in_time`].
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use messages::decorators::please_ack::{AckOn, PleaseAck};
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::msg_fields::protocols::connection::Connection;
+use messages::msg_fields::protocols::cred_issuance::CredentialIssuance;
+use messages::msg_fields::protocols::cred_issuance_v2::CredentialIssuanceV2;
+use messages::msg_fields::protocols::notification::ack::{Ack, AckContent, AckDecorators, AckStatus};
+use messages::msg_fields::protocols::present_proof::PresentProof;
+use messages::msg_fields::protocols::present_proof_v2::PresentProofV2;
+use messages::msg_fields::protocols::report_problem::{
+    Impact, ProblemReport, ProblemReportContent, ProblemReportDecorators,
+};
+use messages::msg_fields::protocols::revocation::Revocation;
+use messages::AriesMessage;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+
+/// Problem code of the problem reports sent for expired messages.
+pub const EXPIRED_PROBLEM_CODE: &str = "expired";
+
+/// The decorators of an inbound message which tell how it is to be processed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessingDecorators {
+    pub thread_id: String,
+    pub please_ack: Option<PleaseAck>,
+    pub timing: Option<Timing>,
+}
+
+impl ProcessingDecorators {
+    /// Reads the decorators of a message of any protocol. The thread id is the message id
+    /// if the message does not belong to a thread yet.
+    pub fn from_message(message: &AriesMessage) -> VcxResult<Self> {
+        let value = serde_json::to_value(message)?;
+
+        let thread_id = value["~thread"]["thid"]
+            .as_str()
+            .or_else(|| value["@id"].as_str())
+            .ok_or_else(|| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, "Message has no @id"))?
+            .to_owned();
+
+        Ok(Self {
+            thread_id,
+            please_ack: decorator(&value, "~please_ack")?,
+            timing: decorator(&value, "~timing")?,
+        })
+    }
+
+    fn acks_on(&self, on: AckOn) -> bool {
+        matches!(&self.please_ack, Some(please_ack) if please_ack.on.contains(&on))
+    }
+}
+
+fn decorator<T: serde::de::DeserializeOwned>(message: &Value, name: &str) -> VcxResult<Option<T>> {
+    match message.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone()).map(Some).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Invalid {} decorator: {}", name, err),
+            )
+        }),
+    }
+}
+
+/// Where a message stands according to its `~timing` decorator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingStatus {
+    Current,
+    /// The message is past its `stale_time`, it can still be handled.
+    Stale,
+    /// The message is past its `expires_time` and must not be handled.
+    Expired,
+    /// The message must not be handled before its `wait_until_time`.
+    Deferred(DateTime<Utc>),
+}
+
+pub fn timing_status(timing: Option<&Timing>, now: DateTime<Utc>) -> TimingStatus {
+    let Some(timing) = timing else {
+        return TimingStatus::Current;
+    };
+
+    match (timing.expires_time, timing.wait_until_time, timing.stale_time) {
+        (Some(expires_time), _, _) if expires_time <= now => TimingStatus::Expired,
+        (_, Some(wait_until_time), _) if wait_until_time > now => TimingStatus::Deferred(wait_until_time),
+        (_, _, Some(stale_time)) if stale_time <= now => TimingStatus::Stale,
+        _ => TimingStatus::Current,
+    }
+}
+
+/// The acks asked for by an admitted message.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestedAcks {
+    /// To be sent before handling the message.
+    pub receipt: Option<Ack>,
+    /// To be sent once the message is handled.
+    pub outcome: Option<Ack>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Admission {
+    Accepted(RequestedAcks),
+    /// The message is to be handled again once the time has come.
+    Deferred(DateTime<Utc>),
+    /// The message expired, the problem report is sent instead of handling it.
+    Rejected(ProblemReport),
+}
+
+/// Decides whether an inbound message is to be handled now, according to its decorators.
+pub fn admit_message(message: &AriesMessage, now: DateTime<Utc>) -> VcxResult<Admission> {
+    let decorators = ProcessingDecorators::from_message(message)?;
+
+    let admission = match timing_status(decorators.timing.as_ref(), now) {
+        TimingStatus::Expired => {
+            let problem_report = build_problem_report(&decorators.thread_id, Impact::MessageContent);
+            Admission::Rejected(problem_report)
+        }
+        TimingStatus::Deferred(wait_until_time) => Admission::Deferred(wait_until_time),
+        status => {
+            if status == TimingStatus::Stale {
+                warn!("Handling stale message of thread {}", decorators.thread_id);
+            }
+
+            let receipt = decorators.acks_on(AckOn::Receipt);
+            // The protocols acknowledging these messages themselves send the outcome ack.
+            let outcome = decorators.acks_on(AckOn::Outcome) && !acked_by_protocol(message);
+
+            Admission::Accepted(RequestedAcks {
+                receipt: receipt.then(|| build_ack(&decorators.thread_id)),
+                outcome: outcome.then(|| build_ack(&decorators.thread_id)),
+            })
+        }
+    };
+
+    Ok(admission)
+}
+
+fn acked_by_protocol(message: &AriesMessage) -> bool {
+    matches!(
+        message,
+        AriesMessage::Connection(Connection::Response(_))
+            | AriesMessage::CredentialIssuance(CredentialIssuance::IssueCredential(_))
+            | AriesMessage::CredentialIssuanceV2(CredentialIssuanceV2::IssueCredential(_))
+            | AriesMessage::PresentProof(PresentProof::Presentation(_))
+            | AriesMessage::PresentProofV2(PresentProofV2::Presentation(_))
+            | AriesMessage::Revocation(Revocation::Revoke(_))
+    )
+}
+
+fn out_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    timing
+}
+
+fn build_ack(thread_id: &str) -> Ack {
+    let mut decorators = AckDecorators::new(Thread::new(thread_id.to_owned()));
+    decorators.timing = Some(out_timing());
+
+    Ack::with_decorators(Uuid::new_v4().to_string(), AckContent::new(AckStatus::Ok), decorators)
+}
+
+fn build_problem_report(thread_id: &str, impact: Impact) -> ProblemReport {
+    let mut content = ProblemReportContent::new(EXPIRED_PROBLEM_CODE.to_owned());
+    content.impact = Some(impact);
+
+    let mut decorators = ProblemReportDecorators::default();
+    decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    decorators.timing = Some(out_timing());
+
+    ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// Leaves out the messages which are not to be handled before a later time, so the next message
+/// to handle is picked among the others. The deferred messages stay unread and are picked up once
+/// their time has come.
+pub fn without_deferred(messages: HashMap<String, AriesMessage>, now: DateTime<Utc>) -> HashMap<String, AriesMessage> {
+    messages
+        .into_iter()
+        .filter(|(uid, message)| {
+            let timing = ProcessingDecorators::from_message(message)
+                .ok()
+                .and_then(|decorators| decorators.timing);
+            match timing_status(timing.as_ref(), now) {
+                TimingStatus::Deferred(wait_until_time) => {
+                    info!("Deferring message {} until {}", uid, wait_until_time);
+                    false
+                }
+                _ => true,
+            }
+        })
+        .collect()
+}
+
+/// Admits a message received over a mediated connection and hands it to `handle` if it is to be
+/// handled now, sending the problem report or the acks the admission calls for.
+///
+/// Returns whether the message is done with, so it can be marked as read.
+pub async fn handle_connection_message<F, Fut>(
+    profile: &Arc<dyn Profile>,
+    connection: &MediatedConnection,
+    message: AriesMessage,
+    handle: F,
+) -> VcxResult<bool>
+where
+    F: FnOnce(AriesMessage) -> Fut,
+    Fut: Future<Output = VcxResult<()>>,
+{
+    match admit_message(&message, Utc::now())? {
+        Admission::Deferred(wait_until_time) => {
+            info!("Deferring message until {}", wait_until_time);
+            Ok(false)
+        }
+        Admission::Rejected(problem_report) => {
+            info!("Rejecting expired message, sending problem report {:?}", problem_report);
+            let send_message = connection.send_message_closure(profile).await?;
+            send_message(problem_report.into()).await?;
+            Ok(true)
+        }
+        Admission::Accepted(acks) => {
+            if let Some(ack) = acks.receipt {
+                let send_message = connection.send_message_closure(profile).await?;
+                send_message(ack.into()).await?;
+            }
+            handle(message).await?;
+            if let Some(ack) = acks.outcome {
+                let send_message = connection.send_message_closure(profile).await?;
+                send_message(ack.into()).await?;
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Declares when an outbound message expires.
+pub fn set_expiry(timing: &mut Option<Timing>, expires_time: DateTime<Utc>) {
+    timing.get_or_insert_with(Timing::default).expires_time = Some(expires_time);
+}
+
+/// Implemented by the state machines whose outbound messages can expire while awaiting an answer.
+pub trait ExpiringThread: Sized {
+    fn thread_id(&self) -> String;
+
+    /// When the outbound message awaiting an answer expires, if it was sent and declared an expiry.
+    fn awaited_expiry(&self) -> Option<DateTime<Utc>>;
+
+    /// Whether the message is the answer to the awaited message, the only one to stop the thread
+    /// from timing out.
+    fn answers_awaited(&self, message: &AriesMessage) -> bool;
+
+    /// Moves to a terminal state because the thread timed out, the problem report being sent to the peer.
+    fn expire(self, problem_report: ProblemReport) -> Self;
+}
+
+/// Times the thread out if the message awaiting an answer expired, returning the problem report
+/// to notify the peer with.
+pub fn time_out<T: ExpiringThread>(sm: T, now: DateTime<Utc>) -> (T, Option<ProblemReport>) {
+    match sm.awaited_expiry() {
+        Some(expires_time) if expires_time <= now => {
+            let problem_report = build_problem_report(&sm.thread_id(), Impact::Thread);
+            (sm.expire(problem_report.clone()), Some(problem_report))
+        }
+        _ => (sm, None),
+    }
+}
+
+/// Whether a received message answers the awaited message of the thread in time, and is to be
+/// handled even though the thread may be past its expiry by the time the message is polled.
+///
+/// The message is judged by the time we received it, or by its `~timing.out_time` if it was sent
+/// before then, as the agency may hold it for a while before it is polled. An `out_time` after we
+/// received the message can't be right and is ignored.
+pub fn answered_in_time<T: ExpiringThread>(
+    sm: &T,
+    message: &AriesMessage,
+    received_time: DateTime<Utc>,
+) -> VcxResult<bool> {
+    if !sm.answers_awaited(message) {
+        return Ok(false);
+    }
+    let Some(expires_time) = sm.awaited_expiry() else {
+        return Ok(true);
+    };
+
+    let sent_time = ProcessingDecorators::from_message(message)?
+        .timing
+        .and_then(|timing| timing.out_time)
+        .filter(|out_time| *out_time < received_time)
+        .unwrap_or(received_time);
+    Ok(sent_time < expires_time)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use chrono::Duration;
+    use messages::msg_fields::protocols::cred_issuance::issue_credential::{
+        IssueCredential, IssueCredentialContent, IssueCredentialDecorators,
+    };
+    use messages::msg_fields::protocols::cred_issuance::offer_credential::{
+        OfferCredential, OfferCredentialContent, OfferCredentialDecorators,
+    };
+    use messages::msg_fields::protocols::cred_issuance::request_credential::{
+        RequestCredential, RequestCredentialContent, RequestCredentialDecorators,
+    };
+    use messages::msg_fields::protocols::cred_issuance::CredentialPreview;
+
+    use crate::handlers::util::OfferInfo;
+    use crate::protocols::issuance::issuer::state_machine::{IssuerSM, IssuerState};
+
+    use super::*;
+
+    fn offer(please_ack: Option<PleaseAck>, timing: Option<Timing>) -> AriesMessage {
+        let mut decorators = OfferCredentialDecorators::default();
+        decorators.please_ack = please_ack;
+        decorators.timing = timing;
+        decorators.thread = Some(Thread::new("thread-1".to_owned()));
+
+        let content = OfferCredentialContent::new(CredentialPreview::new(vec![]), vec![]);
+        OfferCredential::with_decorators("msg-1".to_owned(), content, decorators).into()
+    }
+
+    fn timing(expires_time: Option<DateTime<Utc>>, wait_until_time: Option<DateTime<Utc>>) -> Option<Timing> {
+        let mut timing = Timing::default();
+        timing.expires_time = expires_time;
+        timing.wait_until_time = wait_until_time;
+        Some(timing)
+    }
+
+    #[test]
+    fn test_timing_status() {
+        let now = Utc::now();
+        let earlier = now - Duration::seconds(10);
+        let later = now + Duration::seconds(10);
+
+        assert_eq!(timing_status(None, now), TimingStatus::Current);
+        assert_eq!(
+            timing_status(timing(Some(later), None).as_ref(), now),
+            TimingStatus::Current
+        );
+        assert_eq!(
+            timing_status(timing(Some(earlier), None).as_ref(), now),
+            TimingStatus::Expired
+        );
+        assert_eq!(
+            timing_status(timing(None, Some(later)).as_ref(), now),
+            TimingStatus::Deferred(later)
+        );
+
+        let mut stale = Timing::default();
+        stale.stale_time = Some(earlier);
+        assert_eq!(timing_status(Some(&stale), now), TimingStatus::Stale);
+    }
+
+    #[test]
+    fn test_admit_message_with_acks() {
+        let please_ack = PleaseAck::new(vec![AckOn::Receipt, AckOn::Outcome]);
+        let message = offer(Some(please_ack), None);
+
+        let Admission::Accepted(acks) = admit_message(&message, Utc::now()).unwrap() else {
+            panic!("message should be accepted");
+        };
+        assert_eq!(acks.receipt.unwrap().decorators.thread.thid, "thread-1");
+        assert_eq!(acks.outcome.unwrap().decorators.thread.thid, "thread-1");
+
+        let admission = admit_message(&offer(None, None), Utc::now()).unwrap();
+        assert_eq!(admission, Admission::Accepted(RequestedAcks::default()));
+    }
+
+    #[test]
+    fn test_admit_message_acked_by_protocol() {
+        let mut decorators = IssueCredentialDecorators::new(Thread::new("thread-1".to_owned()));
+        decorators.please_ack = Some(PleaseAck::new(vec![AckOn::Outcome]));
+        let message: AriesMessage =
+            IssueCredential::with_decorators("msg-1".to_owned(), IssueCredentialContent::new(vec![]), decorators)
+                .into();
+
+        let admission = admit_message(&message, Utc::now()).unwrap();
+        assert_eq!(admission, Admission::Accepted(RequestedAcks::default()));
+    }
+
+    #[test]
+    fn test_admit_expired_message() {
+        let now = Utc::now();
+        let message = offer(
+            Some(PleaseAck::new(vec![AckOn::Receipt])),
+            timing(Some(now - Duration::seconds(1)), None),
+        );
+
+        let Admission::Rejected(problem_report) = admit_message(&message, now).unwrap() else {
+            panic!("message should be rejected");
+        };
+        assert_eq!(problem_report.content.description.code, EXPIRED_PROBLEM_CODE);
+        assert_eq!(problem_report.decorators.thread.unwrap().thid, "thread-1");
+
+        // The decorators are read serialized, with their times truncated to the millisecond.
+        let message = offer(None, timing(None, Some(now + Duration::seconds(1))));
+        let Admission::Deferred(wait_until_time) = admit_message(&message, now).unwrap() else {
+            panic!("message should be deferred");
+        };
+        assert!(wait_until_time > now);
+    }
+
+    #[test]
+    fn test_set_expiry() {
+        let expires_time = Utc::now();
+
+        let mut timing = None;
+        set_expiry(&mut timing, expires_time);
+        assert_eq!(timing.unwrap().expires_time, Some(expires_time));
+    }
+
+    fn issuer_offer_sent(expires_time: DateTime<Utc>) -> IssuerSM {
+        let offer_info = OfferInfo::new("{}".to_owned(), "cred_def_id".to_owned(), None, None);
+
+        IssuerSM::new("source")
+            .build_credential_offer_msg("{}", CredentialPreview::new(vec![]), None, &offer_info)
+            .unwrap()
+            .set_offer_expiry(expires_time)
+            .unwrap()
+            .mark_credential_offer_msg_sent()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_time_out() {
+        let now = Utc::now();
+
+        let (issuer_sm, problem_report) = time_out(issuer_offer_sent(now + Duration::seconds(1)), now);
+        assert!(problem_report.is_none());
+        assert_eq!(issuer_sm.get_state(), IssuerState::OfferSent);
+
+        let issuer_sm = issuer_offer_sent(now);
+        let thread_id = issuer_sm.thread_id().unwrap();
+        let (issuer_sm, problem_report) = time_out(issuer_sm, now);
+        let problem_report = problem_report.unwrap();
+        assert_eq!(problem_report.content.impact, Some(Impact::Thread));
+        assert_eq!(problem_report.decorators.thread.unwrap().thid, thread_id);
+        assert_eq!(issuer_sm.get_state(), IssuerState::Failed);
+    }
+
+    fn request(thread_id: &str, out_time: Option<DateTime<Utc>>) -> RequestCredential {
+        let mut decorators = RequestCredentialDecorators::default();
+        decorators.thread = Some(Thread::new(thread_id.to_owned()));
+        decorators.timing = out_time.map(|out_time| {
+            let mut timing = Timing::default();
+            timing.out_time = Some(out_time);
+            timing
+        });
+
+        RequestCredential::with_decorators("msg-1".to_owned(), RequestCredentialContent::new(vec![]), decorators)
+    }
+
+    #[test]
+    fn test_answered_before_expiry_polled_after() {
+        let now = Utc::now();
+        let issuer_sm = issuer_offer_sent(now - Duration::seconds(10));
+        let thread_id = issuer_sm.thread_id().unwrap();
+
+        let late = request(&thread_id, Some(now - Duration::seconds(5)));
+        assert!(!answered_in_time(&issuer_sm, &late.into(), now).unwrap());
+
+        let untimed = request(&thread_id, None);
+        assert!(!answered_in_time(&issuer_sm, &untimed.clone().into(), now).unwrap());
+        let received_time = now - Duration::seconds(20);
+        assert!(answered_in_time(&issuer_sm, &untimed.into(), received_time).unwrap());
+
+        let sent_after_received = request(&thread_id, Some(now));
+        assert!(answered_in_time(&issuer_sm, &sent_after_received.into(), received_time).unwrap());
+
+        let in_time = request(&thread_id, Some(now - Duration::seconds(20)));
+        assert!(answered_in_time(&issuer_sm, &in_time.clone().into(), now).unwrap());
+        let issuer_sm = issuer_sm.receive_request(in_time).unwrap();
+        assert_eq!(issuer_sm.get_state(), IssuerState::RequestReceived);
+
+        let (issuer_sm, problem_report) = time_out(issuer_sm, now);
+        assert!(problem_report.is_none());
+        assert_eq!(issuer_sm.get_state(), IssuerState::RequestReceived);
+    }
+
+    #[test]
+    fn test_only_answer_stops_time_out() {
+        let now = Utc::now();
+        let issuer_sm = issuer_offer_sent(now + Duration::seconds(10));
+        let thread_id = issuer_sm.thread_id().unwrap();
+
+        let mut decorators = ProblemReportDecorators::default();
+        decorators.thread = Some(Thread::new(thread_id.clone()));
+        let problem_report: AriesMessage = ProblemReport::with_decorators(
+            "msg-1".to_owned(),
+            ProblemReportContent::new("code".to_owned()),
+            decorators,
+        )
+        .into();
+        assert!(!answered_in_time(&issuer_sm, &problem_report, now).unwrap());
+        assert!(answered_in_time(&issuer_sm, &request(&thread_id, None).into(), now).unwrap());
+    }
+
+    #[test]
+    fn test_without_deferred() {
+        let now = Utc::now();
+        let messages = HashMap::from([
+            (
+                "deferred".to_owned(),
+                offer(None, timing(None, Some(now + Duration::seconds(10)))),
+            ),
+            ("current".to_owned(), offer(None, None)),
+        ]);
+
+        let messages = without_deferred(messages, now);
+        assert_eq!(messages.keys().collect::<Vec<_>>(), vec!["current"]);
+    }
+}
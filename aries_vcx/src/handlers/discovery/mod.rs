@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::errors::error::VcxResult;
+use crate::utils::didcomm_v2::DIDCOMM_V2_PROFILE;
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use chrono::Utc;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
@@ -8,11 +9,212 @@ use messages::decorators::thread::Thread;
 use messages::decorators::timing::Timing;
 use messages::msg_fields::protocols::discover_features::disclose::{Disclose, DiscloseContent, DiscloseDecorators};
 use messages::msg_fields::protocols::discover_features::query::{Query, QueryContent, QueryDecorators};
-use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
+use messages::msg_fields::protocols::discover_features::{lookup_protocols, ProtocolDescriptor};
+use messages::msg_fields::protocols::discover_features_v2::disclosures::{
+    Disclosure, Disclosures, DisclosuresContent, DisclosuresDecorators,
+};
+use messages::msg_fields::protocols::discover_features_v2::queries::{
+    FeatureQuery, Queries, QueriesContent, QueriesDecorators,
+};
+use messages::msg_fields::protocols::discover_features_v2::FeatureType;
+use messages::msg_types::protocols::basic_message::BasicMessageTypeV1;
+use messages::msg_types::protocols::connection::ConnectionTypeV1;
+use messages::msg_types::protocols::coordinate_mediation::CoordinateMediationTypeV1;
+use messages::msg_types::protocols::cred_issuance::{CredentialIssuanceTypeV1, CredentialIssuanceTypeV2};
+use messages::msg_types::protocols::did_exchange::DidExchangeTypeV1;
+use messages::msg_types::protocols::discover_features::{DiscoverFeaturesTypeV1, DiscoverFeaturesTypeV2};
+use messages::msg_types::protocols::notification::NotificationTypeV1;
+use messages::msg_types::protocols::out_of_band::OutOfBandTypeV1;
+use messages::msg_types::protocols::pickup::PickupTypeV2;
+use messages::msg_types::protocols::present_proof::{PresentProofTypeV1, PresentProofTypeV2};
+use messages::msg_types::protocols::report_problem::ReportProblemTypeV1;
+use messages::msg_types::protocols::revocation::RevocationTypeV2;
+use messages::msg_types::protocols::trust_ping::TrustPingTypeV1;
+use messages::msg_types::{Protocol, Role};
+use shared_vcx::maybe_known::MaybeKnown;
 use uuid::Uuid;
 
 use crate::utils::send_message;
 
+/// The DIDComm versions disclosed by default, named as the profiles of the `accept` list of a service.
+pub const SUPPORTED_DIDCOMM_VERSIONS: [&str; 3] = ["didcomm/aip1", "didcomm/aip2;env=rfc19", DIDCOMM_V2_PROFILE];
+
+/// The protocols handled over a connection by the handlers of this crate, with the roles they
+/// play in them.
+///
+/// The mediator roles and the transactions protocol are left out, their handlers being run apart
+/// from connections; agents running them register them with [`SupportedFeatures::with_handler`].
+pub fn connection_handlers() -> Vec<(Protocol, Vec<Role>)> {
+    vec![
+        (
+            BasicMessageTypeV1::new_v1_0().into(),
+            vec![Role::Receiver, Role::Sender],
+        ),
+        (ConnectionTypeV1::new_v1_0().into(), vec![Role::Inviter, Role::Invitee]),
+        (CoordinateMediationTypeV1::new_v1_0().into(), vec![Role::Recipient]),
+        (
+            CredentialIssuanceTypeV1::new_v1_0().into(),
+            vec![Role::Holder, Role::Issuer],
+        ),
+        (
+            CredentialIssuanceTypeV2::new_v2_0().into(),
+            vec![Role::Holder, Role::Issuer],
+        ),
+        (
+            DidExchangeTypeV1::new_v1_0().into(),
+            vec![Role::Requester, Role::Responder],
+        ),
+        (
+            DiscoverFeaturesTypeV1::new_v1_0().into(),
+            vec![Role::Requester, Role::Responder],
+        ),
+        (
+            DiscoverFeaturesTypeV2::new_v2_0().into(),
+            vec![Role::Requester, Role::Responder],
+        ),
+        (
+            NotificationTypeV1::new_v1_0().into(),
+            vec![Role::Notified, Role::Notifier],
+        ),
+        (OutOfBandTypeV1::new_v1_1().into(), vec![Role::Receiver, Role::Sender]),
+        (PickupTypeV2::new_v2_0().into(), vec![Role::Recipient]),
+        (
+            PresentProofTypeV1::new_v1_0().into(),
+            vec![Role::Prover, Role::Verifier],
+        ),
+        (
+            PresentProofTypeV2::new_v2_0().into(),
+            vec![Role::Prover, Role::Verifier],
+        ),
+        (
+            ReportProblemTypeV1::new_v1_0().into(),
+            vec![Role::Notified, Role::Notifier],
+        ),
+        (RevocationTypeV2::new_v2_0().into(), vec![Role::Holder, Role::Issuer]),
+        (TrustPingTypeV1::new_v1_0().into(), vec![Role::Receiver, Role::Sender]),
+    ]
+}
+
+/// The features disclosed to peers querying them.
+///
+/// Protocols are looked up in the protocol registry of the `messages` crate and disclosed as long
+/// as they have a handler, in the roles the handler plays. Goal codes and DIDComm versions are
+/// disclosed as configured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupportedFeatures {
+    /// The protocols with a handler, and the roles played in them.
+    pub handlers: Vec<(Protocol, Vec<Role>)>,
+    /// The roles played in the protocols, or `None` to disclose all of them.
+    pub roles: Option<Vec<Role>>,
+    pub goal_codes: Vec<String>,
+    pub didcomm_versions: Vec<String>,
+}
+
+impl Default for SupportedFeatures {
+    fn default() -> Self {
+        Self {
+            handlers: connection_handlers(),
+            roles: None,
+            goal_codes: Vec::new(),
+            didcomm_versions: SUPPORTED_DIDCOMM_VERSIONS.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+}
+
+impl SupportedFeatures {
+    /// Registers a handler playing the roles in the protocol.
+    pub fn with_handler(mut self, protocol: Protocol, roles: Vec<Role>) -> Self {
+        match self.handlers.iter_mut().find(|(handled, _)| *handled == protocol) {
+            Some((_, handled_roles)) => handled_roles.extend(roles),
+            None => self.handlers.push((protocol, roles)),
+        }
+        self
+    }
+
+    pub fn with_roles(mut self, roles: Vec<Role>) -> Self {
+        self.roles = Some(roles);
+        self
+    }
+
+    pub fn with_goal_codes(mut self, goal_codes: Vec<String>) -> Self {
+        self.goal_codes = goal_codes;
+        self
+    }
+
+    pub fn with_didcomm_versions(mut self, didcomm_versions: Vec<String>) -> Self {
+        self.didcomm_versions = didcomm_versions;
+        self
+    }
+
+    /// The protocols matching a `discover-features/1.0` query.
+    pub fn protocols(&self, query: &str) -> Vec<ProtocolDescriptor> {
+        lookup_protocols(query, self.roles.as_deref())
+            .into_iter()
+            .filter_map(|mut pd| {
+                let handled_roles = self.handled_roles(&pd.pid)?;
+                let roles: Vec<_> = pd
+                    .roles
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|role| matches!(role, MaybeKnown::Known(role) if handled_roles.contains(role)))
+                    .collect();
+
+                if roles.is_empty() {
+                    return None;
+                }
+                pd.roles = Some(roles);
+                Some(pd)
+            })
+            .collect()
+    }
+
+    fn handled_roles(&self, pid: &MaybeKnown<Protocol>) -> Option<&[Role]> {
+        let MaybeKnown::Known(protocol) = pid else {
+            return None;
+        };
+        self.handlers
+            .iter()
+            .find(|(handled, _)| handled == protocol)
+            .map(|(_, roles)| roles.as_slice())
+    }
+
+    /// The features matching any of the `discover-features/2.0` queries, each disclosed once.
+    /// Queries for feature types that are not known are ignored.
+    pub fn disclosures(&self, queries: &[FeatureQuery]) -> Vec<Disclosure> {
+        let mut disclosures: Vec<Disclosure> = Vec::new();
+        let mut disclose = |disclosure: Disclosure| {
+            if !disclosures
+                .iter()
+                .any(|d| d.feature_type == disclosure.feature_type && d.id == disclosure.id)
+            {
+                disclosures.push(disclosure);
+            }
+        };
+
+        for query in queries {
+            if query.feature_type == MaybeKnown::Known(FeatureType::Protocol) {
+                self.protocols(&query.pattern)
+                    .into_iter()
+                    .for_each(|pd| disclose(pd.into()));
+            }
+
+            let configured = self
+                .goal_codes
+                .iter()
+                .map(|id| (FeatureType::GoalCode, id))
+                .chain(self.didcomm_versions.iter().map(|id| (FeatureType::DidcommVersion, id)));
+
+            for (feature_type, id) in configured {
+                if query.matches(feature_type, id) {
+                    disclose(Disclosure::new(feature_type, id.to_owned()));
+                }
+            }
+        }
+
+        disclosures
+    }
+}
+
 pub async fn send_discovery_query(
     wallet: &Arc<dyn BaseWallet>,
     query: Option<String>,
@@ -39,9 +241,11 @@ pub async fn respond_discovery_query(
     query: Query,
     did_doc: &AriesDidDoc,
     pw_vk: &str,
-    _supported_protocols: Vec<ProtocolDescriptor>,
+    supported_protocols: Vec<ProtocolDescriptor>,
 ) -> VcxResult<()> {
-    let content = DiscloseContent::default();
+    let content = DiscloseContent {
+        protocols: supported_protocols,
+    };
 
     let mut decorators = DiscloseDecorators::new(Thread::new(query.id));
     let mut timing = Timing::default();
@@ -52,3 +256,121 @@ pub async fn respond_discovery_query(
 
     send_message(Arc::clone(wallet), pw_vk.to_string(), did_doc.clone(), disclose.into()).await
 }
+
+pub async fn send_discovery_queries(
+    wallet: &Arc<dyn BaseWallet>,
+    queries: Vec<FeatureQuery>,
+    did_doc: &AriesDidDoc,
+    pw_vk: &str,
+) -> VcxResult<()> {
+    let content = QueriesContent::new(queries);
+
+    let mut decorators = QueriesDecorators::default();
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    let queries = Queries::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+    send_message(Arc::clone(wallet), pw_vk.to_string(), did_doc.clone(), queries.into()).await
+}
+
+pub async fn respond_discovery_queries(
+    wallet: &Arc<dyn BaseWallet>,
+    queries: Queries,
+    did_doc: &AriesDidDoc,
+    pw_vk: &str,
+    supported_features: &SupportedFeatures,
+) -> VcxResult<()> {
+    let content = DisclosuresContent::new(supported_features.disclosures(&queries.content.queries));
+
+    let mut decorators = DisclosuresDecorators::default();
+    decorators.thread = Some(Thread::new(queries.id));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    let disclosures = Disclosures::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+    send_message(
+        Arc::clone(wallet),
+        pw_vk.to_string(),
+        did_doc.clone(),
+        disclosures.into(),
+    )
+    .await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::msg_types::protocols::transactions::TransactionsTypeV1;
+
+    use super::*;
+
+    fn ids(disclosures: &[Disclosure], feature_type: FeatureType) -> Vec<&str> {
+        disclosures
+            .iter()
+            .filter(|d| d.feature_type == MaybeKnown::Known(feature_type))
+            .map(|d| d.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_disclose_protocols_for_roles() {
+        let features = SupportedFeatures::default().with_roles(vec![Role::Invitee]);
+        let queries = vec![FeatureQuery::new(FeatureType::Protocol, "*".to_owned())];
+
+        let disclosures = features.disclosures(&queries);
+
+        let connections = Protocol::from(ConnectionTypeV1::new_v1_0()).to_string();
+        assert_eq!(ids(&disclosures, FeatureType::Protocol), vec![connections.as_str()]);
+        assert_eq!(disclosures[0].roles, Some(vec![MaybeKnown::Known(Role::Invitee)]));
+        assert_eq!(features.protocols("https://didcomm.org/issue-credential/*"), vec![]);
+    }
+
+    #[test]
+    fn test_disclose_configured_features() {
+        let features = SupportedFeatures::default().with_goal_codes(vec!["aries.vc.issue".to_owned()]);
+        let queries = vec![
+            FeatureQuery::new(FeatureType::GoalCode, "aries.*".to_owned()),
+            FeatureQuery::new(FeatureType::DidcommVersion, "didcomm/aip2*".to_owned()),
+            FeatureQuery::new(FeatureType::DidcommVersion, "*".to_owned()),
+            FeatureQuery::new(FeatureType::Protocol, "https://didcomm.org/non-existent/*".to_owned()),
+        ];
+
+        let disclosures = features.disclosures(&queries);
+
+        assert_eq!(ids(&disclosures, FeatureType::GoalCode), vec!["aries.vc.issue"]);
+        assert_eq!(
+            ids(&disclosures, FeatureType::DidcommVersion),
+            vec!["didcomm/aip2;env=rfc19", "didcomm/aip1", DIDCOMM_V2_PROFILE]
+        );
+        assert!(ids(&disclosures, FeatureType::Protocol).is_empty());
+    }
+
+    #[test]
+    fn test_disclose_handled_protocols() {
+        let mediation = Protocol::from(CoordinateMediationTypeV1::new_v1_0());
+        let transactions = Protocol::from(TransactionsTypeV1::new_v1_0());
+        let features = SupportedFeatures::default();
+        let disclosed = features.protocols(&mediation.to_string());
+        assert_eq!(disclosed.len(), 1);
+        assert_eq!(disclosed[0].roles, Some(vec![MaybeKnown::Known(Role::Recipient)]));
+        assert!(features.protocols(&transactions.to_string()).is_empty());
+
+        let features = features
+            .with_handler(mediation.clone(), vec![Role::Mediator])
+            .with_handler(transactions.clone(), vec![Role::Endorser]);
+        let disclosed = features.protocols(&mediation.to_string());
+        assert_eq!(
+            disclosed[0].roles,
+            Some(vec![
+                MaybeKnown::Known(Role::Mediator),
+                MaybeKnown::Known(Role::Recipient)
+            ])
+        );
+        let disclosed = features.protocols(&transactions.to_string());
+        assert_eq!(disclosed[0].roles, Some(vec![MaybeKnown::Known(Role::Endorser)]));
+    }
+}
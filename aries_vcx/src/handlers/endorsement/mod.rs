@@ -0,0 +1,483 @@
+use std::sync::Arc;
+
+use messages::msg_fields::protocols::transactions::{endorse::Endorse, refuse::Refuse, request::Request};
+use serde_json::Value;
+
+use super::util::{get_attach_as_string, matches_thread_id};
+use crate::{
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::{
+        transactions::{build_endorse, build_refuse, build_transaction_request},
+        SendClosure,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TransactionAuthorState {
+    Initial,
+    Requested {
+        thread_id: String,
+        transaction: String,
+        endorser_write_txn: bool,
+    },
+    Written {
+        endorser_did: String,
+    },
+    Refused {
+        endorser_did: String,
+        explanation: Option<String>,
+    },
+}
+
+/// Author side of the `vcx-transactions` protocol, through which an author without the right to
+/// write to the ledger has its transactions endorsed by a peer running aries-vcx.
+///
+/// The transaction sent is the request prepared for endorsement by one of the
+/// `prepare_*_for_endorsement` methods of [`aries_vcx_core::ledger::base_ledger::AnoncredsLedgerWrite`],
+/// naming the DID of the endorser. The endorser either writes it, or returns it endorsed for the
+/// author to write.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionAuthor {
+    state: TransactionAuthorState,
+}
+
+impl Default for TransactionAuthor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionAuthor {
+    pub fn new() -> Self {
+        Self {
+            state: TransactionAuthorState::Initial,
+        }
+    }
+
+    pub fn get_state(&self) -> &TransactionAuthorState {
+        &self.state
+    }
+
+    pub fn is_written(&self) -> bool {
+        matches!(self.state, TransactionAuthorState::Written { .. })
+    }
+
+    pub async fn send_transaction_request(
+        &mut self,
+        transaction: &str,
+        endorser_write_txn: bool,
+        comment: Option<String>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        if !matches!(
+            self.state,
+            TransactionAuthorState::Initial | TransactionAuthorState::Refused { .. }
+        ) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                format!("Cannot request endorsement in state {:?}", self.state),
+            ));
+        }
+
+        let request = build_transaction_request(transaction, endorser_write_txn, comment);
+        let thread_id = request.id.clone();
+
+        send_message(request.into()).await?;
+        self.state = TransactionAuthorState::Requested {
+            thread_id,
+            transaction: transaction.to_owned(),
+            endorser_write_txn,
+        };
+        Ok(())
+    }
+
+    /// Handles the answer of an endorser which endorsed the transaction, writing the endorsed
+    /// transaction unless the endorser already wrote it. The endorsed transaction must be the
+    /// requested one, only signed by the endorser on top.
+    pub async fn handle_endorse(&mut self, profile: &Arc<dyn Profile>, endorse: Endorse) -> VcxResult<()> {
+        let (thread_id, requested_transaction, endorser_write_txn) = self.requested()?;
+
+        if !matches_thread_id!(endorse, thread_id) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot handle endorse: thread id does not match, expected {thread_id:?}"),
+            ));
+        }
+
+        let endorser = transaction_endorser(requested_transaction)?;
+        if endorse.content.endorser_did != endorser {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle endorse: endorsed by {}, while the transaction names {} as its endorser",
+                    endorse.content.endorser_did, endorser
+                ),
+            ));
+        }
+
+        if endorser_write_txn {
+            if !endorse.content.messages_attach.is_empty() {
+                warn!("Ignoring the transaction attached to endorse, the endorser was to write it");
+            }
+        } else {
+            if endorse.content.messages_attach.is_empty() {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    "Cannot handle endorse: the endorsed transaction is missing",
+                ));
+            }
+            let transaction = get_attach_as_string!(&endorse.content.messages_attach);
+            if signed_payload(&transaction)? != signed_payload(requested_transaction)? {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    "Cannot handle endorse: the endorsed transaction differs from the requested one",
+                ));
+            }
+            let ledger = Arc::clone(profile).inject_indy_ledger_write();
+            ledger.submit_endorsed_transaction(&transaction).await?;
+        }
+
+        self.state = TransactionAuthorState::Written {
+            endorser_did: endorse.content.endorser_did,
+        };
+        Ok(())
+    }
+
+    pub fn handle_refuse(&mut self, refuse: Refuse) -> VcxResult<()> {
+        let (thread_id, _, _) = self.requested()?;
+
+        if !matches_thread_id!(refuse, thread_id) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot handle refuse: thread id does not match, expected {thread_id:?}"),
+            ));
+        }
+
+        self.state = TransactionAuthorState::Refused {
+            endorser_did: refuse.content.endorser_did,
+            explanation: refuse.content.explanation,
+        };
+        Ok(())
+    }
+
+    fn requested(&self) -> VcxResult<(&str, &str, bool)> {
+        match &self.state {
+            TransactionAuthorState::Requested {
+                thread_id,
+                transaction,
+                endorser_write_txn,
+            } => Ok((thread_id, transaction, *endorser_write_txn)),
+            state => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Endorsement was not requested, current state: {state:?}"),
+            )),
+        }
+    }
+}
+
+/// The DID of the endorser a ledger request was prepared for.
+fn transaction_endorser(transaction: &str) -> VcxResult<String> {
+    let transaction: Value = serde_json::from_str(transaction)?;
+    transaction["endorser"].as_str().map(ToOwned::to_owned).ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "The transaction was not prepared for endorsement, it names no endorser",
+        )
+    })
+}
+
+/// The part of a ledger request its signatures are over, which the endorser must leave as is.
+fn signed_payload(transaction: &str) -> VcxResult<Value> {
+    let mut payload: Value = serde_json::from_str(transaction)?;
+    if let Some(payload) = payload.as_object_mut() {
+        payload.remove("signature");
+        payload.remove("signatures");
+    }
+    Ok(payload)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TransactionEndorserState {
+    RequestReceived,
+    Endorsed,
+    Refused,
+}
+
+/// Endorser side of the transactions protocol, endorsing the transactions of authors which may not
+/// write them to the ledger themselves.
+///
+/// Whether to endorse the transaction of a request is up to the endorser, which can inspect it
+/// through [`TransactionEndorser::get_transaction`] before endorsing or refusing it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionEndorser {
+    state: TransactionEndorserState,
+    thread_id: String,
+    endorser_did: String,
+    transaction: String,
+    endorser_write_txn: bool,
+}
+
+impl TransactionEndorser {
+    pub fn from_request(request: Request, endorser_did: String) -> VcxResult<Self> {
+        let transaction = get_attach_as_string!(&request.content.messages_attach);
+        let thread_id = request
+            .decorators
+            .thread
+            .map(|thread| thread.thid)
+            .unwrap_or(request.id);
+
+        Ok(Self {
+            state: TransactionEndorserState::RequestReceived,
+            thread_id,
+            endorser_did,
+            transaction,
+            endorser_write_txn: request.content.endorser_write_txn,
+        })
+    }
+
+    pub fn get_state(&self) -> &TransactionEndorserState {
+        &self.state
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// The transaction to endorse, as the JSON of the ledger request.
+    pub fn get_transaction(&self) -> &str {
+        &self.transaction
+    }
+
+    /// Whether the author asked for the transaction to be written by the endorser.
+    pub fn endorser_write_txn(&self) -> bool {
+        self.endorser_write_txn
+    }
+
+    /// Endorses the transaction, writing it when the author asked for it and returning it
+    /// endorsed to the author otherwise.
+    pub async fn endorse(&mut self, profile: &Arc<dyn Profile>, send_message: SendClosure) -> VcxResult<()> {
+        self.ensure_request_received()?;
+
+        let ledger = Arc::clone(profile).inject_indy_ledger_write();
+        let endorse = if self.endorser_write_txn {
+            ledger
+                .endorse_transaction(&self.endorser_did, &self.transaction)
+                .await?;
+            build_endorse(self.thread_id.clone(), self.endorser_did.clone(), None)
+        } else {
+            let endorsed = ledger
+                .sign_endorsed_transaction(&self.endorser_did, &self.transaction)
+                .await?;
+            build_endorse(self.thread_id.clone(), self.endorser_did.clone(), Some(&endorsed))
+        };
+
+        send_message(endorse.into()).await?;
+        self.state = TransactionEndorserState::Endorsed;
+        Ok(())
+    }
+
+    pub async fn refuse(&mut self, explanation: Option<String>, send_message: SendClosure) -> VcxResult<()> {
+        self.ensure_request_received()?;
+
+        let refuse = build_refuse(self.thread_id.clone(), self.endorser_did.clone(), explanation);
+
+        send_message(refuse.into()).await?;
+        self.state = TransactionEndorserState::Refused;
+        Ok(())
+    }
+
+    fn ensure_request_received(&self) -> VcxResult<()> {
+        if self.state != TransactionEndorserState::RequestReceived {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Transaction was already answered, current state: {:?}", self.state),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Mutex;
+
+    use messages::{msg_fields::protocols::transactions::Transactions, AriesMessage};
+
+    use super::*;
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    const TRANSACTION: &str = r#"{"identifier":"VsKV7grR1BUE29mG2Fm2kX","endorser":"V4SGRU86Z58d6TV7PBUe6f"}"#;
+    const ENDORSER_DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+
+    type Sent = Arc<Mutex<Vec<AriesMessage>>>;
+
+    fn _send_message(sent: &Sent) -> SendClosure {
+        let sent = sent.clone();
+        Box::new(move |message: AriesMessage| {
+            Box::pin(async move {
+                sent.lock().unwrap().push(message);
+                VcxResult::Ok(())
+            })
+        })
+    }
+
+    fn _profile() -> Arc<dyn Profile> {
+        Arc::new(MockProfile)
+    }
+
+    async fn _requested(endorser_write_txn: bool) -> (TransactionAuthor, TransactionEndorser) {
+        let sent = Sent::default();
+        let mut author = TransactionAuthor::new();
+        author
+            .send_transaction_request(TRANSACTION, endorser_write_txn, None, _send_message(&sent))
+            .await
+            .unwrap();
+
+        let Some(AriesMessage::Transactions(Transactions::Request(request))) = sent.lock().unwrap().pop() else {
+            panic!("transaction request was not sent");
+        };
+        let endorser = TransactionEndorser::from_request(request, ENDORSER_DID.to_owned()).unwrap();
+        (author, endorser)
+    }
+
+    async fn _endorse(endorser: &mut TransactionEndorser) -> Endorse {
+        let sent = Sent::default();
+        endorser.endorse(&_profile(), _send_message(&sent)).await.unwrap();
+
+        let Some(AriesMessage::Transactions(Transactions::Endorse(endorse))) = sent.lock().unwrap().pop() else {
+            panic!("endorse was not sent");
+        };
+        endorse
+    }
+
+    #[tokio::test]
+    async fn test_endorsed_transaction_returned_to_author() {
+        let (mut author, mut endorser) = _requested(false).await;
+        assert_eq!(endorser.get_transaction(), TRANSACTION);
+        assert!(!endorser.endorser_write_txn());
+
+        let endorse = _endorse(&mut endorser).await;
+        assert_eq!(endorse.content.messages_attach.len(), 1);
+        assert_eq!(endorser.get_state(), &TransactionEndorserState::Endorsed);
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap();
+        assert_eq!(
+            author.get_state(),
+            &TransactionAuthorState::Written {
+                endorser_did: ENDORSER_DID.to_owned()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_written_by_endorser() {
+        let (mut author, mut endorser) = _requested(true).await;
+
+        let endorse = _endorse(&mut endorser).await;
+        assert!(endorse.content.messages_attach.is_empty());
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap();
+        assert!(author.is_written());
+    }
+
+    #[tokio::test]
+    async fn test_endorsed_transaction_missing() {
+        let (mut author, mut endorser) = _requested(false).await;
+
+        let mut endorse = _endorse(&mut endorser).await;
+        endorse.content.messages_attach.clear();
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap_err();
+        assert!(!author.is_written());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_refused() {
+        let (mut author, mut endorser) = _requested(true).await;
+
+        let sent = Sent::default();
+        endorser
+            .refuse(Some("unknown author".to_owned()), _send_message(&sent))
+            .await
+            .unwrap();
+        endorser.endorse(&_profile(), _send_message(&sent)).await.unwrap_err();
+
+        let Some(AriesMessage::Transactions(Transactions::Refuse(refuse))) = sent.lock().unwrap().pop() else {
+            panic!("refuse was not sent");
+        };
+        author.handle_refuse(refuse).unwrap();
+        assert_eq!(
+            author.get_state(),
+            &TransactionAuthorState::Refused {
+                endorser_did: ENDORSER_DID.to_owned(),
+                explanation: Some("unknown author".to_owned())
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_endorse_thread_id_mismatch() {
+        let (mut author, _) = _requested(true).await;
+
+        let endorse = build_endorse("other_thread".to_owned(), ENDORSER_DID.to_owned(), None);
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap_err();
+        assert!(matches!(author.get_state(), TransactionAuthorState::Requested { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_endorse_by_other_endorser() {
+        let (mut author, endorser) = _requested(true).await;
+
+        let endorse = build_endorse(
+            endorser.get_thread_id().to_owned(),
+            "VsKV7grR1BUE29mG2Fm2kX".to_owned(),
+            None,
+        );
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap_err();
+        assert!(matches!(author.get_state(), TransactionAuthorState::Requested { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_endorsed_transaction_tampered() {
+        let (mut author, endorser) = _requested(false).await;
+
+        let tampered = r#"{"identifier":"VsKV7grR1BUE29mG2Fm2kX","endorser":"V4SGRU86Z58d6TV7PBUe6f","operation":{}}"#;
+        let endorse = build_endorse(
+            endorser.get_thread_id().to_owned(),
+            ENDORSER_DID.to_owned(),
+            Some(tampered),
+        );
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap_err();
+        assert!(!author.is_written());
+
+        let signed = r#"{"identifier":"VsKV7grR1BUE29mG2Fm2kX","endorser":"V4SGRU86Z58d6TV7PBUe6f","signatures":{}}"#;
+        let endorse = build_endorse(
+            endorser.get_thread_id().to_owned(),
+            ENDORSER_DID.to_owned(),
+            Some(signed),
+        );
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap();
+        assert!(author.is_written());
+    }
+
+    #[tokio::test]
+    async fn test_attachment_ignored_when_written_by_endorser() {
+        let (mut author, endorser) = _requested(true).await;
+
+        let endorse = build_endorse(
+            endorser.get_thread_id().to_owned(),
+            ENDORSER_DID.to_owned(),
+            Some(r#"{"operation":{}}"#),
+        );
+
+        author.handle_endorse(&_profile(), endorse).await.unwrap();
+        assert!(author.is_written());
+    }
+}
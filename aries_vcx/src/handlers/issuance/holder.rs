@@ -8,11 +8,13 @@ use messages::AriesMessage;
 use std::sync::Arc;
 
 use agency_client::agency_client::AgencyClient;
+use chrono::Utc;
 
 use crate::common::credentials::get_cred_rev_id;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::handlers::decorators::{handle_connection_message, without_deferred};
 use crate::handlers::revocation_notification::receiver::RevocationNotificationReceiver;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
 use crate::protocols::issuance::holder::state_machine::{HolderSM, HolderState};
@@ -210,9 +212,14 @@ impl Holder {
         let send_message = connection.send_message_closure(profile).await?;
 
         let messages = connection.get_messages(agency_client).await?;
-        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
-            self.step(profile, msg.into(), Some(send_message)).await?;
-            connection.update_message_status(&uid, agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(without_deferred(messages, Utc::now())) {
+            let handled = handle_connection_message(profile, connection, msg, |msg| {
+                self.step(profile, msg.into(), Some(send_message))
+            })
+            .await?;
+            if handled {
+                connection.update_message_status(&uid, agency_client).await?;
+            }
         }
         Ok(self.get_state())
     }
@@ -6,14 +6,17 @@ use messages::msg_fields::protocols::cred_issuance::ack::AckCredential;
 use messages::msg_fields::protocols::cred_issuance::propose_credential::ProposeCredential;
 use messages::msg_fields::protocols::cred_issuance::request_credential::RequestCredential;
 use messages::msg_fields::protocols::cred_issuance::{CredentialAttr, CredentialPreview};
+use messages::msg_fields::protocols::report_problem::ProblemReport;
 use messages::AriesMessage;
 use std::sync::Arc;
 
 use agency_client::agency_client::AgencyClient;
+use chrono::{DateTime, Utc};
 
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::handlers::decorators::{answered_in_time, handle_connection_message, time_out, without_deferred};
 use crate::handlers::revocation_notification::sender::RevocationNotificationSender;
 use crate::handlers::util::OfferInfo;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
@@ -156,6 +159,11 @@ impl Issuer {
         Ok(())
     }
 
+    pub fn set_offer_expiry(&mut self, expires_time: DateTime<Utc>) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().set_offer_expiry(expires_time)?;
+        Ok(())
+    }
+
     pub async fn send_credential_offer(&mut self, send_message: SendClosure) -> VcxResult<()> {
         self.issuer_sm = self.issuer_sm.clone().send_credential_offer(send_message).await?;
         Ok(())
@@ -301,6 +309,14 @@ impl Issuer {
         Ok(())
     }
 
+    /// Finishes the thread if the offer expired before a request was received, returning the problem
+    /// report to send to the holder.
+    fn time_out(&mut self) -> Option<ProblemReport> {
+        let (issuer_sm, problem_report) = time_out(self.issuer_sm.clone(), Utc::now());
+        self.issuer_sm = issuer_sm;
+        problem_report
+    }
+
     pub async fn update_state(
         &mut self,
         profile: &Arc<dyn Profile>,
@@ -313,9 +329,30 @@ impl Issuer {
         }
         let send_message = connection.send_message_closure(profile).await?;
         let messages = connection.get_messages(agency_client).await?;
-        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
-            self.step(profile, msg.into(), Some(send_message)).await?;
-            connection.update_message_status(&uid, agency_client).await?;
+        let received_time = Utc::now();
+        let message = self.find_message_to_handle(without_deferred(messages, received_time));
+        // an answer sent before the expiry is handled even if polled after it
+        let answered = match &message {
+            Some((_, msg)) => answered_in_time(&self.issuer_sm, msg, received_time)?,
+            None => false,
+        };
+        if !answered {
+            if let Some(problem_report) = self.time_out() {
+                send_message(problem_report.into()).await?;
+                if let Some((uid, _)) = &message {
+                    connection.update_message_status(uid, agency_client).await?;
+                }
+                return Ok(self.get_state());
+            }
+        }
+        if let Some((uid, msg)) = message {
+            let handled = handle_connection_message(profile, connection, msg, |msg| {
+                self.step(profile, msg.into(), Some(send_message))
+            })
+            .await?;
+            if handled {
+                connection.update_message_status(&uid, agency_client).await?;
+            }
         }
         Ok(self.get_state())
     }
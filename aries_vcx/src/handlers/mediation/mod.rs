@@ -0,0 +1,401 @@
+use messages::msg_fields::protocols::coordinate_mediation::{
+    keylist::Keylist,
+    keylist_query::KeylistQueryPaginate,
+    keylist_update::{KeylistUpdateAction, KeylistUpdateItem},
+    keylist_update_response::{KeylistUpdateResponse, KeylistUpdateResponseItem, KeylistUpdateResult},
+    mediate_deny::MediateDeny,
+    mediate_grant::MediateGrant,
+};
+use url::Url;
+
+use super::util::matches_thread_id;
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::{
+        coordinate_mediation::{build_keylist_query, build_keylist_update, build_mediate_request},
+        SendClosure,
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MediationState {
+    Initial,
+    Requested { thread_id: String },
+    Granted { endpoint: Url, routing_keys: Vec<String> },
+    Denied,
+}
+
+/// Recipient side of the [coordinate mediation](https://github.com/hyperledger/aries-rfcs/blob/main/features/0211-route-coordination/README.md)
+/// protocol.
+///
+/// Once mediation is granted, the mediator's endpoint and routing keys can be used
+/// in place of our own when creating invitations or sending connection requests,
+/// while the recipient keys used for those must be registered through keylist updates.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MediationRecipient {
+    state: MediationState,
+    recipient_keys: Vec<String>,
+    pending_updates: Vec<String>,
+    pending_query: Option<String>,
+}
+
+impl Default for MediationRecipient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MediationRecipient {
+    pub fn new() -> Self {
+        Self {
+            state: MediationState::Initial,
+            recipient_keys: Vec::new(),
+            pending_updates: Vec::new(),
+            pending_query: None,
+        }
+    }
+
+    pub fn get_state(&self) -> &MediationState {
+        &self.state
+    }
+
+    pub fn is_granted(&self) -> bool {
+        matches!(self.state, MediationState::Granted { .. })
+    }
+
+    pub fn endpoint(&self) -> Option<&Url> {
+        match &self.state {
+            MediationState::Granted { endpoint, .. } => Some(endpoint),
+            _ => None,
+        }
+    }
+
+    pub fn routing_keys(&self) -> Option<&[String]> {
+        match &self.state {
+            MediationState::Granted { routing_keys, .. } => Some(routing_keys),
+            _ => None,
+        }
+    }
+
+    /// The recipient keys known to be registered with the mediator.
+    pub fn recipient_keys(&self) -> &[String] {
+        &self.recipient_keys
+    }
+
+    pub async fn send_mediate_request(&mut self, send_message: SendClosure) -> VcxResult<()> {
+        if !matches!(self.state, MediationState::Initial | MediationState::Denied) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                format!("Cannot request mediation in state {:?}", self.state),
+            ));
+        }
+
+        let request = build_mediate_request();
+        let thread_id = request.id.clone();
+
+        send_message(request.into()).await?;
+        self.state = MediationState::Requested { thread_id };
+        Ok(())
+    }
+
+    pub fn handle_mediate_grant(&mut self, grant: MediateGrant) -> VcxResult<()> {
+        let thread_id = self.requested_thread_id()?;
+
+        if !matches_thread_id!(grant, thread_id) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot handle mediate grant: thread id does not match, expected {thread_id:?}"),
+            ));
+        }
+
+        self.state = MediationState::Granted {
+            endpoint: grant.content.endpoint,
+            routing_keys: grant.content.routing_keys,
+        };
+        Ok(())
+    }
+
+    pub fn handle_mediate_deny(&mut self, deny: MediateDeny) -> VcxResult<()> {
+        let thread_id = self.requested_thread_id()?;
+
+        if !matches_thread_id!(deny, thread_id) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot handle mediate deny: thread id does not match, expected {thread_id:?}"),
+            ));
+        }
+
+        self.state = MediationState::Denied;
+        Ok(())
+    }
+
+    /// Sends a keylist update to the mediator.
+    /// The local keylist only changes once the mediator's response is handled.
+    pub async fn send_keylist_update(
+        &mut self,
+        updates: Vec<KeylistUpdateItem>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.ensure_granted()?;
+
+        let update = build_keylist_update(updates);
+        let thread_id = update.id.clone();
+
+        send_message(update.into()).await?;
+        self.pending_updates.push(thread_id);
+        Ok(())
+    }
+
+    pub async fn add_recipient_key(&mut self, recipient_key: String, send_message: SendClosure) -> VcxResult<()> {
+        let update = KeylistUpdateItem::new(recipient_key, KeylistUpdateAction::Add);
+        self.send_keylist_update(vec![update], send_message).await
+    }
+
+    pub async fn remove_recipient_key(&mut self, recipient_key: String, send_message: SendClosure) -> VcxResult<()> {
+        let update = KeylistUpdateItem::new(recipient_key, KeylistUpdateAction::Remove);
+        self.send_keylist_update(vec![update], send_message).await
+    }
+
+    /// Applies the successful updates to the local keylist and returns the ones that failed.
+    pub fn handle_keylist_update_response(
+        &mut self,
+        response: KeylistUpdateResponse,
+    ) -> VcxResult<Vec<KeylistUpdateResponseItem>> {
+        let position = self
+            .pending_updates
+            .iter()
+            .position(|thread_id| matches_thread_id!(response, thread_id.as_str()))
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Keylist update response does not match any pending keylist update",
+                )
+            })?;
+
+        self.pending_updates.remove(position);
+
+        let mut failed = Vec::new();
+
+        for item in response.content.updated {
+            match (item.result, item.action) {
+                (KeylistUpdateResult::Success | KeylistUpdateResult::NoChange, KeylistUpdateAction::Add) => {
+                    if !self.recipient_keys.contains(&item.recipient_key) {
+                        self.recipient_keys.push(item.recipient_key);
+                    }
+                }
+                (KeylistUpdateResult::Success | KeylistUpdateResult::NoChange, KeylistUpdateAction::Remove) => {
+                    self.recipient_keys.retain(|key| key != &item.recipient_key);
+                }
+                (KeylistUpdateResult::ClientError | KeylistUpdateResult::ServerError, _) => failed.push(item),
+            }
+        }
+
+        Ok(failed)
+    }
+
+    pub async fn send_keylist_query(
+        &mut self,
+        paginate: Option<KeylistQueryPaginate>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.ensure_granted()?;
+
+        let query = build_keylist_query(paginate);
+        let thread_id = query.id.clone();
+
+        send_message(query.into()).await?;
+        self.pending_query = Some(thread_id);
+        Ok(())
+    }
+
+    /// Syncs the local keylist with the one reported by the mediator.
+    ///
+    /// A paginated keylist starting at a non-zero offset extends the local keylist
+    /// instead of replacing it.
+    pub fn handle_keylist(&mut self, keylist: Keylist) -> VcxResult<()> {
+        let matches = self
+            .pending_query
+            .as_deref()
+            .map(|thread_id| matches_thread_id!(keylist, thread_id))
+            .unwrap_or_default();
+
+        if !matches {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Keylist does not match the pending keylist query",
+            ));
+        }
+
+        let keys = keylist.content.keys.into_iter().map(|item| item.recipient_key);
+
+        match keylist.content.pagination {
+            Some(pagination) if pagination.offset > 0 => {
+                for key in keys {
+                    if !self.recipient_keys.contains(&key) {
+                        self.recipient_keys.push(key);
+                    }
+                }
+            }
+            _ => self.recipient_keys = keys.collect(),
+        }
+
+        self.pending_query = None;
+        Ok(())
+    }
+
+    fn ensure_granted(&self) -> VcxResult<()> {
+        if !self.is_granted() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Mediation has not been granted",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn requested_thread_id(&self) -> VcxResult<&str> {
+        match &self.state {
+            MediationState::Requested { thread_id } => Ok(thread_id),
+            state => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Mediation was not requested, current state: {state:?}"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::coordinate_mediation::{
+            keylist::{KeylistContent, KeylistDecorators, KeylistItem},
+            keylist_update_response::{KeylistUpdateResponseContent, KeylistUpdateResponseDecorators},
+            mediate_deny::{MediateDenyContent, MediateDenyDecorators},
+            mediate_grant::{MediateGrantContent, MediateGrantDecorators},
+        },
+        AriesMessage,
+    };
+
+    use super::*;
+
+    const ENDPOINT: &str = "https://mediator.example.com/";
+    const ROUTING_KEY: &str = "routing_key";
+    const RECIPIENT_KEY: &str = "recipient_key";
+
+    fn _send_message() -> SendClosure {
+        Box::new(|_: AriesMessage| Box::pin(async { VcxResult::Ok(()) }))
+    }
+
+    fn _grant(thread_id: &str) -> MediateGrant {
+        let content = MediateGrantContent::new(ENDPOINT.parse().unwrap(), vec![ROUTING_KEY.to_owned()]);
+        let decorators = MediateGrantDecorators::new(Thread::new(thread_id.to_owned()));
+        MediateGrant::with_decorators("grant".to_owned(), content, decorators)
+    }
+
+    fn _thread_id(recipient: &MediationRecipient) -> String {
+        match recipient.get_state() {
+            MediationState::Requested { thread_id } => thread_id.clone(),
+            state => panic!("unexpected state {state:?}"),
+        }
+    }
+
+    async fn _granted_recipient() -> MediationRecipient {
+        let mut recipient = MediationRecipient::new();
+        recipient.send_mediate_request(_send_message()).await.unwrap();
+        let thread_id = _thread_id(&recipient);
+        recipient.handle_mediate_grant(_grant(&thread_id)).unwrap();
+        recipient
+    }
+
+    #[tokio::test]
+    async fn test_mediation_granted() {
+        let recipient = _granted_recipient().await;
+
+        assert!(recipient.is_granted());
+        assert_eq!(recipient.endpoint().unwrap().as_str(), ENDPOINT);
+        assert_eq!(recipient.routing_keys().unwrap(), [ROUTING_KEY.to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_mediation_denied() {
+        let mut recipient = MediationRecipient::new();
+        recipient.send_mediate_request(_send_message()).await.unwrap();
+        let thread_id = _thread_id(&recipient);
+
+        let decorators = MediateDenyDecorators::new(Thread::new(thread_id));
+        let deny = MediateDeny::with_decorators("deny".to_owned(), MediateDenyContent::default(), decorators);
+        recipient.handle_mediate_deny(deny).unwrap();
+
+        assert_eq!(recipient.get_state(), &MediationState::Denied);
+        assert!(recipient.routing_keys().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_grant_thread_id_mismatch() {
+        let mut recipient = MediationRecipient::new();
+        recipient.send_mediate_request(_send_message()).await.unwrap();
+
+        recipient.handle_mediate_grant(_grant("other_thread")).unwrap_err();
+        assert!(!recipient.is_granted());
+    }
+
+    #[tokio::test]
+    async fn test_keylist_update_requires_grant() {
+        let mut recipient = MediationRecipient::new();
+
+        recipient
+            .add_recipient_key(RECIPIENT_KEY.to_owned(), _send_message())
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_keylist_update_response() {
+        let mut recipient = _granted_recipient().await;
+
+        recipient
+            .add_recipient_key(RECIPIENT_KEY.to_owned(), _send_message())
+            .await
+            .unwrap();
+        let thread_id = recipient.pending_updates[0].clone();
+
+        let content = KeylistUpdateResponseContent::new(vec![
+            KeylistUpdateResponseItem::new(
+                RECIPIENT_KEY.to_owned(),
+                KeylistUpdateAction::Add,
+                KeylistUpdateResult::Success,
+            ),
+            KeylistUpdateResponseItem::new(
+                "bad_key".to_owned(),
+                KeylistUpdateAction::Add,
+                KeylistUpdateResult::ClientError,
+            ),
+        ]);
+        let decorators = KeylistUpdateResponseDecorators::new(Thread::new(thread_id));
+        let response = KeylistUpdateResponse::with_decorators("response".to_owned(), content, decorators);
+
+        let failed = recipient.handle_keylist_update_response(response).unwrap();
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].recipient_key, "bad_key");
+        assert_eq!(recipient.recipient_keys(), [RECIPIENT_KEY.to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_keylist_query() {
+        let mut recipient = _granted_recipient().await;
+
+        recipient.send_keylist_query(None, _send_message()).await.unwrap();
+        let thread_id = recipient.pending_query.clone().unwrap();
+
+        let content = KeylistContent::new(vec![KeylistItem::new(RECIPIENT_KEY.to_owned())]);
+        let decorators = KeylistDecorators::new(Thread::new(thread_id));
+        let keylist = Keylist::with_decorators("keylist".to_owned(), content, decorators);
+
+        recipient.handle_keylist(keylist).unwrap();
+        assert_eq!(recipient.recipient_keys(), [RECIPIENT_KEY.to_owned()]);
+    }
+}
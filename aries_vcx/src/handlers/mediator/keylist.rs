@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet};
+use messages::msg_fields::protocols::coordinate_mediation::keylist_update_response::KeylistUpdateResult;
+use serde_json::Value;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    utils::didcomm_v2::keys::ed25519_from_kid,
+};
+
+pub const MEDIATION_CLIENT_RECORD_TYPE: &str = "MediationClient";
+pub const ROUTED_KEY_RECORD_TYPE: &str = "MediationRoutedKey";
+
+const CLIENT_KEY_TAG: &str = "client_key";
+
+/// Mediator side data about a recipient which was granted mediation.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MediationClient {
+    pub live_delivery: bool,
+}
+
+/// Converts a `did:key` recipient key to its base58 encoded form, leaving raw verkeys untouched,
+/// so that keys are matched regardless of the format the recipient or the sender used.
+pub fn normalize_recipient_key(key: &str) -> VcxResult<String> {
+    if key.starts_with("did:key:") {
+        Ok(bs58::encode(ed25519_from_kid(key)?).into_string())
+    } else {
+        Ok(key.to_owned())
+    }
+}
+
+/// Storage of mediation clients and the recipient keys routed on their behalf,
+/// kept as [`BaseWallet`] records.
+///
+/// Routed keys are stored with the client key as their value and as a tag,
+/// so lookups work both from a forwarded message's recipient and from a client.
+#[derive(Debug, Clone)]
+pub struct MediatorKeylist {
+    wallet: Arc<dyn BaseWallet>,
+}
+
+impl MediatorKeylist {
+    pub fn new(wallet: Arc<dyn BaseWallet>) -> Self {
+        Self { wallet }
+    }
+
+    pub async fn get_client(&self, client_key: &str) -> VcxResult<Option<MediationClient>> {
+        match self
+            .wallet
+            .get_wallet_record_value(MEDIATION_CLIENT_RECORD_TYPE, client_key)
+            .await
+        {
+            Ok(value) => Ok(Some(serde_json::from_str(&value)?)),
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn save_client(&self, client_key: &str, client: &MediationClient) -> VcxResult<()> {
+        let value = serde_json::to_string(client)?;
+
+        if self.get_client(client_key).await?.is_some() {
+            self.wallet
+                .update_wallet_record_value(MEDIATION_CLIENT_RECORD_TYPE, client_key, &value)
+                .await?;
+        } else {
+            self.wallet
+                .add_wallet_record(MEDIATION_CLIENT_RECORD_TYPE, client_key, &value, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the key of the client the recipient key is routed to, if any.
+    pub async fn client_for_key(&self, recipient_key: &str) -> VcxResult<Option<String>> {
+        let recipient_key = normalize_recipient_key(recipient_key)?;
+
+        match self
+            .wallet
+            .get_wallet_record_value(ROUTED_KEY_RECORD_TYPE, &recipient_key)
+            .await
+        {
+            Ok(client_key) => Ok(Some(client_key)),
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Routes the recipient key to the client.
+    ///
+    /// Keys already routed to another client are rejected as a client error.
+    pub async fn add_key(&self, client_key: &str, recipient_key: &str) -> VcxResult<KeylistUpdateResult> {
+        let recipient_key = normalize_recipient_key(recipient_key)?;
+
+        match self.client_for_key(&recipient_key).await? {
+            Some(owner) if owner == client_key => Ok(KeylistUpdateResult::NoChange),
+            Some(_) => Ok(KeylistUpdateResult::ClientError),
+            None => {
+                let tags = json!({ CLIENT_KEY_TAG: client_key }).to_string();
+                self.wallet
+                    .add_wallet_record(ROUTED_KEY_RECORD_TYPE, &recipient_key, client_key, Some(&tags))
+                    .await?;
+                Ok(KeylistUpdateResult::Success)
+            }
+        }
+    }
+
+    /// Stops routing the recipient key to the client.
+    pub async fn remove_key(&self, client_key: &str, recipient_key: &str) -> VcxResult<KeylistUpdateResult> {
+        let recipient_key = normalize_recipient_key(recipient_key)?;
+
+        match self.client_for_key(&recipient_key).await? {
+            Some(owner) if owner == client_key => {
+                self.wallet
+                    .delete_wallet_record(ROUTED_KEY_RECORD_TYPE, &recipient_key)
+                    .await?;
+                Ok(KeylistUpdateResult::Success)
+            }
+            _ => Ok(KeylistUpdateResult::NoChange),
+        }
+    }
+
+    /// Returns the recipient keys routed to the client, sorted.
+    pub async fn keys(&self, client_key: &str) -> VcxResult<Vec<String>> {
+        let query = json!({ CLIENT_KEY_TAG: client_key }).to_string();
+        let records = self
+            .wallet
+            .iterate_wallet_records(ROUTED_KEY_RECORD_TYPE, &query, "{}")
+            .await?
+            .collect()
+            .await?;
+
+        let mut keys = records
+            .iter()
+            .map(|record| {
+                let record: Value = serde_json::from_str(record)?;
+                record["id"].as_str().map(ToOwned::to_owned).ok_or_else(|| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidJson,
+                        format!("Routed key record has no id: {record}"),
+                    )
+                })
+            })
+            .collect::<VcxResult<Vec<_>>>()?;
+
+        keys.sort();
+        Ok(keys)
+    }
+}
@@ -0,0 +1,1009 @@
+pub mod keylist;
+pub mod queue;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use async_trait::async_trait;
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, transport::Transport},
+    msg_fields::protocols::{
+        coordinate_mediation::{
+            keylist::{Keylist, KeylistItem, KeylistPagination},
+            keylist_query::KeylistQuery,
+            keylist_update::{KeylistUpdate, KeylistUpdateAction},
+            keylist_update_response::{KeylistUpdateResponse, KeylistUpdateResponseItem, KeylistUpdateResult},
+            mediate_grant::MediateGrant,
+            mediate_request::MediateRequest,
+            CoordinateMediation,
+        },
+        pickup::{
+            delivery_request::DeliveryRequest, live_delivery_change::LiveDeliveryChange,
+            messages_received::MessagesReceived, status::Status, status::StatusContent, status_request::StatusRequest,
+            Pickup,
+        },
+        routing::Forward,
+    },
+    AriesMessage,
+};
+use url::Url;
+
+use self::{
+    keylist::{normalize_recipient_key, MediationClient, MediatorKeylist},
+    queue::MessageQueue,
+};
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::{
+        coordinate_mediation::{build_keylist, build_keylist_update_response, build_mediate_grant},
+        pickup::{build_delivery, build_status},
+    },
+    transport::InboundHandler,
+    utils::encryption_envelope::EncryptionEnvelope,
+};
+
+/// Messages kept for a recipient key unless set with [`Mediator::with_max_queued_messages`].
+pub const DEFAULT_MAX_QUEUED_MESSAGES: usize = 100;
+
+/// Recipient keys routed to a client unless set with [`Mediator::with_max_client_keys`].
+pub const DEFAULT_MAX_CLIENT_KEYS: usize = 1000;
+
+/// What became of a forwarded message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForwardOutcome {
+    /// The message was queued until its recipient picks it up.
+    Queued { client_key: String, message_id: String },
+    /// The client enabled live delivery, so the packed message should be sent to it
+    /// directly over its live connection. Should that fail, the message can still be
+    /// queued through [`Mediator::queue_message`].
+    Relay {
+        client_key: String,
+        recipient_key: String,
+        message: Vec<u8>,
+    },
+}
+
+/// What became of a message received by the mediator.
+#[derive(Debug, Clone)]
+pub enum MediatorOutcome {
+    Forward(ForwardOutcome),
+    /// The message came from a client and must be answered with `message`,
+    /// packed for `client_key`.
+    Reply {
+        client_key: String,
+        message: AriesMessage,
+    },
+}
+
+/// Mediator side of the [coordinate mediation](https://github.com/hyperledger/aries-rfcs/blob/main/features/0211-route-coordination/README.md),
+/// [routing](https://github.com/hyperledger/aries-rfcs/blob/main/concepts/0046-mediators-and-relays/README.md)
+/// and [pickup](https://github.com/hyperledger/aries-rfcs/blob/main/features/0685-pickup-v2/README.md) protocols.
+///
+/// Clients are identified by the verkey they use on their connection with the mediator.
+/// Mediation is granted to any client that requests it; a mediator that wants to refuse
+/// some clients should answer their requests with a `mediate-deny` instead of calling
+/// [`Mediator::handle_mediate_request`].
+///
+/// As forwards can come from anyone, only so many messages are queued for each recipient key,
+/// and forwards to a key whose queue is full are rejected until its client picks some up.
+/// Likewise, each client has only so many recipient keys routed to it.
+///
+/// Served over a connection the clients keep open, such as a WebSocket, the mediator returns its
+/// replies to the clients asking for it with the `~transport` decorator, see [`InboundHandler`].
+#[derive(Debug, Clone)]
+pub struct Mediator {
+    wallet: Arc<dyn BaseWallet>,
+    endpoint: Url,
+    routing_keys: Vec<String>,
+    keylist: MediatorKeylist,
+    queue: Arc<dyn MessageQueue>,
+    max_queued_messages: usize,
+    max_client_keys: usize,
+    queue_locks: Arc<KeyLocks>,
+    keylist_locks: Arc<KeyLocks>,
+}
+
+impl Mediator {
+    /// Creates a mediator reachable at `endpoint`, to which forward messages are packed
+    /// for `routing_keys`. The private parts of the routing keys must be in the wallet.
+    pub fn new(
+        wallet: Arc<dyn BaseWallet>,
+        endpoint: Url,
+        routing_keys: Vec<String>,
+        queue: Arc<dyn MessageQueue>,
+    ) -> Self {
+        Self {
+            keylist: MediatorKeylist::new(Arc::clone(&wallet)),
+            wallet,
+            endpoint,
+            routing_keys,
+            queue,
+            max_queued_messages: DEFAULT_MAX_QUEUED_MESSAGES,
+            max_client_keys: DEFAULT_MAX_CLIENT_KEYS,
+            queue_locks: Default::default(),
+            keylist_locks: Default::default(),
+        }
+    }
+
+    /// Sets how many messages are queued for a recipient key before further ones are rejected.
+    pub fn with_max_queued_messages(mut self, max_queued_messages: usize) -> Self {
+        self.max_queued_messages = max_queued_messages;
+        self
+    }
+
+    /// Sets how many recipient keys are routed to a client before further ones are rejected.
+    pub fn with_max_client_keys(mut self, max_client_keys: usize) -> Self {
+        self.max_client_keys = max_client_keys;
+        self
+    }
+
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    pub fn routing_keys(&self) -> &[String] {
+        &self.routing_keys
+    }
+
+    pub fn keylist(&self) -> &MediatorKeylist {
+        &self.keylist
+    }
+
+    /// Unpacks a message received at the mediator's endpoint and handles it.
+    ///
+    /// Forward messages can come from anyone and are routed to the client owning their recipient key,
+    /// while any other message must be authcrypted by a client and gets a reply.
+    pub async fn receive(&self, payload: Vec<u8>) -> VcxResult<MediatorOutcome> {
+        let (message, sender_vk) = EncryptionEnvelope::anon_unpack(&self.wallet, payload).await?;
+        self.receive_unpacked(message, sender_vk).await
+    }
+
+    async fn receive_unpacked(&self, message: AriesMessage, sender_vk: Option<String>) -> VcxResult<MediatorOutcome> {
+        if let AriesMessage::Routing(forward) = message {
+            return self.handle_forward(&forward).await.map(MediatorOutcome::Forward);
+        }
+
+        let client_key = sender_vk.ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                "Messages other than forwards must be authcrypted by a mediation client",
+            )
+        })?;
+
+        let message = self.handle_message(&client_key, message).await?;
+        Ok(MediatorOutcome::Reply { client_key, message })
+    }
+
+    /// Packs a reply for a client, authcrypted with the mediator key the client's message was
+    /// packed for.
+    pub async fn pack_reply(&self, mediator_key: &str, client_key: &str, message: &AriesMessage) -> VcxResult<Vec<u8>> {
+        let receiver_keys = json!([client_key]).to_string();
+        let message = serde_json::to_vec(message)?;
+        Ok(self
+            .wallet
+            .pack_message(Some(mediator_key), &receiver_keys, &message)
+            .await?)
+    }
+
+    /// Handles a coordinate mediation or pickup message from a client, returning the reply.
+    pub async fn handle_message(&self, client_key: &str, message: AriesMessage) -> VcxResult<AriesMessage> {
+        match message {
+            AriesMessage::CoordinateMediation(CoordinateMediation::MediateRequest(msg)) => {
+                self.handle_mediate_request(client_key, &msg).await.map(From::from)
+            }
+            AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdate(msg)) => {
+                self.handle_keylist_update(client_key, &msg).await.map(From::from)
+            }
+            AriesMessage::CoordinateMediation(CoordinateMediation::KeylistQuery(msg)) => {
+                self.handle_keylist_query(client_key, &msg).await.map(From::from)
+            }
+            AriesMessage::Pickup(Pickup::StatusRequest(msg)) => {
+                self.handle_status_request(client_key, &msg).await.map(From::from)
+            }
+            AriesMessage::Pickup(Pickup::DeliveryRequest(msg)) => self.handle_delivery_request(client_key, &msg).await,
+            AriesMessage::Pickup(Pickup::MessagesReceived(msg)) => {
+                self.handle_messages_received(client_key, &msg).await.map(From::from)
+            }
+            AriesMessage::Pickup(Pickup::LiveDeliveryChange(msg)) => {
+                self.handle_live_delivery_change(client_key, &msg).await.map(From::from)
+            }
+            msg => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!("Mediator can't handle message: {msg:?}"),
+            )),
+        }
+    }
+
+    pub async fn handle_mediate_request(&self, client_key: &str, request: &MediateRequest) -> VcxResult<MediateGrant> {
+        if self.keylist.get_client(client_key).await?.is_none() {
+            self.keylist
+                .save_client(client_key, &MediationClient::default())
+                .await?;
+        }
+
+        let thread_id = reply_thread_id(&request.id, request.decorators.thread.as_ref());
+        Ok(build_mediate_grant(
+            thread_id,
+            self.endpoint.clone(),
+            self.routing_keys.clone(),
+        ))
+    }
+
+    pub async fn handle_keylist_update(
+        &self,
+        client_key: &str,
+        update: &KeylistUpdate,
+    ) -> VcxResult<KeylistUpdateResponse> {
+        self.granted_client(client_key).await?;
+
+        let updated = self
+            .keylist_locks
+            .run(client_key, move || async move {
+                let mut key_count = self.keylist.keys(client_key).await?.len();
+                let mut updated = Vec::with_capacity(update.content.updates.len());
+                for item in &update.content.updates {
+                    let result = if normalize_recipient_key(&item.recipient_key).is_err() {
+                        KeylistUpdateResult::ClientError
+                    } else {
+                        match item.action {
+                            KeylistUpdateAction::Add if key_count >= self.max_client_keys => {
+                                match self.keylist.client_for_key(&item.recipient_key).await? {
+                                    Some(owner) if owner == client_key => KeylistUpdateResult::NoChange,
+                                    _ => KeylistUpdateResult::ClientError,
+                                }
+                            }
+                            KeylistUpdateAction::Add => self.keylist.add_key(client_key, &item.recipient_key).await?,
+                            KeylistUpdateAction::Remove => {
+                                self.keylist.remove_key(client_key, &item.recipient_key).await?
+                            }
+                        }
+                    };
+
+                    if result == KeylistUpdateResult::Success {
+                        match item.action {
+                            KeylistUpdateAction::Add => key_count += 1,
+                            KeylistUpdateAction::Remove => key_count -= 1,
+                        }
+                    }
+                    updated.push(KeylistUpdateResponseItem::new(
+                        item.recipient_key.clone(),
+                        item.action,
+                        result,
+                    ));
+                }
+                Ok(updated)
+            })
+            .await?;
+
+        let thread_id = reply_thread_id(&update.id, update.decorators.thread.as_ref());
+        Ok(build_keylist_update_response(thread_id, updated))
+    }
+
+    pub async fn handle_keylist_query(&self, client_key: &str, query: &KeylistQuery) -> VcxResult<Keylist> {
+        self.granted_client(client_key).await?;
+
+        let keys = self.keylist.keys(client_key).await?;
+        let total = keys.len() as u32;
+
+        let (keys, pagination) = match &query.content.paginate {
+            Some(paginate) => {
+                let offset = paginate.offset.min(total);
+                let page: Vec<String> = keys
+                    .into_iter()
+                    .skip(offset as usize)
+                    .take(paginate.limit as usize)
+                    .collect();
+                let count = page.len() as u32;
+                (
+                    page,
+                    Some(KeylistPagination::new(count, offset, total - offset - count)),
+                )
+            }
+            None => (keys, None),
+        };
+
+        let keys = keys.into_iter().map(KeylistItem::new).collect();
+        let thread_id = reply_thread_id(&query.id, query.decorators.thread.as_ref());
+        Ok(build_keylist(thread_id, keys, pagination))
+    }
+
+    /// Unwraps a forward message and queues or relays the inner packed message
+    /// for the client owning its recipient key.
+    pub async fn handle_forward(&self, forward: &Forward) -> VcxResult<ForwardOutcome> {
+        let recipient_key = normalize_recipient_key(&forward.content.to)?;
+
+        let client_key = self.keylist.client_for_key(&recipient_key).await?.ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidVerkey,
+                format!("No mediation client registered recipient key {recipient_key}"),
+            )
+        })?;
+        let client = self.granted_client(&client_key).await?;
+
+        let message = serde_json::to_vec(&forward.content.msg)?;
+
+        if client.live_delivery {
+            Ok(ForwardOutcome::Relay {
+                client_key,
+                recipient_key,
+                message,
+            })
+        } else {
+            let message_id = self.push_message(&recipient_key, message).await?;
+            Ok(ForwardOutcome::Queued { client_key, message_id })
+        }
+    }
+
+    /// Queues a packed message for a recipient key, e.g. when relaying it to a client failed.
+    pub async fn queue_message(&self, recipient_key: &str, message: Vec<u8>) -> VcxResult<String> {
+        self.push_message(&normalize_recipient_key(recipient_key)?, message)
+            .await
+    }
+
+    pub async fn handle_status_request(&self, client_key: &str, request: &StatusRequest) -> VcxResult<Status> {
+        let client = self.granted_client(client_key).await?;
+        let recipient_key = request.content.recipient_key.as_deref();
+        let keys = self.pickup_keys(client_key, recipient_key).await?;
+
+        let thread_id = reply_thread_id(&request.id, request.decorators.thread.as_ref());
+        self.build_status(thread_id, recipient_key, &keys, &client).await
+    }
+
+    /// Answers with a delivery of the oldest queued messages, or with a status
+    /// when there are no messages to deliver.
+    pub async fn handle_delivery_request(
+        &self,
+        client_key: &str,
+        request: &DeliveryRequest,
+    ) -> VcxResult<AriesMessage> {
+        let client = self.granted_client(client_key).await?;
+        let recipient_key = request.content.recipient_key.as_deref();
+        let keys = self.pickup_keys(client_key, recipient_key).await?;
+
+        let thread_id = reply_thread_id(&request.id, request.decorators.thread.as_ref());
+        let messages = self.queue.messages(&keys, Some(request.content.limit as usize)).await?;
+
+        if messages.is_empty() {
+            return self
+                .build_status(thread_id, recipient_key, &keys, &client)
+                .await
+                .map(From::from);
+        }
+
+        let messages = messages.into_iter().map(|msg| (msg.id, msg.message)).collect();
+        Ok(build_delivery(thread_id, recipient_key.map(ToOwned::to_owned), messages).into())
+    }
+
+    pub async fn handle_messages_received(&self, client_key: &str, received: &MessagesReceived) -> VcxResult<Status> {
+        let client = self.granted_client(client_key).await?;
+        let keys = self.keylist.keys(client_key).await?;
+
+        self.queue.remove(&keys, &received.content.message_id_list).await?;
+
+        let thread_id = reply_thread_id(&received.id, received.decorators.thread.as_ref());
+        self.build_status(thread_id, None, &keys, &client).await
+    }
+
+    pub async fn handle_live_delivery_change(
+        &self,
+        client_key: &str,
+        change: &LiveDeliveryChange,
+    ) -> VcxResult<Status> {
+        let mut client = self.granted_client(client_key).await?;
+        client.live_delivery = change.content.live_delivery;
+        self.keylist.save_client(client_key, &client).await?;
+
+        let keys = self.keylist.keys(client_key).await?;
+        let thread_id = reply_thread_id(&change.id, change.decorators.thread.as_ref());
+        self.build_status(thread_id, None, &keys, &client).await
+    }
+
+    /// Queues a message unless the queue of the recipient key is full, one message of the key at
+    /// a time so that concurrent forwards don't overfill it.
+    async fn push_message(&self, recipient_key: &str, message: Vec<u8>) -> VcxResult<String> {
+        self.queue_locks
+            .run(recipient_key, move || async move {
+                let queued = self
+                    .queue
+                    .messages(&[recipient_key.to_owned()], Some(self.max_queued_messages))
+                    .await?;
+                if queued.len() >= self.max_queued_messages {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidState,
+                        format!("Message queue of recipient key {recipient_key} is full"),
+                    ));
+                }
+                self.queue.push(recipient_key, message).await
+            })
+            .await
+    }
+
+    async fn granted_client(&self, client_key: &str) -> VcxResult<MediationClient> {
+        self.keylist.get_client(client_key).await?.ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Mediation was not granted to {client_key}"),
+            )
+        })
+    }
+
+    /// Returns the keys a pickup request is about: the requested recipient key, provided
+    /// it belongs to the client, or all of the client's keys otherwise.
+    async fn pickup_keys(&self, client_key: &str, recipient_key: Option<&str>) -> VcxResult<Vec<String>> {
+        let Some(recipient_key) = recipient_key else {
+            return self.keylist.keys(client_key).await;
+        };
+
+        match self.keylist.client_for_key(recipient_key).await? {
+            Some(owner) if owner == client_key => Ok(vec![normalize_recipient_key(recipient_key)?]),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Recipient key {recipient_key} is not registered by {client_key}"),
+            )),
+        }
+    }
+
+    async fn build_status(
+        &self,
+        thread_id: String,
+        recipient_key: Option<&str>,
+        keys: &[String],
+        client: &MediationClient,
+    ) -> VcxResult<Status> {
+        let messages = self.queue.messages(keys, None).await?;
+
+        let mut content = StatusContent::new(messages.len() as u32);
+        content.recipient_key = recipient_key.map(ToOwned::to_owned);
+        content.oldest_received_time = messages.iter().map(|msg| msg.received_time).min();
+        content.newest_received_time = messages.iter().map(|msg| msg.received_time).max();
+        content.longest_waited_seconds = content
+            .oldest_received_time
+            .map(|oldest| (Utc::now() - oldest).num_seconds().max(0) as u64);
+        content.total_bytes = Some(messages.iter().map(|msg| msg.message.len() as u64).sum());
+        content.live_delivery = Some(client.live_delivery);
+
+        Ok(build_status(thread_id, content))
+    }
+}
+
+#[async_trait]
+impl InboundHandler for Mediator {
+    /// Replies are only returned to the clients asking for it, the mediator knowing no endpoint
+    /// to send them to otherwise. Forwards to clients with live delivery enabled are queued, as
+    /// the live connections of the clients are not tracked here.
+    async fn receive_packed(&self, payload: Vec<u8>) -> VcxResult<Option<Vec<u8>>> {
+        let mediator_key = EncryptionEnvelope::recipient_keys(&payload)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidMessageFormat,
+                    "Packed message has no recipients",
+                )
+            })?;
+        let (message, sender_vk) = EncryptionEnvelope::anon_unpack(&self.wallet, payload).await?;
+        let return_route = returns_reply(&message)?;
+
+        match self.receive_unpacked(message, sender_vk).await? {
+            MediatorOutcome::Reply { client_key, message } if return_route => {
+                self.pack_reply(&mediator_key, &client_key, &message).await.map(Some)
+            }
+            MediatorOutcome::Reply { client_key, message } => {
+                warn!("Dropping reply {message:?} to {client_key}, which asked for no return route");
+                Ok(None)
+            }
+            MediatorOutcome::Forward(ForwardOutcome::Relay {
+                recipient_key, message, ..
+            }) => {
+                self.queue_message(&recipient_key, message).await?;
+                Ok(None)
+            }
+            MediatorOutcome::Forward(ForwardOutcome::Queued { .. }) => Ok(None),
+        }
+    }
+}
+
+/// Whether the sender of the message asked with its `~transport` decorator for the reply, which
+/// belongs to the message's thread, to be returned over the connection the message came on.
+fn returns_reply(message: &AriesMessage) -> VcxResult<bool> {
+    let message = serde_json::to_value(message)?;
+    let Some(transport) = message.get("~transport") else {
+        return Ok(false);
+    };
+    let transport: Transport = serde_json::from_value(transport.clone())?;
+    let thread_id = message["~thread"]["thid"]
+        .as_str()
+        .or_else(|| message["@id"].as_str())
+        .unwrap_or_default();
+    Ok(transport.returns_thread(thread_id))
+}
+
+/// Serialises the operations made on behalf of a key, across the clones of a mediator.
+#[derive(Debug, Default)]
+struct KeyLocks {
+    locks: Mutex<HashMap<String, KeyLock>>,
+}
+
+type KeyLock = Arc<tokio::sync::Mutex<()>>;
+
+impl KeyLocks {
+    async fn run<F, Fut, T>(&self, key: &str, operation: F) -> VcxResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = VcxResult<T>>,
+    {
+        let lock = Arc::clone(self.lock_map()?.entry(key.to_owned()).or_default());
+        let result = {
+            let _guard = lock.lock().await;
+            operation().await
+        };
+
+        // Nobody else waits on the key once the map and this operation hold its only references.
+        let mut locks = self.lock_map()?;
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(key);
+        }
+        result
+    }
+
+    fn lock_map(&self) -> VcxResult<std::sync::MutexGuard<'_, HashMap<String, KeyLock>>> {
+        self.locks.lock().map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Mediator key locks are poisoned: {err}"),
+            )
+        })
+    }
+}
+
+fn reply_thread_id(msg_id: &str, thread: Option<&Thread>) -> String {
+    thread
+        .map(|thread| thread.thid.clone())
+        .unwrap_or_else(|| msg_id.to_owned())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod integration_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+    use messages::msg_fields::protocols::{
+        coordinate_mediation::{keylist_query::KeylistQueryPaginate, keylist_update::KeylistUpdateItem},
+        routing::ForwardContent,
+    };
+
+    use super::{
+        queue::{InMemoryMessageQueue, WalletMessageQueue},
+        *,
+    };
+    use crate::protocols::{
+        coordinate_mediation::{build_keylist_query, build_keylist_update, build_mediate_request},
+        pickup::{
+            build_delivery_request, build_live_delivery_change, build_messages_received, build_status_request,
+            extract_delivered_messages,
+        },
+    };
+    use crate::utils::devsetup::SetupLibraryWallet;
+
+    const CLIENT_KEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+    const RECIPIENT_KEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+
+    fn make_mediator(wallet: Arc<dyn BaseWallet>) -> Mediator {
+        Mediator::new(
+            wallet,
+            "https://mediator.example.org".parse().unwrap(),
+            vec!["mediator_routing_key".to_owned()],
+            Arc::new(InMemoryMessageQueue::new()),
+        )
+    }
+
+    fn make_forward(to: &str) -> Forward {
+        Forward::new(
+            "forward_id".to_owned(),
+            ForwardContent::new(to.to_owned(), json!({"protected": "test"})),
+        )
+    }
+
+    async fn grant_with_key(mediator: &Mediator) {
+        mediator
+            .handle_mediate_request(CLIENT_KEY, &build_mediate_request())
+            .await
+            .unwrap();
+        let update = build_keylist_update(vec![KeylistUpdateItem::new(
+            RECIPIENT_KEY.to_owned(),
+            KeylistUpdateAction::Add,
+        )]);
+        mediator.handle_keylist_update(CLIENT_KEY, &update).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mediator_grants_and_updates_keylist() {
+        SetupLibraryWallet::run(|setup| async move {
+            let mediator = make_mediator(Arc::new(IndySdkWallet::new(setup.wallet_handle)));
+
+            let update = build_keylist_update(vec![]);
+            assert_eq!(
+                mediator
+                    .handle_keylist_update(CLIENT_KEY, &update)
+                    .await
+                    .unwrap_err()
+                    .kind(),
+                AriesVcxErrorKind::InvalidState
+            );
+
+            let request = build_mediate_request();
+            let grant = mediator.handle_mediate_request(CLIENT_KEY, &request).await.unwrap();
+            assert_eq!(grant.decorators.thread.thid, request.id);
+            assert_eq!(grant.content.endpoint, *mediator.endpoint());
+            assert_eq!(grant.content.routing_keys, mediator.routing_keys());
+
+            let did_key_recipient = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th";
+            let update = build_keylist_update(vec![
+                KeylistUpdateItem::new(RECIPIENT_KEY.to_owned(), KeylistUpdateAction::Add),
+                KeylistUpdateItem::new(RECIPIENT_KEY.to_owned(), KeylistUpdateAction::Add),
+                KeylistUpdateItem::new(did_key_recipient.to_owned(), KeylistUpdateAction::Add),
+                KeylistUpdateItem::new("did:key:invalid".to_owned(), KeylistUpdateAction::Add),
+            ]);
+            let response = mediator.handle_keylist_update(CLIENT_KEY, &update).await.unwrap();
+            let results: Vec<_> = response.content.updated.iter().map(|item| item.result).collect();
+            assert_eq!(
+                results,
+                vec![
+                    KeylistUpdateResult::Success,
+                    KeylistUpdateResult::NoChange,
+                    KeylistUpdateResult::Success,
+                    KeylistUpdateResult::ClientError
+                ]
+            );
+            assert_eq!(response.decorators.thread.thid, update.id);
+
+            let other_client = "other_client_key";
+            mediator
+                .handle_mediate_request(other_client, &build_mediate_request())
+                .await
+                .unwrap();
+            let update = build_keylist_update(vec![KeylistUpdateItem::new(
+                RECIPIENT_KEY.to_owned(),
+                KeylistUpdateAction::Add,
+            )]);
+            let response = mediator.handle_keylist_update(other_client, &update).await.unwrap();
+            assert_eq!(response.content.updated[0].result, KeylistUpdateResult::ClientError);
+
+            let keylist = mediator
+                .handle_keylist_query(CLIENT_KEY, &build_keylist_query(None))
+                .await
+                .unwrap();
+            assert_eq!(keylist.content.keys.len(), 2);
+            assert!(keylist.content.pagination.is_none());
+
+            let paginate = KeylistQueryPaginate::new(1, 1);
+            let keylist = mediator
+                .handle_keylist_query(CLIENT_KEY, &build_keylist_query(Some(paginate)))
+                .await
+                .unwrap();
+            assert_eq!(keylist.content.keys.len(), 1);
+            assert_eq!(keylist.content.pagination, Some(KeylistPagination::new(1, 1, 0)));
+
+            let update = build_keylist_update(vec![KeylistUpdateItem::new(
+                RECIPIENT_KEY.to_owned(),
+                KeylistUpdateAction::Remove,
+            )]);
+            let response = mediator.handle_keylist_update(CLIENT_KEY, &update).await.unwrap();
+            assert_eq!(response.content.updated[0].result, KeylistUpdateResult::Success);
+            assert!(mediator
+                .keylist()
+                .client_for_key(RECIPIENT_KEY)
+                .await
+                .unwrap()
+                .is_none());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_queues_forwards_for_pickup() {
+        SetupLibraryWallet::run(|setup| async move {
+            let mediator = make_mediator(Arc::new(IndySdkWallet::new(setup.wallet_handle)));
+            grant_with_key(&mediator).await;
+
+            let err = mediator.handle_forward(&make_forward("unknown_key")).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidVerkey);
+
+            let ForwardOutcome::Queued { client_key, message_id } =
+                mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap()
+            else {
+                panic!("forward should have been queued");
+            };
+            assert_eq!(client_key, CLIENT_KEY);
+
+            let status = mediator
+                .handle_status_request(CLIENT_KEY, &build_status_request(None))
+                .await
+                .unwrap();
+            assert_eq!(status.content.message_count, 1);
+            assert_eq!(status.content.live_delivery, Some(false));
+
+            let err = mediator
+                .handle_status_request(CLIENT_KEY, &build_status_request(Some("unknown_key".to_owned())))
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+
+            let request = build_delivery_request(10, Some(RECIPIENT_KEY.to_owned()));
+            let AriesMessage::Pickup(Pickup::Delivery(delivery)) =
+                mediator.handle_delivery_request(CLIENT_KEY, &request).await.unwrap()
+            else {
+                panic!("expected a delivery");
+            };
+            assert_eq!(delivery.decorators.thread.as_ref().unwrap().thid, request.id);
+            let delivered = extract_delivered_messages(&delivery).unwrap();
+            assert_eq!(
+                delivered,
+                vec![(
+                    message_id.clone(),
+                    serde_json::to_vec(&json!({"protected": "test"})).unwrap()
+                )]
+            );
+
+            let status = mediator
+                .handle_messages_received(CLIENT_KEY, &build_messages_received(vec![message_id]))
+                .await
+                .unwrap();
+            assert_eq!(status.content.message_count, 0);
+
+            let response = mediator
+                .handle_delivery_request(CLIENT_KEY, &build_delivery_request(10, None))
+                .await
+                .unwrap();
+            assert!(matches!(response, AriesMessage::Pickup(Pickup::Status(_))));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_rejects_forwards_to_full_queue() {
+        SetupLibraryWallet::run(|setup| async move {
+            let mediator = make_mediator(Arc::new(IndySdkWallet::new(setup.wallet_handle))).with_max_queued_messages(2);
+            grant_with_key(&mediator).await;
+
+            let ForwardOutcome::Queued { message_id, .. } =
+                mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap()
+            else {
+                panic!("forward should have been queued");
+            };
+            mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap();
+
+            let err = mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidState);
+            let err = mediator
+                .queue_message(RECIPIENT_KEY, b"message".to_vec())
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidState);
+
+            let status = mediator
+                .handle_messages_received(CLIENT_KEY, &build_messages_received(vec![message_id]))
+                .await
+                .unwrap();
+            assert_eq!(status.content.message_count, 1);
+            mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap();
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_fills_queue_once_on_concurrent_forwards() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let queue = Arc::new(WalletMessageQueue::new(Arc::clone(&wallet)));
+            // queued in the wallet, so that the forwards interleave
+            let mediator = Mediator::new(
+                wallet,
+                "https://mediator.example.org".parse().unwrap(),
+                vec!["mediator_routing_key".to_owned()],
+                queue,
+            )
+            .with_max_queued_messages(2);
+            grant_with_key(&mediator).await;
+
+            let forward = make_forward(RECIPIENT_KEY);
+            let outcomes = tokio::join!(
+                mediator.handle_forward(&forward),
+                mediator.handle_forward(&forward),
+                mediator.handle_forward(&forward)
+            );
+            let queued = [outcomes.0, outcomes.1, outcomes.2]
+                .into_iter()
+                .filter(|outcome| outcome.is_ok())
+                .count();
+            assert_eq!(queued, 2);
+
+            let status = mediator
+                .handle_status_request(CLIENT_KEY, &build_status_request(None))
+                .await
+                .unwrap();
+            assert_eq!(status.content.message_count, 2);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_limits_client_keys() {
+        SetupLibraryWallet::run(|setup| async move {
+            let mediator = make_mediator(Arc::new(IndySdkWallet::new(setup.wallet_handle))).with_max_client_keys(1);
+            grant_with_key(&mediator).await;
+
+            let other_key = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th";
+            let update = build_keylist_update(vec![
+                KeylistUpdateItem::new(RECIPIENT_KEY.to_owned(), KeylistUpdateAction::Add),
+                KeylistUpdateItem::new(other_key.to_owned(), KeylistUpdateAction::Add),
+                KeylistUpdateItem::new(RECIPIENT_KEY.to_owned(), KeylistUpdateAction::Remove),
+                KeylistUpdateItem::new(other_key.to_owned(), KeylistUpdateAction::Add),
+            ]);
+            let response = mediator.handle_keylist_update(CLIENT_KEY, &update).await.unwrap();
+            let results: Vec<_> = response.content.updated.iter().map(|item| item.result).collect();
+            assert_eq!(
+                results,
+                vec![
+                    KeylistUpdateResult::NoChange,
+                    KeylistUpdateResult::ClientError,
+                    KeylistUpdateResult::Success,
+                    KeylistUpdateResult::Success
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_relays_forwards_on_live_delivery() {
+        SetupLibraryWallet::run(|setup| async move {
+            let mediator = make_mediator(Arc::new(IndySdkWallet::new(setup.wallet_handle)));
+            grant_with_key(&mediator).await;
+
+            let status = mediator
+                .handle_live_delivery_change(CLIENT_KEY, &build_live_delivery_change(true))
+                .await
+                .unwrap();
+            assert_eq!(status.content.live_delivery, Some(true));
+
+            let outcome = mediator.handle_forward(&make_forward(RECIPIENT_KEY)).await.unwrap();
+            assert!(matches!(outcome, ForwardOutcome::Relay { client_key, .. } if client_key == CLIENT_KEY));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mediator_receives_packed_messages() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, routing_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, client_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let receiver_keys = json!([routing_key]).to_string();
+
+            let mediator = Mediator::new(
+                Arc::clone(&wallet),
+                "https://mediator.example.org".parse().unwrap(),
+                vec![routing_key.clone()],
+                Arc::new(InMemoryMessageQueue::new()),
+            );
+
+            let request = AriesMessage::from(build_mediate_request());
+            let packed = wallet
+                .pack_message(
+                    Some(&client_key),
+                    &receiver_keys,
+                    &serde_json::to_vec(&request).unwrap(),
+                )
+                .await
+                .unwrap();
+            let MediatorOutcome::Reply {
+                client_key: sender,
+                message,
+            } = mediator.receive(packed).await.unwrap()
+            else {
+                panic!("expected a reply");
+            };
+            assert_eq!(sender, client_key);
+            assert!(matches!(
+                message,
+                AriesMessage::CoordinateMediation(CoordinateMediation::MediateGrant(_))
+            ));
+
+            let update = build_keylist_update(vec![KeylistUpdateItem::new(
+                RECIPIENT_KEY.to_owned(),
+                KeylistUpdateAction::Add,
+            )]);
+            mediator.handle_keylist_update(&client_key, &update).await.unwrap();
+
+            let forward = AriesMessage::from(make_forward(RECIPIENT_KEY));
+            let packed = wallet
+                .pack_message(None, &receiver_keys, &serde_json::to_vec(&forward).unwrap())
+                .await
+                .unwrap();
+            let outcome = mediator.receive(packed).await.unwrap();
+            assert!(matches!(
+                outcome,
+                MediatorOutcome::Forward(ForwardOutcome::Queued { client_key: owner, .. }) if owner == client_key
+            ));
+
+            let status_request = AriesMessage::from(build_status_request(None));
+            let packed = wallet
+                .pack_message(None, &receiver_keys, &serde_json::to_vec(&status_request).unwrap())
+                .await
+                .unwrap();
+            let err = mediator.receive(packed).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidMessageFormat);
+        })
+        .await;
+    }
+
+    #[cfg(feature = "ws_transport")]
+    #[tokio::test]
+    async fn test_mediator_returns_replies_over_websocket() {
+        use messages::decorators::transport::ReturnRoute;
+
+        use crate::transport::{
+            ws::{serve, WsTransport},
+            Transport as _,
+        };
+
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, mediator_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, client_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let receiver_keys = json!([mediator_key]).to_string();
+
+            let mediator = Mediator::new(
+                Arc::clone(&wallet),
+                "https://mediator.example.org".parse().unwrap(),
+                vec![mediator_key.clone()],
+                Arc::new(InMemoryMessageQueue::new()),
+            );
+            mediator
+                .handle_mediate_request(&client_key, &build_mediate_request())
+                .await
+                .unwrap();
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let endpoint = Url::parse(&format!("ws://{}/", listener.local_addr().unwrap())).unwrap();
+            tokio::spawn(serve(listener, Arc::new(mediator)));
+            let (transport, mut inbound) = WsTransport::new();
+
+            let unreturned = AriesMessage::from(build_status_request(None));
+            let packed = wallet
+                .pack_message(
+                    Some(&client_key),
+                    &receiver_keys,
+                    &serde_json::to_vec(&unreturned).unwrap(),
+                )
+                .await
+                .unwrap();
+            transport.send_message(packed, endpoint.clone()).await.unwrap();
+
+            let mut returned = build_status_request(None);
+            returned.decorators.transport = Some(Transport::new(ReturnRoute::All));
+            let packed = wallet
+                .pack_message(
+                    Some(&client_key),
+                    &receiver_keys,
+                    &serde_json::to_vec(&AriesMessage::from(returned.clone())).unwrap(),
+                )
+                .await
+                .unwrap();
+            transport.send_message(packed, endpoint).await.unwrap();
+
+            let reply = inbound.receive().await.unwrap();
+            let (reply, sender) = EncryptionEnvelope::anon_unpack(&wallet, reply).await.unwrap();
+            assert_eq!(sender, Some(mediator_key));
+            let AriesMessage::Pickup(Pickup::Status(status)) = reply else {
+                panic!("expected a status");
+            };
+            assert_eq!(status.decorators.thread.unwrap().thid, returned.id);
+            assert!(inbound.try_receive().is_none());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,281 @@
+use std::sync::{Arc, Mutex};
+
+use ::uuid::Uuid;
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+pub const QUEUED_MESSAGE_RECORD_TYPE: &str = "MediationQueuedMessage";
+
+const RECIPIENT_KEY_TAG: &str = "recipient_key";
+
+/// A packed message held by the mediator until its recipient picks it up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedMessage {
+    pub id: String,
+    pub recipient_key: String,
+    pub received_time: DateTime<Utc>,
+    pub message: Vec<u8>,
+}
+
+/// Storage of the packed messages a mediator keeps for its clients.
+#[async_trait]
+pub trait MessageQueue: std::fmt::Debug + Send + Sync {
+    /// Stores a message for the recipient key, returning the ID assigned to it.
+    async fn push(&self, recipient_key: &str, message: Vec<u8>) -> VcxResult<String>;
+
+    /// Returns the messages stored for any of the recipient keys, oldest first,
+    /// up to `limit` messages if one is given. Messages are not removed from the queue.
+    async fn messages(&self, recipient_keys: &[String], limit: Option<usize>) -> VcxResult<Vec<QueuedMessage>>;
+
+    /// Removes the messages with the given IDs, as long as they are stored for one of the recipient keys.
+    /// Unknown IDs are ignored.
+    async fn remove(&self, recipient_keys: &[String], message_ids: &[String]) -> VcxResult<()>;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryMessageQueue {
+    messages: Mutex<Vec<QueuedMessage>>,
+}
+
+impl InMemoryMessageQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> VcxResult<std::sync::MutexGuard<'_, Vec<QueuedMessage>>> {
+        self.messages.lock().map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Message queue lock is poisoned: {err}"),
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl MessageQueue for InMemoryMessageQueue {
+    async fn push(&self, recipient_key: &str, message: Vec<u8>) -> VcxResult<String> {
+        let id = Uuid::new_v4().to_string();
+
+        self.lock()?.push(QueuedMessage {
+            id: id.clone(),
+            recipient_key: recipient_key.to_owned(),
+            received_time: Utc::now(),
+            message,
+        });
+
+        Ok(id)
+    }
+
+    async fn messages(&self, recipient_keys: &[String], limit: Option<usize>) -> VcxResult<Vec<QueuedMessage>> {
+        Ok(self
+            .lock()?
+            .iter()
+            .filter(|msg| recipient_keys.contains(&msg.recipient_key))
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn remove(&self, recipient_keys: &[String], message_ids: &[String]) -> VcxResult<()> {
+        self.lock()?
+            .retain(|msg| !(recipient_keys.contains(&msg.recipient_key) && message_ids.contains(&msg.id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct StoredMessage {
+    recipient_key: String,
+    received_time: DateTime<Utc>,
+    message: String,
+}
+
+/// [`MessageQueue`] keeping messages as [`BaseWallet`] records, so they survive mediator restarts.
+#[derive(Debug, Clone)]
+pub struct WalletMessageQueue {
+    wallet: Arc<dyn BaseWallet>,
+}
+
+impl WalletMessageQueue {
+    pub fn new(wallet: Arc<dyn BaseWallet>) -> Self {
+        Self { wallet }
+    }
+
+    fn parse_record(record: &str) -> VcxResult<QueuedMessage> {
+        let record: Value = serde_json::from_str(record)?;
+
+        let (id, value) = match (record["id"].as_str(), record["value"].as_str()) {
+            (Some(id), Some(value)) => (id, value),
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Queued message record is missing its id or value: {record}"),
+                ))
+            }
+        };
+
+        let stored: StoredMessage = serde_json::from_str(value)?;
+        let message = base64::decode(&stored.message).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Queued message {id} is not base64 encoded: {err}"),
+            )
+        })?;
+
+        Ok(QueuedMessage {
+            id: id.to_owned(),
+            recipient_key: stored.recipient_key,
+            received_time: stored.received_time,
+            message,
+        })
+    }
+}
+
+#[async_trait]
+impl MessageQueue for WalletMessageQueue {
+    async fn push(&self, recipient_key: &str, message: Vec<u8>) -> VcxResult<String> {
+        let id = Uuid::new_v4().to_string();
+
+        let stored = StoredMessage {
+            recipient_key: recipient_key.to_owned(),
+            received_time: Utc::now(),
+            message: base64::encode(&message),
+        };
+        let value = serde_json::to_string(&stored)?;
+        let tags = json!({ RECIPIENT_KEY_TAG: recipient_key }).to_string();
+
+        self.wallet
+            .add_wallet_record(QUEUED_MESSAGE_RECORD_TYPE, &id, &value, Some(&tags))
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn messages(&self, recipient_keys: &[String], limit: Option<usize>) -> VcxResult<Vec<QueuedMessage>> {
+        if recipient_keys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let query = json!({ RECIPIENT_KEY_TAG: { "$in": recipient_keys } }).to_string();
+        let records = self
+            .wallet
+            .iterate_wallet_records(QUEUED_MESSAGE_RECORD_TYPE, &query, "{}")
+            .await?
+            .collect()
+            .await?;
+
+        let mut messages = records
+            .iter()
+            .map(|record| Self::parse_record(record))
+            .collect::<VcxResult<Vec<_>>>()?;
+
+        // Wallet searches don't preserve insertion order.
+        messages.sort_by_key(|msg| msg.received_time);
+        messages.truncate(limit.unwrap_or(usize::MAX));
+
+        Ok(messages)
+    }
+
+    async fn remove(&self, recipient_keys: &[String], message_ids: &[String]) -> VcxResult<()> {
+        for id in message_ids {
+            let value = match self
+                .wallet
+                .get_wallet_record_value(QUEUED_MESSAGE_RECORD_TYPE, id)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            let stored: StoredMessage = serde_json::from_str(&value)?;
+            if recipient_keys.contains(&stored.recipient_key) {
+                self.wallet.delete_wallet_record(QUEUED_MESSAGE_RECORD_TYPE, id).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_queue_keeps_order_and_scope() {
+        let queue = InMemoryMessageQueue::new();
+        let alice = vec!["alice_key".to_owned()];
+        let bob = vec!["bob_key".to_owned()];
+
+        let first = queue.push("alice_key", b"first".to_vec()).await.unwrap();
+        let second = queue.push("alice_key", b"second".to_vec()).await.unwrap();
+        queue.push("bob_key", b"other".to_vec()).await.unwrap();
+
+        let messages = queue.messages(&alice, None).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, first);
+        assert_eq!(messages[0].message, b"first".to_vec());
+        assert_eq!(messages[1].id, second);
+
+        let messages = queue.messages(&alice, Some(1)).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, first);
+
+        queue.remove(&bob, &[first.clone()]).await.unwrap();
+        assert_eq!(queue.messages(&alice, None).await.unwrap().len(), 2);
+
+        queue.remove(&alice, &[first, "unknown".to_owned()]).await.unwrap();
+        let messages = queue.messages(&alice, None).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, second);
+        assert_eq!(queue.messages(&bob, None).await.unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod integration_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+
+    use super::*;
+    use crate::utils::devsetup::SetupLibraryWallet;
+
+    #[tokio::test]
+    async fn test_wallet_queue_keeps_order_and_scope() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let queue = WalletMessageQueue::new(wallet);
+            let alice = vec!["alice_key".to_owned()];
+            let bob = vec!["bob_key".to_owned()];
+
+            let first = queue.push("alice_key", b"first".to_vec()).await.unwrap();
+            let second = queue.push("alice_key", b"second".to_vec()).await.unwrap();
+            queue.push("bob_key", b"other".to_vec()).await.unwrap();
+
+            let messages = queue.messages(&alice, None).await.unwrap();
+            assert_eq!(messages.len(), 2);
+            assert_eq!(messages[0].id, first);
+            assert_eq!(messages[0].recipient_key, "alice_key");
+            assert_eq!(messages[0].message, b"first".to_vec());
+            assert_eq!(messages[1].id, second);
+
+            queue.remove(&bob, &[first.clone()]).await.unwrap();
+            assert_eq!(queue.messages(&alice, None).await.unwrap().len(), 2);
+
+            queue.remove(&alice, &[first, "unknown".to_owned()]).await.unwrap();
+            let messages = queue.messages(&alice, None).await.unwrap();
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].id, second);
+        })
+        .await;
+    }
+}
@@ -7,9 +7,14 @@ use crate::protocols::proof_presentation::prover::state_machine::ProverState;
 use crate::protocols::proof_presentation::verifier::state_machine::VerifierState;
 
 pub mod connection;
+pub mod decorators;
 pub mod discovery;
+pub mod endorsement;
 pub mod issuance;
+pub mod mediation;
+pub mod mediator;
 pub mod out_of_band;
+pub mod pickup;
 pub mod proof_presentation;
 pub mod revocation_notification;
 pub mod trust_ping;
@@ -0,0 +1,184 @@
+use messages::msg_fields::protocols::pickup::{delivery::Delivery, status::Status};
+
+use crate::{
+    errors::error::VcxResult,
+    protocols::{
+        pickup::{
+            build_delivery_request, build_live_delivery_change, build_messages_received, build_status_request,
+            extract_delivered_messages,
+        },
+        SendClosure,
+    },
+};
+
+/// Recipient side of the [message pickup](https://github.com/hyperledger/aries-rfcs/blob/main/features/0685-pickup-v2/README.md)
+/// protocol, used to retrieve messages queued by a mediator.
+///
+/// Responses from the mediator are expected to come back through a return route,
+/// which is why the `handle_*` methods take the received messages as input.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PickupClient {
+    recipient_key: Option<String>,
+    message_count: Option<u32>,
+    live_delivery: bool,
+}
+
+impl PickupClient {
+    /// Creates a new [`PickupClient`], optionally restricted to messages
+    /// addressed to `recipient_key`.
+    pub fn new(recipient_key: Option<String>) -> Self {
+        Self {
+            recipient_key,
+            message_count: None,
+            live_delivery: false,
+        }
+    }
+
+    pub fn recipient_key(&self) -> Option<&str> {
+        self.recipient_key.as_deref()
+    }
+
+    /// The number of queued messages, as reported by the last status received.
+    pub fn message_count(&self) -> Option<u32> {
+        self.message_count
+    }
+
+    pub fn is_live_delivery(&self) -> bool {
+        self.live_delivery
+    }
+
+    pub async fn send_status_request(&self, send_message: SendClosure) -> VcxResult<()> {
+        let request = build_status_request(self.recipient_key.clone());
+        send_message(request.into()).await
+    }
+
+    pub fn handle_status(&mut self, status: &Status) -> u32 {
+        self.message_count = Some(status.content.message_count);
+
+        if let Some(live_delivery) = status.content.live_delivery {
+            self.live_delivery = live_delivery;
+        }
+
+        status.content.message_count
+    }
+
+    pub async fn send_delivery_request(&self, limit: u32, send_message: SendClosure) -> VcxResult<()> {
+        let request = build_delivery_request(limit, self.recipient_key.clone());
+        send_message(request.into()).await
+    }
+
+    /// Returns the packed messages of a [`Delivery`] along with their IDs.
+    ///
+    /// The mediator keeps the messages queued until they are acknowledged
+    /// through [`PickupClient::send_messages_received`].
+    pub fn handle_delivery(&mut self, delivery: &Delivery) -> VcxResult<Vec<(String, Vec<u8>)>> {
+        let messages = extract_delivered_messages(delivery)?;
+
+        if let Some(count) = self.message_count.as_mut() {
+            *count = count.saturating_sub(messages.len() as u32);
+        }
+
+        Ok(messages)
+    }
+
+    pub async fn send_messages_received(&self, message_ids: Vec<String>, send_message: SendClosure) -> VcxResult<()> {
+        let ack = build_messages_received(message_ids);
+        send_message(ack.into()).await
+    }
+
+    /// Asks the mediator to switch live delivery mode on or off.
+    ///
+    /// In live mode, the mediator pushes messages over the session as soon as they arrive
+    /// instead of queueing them, so it is only meaningful over a persistent transport.
+    pub async fn set_live_delivery(&mut self, live_delivery: bool, send_message: SendClosure) -> VcxResult<()> {
+        let change = build_live_delivery_change(live_delivery);
+        send_message(change.into()).await?;
+        self.live_delivery = live_delivery;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::{
+        decorators::attachment::{Attachment, AttachmentData, AttachmentType},
+        msg_fields::protocols::pickup::{
+            delivery::{DeliveryContent, DeliveryDecorators},
+            status::{StatusContent, StatusDecorators},
+        },
+        AriesMessage,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn _send_message() -> SendClosure {
+        Box::new(|_: AriesMessage| Box::pin(async { VcxResult::Ok(()) }))
+    }
+
+    fn _attachment(id: &str, content: AttachmentType) -> Attachment {
+        let mut attachment = Attachment::new(AttachmentData::new(content));
+        attachment.id = Some(id.to_owned());
+        attachment
+    }
+
+    #[test]
+    fn test_handle_status() {
+        let mut client = PickupClient::new(None);
+
+        let mut content = StatusContent::new(2);
+        content.live_delivery = Some(true);
+        let status = Status::with_decorators("status".to_owned(), content, StatusDecorators::default());
+
+        assert_eq!(client.handle_status(&status), 2);
+        assert_eq!(client.message_count(), Some(2));
+        assert!(client.is_live_delivery());
+    }
+
+    #[test]
+    fn test_handle_delivery() {
+        let mut client = PickupClient::new(Some("recipient_key".to_owned()));
+        let packed = json!({"protected": "test", "iv": "test", "ciphertext": "test", "tag": "test"});
+
+        let content = DeliveryContent::new(vec![
+            _attachment("msg_1", AttachmentType::Base64(base64::encode(packed.to_string()))),
+            _attachment("msg_2", AttachmentType::Json(packed.clone())),
+        ]);
+        let delivery = Delivery::with_decorators("delivery".to_owned(), content, DeliveryDecorators::default());
+
+        let messages = client.handle_delivery(&delivery).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, "msg_1");
+        assert_eq!(messages[1].0, "msg_2");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&messages[0].1).unwrap(),
+            packed
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&messages[1].1).unwrap(),
+            packed
+        );
+    }
+
+    #[test]
+    fn test_handle_delivery_without_attachment_id() {
+        let mut client = PickupClient::new(None);
+
+        let mut attachment = _attachment("msg_1", AttachmentType::Base64(base64::encode("{}")));
+        attachment.id = None;
+        let content = DeliveryContent::new(vec![attachment]);
+        let delivery = Delivery::with_decorators("delivery".to_owned(), content, DeliveryDecorators::default());
+
+        client.handle_delivery(&delivery).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_set_live_delivery() {
+        let mut client = PickupClient::new(None);
+
+        client.set_live_delivery(true, _send_message()).await.unwrap();
+        assert!(client.is_live_delivery());
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use agency_client::agency_client::AgencyClient;
+use chrono::Utc;
 use messages::msg_fields::protocols::present_proof::ack::AckPresentation;
 use messages::msg_fields::protocols::present_proof::present::Presentation;
 use messages::msg_fields::protocols::present_proof::propose::PresentationPreview;
@@ -12,6 +13,7 @@ use messages::AriesMessage;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::handlers::decorators::{handle_connection_message, without_deferred};
 use crate::handlers::util::{get_attach_as_string, PresentationProposalData};
 use crate::protocols::proof_presentation::prover::messages::ProverMessages;
 use crate::protocols::proof_presentation::prover::state_machine::{ProverSM, ProverState};
@@ -240,9 +242,14 @@ impl Prover {
         let send_message = connection.send_message_closure(profile).await?;
 
         let messages = connection.get_messages(agency_client).await?;
-        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
-            self.step(profile, msg.into(), Some(send_message)).await?;
-            connection.update_message_status(&uid, agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(without_deferred(messages, Utc::now())) {
+            let handled = handle_connection_message(profile, connection, msg, |msg| {
+                self.step(profile, msg.into(), Some(send_message))
+            })
+            .await?;
+            if handled {
+                connection.update_message_status(&uid, agency_client).await?;
+            }
         }
         Ok(self.get_state())
     }
@@ -3,15 +3,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use agency_client::agency_client::AgencyClient;
+use chrono::{DateTime, Utc};
 use messages::msg_fields::protocols::present_proof::present::Presentation;
 use messages::msg_fields::protocols::present_proof::propose::ProposePresentation;
 use messages::msg_fields::protocols::present_proof::request::RequestPresentation;
+use messages::msg_fields::protocols::report_problem::ProblemReport;
 use messages::AriesMessage;
 
 use crate::common::proofs::proof_request::PresentationRequestData;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::handlers::decorators::{answered_in_time, handle_connection_message, time_out, without_deferred};
 use crate::handlers::util::get_attach_as_string;
 use crate::protocols::proof_presentation::verifier::messages::VerifierMessages;
 use crate::protocols::proof_presentation::verifier::state_machine::{VerifierSM, VerifierState};
@@ -125,6 +128,12 @@ impl Verifier {
         Ok(())
     }
 
+    pub fn set_request_expiry(&mut self, expires_time: DateTime<Utc>) -> VcxResult<()> {
+        trace!("Verifier::set_request_expiry >>> expires_time: {:?}", expires_time);
+        self.verifier_sm = self.verifier_sm.clone().set_request_expiry(expires_time)?;
+        Ok(())
+    }
+
     pub fn get_presentation_request_msg(&self) -> VcxResult<RequestPresentation> {
         self.verifier_sm.presentation_request_msg()
     }
@@ -191,6 +200,14 @@ impl Verifier {
         Ok(())
     }
 
+    /// Finishes the thread if the presentation request expired before a presentation was received,
+    /// returning the problem report to send to the prover.
+    fn time_out(&mut self) -> Option<ProblemReport> {
+        let (verifier_sm, problem_report) = time_out(self.verifier_sm.clone(), Utc::now());
+        self.verifier_sm = verifier_sm;
+        problem_report
+    }
+
     pub async fn update_state(
         &mut self,
         profile: &Arc<dyn Profile>,
@@ -204,9 +221,30 @@ impl Verifier {
         let send_message = connection.send_message_closure(profile).await?;
 
         let messages = connection.get_messages(agency_client).await?;
-        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
-            self.step(profile, msg.into(), Some(send_message)).await?;
-            connection.update_message_status(&uid, agency_client).await?;
+        let received_time = Utc::now();
+        let message = self.find_message_to_handle(without_deferred(messages, received_time));
+        // an answer sent before the expiry is handled even if polled after it
+        let answered = match &message {
+            Some((_, msg)) => answered_in_time(&self.verifier_sm, msg, received_time)?,
+            None => false,
+        };
+        if !answered {
+            if let Some(problem_report) = self.time_out() {
+                send_message(problem_report.into()).await?;
+                if let Some((uid, _)) = &message {
+                    connection.update_message_status(uid, agency_client).await?;
+                }
+                return Ok(self.get_state());
+            }
+        }
+        if let Some((uid, msg)) = message {
+            let handled = handle_connection_message(profile, connection, msg, |msg| {
+                self.step(profile, msg.into(), Some(send_message))
+            })
+            .await?;
+            if handled {
+                connection.update_message_status(&uid, agency_client).await?;
+            }
         }
         Ok(self.get_state())
     }
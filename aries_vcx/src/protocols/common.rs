@@ -1,9 +1,19 @@
 use messages::{
-    decorators::thread::Thread,
-    msg_fields::protocols::report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    decorators::{
+        attachment::{Attachment, AttachmentData, AttachmentType},
+        thread::Thread,
+    },
+    misc::MimeType,
+    msg_fields::protocols::{
+        common::attachment_format_id::AttachmentFormatSpecifier,
+        report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    },
 };
+use shared_vcx::maybe_known::MaybeKnown;
 use uuid::Uuid;
 
+use crate::errors::error::prelude::*;
+
 pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> ProblemReport {
     let id = Uuid::new_v4().to_string();
     let content = ProblemReportContent::new(comment.unwrap_or_default());
@@ -14,6 +24,77 @@ pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> Pro
     ProblemReport::with_decorators(id, content, decorators)
 }
 
+/// Returns an error if `thread` does not belong to the protocol instance identified by `thread_id`.
+pub(crate) fn verify_thread_id(thread_id: &str, thread: Option<&Thread>) -> VcxResult<()> {
+    match thread {
+        Some(thread) if thread.thid == thread_id => Ok(()),
+        _ => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot handle message: thread id does not match, expected {thread_id}, received {thread:?}"),
+        )),
+    }
+}
+
+/// Looks up the attachment bound to `format` through the `formats` array of a message
+/// and returns its decoded content.
+pub(crate) fn extract_attachment_content<F>(
+    formats: &[AttachmentFormatSpecifier<F>],
+    attachments: &[Attachment],
+    format: &MaybeKnown<F>,
+) -> VcxResult<Vec<u8>>
+where
+    F: PartialEq + std::fmt::Debug,
+{
+    let attach_id = formats
+        .iter()
+        .find(|spec| &spec.format == format)
+        .map(|spec| spec.attach_id.as_str())
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Message does not contain an attachment of format {format:?}"),
+            )
+        })?;
+
+    let attachment = attachments
+        .iter()
+        .find(|attach| attach.id.as_deref() == Some(attach_id))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Message does not contain the attachment with id {attach_id}"),
+            )
+        })?;
+
+    match &attachment.data.content {
+        AttachmentType::Base64(encoded) => base64::decode(encoded).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Attachment {attach_id} is not base64 encoded: {err}"),
+            )
+        }),
+        AttachmentType::Json(json) => Ok(serde_json::to_vec(json)?),
+        _ => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            format!("Unsupported content for attachment {attach_id}"),
+        )),
+    }
+}
+
+/// Builds a base64 encoded JSON attachment along with the format specifier binding it to `format`.
+pub(crate) fn make_format_attachment<F>(
+    attach_id: String,
+    format: MaybeKnown<F>,
+    content: &[u8],
+) -> (AttachmentFormatSpecifier<F>, Attachment) {
+    let attach_type = AttachmentType::Base64(base64::encode(content));
+    let mut attachment = Attachment::new(AttachmentData::new(attach_type));
+    attachment.id = Some(attach_id.clone());
+    attachment.mime_type = Some(MimeType::Json);
+
+    (AttachmentFormatSpecifier::new(attach_id, format), attachment)
+}
+
 // #[cfg(test)]
 // mod test {
 //     use crate::protocols::common::build_problem_report_msg;
@@ -33,3 +114,30 @@ pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> Pro
 //         .unwrap());
 //     }
 // }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::OfferCredentialAttachmentFormatType;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_attachment_content() {
+        let format = MaybeKnown::Known(OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0);
+        let (spec, attachment) = make_format_attachment("offer-0".to_owned(), format.clone(), b"{}");
+
+        let content = extract_attachment_content(&[spec], &[attachment], &format).unwrap();
+        assert_eq!(content, b"{}");
+    }
+
+    #[test]
+    fn test_extract_attachment_content_fails_for_missing_format() {
+        let format = MaybeKnown::Known(OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0);
+        let other_format = MaybeKnown::Known(OfferCredentialAttachmentFormatType::AriesLdProofVcDetail1_0);
+        let (spec, attachment) = make_format_attachment("offer-0".to_owned(), format, b"{}");
+
+        let err = extract_attachment_content(&[spec], &[attachment], &other_format).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidMessageFormat);
+    }
+}
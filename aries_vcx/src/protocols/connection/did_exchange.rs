@@ -0,0 +1,82 @@
+//! Helpers shared by the [DID exchange](https://github.com/hyperledger/aries-rfcs/blob/main/features/0023-did-exchange/README.md)
+//! requester and responder connections.
+
+use std::{error::Error, sync::Arc};
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::did_exchange::problem_report::{
+        ProblemCode, ProblemReport, ProblemReportContent, ProblemReportDecorators,
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use super::{wrap_and_send_msg, Connection};
+use crate::transport::Transport;
+
+/// Builds the [`AriesDidDoc`] advertised to the counterparty for the given verkey.
+pub(super) fn build_own_did_doc(
+    did: String,
+    verkey: String,
+    service_endpoint: Url,
+    routing_keys: Vec<String>,
+) -> AriesDidDoc {
+    let mut did_doc = AriesDidDoc::default();
+    did_doc.set_id(did);
+    did_doc.set_service_endpoint(service_endpoint);
+    did_doc.set_routing_keys(routing_keys);
+    did_doc.set_recipient_keys(vec![verkey]);
+    did_doc
+}
+
+impl<I, S> Connection<I, S> {
+    fn create_did_exchange_problem_report<E>(
+        &self,
+        problem_code: ProblemCode,
+        err: &E,
+        thread_id: &str,
+    ) -> ProblemReport
+    where
+        E: Error,
+    {
+        let content = ProblemReportContent {
+            problem_code: Some(problem_code),
+            explain: Some(err.to_string()),
+        };
+
+        let mut decorators = ProblemReportDecorators::new(Thread::new(thread_id.to_owned()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+
+    /// Sends a DID exchange problem report, logging (but otherwise ignoring) any error encountered.
+    pub(super) async fn send_did_exchange_problem_report<E, T>(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        problem_code: ProblemCode,
+        err: &E,
+        thread_id: &str,
+        did_doc: &AriesDidDoc,
+        transport: &T,
+    ) where
+        E: Error,
+        T: Transport,
+    {
+        let sender_verkey = &self.pairwise_info().pw_vk;
+        let problem_report = self.create_did_exchange_problem_report(problem_code, err, thread_id);
+        let res = wrap_and_send_msg(wallet, &problem_report.into(), sender_verkey, did_doc, transport).await;
+
+        if let Err(e) = res {
+            trace!("Error encountered when sending ProblemReport: {}", e);
+        } else {
+            info!("Error report sent!");
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use super::{GenericConnection, GenericState, InviteeState, InviterState};
+use super::{GenericConnection, GenericState, InviteeState, InviterState, RequesterState, ResponderState};
 use crate::{
     errors::error::{AriesVcxError, AriesVcxErrorKind},
     protocols::connection::{
-        initiation_type::{Invitee, Inviter},
+        initiation_type::{Invitee, Inviter, Requester, Responder},
         invitee::states::{
             completed::Completed as InviteeCompleted, initial::Initial as InviteeInitial,
             invited::Invited as InviteeInvited, requested::Requested as InviteeRequested,
@@ -13,6 +13,15 @@ use crate::{
             invited::Invited as InviterInvited, requested::Requested as InviterRequested,
             responded::Responded as InviterResponded,
         },
+        requester::states::{
+            completed::Completed as RequesterCompleted, initial::Initial as RequesterInitial,
+            invited::Invited as RequesterInvited, requested::Requested as RequesterRequested,
+            responded::Responded as RequesterResponded,
+        },
+        responder::states::{
+            completed::Completed as ResponderCompleted, initial::Initial as ResponderInitial,
+            requested::Requested as ResponderRequested, responded::Responded as ResponderResponded,
+        },
         Connection,
     },
 };
@@ -45,40 +54,42 @@ macro_rules! from_concrete_to_vague {
 /// Macro used for boilerplace implementation of the
 /// [`TryFrom`] trait from a vague connection state to a concrete state.
 macro_rules! try_from_vague_to_concrete {
-    ($from:ident, $var:ident, $to:ident) => {
-        impl TryFrom<$from> for $to {
+    (GenericState::$good_var:ident, $state:ident, $init_type:ident) => {
+        impl<S> TryFrom<GenericState> for ($init_type, S)
+        where
+            S: TryFrom<$state, Error = AriesVcxError>,
+        {
             type Error = AriesVcxError;
 
-            fn try_from(value: $from) -> Result<Self, Self::Error> {
+            fn try_from(value: GenericState) -> Result<Self, Self::Error> {
                 match value {
-                    $from::$var(s) => Ok(s),
+                    GenericState::$good_var(s) => S::try_from(s).map(|s| ($init_type, s)),
                     _ => Err(AriesVcxError::from_msg(
                         AriesVcxErrorKind::InvalidState,
-                        format!("unexpected connection state: {:?}!", value),
+                        format!(
+                            concat!(
+                                "Expected ",
+                                stringify!(GenericState::$good_var),
+                                " connection state, found {:?}"
+                            ),
+                            value
+                        ),
                     )),
                 }
             }
         }
     };
 
-    ($state:ident, $good_var:ident, $bad_var:ident, $init_type:ident) => {
-        impl<S> TryFrom<GenericState> for ($init_type, S)
-        where
-            S: TryFrom<$state, Error = AriesVcxError>,
-        {
+    ($from:ident, $var:ident, $to:ident) => {
+        impl TryFrom<$from> for $to {
             type Error = AriesVcxError;
 
-            fn try_from(value: GenericState) -> Result<Self, Self::Error> {
+            fn try_from(value: $from) -> Result<Self, Self::Error> {
                 match value {
-                    GenericState::$good_var(s) => S::try_from(s).map(|s| ($init_type, s)),
-                    GenericState::$bad_var(_) => Err(AriesVcxError::from_msg(
+                    $from::$var(s) => Ok(s),
+                    _ => Err(AriesVcxError::from_msg(
                         AriesVcxErrorKind::InvalidState,
-                        concat!(
-                            "Expected ",
-                            stringify!(VagueState::$good_var),
-                            "connection state, found ",
-                            stringify!(VagueState::$bad_var),
-                        ),
+                        format!("unexpected connection state: {:?}!", value),
                     )),
                 }
             }
@@ -103,6 +114,8 @@ where
 
 from_concrete_to_vague!(Inviter, InviterState, Inviter, GenericState);
 from_concrete_to_vague!(Invitee, InviteeState, Invitee, GenericState);
+from_concrete_to_vague!(Requester, RequesterState, Requester, GenericState);
+from_concrete_to_vague!(Responder, ResponderState, Responder, GenericState);
 
 from_concrete_to_vague!(InviterInitial, Initial, InviterState);
 from_concrete_to_vague!(InviterInvited, Invited, InviterState);
@@ -116,6 +129,17 @@ from_concrete_to_vague!(InviteeRequested, Requested, InviteeState);
 from_concrete_to_vague!(InviteeResponded, Responded, InviteeState);
 from_concrete_to_vague!(InviteeCompleted, Completed, InviteeState);
 
+from_concrete_to_vague!(RequesterInitial, Initial, RequesterState);
+from_concrete_to_vague!(RequesterInvited, Invited, RequesterState);
+from_concrete_to_vague!(RequesterRequested, Requested, RequesterState);
+from_concrete_to_vague!(RequesterResponded, Responded, RequesterState);
+from_concrete_to_vague!(RequesterCompleted, Completed, RequesterState);
+
+from_concrete_to_vague!(ResponderInitial, Initial, ResponderState);
+from_concrete_to_vague!(ResponderRequested, Requested, ResponderState);
+from_concrete_to_vague!(ResponderResponded, Responded, ResponderState);
+from_concrete_to_vague!(ResponderCompleted, Completed, ResponderState);
+
 // ---------------------------- Try From Vague State to Concrete State implementations ----------------------------
 impl<I, S> TryFrom<GenericConnection> for Connection<I, S>
 where
@@ -130,8 +154,10 @@ where
     }
 }
 
-try_from_vague_to_concrete!(InviterState, Inviter, Invitee, Inviter);
-try_from_vague_to_concrete!(InviteeState, Invitee, Inviter, Invitee);
+try_from_vague_to_concrete!(GenericState::Inviter, InviterState, Inviter);
+try_from_vague_to_concrete!(GenericState::Invitee, InviteeState, Invitee);
+try_from_vague_to_concrete!(GenericState::Requester, RequesterState, Requester);
+try_from_vague_to_concrete!(GenericState::Responder, ResponderState, Responder);
 
 try_from_vague_to_concrete!(InviterState, Initial, InviterInitial);
 try_from_vague_to_concrete!(InviterState, Invited, InviterInvited);
@@ -144,3 +170,14 @@ try_from_vague_to_concrete!(InviteeState, Invited, InviteeInvited);
 try_from_vague_to_concrete!(InviteeState, Requested, InviteeRequested);
 try_from_vague_to_concrete!(InviteeState, Responded, InviteeResponded);
 try_from_vague_to_concrete!(InviteeState, Completed, InviteeCompleted);
+
+try_from_vague_to_concrete!(RequesterState, Initial, RequesterInitial);
+try_from_vague_to_concrete!(RequesterState, Invited, RequesterInvited);
+try_from_vague_to_concrete!(RequesterState, Requested, RequesterRequested);
+try_from_vague_to_concrete!(RequesterState, Responded, RequesterResponded);
+try_from_vague_to_concrete!(RequesterState, Completed, RequesterCompleted);
+
+try_from_vague_to_concrete!(ResponderState, Initial, ResponderInitial);
+try_from_vague_to_concrete!(ResponderState, Requested, ResponderRequested);
+try_from_vague_to_concrete!(ResponderState, Responded, ResponderResponded);
+try_from_vague_to_concrete!(ResponderState, Completed, ResponderCompleted);
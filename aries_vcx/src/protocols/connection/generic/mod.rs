@@ -24,6 +24,15 @@ use crate::{
             responded::Responded as InviterResponded,
         },
         pairwise_info::PairwiseInfo,
+        requester::states::{
+            completed::Completed as RequesterCompleted, initial::Initial as RequesterInitial,
+            invited::Invited as RequesterInvited, requested::Requested as RequesterRequested,
+            responded::Responded as RequesterResponded,
+        },
+        responder::states::{
+            completed::Completed as ResponderCompleted, initial::Initial as ResponderInitial,
+            requested::Requested as ResponderRequested, responded::Responded as ResponderResponded,
+        },
         trait_bounds::{TheirDidDoc, ThreadId},
     },
     transport::Transport,
@@ -81,6 +90,8 @@ pub struct GenericConnection {
 pub enum GenericState {
     Inviter(InviterState),
     Invitee(InviteeState),
+    Requester(RequesterState),
+    Responder(ResponderState),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -101,6 +112,23 @@ pub enum InviteeState {
     Completed(InviteeCompleted),
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RequesterState {
+    Initial(RequesterInitial),
+    Invited(RequesterInvited),
+    Requested(RequesterRequested),
+    Responded(RequesterResponded),
+    Completed(RequesterCompleted),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ResponderState {
+    Initial(ResponderInitial),
+    Requested(ResponderRequested),
+    Responded(ResponderResponded),
+    Completed(ResponderCompleted),
+}
+
 impl GenericConnection {
     /// Returns the underlying [`super::Connection`]'s state as a [`ThinState`].
     /// Used for pattern matching when there's no hint as to what connection type
@@ -121,6 +149,15 @@ impl GenericConnection {
             GenericState::Inviter(InviterState::Requested(s)) => Some(s.thread_id()),
             GenericState::Inviter(InviterState::Responded(s)) => Some(s.thread_id()),
             GenericState::Inviter(InviterState::Completed(s)) => Some(s.thread_id()),
+            GenericState::Requester(RequesterState::Initial(_)) => None,
+            GenericState::Requester(RequesterState::Invited(s)) => Some(s.thread_id()),
+            GenericState::Requester(RequesterState::Requested(s)) => Some(s.thread_id()),
+            GenericState::Requester(RequesterState::Responded(s)) => Some(s.thread_id()),
+            GenericState::Requester(RequesterState::Completed(s)) => Some(s.thread_id()),
+            GenericState::Responder(ResponderState::Initial(_)) => None,
+            GenericState::Responder(ResponderState::Requested(s)) => Some(s.thread_id()),
+            GenericState::Responder(ResponderState::Responded(s)) => Some(s.thread_id()),
+            GenericState::Responder(ResponderState::Completed(s)) => Some(s.thread_id()),
         }
     }
 
@@ -140,17 +177,31 @@ impl GenericConnection {
             GenericState::Inviter(InviterState::Requested(s)) => Some(s.their_did_doc()),
             GenericState::Inviter(InviterState::Responded(s)) => Some(s.their_did_doc()),
             GenericState::Inviter(InviterState::Completed(s)) => Some(s.their_did_doc()),
+            GenericState::Requester(RequesterState::Initial(_)) => None,
+            GenericState::Requester(RequesterState::Invited(s)) => Some(s.their_did_doc()),
+            GenericState::Requester(RequesterState::Requested(s)) => Some(s.their_did_doc()),
+            GenericState::Requester(RequesterState::Responded(s)) => Some(s.their_did_doc()),
+            GenericState::Requester(RequesterState::Completed(s)) => Some(s.their_did_doc()),
+            GenericState::Responder(ResponderState::Initial(_)) => None,
+            GenericState::Responder(ResponderState::Requested(s)) => Some(s.their_did_doc()),
+            GenericState::Responder(ResponderState::Responded(s)) => Some(s.their_did_doc()),
+            GenericState::Responder(ResponderState::Completed(s)) => Some(s.their_did_doc()),
         }
     }
 
     pub fn bootstrap_did_doc(&self) -> Option<&AriesDidDoc> {
         match &self.state {
-            GenericState::Inviter(_) => None,
+            GenericState::Inviter(_) | GenericState::Responder(_) => None,
             GenericState::Invitee(InviteeState::Initial(_)) => None,
             GenericState::Invitee(InviteeState::Invited(s)) => Some(s.bootstrap_did_doc()),
             GenericState::Invitee(InviteeState::Requested(s)) => Some(s.bootstrap_did_doc()),
             GenericState::Invitee(InviteeState::Responded(s)) => Some(s.bootstrap_did_doc()),
             GenericState::Invitee(InviteeState::Completed(s)) => Some(s.bootstrap_did_doc()),
+            GenericState::Requester(RequesterState::Initial(_)) => None,
+            GenericState::Requester(RequesterState::Invited(s)) => Some(s.bootstrap_did_doc()),
+            GenericState::Requester(RequesterState::Requested(s)) => Some(s.bootstrap_did_doc()),
+            GenericState::Requester(RequesterState::Responded(s)) => Some(s.bootstrap_did_doc()),
+            GenericState::Requester(RequesterState::Completed(s)) => Some(s.bootstrap_did_doc()),
         }
     }
 
@@ -209,6 +260,7 @@ mod connection_serde_tests {
 
     use async_trait::async_trait;
     use chrono::Utc;
+    use diddoc_legacy::aries::service::AriesService;
     use messages::decorators::thread::Thread;
     use messages::decorators::timing::Timing;
     use messages::msg_fields::protocols::connection::invitation::{
@@ -217,16 +269,32 @@ mod connection_serde_tests {
     use messages::msg_fields::protocols::connection::request::{Request, RequestContent, RequestDecorators};
     use messages::msg_fields::protocols::connection::response::{Response, ResponseContent, ResponseDecorators};
     use messages::msg_fields::protocols::connection::ConnectionData;
+    use messages::msg_fields::protocols::did_exchange::complete::{Complete, CompleteContent, CompleteDecorators};
+    use messages::msg_fields::protocols::did_exchange::request::{
+        Request as DidExchangeRequest, RequestContent as DidExchangeRequestContent,
+        RequestDecorators as DidExchangeRequestDecorators,
+    };
+    use messages::msg_fields::protocols::did_exchange::response::{
+        Response as DidExchangeResponse, ResponseContent as DidExchangeResponseContent,
+        ResponseDecorators as DidExchangeResponseDecorators,
+    };
     use messages::msg_fields::protocols::notification::ack::{Ack, AckContent, AckDecorators, AckStatus};
+    use messages::msg_fields::protocols::out_of_band::invitation::{
+        Invitation as OobInvitation, InvitationContent as OobInvitationContent,
+        InvitationDecorators as OobInvitationDecorators, OobService,
+    };
     use url::Url;
     use uuid::Uuid;
 
     use super::*;
-    use crate::common::signing::sign_connection_response;
+    use crate::common::signing::{sign_connection_response, sign_did_doc_attachment};
     use crate::core::profile::profile::Profile;
     use crate::handlers::util::AnyInvitation;
     use crate::protocols::connection::serializable::*;
-    use crate::protocols::connection::{invitee::InviteeConnection, inviter::InviterConnection, Connection};
+    use crate::protocols::connection::{
+        invitee::InviteeConnection, inviter::InviterConnection, requester::RequesterConnection,
+        responder::ResponderConnection, Connection,
+    };
     use crate::utils::mockdata::profile::mock_profile::MockProfile;
     use std::sync::Arc;
 
@@ -254,11 +322,36 @@ mod connection_serde_tests {
         }
     }
 
+    impl<'a> From<RefRequesterState<'a>> for RequesterState {
+        fn from(value: RefRequesterState<'a>) -> Self {
+            match value {
+                RefRequesterState::Initial(s) => Self::Initial(s.to_owned()),
+                RefRequesterState::Invited(s) => Self::Invited(s.to_owned()),
+                RefRequesterState::Requested(s) => Self::Requested(s.to_owned()),
+                RefRequesterState::Responded(s) => Self::Responded(s.to_owned()),
+                RefRequesterState::Completed(s) => Self::Completed(s.to_owned()),
+            }
+        }
+    }
+
+    impl<'a> From<RefResponderState<'a>> for ResponderState {
+        fn from(value: RefResponderState<'a>) -> Self {
+            match value {
+                RefResponderState::Initial(s) => Self::Initial(s.to_owned()),
+                RefResponderState::Requested(s) => Self::Requested(s.to_owned()),
+                RefResponderState::Responded(s) => Self::Responded(s.to_owned()),
+                RefResponderState::Completed(s) => Self::Completed(s.to_owned()),
+            }
+        }
+    }
+
     impl<'a> From<RefState<'a>> for GenericState {
         fn from(value: RefState<'a>) -> Self {
             match value {
                 RefState::Invitee(s) => Self::Invitee(s.into()),
                 RefState::Inviter(s) => Self::Inviter(s.into()),
+                RefState::Requester(s) => Self::Requester(s.into()),
+                RefState::Responder(s) => Self::Responder(s.into()),
             }
         }
     }
@@ -303,11 +396,36 @@ mod connection_serde_tests {
         }
     }
 
+    impl<'a> From<&'a RequesterState> for RefRequesterState<'a> {
+        fn from(value: &'a RequesterState) -> Self {
+            match value {
+                RequesterState::Initial(s) => Self::Initial(s),
+                RequesterState::Invited(s) => Self::Invited(s),
+                RequesterState::Requested(s) => Self::Requested(s),
+                RequesterState::Responded(s) => Self::Responded(s),
+                RequesterState::Completed(s) => Self::Completed(s),
+            }
+        }
+    }
+
+    impl<'a> From<&'a ResponderState> for RefResponderState<'a> {
+        fn from(value: &'a ResponderState) -> Self {
+            match value {
+                ResponderState::Initial(s) => Self::Initial(s),
+                ResponderState::Requested(s) => Self::Requested(s),
+                ResponderState::Responded(s) => Self::Responded(s),
+                ResponderState::Completed(s) => Self::Completed(s),
+            }
+        }
+    }
+
     impl<'a> From<&'a GenericState> for RefState<'a> {
         fn from(value: &'a GenericState) -> Self {
             match value {
                 GenericState::Invitee(s) => Self::Invitee(s.into()),
                 GenericState::Inviter(s) => Self::Inviter(s.into()),
+                GenericState::Requester(s) => Self::Requester(s.into()),
+                GenericState::Responder(s) => Self::Responder(s.into()),
             }
         }
     }
@@ -504,6 +622,127 @@ mod connection_serde_tests {
         con.acknowledge_connection(&msg).unwrap()
     }
 
+    fn make_did_doc(did: &str) -> AriesDidDoc {
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id(did.to_owned());
+        did_doc.set_service_endpoint(SERVICE_ENDPOINT.parse().unwrap());
+        did_doc.set_recipient_keys(vec![PW_KEY.to_owned()]);
+        did_doc.set_routing_keys(Vec::new());
+        did_doc
+    }
+
+    async fn make_requester_initial() -> RequesterConnection<RequesterInitial> {
+        let (source_id, pairwise_info) = make_initial_parts().await;
+        Connection::new_requester(source_id, pairwise_info)
+    }
+
+    async fn make_requester_invited() -> RequesterConnection<RequesterInvited> {
+        let profile = make_mock_profile();
+        let service = AriesService::create()
+            .set_service_endpoint(SERVICE_ENDPOINT.parse().unwrap())
+            .set_recipient_keys(vec![PW_KEY.to_owned()]);
+
+        let content = OobInvitationContent::new(vec![OobService::AriesService(service)]);
+        let decorators = OobInvitationDecorators::default();
+        let invitation = OobInvitation::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        make_requester_initial()
+            .await
+            .accept_invitation(&profile, invitation)
+            .await
+            .unwrap()
+    }
+
+    async fn make_requester_requested() -> RequesterConnection<RequesterRequested> {
+        let wallet = make_mock_profile().inject_wallet();
+        let service_endpoint = SERVICE_ENDPOINT.parse().unwrap();
+        let routing_keys = vec![];
+
+        make_requester_invited()
+            .await
+            .send_request(&wallet, service_endpoint, routing_keys, &MockTransport)
+            .await
+            .unwrap()
+    }
+
+    async fn make_requester_responded() -> RequesterConnection<RequesterResponded> {
+        let wallet = make_mock_profile().inject_wallet();
+        let con = make_requester_requested().await;
+
+        let mut content = DidExchangeResponseContent::new(PW_KEY.to_owned());
+        content.did_doc = Some(
+            sign_did_doc_attachment(&wallet, PW_KEY, &make_did_doc(PW_KEY))
+                .await
+                .unwrap(),
+        );
+
+        let decorators = DidExchangeResponseDecorators::new(Thread::new(con.thread_id().to_owned()));
+        let response = DidExchangeResponse::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        con.handle_response(&wallet, response, &MockTransport).await.unwrap()
+    }
+
+    async fn make_requester_completed() -> RequesterConnection<RequesterCompleted> {
+        let wallet = make_mock_profile().inject_wallet();
+
+        make_requester_responded()
+            .await
+            .send_complete(&wallet, &MockTransport)
+            .await
+            .unwrap()
+    }
+
+    async fn make_responder_initial() -> ResponderConnection<ResponderInitial> {
+        let (source_id, pairwise_info) = make_initial_parts().await;
+        Connection::new_responder(source_id, pairwise_info)
+    }
+
+    async fn make_responder_requested() -> ResponderConnection<ResponderRequested> {
+        let wallet = make_mock_profile().inject_wallet();
+        let con = make_responder_initial().await;
+        let new_service_endpoint = SERVICE_ENDPOINT.to_owned().parse().expect("url should be valid");
+        let new_routing_keys = vec![];
+
+        let mut content = DidExchangeRequestContent::new(String::new(), PW_KEY.to_owned());
+        content.did_doc = Some(
+            sign_did_doc_attachment(&wallet, PW_KEY, &make_did_doc(PW_KEY))
+                .await
+                .unwrap(),
+        );
+
+        let id = Uuid::new_v4().to_string();
+        let mut thread = Thread::new(id.clone());
+        thread.pthid = Some(Uuid::new_v4().to_string());
+
+        let mut decorators = DidExchangeRequestDecorators::default();
+        decorators.thread = Some(thread);
+
+        let request = DidExchangeRequest::with_decorators(id, content, decorators);
+
+        con.handle_request(&wallet, request, new_service_endpoint, new_routing_keys, &MockTransport)
+            .await
+            .unwrap()
+    }
+
+    async fn make_responder_responded() -> ResponderConnection<ResponderResponded> {
+        let wallet = make_mock_profile().inject_wallet();
+
+        make_responder_requested()
+            .await
+            .send_response(&wallet, &MockTransport)
+            .await
+            .unwrap()
+    }
+
+    async fn make_responder_completed() -> ResponderConnection<ResponderCompleted> {
+        let con = make_responder_responded().await;
+
+        let decorators = CompleteDecorators::new(Thread::new(con.thread_id().to_owned()));
+        let complete = Complete::with_decorators(Uuid::new_v4().to_string(), CompleteContent::default(), decorators);
+
+        con.handle_complete(complete).unwrap()
+    }
+
     macro_rules! generate_test {
         ($name:ident, $func:ident) => {
             #[tokio::test]
@@ -525,4 +764,15 @@ mod connection_serde_tests {
     generate_test!(inviter_connection_requested, make_inviter_requested);
     generate_test!(inviter_connection_responded, make_inviter_responded);
     generate_test!(inviter_connection_complete, make_inviter_completed);
+
+    generate_test!(requester_connection_initial, make_requester_initial);
+    generate_test!(requester_connection_invited, make_requester_invited);
+    generate_test!(requester_connection_requested, make_requester_requested);
+    generate_test!(requester_connection_responded, make_requester_responded);
+    generate_test!(requester_connection_complete, make_requester_completed);
+
+    generate_test!(responder_connection_initial, make_responder_initial);
+    generate_test!(responder_connection_requested, make_responder_requested);
+    generate_test!(responder_connection_responded, make_responder_responded);
+    generate_test!(responder_connection_complete, make_responder_completed);
 }
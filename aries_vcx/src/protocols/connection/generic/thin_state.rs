@@ -1,16 +1,18 @@
-use super::{GenericState, InviteeState, InviterState};
+use super::{GenericState, InviteeState, InviterState, RequesterState, ResponderState};
 
 /// Small sized enum used for determining
 /// a connection's state in terms of initiation type.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ThinState {
     Invitee(State),
     Inviter(State),
+    Requester(State),
+    Responder(State),
 }
 
 /// Small sized enum used for determining
 /// a connection's state in terms of connection stage.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum State {
     Initial,
     Invited,
@@ -24,6 +26,8 @@ impl From<&GenericState> for ThinState {
         match value {
             GenericState::Invitee(v) => Self::Invitee(v.into()),
             GenericState::Inviter(v) => Self::Inviter(v.into()),
+            GenericState::Requester(v) => Self::Requester(v.into()),
+            GenericState::Responder(v) => Self::Responder(v.into()),
         }
     }
 }
@@ -51,3 +55,26 @@ impl From<&InviteeState> for State {
         }
     }
 }
+
+impl From<&RequesterState> for State {
+    fn from(value: &RequesterState) -> Self {
+        match value {
+            RequesterState::Initial(_) => Self::Initial,
+            RequesterState::Invited(_) => Self::Invited,
+            RequesterState::Requested(_) => Self::Requested,
+            RequesterState::Responded(_) => Self::Responded,
+            RequesterState::Completed(_) => Self::Completed,
+        }
+    }
+}
+
+impl From<&ResponderState> for State {
+    fn from(value: &ResponderState) -> Self {
+        match value {
+            ResponderState::Initial(_) => Self::Initial,
+            ResponderState::Requested(_) => Self::Requested,
+            ResponderState::Responded(_) => Self::Responded,
+            ResponderState::Completed(_) => Self::Completed,
+        }
+    }
+}
@@ -5,3 +5,11 @@ pub struct Inviter;
 /// Unit struct illustrating that the connection was initiated by an invitee.
 #[derive(Clone, Copy, Debug)]
 pub struct Invitee;
+
+/// Unit struct illustrating that the connection was initiated by a DID exchange requester.
+#[derive(Clone, Copy, Debug)]
+pub struct Requester;
+
+/// Unit struct illustrating that the connection was initiated by a DID exchange responder.
+#[derive(Clone, Copy, Debug)]
+pub struct Responder;
@@ -1,8 +1,11 @@
+mod did_exchange;
 mod generic;
 pub mod initiation_type;
 pub mod invitee;
 pub mod inviter;
 pub mod pairwise_info;
+pub mod requester;
+pub mod responder;
 mod serializable;
 mod trait_bounds;
 
@@ -13,7 +16,7 @@ use messages::{
     decorators::{thread::Thread, timing::Timing},
     msg_fields::protocols::{
         connection::problem_report::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
-        discover_features::{disclose::Disclose, query::QueryContent, ProtocolDescriptor},
+        discover_features::{disclose::Disclose, ProtocolDescriptor},
     },
     AriesMessage,
 };
@@ -22,6 +25,7 @@ use uuid::Uuid;
 
 use crate::{
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::discovery::SupportedFeatures,
     transport::Transport,
     utils::encryption_envelope::EncryptionEnvelope,
 };
@@ -35,6 +39,9 @@ use self::{
 pub use self::generic::{GenericConnection, State, ThinState};
 
 /// A state machine for progressing through the [connection protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0160-connection-protocol/README.md).
+///
+/// The [`requester`] and [`responder`] initiation types progress through the
+/// [DID exchange protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0023-did-exchange/README.md) instead.
 #[derive(Clone, Deserialize)]
 #[serde(try_from = "GenericConnection")]
 #[serde(bound = "(I, S): TryFrom<GenericState, Error = AriesVcxError>")]
@@ -74,8 +81,7 @@ impl<I, S> Connection<I, S> {
     }
 
     pub fn protocols(&self) -> Vec<ProtocolDescriptor> {
-        let query = QueryContent::new("*".to_owned());
-        query.lookup()
+        SupportedFeatures::default().protocols("*")
     }
 }
 
@@ -0,0 +1,299 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        did_exchange::{
+            complete::{Complete, CompleteContent, CompleteDecorators},
+            problem_report::ProblemCode,
+            request::{Request, RequestContent, RequestDecorators},
+            response::Response,
+        },
+        out_of_band::invitation::Invitation as OobInvitation,
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{
+    completed::Completed, initial::Initial, invited::Invited, requested::Requested, responded::Responded,
+};
+use super::{
+    did_exchange::build_own_did_doc, initiation_type::Requester, pairwise_info::PairwiseInfo,
+    trait_bounds::BootstrapDidDoc, Connection,
+};
+use crate::{
+    common::{
+        ledger::transactions::{get_service, into_did_doc},
+        signing::{decode_signed_did_doc_attachment, sign_did_doc_attachment},
+    },
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::AnyInvitation,
+    transport::Transport,
+};
+
+/// Convenience alias
+pub type RequesterConnection<S> = Connection<Requester, S>;
+
+impl RequesterConnection<Initial> {
+    /// Creates a new [`RequesterConnection<Initial>`].
+    pub fn new_requester(source_id: String, pairwise_info: PairwiseInfo) -> Self {
+        Self {
+            source_id,
+            state: Initial,
+            pairwise_info,
+            initiation_type: Requester,
+        }
+    }
+
+    /// Accepts an out-of-band [`OobInvitation`] and transitions to [`RequesterConnection<Invited>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if a DidDoc could not be resolved from the [`OobInvitation`].
+    pub async fn accept_invitation(
+        self,
+        profile: &Arc<dyn Profile>,
+        invitation: OobInvitation,
+    ) -> VcxResult<RequesterConnection<Invited>> {
+        trace!("Connection::accept_invitation >>> invitation: {:?}", &invitation);
+
+        let invitation_id = invitation.id.clone();
+        let did_doc = into_did_doc(profile, &AnyInvitation::Oob(invitation)).await?;
+
+        Ok(Connection {
+            state: Invited::new(did_doc, invitation_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Requester,
+        })
+    }
+
+    /// Uses the public DID of the responder as an implicit invitation
+    /// and transitions to [`RequesterConnection<Invited>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the service of the public DID could not be resolved from the ledger.
+    pub async fn accept_implicit_invitation(
+        self,
+        profile: &Arc<dyn Profile>,
+        their_public_did: &str,
+    ) -> VcxResult<RequesterConnection<Invited>> {
+        trace!(
+            "Connection::accept_implicit_invitation >>> their_public_did: {}",
+            their_public_did
+        );
+
+        let invitation_id = match their_public_did.starts_with("did:") {
+            true => their_public_did.to_owned(),
+            false => format!("did:sov:{their_public_did}"),
+        };
+
+        let service = get_service(profile, &their_public_did.to_owned()).await?;
+
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id(invitation_id.clone());
+        did_doc.set_service_endpoint(service.service_endpoint);
+        did_doc.set_recipient_keys(service.recipient_keys);
+        did_doc.set_routing_keys(service.routing_keys);
+
+        Ok(Connection {
+            state: Invited::new(did_doc, invitation_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Requester,
+        })
+    }
+}
+
+impl RequesterConnection<Invited> {
+    /// Sends a [`Request`] to the responder and transitions to [`RequesterConnection<Requested>`].
+    ///
+    /// The request carries our DidDoc as an attachment signed with our pairwise verkey.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if signing the DidDoc or sending the request fails.
+    pub async fn send_request<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+        transport: &T,
+    ) -> VcxResult<RequesterConnection<Requested>>
+    where
+        T: Transport,
+    {
+        trace!("Connection::send_request");
+
+        let did_doc = build_own_did_doc(
+            self.pairwise_info.pw_did.clone(),
+            self.pairwise_info.pw_vk.clone(),
+            service_endpoint,
+            routing_keys,
+        );
+
+        let mut content = RequestContent::new(self.source_id.clone(), self.pairwise_info.pw_did.clone());
+        content.did_doc = Some(sign_did_doc_attachment(wallet, &self.pairwise_info.pw_vk, &did_doc).await?);
+
+        // Invitations, whether explicit or implicit, can be used by multiple requesters.
+        // The invitation ID is therefore the parent thread ID, while the request ID is the thread ID.
+        let id = Uuid::new_v4().to_string();
+        let mut thread = Thread::new(id.clone());
+        thread.pthid = Some(self.state.invitation_id.clone());
+
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+
+        let decorators = RequestDecorators {
+            thread: Some(thread),
+            timing: Some(timing),
+        };
+
+        let request = Request::with_decorators(id.clone(), content, decorators);
+
+        self.send_message(wallet, &request.into(), transport).await?;
+
+        Ok(Connection {
+            state: Requested::new(self.state.did_doc, id, self.state.invitation_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Requester,
+        })
+    }
+}
+
+impl RequesterConnection<Requested> {
+    /// Processes a [`Response`] from the responder and transitions to [`RequesterConnection<Responded>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if:
+    ///     * the thread ID of the response does not match the connection thread ID
+    ///     * no recipient verkeys are present in the invitation
+    ///     * the response has no attached DidDoc or the DidDoc is not signed with the invitation key
+    pub async fn handle_response<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        response: Response,
+        transport: &T,
+    ) -> VcxResult<RequesterConnection<Responded>>
+    where
+        T: Transport,
+    {
+        if response.decorators.thread.thid != self.state.thread_id {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle message {:?}: thread id does not match, expected {:?}",
+                    response, self.state.thread_id
+                ),
+            ));
+        }
+
+        let keys = self.state.did_doc.recipient_keys()?;
+        let invitation_key = keys.first().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            "Cannot handle response: invitation verkey not found",
+        ))?;
+
+        let res = match &response.content.did_doc {
+            Some(attachment) => decode_signed_did_doc_attachment(wallet, attachment, invitation_key).await,
+            None => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                "Cannot handle response: DID exchange responses without an attached DidDoc are not supported",
+            )),
+        };
+
+        let did_doc = match res {
+            Ok(did_doc) => did_doc,
+            Err(err) => {
+                error!("Response DidDoc validation failed! Sending ProblemReport...");
+
+                self.send_did_exchange_problem_report(
+                    wallet,
+                    ProblemCode::ResponseNotAccepted,
+                    &err,
+                    &self.state.thread_id,
+                    &self.state.did_doc,
+                    transport,
+                )
+                .await;
+
+                return Err(err);
+            }
+        };
+
+        let state = Responded::new(
+            did_doc,
+            self.state.did_doc,
+            self.state.thread_id,
+            self.state.invitation_id,
+        );
+
+        Ok(Connection {
+            state,
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Requester,
+        })
+    }
+}
+
+impl RequesterConnection<Responded> {
+    /// Sends a [`Complete`] message to the responder and transitions to [`RequesterConnection<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if sending the message fails.
+    pub async fn send_complete<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        transport: &T,
+    ) -> VcxResult<RequesterConnection<Completed>>
+    where
+        T: Transport,
+    {
+        let mut thread = Thread::new(self.state.thread_id.clone());
+        thread.pthid = Some(self.state.invitation_id.clone());
+
+        let mut decorators = CompleteDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let msg = Complete::with_decorators(Uuid::new_v4().to_string(), CompleteContent::default(), decorators);
+
+        self.send_message(wallet, &msg.into(), transport).await?;
+
+        let state = Completed::new(
+            self.state.did_doc,
+            self.state.bootstrap_did_doc,
+            self.state.thread_id,
+            None,
+        );
+
+        Ok(Connection {
+            state,
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Requester,
+        })
+    }
+}
+
+impl<S> RequesterConnection<S>
+where
+    S: BootstrapDidDoc,
+{
+    pub fn bootstrap_did_doc(&self) -> &AriesDidDoc {
+        self.state.bootstrap_did_doc()
+    }
+}
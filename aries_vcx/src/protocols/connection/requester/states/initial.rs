@@ -0,0 +1,2 @@
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Initial;
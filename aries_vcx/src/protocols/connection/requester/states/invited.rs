@@ -0,0 +1,30 @@
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+
+use crate::protocols::connection::trait_bounds::{BootstrapDidDoc, TheirDidDoc, ThreadId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Invited {
+    pub(crate) did_doc: AriesDidDoc,
+    /// The ID of the invitation, either explicit (out-of-band) or implicit (public DID).
+    pub(crate) invitation_id: String,
+}
+
+impl Invited {
+    pub fn new(did_doc: AriesDidDoc, invitation_id: String) -> Self {
+        Self { did_doc, invitation_id }
+    }
+}
+
+impl TheirDidDoc for Invited {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl BootstrapDidDoc for Invited {}
+
+impl ThreadId for Invited {
+    fn thread_id(&self) -> &str {
+        &self.invitation_id
+    }
+}
@@ -0,0 +1,5 @@
+pub mod completed;
+pub mod initial;
+pub mod invited;
+pub mod requested;
+pub mod responded;
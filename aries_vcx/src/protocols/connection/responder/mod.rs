@@ -0,0 +1,191 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::did_exchange::{
+        complete::Complete,
+        problem_report::ProblemCode,
+        request::Request,
+        response::{Response, ResponseContent, ResponseDecorators},
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{completed::Completed, initial::Initial, requested::Requested, responded::Responded};
+use super::{did_exchange::build_own_did_doc, initiation_type::Responder, pairwise_info::PairwiseInfo, Connection};
+use crate::{
+    common::signing::{decode_did_doc_attachment, sign_did_doc_attachment},
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    transport::Transport,
+};
+
+/// Convenience alias
+pub type ResponderConnection<S> = Connection<Responder, S>;
+
+impl ResponderConnection<Initial> {
+    /// Creates a new [`ResponderConnection<Initial>`].
+    ///
+    /// The provided [`PairwiseInfo`] must hold the key advertised to the requester,
+    /// be it the recipient key of an out-of-band invitation or the verkey of our public DID,
+    /// as the [`Response`] DidDoc gets signed with it.
+    pub fn new_responder(source_id: String, pairwise_info: PairwiseInfo) -> Self {
+        Self {
+            source_id,
+            state: Initial,
+            pairwise_info,
+            initiation_type: Responder,
+        }
+    }
+
+    /// Processes a [`Request`] and transitions to [`ResponderConnection<Requested>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if either:
+    ///     * the [`Request`] has no attached DidDoc or its signature is invalid
+    ///     * the [`Request`]'s DidDoc is not valid
+    ///     * generating new [`PairwiseInfo`] or signing the [`Response`] fails
+    pub async fn handle_request<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        request: Request,
+        new_service_endpoint: Url,
+        new_routing_keys: Vec<String>,
+        transport: &T,
+    ) -> VcxResult<ResponderConnection<Requested>>
+    where
+        T: Transport,
+    {
+        trace!(
+            "Connection::handle_request >>> request: {:?}, service_endpoint: {}, routing_keys: {:?}",
+            request,
+            new_service_endpoint,
+            new_routing_keys,
+        );
+
+        let thread_id = request
+            .decorators
+            .thread
+            .as_ref()
+            .map(|t| t.thid.clone())
+            .unwrap_or(request.id.clone());
+
+        // Without a DidDoc there's no way to reach the requester, not even with a ProblemReport.
+        let attachment = request.content.did_doc.as_ref().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Cannot handle request: DID exchange requests without an attached DidDoc are not supported",
+        ))?;
+        let did_doc = decode_did_doc_attachment(wallet, attachment).await?;
+
+        // If the request's DidDoc validation fails, we generate and send a ProblemReport.
+        // We then return early with the provided error.
+        if let Err(err) = did_doc.validate() {
+            error!("Request DidDoc validation failed! Sending ProblemReport...");
+
+            self.send_did_exchange_problem_report(
+                wallet,
+                ProblemCode::RequestNotAccepted,
+                &err,
+                &thread_id,
+                &did_doc,
+                transport,
+            )
+            .await;
+
+            Err(err)?;
+        }
+
+        // Generate new pairwise info that will be used from this point on
+        // and incorporate that into the response.
+        let new_pairwise_info = PairwiseInfo::create(wallet).await?;
+        let own_did_doc = build_own_did_doc(
+            new_pairwise_info.pw_did.clone(),
+            new_pairwise_info.pw_vk.clone(),
+            new_service_endpoint,
+            new_routing_keys,
+        );
+
+        // The DidDoc is signed with the invitation key so the requester can authenticate the response.
+        let mut content = ResponseContent::new(new_pairwise_info.pw_did.clone());
+        content.did_doc = Some(sign_did_doc_attachment(wallet, &self.pairwise_info.pw_vk, &own_did_doc).await?);
+
+        let mut decorators = ResponseDecorators::new(Thread::new(thread_id));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let response = Response::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(Connection {
+            state: Requested::new(response, did_doc),
+            source_id: self.source_id,
+            pairwise_info: new_pairwise_info,
+            initiation_type: Responder,
+        })
+    }
+}
+
+impl ResponderConnection<Requested> {
+    /// Sends a [`Response`] to the requester and transitions to [`ResponderConnection<Responded>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if sending the response fails.
+    pub async fn send_response<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        transport: &T,
+    ) -> VcxResult<ResponderConnection<Responded>>
+    where
+        T: Transport,
+    {
+        trace!(
+            "Connection::send_response >>> signed_response: {:?}",
+            &self.state.signed_response
+        );
+
+        let thread_id = self.state.signed_response.decorators.thread.thid.clone();
+
+        self.send_message(wallet, &self.state.signed_response.clone().into(), transport)
+            .await?;
+
+        Ok(Connection {
+            state: Responded::new(self.state.did_doc, thread_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Responder,
+        })
+    }
+}
+
+impl ResponderConnection<Responded> {
+    /// Processes a [`Complete`] message and transitions to [`ResponderConnection<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the message's thread ID does not match
+    /// the ID of the thread context used in this connection.
+    pub fn handle_complete(self, complete: Complete) -> VcxResult<ResponderConnection<Completed>> {
+        if complete.decorators.thread.thid != self.state.thread_id {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle message {:?}: thread id does not match, expected {:?}",
+                    complete, self.state.thread_id
+                ),
+            ));
+        }
+
+        Ok(Connection {
+            state: Completed::new(self.state.did_doc, self.state.thread_id, None),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Responder,
+        })
+    }
+}
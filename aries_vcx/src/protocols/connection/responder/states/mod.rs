@@ -0,0 +1,4 @@
+pub mod completed;
+pub mod initial;
+pub mod requested;
+pub mod responded;
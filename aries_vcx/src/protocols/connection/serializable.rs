@@ -1,7 +1,7 @@
 use serde::Serialize;
 
 use crate::protocols::connection::{
-    initiation_type::{Invitee, Inviter},
+    initiation_type::{Invitee, Inviter, Requester, Responder},
     invitee::states::{
         completed::Completed as InviteeCompleted, initial::Initial as InviteeInitial,
         invited::Invited as InviteeInvited, requested::Requested as InviteeRequested,
@@ -13,6 +13,15 @@ use crate::protocols::connection::{
         responded::Responded as InviterResponded,
     },
     pairwise_info::PairwiseInfo,
+    requester::states::{
+        completed::Completed as RequesterCompleted, initial::Initial as RequesterInitial,
+        invited::Invited as RequesterInvited, requested::Requested as RequesterRequested,
+        responded::Responded as RequesterResponded,
+    },
+    responder::states::{
+        completed::Completed as ResponderCompleted, initial::Initial as ResponderInitial,
+        requested::Requested as ResponderRequested, responded::Responded as ResponderResponded,
+    },
     Connection,
 };
 
@@ -57,6 +66,8 @@ pub struct SerializableConnection<'a> {
 pub enum RefState<'a> {
     Inviter(RefInviterState<'a>),
     Invitee(RefInviteeState<'a>),
+    Requester(RefRequesterState<'a>),
+    Responder(RefResponderState<'a>),
 }
 
 #[derive(Debug, Serialize)]
@@ -77,6 +88,23 @@ pub enum RefInviteeState<'a> {
     Completed(&'a InviteeCompleted),
 }
 
+#[derive(Debug, Serialize)]
+pub enum RefRequesterState<'a> {
+    Initial(&'a RequesterInitial),
+    Invited(&'a RequesterInvited),
+    Requested(&'a RequesterRequested),
+    Responded(&'a RequesterResponded),
+    Completed(&'a RequesterCompleted),
+}
+
+#[derive(Debug, Serialize)]
+pub enum RefResponderState<'a> {
+    Initial(&'a ResponderInitial),
+    Requested(&'a ResponderRequested),
+    Responded(&'a ResponderResponded),
+    Completed(&'a ResponderCompleted),
+}
+
 impl<'a, I, S> From<&'a Connection<I, S>> for SerializableConnection<'a>
 where
     RefState<'a>: From<(&'a I, &'a S)>,
@@ -91,6 +119,8 @@ where
 
 from_concrete_to_serializable!(Inviter, RefInviterState, Inviter, RefState);
 from_concrete_to_serializable!(Invitee, RefInviteeState, Invitee, RefState);
+from_concrete_to_serializable!(Requester, RefRequesterState, Requester, RefState);
+from_concrete_to_serializable!(Responder, RefResponderState, Responder, RefState);
 
 from_concrete_to_serializable!(InviterInitial, Initial, RefInviterState);
 from_concrete_to_serializable!(InviterInvited, Invited, RefInviterState);
@@ -104,6 +134,17 @@ from_concrete_to_serializable!(InviteeRequested, Requested, RefInviteeState);
 from_concrete_to_serializable!(InviteeResponded, Responded, RefInviteeState);
 from_concrete_to_serializable!(InviteeCompleted, Completed, RefInviteeState);
 
+from_concrete_to_serializable!(RequesterInitial, Initial, RefRequesterState);
+from_concrete_to_serializable!(RequesterInvited, Invited, RefRequesterState);
+from_concrete_to_serializable!(RequesterRequested, Requested, RefRequesterState);
+from_concrete_to_serializable!(RequesterResponded, Responded, RefRequesterState);
+from_concrete_to_serializable!(RequesterCompleted, Completed, RefRequesterState);
+
+from_concrete_to_serializable!(ResponderInitial, Initial, RefResponderState);
+from_concrete_to_serializable!(ResponderRequested, Requested, RefResponderState);
+from_concrete_to_serializable!(ResponderResponded, Responded, RefResponderState);
+from_concrete_to_serializable!(ResponderCompleted, Completed, RefResponderState);
+
 impl<'a> SerializableConnection<'a> {
     fn new(source_id: &'a str, pairwise_info: &'a PairwiseInfo, state: RefState<'a>) -> Self {
         Self {
@@ -0,0 +1,88 @@
+use ::uuid::Uuid;
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::coordinate_mediation::{
+        keylist::{Keylist, KeylistContent, KeylistDecorators, KeylistItem, KeylistPagination},
+        keylist_query::{KeylistQuery, KeylistQueryContent, KeylistQueryDecorators, KeylistQueryPaginate},
+        keylist_update::{KeylistUpdate, KeylistUpdateContent, KeylistUpdateDecorators, KeylistUpdateItem},
+        keylist_update_response::{
+            KeylistUpdateResponse, KeylistUpdateResponseContent, KeylistUpdateResponseDecorators,
+            KeylistUpdateResponseItem,
+        },
+        mediate_deny::{MediateDeny, MediateDenyContent, MediateDenyDecorators},
+        mediate_grant::{MediateGrant, MediateGrantContent, MediateGrantDecorators},
+        mediate_request::{MediateRequest, MediateRequestContent, MediateRequestDecorators},
+    },
+};
+use url::Url;
+
+fn make_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    timing
+}
+
+pub fn build_mediate_request() -> MediateRequest {
+    let mut decorators = MediateRequestDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    MediateRequest::with_decorators(Uuid::new_v4().to_string(), MediateRequestContent::default(), decorators)
+}
+
+pub fn build_keylist_update(updates: Vec<KeylistUpdateItem>) -> KeylistUpdate {
+    let content = KeylistUpdateContent::new(updates);
+
+    let mut decorators = KeylistUpdateDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    KeylistUpdate::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_keylist_query(paginate: Option<KeylistQueryPaginate>) -> KeylistQuery {
+    let mut content = KeylistQueryContent::default();
+    content.paginate = paginate;
+
+    let mut decorators = KeylistQueryDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    KeylistQuery::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_mediate_grant(thread_id: String, endpoint: Url, routing_keys: Vec<String>) -> MediateGrant {
+    let content = MediateGrantContent::new(endpoint, routing_keys);
+
+    let mut decorators = MediateGrantDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    MediateGrant::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_mediate_deny(thread_id: String) -> MediateDeny {
+    let mut decorators = MediateDenyDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    MediateDeny::with_decorators(Uuid::new_v4().to_string(), MediateDenyContent::default(), decorators)
+}
+
+pub fn build_keylist_update_response(
+    thread_id: String,
+    updated: Vec<KeylistUpdateResponseItem>,
+) -> KeylistUpdateResponse {
+    let content = KeylistUpdateResponseContent::new(updated);
+
+    let mut decorators = KeylistUpdateResponseDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    KeylistUpdateResponse::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_keylist(thread_id: String, keys: Vec<KeylistItem>, pagination: Option<KeylistPagination>) -> Keylist {
+    let mut content = KeylistContent::new(keys);
+    content.pagination = pagination;
+
+    let mut decorators = KeylistDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    Keylist::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
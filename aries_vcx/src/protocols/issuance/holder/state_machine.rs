@@ -45,7 +45,7 @@ pub enum HolderFullState {
     Finished(FinishedHolderState),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HolderState {
     Initial,
     ProposalSent,
@@ -7,7 +7,7 @@ use crate::handlers::util::{
     get_attach_as_string, make_attach_from_str, matches_opt_thread_id, matches_thread_id, AttachmentId, OfferInfo,
     Status,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use messages::decorators::please_ack::PleaseAck;
 use messages::decorators::thread::Thread;
 use messages::decorators::timing::Timing;
@@ -29,6 +29,7 @@ use uuid::Uuid;
 use crate::common::credentials::encoding::encode_attributes;
 use crate::common::credentials::is_cred_revoked;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::handlers::decorators::{set_expiry, ExpiringThread};
 use crate::protocols::common::build_problem_report_msg;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
 use crate::protocols::issuance::issuer::states::credential_sent::CredentialSentState;
@@ -52,7 +53,7 @@ pub enum IssuerFullState {
     Finished(FinishedState),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IssuerState {
     Initial,
     OfferSet,
@@ -407,6 +408,28 @@ impl IssuerSM {
         Ok(Self::step(source_id, thread_id, state))
     }
 
+    /// Declares when the offer expires, after which the thread is timed out if no request was received.
+    pub fn set_offer_expiry(self, expires_time: DateTime<Utc>) -> VcxResult<Self> {
+        let Self {
+            state,
+            source_id,
+            thread_id,
+        } = self;
+        let state = match state {
+            IssuerFullState::OfferSet(mut state) => {
+                set_expiry(&mut state.offer.decorators.timing, expires_time);
+                IssuerFullState::OfferSet(state)
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("Can not set_offer_expiry in current state {}.", state),
+                ))
+            }
+        };
+        Ok(Self::step(source_id, thread_id, state))
+    }
+
     pub fn receive_proposal(self, proposal: ProposeCredential) -> VcxResult<Self> {
         verify_thread_id(
             &self.thread_id,
@@ -576,6 +599,34 @@ impl IssuerSM {
     }
 }
 
+impl ExpiringThread for IssuerSM {
+    fn thread_id(&self) -> String {
+        self.thread_id.clone()
+    }
+
+    fn awaited_expiry(&self) -> Option<DateTime<Utc>> {
+        match &self.state {
+            IssuerFullState::OfferSent(state) => state.offer.decorators.timing.as_ref()?.expires_time,
+            _ => None,
+        }
+    }
+
+    fn answers_awaited(&self, message: &AriesMessage) -> bool {
+        matches!(
+            message,
+            AriesMessage::CredentialIssuance(CredentialIssuance::RequestCredential(_))
+        )
+    }
+
+    fn expire(self, problem_report: ProblemReport) -> Self {
+        let state = match self.state {
+            IssuerFullState::OfferSent(state) => IssuerFullState::Finished((state, problem_report).into()),
+            s => s,
+        };
+        Self { state, ..self }
+    }
+}
+
 async fn _create_credential(
     profile: &Arc<dyn Profile>,
     request: &RequestCredential,
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::{
+    IssueCredentialAttachmentFormatType, IssueCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::ProposeCredentialAttachmentFormatType;
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::RequestCredentialAttachmentFormatType;
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::HolderCredentialIssuanceFormat;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::global::settings;
+use crate::protocols::issuance_v2::formats::issuer::hyperledger_indy::HyperledgerIndyCredentialFilter;
+
+/// The `hlindy/...@v2.0` (AnonCreds over Hyperledger Indy) attachment formats, from the holder's perspective.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperledgerIndyHolderCredentialIssuanceFormat;
+
+#[derive(Debug, Clone)]
+pub struct HyperledgerIndyCreateRequestInput {
+    pub my_pairwise_did: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HyperledgerIndyCreatedRequestMetadata {
+    pub credential_request_metadata: String,
+    pub credential_def_json: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HyperledgerIndyStoredCredentialMetadata {
+    pub credential_id: String,
+}
+
+fn attachment_content_to_string(content: Vec<u8>, what: &str) -> VcxResult<String> {
+    String::from_utf8(content).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::EncodeError,
+            format!("{what} is not valid UTF-8: {err}"),
+        )
+    })
+}
+
+fn get_json_str_field(json: &str, field: &str) -> VcxResult<Option<String>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(value[field].as_str().map(ToOwned::to_owned))
+}
+
+#[async_trait]
+impl HolderCredentialIssuanceFormat for HyperledgerIndyHolderCredentialIssuanceFormat {
+    type CreateProposalInput = HyperledgerIndyCredentialFilter;
+
+    type CreateRequestInput = HyperledgerIndyCreateRequestInput;
+    type CreatedRequestMetadata = HyperledgerIndyCreatedRequestMetadata;
+
+    type StoredCredentialMetadata = HyperledgerIndyStoredCredentialMetadata;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType> {
+        MaybeKnown::Known(ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0)
+    }
+
+    fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType> {
+        MaybeKnown::Known(OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0)
+    }
+
+    fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType> {
+        MaybeKnown::Known(RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0)
+    }
+
+    fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType> {
+        MaybeKnown::Known(IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0)
+    }
+
+    async fn create_proposal_attachment_content(data: &HyperledgerIndyCredentialFilter) -> VcxResult<Vec<u8>> {
+        Ok(serde_json::to_vec(data)?)
+    }
+
+    async fn create_request_attachment_content(
+        profile: &Arc<dyn Profile>,
+        offer_message: &OfferCredentialV2,
+        data: &HyperledgerIndyCreateRequestInput,
+    ) -> VcxResult<(Vec<u8>, HyperledgerIndyCreatedRequestMetadata)> {
+        let offer_json = attachment_content_to_string(Self::extract_offer_attachment_content(offer_message)?, "Offer")?;
+
+        let cred_def_id = get_json_str_field(&offer_json, "cred_def_id")?.ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                "Credential offer does not contain a cred_def_id",
+            )
+        })?;
+
+        let ledger = Arc::clone(profile).inject_anoncreds_ledger_read();
+        let credential_def_json = ledger.get_cred_def(&cred_def_id, None).await?;
+
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let (credential_request, credential_request_metadata) = anoncreds
+            .prover_create_credential_req(
+                &data.my_pairwise_did,
+                &offer_json,
+                &credential_def_json,
+                settings::DEFAULT_LINK_SECRET_ALIAS,
+            )
+            .await?;
+
+        let metadata = HyperledgerIndyCreatedRequestMetadata {
+            credential_request_metadata,
+            credential_def_json,
+        };
+
+        Ok((credential_request.into_bytes(), metadata))
+    }
+
+    async fn process_and_store_credential(
+        profile: &Arc<dyn Profile>,
+        credential_message: &IssueCredentialV2,
+        request_metadata: &HyperledgerIndyCreatedRequestMetadata,
+    ) -> VcxResult<HyperledgerIndyStoredCredentialMetadata> {
+        let credential_json = attachment_content_to_string(
+            Self::extract_credential_attachment_content(credential_message)?,
+            "Credential",
+        )?;
+
+        let rev_reg_def_json = match get_json_str_field(&credential_json, "rev_reg_id")? {
+            Some(rev_reg_id) => {
+                let ledger = Arc::clone(profile).inject_anoncreds_ledger_read();
+                Some(ledger.get_rev_reg_def_json(&rev_reg_id).await?)
+            }
+            None => None,
+        };
+
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let credential_id = anoncreds
+            .prover_store_credential(
+                None,
+                &request_metadata.credential_request_metadata,
+                &credential_json,
+                &request_metadata.credential_def_json,
+                rev_reg_def_json.as_deref(),
+            )
+            .await?;
+
+        Ok(HyperledgerIndyStoredCredentialMetadata { credential_id })
+    }
+}
@@ -0,0 +1,67 @@
+pub mod hyperledger_indy;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::{
+    IssueCredentialAttachmentFormatType, IssueCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::ProposeCredentialAttachmentFormatType;
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::RequestCredentialAttachmentFormatType;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::VcxResult;
+use crate::protocols::common::extract_attachment_content;
+
+/// Trait representing some issue-credential/2.0 attachment format from the holder's perspective.
+#[async_trait]
+pub trait HolderCredentialIssuanceFormat {
+    type CreateProposalInput: Send + Sync;
+
+    type CreateRequestInput: Send + Sync;
+    type CreatedRequestMetadata: Serialize + DeserializeOwned + Clone + Debug + PartialEq + Send + Sync;
+
+    type StoredCredentialMetadata: Serialize + DeserializeOwned + Clone + Debug + PartialEq + Send + Sync;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType>;
+    fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType>;
+    fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType>;
+    fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType>;
+
+    fn extract_offer_attachment_content(offer_message: &OfferCredentialV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &offer_message.content.formats,
+            &offer_message.content.offers_attach,
+            &Self::get_offer_attachment_format(),
+        )
+    }
+
+    fn extract_credential_attachment_content(credential_message: &IssueCredentialV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &credential_message.content.formats,
+            &credential_message.content.credentials_attach,
+            &Self::get_credential_attachment_format(),
+        )
+    }
+
+    async fn create_proposal_attachment_content(data: &Self::CreateProposalInput) -> VcxResult<Vec<u8>>;
+
+    async fn create_request_attachment_content(
+        profile: &Arc<dyn Profile>,
+        offer_message: &OfferCredentialV2,
+        data: &Self::CreateRequestInput,
+    ) -> VcxResult<(Vec<u8>, Self::CreatedRequestMetadata)>;
+
+    async fn process_and_store_credential(
+        profile: &Arc<dyn Profile>,
+        credential_message: &IssueCredentialV2,
+        request_metadata: &Self::CreatedRequestMetadata,
+    ) -> VcxResult<Self::StoredCredentialMetadata>;
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::IssueCredentialAttachmentFormatType;
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::{
+    ProposeCredentialAttachmentFormatType, ProposeCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::{
+    RequestCredentialAttachmentFormatType, RequestCredentialV2,
+};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::IssuerCredentialIssuanceFormat;
+use crate::common::credentials::encoding::encode_attributes;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::extract_attachment_content;
+
+/// The `hlindy/...@v2.0` (AnonCreds over Hyperledger Indy) attachment formats, from the issuer's perspective.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperledgerIndyIssuerCredentialIssuanceFormat;
+
+/// Filter sent by the holder in a proposal, restricting the credential it would like to receive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HyperledgerIndyCredentialFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_issuer_did: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer_did: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cred_def_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperledgerIndyCreateOfferInput {
+    pub cred_def_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HyperledgerIndyCreatedOfferMetadata {
+    pub offer_json: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperledgerIndyCreateCredentialInput {
+    /// JSON map of the credential attribute names to their raw values.
+    pub credential_values_json: String,
+    pub revocation_info: Option<HyperledgerIndyCreateCredentialRevocationInfoInput>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperledgerIndyCreateCredentialRevocationInfoInput {
+    pub rev_reg_id: String,
+    pub tails_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HyperledgerIndyCreatedCredentialMetadata {
+    pub credential_revocation_id: Option<String>,
+}
+
+#[async_trait]
+impl IssuerCredentialIssuanceFormat for HyperledgerIndyIssuerCredentialIssuanceFormat {
+    type ProposalDetails = HyperledgerIndyCredentialFilter;
+
+    type CreateOfferInput = HyperledgerIndyCreateOfferInput;
+    type CreatedOfferMetadata = HyperledgerIndyCreatedOfferMetadata;
+
+    type CreateCredentialInput = HyperledgerIndyCreateCredentialInput;
+    type CreatedCredentialMetadata = HyperledgerIndyCreatedCredentialMetadata;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType> {
+        MaybeKnown::Known(ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0)
+    }
+
+    fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType> {
+        MaybeKnown::Known(OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0)
+    }
+
+    fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType> {
+        MaybeKnown::Known(RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0)
+    }
+
+    fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType> {
+        MaybeKnown::Known(IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0)
+    }
+
+    fn extract_proposal_details(proposal_message: &ProposeCredentialV2) -> VcxResult<Self::ProposalDetails> {
+        let attachment_content = extract_attachment_content(
+            &proposal_message.content.formats,
+            &proposal_message.content.filters_attach,
+            &Self::get_proposal_attachment_format(),
+        )?;
+
+        Ok(serde_json::from_slice(&attachment_content)?)
+    }
+
+    async fn create_offer_attachment_content(
+        profile: &Arc<dyn Profile>,
+        data: &HyperledgerIndyCreateOfferInput,
+    ) -> VcxResult<(Vec<u8>, HyperledgerIndyCreatedOfferMetadata)> {
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let offer_json = anoncreds.issuer_create_credential_offer(&data.cred_def_id).await?;
+
+        let metadata = HyperledgerIndyCreatedOfferMetadata {
+            offer_json: offer_json.clone(),
+        };
+
+        Ok((offer_json.into_bytes(), metadata))
+    }
+
+    async fn create_credential_attachment_content(
+        profile: &Arc<dyn Profile>,
+        offer_message: &OfferCredentialV2,
+        request_message: &RequestCredentialV2,
+        data: &HyperledgerIndyCreateCredentialInput,
+    ) -> VcxResult<(Vec<u8>, HyperledgerIndyCreatedCredentialMetadata)> {
+        let offer_bytes = Self::extract_offer_attachment_content(offer_message)?;
+        let offer_json = String::from_utf8(offer_bytes).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::EncodeError,
+                format!("Offer is not valid UTF-8: {err}"),
+            )
+        })?;
+
+        let request_bytes = Self::extract_request_attachment_content(request_message)?;
+        let request_json = String::from_utf8(request_bytes).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::EncodeError,
+                format!("Credential request is not valid UTF-8: {err}"),
+            )
+        })?;
+
+        let encoded_values = encode_attributes(&data.credential_values_json)?;
+
+        let (rev_reg_id, tails_dir) = match &data.revocation_info {
+            Some(info) => (Some(info.rev_reg_id.clone()), Some(info.tails_dir.clone())),
+            None => (None, None),
+        };
+
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let (credential_json, credential_revocation_id, _) = anoncreds
+            .issuer_create_credential(&offer_json, &request_json, &encoded_values, rev_reg_id, tails_dir)
+            .await?;
+
+        let metadata = HyperledgerIndyCreatedCredentialMetadata {
+            credential_revocation_id,
+        };
+
+        Ok((credential_json.into_bytes(), metadata))
+    }
+}
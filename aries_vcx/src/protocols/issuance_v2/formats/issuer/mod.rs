@@ -0,0 +1,70 @@
+pub mod hyperledger_indy;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::IssueCredentialAttachmentFormatType;
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::{
+    ProposeCredentialAttachmentFormatType, ProposeCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::{
+    RequestCredentialAttachmentFormatType, RequestCredentialV2,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::VcxResult;
+use crate::protocols::common::extract_attachment_content;
+
+/// Trait representing some issue-credential/2.0 attachment format from the issuer's perspective.
+#[async_trait]
+pub trait IssuerCredentialIssuanceFormat {
+    type ProposalDetails;
+
+    type CreateOfferInput: Send + Sync;
+    type CreatedOfferMetadata: Serialize + DeserializeOwned + Clone + Debug + PartialEq + Send + Sync;
+
+    type CreateCredentialInput: Send + Sync;
+    type CreatedCredentialMetadata: Serialize + DeserializeOwned + Clone + Debug + PartialEq + Send + Sync;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType>;
+    fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType>;
+    fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType>;
+    fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType>;
+
+    fn extract_proposal_details(proposal_message: &ProposeCredentialV2) -> VcxResult<Self::ProposalDetails>;
+
+    fn extract_offer_attachment_content(offer_message: &OfferCredentialV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &offer_message.content.formats,
+            &offer_message.content.offers_attach,
+            &Self::get_offer_attachment_format(),
+        )
+    }
+
+    fn extract_request_attachment_content(request_message: &RequestCredentialV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &request_message.content.formats,
+            &request_message.content.requests_attach,
+            &Self::get_request_attachment_format(),
+        )
+    }
+
+    async fn create_offer_attachment_content(
+        profile: &Arc<dyn Profile>,
+        data: &Self::CreateOfferInput,
+    ) -> VcxResult<(Vec<u8>, Self::CreatedOfferMetadata)>;
+
+    async fn create_credential_attachment_content(
+        profile: &Arc<dyn Profile>,
+        offer_message: &OfferCredentialV2,
+        request_message: &RequestCredentialV2,
+        data: &Self::CreateCredentialInput,
+    ) -> VcxResult<(Vec<u8>, Self::CreatedCredentialMetadata)>;
+}
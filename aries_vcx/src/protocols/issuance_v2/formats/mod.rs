@@ -0,0 +1,8 @@
+//! Attachment formats supported by the issue credential 2.0 protocol.
+//!
+//! The protocol itself is agnostic of the credential format in use. Everything format specific,
+//! such as creating an offer or storing a received credential, is delegated to implementations of
+//! [`issuer::IssuerCredentialIssuanceFormat`] and [`holder::HolderCredentialIssuanceFormat`].
+
+pub mod holder;
+pub mod issuer;
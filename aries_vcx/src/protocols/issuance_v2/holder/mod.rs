@@ -0,0 +1,213 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::cred_issuance_v2::ack::{AckCredentialV2, AckCredentialV2Content};
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::IssueCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::OfferCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::problem_report::CredIssuanceV2ProblemReport;
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::{
+    ProposeCredentialV2, ProposeCredentialV2Content, ProposeCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::{
+    RequestCredentialV2, RequestCredentialV2Content, RequestCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::CredentialPreviewV2;
+use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
+use uuid::Uuid;
+
+use self::states::complete::Complete;
+use self::states::offer_received::OfferReceived;
+use self::states::proposal_prepared::ProposalPrepared;
+use self::states::request_prepared::RequestPrepared;
+use super::formats::holder::HolderCredentialIssuanceFormat;
+use super::{build_problem_report, Failed};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::{make_format_attachment, verify_thread_id};
+
+/// Holder side of the issue-credential/2.0 protocol, in the state `S`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HolderV2<S> {
+    state: S,
+    thread_id: String,
+}
+
+impl<S> HolderV2<S> {
+    pub fn get_state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// Aborts the protocol, transitioning to [`Failed`].
+    /// The problem report to send to the issuer can be retrieved from the [`Failed`] state.
+    pub fn prepare_problem_report(self, comment: Option<String>) -> HolderV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+
+        HolderV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        }
+    }
+
+    /// Handles a problem report received from the issuer, transitioning to [`Failed`].
+    pub fn receive_problem_report(self, problem_report: CredIssuanceV2ProblemReport) -> VcxResult<HolderV2<Failed>> {
+        verify_thread_id(&self.thread_id, problem_report.decorators.thread.as_ref())?;
+
+        Ok(HolderV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+async fn create_proposal<T: HolderCredentialIssuanceFormat>(
+    input: &T::CreateProposalInput,
+    preview: Option<CredentialPreviewV2>,
+    thread: Option<Thread>,
+) -> VcxResult<ProposeCredentialV2> {
+    let attachment_content = T::create_proposal_attachment_content(input).await?;
+    let (format, attachment) = make_format_attachment(
+        Uuid::new_v4().to_string(),
+        T::get_proposal_attachment_format(),
+        &attachment_content,
+    );
+
+    let mut content = ProposeCredentialV2Content::new(vec![format], vec![attachment]);
+    content.credential_preview = preview;
+
+    let decorators = ProposeCredentialV2Decorators {
+        thread,
+        ..Default::default()
+    };
+
+    Ok(ProposeCredentialV2::with_decorators(
+        Uuid::new_v4().to_string(),
+        content,
+        decorators,
+    ))
+}
+
+impl<T: HolderCredentialIssuanceFormat> HolderV2<ProposalPrepared<T>> {
+    /// Starts the protocol by proposing a credential to the issuer.
+    pub async fn with_proposal(
+        input: &T::CreateProposalInput,
+        preview: Option<CredentialPreviewV2>,
+    ) -> VcxResult<Self> {
+        let proposal = create_proposal::<T>(input, preview, None).await?;
+        let thread_id = proposal.id.clone();
+
+        Ok(Self {
+            state: ProposalPrepared::new(proposal),
+            thread_id,
+        })
+    }
+
+    /// Handles the offer the issuer responded with, transitioning to [`OfferReceived`].
+    pub fn receive_offer(self, offer: OfferCredentialV2) -> VcxResult<HolderV2<OfferReceived<T>>> {
+        verify_thread_id(&self.thread_id, offer.decorators.thread.as_ref())?;
+
+        Ok(HolderV2 {
+            state: OfferReceived::new(offer),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: HolderCredentialIssuanceFormat> HolderV2<OfferReceived<T>> {
+    /// Starts the protocol from an offer received from the issuer.
+    pub fn from_offer(offer: OfferCredentialV2) -> Self {
+        let thread_id = offer
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| offer.id.clone());
+
+        Self {
+            state: OfferReceived::new(offer),
+            thread_id,
+        }
+    }
+
+    pub fn get_credential_preview(&self) -> &CredentialPreviewV2 {
+        &self.state.get_offer().content.credential_preview
+    }
+
+    /// Responds to the offer with a counter proposal, transitioning to [`ProposalPrepared`].
+    pub async fn prepare_proposal(
+        self,
+        input: &T::CreateProposalInput,
+        preview: Option<CredentialPreviewV2>,
+    ) -> VcxResult<HolderV2<ProposalPrepared<T>>> {
+        let thread = Some(Thread::new(self.thread_id.clone()));
+        let proposal = create_proposal::<T>(input, preview, thread).await?;
+
+        Ok(HolderV2 {
+            state: ProposalPrepared::new(proposal),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Accepts the offer with a credential request, transitioning to [`RequestPrepared`].
+    pub async fn prepare_credential_request(
+        self,
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateRequestInput,
+    ) -> VcxResult<HolderV2<RequestPrepared<T>>> {
+        let (attachment_content, request_metadata) =
+            T::create_request_attachment_content(profile, self.state.get_offer(), input).await?;
+
+        let (format, attachment) = make_format_attachment(
+            Uuid::new_v4().to_string(),
+            T::get_request_attachment_format(),
+            &attachment_content,
+        );
+
+        let content = RequestCredentialV2Content::new(vec![format], vec![attachment]);
+
+        let decorators = RequestCredentialV2Decorators {
+            thread: Some(Thread::new(self.thread_id.clone())),
+            ..Default::default()
+        };
+
+        let request = RequestCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(HolderV2 {
+            state: RequestPrepared::new(request_metadata, request),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: HolderCredentialIssuanceFormat> HolderV2<RequestPrepared<T>> {
+    /// Processes and stores the credential issued by the issuer, transitioning to [`Complete`].
+    ///
+    /// If the issuer requested an ack, it is prepared as part of the transition and
+    /// can be retrieved from the [`Complete`] state.
+    pub async fn receive_credential(
+        self,
+        profile: &Arc<dyn Profile>,
+        credential: IssueCredentialV2,
+    ) -> VcxResult<HolderV2<Complete<T>>> {
+        verify_thread_id(&self.thread_id, Some(&credential.decorators.thread))?;
+
+        let stored_credential_metadata =
+            T::process_and_store_credential(profile, &credential, self.state.get_request_metadata()).await?;
+
+        let ack = credential.decorators.please_ack.as_ref().map(|_| {
+            let content = AckCredentialV2Content::new(AckStatus::Ok);
+            let decorators = AckDecorators::new(Thread::new(self.thread_id.clone()));
+            AckCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+        });
+
+        Ok(HolderV2 {
+            state: Complete::new(credential, stored_credential_metadata, ack),
+            thread_id: self.thread_id,
+        })
+    }
+}
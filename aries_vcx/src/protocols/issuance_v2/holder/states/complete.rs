@@ -0,0 +1,39 @@
+use messages::msg_fields::protocols::cred_issuance_v2::ack::AckCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::IssueCredentialV2;
+
+use crate::protocols::issuance_v2::formats::holder::HolderCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Complete<T: HolderCredentialIssuanceFormat> {
+    credential: IssueCredentialV2,
+    stored_credential_metadata: T::StoredCredentialMetadata,
+    ack: Option<AckCredentialV2>,
+}
+
+impl<T: HolderCredentialIssuanceFormat> Complete<T> {
+    pub fn new(
+        credential: IssueCredentialV2,
+        stored_credential_metadata: T::StoredCredentialMetadata,
+        ack: Option<AckCredentialV2>,
+    ) -> Self {
+        Self {
+            credential,
+            stored_credential_metadata,
+            ack,
+        }
+    }
+
+    pub fn get_credential(&self) -> &IssueCredentialV2 {
+        &self.credential
+    }
+
+    pub fn get_stored_credential_metadata(&self) -> &T::StoredCredentialMetadata {
+        &self.stored_credential_metadata
+    }
+
+    /// The ack to send to the issuer, if it requested one through `~please_ack`.
+    pub fn get_ack(&self) -> Option<&AckCredentialV2> {
+        self.ack.as_ref()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod complete;
+pub mod offer_received;
+pub mod proposal_prepared;
+pub mod request_prepared;
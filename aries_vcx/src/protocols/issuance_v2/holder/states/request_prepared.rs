@@ -0,0 +1,27 @@
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::RequestCredentialV2;
+
+use crate::protocols::issuance_v2::formats::holder::HolderCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct RequestPrepared<T: HolderCredentialIssuanceFormat> {
+    request_metadata: T::CreatedRequestMetadata,
+    request: RequestCredentialV2,
+}
+
+impl<T: HolderCredentialIssuanceFormat> RequestPrepared<T> {
+    pub fn new(request_metadata: T::CreatedRequestMetadata, request: RequestCredentialV2) -> Self {
+        Self {
+            request_metadata,
+            request,
+        }
+    }
+
+    pub fn get_request_metadata(&self) -> &T::CreatedRequestMetadata {
+        &self.request_metadata
+    }
+
+    pub fn get_request(&self) -> &RequestCredentialV2 {
+        &self.request
+    }
+}
@@ -0,0 +1,238 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use messages::decorators::please_ack::{AckOn, PleaseAck};
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::cred_issuance_v2::ack::AckCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::{
+    IssueCredentialV2, IssueCredentialV2Content, IssueCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+    OfferCredentialV2, OfferCredentialV2Content, OfferCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance_v2::problem_report::CredIssuanceV2ProblemReport;
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::ProposeCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::RequestCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::CredentialPreviewV2;
+use uuid::Uuid;
+
+use self::states::complete::Complete;
+use self::states::credential_prepared::CredentialPrepared;
+use self::states::offer_prepared::OfferPrepared;
+use self::states::proposal_received::ProposalReceived;
+use self::states::request_received::RequestReceived;
+use super::formats::issuer::IssuerCredentialIssuanceFormat;
+use super::{build_problem_report, Failed};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::{make_format_attachment, verify_thread_id};
+
+/// Issuer side of the issue-credential/2.0 protocol, in the state `S`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IssuerV2<S> {
+    state: S,
+    thread_id: String,
+}
+
+impl<S> IssuerV2<S> {
+    pub fn get_state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// Aborts the protocol, transitioning to [`Failed`].
+    /// The problem report to send to the holder can be retrieved from the [`Failed`] state.
+    pub fn prepare_problem_report(self, comment: Option<String>) -> IssuerV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+
+        IssuerV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        }
+    }
+
+    /// Handles a problem report received from the holder, transitioning to [`Failed`].
+    pub fn receive_problem_report(self, problem_report: CredIssuanceV2ProblemReport) -> VcxResult<IssuerV2<Failed>> {
+        verify_thread_id(&self.thread_id, problem_report.decorators.thread.as_ref())?;
+
+        Ok(IssuerV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+async fn create_offer<T: IssuerCredentialIssuanceFormat>(
+    profile: &Arc<dyn Profile>,
+    input: &T::CreateOfferInput,
+    preview: CredentialPreviewV2,
+    thread: Option<Thread>,
+    replacement_id: Option<String>,
+) -> VcxResult<(T::CreatedOfferMetadata, OfferCredentialV2)> {
+    let (attachment_content, offer_metadata) = T::create_offer_attachment_content(profile, input).await?;
+    let (format, attachment) = make_format_attachment(
+        Uuid::new_v4().to_string(),
+        T::get_offer_attachment_format(),
+        &attachment_content,
+    );
+
+    let mut content = OfferCredentialV2Content::new(preview, vec![format], vec![attachment]);
+    content.replacement_id = replacement_id;
+
+    let decorators = OfferCredentialV2Decorators {
+        thread,
+        ..Default::default()
+    };
+
+    let offer = OfferCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+    Ok((offer_metadata, offer))
+}
+
+impl<T: IssuerCredentialIssuanceFormat> IssuerV2<ProposalReceived<T>> {
+    /// Starts the protocol from a proposal received from the holder.
+    pub fn from_proposal(proposal: ProposeCredentialV2) -> Self {
+        let thread_id = proposal
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| proposal.id.clone());
+
+        Self {
+            state: ProposalReceived::new(proposal),
+            thread_id,
+        }
+    }
+
+    /// Extracts the format specific details of the received proposal.
+    pub fn get_proposal_details(&self) -> VcxResult<T::ProposalDetails> {
+        T::extract_proposal_details(self.state.get_proposal())
+    }
+
+    /// Responds to the proposal with an offer, transitioning to [`OfferPrepared`].
+    pub async fn prepare_offer(
+        self,
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateOfferInput,
+        preview: CredentialPreviewV2,
+        replacement_id: Option<String>,
+    ) -> VcxResult<IssuerV2<OfferPrepared<T>>> {
+        let thread = Some(Thread::new(self.thread_id.clone()));
+        let (offer_metadata, offer) = create_offer::<T>(profile, input, preview, thread, replacement_id).await?;
+
+        Ok(IssuerV2 {
+            state: OfferPrepared::new(offer_metadata, offer),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: IssuerCredentialIssuanceFormat> IssuerV2<OfferPrepared<T>> {
+    /// Starts the protocol by offering a credential to the holder.
+    pub async fn with_offer(
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateOfferInput,
+        preview: CredentialPreviewV2,
+        replacement_id: Option<String>,
+    ) -> VcxResult<Self> {
+        let (offer_metadata, offer) = create_offer::<T>(profile, input, preview, None, replacement_id).await?;
+        let thread_id = offer.id.clone();
+
+        Ok(Self {
+            state: OfferPrepared::new(offer_metadata, offer),
+            thread_id,
+        })
+    }
+
+    /// Handles a counter proposal of the holder, transitioning back to [`ProposalReceived`].
+    pub fn receive_proposal(self, proposal: ProposeCredentialV2) -> VcxResult<IssuerV2<ProposalReceived<T>>> {
+        verify_thread_id(&self.thread_id, proposal.decorators.thread.as_ref())?;
+
+        Ok(IssuerV2 {
+            state: ProposalReceived::new(proposal),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Handles the credential request of the holder, transitioning to [`RequestReceived`].
+    pub fn receive_request(self, request: RequestCredentialV2) -> VcxResult<IssuerV2<RequestReceived<T>>> {
+        verify_thread_id(&self.thread_id, request.decorators.thread.as_ref())?;
+
+        let (offer_metadata, offer) = self.state.into_parts();
+
+        Ok(IssuerV2 {
+            state: RequestReceived::new(offer_metadata, offer, request),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: IssuerCredentialIssuanceFormat> IssuerV2<RequestReceived<T>> {
+    /// Creates the credential requested by the holder, transitioning to [`CredentialPrepared`].
+    ///
+    /// When `please_ack` is set, the holder is asked to acknowledge the received credential.
+    pub async fn prepare_credential(
+        self,
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateCredentialInput,
+        please_ack: bool,
+        replacement_id: Option<String>,
+    ) -> VcxResult<IssuerV2<CredentialPrepared<T>>> {
+        let (attachment_content, credential_metadata) =
+            T::create_credential_attachment_content(profile, self.state.get_offer(), self.state.get_request(), input)
+                .await?;
+
+        let (format, attachment) = make_format_attachment(
+            Uuid::new_v4().to_string(),
+            T::get_credential_attachment_format(),
+            &attachment_content,
+        );
+
+        let mut content = IssueCredentialV2Content::new(vec![format], vec![attachment]);
+        content.replacement_id = replacement_id;
+
+        let mut decorators = IssueCredentialV2Decorators::new(Thread::new(self.thread_id.clone()));
+        if please_ack {
+            decorators.please_ack = Some(PleaseAck::new(vec![AckOn::Outcome]));
+        }
+
+        let credential = IssueCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(IssuerV2 {
+            state: CredentialPrepared::new(credential_metadata, credential),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: IssuerCredentialIssuanceFormat> IssuerV2<CredentialPrepared<T>> {
+    /// Completes the protocol once the credential was sent, if no ack was requested from the holder.
+    pub fn complete_without_ack(self) -> VcxResult<IssuerV2<Complete<T>>> {
+        if self.state.is_expecting_ack() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Cannot complete the protocol without an ack, as one was requested from the holder",
+            ));
+        }
+
+        Ok(IssuerV2 {
+            state: Complete::new(self.state.into_credential_metadata(), None),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Completes the protocol with the ack received from the holder.
+    pub fn complete_with_ack(self, ack: AckCredentialV2) -> VcxResult<IssuerV2<Complete<T>>> {
+        verify_thread_id(&self.thread_id, Some(&ack.decorators.thread))?;
+
+        Ok(IssuerV2 {
+            state: Complete::new(self.state.into_credential_metadata(), Some(ack)),
+            thread_id: self.thread_id,
+        })
+    }
+}
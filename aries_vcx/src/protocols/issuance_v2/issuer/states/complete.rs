@@ -0,0 +1,27 @@
+use messages::msg_fields::protocols::cred_issuance_v2::ack::AckCredentialV2;
+
+use crate::protocols::issuance_v2::formats::issuer::IssuerCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Complete<T: IssuerCredentialIssuanceFormat> {
+    credential_metadata: T::CreatedCredentialMetadata,
+    ack: Option<AckCredentialV2>,
+}
+
+impl<T: IssuerCredentialIssuanceFormat> Complete<T> {
+    pub fn new(credential_metadata: T::CreatedCredentialMetadata, ack: Option<AckCredentialV2>) -> Self {
+        Self {
+            credential_metadata,
+            ack,
+        }
+    }
+
+    pub fn get_credential_metadata(&self) -> &T::CreatedCredentialMetadata {
+        &self.credential_metadata
+    }
+
+    pub fn get_ack(&self) -> Option<&AckCredentialV2> {
+        self.ack.as_ref()
+    }
+}
@@ -0,0 +1,36 @@
+use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::IssueCredentialV2;
+
+use crate::protocols::issuance_v2::formats::issuer::IssuerCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct CredentialPrepared<T: IssuerCredentialIssuanceFormat> {
+    credential_metadata: T::CreatedCredentialMetadata,
+    credential: IssueCredentialV2,
+}
+
+impl<T: IssuerCredentialIssuanceFormat> CredentialPrepared<T> {
+    pub fn new(credential_metadata: T::CreatedCredentialMetadata, credential: IssueCredentialV2) -> Self {
+        Self {
+            credential_metadata,
+            credential,
+        }
+    }
+
+    pub fn get_credential_metadata(&self) -> &T::CreatedCredentialMetadata {
+        &self.credential_metadata
+    }
+
+    pub fn get_credential(&self) -> &IssueCredentialV2 {
+        &self.credential
+    }
+
+    /// Whether the holder was asked to acknowledge the credential through `~please_ack`.
+    pub fn is_expecting_ack(&self) -> bool {
+        self.credential.decorators.please_ack.is_some()
+    }
+
+    pub(crate) fn into_credential_metadata(self) -> T::CreatedCredentialMetadata {
+        self.credential_metadata
+    }
+}
@@ -0,0 +1,5 @@
+pub mod complete;
+pub mod credential_prepared;
+pub mod offer_prepared;
+pub mod proposal_received;
+pub mod request_received;
@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::ProposeCredentialV2;
+
+use crate::protocols::issuance_v2::formats::issuer::IssuerCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct ProposalReceived<T: IssuerCredentialIssuanceFormat> {
+    proposal: ProposeCredentialV2,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: IssuerCredentialIssuanceFormat> ProposalReceived<T> {
+    pub fn new(proposal: ProposeCredentialV2) -> Self {
+        Self {
+            proposal,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_proposal(&self) -> &ProposeCredentialV2 {
+        &self.proposal
+    }
+}
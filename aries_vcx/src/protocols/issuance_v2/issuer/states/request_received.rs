@@ -0,0 +1,38 @@
+use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::OfferCredentialV2;
+use messages::msg_fields::protocols::cred_issuance_v2::request_credential::RequestCredentialV2;
+
+use crate::protocols::issuance_v2::formats::issuer::IssuerCredentialIssuanceFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct RequestReceived<T: IssuerCredentialIssuanceFormat> {
+    offer_metadata: T::CreatedOfferMetadata,
+    offer: OfferCredentialV2,
+    request: RequestCredentialV2,
+}
+
+impl<T: IssuerCredentialIssuanceFormat> RequestReceived<T> {
+    pub fn new(
+        offer_metadata: T::CreatedOfferMetadata,
+        offer: OfferCredentialV2,
+        request: RequestCredentialV2,
+    ) -> Self {
+        Self {
+            offer_metadata,
+            offer,
+            request,
+        }
+    }
+
+    pub fn get_offer_metadata(&self) -> &T::CreatedOfferMetadata {
+        &self.offer_metadata
+    }
+
+    pub fn get_offer(&self) -> &OfferCredentialV2 {
+        &self.offer
+    }
+
+    pub fn get_request(&self) -> &RequestCredentialV2 {
+        &self.request
+    }
+}
@@ -0,0 +1,300 @@
+//! State machines of the issue-credential/2.0 protocol ([RFC 0453](https://github.com/hyperledger/aries-rfcs/blob/main/features/0453-issue-credential-v2/README.md)).
+//!
+//! Unlike the issue-credential/1.0 state machines, these do not send messages themselves.
+//! Each transition consumes the state machine and returns it in its next state, exposing the
+//! message that has to be sent to the counterparty through a getter.
+//!
+//! Everything specific to a credential format is delegated to the [`formats`], so the AnonCreds
+//! (`hlindy/...@v2.0`) format is just one of the formats the protocol can run with.
+
+pub mod formats;
+pub mod holder;
+pub mod issuer;
+
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::cred_issuance_v2::problem_report::{
+    CredIssuanceV2ProblemReport, CredIssuanceV2ProblemReportContent,
+};
+use messages::msg_fields::protocols::report_problem::ProblemReportDecorators;
+use uuid::Uuid;
+
+fn build_problem_report(comment: Option<String>, thread_id: &str) -> CredIssuanceV2ProblemReport {
+    let content = CredIssuanceV2ProblemReportContent::new(comment.unwrap_or_default());
+
+    let decorators = ProblemReportDecorators {
+        thread: Some(Thread::new(thread_id.to_owned())),
+        ..Default::default()
+    };
+
+    CredIssuanceV2ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// The final, unsuccessful state of both roles, holding the problem report that was either
+/// sent or received.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Failed {
+    problem_report: CredIssuanceV2ProblemReport,
+}
+
+impl Failed {
+    pub fn new(problem_report: CredIssuanceV2ProblemReport) -> Self {
+        Self { problem_report }
+    }
+
+    pub fn get_problem_report(&self) -> &CredIssuanceV2ProblemReport {
+        &self.problem_report
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use messages::msg_fields::protocols::cred_issuance::CredentialAttr;
+    use messages::msg_fields::protocols::cred_issuance_v2::issue_credential::{
+        IssueCredentialAttachmentFormatType, IssueCredentialV2,
+    };
+    use messages::msg_fields::protocols::cred_issuance_v2::offer_credential::{
+        OfferCredentialAttachmentFormatType, OfferCredentialV2,
+    };
+    use messages::msg_fields::protocols::cred_issuance_v2::propose_credential::{
+        ProposeCredentialAttachmentFormatType, ProposeCredentialV2,
+    };
+    use messages::msg_fields::protocols::cred_issuance_v2::request_credential::{
+        RequestCredentialAttachmentFormatType, RequestCredentialV2,
+    };
+    use messages::msg_fields::protocols::cred_issuance_v2::CredentialPreviewV2;
+    use shared_vcx::maybe_known::MaybeKnown;
+
+    use super::formats::holder::HolderCredentialIssuanceFormat;
+    use super::formats::issuer::IssuerCredentialIssuanceFormat;
+    use super::holder::states::offer_received::OfferReceived;
+    use super::holder::states::proposal_prepared::ProposalPrepared;
+    use super::holder::HolderV2;
+    use super::issuer::states::offer_prepared::OfferPrepared;
+    use super::issuer::states::proposal_received::ProposalReceived;
+    use super::issuer::IssuerV2;
+    use super::*;
+    use crate::core::profile::profile::Profile;
+    use crate::errors::error::prelude::*;
+    use crate::protocols::common::extract_attachment_content;
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    /// Format echoing its inputs as attachment content, so the protocol flow
+    /// can be tested without any credential format machinery.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct TestFormat;
+
+    #[async_trait]
+    impl IssuerCredentialIssuanceFormat for TestFormat {
+        type ProposalDetails = String;
+        type CreateOfferInput = String;
+        type CreatedOfferMetadata = String;
+        type CreateCredentialInput = String;
+        type CreatedCredentialMetadata = String;
+
+        fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType> {
+            MaybeKnown::Unknown("test/filter@v1.0".to_owned())
+        }
+
+        fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType> {
+            MaybeKnown::Unknown("test/offer@v1.0".to_owned())
+        }
+
+        fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType> {
+            MaybeKnown::Unknown("test/request@v1.0".to_owned())
+        }
+
+        fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType> {
+            MaybeKnown::Unknown("test/credential@v1.0".to_owned())
+        }
+
+        fn extract_proposal_details(proposal_message: &ProposeCredentialV2) -> VcxResult<String> {
+            let content = extract_attachment_content(
+                &proposal_message.content.formats,
+                &proposal_message.content.filters_attach,
+                &<Self as IssuerCredentialIssuanceFormat>::get_proposal_attachment_format(),
+            )?;
+            Ok(String::from_utf8(content).unwrap())
+        }
+
+        async fn create_offer_attachment_content(
+            _profile: &Arc<dyn Profile>,
+            data: &String,
+        ) -> VcxResult<(Vec<u8>, String)> {
+            Ok((data.as_bytes().to_vec(), "offer_metadata".to_owned()))
+        }
+
+        async fn create_credential_attachment_content(
+            _profile: &Arc<dyn Profile>,
+            _offer_message: &OfferCredentialV2,
+            request_message: &RequestCredentialV2,
+            data: &String,
+        ) -> VcxResult<(Vec<u8>, String)> {
+            let request = Self::extract_request_attachment_content(request_message)?;
+            assert_eq!(request, b"test_request");
+            Ok((data.as_bytes().to_vec(), "credential_metadata".to_owned()))
+        }
+    }
+
+    #[async_trait]
+    impl HolderCredentialIssuanceFormat for TestFormat {
+        type CreateProposalInput = String;
+        type CreateRequestInput = String;
+        type CreatedRequestMetadata = String;
+        type StoredCredentialMetadata = String;
+
+        fn get_proposal_attachment_format() -> MaybeKnown<ProposeCredentialAttachmentFormatType> {
+            <Self as IssuerCredentialIssuanceFormat>::get_proposal_attachment_format()
+        }
+
+        fn get_offer_attachment_format() -> MaybeKnown<OfferCredentialAttachmentFormatType> {
+            <Self as IssuerCredentialIssuanceFormat>::get_offer_attachment_format()
+        }
+
+        fn get_request_attachment_format() -> MaybeKnown<RequestCredentialAttachmentFormatType> {
+            <Self as IssuerCredentialIssuanceFormat>::get_request_attachment_format()
+        }
+
+        fn get_credential_attachment_format() -> MaybeKnown<IssueCredentialAttachmentFormatType> {
+            <Self as IssuerCredentialIssuanceFormat>::get_credential_attachment_format()
+        }
+
+        async fn create_proposal_attachment_content(data: &String) -> VcxResult<Vec<u8>> {
+            Ok(data.as_bytes().to_vec())
+        }
+
+        async fn create_request_attachment_content(
+            _profile: &Arc<dyn Profile>,
+            offer_message: &OfferCredentialV2,
+            data: &String,
+        ) -> VcxResult<(Vec<u8>, String)> {
+            let offer = <Self as HolderCredentialIssuanceFormat>::extract_offer_attachment_content(offer_message)?;
+            assert_eq!(offer, b"test_offer");
+            Ok((data.as_bytes().to_vec(), "request_metadata".to_owned()))
+        }
+
+        async fn process_and_store_credential(
+            _profile: &Arc<dyn Profile>,
+            credential_message: &IssueCredentialV2,
+            request_metadata: &String,
+        ) -> VcxResult<String> {
+            assert_eq!(request_metadata, "request_metadata");
+            let credential = Self::extract_credential_attachment_content(credential_message)?;
+            Ok(String::from_utf8(credential).unwrap())
+        }
+    }
+
+    fn make_preview() -> CredentialPreviewV2 {
+        CredentialPreviewV2::new(vec![CredentialAttr::new("name".to_owned(), "Alice".to_owned())])
+    }
+
+    #[tokio::test]
+    async fn test_issuance_flow_starting_with_proposal() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let holder = HolderV2::<ProposalPrepared<TestFormat>>::with_proposal(&"test_filter".to_owned(), None)
+            .await
+            .unwrap();
+
+        let issuer = IssuerV2::<ProposalReceived<TestFormat>>::from_proposal(holder.get_state().get_proposal().clone());
+        assert_eq!(issuer.get_thread_id(), holder.get_thread_id());
+        assert_eq!(issuer.get_proposal_details().unwrap(), "test_filter");
+
+        let issuer = issuer
+            .prepare_offer(&profile, &"test_offer".to_owned(), make_preview(), None)
+            .await
+            .unwrap();
+
+        let holder = holder.receive_offer(issuer.get_state().get_offer().clone()).unwrap();
+        assert_eq!(holder.get_credential_preview(), &make_preview());
+
+        let holder = holder
+            .prepare_credential_request(&profile, &"test_request".to_owned())
+            .await
+            .unwrap();
+
+        let issuer = issuer
+            .receive_request(holder.get_state().get_request().clone())
+            .unwrap();
+        let issuer = issuer
+            .prepare_credential(&profile, &"test_credential".to_owned(), true, None)
+            .await
+            .unwrap();
+        assert_eq!(issuer.get_state().get_credential_metadata(), "credential_metadata");
+        assert!(issuer.get_state().is_expecting_ack());
+
+        let holder = holder
+            .receive_credential(&profile, issuer.get_state().get_credential().clone())
+            .await
+            .unwrap();
+        assert_eq!(holder.get_state().get_stored_credential_metadata(), "test_credential");
+
+        let ack = holder.get_state().get_ack().unwrap().clone();
+        let issuer = issuer.complete_with_ack(ack).unwrap();
+        assert!(issuer.get_state().get_ack().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_issuer_rejects_message_from_other_thread() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let issuer =
+            IssuerV2::<OfferPrepared<TestFormat>>::with_offer(&profile, &"test_offer".to_owned(), make_preview(), None)
+                .await
+                .unwrap();
+
+        let mut offer = issuer.get_state().get_offer().clone();
+        offer.id = "other_thread".to_owned();
+
+        let holder = HolderV2::<OfferReceived<TestFormat>>::from_offer(offer)
+            .prepare_credential_request(&profile, &"test_request".to_owned())
+            .await
+            .unwrap();
+
+        let err = issuer
+            .receive_request(holder.get_state().get_request().clone())
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[tokio::test]
+    async fn test_issuer_cannot_complete_without_requested_ack() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let issuer =
+            IssuerV2::<OfferPrepared<TestFormat>>::with_offer(&profile, &"test_offer".to_owned(), make_preview(), None)
+                .await
+                .unwrap();
+
+        let holder = HolderV2::<OfferReceived<TestFormat>>::from_offer(issuer.get_state().get_offer().clone())
+            .prepare_credential_request(&profile, &"test_request".to_owned())
+            .await
+            .unwrap();
+
+        let issuer = issuer
+            .receive_request(holder.get_state().get_request().clone())
+            .unwrap()
+            .prepare_credential(&profile, &"test_credential".to_owned(), true, None)
+            .await
+            .unwrap();
+
+        let err = issuer.clone().complete_without_ack().unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidState);
+
+        let issuer = issuer.prepare_problem_report(Some("aborted".to_owned()));
+        assert_eq!(
+            issuer
+                .get_state()
+                .get_problem_report()
+                .decorators
+                .thread
+                .as_ref()
+                .unwrap()
+                .thid,
+            issuer.get_thread_id()
+        );
+    }
+}
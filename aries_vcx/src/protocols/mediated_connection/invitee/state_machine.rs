@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use crate::common::signing::decode_signed_connection_response;
 use crate::errors::error::prelude::*;
+use crate::handlers::discovery::SupportedFeatures;
 use crate::handlers::util::{matches_thread_id, verify_thread_id, AnyInvitation};
 use crate::protocols::mediated_connection::invitee::states::completed::CompletedState;
 use crate::protocols::mediated_connection::invitee::states::initial::InitialState;
@@ -25,7 +26,6 @@ use messages::msg_fields::protocols::connection::request::{Request, RequestConte
 use messages::msg_fields::protocols::connection::response::Response;
 use messages::msg_fields::protocols::connection::{Connection, ConnectionData};
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
-use messages::msg_fields::protocols::discover_features::query::QueryContent;
 use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
 use messages::msg_fields::protocols::notification::ack::{Ack, AckContent, AckDecorators, AckStatus};
 use messages::AriesMessage;
@@ -159,8 +159,7 @@ impl SmConnectionInvitee {
     }
 
     pub fn get_protocols(&self) -> Vec<ProtocolDescriptor> {
-        let query = QueryContent::new("*".to_owned());
-        query.lookup()
+        SupportedFeatures::default().protocols("*")
     }
 
     pub fn get_remote_protocols(&self) -> Option<Vec<ProtocolDescriptor>> {
@@ -17,7 +17,6 @@ use messages::msg_fields::protocols::connection::request::Request;
 use messages::msg_fields::protocols::connection::response::{Response, ResponseContent, ResponseDecorators};
 use messages::msg_fields::protocols::connection::{Connection, ConnectionData};
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
-use messages::msg_fields::protocols::discover_features::query::QueryContent;
 use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
 use messages::msg_fields::protocols::trust_ping::TrustPing;
 use messages::AriesMessage;
@@ -26,6 +25,7 @@ use uuid::Uuid;
 
 use crate::common::signing::sign_connection_response;
 use crate::errors::error::prelude::*;
+use crate::handlers::discovery::SupportedFeatures;
 use crate::handlers::util::{verify_thread_id, AnyInvitation};
 use crate::protocols::mediated_connection::inviter::states::completed::CompletedState;
 use crate::protocols::mediated_connection::inviter::states::initial::InitialState;
@@ -144,8 +144,7 @@ impl SmConnectionInviter {
     }
 
     pub fn get_protocols(&self) -> Vec<ProtocolDescriptor> {
-        let query = QueryContent::new("*".to_owned());
-        query.lookup()
+        SupportedFeatures::default().protocols("*")
     }
 
     pub fn get_remote_protocols(&self) -> Option<Vec<ProtocolDescriptor>> {
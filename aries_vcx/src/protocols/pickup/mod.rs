@@ -0,0 +1,133 @@
+use ::uuid::Uuid;
+use chrono::Utc;
+use messages::{
+    decorators::{
+        attachment::{Attachment, AttachmentData, AttachmentType},
+        thread::Thread,
+        timing::Timing,
+    },
+    msg_fields::protocols::pickup::{
+        delivery::{Delivery, DeliveryContent, DeliveryDecorators},
+        delivery_request::{DeliveryRequest, DeliveryRequestContent, DeliveryRequestDecorators},
+        live_delivery_change::{LiveDeliveryChange, LiveDeliveryChangeContent, LiveDeliveryChangeDecorators},
+        messages_received::{MessagesReceived, MessagesReceivedContent, MessagesReceivedDecorators},
+        status::{Status, StatusContent, StatusDecorators},
+        status_request::{StatusRequest, StatusRequestContent, StatusRequestDecorators},
+    },
+};
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+fn make_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    timing
+}
+
+pub fn build_status_request(recipient_key: Option<String>) -> StatusRequest {
+    let mut content = StatusRequestContent::default();
+    content.recipient_key = recipient_key;
+
+    let mut decorators = StatusRequestDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    StatusRequest::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_delivery_request(limit: u32, recipient_key: Option<String>) -> DeliveryRequest {
+    let mut content = DeliveryRequestContent::new(limit);
+    content.recipient_key = recipient_key;
+
+    let mut decorators = DeliveryRequestDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    DeliveryRequest::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_messages_received(message_id_list: Vec<String>) -> MessagesReceived {
+    let content = MessagesReceivedContent::new(message_id_list);
+
+    let mut decorators = MessagesReceivedDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    MessagesReceived::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_live_delivery_change(live_delivery: bool) -> LiveDeliveryChange {
+    let content = LiveDeliveryChangeContent::new(live_delivery);
+
+    let mut decorators = LiveDeliveryChangeDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    LiveDeliveryChange::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_status(thread_id: String, content: StatusContent) -> Status {
+    let mut decorators = StatusDecorators::default();
+    decorators.thread = Some(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    Status::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// Builds a [`Delivery`] out of packed messages paired with their IDs.
+/// Each message is attached base64 encoded, using its ID as the attachment ID,
+/// so that the recipient can acknowledge it through a `messages-received` message.
+pub fn build_delivery(thread_id: String, recipient_key: Option<String>, messages: Vec<(String, Vec<u8>)>) -> Delivery {
+    let attach = messages
+        .into_iter()
+        .map(|(id, msg)| {
+            let mut attachment = Attachment::new(AttachmentData::new(AttachmentType::Base64(base64::encode(&msg))));
+            attachment.id = Some(id);
+            attachment
+        })
+        .collect();
+
+    let mut content = DeliveryContent::new(attach);
+    content.recipient_key = recipient_key;
+
+    let mut decorators = DeliveryDecorators::default();
+    decorators.thread = Some(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    Delivery::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// Extracts the packed messages from a [`Delivery`], paired with their IDs.
+///
+/// # Errors
+///
+/// Will error out if an attachment has no ID or its content is neither base64 nor JSON.
+pub fn extract_delivered_messages(delivery: &Delivery) -> VcxResult<Vec<(String, Vec<u8>)>> {
+    delivery
+        .content
+        .attach
+        .iter()
+        .map(|attachment| {
+            let id = attachment.id.clone().ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidMessageFormat,
+                    "Delivered message attachment has no ID",
+                )
+            })?;
+
+            let msg = match &attachment.data.content {
+                AttachmentType::Base64(encoded) => base64::decode(encoded).map_err(|err| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::SerializationError,
+                        format!("Delivered message {id} is not base64 encoded: {err}"),
+                    )
+                })?,
+                AttachmentType::Json(json) => serde_json::to_vec(json)?,
+                _ => {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidMessageFormat,
+                        format!("Unsupported content for delivered message {id}"),
+                    ))
+                }
+            };
+
+            Ok((id, msg))
+        })
+        .collect()
+}
@@ -46,7 +46,7 @@ pub struct ProverSM {
     state: ProverFullState,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProverState {
     Initial,
     PresentationProposalSent,
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use crate::common::proofs::proof_request::PresentationRequestData;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
+use crate::handlers::decorators::{set_expiry, ExpiringThread};
 use crate::handlers::util::{make_attach_from_str, matches_opt_thread_id, matches_thread_id, AttachmentId, Status};
 use crate::protocols::common::build_problem_report_msg;
 use crate::protocols::proof_presentation::verifier::messages::VerifierMessages;
@@ -16,7 +17,7 @@ use crate::protocols::proof_presentation::verifier::states::presentation_request
 use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
 use crate::protocols::proof_presentation::verifier::verify_thread_id;
 use crate::protocols::SendClosure;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use messages::decorators::thread::Thread;
 use messages::decorators::timing::Timing;
 use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
@@ -41,7 +42,7 @@ pub struct VerifierSM {
     state: VerifierFullState,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VerifierState {
     Initial,
     PresentationProposalReceived,
@@ -356,6 +357,33 @@ impl VerifierSM {
         })
     }
 
+    /// Declares when the presentation request expires, after which the thread is timed out if no
+    /// presentation was received.
+    pub fn set_request_expiry(self, expires_time: DateTime<Utc>) -> VcxResult<Self> {
+        let Self {
+            source_id,
+            thread_id,
+            state,
+        } = self;
+        let state = match state {
+            VerifierFullState::PresentationRequestSet(mut state) => {
+                set_expiry(&mut state.presentation_request.decorators.timing, expires_time);
+                VerifierFullState::PresentationRequestSet(state)
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Can not set_request_expiry in current state.",
+                ))
+            }
+        };
+        Ok(Self {
+            source_id,
+            thread_id,
+            state,
+        })
+    }
+
     pub fn mark_presentation_request_msg_sent(self) -> VcxResult<Self> {
         let Self {
             state,
@@ -506,6 +534,35 @@ impl VerifierSM {
     }
 }
 
+impl ExpiringThread for VerifierSM {
+    fn thread_id(&self) -> String {
+        self.thread_id.clone()
+    }
+
+    fn awaited_expiry(&self) -> Option<DateTime<Utc>> {
+        match &self.state {
+            VerifierFullState::PresentationRequestSent(state) => {
+                state.presentation_request.decorators.timing.as_ref()?.expires_time
+            }
+            _ => None,
+        }
+    }
+
+    fn answers_awaited(&self, message: &AriesMessage) -> bool {
+        matches!(message, AriesMessage::PresentProof(PresentProof::Presentation(_)))
+    }
+
+    fn expire(self, problem_report: ProblemReport) -> Self {
+        let state = match self.state {
+            VerifierFullState::PresentationRequestSent(state) => {
+                VerifierFullState::Finished((state, problem_report).into())
+            }
+            s => s,
+        };
+        Self { state, ..self }
+    }
+}
+
 // #[cfg(test)]
 // pub mod unit_tests {
 //     use crate::common::proofs::proof_request::test_utils::_presentation_request_data;
@@ -0,0 +1,8 @@
+//! Attachment formats supported by the present proof 2.0 protocol.
+//!
+//! The protocol itself is agnostic of the proof format in use. Everything format specific,
+//! such as generating or verifying a presentation, is delegated to implementations of
+//! [`prover::ProverProofPresentationFormat`] and [`verifier::VerifierProofPresentationFormat`].
+
+pub mod prover;
+pub mod verifier;
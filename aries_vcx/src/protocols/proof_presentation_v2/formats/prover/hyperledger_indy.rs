@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationAttachmentFormatType;
+use messages::msg_fields::protocols::present_proof_v2::propose::ProposePresentationAttachmentFormatType;
+use messages::msg_fields::protocols::present_proof_v2::request::{
+    RequestPresentationAttachmentFormatType, RequestPresentationV2,
+};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::ProverProofPresentationFormat;
+use crate::common::proofs::proof_request::ProofRequestData;
+use crate::common::proofs::prover::prover::generate_indy_proof;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::proof_presentation::types::SelectedCredentials;
+
+/// The `hlindy/...@v2.0` (AnonCreds over Hyperledger Indy) attachment formats, from the prover's perspective.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperledgerIndyProverProofPresentationFormat;
+
+#[derive(Debug, Clone)]
+pub struct HyperledgerIndyCreatePresentationInput {
+    pub credentials: SelectedCredentials,
+    pub self_attested_attributes: HashMap<String, String>,
+}
+
+#[async_trait]
+impl ProverProofPresentationFormat for HyperledgerIndyProverProofPresentationFormat {
+    type CreateProposalInput = ProofRequestData;
+
+    type RequestDetails = ProofRequestData;
+
+    type CreatePresentationInput = HyperledgerIndyCreatePresentationInput;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType> {
+        MaybeKnown::Known(ProposePresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0)
+    }
+
+    fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType> {
+        MaybeKnown::Known(RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0)
+    }
+
+    fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType> {
+        MaybeKnown::Known(PresentationAttachmentFormatType::HyperledgerIndyProof2_0)
+    }
+
+    fn extract_request_details(request_message: &RequestPresentationV2) -> VcxResult<ProofRequestData> {
+        Ok(serde_json::from_slice(&Self::extract_request_attachment_content(
+            request_message,
+        )?)?)
+    }
+
+    async fn create_proposal_attachment_content(data: &ProofRequestData) -> VcxResult<Vec<u8>> {
+        Ok(serde_json::to_vec(data)?)
+    }
+
+    async fn create_presentation_attachment_content(
+        profile: &Arc<dyn Profile>,
+        request_message: &RequestPresentationV2,
+        data: &HyperledgerIndyCreatePresentationInput,
+    ) -> VcxResult<Vec<u8>> {
+        let request_bytes = Self::extract_request_attachment_content(request_message)?;
+        let request_json = String::from_utf8(request_bytes).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::EncodeError,
+                format!("Presentation request is not valid UTF-8: {err}"),
+            )
+        })?;
+
+        let presentation = generate_indy_proof(
+            profile,
+            &data.credentials,
+            &data.self_attested_attributes,
+            &request_json,
+        )
+        .await?;
+
+        Ok(presentation.into_bytes())
+    }
+}
@@ -0,0 +1,47 @@
+pub mod hyperledger_indy;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationAttachmentFormatType;
+use messages::msg_fields::protocols::present_proof_v2::propose::ProposePresentationAttachmentFormatType;
+use messages::msg_fields::protocols::present_proof_v2::request::{
+    RequestPresentationAttachmentFormatType, RequestPresentationV2,
+};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::VcxResult;
+use crate::protocols::common::extract_attachment_content;
+
+/// Trait representing some present-proof/2.0 attachment format from the prover's perspective.
+#[async_trait]
+pub trait ProverProofPresentationFormat {
+    type CreateProposalInput: Send + Sync;
+
+    type RequestDetails;
+
+    type CreatePresentationInput: Send + Sync;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType>;
+    fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType>;
+    fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType>;
+
+    fn extract_request_attachment_content(request_message: &RequestPresentationV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &request_message.content.formats,
+            &request_message.content.request_presentations_attach,
+            &Self::get_request_attachment_format(),
+        )
+    }
+
+    fn extract_request_details(request_message: &RequestPresentationV2) -> VcxResult<Self::RequestDetails>;
+
+    async fn create_proposal_attachment_content(data: &Self::CreateProposalInput) -> VcxResult<Vec<u8>>;
+
+    async fn create_presentation_attachment_content(
+        profile: &Arc<dyn Profile>,
+        request_message: &RequestPresentationV2,
+        data: &Self::CreatePresentationInput,
+    ) -> VcxResult<Vec<u8>>;
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof_v2::present::{PresentationAttachmentFormatType, PresentationV2};
+use messages::msg_fields::protocols::present_proof_v2::propose::{
+    ProposePresentationAttachmentFormatType, ProposePresentationV2,
+};
+use messages::msg_fields::protocols::present_proof_v2::request::{
+    RequestPresentationAttachmentFormatType, RequestPresentationV2,
+};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::VerifierProofPresentationFormat;
+use crate::common::proofs::proof_request::ProofRequestData;
+use crate::common::proofs::verifier::verifier::validate_indy_proof;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::extract_attachment_content;
+
+/// The `hlindy/...@v2.0` (AnonCreds over Hyperledger Indy) attachment formats, from the verifier's perspective.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperledgerIndyVerifierProofPresentationFormat;
+
+fn attachment_content_to_string(content: Vec<u8>, what: &str) -> VcxResult<String> {
+    String::from_utf8(content).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::EncodeError,
+            format!("{what} is not valid UTF-8: {err}"),
+        )
+    })
+}
+
+#[async_trait]
+impl VerifierProofPresentationFormat for HyperledgerIndyVerifierProofPresentationFormat {
+    type ProposalDetails = ProofRequestData;
+
+    type CreateRequestInput = ProofRequestData;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType> {
+        MaybeKnown::Known(ProposePresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0)
+    }
+
+    fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType> {
+        MaybeKnown::Known(RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0)
+    }
+
+    fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType> {
+        MaybeKnown::Known(PresentationAttachmentFormatType::HyperledgerIndyProof2_0)
+    }
+
+    fn extract_proposal_details(proposal_message: &ProposePresentationV2) -> VcxResult<ProofRequestData> {
+        let attachment_content = extract_attachment_content(
+            &proposal_message.content.formats,
+            &proposal_message.content.proposals_attach,
+            &Self::get_proposal_attachment_format(),
+        )?;
+
+        Ok(serde_json::from_slice(&attachment_content)?)
+    }
+
+    async fn create_request_attachment_content(
+        _profile: &Arc<dyn Profile>,
+        data: &ProofRequestData,
+    ) -> VcxResult<Vec<u8>> {
+        Ok(serde_json::to_vec(data)?)
+    }
+
+    async fn verify_presentation(
+        profile: &Arc<dyn Profile>,
+        request_message: &RequestPresentationV2,
+        presentation_message: &PresentationV2,
+    ) -> VcxResult<bool> {
+        let request_json = attachment_content_to_string(
+            Self::extract_request_attachment_content(request_message)?,
+            "Presentation request",
+        )?;
+        let presentation_json = attachment_content_to_string(
+            Self::extract_presentation_attachment_content(presentation_message)?,
+            "Presentation",
+        )?;
+
+        validate_indy_proof(profile, &presentation_json, &request_json).await
+    }
+}
@@ -0,0 +1,59 @@
+pub mod hyperledger_indy;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof_v2::present::{PresentationAttachmentFormatType, PresentationV2};
+use messages::msg_fields::protocols::present_proof_v2::propose::{
+    ProposePresentationAttachmentFormatType, ProposePresentationV2,
+};
+use messages::msg_fields::protocols::present_proof_v2::request::{
+    RequestPresentationAttachmentFormatType, RequestPresentationV2,
+};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::VcxResult;
+use crate::protocols::common::extract_attachment_content;
+
+/// Trait representing some present-proof/2.0 attachment format from the verifier's perspective.
+#[async_trait]
+pub trait VerifierProofPresentationFormat {
+    type ProposalDetails;
+
+    type CreateRequestInput: Send + Sync;
+
+    fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType>;
+    fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType>;
+    fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType>;
+
+    fn extract_proposal_details(proposal_message: &ProposePresentationV2) -> VcxResult<Self::ProposalDetails>;
+
+    fn extract_request_attachment_content(request_message: &RequestPresentationV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &request_message.content.formats,
+            &request_message.content.request_presentations_attach,
+            &Self::get_request_attachment_format(),
+        )
+    }
+
+    fn extract_presentation_attachment_content(presentation_message: &PresentationV2) -> VcxResult<Vec<u8>> {
+        extract_attachment_content(
+            &presentation_message.content.formats,
+            &presentation_message.content.presentations_attach,
+            &Self::get_presentation_attachment_format(),
+        )
+    }
+
+    async fn create_request_attachment_content(
+        profile: &Arc<dyn Profile>,
+        data: &Self::CreateRequestInput,
+    ) -> VcxResult<Vec<u8>>;
+
+    /// Verifies the presentation against the request it answers, returning whether it is valid.
+    async fn verify_presentation(
+        profile: &Arc<dyn Profile>,
+        request_message: &RequestPresentationV2,
+        presentation_message: &PresentationV2,
+    ) -> VcxResult<bool>;
+}
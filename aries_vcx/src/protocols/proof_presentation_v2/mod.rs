@@ -0,0 +1,277 @@
+//! State machines of the present-proof/2.0 protocol ([RFC 0454](https://github.com/hyperledger/aries-rfcs/blob/main/features/0454-present-proof-v2/README.md)).
+//!
+//! Just like the issue-credential/2.0 state machines in [`crate::protocols::issuance_v2`], transitions
+//! consume the state machine and the messages to send to the counterparty are exposed through getters.
+//!
+//! The proof format is a type parameter of the state machines, so AnonCreds (`hlindy/...@v2.0`) proofs
+//! and other formats, such as DIF Presentation Exchange, share the same prover and verifier flows.
+
+pub mod formats;
+pub mod prover;
+pub mod verifier;
+
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::present_proof_v2::problem_report::{
+    PresentProofV2ProblemReport, PresentProofV2ProblemReportContent,
+};
+use messages::msg_fields::protocols::report_problem::ProblemReportDecorators;
+use uuid::Uuid;
+
+fn build_problem_report(comment: Option<String>, thread_id: &str) -> PresentProofV2ProblemReport {
+    let content = PresentProofV2ProblemReportContent::new(comment.unwrap_or_default());
+
+    let decorators = ProblemReportDecorators {
+        thread: Some(Thread::new(thread_id.to_owned())),
+        ..Default::default()
+    };
+
+    PresentProofV2ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// The final, unsuccessful state of both roles, holding the problem report that was either
+/// sent or received.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Failed {
+    problem_report: PresentProofV2ProblemReport,
+}
+
+impl Failed {
+    pub fn new(problem_report: PresentProofV2ProblemReport) -> Self {
+        Self { problem_report }
+    }
+
+    pub fn get_problem_report(&self) -> &PresentProofV2ProblemReport {
+        &self.problem_report
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use messages::msg_fields::protocols::present_proof_v2::present::{
+        PresentationAttachmentFormatType, PresentationV2,
+    };
+    use messages::msg_fields::protocols::present_proof_v2::propose::{
+        ProposePresentationAttachmentFormatType, ProposePresentationV2,
+    };
+    use messages::msg_fields::protocols::present_proof_v2::request::{
+        RequestPresentationAttachmentFormatType, RequestPresentationV2,
+    };
+    use shared_vcx::maybe_known::MaybeKnown;
+
+    use super::formats::prover::ProverProofPresentationFormat;
+    use super::formats::verifier::VerifierProofPresentationFormat;
+    use super::prover::states::proposal_prepared::ProposalPrepared;
+    use super::prover::states::request_received::RequestReceived;
+    use super::prover::ProverV2;
+    use super::verifier::states::proposal_received::ProposalReceived;
+    use super::verifier::states::request_prepared::RequestPrepared;
+    use super::verifier::VerifierV2;
+    use super::*;
+    use crate::core::profile::profile::Profile;
+    use crate::errors::error::prelude::*;
+    use crate::protocols::common::extract_attachment_content;
+    use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    /// Format echoing its inputs as attachment content, so the protocol flow
+    /// can be tested without any proof format machinery.
+    /// Presentations are valid if their content is `valid_presentation`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct TestFormat;
+
+    #[async_trait]
+    impl VerifierProofPresentationFormat for TestFormat {
+        type ProposalDetails = String;
+        type CreateRequestInput = String;
+
+        fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType> {
+            MaybeKnown::Unknown("test/proposal@v1.0".to_owned())
+        }
+
+        fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType> {
+            MaybeKnown::Unknown("test/request@v1.0".to_owned())
+        }
+
+        fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType> {
+            MaybeKnown::Unknown("test/presentation@v1.0".to_owned())
+        }
+
+        fn extract_proposal_details(proposal_message: &ProposePresentationV2) -> VcxResult<String> {
+            let content = extract_attachment_content(
+                &proposal_message.content.formats,
+                &proposal_message.content.proposals_attach,
+                &<Self as VerifierProofPresentationFormat>::get_proposal_attachment_format(),
+            )?;
+            Ok(String::from_utf8(content).unwrap())
+        }
+
+        async fn create_request_attachment_content(_profile: &Arc<dyn Profile>, data: &String) -> VcxResult<Vec<u8>> {
+            Ok(data.as_bytes().to_vec())
+        }
+
+        async fn verify_presentation(
+            _profile: &Arc<dyn Profile>,
+            request_message: &RequestPresentationV2,
+            presentation_message: &PresentationV2,
+        ) -> VcxResult<bool> {
+            let request =
+                <Self as VerifierProofPresentationFormat>::extract_request_attachment_content(request_message)?;
+            assert_eq!(request, b"test_request");
+            let presentation = Self::extract_presentation_attachment_content(presentation_message)?;
+            Ok(presentation == b"valid_presentation")
+        }
+    }
+
+    #[async_trait]
+    impl ProverProofPresentationFormat for TestFormat {
+        type CreateProposalInput = String;
+        type RequestDetails = String;
+        type CreatePresentationInput = String;
+
+        fn get_proposal_attachment_format() -> MaybeKnown<ProposePresentationAttachmentFormatType> {
+            <Self as VerifierProofPresentationFormat>::get_proposal_attachment_format()
+        }
+
+        fn get_request_attachment_format() -> MaybeKnown<RequestPresentationAttachmentFormatType> {
+            <Self as VerifierProofPresentationFormat>::get_request_attachment_format()
+        }
+
+        fn get_presentation_attachment_format() -> MaybeKnown<PresentationAttachmentFormatType> {
+            <Self as VerifierProofPresentationFormat>::get_presentation_attachment_format()
+        }
+
+        fn extract_request_details(request_message: &RequestPresentationV2) -> VcxResult<String> {
+            let content = <Self as ProverProofPresentationFormat>::extract_request_attachment_content(request_message)?;
+            Ok(String::from_utf8(content).unwrap())
+        }
+
+        async fn create_proposal_attachment_content(data: &String) -> VcxResult<Vec<u8>> {
+            Ok(data.as_bytes().to_vec())
+        }
+
+        async fn create_presentation_attachment_content(
+            _profile: &Arc<dyn Profile>,
+            _request_message: &RequestPresentationV2,
+            data: &String,
+        ) -> VcxResult<Vec<u8>> {
+            Ok(data.as_bytes().to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_presentation_flow_starting_with_proposal() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let prover = ProverV2::<ProposalPrepared<TestFormat>>::with_proposal(&"test_proposal".to_owned())
+            .await
+            .unwrap();
+
+        let verifier =
+            VerifierV2::<ProposalReceived<TestFormat>>::from_proposal(prover.get_state().get_proposal().clone());
+        assert_eq!(verifier.get_thread_id(), prover.get_thread_id());
+        assert_eq!(verifier.get_proposal_details().unwrap(), "test_proposal");
+
+        let verifier = verifier
+            .prepare_request(&profile, &"test_request".to_owned(), false)
+            .await
+            .unwrap();
+
+        let prover = prover
+            .receive_request(verifier.get_state().get_request().clone())
+            .unwrap();
+        assert_eq!(prover.get_request_details().unwrap(), "test_request");
+
+        let prover = prover
+            .prepare_presentation(&profile, &"valid_presentation".to_owned(), true)
+            .await
+            .unwrap();
+        assert!(prover.get_state().is_expecting_ack());
+
+        let verifier = verifier
+            .receive_presentation(&profile, prover.get_state().get_presentation().clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            verifier.get_state().get_verification_status(),
+            &PresentationVerificationStatus::Valid
+        );
+
+        let ack = verifier.get_state().get_ack().unwrap().clone();
+        let prover = prover.complete_with_ack(ack).unwrap();
+        assert!(prover.get_state().get_ack().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_presentation_is_not_acked() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let verifier =
+            VerifierV2::<RequestPrepared<TestFormat>>::with_request(&profile, &"test_request".to_owned(), true)
+                .await
+                .unwrap();
+
+        let prover = ProverV2::<RequestReceived<TestFormat>>::from_request(verifier.get_state().get_request().clone());
+        assert_eq!(prover.get_thread_id(), verifier.get_thread_id());
+
+        let prover = prover
+            .prepare_presentation(&profile, &"invalid_presentation".to_owned(), false)
+            .await
+            .unwrap();
+
+        let verifier = verifier
+            .receive_presentation(&profile, prover.get_state().get_presentation().clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            verifier.get_state().get_verification_status(),
+            &PresentationVerificationStatus::Invalid
+        );
+        assert!(verifier.get_state().get_ack().is_none());
+
+        let prover = prover.complete_without_ack().unwrap();
+        assert!(prover.get_state().get_ack().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prover_cannot_complete_without_requested_ack() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let verifier =
+            VerifierV2::<RequestPrepared<TestFormat>>::with_request(&profile, &"test_request".to_owned(), false)
+                .await
+                .unwrap();
+
+        let prover = ProverV2::<RequestReceived<TestFormat>>::from_request(verifier.get_state().get_request().clone())
+            .prepare_presentation(&profile, &"valid_presentation".to_owned(), true)
+            .await
+            .unwrap();
+
+        let err = prover.complete_without_ack().unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidState);
+    }
+
+    #[tokio::test]
+    async fn test_verifier_rejects_message_from_other_thread() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let verifier =
+            VerifierV2::<RequestPrepared<TestFormat>>::with_request(&profile, &"test_request".to_owned(), false)
+                .await
+                .unwrap();
+
+        let mut request = verifier.get_state().get_request().clone();
+        request.id = "other_thread".to_owned();
+
+        let prover = ProverV2::<RequestReceived<TestFormat>>::from_request(request);
+        let problem_report = prover.prepare_problem_report(Some("test_comment".to_owned()));
+
+        let err = verifier
+            .receive_problem_report(problem_report.get_state().get_problem_report().clone())
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
@@ -0,0 +1,204 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use messages::decorators::please_ack::{AckOn, PleaseAck};
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::present_proof_v2::ack::AckPresentationV2;
+use messages::msg_fields::protocols::present_proof_v2::present::{
+    PresentationV2, PresentationV2Content, PresentationV2Decorators,
+};
+use messages::msg_fields::protocols::present_proof_v2::problem_report::PresentProofV2ProblemReport;
+use messages::msg_fields::protocols::present_proof_v2::propose::{
+    ProposePresentationV2, ProposePresentationV2Content, ProposePresentationV2Decorators,
+};
+use messages::msg_fields::protocols::present_proof_v2::request::RequestPresentationV2;
+use uuid::Uuid;
+
+use self::states::complete::Complete;
+use self::states::presentation_prepared::PresentationPrepared;
+use self::states::proposal_prepared::ProposalPrepared;
+use self::states::request_received::RequestReceived;
+use super::formats::prover::ProverProofPresentationFormat;
+use super::{build_problem_report, Failed};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::{make_format_attachment, verify_thread_id};
+
+/// Prover side of the present-proof/2.0 protocol, in the state `S`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProverV2<S> {
+    state: S,
+    thread_id: String,
+}
+
+impl<S> ProverV2<S> {
+    pub fn get_state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// Aborts the protocol, transitioning to [`Failed`].
+    /// The problem report to send to the verifier can be retrieved from the [`Failed`] state.
+    pub fn prepare_problem_report(self, comment: Option<String>) -> ProverV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+
+        ProverV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        }
+    }
+
+    /// Handles a problem report received from the verifier, transitioning to [`Failed`].
+    pub fn receive_problem_report(self, problem_report: PresentProofV2ProblemReport) -> VcxResult<ProverV2<Failed>> {
+        verify_thread_id(&self.thread_id, problem_report.decorators.thread.as_ref())?;
+
+        Ok(ProverV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+async fn create_proposal<T: ProverProofPresentationFormat>(
+    input: &T::CreateProposalInput,
+    thread: Option<Thread>,
+) -> VcxResult<ProposePresentationV2> {
+    let attachment_content = T::create_proposal_attachment_content(input).await?;
+    let (format, attachment) = make_format_attachment(
+        Uuid::new_v4().to_string(),
+        T::get_proposal_attachment_format(),
+        &attachment_content,
+    );
+
+    let content = ProposePresentationV2Content::new(vec![format], vec![attachment]);
+    let decorators = ProposePresentationV2Decorators {
+        thread,
+        ..Default::default()
+    };
+
+    Ok(ProposePresentationV2::with_decorators(
+        Uuid::new_v4().to_string(),
+        content,
+        decorators,
+    ))
+}
+
+impl<T: ProverProofPresentationFormat> ProverV2<ProposalPrepared<T>> {
+    /// Starts the protocol by proposing a presentation to the verifier.
+    pub async fn with_proposal(input: &T::CreateProposalInput) -> VcxResult<Self> {
+        let proposal = create_proposal::<T>(input, None).await?;
+        let thread_id = proposal.id.clone();
+
+        Ok(Self {
+            state: ProposalPrepared::new(proposal),
+            thread_id,
+        })
+    }
+
+    /// Handles the presentation request the verifier responded with, transitioning to [`RequestReceived`].
+    pub fn receive_request(self, request: RequestPresentationV2) -> VcxResult<ProverV2<RequestReceived<T>>> {
+        verify_thread_id(&self.thread_id, request.decorators.thread.as_ref())?;
+
+        Ok(ProverV2 {
+            state: RequestReceived::new(request),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: ProverProofPresentationFormat> ProverV2<RequestReceived<T>> {
+    /// Starts the protocol from a presentation request received from the verifier.
+    pub fn from_request(request: RequestPresentationV2) -> Self {
+        let thread_id = request
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| request.id.clone());
+
+        Self {
+            state: RequestReceived::new(request),
+            thread_id,
+        }
+    }
+
+    /// Extracts the format specific details of the received presentation request.
+    pub fn get_request_details(&self) -> VcxResult<T::RequestDetails> {
+        T::extract_request_details(self.state.get_request())
+    }
+
+    /// Responds to the request with a counter proposal, transitioning to [`ProposalPrepared`].
+    pub async fn prepare_proposal(self, input: &T::CreateProposalInput) -> VcxResult<ProverV2<ProposalPrepared<T>>> {
+        let thread = Some(Thread::new(self.thread_id.clone()));
+        let proposal = create_proposal::<T>(input, thread).await?;
+
+        Ok(ProverV2 {
+            state: ProposalPrepared::new(proposal),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Generates the presentation answering the request, transitioning to [`PresentationPrepared`].
+    ///
+    /// When `please_ack` is set, the verifier is asked to acknowledge the presentation.
+    pub async fn prepare_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        input: &T::CreatePresentationInput,
+        please_ack: bool,
+    ) -> VcxResult<ProverV2<PresentationPrepared<T>>> {
+        let attachment_content =
+            T::create_presentation_attachment_content(profile, self.state.get_request(), input).await?;
+
+        let (format, attachment) = make_format_attachment(
+            Uuid::new_v4().to_string(),
+            T::get_presentation_attachment_format(),
+            &attachment_content,
+        );
+
+        let content = PresentationV2Content::new(vec![format], vec![attachment]);
+
+        let mut decorators = PresentationV2Decorators::new(Thread::new(self.thread_id.clone()));
+        if please_ack {
+            decorators.please_ack = Some(PleaseAck::new(vec![AckOn::Outcome]));
+        }
+
+        let presentation = PresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(ProverV2 {
+            state: PresentationPrepared::new(presentation),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: ProverProofPresentationFormat> ProverV2<PresentationPrepared<T>> {
+    /// Completes the protocol once the presentation was sent, if no ack was requested from the verifier.
+    pub fn complete_without_ack(self) -> VcxResult<ProverV2<Complete<T>>> {
+        if self.state.is_expecting_ack() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Cannot complete the protocol without an ack, as one was requested from the verifier",
+            ));
+        }
+
+        Ok(ProverV2 {
+            state: Complete::new(self.state.into_presentation(), None),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Completes the protocol with the ack received from the verifier.
+    pub fn complete_with_ack(self, ack: AckPresentationV2) -> VcxResult<ProverV2<Complete<T>>> {
+        verify_thread_id(&self.thread_id, Some(&ack.decorators.thread))?;
+
+        Ok(ProverV2 {
+            state: Complete::new(self.state.into_presentation(), Some(ack)),
+            thread_id: self.thread_id,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::present_proof_v2::ack::AckPresentationV2;
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationV2;
+
+use crate::protocols::proof_presentation_v2::formats::prover::ProverProofPresentationFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Complete<T: ProverProofPresentationFormat> {
+    presentation: PresentationV2,
+    ack: Option<AckPresentationV2>,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ProverProofPresentationFormat> Complete<T> {
+    pub fn new(presentation: PresentationV2, ack: Option<AckPresentationV2>) -> Self {
+        Self {
+            presentation,
+            ack,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_presentation(&self) -> &PresentationV2 {
+        &self.presentation
+    }
+
+    pub fn get_ack(&self) -> Option<&AckPresentationV2> {
+        self.ack.as_ref()
+    }
+}
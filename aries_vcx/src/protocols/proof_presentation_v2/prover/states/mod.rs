@@ -0,0 +1,4 @@
+pub mod complete;
+pub mod presentation_prepared;
+pub mod proposal_prepared;
+pub mod request_received;
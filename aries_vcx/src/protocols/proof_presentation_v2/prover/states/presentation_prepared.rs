@@ -0,0 +1,35 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationV2;
+
+use crate::protocols::proof_presentation_v2::formats::prover::ProverProofPresentationFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct PresentationPrepared<T: ProverProofPresentationFormat> {
+    presentation: PresentationV2,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ProverProofPresentationFormat> PresentationPrepared<T> {
+    pub fn new(presentation: PresentationV2) -> Self {
+        Self {
+            presentation,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_presentation(&self) -> &PresentationV2 {
+        &self.presentation
+    }
+
+    /// Whether the verifier was asked to acknowledge the presentation through `~please_ack`.
+    pub fn is_expecting_ack(&self) -> bool {
+        self.presentation.decorators.please_ack.is_some()
+    }
+
+    pub(crate) fn into_presentation(self) -> PresentationV2 {
+        self.presentation
+    }
+}
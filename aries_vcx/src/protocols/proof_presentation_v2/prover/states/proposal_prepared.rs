@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::present_proof_v2::propose::ProposePresentationV2;
+
+use crate::protocols::proof_presentation_v2::formats::prover::ProverProofPresentationFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct ProposalPrepared<T: ProverProofPresentationFormat> {
+    proposal: ProposePresentationV2,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ProverProofPresentationFormat> ProposalPrepared<T> {
+    pub fn new(proposal: ProposePresentationV2) -> Self {
+        Self {
+            proposal,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_proposal(&self) -> &ProposePresentationV2 {
+        &self.proposal
+    }
+}
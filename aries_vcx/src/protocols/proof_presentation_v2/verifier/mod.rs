@@ -0,0 +1,188 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use messages::decorators::thread::Thread;
+use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
+use messages::msg_fields::protocols::present_proof_v2::ack::{AckPresentationV2, AckPresentationV2Content};
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationV2;
+use messages::msg_fields::protocols::present_proof_v2::problem_report::PresentProofV2ProblemReport;
+use messages::msg_fields::protocols::present_proof_v2::propose::ProposePresentationV2;
+use messages::msg_fields::protocols::present_proof_v2::request::{
+    RequestPresentationV2, RequestPresentationV2Content, RequestPresentationV2Decorators,
+};
+use uuid::Uuid;
+
+use self::states::complete::Complete;
+use self::states::proposal_received::ProposalReceived;
+use self::states::request_prepared::RequestPrepared;
+use super::formats::verifier::VerifierProofPresentationFormat;
+use super::{build_problem_report, Failed};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::protocols::common::{make_format_attachment, verify_thread_id};
+use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+
+/// Verifier side of the present-proof/2.0 protocol, in the state `S`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerifierV2<S> {
+    state: S,
+    thread_id: String,
+}
+
+impl<S> VerifierV2<S> {
+    pub fn get_state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    /// Aborts the protocol, transitioning to [`Failed`].
+    /// The problem report to send to the prover can be retrieved from the [`Failed`] state.
+    pub fn prepare_problem_report(self, comment: Option<String>) -> VerifierV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+
+        VerifierV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        }
+    }
+
+    /// Handles a problem report received from the prover, transitioning to [`Failed`].
+    pub fn receive_problem_report(self, problem_report: PresentProofV2ProblemReport) -> VcxResult<VerifierV2<Failed>> {
+        verify_thread_id(&self.thread_id, problem_report.decorators.thread.as_ref())?;
+
+        Ok(VerifierV2 {
+            state: Failed::new(problem_report),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+async fn create_request<T: VerifierProofPresentationFormat>(
+    profile: &Arc<dyn Profile>,
+    input: &T::CreateRequestInput,
+    will_confirm: bool,
+    thread: Option<Thread>,
+) -> VcxResult<RequestPresentationV2> {
+    let attachment_content = T::create_request_attachment_content(profile, input).await?;
+    let (format, attachment) = make_format_attachment(
+        Uuid::new_v4().to_string(),
+        T::get_request_attachment_format(),
+        &attachment_content,
+    );
+
+    let mut content = RequestPresentationV2Content::new(vec![format], vec![attachment]);
+    content.will_confirm = Some(will_confirm);
+
+    let decorators = RequestPresentationV2Decorators {
+        thread,
+        ..Default::default()
+    };
+
+    Ok(RequestPresentationV2::with_decorators(
+        Uuid::new_v4().to_string(),
+        content,
+        decorators,
+    ))
+}
+
+impl<T: VerifierProofPresentationFormat> VerifierV2<ProposalReceived<T>> {
+    /// Starts the protocol from a proposal received from the prover.
+    pub fn from_proposal(proposal: ProposePresentationV2) -> Self {
+        let thread_id = proposal
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| proposal.id.clone());
+
+        Self {
+            state: ProposalReceived::new(proposal),
+            thread_id,
+        }
+    }
+
+    /// Extracts the format specific details of the received proposal.
+    pub fn get_proposal_details(&self) -> VcxResult<T::ProposalDetails> {
+        T::extract_proposal_details(self.state.get_proposal())
+    }
+
+    /// Responds to the proposal with a presentation request, transitioning to [`RequestPrepared`].
+    ///
+    /// When `will_confirm` is set, the verifier commits to acknowledging a valid presentation.
+    pub async fn prepare_request(
+        self,
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateRequestInput,
+        will_confirm: bool,
+    ) -> VcxResult<VerifierV2<RequestPrepared<T>>> {
+        let thread = Some(Thread::new(self.thread_id.clone()));
+        let request = create_request::<T>(profile, input, will_confirm, thread).await?;
+
+        Ok(VerifierV2 {
+            state: RequestPrepared::new(request),
+            thread_id: self.thread_id,
+        })
+    }
+}
+
+impl<T: VerifierProofPresentationFormat> VerifierV2<RequestPrepared<T>> {
+    /// Starts the protocol by requesting a presentation from the prover.
+    pub async fn with_request(
+        profile: &Arc<dyn Profile>,
+        input: &T::CreateRequestInput,
+        will_confirm: bool,
+    ) -> VcxResult<Self> {
+        let request = create_request::<T>(profile, input, will_confirm, None).await?;
+        let thread_id = request.id.clone();
+
+        Ok(Self {
+            state: RequestPrepared::new(request),
+            thread_id,
+        })
+    }
+
+    /// Handles a counter proposal of the prover, transitioning back to [`ProposalReceived`].
+    pub fn receive_proposal(self, proposal: ProposePresentationV2) -> VcxResult<VerifierV2<ProposalReceived<T>>> {
+        verify_thread_id(&self.thread_id, proposal.decorators.thread.as_ref())?;
+
+        Ok(VerifierV2 {
+            state: ProposalReceived::new(proposal),
+            thread_id: self.thread_id,
+        })
+    }
+
+    /// Verifies the presentation of the prover, transitioning to [`Complete`].
+    ///
+    /// If the presentation is valid and the prover asked for an ack, or the verifier announced
+    /// it would confirm the presentation, the ack is prepared as part of the transition.
+    pub async fn receive_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        presentation: PresentationV2,
+    ) -> VcxResult<VerifierV2<Complete<T>>> {
+        verify_thread_id(&self.thread_id, Some(&presentation.decorators.thread))?;
+
+        let request = self.state.get_request();
+        let verification_status = match T::verify_presentation(profile, request, &presentation).await? {
+            true => PresentationVerificationStatus::Valid,
+            false => PresentationVerificationStatus::Invalid,
+        };
+
+        let wants_ack = presentation.decorators.please_ack.is_some() || request.content.will_confirm.unwrap_or(false);
+
+        let ack = (wants_ack && verification_status == PresentationVerificationStatus::Valid).then(|| {
+            let content = AckPresentationV2Content::new(AckStatus::Ok);
+            let decorators = AckDecorators::new(Thread::new(self.thread_id.clone()));
+            AckPresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+        });
+
+        Ok(VerifierV2 {
+            state: Complete::new(presentation, verification_status, ack),
+            thread_id: self.thread_id,
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::present_proof_v2::ack::AckPresentationV2;
+use messages::msg_fields::protocols::present_proof_v2::present::PresentationV2;
+
+use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+use crate::protocols::proof_presentation_v2::formats::verifier::VerifierProofPresentationFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct Complete<T: VerifierProofPresentationFormat> {
+    presentation: PresentationV2,
+    verification_status: PresentationVerificationStatus,
+    ack: Option<AckPresentationV2>,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: VerifierProofPresentationFormat> Complete<T> {
+    pub fn new(
+        presentation: PresentationV2,
+        verification_status: PresentationVerificationStatus,
+        ack: Option<AckPresentationV2>,
+    ) -> Self {
+        Self {
+            presentation,
+            verification_status,
+            ack,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_presentation(&self) -> &PresentationV2 {
+        &self.presentation
+    }
+
+    pub fn get_verification_status(&self) -> &PresentationVerificationStatus {
+        &self.verification_status
+    }
+
+    /// The ack to send to the prover, if the presentation is valid and the prover
+    /// requested one through `~please_ack` or the verifier announced it with `will_confirm`.
+    pub fn get_ack(&self) -> Option<&AckPresentationV2> {
+        self.ack.as_ref()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod complete;
+pub mod proposal_received;
+pub mod request_prepared;
@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use messages::msg_fields::protocols::present_proof_v2::request::RequestPresentationV2;
+
+use crate::protocols::proof_presentation_v2::formats::verifier::VerifierProofPresentationFormat;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct RequestPrepared<T: VerifierProofPresentationFormat> {
+    request: RequestPresentationV2,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: VerifierProofPresentationFormat> RequestPrepared<T> {
+    pub fn new(request: RequestPresentationV2) -> Self {
+        Self {
+            request,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get_request(&self) -> &RequestPresentationV2 {
+        &self.request
+    }
+}
@@ -0,0 +1,53 @@
+use ::uuid::Uuid;
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::transactions::{
+        endorse::{Endorse, EndorseContent, EndorseDecorators},
+        refuse::{Refuse, RefuseContent, RefuseDecorators},
+        request::{Request, RequestContent, RequestDecorators},
+    },
+};
+
+use crate::handlers::util::{make_attach_from_str, AttachmentId};
+
+fn make_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    timing
+}
+
+pub fn build_transaction_request(transaction: &str, endorser_write_txn: bool, comment: Option<String>) -> Request {
+    let attachment = make_attach_from_str!(transaction, AttachmentId::LedgerTransaction.as_ref().to_string());
+    let mut content = RequestContent::new(vec![attachment], endorser_write_txn);
+    content.comment = comment;
+
+    let mut decorators = RequestDecorators::default();
+    decorators.timing = Some(make_timing());
+
+    Request::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// Builds the answer of an endorser, carrying the endorsed transaction unless the endorser wrote it.
+pub fn build_endorse(thread_id: String, endorser_did: String, endorsed_transaction: Option<&str>) -> Endorse {
+    let mut content = EndorseContent::new(endorser_did);
+    content.messages_attach = endorsed_transaction
+        .map(|transaction| make_attach_from_str!(transaction, AttachmentId::LedgerTransaction.as_ref().to_string()))
+        .into_iter()
+        .collect();
+
+    let mut decorators = EndorseDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    Endorse::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_refuse(thread_id: String, endorser_did: String, explanation: Option<String>) -> Refuse {
+    let mut content = RefuseContent::new(endorser_did);
+    content.explanation = explanation;
+
+    let mut decorators = RefuseDecorators::new(Thread::new(thread_id));
+    decorators.timing = Some(make_timing());
+
+    Refuse::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
@@ -3,6 +3,9 @@ use url::Url;
 
 use crate::errors::error::VcxResult;
 
+#[cfg(feature = "ws_transport")]
+pub mod ws;
+
 /// Trait used for implementing a mechanism to send a message, used by [`crate::protocols::connection::Connection`].
 #[async_trait]
 pub trait Transport: Send + Sync {
@@ -21,3 +24,12 @@ where
         self.send_message(msg, service_endpoint).await
     }
 }
+
+/// Trait used for handling the packed messages received over a connection the sender keeps open,
+/// such as a WebSocket, on which replies can be returned.
+#[async_trait]
+pub trait InboundHandler: Send + Sync {
+    /// Handles a packed message, returning the packed reply to write back over the connection it
+    /// came on when its sender asked for it with the `~transport` decorator.
+    async fn receive_packed(&self, payload: Vec<u8>) -> VcxResult<Option<Vec<u8>>>;
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{accept_async, connect_async, MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+use crate::errors::error::prelude::*;
+use crate::transport::{InboundHandler, Transport};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WsSink = SplitSink<WsStream, Message>;
+
+/// How many received messages are buffered until taken from the [`WsInbound`]. Past it, the
+/// sockets are not read until the inbound catches up.
+pub const INBOUND_CAPACITY: usize = 100;
+
+/// Sends messages over WebSockets to `ws://` and `wss://` endpoints, keeping one socket open per
+/// endpoint.
+///
+/// Whatever the endpoint sends back over a socket is handed as is to the [`WsInbound`] created
+/// along with the transport. Asking the recipient to reply over the socket, with the `~transport`
+/// decorator, is left to the caller building the message.
+pub struct WsTransport {
+    sockets: Mutex<HashMap<Url, Arc<Mutex<Option<WsSink>>>>>,
+    inbound: mpsc::Sender<Vec<u8>>,
+}
+
+/// The receiving side of a [`WsTransport`], yielding the packed messages received over its sockets.
+pub struct WsInbound {
+    receiver: mpsc::Receiver<Vec<u8>>,
+}
+
+impl WsInbound {
+    /// Waits for the next message, returning `None` once the transport is dropped and all of its
+    /// sockets are closed.
+    pub async fn receive(&mut self) -> Option<Vec<u8>> {
+        self.receiver.recv().await
+    }
+
+    /// Returns a message if one was already received.
+    pub fn try_receive(&mut self) -> Option<Vec<u8>> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl WsTransport {
+    pub fn new() -> (Self, WsInbound) {
+        let (inbound, receiver) = mpsc::channel(INBOUND_CAPACITY);
+        let transport = Self {
+            sockets: Mutex::new(HashMap::new()),
+            inbound,
+        };
+        (transport, WsInbound { receiver })
+    }
+
+    /// Closes the socket open to the endpoint, if any.
+    pub async fn close(&self, service_endpoint: &Url) -> VcxResult<()> {
+        let socket = self.sockets.lock().await.remove(service_endpoint);
+        if let Some(socket) = socket {
+            if let Some(mut sink) = socket.lock().await.take() {
+                sink.close().await.map_err(ws_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The slot of the socket to the endpoint, empty until a socket is opened.
+    async fn socket(&self, service_endpoint: &Url) -> Arc<Mutex<Option<WsSink>>> {
+        self.sockets
+            .lock()
+            .await
+            .entry(service_endpoint.clone())
+            .or_default()
+            .clone()
+    }
+
+    async fn connect(&self, service_endpoint: &Url) -> VcxResult<WsSink> {
+        debug!("WsTransport::connect >> opening socket to {}", service_endpoint);
+        let (socket, _) = connect_async(service_endpoint.as_str()).await.map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::PostMessageFailed,
+                format!("Unable to open socket to {}: {}", service_endpoint, err),
+            )
+        })?;
+        let (sink, stream) = socket.split();
+        tokio::spawn(forward_inbound(stream, self.inbound.clone(), service_endpoint.clone()));
+        Ok(sink)
+    }
+}
+
+#[async_trait]
+impl Transport for WsTransport {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: Url) -> VcxResult<()> {
+        if !matches!(service_endpoint.scheme(), "ws" | "wss") {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("Endpoint {} is not a WebSocket endpoint", service_endpoint),
+            ));
+        }
+
+        // The messages to an endpoint are sent one at a time over its socket, so that concurrent
+        // sends share the socket rather than each opening one, while a slow endpoint doesn't block
+        // the sends to the other ones.
+        let socket = self.socket(&service_endpoint).await;
+        let mut sink = socket.lock().await;
+        if let Some(open) = sink.as_mut() {
+            match open.send(Message::Binary(msg.clone())).await {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    warn!(
+                        "WsTransport::send_message >> socket to {} failed, reconnecting: {}",
+                        service_endpoint, err
+                    );
+                    *sink = None;
+                }
+            }
+        }
+
+        let mut opened = self.connect(&service_endpoint).await?;
+        opened.send(Message::Binary(msg)).await.map_err(ws_error)?;
+        *sink = Some(opened);
+        Ok(())
+    }
+}
+
+/// Serves WebSocket connections accepted on the listener, handing the messages received to the
+/// handler and writing its replies back over the socket each message came on. Serves until
+/// accepting a connection fails.
+pub async fn serve<H>(listener: TcpListener, handler: Arc<H>) -> VcxResult<()>
+where
+    H: InboundHandler + ?Sized + 'static,
+{
+    loop {
+        let (stream, peer) = listener.accept().await.map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::IOError,
+                format!("Unable to accept WebSocket connection: {}", err),
+            )
+        })?;
+        debug!("serve >> accepted connection from {}", peer);
+        tokio::spawn(serve_socket(stream, handler.clone()));
+    }
+}
+
+/// Hands the messages received over the socket to the handler one at a time, writing back the
+/// replies it returns, until the socket is closed.
+async fn serve_socket<H>(stream: TcpStream, handler: Arc<H>)
+where
+    H: InboundHandler + ?Sized,
+{
+    let mut socket = match accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            warn!("serve_socket >> WebSocket handshake failed: {}", err);
+            return;
+        }
+    };
+    while let Some(message) = socket.next().await {
+        let payload = match message {
+            Ok(Message::Binary(payload)) => payload,
+            Ok(Message::Text(payload)) => payload.into_bytes(),
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(err) => {
+                warn!("serve_socket >> socket failed: {}", err);
+                break;
+            }
+        };
+        match handler.receive_packed(payload).await {
+            Ok(Some(reply)) => {
+                if let Err(err) = socket.send(Message::Binary(reply)).await {
+                    warn!("serve_socket >> unable to return reply: {}", err);
+                    break;
+                }
+            }
+            Ok(None) => {}
+            Err(err) => warn!("serve_socket >> failed to handle message: {}", err),
+        }
+    }
+}
+
+/// Hands the messages received over the socket to the inbound channel, waiting while it is full,
+/// until the socket is closed. The sink is left in place, the next message sent to the endpoint failing on it and reconnecting.
+async fn forward_inbound(mut stream: SplitStream<WsStream>, inbound: mpsc::Sender<Vec<u8>>, service_endpoint: Url) {
+    while let Some(message) = stream.next().await {
+        let payload = match message {
+            Ok(Message::Binary(payload)) => payload,
+            Ok(Message::Text(payload)) => payload.into_bytes(),
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(err) => {
+                warn!("WsTransport >> socket to {} failed: {}", service_endpoint, err);
+                break;
+            }
+        };
+        if inbound.send(payload).await.is_err() {
+            debug!(
+                "WsTransport >> inbound of socket to {} dropped, discarding message",
+                service_endpoint
+            );
+        }
+    }
+    debug!("WsTransport >> socket to {} closed", service_endpoint);
+}
+
+fn ws_error(err: tokio_tungstenite::tungstenite::Error) -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::PostMessageFailed,
+        format!("WebSocket error: {}", err),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Serves a WebSocket endpoint sending every message back over the socket it came on.
+    async fn spawn_returning_endpoint() -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = Url::parse(&format!("ws://{}/", listener.local_addr().unwrap())).unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut socket = accept_async(stream).await.unwrap();
+                    while let Some(Ok(message)) = socket.next().await {
+                        if message.is_binary() && socket.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (endpoint, accepted)
+    }
+
+    #[tokio::test]
+    async fn test_ws_transport_receives_returned_messages() {
+        let (endpoint, accepted) = spawn_returning_endpoint().await;
+        let (transport, mut inbound) = WsTransport::new();
+
+        transport
+            .send_message(b"first".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+        transport
+            .send_message(b"second".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(inbound.receive().await.unwrap(), b"first".to_vec());
+        assert_eq!(inbound.receive().await.unwrap(), b"second".to_vec());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ws_transport_reconnects_after_close() {
+        let (endpoint, accepted) = spawn_returning_endpoint().await;
+        let (transport, mut inbound) = WsTransport::new();
+
+        transport
+            .send_message(b"first".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+        assert_eq!(inbound.receive().await.unwrap(), b"first".to_vec());
+        transport.close(&endpoint).await.unwrap();
+
+        transport
+            .send_message(b"second".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+        assert_eq!(inbound.receive().await.unwrap(), b"second".to_vec());
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ws_transport_not_blocked_by_connecting_endpoint() {
+        // Accepts connections without ever answering the WebSocket handshake.
+        let stalled = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let stalled_endpoint = Url::parse(&format!("ws://{}/", stalled.local_addr().unwrap())).unwrap();
+        let (endpoint, _) = spawn_returning_endpoint().await;
+        let (transport, mut inbound) = WsTransport::new();
+        let transport = Arc::new(transport);
+
+        let stalled_send = tokio::spawn({
+            let transport = transport.clone();
+            async move { transport.send_message(b"stalled".to_vec(), stalled_endpoint).await }
+        });
+        let (_stalled_stream, _) = stalled.accept().await.unwrap();
+
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            transport.send_message(b"first".to_vec(), endpoint),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(inbound.receive().await.unwrap(), b"first".to_vec());
+        stalled_send.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ws_transport_shares_socket_between_concurrent_sends() {
+        let (endpoint, accepted) = spawn_returning_endpoint().await;
+        let (transport, mut inbound) = WsTransport::new();
+        let transport = Arc::new(transport);
+
+        let sends = (0..5u8)
+            .map(|index| {
+                let (transport, endpoint) = (transport.clone(), endpoint.clone());
+                tokio::spawn(async move { transport.send_message(vec![index], endpoint).await })
+            })
+            .collect::<Vec<_>>();
+        for send in sends {
+            send.await.unwrap().unwrap();
+        }
+
+        let mut returned = Vec::new();
+        for _ in 0..5 {
+            returned.push(inbound.receive().await.unwrap());
+        }
+        returned.sort();
+        assert_eq!(returned, (0..5u8).map(|index| vec![index]).collect::<Vec<_>>());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    /// Returns a reply to the messages asking for one, as if they carried a `~transport` decorator.
+    struct ReturningHandler;
+
+    #[async_trait]
+    impl InboundHandler for ReturningHandler {
+        async fn receive_packed(&self, payload: Vec<u8>) -> VcxResult<Option<Vec<u8>>> {
+            match payload.strip_prefix(b"return ") {
+                Some(returned) => Ok(Some([b"reply to ", returned].concat())),
+                None => Ok(None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serve_returns_replies_over_socket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = Url::parse(&format!("ws://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(serve(listener, Arc::new(ReturningHandler)));
+        let (transport, mut inbound) = WsTransport::new();
+
+        transport
+            .send_message(b"first".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+        transport
+            .send_message(b"return second".to_vec(), endpoint.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(inbound.receive().await.unwrap(), b"reply to second".to_vec());
+        assert!(inbound.try_receive().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ws_transport_rejects_http_endpoint() {
+        let (transport, _inbound) = WsTransport::new();
+        let err = transport
+            .send_message(b"message".to_vec(), Url::parse("http://127.0.0.1:8080/").unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidUrl);
+    }
+}
@@ -16,6 +16,8 @@ use aries_vcx_core::indy::wallet::{
     open_wallet, wallet_configure_issuer, WalletConfig,
 };
 
+#[cfg(feature = "in_memory_ledger")]
+use aries_vcx_core::ledger::in_memory_ledger::{InMemoryLedger, LedgerRole};
 #[cfg(feature = "modular_libs")]
 use aries_vcx_core::ledger::request_submitter::vdr_ledger::LedgerPoolConfig;
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
@@ -100,6 +102,12 @@ pub struct SetupPool {
     pub genesis_file_path: String,
 }
 
+/// In-memory ledger for the agents of a test to share, in place of a pool.
+#[cfg(feature = "in_memory_ledger")]
+pub struct SetupInMemoryLedger {
+    pub ledger: Arc<InMemoryLedger>,
+}
+
 fn reset_global_state() {
     warn!("reset_global_state >>");
     AgencyMockDecrypted::clear_mocks();
@@ -371,6 +379,12 @@ impl SetupProfile {
         init_test_logging();
         set_test_configs();
 
+        #[cfg(feature = "in_memory_ledger")]
+        return {
+            info!("SetupProfile >> using in-memory ledger profile");
+            SetupProfile::init_in_memory_ledger().await
+        };
+
         #[cfg(feature = "mixed_breed")]
         return {
             info!("SetupProfile >> using mixed breed profile");
@@ -486,6 +500,38 @@ impl SetupProfile {
         }
     }
 
+    #[cfg(feature = "in_memory_ledger")]
+    async fn init_in_memory_ledger() -> SetupProfile {
+        SetupProfile::init_with_in_memory_ledger(SetupInMemoryLedger::init().ledger).await
+    }
+
+    /// Sets up the institution on a ledger which the profiles of other agents can share.
+    #[cfg(feature = "in_memory_ledger")]
+    pub async fn init_with_in_memory_ledger(ledger: Arc<InMemoryLedger>) -> SetupProfile {
+        init_test_logging();
+        set_test_configs();
+
+        let (institution_did, wallet_handle) = setup_issuer_wallet().await;
+
+        let profile = in_memory_ledger_profile(wallet_handle, ledger);
+
+        Arc::clone(&profile)
+            .inject_anoncreds()
+            .prover_create_link_secret(settings::DEFAULT_LINK_SECRET_ALIAS)
+            .await
+            .unwrap();
+
+        async fn in_memory_ledger_teardown() {
+            // nothing to do
+        }
+
+        SetupProfile {
+            institution_did,
+            profile,
+            teardown: Arc::new(move || Box::pin(in_memory_ledger_teardown())),
+        }
+    }
+
     #[cfg(feature = "vdr_proxy_ledger")]
     async fn init_vdr_proxy_ledger() -> SetupProfile {
         use std::env;
@@ -608,6 +654,45 @@ pub fn create_new_seed() -> String {
     format!("{x:032}")
 }
 
+#[cfg(feature = "in_memory_ledger")]
+impl SetupInMemoryLedger {
+    /// A ledger on which the trustee DID the test institutions use is written.
+    pub fn init() -> SetupInMemoryLedger {
+        init_test_logging();
+
+        let ledger = Arc::new(InMemoryLedger::new());
+        ledger
+            .add_genesis_nym(
+                utils::constants::TRUSTEE_DID,
+                utils::constants::TRUSTEE_VERKEY,
+                Some(LedgerRole::Trustee),
+            )
+            .unwrap();
+
+        SetupInMemoryLedger { ledger }
+    }
+
+    pub async fn run<F>(f: impl FnOnce(Self) -> F)
+    where
+        F: Future<Output = ()>,
+    {
+        f(Self::init()).await;
+
+        reset_global_state();
+    }
+}
+
+/// Profile of an agent keeping its wallet in vdrtools and writing to the in-memory ledger.
+#[cfg(feature = "in_memory_ledger")]
+pub fn in_memory_ledger_profile(wallet_handle: WalletHandle, ledger: Arc<InMemoryLedger>) -> Arc<dyn Profile> {
+    use crate::core::profile::in_memory_ledger_profile::InMemoryLedgerProfile;
+    use aries_vcx_core::anoncreds::indy_anoncreds::IndySdkAnonCreds;
+
+    let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(wallet_handle));
+    let anoncreds = Arc::new(IndySdkAnonCreds::new(wallet_handle));
+    Arc::new(InMemoryLedgerProfile::new(wallet, anoncreds, ledger))
+}
+
 pub async fn setup_issuer_wallet_and_agency_client() -> (String, WalletHandle, AgencyClient) {
     let enterprise_seed = "000000000000000000000000Trustee1";
     let config_wallet = WalletConfig {
@@ -0,0 +1,317 @@
+//! Low level cryptographic primitives needed for DIDComm v2 envelopes, implemented on top of OpenSSL.
+//!
+//! See the [JWA RFC](<https://datatracker.ietf.org/doc/html/rfc7518>) and the
+//! [ECDH-1PU draft](<https://datatracker.ietf.org/doc/html/draft-madden-jose-ecdh-1pu-04>).
+
+use openssl::aes::{self, AesKey};
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::derive::Deriver;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkey::{Id, PKey};
+use openssl::rand::rand_bytes;
+use openssl::sha::Sha256;
+use openssl::sign::Signer;
+use openssl::symm::{self, Cipher};
+
+use crate::errors::error::prelude::*;
+
+pub const KEY_LEN: usize = 32;
+pub const CEK_LEN: usize = 64;
+pub const IV_LEN: usize = 16;
+pub const TAG_LEN: usize = 32;
+const WRAPPED_CEK_LEN: usize = CEK_LEN + 8;
+
+// Field prime of Curve25519: 2^255 - 19
+const P25519_HEX: &str = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+
+fn crypto_err(err: ErrorStack) -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::InvalidInput,
+        format!("Cryptographic operation failed: {err}"),
+    )
+}
+
+pub fn b64_encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+pub fn b64_decode(data: &str) -> VcxResult<Vec<u8>> {
+    base64::decode_config(data, base64::URL_SAFE_NO_PAD).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot decode base64url value: {err}"),
+        )
+    })
+}
+
+pub fn random_bytes(len: usize) -> VcxResult<Vec<u8>> {
+    let mut buf = vec![0; len];
+    rand_bytes(&mut buf).map_err(crypto_err)?;
+    Ok(buf)
+}
+
+/// Converts an Ed25519 public key to the X25519 public key of the same key pair,
+/// using the birational map between the curves: `u = (1 + y) / (1 - y) mod p`.
+pub fn ed25519_to_x25519_public(ed25519_pk: &[u8]) -> VcxResult<Vec<u8>> {
+    if ed25519_pk.len() != KEY_LEN {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidVerkey,
+            format!("Invalid Ed25519 public key length: {}", ed25519_pk.len()),
+        ));
+    }
+
+    // The key is the little endian y coordinate, with the highest bit storing the sign of x.
+    let mut y_be = ed25519_pk.to_vec();
+    y_be[KEY_LEN - 1] &= 0x7f;
+    y_be.reverse();
+
+    let convert = || -> Result<Vec<u8>, ErrorStack> {
+        let mut ctx = BigNumContext::new()?;
+        let p = BigNum::from_hex_str(P25519_HEX)?;
+        let y = BigNum::from_slice(&y_be)?;
+        let one = BigNum::from_u32(1)?;
+
+        let mut numerator = BigNum::new()?;
+        numerator.mod_add(&one, &y, &p, &mut ctx)?;
+        let mut denominator = BigNum::new()?;
+        denominator.mod_sub(&one, &y, &p, &mut ctx)?;
+        let mut denominator_inv = BigNum::new()?;
+        denominator_inv.mod_inverse(&denominator, &p, &mut ctx)?;
+        let mut u = BigNum::new()?;
+        u.mod_mul(&numerator, &denominator_inv, &p, &mut ctx)?;
+
+        let mut u_le = u.to_vec_padded(KEY_LEN as i32)?;
+        u_le.reverse();
+        Ok(u_le)
+    };
+
+    convert().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidVerkey,
+            format!("Cannot convert Ed25519 public key to X25519: {err}"),
+        )
+    })
+}
+
+/// Generates an X25519 key pair, returned as the raw `(private, public)` keys.
+pub fn x25519_generate() -> VcxResult<(Vec<u8>, Vec<u8>)> {
+    let key = PKey::generate_x25519().map_err(crypto_err)?;
+    let private = key.raw_private_key().map_err(crypto_err)?;
+    let public = key.raw_public_key().map_err(crypto_err)?;
+    Ok((private, public))
+}
+
+/// Computes the X25519 shared secret between a raw private key and a raw public key.
+pub fn x25519_ecdh(private: &[u8], public: &[u8]) -> VcxResult<Vec<u8>> {
+    let private = PKey::private_key_from_raw_bytes(private, Id::X25519).map_err(crypto_err)?;
+    let public = PKey::public_key_from_raw_bytes(public, Id::X25519).map_err(crypto_err)?;
+
+    let mut deriver = Deriver::new(&private).map_err(crypto_err)?;
+    deriver.set_peer(&public).map_err(crypto_err)?;
+    deriver.derive_to_vec().map_err(crypto_err)
+}
+
+/// Single step Concat KDF, as used by `ECDH-ES` and `ECDH-1PU` in key agreement with key wrapping mode.
+///
+/// The `cc_tag` is only used by `ECDH-1PU`, which binds the content encryption tag
+/// to the derived key. It must be empty for `ECDH-ES`.
+pub fn concat_kdf(z: &[u8], alg: &str, apu: &[u8], apv: &[u8], cc_tag: &[u8]) -> [u8; KEY_LEN] {
+    let mut hasher = Sha256::new();
+
+    // A single round is enough for a 256 bit key.
+    hasher.update(&1u32.to_be_bytes());
+    hasher.update(z);
+
+    for part in [alg.as_bytes(), apu, apv] {
+        hasher.update(&(part.len() as u32).to_be_bytes());
+        hasher.update(part);
+    }
+
+    hasher.update(&((KEY_LEN * 8) as u32).to_be_bytes());
+
+    if !cc_tag.is_empty() {
+        hasher.update(&(cc_tag.len() as u32).to_be_bytes());
+        hasher.update(cc_tag);
+    }
+
+    hasher.finish()
+}
+
+/// Wraps a content encryption key with `A256KW`.
+pub fn aes_key_wrap(kek: &[u8], cek: &[u8]) -> VcxResult<Vec<u8>> {
+    if cek.len() != CEK_LEN {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            format!("Invalid content encryption key length: {}", cek.len()),
+        ));
+    }
+
+    let kek = AesKey::new_encrypt(kek)
+        .map_err(|_| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, "Invalid key encryption key"))?;
+
+    let mut wrapped = vec![0; WRAPPED_CEK_LEN];
+    aes::wrap_key(&kek, None, &mut wrapped, cek)
+        .map_err(|_| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, "Cannot wrap content encryption key"))?;
+
+    Ok(wrapped)
+}
+
+/// Unwraps a content encryption key wrapped with `A256KW`.
+pub fn aes_key_unwrap(kek: &[u8], wrapped: &[u8]) -> VcxResult<Vec<u8>> {
+    if wrapped.len() != WRAPPED_CEK_LEN {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Invalid wrapped content encryption key length: {}", wrapped.len()),
+        ));
+    }
+
+    let kek = AesKey::new_decrypt(kek)
+        .map_err(|_| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, "Invalid key encryption key"))?;
+
+    let mut cek = vec![0; CEK_LEN];
+    aes::unwrap_key(&kek, None, &mut cek, wrapped)
+        .map_err(|_| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, "Cannot unwrap content encryption key"))?;
+
+    Ok(cek)
+}
+
+fn a256cbc_hs512_tag(mac_key: &[u8], aad: &[u8], iv: &[u8], ciphertext: &[u8]) -> VcxResult<Vec<u8>> {
+    let al = ((aad.len() as u64) * 8).to_be_bytes();
+
+    let key = PKey::hmac(mac_key).map_err(crypto_err)?;
+    let mut signer = Signer::new(MessageDigest::sha512(), &key).map_err(crypto_err)?;
+    signer.update(aad).map_err(crypto_err)?;
+    signer.update(iv).map_err(crypto_err)?;
+    signer.update(ciphertext).map_err(crypto_err)?;
+    signer.update(&al).map_err(crypto_err)?;
+
+    let mut mac = signer.sign_to_vec().map_err(crypto_err)?;
+    mac.truncate(TAG_LEN);
+    Ok(mac)
+}
+
+/// Encrypts the plaintext with `A256CBC-HS512`, returning the ciphertext and the authentication tag.
+pub fn a256cbc_hs512_encrypt(cek: &[u8], iv: &[u8], aad: &[u8], plaintext: &[u8]) -> VcxResult<(Vec<u8>, Vec<u8>)> {
+    if cek.len() != CEK_LEN || iv.len() != IV_LEN {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            "Invalid A256CBC-HS512 key or IV length",
+        ));
+    }
+
+    let (mac_key, enc_key) = cek.split_at(KEY_LEN);
+    let ciphertext = symm::encrypt(Cipher::aes_256_cbc(), enc_key, Some(iv), plaintext).map_err(crypto_err)?;
+    let tag = a256cbc_hs512_tag(mac_key, aad, iv, &ciphertext)?;
+
+    Ok((ciphertext, tag))
+}
+
+/// Authenticates and decrypts a ciphertext encrypted with `A256CBC-HS512`.
+pub fn a256cbc_hs512_decrypt(cek: &[u8], iv: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> VcxResult<Vec<u8>> {
+    if cek.len() != CEK_LEN || iv.len() != IV_LEN {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Invalid A256CBC-HS512 key or IV length",
+        ));
+    }
+
+    let (mac_key, enc_key) = cek.split_at(KEY_LEN);
+    let expected_tag = a256cbc_hs512_tag(mac_key, aad, iv, ciphertext)?;
+
+    if tag.len() != TAG_LEN || !memcmp::eq(tag, &expected_tag) {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Message authentication failed",
+        ));
+    }
+
+    symm::decrypt(Cipher::aes_256_cbc(), enc_key, Some(iv), ciphertext).map_err(crypto_err)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_to_x25519_public() {
+        // RFC 8032, section 7.1, test 1 public key
+        let ed25519_pk = hex_decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let expected = hex_decode("d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e");
+
+        let x25519_pk = ed25519_to_x25519_public(&ed25519_pk).unwrap();
+        assert_eq!(x25519_pk, expected);
+    }
+
+    #[test]
+    fn test_ed25519_to_x25519_invalid_length() {
+        assert!(ed25519_to_x25519_public(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_x25519_ecdh_agrees() {
+        let (alice_sk, alice_pk) = x25519_generate().unwrap();
+        let (bob_sk, bob_pk) = x25519_generate().unwrap();
+
+        let alice_secret = x25519_ecdh(&alice_sk, &bob_pk).unwrap();
+        let bob_secret = x25519_ecdh(&bob_sk, &alice_pk).unwrap();
+
+        assert_eq!(alice_secret, bob_secret);
+    }
+
+    #[test]
+    fn test_key_wrap_roundtrip() {
+        let kek = random_bytes(KEY_LEN).unwrap();
+        let cek = random_bytes(CEK_LEN).unwrap();
+
+        let wrapped = aes_key_wrap(&kek, &cek).unwrap();
+        let unwrapped = aes_key_unwrap(&kek, &wrapped).unwrap();
+
+        assert_eq!(cek, unwrapped);
+    }
+
+    #[test]
+    fn test_a256cbc_hs512_roundtrip() {
+        let cek = random_bytes(CEK_LEN).unwrap();
+        let iv = random_bytes(IV_LEN).unwrap();
+        let aad = b"test_aad";
+        let plaintext = b"test_plaintext";
+
+        let (ciphertext, tag) = a256cbc_hs512_encrypt(&cek, &iv, aad, plaintext).unwrap();
+        let decrypted = a256cbc_hs512_decrypt(&cek, &iv, aad, &ciphertext, &tag).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        let res = a256cbc_hs512_decrypt(&cek, &iv, b"other_aad", &ciphertext, &tag);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_a256cbc_hs512_rfc7518_vector() {
+        // RFC 7518, Appendix B.3
+        let cek: Vec<u8> = (0u8..64).collect();
+        let iv = hex_decode("1af38c2dc2b96ffdd86694092341bc04");
+        let plaintext = hex_decode(concat!(
+            "41206369706865722073797374656d206d757374206e6f742062652072657175",
+            "6972656420746f206265207365637265742c20616e64206974206d7573742062",
+            "652061626c6520746f2066616c6c20696e746f207468652068616e6473206f66",
+            "2074686520656e656d7920776974686f757420696e636f6e76656e69656e6365"
+        ));
+        let aad = hex_decode(concat!(
+            "546865207365636f6e64207072696e6369706c65206f66204175677573746520",
+            "4b6572636b686f666673"
+        ));
+        let expected_tag = hex_decode("4dd3b4c088a7f45c216839645b2012bf2e6269a8c56a816dbc1b267761955bc5");
+
+        let (_, tag) = a256cbc_hs512_encrypt(&cek, &iv, &aad, &plaintext).unwrap();
+        assert_eq!(tag, expected_tag);
+    }
+
+    fn hex_decode(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
@@ -0,0 +1,323 @@
+//! DIDComm v2 encryption, producing and consuming [`Jwe`] envelopes.
+//!
+//! Two key management algorithms are supported, both using `A256KW` key wrapping
+//! and `A256CBC-HS512` content encryption:
+//! - `ECDH-ES+A256KW` for anonymous encryption (anoncrypt)
+//! - `ECDH-1PU+A256KW` for authenticated encryption (authcrypt)
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use messages::didcomm_v2::{Jwe, JweRecipient, ENCRYPTED_MEDIA_TYPE};
+use openssl::sha::sha256;
+
+use crate::errors::error::prelude::*;
+use crate::utils::didcomm_v2::{crypto, keys};
+
+pub const ANONCRYPT_ALG: &str = "ECDH-ES+A256KW";
+pub const AUTHCRYPT_ALG: &str = "ECDH-1PU+A256KW";
+pub const CONTENT_ENC: &str = "A256CBC-HS512";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ProtectedHeader {
+    typ: String,
+    alg: String,
+    enc: String,
+    apv: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apu: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skid: Option<String>,
+    epk: EphemeralKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct EphemeralKey {
+    kty: String,
+    crv: String,
+    x: String,
+}
+
+impl EphemeralKey {
+    fn new(public_key: &[u8]) -> Self {
+        Self {
+            kty: "OKP".to_owned(),
+            crv: "X25519".to_owned(),
+            x: crypto::b64_encode(public_key),
+        }
+    }
+}
+
+/// Private part of one of our X25519 key agreement keys.
+pub enum KeyAgreementKey<'a> {
+    /// Key kept in the wallet records, see [`keys::create_key_agreement_key`].
+    Stored(Vec<u8>),
+    /// Key converted from an Ed25519 key of the wallet, by its verkey, see [`keys::kid_for_verkey`].
+    Wallet(&'a Arc<dyn BaseWallet>, String),
+}
+
+impl KeyAgreementKey<'_> {
+    async fn key_agreement(&self, public_key: &[u8]) -> VcxResult<Vec<u8>> {
+        match self {
+            Self::Stored(private_key) => crypto::x25519_ecdh(private_key, public_key),
+            Self::Wallet(wallet, verkey) => Ok(wallet.key_agreement(verkey, public_key).await?),
+        }
+    }
+}
+
+/// Sender of an authcrypted message, identified by the key id and its X25519 key agreement key.
+pub struct AuthcryptSender<'a> {
+    pub kid: &'a str,
+    pub key: KeyAgreementKey<'a>,
+}
+
+/// Key id of the sender and the shared secrets of its key with each of the recipient keys.
+struct SenderSecrets<'a> {
+    kid: &'a str,
+    shared_secrets: Vec<Vec<u8>>,
+}
+
+/// The `apv` is the digest of the sorted recipient key ids joined by `.`,
+/// which binds the derived keys to the full set of recipients.
+fn make_apv(recipient_kids: &[String]) -> Vec<u8> {
+    let mut kids: Vec<&str> = recipient_kids.iter().map(String::as_str).collect();
+    kids.sort_unstable();
+    sha256(kids.join(".").as_bytes()).to_vec()
+}
+
+fn invalid_jwe(msg: impl Into<String>) -> AriesVcxError {
+    AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, msg.into())
+}
+
+/// Anonymously encrypts `plaintext` for the given recipient key ids.
+pub fn anoncrypt(plaintext: &[u8], recipient_kids: &[String]) -> VcxResult<Jwe> {
+    encrypt(plaintext, recipient_kids, None)
+}
+
+/// Encrypts `plaintext` for the given recipient key ids, authenticating the sender.
+pub async fn authcrypt(plaintext: &[u8], recipient_kids: &[String], sender: AuthcryptSender<'_>) -> VcxResult<Jwe> {
+    let mut shared_secrets = Vec::with_capacity(recipient_kids.len());
+
+    for kid in recipient_kids {
+        shared_secrets.push(sender.key.key_agreement(&keys::x25519_from_kid(kid)?).await?);
+    }
+
+    let sender = SenderSecrets {
+        kid: sender.kid,
+        shared_secrets,
+    };
+
+    encrypt(plaintext, recipient_kids, Some(sender))
+}
+
+fn encrypt(plaintext: &[u8], recipient_kids: &[String], sender: Option<SenderSecrets<'_>>) -> VcxResult<Jwe> {
+    if recipient_kids.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            "Cannot encrypt DIDComm v2 message without recipients",
+        ));
+    }
+
+    let (epk_private, epk_public) = crypto::x25519_generate()?;
+    let apv = make_apv(recipient_kids);
+
+    let (alg, apu, skid) = match &sender {
+        Some(sender) => (
+            AUTHCRYPT_ALG,
+            Some(crypto::b64_encode(sender.kid.as_bytes())),
+            Some(sender.kid.to_owned()),
+        ),
+        None => (ANONCRYPT_ALG, None, None),
+    };
+
+    let header = ProtectedHeader {
+        typ: ENCRYPTED_MEDIA_TYPE.to_owned(),
+        alg: alg.to_owned(),
+        enc: CONTENT_ENC.to_owned(),
+        apv: crypto::b64_encode(&apv),
+        apu,
+        skid,
+        epk: EphemeralKey::new(&epk_public),
+    };
+
+    let protected = crypto::b64_encode(&serde_json::to_vec(&header)?);
+
+    let cek = crypto::random_bytes(crypto::CEK_LEN)?;
+    let iv = crypto::random_bytes(crypto::IV_LEN)?;
+    let (ciphertext, tag) = crypto::a256cbc_hs512_encrypt(&cek, &iv, protected.as_bytes(), plaintext)?;
+
+    let mut recipients = Vec::with_capacity(recipient_kids.len());
+
+    for (i, kid) in recipient_kids.iter().enumerate() {
+        let recipient_pk = keys::x25519_from_kid(kid)?;
+        let mut z = crypto::x25519_ecdh(&epk_private, &recipient_pk)?;
+
+        let kek = match &sender {
+            Some(sender) => {
+                z.extend_from_slice(&sender.shared_secrets[i]);
+                crypto::concat_kdf(&z, alg, sender.kid.as_bytes(), &apv, &tag)
+            }
+            None => crypto::concat_kdf(&z, alg, &[], &apv, &[]),
+        };
+
+        let encrypted_key = crypto::aes_key_wrap(&kek, &cek)?;
+        recipients.push(JweRecipient::new(kid.to_owned(), crypto::b64_encode(&encrypted_key)));
+    }
+
+    Ok(Jwe {
+        protected,
+        recipients,
+        iv: crypto::b64_encode(&iv),
+        ciphertext: crypto::b64_encode(&ciphertext),
+        tag: crypto::b64_encode(&tag),
+    })
+}
+
+/// Finds the first recipient of the [`Jwe`] whose key agreement key is held in the wallet, either
+/// in the key agreement records or as an Ed25519 key, returning it with its shared secret with `epk`.
+async fn find_recipient<'a>(
+    wallet: &'a Arc<dyn BaseWallet>,
+    jwe: &'a Jwe,
+    epk: &[u8],
+) -> VcxResult<Option<(&'a JweRecipient, KeyAgreementKey<'a>, Vec<u8>)>> {
+    for recipient in jwe.recipients.iter() {
+        let key = match keys::get_key_agreement_private(wallet, &recipient.header.kid).await? {
+            Some(private_key) => KeyAgreementKey::Stored(private_key),
+            None => match keys::verkey_for_kid(&recipient.header.kid) {
+                Some(verkey) => KeyAgreementKey::Wallet(wallet, verkey),
+                None => continue,
+            },
+        };
+
+        match key.key_agreement(epk).await {
+            Ok(z) => return Ok(Some((recipient, key, z))),
+            Err(err) if err.kind() == AriesVcxErrorKind::WalletRecordNotFound => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Decrypts a [`Jwe`] with the first recipient key agreement key held in the wallet.
+///
+/// Returns the plaintext and, for authcrypted messages, the key id of the sender.
+pub async fn decrypt(wallet: &Arc<dyn BaseWallet>, jwe: &Jwe) -> VcxResult<(Vec<u8>, Option<String>)> {
+    let header: ProtectedHeader = serde_json::from_slice(&crypto::b64_decode(&jwe.protected)?)
+        .map_err(|err| invalid_jwe(format!("Cannot deserialize JWE protected header: {err}")))?;
+
+    if header.enc != CONTENT_ENC {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::UnimplementedFeature,
+            format!("Unsupported JWE content encryption: {}", header.enc),
+        ));
+    }
+
+    let sender_pk = match header.alg.as_str() {
+        ANONCRYPT_ALG => None,
+        AUTHCRYPT_ALG => {
+            let skid = header
+                .skid
+                .as_deref()
+                .ok_or_else(|| invalid_jwe("Authcrypted JWE is missing the sender key id"))?;
+            Some(keys::x25519_from_kid(skid)?)
+        }
+        alg => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::UnimplementedFeature,
+                format!("Unsupported JWE key management algorithm: {alg}"),
+            ))
+        }
+    };
+
+    let epk = crypto::b64_decode(&header.epk.x)?;
+
+    let (recipient, recipient_key, mut z) = find_recipient(wallet, jwe, &epk).await?.ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::WalletRecordNotFound,
+            "No key agreement key for any of the JWE recipients found in wallet",
+        )
+    })?;
+
+    let apv = crypto::b64_decode(&header.apv)?;
+    let iv = crypto::b64_decode(&jwe.iv)?;
+    let ciphertext = crypto::b64_decode(&jwe.ciphertext)?;
+    let tag = crypto::b64_decode(&jwe.tag)?;
+    let encrypted_key = crypto::b64_decode(&recipient.encrypted_key)?;
+
+    let kek = match &sender_pk {
+        Some(sender_pk) => {
+            let apu = header
+                .apu
+                .as_deref()
+                .map(crypto::b64_decode)
+                .transpose()?
+                .unwrap_or_default();
+
+            z.extend(recipient_key.key_agreement(sender_pk).await?);
+            crypto::concat_kdf(&z, &header.alg, &apu, &apv, &tag)
+        }
+        None => crypto::concat_kdf(&z, &header.alg, &[], &apv, &[]),
+    };
+
+    let cek = crypto::aes_key_unwrap(&kek, &encrypted_key)?;
+    let plaintext = crypto::a256cbc_hs512_decrypt(&cek, &iv, jwe.protected.as_bytes(), &ciphertext, &tag)?;
+
+    Ok((plaintext, header.skid))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_apv_ignores_recipient_order() {
+        let kids_1 = vec!["did:key:z1#z1".to_owned(), "did:key:z2#z2".to_owned()];
+        let kids_2 = vec!["did:key:z2#z2".to_owned(), "did:key:z1#z1".to_owned()];
+
+        assert_eq!(make_apv(&kids_1), make_apv(&kids_2));
+    }
+
+    #[test]
+    fn test_anoncrypt_header() {
+        let (_, public) = crypto::x25519_generate().unwrap();
+        let kid = keys::x25519_kid(&public);
+
+        let jwe = anoncrypt(b"test_plaintext", &[kid.clone()]).unwrap();
+        let header: ProtectedHeader = serde_json::from_slice(&crypto::b64_decode(&jwe.protected).unwrap()).unwrap();
+
+        assert_eq!(header.alg, ANONCRYPT_ALG);
+        assert_eq!(header.enc, CONTENT_ENC);
+        assert_eq!(header.typ, ENCRYPTED_MEDIA_TYPE);
+        assert!(header.skid.is_none());
+        assert_eq!(jwe.recipients.len(), 1);
+        assert_eq!(jwe.recipients[0].header.kid, kid);
+    }
+
+    #[tokio::test]
+    async fn test_authcrypt_header() {
+        let (_, recipient_public) = crypto::x25519_generate().unwrap();
+        let (sender_private, sender_public) = crypto::x25519_generate().unwrap();
+        let sender_kid = keys::x25519_kid(&sender_public);
+
+        let sender = AuthcryptSender {
+            kid: &sender_kid,
+            key: KeyAgreementKey::Stored(sender_private),
+        };
+
+        let jwe = authcrypt(b"test_plaintext", &[keys::x25519_kid(&recipient_public)], sender)
+            .await
+            .unwrap();
+        let header: ProtectedHeader = serde_json::from_slice(&crypto::b64_decode(&jwe.protected).unwrap()).unwrap();
+
+        assert_eq!(header.alg, AUTHCRYPT_ALG);
+        assert_eq!(header.skid.as_deref(), Some(sender_kid.as_str()));
+        assert_eq!(header.apu, Some(crypto::b64_encode(sender_kid.as_bytes())));
+    }
+
+    #[test]
+    fn test_encrypt_fails_without_recipients() {
+        assert!(anoncrypt(b"test_plaintext", &[]).is_err());
+    }
+}
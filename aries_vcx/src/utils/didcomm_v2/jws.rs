@@ -0,0 +1,87 @@
+//! DIDComm v2 signing, producing and consuming [`Jws`] envelopes.
+//!
+//! Signatures are `EdDSA` signatures made with Ed25519 keys held in the wallet.
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use messages::didcomm_v2::{Jws, JwsSignature, JwsSignatureHeader, SIGNED_MEDIA_TYPE};
+
+use crate::errors::error::prelude::*;
+use crate::utils::didcomm_v2::{crypto, keys};
+
+pub const SIGNATURE_ALG: &str = "EdDSA";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ProtectedHeader {
+    typ: String,
+    alg: String,
+    kid: String,
+}
+
+fn signing_input(protected: &str, payload: &str) -> Vec<u8> {
+    format!("{protected}.{payload}").into_bytes()
+}
+
+/// Signs `payload` with the wallet key `verkey`.
+pub async fn sign(wallet: &Arc<dyn BaseWallet>, verkey: &str, payload: &[u8]) -> VcxResult<Jws> {
+    let kid = keys::ed25519_kid(&keys::verkey_to_bytes(verkey)?);
+
+    let header = ProtectedHeader {
+        typ: SIGNED_MEDIA_TYPE.to_owned(),
+        alg: SIGNATURE_ALG.to_owned(),
+        kid: kid.clone(),
+    };
+
+    let protected = crypto::b64_encode(&serde_json::to_vec(&header)?);
+    let payload = crypto::b64_encode(payload);
+
+    let signature = wallet.sign(verkey, &signing_input(&protected, &payload)).await?;
+
+    Ok(Jws {
+        payload,
+        signatures: vec![JwsSignature {
+            protected,
+            header: Some(JwsSignatureHeader { kid }),
+            signature: crypto::b64_encode(&signature),
+        }],
+    })
+}
+
+/// Verifies the signature of a [`Jws`], returning the payload and the base58 verkey of the signer.
+pub async fn verify(wallet: &Arc<dyn BaseWallet>, jws: &Jws) -> VcxResult<(Vec<u8>, String)> {
+    let [signature] = jws.signatures.as_slice() else {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Expected exactly one JWS signature, found {}", jws.signatures.len()),
+        ));
+    };
+
+    let header: ProtectedHeader =
+        serde_json::from_slice(&crypto::b64_decode(&signature.protected)?).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize JWS protected header: {err}"),
+            )
+        })?;
+
+    if header.alg != SIGNATURE_ALG {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::UnimplementedFeature,
+            format!("Unsupported JWS algorithm: {}", header.alg),
+        ));
+    }
+
+    let verkey = bs58::encode(keys::ed25519_from_kid(&header.kid)?).into_string();
+    let input = signing_input(&signature.protected, &jws.payload);
+    let signature = crypto::b64_decode(&signature.signature)?;
+
+    if !wallet.verify(&verkey, &input, &signature).await? {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "JWS signature verification failed",
+        ));
+    }
+
+    Ok((crypto::b64_decode(&jws.payload)?, verkey))
+}
@@ -0,0 +1,198 @@
+//! Key handling for DIDComm v2.
+//!
+//! DIDComm v2 encryption uses X25519 key agreement keys, referenced through key ids (`kid`).
+//! Keys are identified by their `did:key` verification method id, which embeds the public key
+//! itself, so a peer can process a message without resolving a DID document first.
+//!
+//! Ed25519 verkeys are used for key agreement through their X25519 conversion, which is identified
+//! the way the `did:key` DID of the verkey identifies it, as `did:key:<ed25519 key>#<x25519 key>`.
+//! The wallet performs the key agreement for those keys without exposing their private part.
+//! Standalone X25519 key agreement keys can also be generated here and kept in the wallet as records.
+
+use std::sync::Arc;
+
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+
+use crate::errors::error::prelude::*;
+use crate::utils::didcomm_v2::crypto;
+
+pub const KEY_AGREEMENT_RECORD_TYPE: &str = "DidCommV2KeyAgreement";
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const MULTIBASE_BASE58_BTC: char = 'z';
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+const X25519_MULTICODEC: [u8; 2] = [0xec, 0x01];
+
+fn multibase_key(multicodec: [u8; 2], public_key: &[u8]) -> String {
+    let mut bytes = multicodec.to_vec();
+    bytes.extend_from_slice(public_key);
+    format!("{MULTIBASE_BASE58_BTC}{}", bs58::encode(bytes).into_string())
+}
+
+fn did_key_kid(multicodec: [u8; 2], public_key: &[u8]) -> String {
+    let key = multibase_key(multicodec, public_key);
+    format!("{DID_KEY_PREFIX}{key}#{key}")
+}
+
+fn public_key_from_multibase(key: &str, multicodec: [u8; 2]) -> Option<Vec<u8>> {
+    let bytes = bs58::decode(key.strip_prefix(MULTIBASE_BASE58_BTC)?).into_vec().ok()?;
+
+    match bytes.strip_prefix(&multicodec) {
+        Some(public_key) if public_key.len() == crypto::KEY_LEN => Some(public_key.to_vec()),
+        _ => None,
+    }
+}
+
+/// Reads the key of the given type from a `did:key` key id, which is the key in the fragment
+/// when it is of that type (such as the X25519 key of `did:key:<ed25519 key>#<x25519 key>`)
+/// and the key of the DID itself otherwise.
+fn public_key_from_kid(kid: &str, multicodec: [u8; 2]) -> VcxResult<Vec<u8>> {
+    let invalid_kid =
+        || AriesVcxError::from_msg(AriesVcxErrorKind::InvalidVerkey, format!("Unsupported key id: {kid}"));
+
+    let key = kid.strip_prefix(DID_KEY_PREFIX).ok_or_else(invalid_kid)?;
+    let (did_key, fragment) = key.split_once('#').unwrap_or((key, ""));
+
+    public_key_from_multibase(fragment, multicodec)
+        .or_else(|| public_key_from_multibase(did_key, multicodec))
+        .ok_or_else(invalid_kid)
+}
+
+/// Builds the key id of a raw X25519 public key.
+pub fn x25519_kid(public_key: &[u8]) -> String {
+    did_key_kid(X25519_MULTICODEC, public_key)
+}
+
+/// Builds the key id of a raw Ed25519 public key.
+pub fn ed25519_kid(public_key: &[u8]) -> String {
+    did_key_kid(ED25519_MULTICODEC, public_key)
+}
+
+/// Builds the `did:key` DID of a raw Ed25519 public key.
+pub fn ed25519_did_key(public_key: &[u8]) -> String {
+    format!("{DID_KEY_PREFIX}{}", multibase_key(ED25519_MULTICODEC, public_key))
+}
+
+/// Extracts the raw X25519 public key from a key id.
+pub fn x25519_from_kid(kid: &str) -> VcxResult<Vec<u8>> {
+    public_key_from_kid(kid, X25519_MULTICODEC)
+}
+
+/// Extracts the raw Ed25519 public key from a key id.
+pub fn ed25519_from_kid(kid: &str) -> VcxResult<Vec<u8>> {
+    public_key_from_kid(kid, ED25519_MULTICODEC)
+}
+
+pub fn verkey_to_bytes(verkey: &str) -> VcxResult<Vec<u8>> {
+    bs58::decode(verkey).into_vec().map_err(|_| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotBase58,
+            format!("Verkey is not base58 encoded: {verkey}"),
+        )
+    })
+}
+
+/// Derives the X25519 key agreement key id of a base58 encoded Ed25519 verkey,
+/// which is how DIDComm v1 keys are addressed when encrypting DIDComm v2 messages.
+pub fn kid_for_verkey(verkey: &str) -> VcxResult<(String, Vec<u8>)> {
+    let ed25519_pk = verkey_to_bytes(verkey)?;
+    let x25519_pk = crypto::ed25519_to_x25519_public(&ed25519_pk)?;
+    let kid = format!(
+        "{}#{}",
+        ed25519_did_key(&ed25519_pk),
+        multibase_key(X25519_MULTICODEC, &x25519_pk)
+    );
+    Ok((kid, x25519_pk))
+}
+
+/// The base58 encoded Ed25519 verkey a key agreement key id was derived from by [`kid_for_verkey`],
+/// or [`None`] if the key id is not of such a key.
+pub fn verkey_for_kid(kid: &str) -> Option<String> {
+    let ed25519_pk = ed25519_from_kid(kid).ok()?;
+    let x25519_pk = x25519_from_kid(kid).ok()?;
+
+    match crypto::ed25519_to_x25519_public(&ed25519_pk) {
+        Ok(converted) if converted == x25519_pk => Some(bs58::encode(ed25519_pk).into_string()),
+        _ => None,
+    }
+}
+
+/// Generates a new X25519 key agreement key and stores it in the wallet.
+/// Returns the key id of the generated key.
+pub async fn create_key_agreement_key(wallet: &Arc<dyn BaseWallet>) -> VcxResult<String> {
+    let (private, public) = crypto::x25519_generate()?;
+    let kid = x25519_kid(&public);
+    let value = bs58::encode(private).into_string();
+
+    wallet
+        .add_wallet_record(KEY_AGREEMENT_RECORD_TYPE, &kid, &value, None)
+        .await?;
+
+    Ok(kid)
+}
+
+/// Retrieves the private part of a key agreement key from the wallet,
+/// or [`None`] if the wallet does not hold the key.
+pub async fn get_key_agreement_private(wallet: &Arc<dyn BaseWallet>, kid: &str) -> VcxResult<Option<Vec<u8>>> {
+    match wallet.get_wallet_record_value(KEY_AGREEMENT_RECORD_TYPE, kid).await {
+        Ok(value) => verkey_to_bytes(&value).map(Some),
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_x25519_kid_roundtrip() {
+        let (_, public) = crypto::x25519_generate().unwrap();
+        let kid = x25519_kid(&public);
+
+        assert!(kid.starts_with("did:key:z6LS"));
+        assert_eq!(x25519_from_kid(&kid).unwrap(), public);
+        assert!(ed25519_from_kid(&kid).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_kid() {
+        let verkey = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+        let public = verkey_to_bytes(verkey).unwrap();
+        let kid = ed25519_kid(&public);
+
+        assert!(kid.starts_with("did:key:z6Mk"));
+        assert_eq!(ed25519_from_kid(&kid).unwrap(), public);
+
+        let did_key = ed25519_did_key(&public);
+        assert!(kid.starts_with(&format!("{did_key}#")));
+        assert_eq!(ed25519_from_kid(&did_key).unwrap(), public);
+    }
+
+    #[test]
+    fn test_kid_for_verkey() {
+        let verkey = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+        let (kid, x25519_pk) = kid_for_verkey(verkey).unwrap();
+
+        assert!(kid.starts_with(&format!("{}#z6LS", ed25519_did_key(&verkey_to_bytes(verkey).unwrap()))));
+        assert_eq!(x25519_from_kid(&kid).unwrap(), x25519_pk);
+        assert_eq!(verkey_for_kid(&kid).as_deref(), Some(verkey));
+
+        let (_, other_x25519_pk) = crypto::x25519_generate().unwrap();
+        let forged_kid = format!(
+            "{}#{}",
+            ed25519_did_key(&verkey_to_bytes(verkey).unwrap()),
+            multibase_key(X25519_MULTICODEC, &other_x25519_pk)
+        );
+        assert_eq!(verkey_for_kid(&forged_kid), None);
+        assert_eq!(verkey_for_kid(&x25519_kid(&x25519_pk)), None);
+    }
+
+    #[test]
+    fn test_invalid_kid() {
+        assert!(x25519_from_kid("did:example:123#key-1").is_err());
+        assert!(x25519_from_kid("did:key:123").is_err());
+    }
+}
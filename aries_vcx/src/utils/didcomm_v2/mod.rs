@@ -0,0 +1,258 @@
+//! DIDComm v2 envelope support.
+//!
+//! Outbound messages are converted to DIDComm v2 plaintext messages and encrypted for the
+//! recipient keys, authenticated with the key agreement key of the pairwise verkey (when there
+//! is one) and anonymously otherwise.
+//! Routing keys are handled by wrapping the message into `routing/2.0` forward messages.
+//!
+//! Inbound DIDComm v2 messages are decrypted with the key agreement keys of the wallet verkeys
+//! or those stored in the wallet (see [`keys::create_key_agreement_key`]) and converted back
+//! to an [`AriesMessage`], so the rest of the library stays agnostic of the DIDComm version in use.
+
+pub mod crypto;
+pub mod jwe;
+pub mod jws;
+pub mod keys;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::decorators::attachment::AttachmentType;
+use messages::didcomm_v2::{AttachmentDataV2, AttachmentV2, DidCommV2Envelope, PlaintextMessage};
+use messages::AriesMessage;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::errors::error::prelude::*;
+
+pub const DIDCOMM_V2_PROFILE: &str = "didcomm/v2";
+pub const FORWARD_V2_TYPE: &str = "https://didcomm.org/routing/2.0/forward";
+
+/// The DIDComm version used to communicate with a counterparty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DidCommVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl DidCommVersion {
+    /// Picks the DIDComm version based on the `accept` list of a service.
+    ///
+    /// DIDComm v1 stays the default, so DIDComm v2 is only used when the counterparty
+    /// accepts it and does not accept any DIDComm v1 profile (such as `didcomm/aip2;env=rfc19`).
+    pub fn from_accept(accept: &[String]) -> Self {
+        let accepts_v2 = accept.iter().any(|profile| profile == DIDCOMM_V2_PROFILE);
+        let accepts_v1 = accept.iter().any(|profile| profile != DIDCOMM_V2_PROFILE);
+
+        if accepts_v2 && !accepts_v1 {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+}
+
+/// Tells whether a packed message is a DIDComm v2 message.
+///
+/// DIDComm v1 packed messages are JWE-like structures as well,
+/// but their protected header uses the `Authcrypt` or `Anoncrypt` algorithms.
+pub fn is_didcomm_v2(payload: &[u8]) -> bool {
+    let Ok(Value::Object(obj)) = serde_json::from_slice::<Value>(payload) else {
+        return false;
+    };
+
+    if obj.contains_key("signatures") || obj.contains_key("body") {
+        return true;
+    }
+
+    obj.get("protected")
+        .and_then(Value::as_str)
+        .and_then(|protected| crypto::b64_decode(protected).ok())
+        .and_then(|header| serde_json::from_slice::<Value>(&header).ok())
+        .and_then(|header| header["alg"].as_str().map(|alg| alg.starts_with("ECDH-")))
+        .unwrap_or(false)
+}
+
+/// Recipient and routing keys can either be base58 Ed25519 verkeys or X25519 key ids.
+fn recipient_kid(key: &str) -> VcxResult<String> {
+    if key.starts_with("did:key:") {
+        keys::x25519_from_kid(key)?;
+        Ok(key.to_owned())
+    } else {
+        keys::kid_for_verkey(key).map(|(kid, _)| kid)
+    }
+}
+
+/// Packs a message as a DIDComm v2 message for the counterparty described by `did_doc`.
+pub async fn pack(
+    wallet: &Arc<dyn BaseWallet>,
+    message: &AriesMessage,
+    pw_verkey: Option<&str>,
+    did_doc: &AriesDidDoc,
+) -> VcxResult<Vec<u8>> {
+    let plaintext = serde_json::to_vec(&PlaintextMessage::try_from(message)?)?;
+
+    let recipient_kids = did_doc
+        .recipient_keys()?
+        .iter()
+        .map(|key| recipient_kid(key))
+        .collect::<VcxResult<Vec<_>>>()?;
+
+    let mut to = recipient_kids.first().cloned().ok_or(AriesVcxError::from_msg(
+        AriesVcxErrorKind::InvalidState,
+        format!("Recipient Key not found in DIDDoc: {:?}", did_doc),
+    ))?;
+
+    let jwe = match pw_verkey {
+        Some(verkey) => {
+            let (sender_kid, _) = keys::kid_for_verkey(verkey)?;
+            let sender = jwe::AuthcryptSender {
+                kid: &sender_kid,
+                key: jwe::KeyAgreementKey::Wallet(wallet, verkey.to_owned()),
+            };
+            jwe::authcrypt(&plaintext, &recipient_kids, sender).await?
+        }
+        None => jwe::anoncrypt(&plaintext, &recipient_kids)?,
+    };
+
+    let mut message = serde_json::to_vec(&jwe)?;
+
+    for routing_key in did_doc.routing_keys().iter() {
+        let routing_kid = recipient_kid(routing_key)?;
+        message = wrap_into_forward(message, &to, &routing_kid)?;
+        to = routing_kid;
+    }
+
+    Ok(message)
+}
+
+fn wrap_into_forward(message: Vec<u8>, next: &str, routing_kid: &str) -> VcxResult<Vec<u8>> {
+    let mut body = Map::new();
+    body.insert("next".to_owned(), Value::String(next.to_owned()));
+
+    let attachment = AttachmentV2::new(AttachmentDataV2::new(AttachmentType::Json(serde_json::from_slice(
+        &message,
+    )?)));
+
+    let mut forward = PlaintextMessage::new(Uuid::new_v4().to_string(), FORWARD_V2_TYPE.to_owned(), body);
+    forward.attachments = vec![attachment];
+
+    let jwe = jwe::anoncrypt(&serde_json::to_vec(&forward)?, &[routing_kid.to_owned()])?;
+    Ok(serde_json::to_vec(&jwe)?)
+}
+
+/// Records the verkey a layer of the message was authenticated by, which must be the same for all the layers.
+fn authenticate_sender(sender_vk: &mut Option<String>, verkey: String) -> VcxResult<()> {
+    match sender_vk {
+        Some(sender_vk) if *sender_vk != verkey => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("DIDComm v2 message is authenticated by both {sender_vk} and {verkey}"),
+        )),
+        _ => {
+            *sender_vk = Some(verkey);
+            Ok(())
+        }
+    }
+}
+
+/// Unpacks a DIDComm v2 message, returning the serialized [`AriesMessage`] and the verkey of
+/// the sender, if the message was authcrypted with the key of a verkey or signed.
+pub async fn unpack(wallet: &Arc<dyn BaseWallet>, payload: &[u8]) -> VcxResult<(String, Option<String>)> {
+    let mut envelope = DidCommV2Envelope::from_slice(payload).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize DIDComm v2 message: {}", err),
+        )
+    })?;
+
+    let mut sender_vk = None;
+    let mut signed = false;
+
+    let plaintext = loop {
+        envelope = match envelope {
+            DidCommV2Envelope::Encrypted(jwe) => {
+                let (decrypted, skid) = jwe::decrypt(wallet, &jwe).await?;
+                if let Some(verkey) = skid.as_deref().and_then(keys::verkey_for_kid) {
+                    authenticate_sender(&mut sender_vk, verkey)?;
+                }
+                DidCommV2Envelope::from_slice(&decrypted)?
+            }
+            DidCommV2Envelope::Signed(jws) if !signed => {
+                let (verified, signer) = jws::verify(wallet, &jws).await?;
+                signed = true;
+                authenticate_sender(&mut sender_vk, signer)?;
+                DidCommV2Envelope::from_slice(&verified)?
+            }
+            DidCommV2Envelope::Signed(_) => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    "Nested DIDComm v2 signatures are not supported",
+                ))
+            }
+            DidCommV2Envelope::Plaintext(plaintext) => break plaintext,
+        };
+    };
+
+    let message = AriesMessage::try_from(plaintext).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot convert DIDComm v2 message: {}", err),
+        )
+    })?;
+
+    Ok((serde_json::to_string(&message)?, sender_vk))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_didcomm_version_from_accept() {
+        let accept = |profiles: &[&str]| profiles.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(DidCommVersion::from_accept(&[]), DidCommVersion::V1);
+        assert_eq!(
+            DidCommVersion::from_accept(&accept(&["didcomm/aip2;env=rfc19"])),
+            DidCommVersion::V1
+        );
+        assert_eq!(
+            DidCommVersion::from_accept(&accept(&["didcomm/v2", "didcomm/aip2;env=rfc19"])),
+            DidCommVersion::V1
+        );
+        assert_eq!(
+            DidCommVersion::from_accept(&accept(&["didcomm/v2"])),
+            DidCommVersion::V2
+        );
+    }
+
+    #[test]
+    fn test_is_didcomm_v2() {
+        let (_, public) = crypto::x25519_generate().unwrap();
+        let jwe = jwe::anoncrypt(b"test_plaintext", &[keys::x25519_kid(&public)]).unwrap();
+
+        assert!(is_didcomm_v2(&serde_json::to_vec(&jwe).unwrap()));
+
+        let v1_protected = crypto::b64_encode(br#"{"enc":"xchacha20poly1305_ietf","typ":"JWM/1.0","alg":"Anoncrypt"}"#);
+        let v1_envelope = json!({
+            "protected": v1_protected,
+            "iv": "test_iv",
+            "ciphertext": "test_ciphertext",
+            "tag": "test_tag"
+        });
+
+        assert!(!is_didcomm_v2(&serde_json::to_vec(&v1_envelope).unwrap()));
+        assert!(!is_didcomm_v2(b"not json"));
+    }
+
+    #[test]
+    fn test_recipient_kid_from_verkey() {
+        let kid = recipient_kid("8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K").unwrap();
+        assert!(kid.starts_with("did:key:z6Mk"));
+        assert!(kid.contains("#z6LS"));
+        assert_eq!(recipient_kid(&kid).unwrap(), kid);
+    }
+}
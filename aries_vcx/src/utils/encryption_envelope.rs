@@ -12,6 +12,7 @@ use uuid::Uuid;
 use crate::errors::error::prelude::*;
 use crate::global::settings;
 use crate::utils::constants;
+use crate::utils::didcomm_v2::{self, DidCommVersion};
 
 #[derive(Debug)]
 pub struct EncryptionEnvelope(pub Vec<u8>);
@@ -34,6 +35,12 @@ impl EncryptionEnvelope {
             return Ok(EncryptionEnvelope(vec![]));
         }
 
+        if DidCommVersion::from_accept(&did_doc.accept()) == DidCommVersion::V2 {
+            return didcomm_v2::pack(wallet, message, pw_verkey, did_doc)
+                .await
+                .map(EncryptionEnvelope);
+        }
+
         EncryptionEnvelope::encrypt_for_pairwise(wallet, message, pw_verkey, did_doc)
             .and_then(|message| async move {
                 EncryptionEnvelope::wrap_into_forward_messages(wallet, message, did_doc).await
@@ -102,6 +109,55 @@ impl EncryptionEnvelope {
             .map_err(|err| err.into())
     }
 
+    /// Reads the verkeys a packed message is addressed to from its recipients, without decrypting it,
+    /// so that the connection it was sent over can be found before unpacking it.
+    ///
+    /// DIDComm v1 messages list their recipients in their protected header, DIDComm v2 ones at the top
+    /// level. The key ids of DIDComm v2 recipients are read back as the verkeys they were derived from
+    /// by [`didcomm_v2::keys::kid_for_verkey`], other key ids being returned as is.
+    pub fn recipient_keys(payload: &[u8]) -> VcxResult<Vec<String>> {
+        let invalid = |msg: &str| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidMessageFormat, msg);
+
+        let envelope: serde_json::Value =
+            serde_json::from_slice(payload).map_err(|_| invalid("Payload is not a packed message"))?;
+
+        if didcomm_v2::is_didcomm_v2(payload) {
+            let recipients = envelope["recipients"]
+                .as_array()
+                .ok_or_else(|| invalid("Packed message has no recipients"))?;
+            return recipients
+                .iter()
+                .map(|recipient| {
+                    let kid = recipient["header"]["kid"]
+                        .as_str()
+                        .ok_or_else(|| invalid("Packed message has recipient without kid"))?;
+                    Ok(didcomm_v2::keys::verkey_for_kid(kid).unwrap_or_else(|| kid.to_owned()))
+                })
+                .collect();
+        }
+
+        let protected = envelope["protected"]
+            .as_str()
+            .ok_or_else(|| invalid("Packed message has no protected header"))?;
+        let protected = base64::decode_config(protected, base64::URL_SAFE)
+            .map_err(|_| invalid("Packed message has invalid protected header"))?;
+        let protected: serde_json::Value =
+            serde_json::from_slice(&protected).map_err(|_| invalid("Packed message has invalid protected header"))?;
+
+        let recipients = protected["recipients"]
+            .as_array()
+            .ok_or_else(|| invalid("Packed message has no recipients"))?;
+        recipients
+            .iter()
+            .map(|recipient| {
+                recipient["header"]["kid"]
+                    .as_str()
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| invalid("Packed message has recipient without kid"))
+            })
+            .collect()
+    }
+
     async fn _unpack_a2a_message(
         wallet: &Arc<dyn BaseWallet>,
         payload: Vec<u8>,
@@ -111,6 +167,10 @@ impl EncryptionEnvelope {
             payload.len()
         );
 
+        if didcomm_v2::is_didcomm_v2(&payload) {
+            return didcomm_v2::unpack(wallet, &payload).await;
+        }
+
         let unpacked_msg = wallet.unpack_message(&payload).await?;
 
         let msg_value: serde_json::Value = serde_json::from_slice(unpacked_msg.as_slice()).map_err(|err| {
@@ -390,3 +450,147 @@ impl EncryptionEnvelope {
 //         .await;
 //     }
 // }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod integration_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+    use chrono::{TimeZone, Utc};
+    use messages::decorators::thread::Thread;
+    use messages::msg_fields::protocols::basic_message::{BasicMessage, BasicMessageContent, BasicMessageDecorators};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::utils::devsetup::SetupLibraryWallet;
+    use crate::utils::didcomm_v2::{crypto, jwe, DIDCOMM_V2_PROFILE};
+
+    fn basic_message() -> AriesMessage {
+        let content = BasicMessageContent::new("test_content".to_owned(), Utc.timestamp_opt(0, 0).unwrap());
+        let mut decorators = BasicMessageDecorators::default();
+        decorators.thread = Some(Thread::new("test_thid".to_owned()));
+
+        AriesMessage::from(BasicMessage::with_decorators("test_id".to_owned(), content, decorators))
+    }
+
+    fn didcomm_v2_did_doc(recipient_key: &str) -> AriesDidDoc {
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_recipient_keys(vec![recipient_key.to_owned()]);
+        did_doc.service[0].accept = vec![DIDCOMM_V2_PROFILE.to_owned()];
+        did_doc
+    }
+
+    fn jwe_alg(payload: &[u8]) -> String {
+        let envelope: Value = serde_json::from_slice(payload).unwrap();
+        let protected = crypto::b64_decode(envelope["protected"].as_str().unwrap()).unwrap();
+        let protected: Value = serde_json::from_slice(&protected).unwrap();
+        protected["alg"].as_str().unwrap().to_owned()
+    }
+
+    #[tokio::test]
+    async fn test_didcomm_v2_anoncrypt_roundtrip() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, recipient_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let message = basic_message();
+            let envelope = EncryptionEnvelope::create(&wallet, &message, None, &didcomm_v2_did_doc(&recipient_vk))
+                .await
+                .unwrap();
+            assert_eq!(jwe_alg(&envelope.0), jwe::ANONCRYPT_ALG);
+
+            let (unpacked, sender_vk) = EncryptionEnvelope::anon_unpack(&wallet, envelope.0).await.unwrap();
+            assert_eq!(unpacked, message);
+            assert_eq!(sender_vk, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_didcomm_v2_authcrypt_roundtrip() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, sender_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, recipient_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, other_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let message = basic_message();
+            let envelope =
+                EncryptionEnvelope::create(&wallet, &message, Some(&sender_vk), &didcomm_v2_did_doc(&recipient_vk))
+                    .await
+                    .unwrap();
+            assert_eq!(jwe_alg(&envelope.0), jwe::AUTHCRYPT_ALG);
+
+            let (unpacked, unpacked_sender_vk) = EncryptionEnvelope::anon_unpack(&wallet, envelope.0.clone())
+                .await
+                .unwrap();
+            assert_eq!(unpacked, message);
+            assert_eq!(unpacked_sender_vk, Some(sender_vk.clone()));
+
+            let unpacked = EncryptionEnvelope::auth_unpack(&wallet, envelope.0.clone(), &sender_vk)
+                .await
+                .unwrap();
+            assert_eq!(unpacked, message);
+
+            assert!(EncryptionEnvelope::auth_unpack(&wallet, envelope.0, &other_vk)
+                .await
+                .is_err());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_didcomm_v2_unpack_fails_without_recipient_key() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let foreign_vk = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+
+            let envelope = EncryptionEnvelope::create(&wallet, &basic_message(), None, &didcomm_v2_did_doc(foreign_vk))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                EncryptionEnvelope::anon_unpack(&wallet, envelope.0)
+                    .await
+                    .unwrap_err()
+                    .kind(),
+                AriesVcxErrorKind::WalletRecordNotFound
+            );
+        })
+        .await;
+    }
+    #[tokio::test]
+    async fn test_recipient_keys() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, recipient_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let message = serde_json::to_vec(&basic_message()).unwrap();
+
+            let packed = wallet
+                .pack_message(None, &json!([recipient_vk]).to_string(), &message)
+                .await
+                .unwrap();
+            assert_eq!(
+                EncryptionEnvelope::recipient_keys(&packed).unwrap(),
+                vec![recipient_vk.clone()]
+            );
+
+            let envelope =
+                EncryptionEnvelope::create(&wallet, &basic_message(), None, &didcomm_v2_did_doc(&recipient_vk))
+                    .await
+                    .unwrap();
+            assert_eq!(
+                EncryptionEnvelope::recipient_keys(&envelope.0).unwrap(),
+                vec![recipient_vk]
+            );
+
+            let kid = crate::utils::didcomm_v2::keys::create_key_agreement_key(&wallet)
+                .await
+                .unwrap();
+            let envelope = EncryptionEnvelope::create(&wallet, &basic_message(), None, &didcomm_v2_did_doc(&kid))
+                .await
+                .unwrap();
+            assert_eq!(EncryptionEnvelope::recipient_keys(&envelope.0).unwrap(), vec![kid]);
+        })
+        .await;
+    }
+}
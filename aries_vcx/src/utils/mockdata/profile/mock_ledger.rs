@@ -43,6 +43,14 @@ impl IndyLedgerWrite for MockLedger {
         Ok(())
     }
 
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        Ok(request_json.to_string())
+    }
+
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
     async fn publish_nym(
         &self,
         submitter_did: &str,
@@ -116,6 +124,43 @@ impl AnoncredsLedgerWrite for MockLedger {
     ) -> VcxCoreResult<()> {
         Ok(())
     }
+
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        Ok(utils::constants::REQUEST_WITH_ENDORSER.to_string())
+    }
+
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        Ok(utils::constants::REQUEST_WITH_ENDORSER.to_string())
+    }
+
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        Ok(utils::constants::REQUEST_WITH_ENDORSER.to_string())
+    }
+
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        Ok(utils::constants::REQUEST_WITH_ENDORSER.to_string())
+    }
 }
 
 #[cfg(test)]
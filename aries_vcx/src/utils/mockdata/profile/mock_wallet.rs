@@ -92,6 +92,10 @@ impl BaseWallet for MockWallet {
         Ok(true)
     }
 
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        Ok(their_pk.to_vec())
+    }
+
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(msg.to_vec())
     }
@@ -54,6 +54,7 @@ pub mod uuid;
 
 #[macro_use]
 pub mod test_logger;
+pub mod didcomm_v2;
 pub mod encryption_envelope;
 pub mod filters;
 pub mod serialization;
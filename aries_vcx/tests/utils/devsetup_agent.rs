@@ -17,6 +17,8 @@ pub mod test_utils {
         close_wallet, create_wallet_with_master_secret, delete_wallet, open_wallet, wallet_configure_issuer,
         IssuerConfig, WalletConfig,
     };
+    #[cfg(feature = "in_memory_ledger")]
+    use aries_vcx_core::ledger::in_memory_ledger::InMemoryLedger;
     #[cfg(feature = "modular_libs")]
     use aries_vcx_core::ledger::request_submitter::vdr_ledger::LedgerPoolConfig;
     use aries_vcx_core::wallet::base_wallet::BaseWallet;
@@ -143,6 +145,16 @@ pub mod test_utils {
 
     impl Faber {
         pub async fn setup(pool_handle: PoolHandle) -> Faber {
+            Faber::setup_with_profile(|wallet_handle| Arc::new(VdrtoolsProfile::new(wallet_handle, pool_handle))).await
+        }
+
+        #[cfg(feature = "in_memory_ledger")]
+        pub async fn setup_in_memory_ledger(ledger: &Arc<InMemoryLedger>) -> Faber {
+            let ledger = Arc::clone(ledger);
+            Faber::setup_with_profile(|wallet_handle| in_memory_ledger_profile(wallet_handle, ledger)).await
+        }
+
+        async fn setup_with_profile(create_profile: impl FnOnce(WalletHandle) -> Arc<dyn Profile>) -> Faber {
             settings::reset_config_values().unwrap();
             let enterprise_seed = "000000000000000000000000Trustee1";
             let config_wallet = WalletConfig {
@@ -164,8 +176,7 @@ pub mod test_utils {
             create_wallet_with_master_secret(&config_wallet).await.unwrap();
             let wallet_handle = open_wallet(&config_wallet).await.unwrap();
 
-            let indy_profile = VdrtoolsProfile::new(wallet_handle, pool_handle);
-            let profile: Arc<dyn Profile> = Arc::new(indy_profile);
+            let profile = create_profile(wallet_handle);
 
             let config_issuer = wallet_configure_issuer(wallet_handle, enterprise_seed).await.unwrap();
             init_issuer_config(&config_issuer.institution_did).unwrap();
@@ -466,6 +477,13 @@ pub mod test_utils {
         Alice::setup(alice_profile, teardown).await
     }
 
+    #[cfg(feature = "in_memory_ledger")]
+    pub async fn create_test_alice_instance_in_memory_ledger(setup: &SetupInMemoryLedger) -> Alice {
+        let (alice_profile, teardown) = Alice::setup_in_memory_ledger_profile(Arc::clone(&setup.ledger)).await;
+
+        Alice::setup(alice_profile, teardown).await
+    }
+
     impl Alice {
         async fn setup_indy_wallet() -> (WalletHandle, WalletConfig) {
             settings::reset_config_values().unwrap();
@@ -521,6 +539,18 @@ pub mod test_utils {
             )
         }
 
+        #[cfg(feature = "in_memory_ledger")]
+        pub async fn setup_in_memory_ledger_profile(
+            ledger: Arc<InMemoryLedger>,
+        ) -> (Arc<dyn Profile>, Arc<dyn Fn() -> BoxFuture<'static, ()>>) {
+            let (wallet_handle, config_wallet) = Alice::setup_indy_wallet().await;
+
+            (
+                in_memory_ledger_profile(wallet_handle, ledger),
+                Arc::new(move || Box::pin(teardown_indy_wallet(wallet_handle, config_wallet.clone()))),
+            )
+        }
+
         pub async fn setup(profile: Arc<dyn Profile>, teardown: Arc<dyn Fn() -> BoxFuture<'static, ()>>) -> Alice {
             let config_provision_agent = AgentProvisionConfig {
                 agency_did: AGENCY_DID.to_string(),
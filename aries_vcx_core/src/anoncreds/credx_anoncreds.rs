@@ -35,23 +35,11 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use super::base_anoncreds::BaseAnonCreds;
-
-const CATEGORY_LINK_SECRET: &str = "VCX_LINK_SECRET";
-
-const CATEGORY_CREDENTIAL: &str = "VCX_CREDENTIAL";
-const CATEGORY_CRED_DEF: &str = "VCX_CRED_DEF";
-const CATEGORY_CRED_KEY_CORRECTNESS_PROOF: &str = "VCX_CRED_KEY_CORRECTNESS_PROOF";
-const CATEGORY_CRED_DEF_PRIV: &str = "VCX_CRED_DEF_PRIV";
-const CATEGORY_CRED_SCHEMA: &str = "VCX_CRED_SCHEMA";
-
-// Category used for mapping a cred_def_id to a schema_id
-const CATEGORY_CRED_MAP_SCHEMA_ID: &str = "VCX_CRED_MAP_SCHEMA_ID";
-
-const CATEGORY_REV_REG: &str = "VCX_REV_REG";
-const CATEGORY_REV_REG_DELTA: &str = "VCX_REV_REG_DELTA";
-const CATEGORY_REV_REG_INFO: &str = "VCX_REV_REG_INFO";
-const CATEGORY_REV_REG_DEF: &str = "VCX_REV_REG_DEF";
-const CATEGORY_REV_REG_DEF_PRIV: &str = "VCX_REV_REG_DEF_PRIV";
+use super::credx_categories::{
+    CATEGORY_CREDENTIAL, CATEGORY_CRED_DEF, CATEGORY_CRED_DEF_PRIV, CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+    CATEGORY_CRED_MAP_SCHEMA_ID, CATEGORY_CRED_SCHEMA, CATEGORY_LINK_SECRET, CATEGORY_REV_REG, CATEGORY_REV_REG_DEF,
+    CATEGORY_REV_REG_DEF_PRIV, CATEGORY_REV_REG_DELTA, CATEGORY_REV_REG_INFO,
+};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RevocationRegistryInfo {
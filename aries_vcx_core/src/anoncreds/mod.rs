@@ -1,4 +1,5 @@
 pub mod base_anoncreds;
+pub mod credx_categories;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 pub mod credx_anoncreds;
 #[cfg(feature = "vdrtools")]
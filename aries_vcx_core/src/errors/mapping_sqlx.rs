@@ -0,0 +1,25 @@
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
+
+// Extended result codes of SQLite constraint violations
+const SQLITE_CONSTRAINT_PRIMARYKEY: &str = "1555";
+const SQLITE_CONSTRAINT_UNIQUE: &str = "2067";
+
+impl From<sqlx::Error> for AriesVcxCoreError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletRecordNotFound, err),
+            sqlx::Error::Database(db_err)
+                if matches!(
+                    db_err.code().as_deref(),
+                    Some(SQLITE_CONSTRAINT_PRIMARYKEY | SQLITE_CONSTRAINT_UNIQUE)
+                ) =>
+            {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::DuplicationWalletRecord, err)
+            }
+            sqlx::Error::Io(_) | sqlx::Error::Database(_) => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::IOError, err)
+            }
+            _ => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::UnknownError, err),
+        }
+    }
+}
@@ -303,10 +303,30 @@ pub async fn endorse_transaction(
         return Ok(());
     }
 
+    let transaction = sign_endorsed_transaction(wallet_handle, endorser_did, transaction_json).await?;
+    submit_endorsed_transaction(pool_handle, &transaction).await
+}
+
+pub async fn sign_endorsed_transaction(
+    wallet_handle: WalletHandle,
+    endorser_did: &str,
+    transaction_json: &str,
+) -> VcxCoreResult<String> {
+    if settings::indy_mocks_enabled() {
+        return Ok(transaction_json.to_string());
+    }
+
     verify_transaction_can_be_endorsed(transaction_json, endorser_did)?;
 
-    let transaction = multisign_request(wallet_handle, endorser_did, transaction_json).await?;
-    let response = libindy_submit_request(pool_handle, &transaction).await?;
+    multisign_request(wallet_handle, endorser_did, transaction_json).await
+}
+
+pub async fn submit_endorsed_transaction(pool_handle: PoolHandle, transaction_json: &str) -> VcxCoreResult<()> {
+    if settings::indy_mocks_enabled() {
+        return Ok(());
+    }
+
+    let response = libindy_submit_request(pool_handle, transaction_json).await?;
 
     match parse_response(&response)? {
         Response::Reply(_) => Ok(()),
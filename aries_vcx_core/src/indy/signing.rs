@@ -30,6 +30,15 @@ pub async fn verify(vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<boo
     Ok(res)
 }
 
+pub async fn key_agreement(wallet_handle: WalletHandle, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    let res = Locator::instance()
+        .crypto_controller
+        .crypto_key_agreement(wallet_handle, my_vk, their_pk)
+        .await?;
+
+    Ok(res)
+}
+
 pub async fn pack_message(
     wallet_handle: WalletHandle,
     sender_vk: Option<&str>,
@@ -468,6 +468,34 @@ pub async fn open_search_wallet(
     Ok(res)
 }
 
+/// Like [open_search_wallet], but also allowing the record types vdrtools keeps for itself
+/// (`Indy::Did`, `Indy::Key` with the private keys, anoncreds objects...), so that all of a wallet
+/// can be read out. Only for migrating the wallet.
+pub(crate) async fn open_migration_search_wallet(
+    wallet_handle: WalletHandle,
+    xtype: &str,
+    query: &str,
+    options: &str,
+) -> VcxCoreResult<SearchHandle> {
+    trace!(
+        "open_migration_search >>> xtype: {}, query: {}, options: {}",
+        secret!(&xtype),
+        query,
+        options
+    );
+
+    if settings::indy_mocks_enabled() {
+        return Ok(SearchHandle(1));
+    }
+
+    let res = Locator::instance()
+        .non_secret_controller
+        .open_migration_search(wallet_handle, xtype.into(), query.into(), options.into())
+        .await?;
+
+    Ok(res)
+}
+
 // TODO - FUTURE - revert to pub(crate) after libvcx dependency is fixed
 pub async fn fetch_next_records_wallet(
     wallet_handle: WalletHandle,
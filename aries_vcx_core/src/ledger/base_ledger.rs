@@ -24,6 +24,11 @@ pub trait IndyLedgerWrite: Debug + Send + Sync {
     ) -> VcxCoreResult<String>;
     async fn set_endorser(&self, submitter_did: &str, request: &str, endorser: &str) -> VcxCoreResult<String>;
     async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()>;
+    /// Signs a transaction prepared for endorsement as its endorser, without writing it, for its
+    /// author to write it with [`IndyLedgerWrite::submit_endorsed_transaction`].
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String>;
+    /// Writes a transaction signed by both its author and its endorser.
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()>;
     async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String>;
 }
 
@@ -57,4 +62,33 @@ pub trait AnoncredsLedgerWrite: Debug + Send + Sync {
         rev_reg_entry_json: &str,
         submitter_did: &str,
     ) -> VcxCoreResult<()>;
+
+    // The `prepare_*_for_endorsement` methods build the write request of an author without the
+    // right to write it, naming the endorser and signed by the author. The endorser writes it with
+    // `IndyLedgerWrite::endorse_transaction`.
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String>;
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String>;
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String>;
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String>;
 }
@@ -0,0 +1,799 @@
+//! An indy ledger kept in the memory of the process, for tests and demos which should not depend
+//! on a running pool.
+//!
+//! Requests are written the way indy-node writes them: they must be signed by their author, and by
+//! their endorser if they have one, with the verkeys of the DIDs on the ledger; the transaction
+//! author agreement must be accepted once the ledger has one; and the auth rules of the default
+//! indy-node configuration decide which roles may write what.
+
+mod request;
+mod state;
+
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::common::ledger::transactions::verify_transaction_can_be_endorsed;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::global::author_agreement::get_txn_author_agreement;
+use crate::wallet::base_wallet::BaseWallet;
+
+use self::request::{Operation, Request, RevRegEntryValue, SchemaData, TaaAcceptance};
+pub use self::state::LedgerRole;
+use self::state::{reject, taa_digest, LedgerState};
+
+use super::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite};
+
+/// The ledger, implementing the read traits. Writes go through an [`InMemoryLedgerWrite`], which
+/// signs them with the keys of a wallet.
+#[derive(Debug, Default)]
+pub struct InMemoryLedger {
+    state: RwLock<LedgerState>,
+}
+
+impl InMemoryLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the DID as the genesis transactions of a pool do, without any request. Ledgers start
+    /// empty, so at least one trustee or steward has to be added this way.
+    pub fn add_genesis_nym(&self, did: &str, verkey: &str, role: Option<LedgerRole>) -> VcxCoreResult<()> {
+        self.write_state()?.add_genesis_nym(did, verkey, role)
+    }
+
+    /// Sets the transaction author agreement, which every write must accept from then on.
+    pub fn set_txn_author_agreement(&self, text: &str, version: &str, mechanisms: Vec<String>) -> VcxCoreResult<()> {
+        self.write_state()?.set_txn_author_agreement(text, version, mechanisms);
+        Ok(())
+    }
+
+    /// The acceptance mechanisms of the transaction author agreement.
+    pub fn acceptance_mechanisms(&self) -> VcxCoreResult<Vec<String>> {
+        Ok(self.read_state()?.acceptance_mechanisms())
+    }
+
+    /// Freezes the clock of the ledger, which runs with the system time otherwise.
+    pub fn set_time(&self, timestamp: u64) -> VcxCoreResult<()> {
+        self.write_state()?.set_clock(Some(timestamp));
+        Ok(())
+    }
+
+    /// Moves the clock of the ledger forward, freezing it.
+    pub fn advance_time(&self, seconds: u64) -> VcxCoreResult<()> {
+        self.write_state()?.advance_clock(seconds);
+        Ok(())
+    }
+
+    pub fn now(&self) -> VcxCoreResult<u64> {
+        Ok(self.read_state()?.now())
+    }
+
+    /// Submits a signed request in the JSON format of indy-node, returning the reply to it.
+    pub async fn submit_request(&self, request_json: &str, wallet: &dyn BaseWallet) -> VcxCoreResult<String> {
+        let request = Request::from_json(request_json)?;
+        self.submit(&request, wallet).await.map(|reply| reply.to_string())
+    }
+
+    /// Verifies the signatures of the request, using the wallet for the crypto only, and writes it.
+    async fn submit(&self, request: &Request, wallet: &dyn BaseWallet) -> VcxCoreResult<Value> {
+        let payload = request.signing_payload()?;
+        let mut verkeys = Vec::new();
+        {
+            let state = self.read_state()?;
+            for signer in request.signers() {
+                let verkey = state
+                    .verkey(signer)?
+                    .ok_or_else(|| reject(format!("{} is not on the ledger", signer)))?;
+                verkeys.push((signer, verkey));
+            }
+        }
+
+        for (signer, verkey) in &verkeys {
+            let signature = request
+                .signature_of(signer)
+                .ok_or_else(|| reject(format!("Request is not signed by {}", signer)))?;
+            let signature = bs58::decode(signature)
+                .into_vec()
+                .map_err(|_| reject(format!("Signature of {} is not base58", signer)))?;
+            if !wallet.verify(verkey, &payload, &signature).await? {
+                return Err(reject(format!("Invalid signature of {}", signer)));
+            }
+        }
+
+        let mut state = self.write_state()?;
+        for (signer, verkey) in &verkeys {
+            if state.verkey(signer)?.as_ref() != Some(verkey) {
+                return Err(reject(format!("Verkey of {} changed while submitting", signer)));
+            }
+        }
+        state.apply(request)
+    }
+
+    fn read_state(&self) -> VcxCoreResult<RwLockReadGuard<'_, LedgerState>> {
+        self.state.read().map_err(|_| lock_poisoned())
+    }
+
+    fn write_state(&self) -> VcxCoreResult<RwLockWriteGuard<'_, LedgerState>> {
+        self.state.write().map_err(|_| lock_poisoned())
+    }
+}
+
+fn lock_poisoned() -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "In-memory ledger lock poisoned")
+}
+
+fn invalid_json(item: &str, err: serde_json::Error) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidJson, format!("Invalid {}: {}", item, err))
+}
+
+#[async_trait]
+impl IndyLedgerRead for InMemoryLedger {
+    async fn get_attr(&self, target_did: &str, attr_name: &str) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_attr(target_did, attr_name).to_string())
+    }
+
+    async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_nym(did).to_string())
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_txn_author_agreement().to_string())
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_txn(seq_no).to_string())
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerRead for InMemoryLedger {
+    async fn get_schema(&self, schema_id: &str, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_schema(schema_id)?.to_string())
+    }
+
+    async fn get_cred_def(&self, cred_def_id: &str, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_cred_def(cred_def_id)?.to_string())
+    }
+
+    async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+        Ok(self.read_state()?.get_rev_reg_def(rev_reg_id)?.to_string())
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, String, u64)> {
+        let state = self.read_state()?;
+        let to = to.unwrap_or_else(|| state.now());
+        let (delta, timestamp) = state.get_rev_reg_delta(rev_reg_id, from, to)?;
+        Ok((rev_reg_id.to_string(), delta.to_string(), timestamp))
+    }
+
+    async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+        let (rev_reg, timestamp) = self.read_state()?.get_rev_reg(rev_reg_id, timestamp)?;
+        Ok((rev_reg_id.to_string(), rev_reg.to_string(), timestamp))
+    }
+}
+
+/// Writes to an [`InMemoryLedger`], signing the requests with the keys of the wallet and
+/// accepting the transaction author agreement set in the settings, as the indy-vdr writer does.
+pub struct InMemoryLedgerWrite {
+    ledger: Arc<InMemoryLedger>,
+    wallet: Arc<dyn BaseWallet>,
+    next_req_id: AtomicU64,
+}
+
+impl InMemoryLedgerWrite {
+    pub fn new(ledger: Arc<InMemoryLedger>, wallet: Arc<dyn BaseWallet>) -> Self {
+        Self {
+            ledger,
+            wallet,
+            next_req_id: AtomicU64::new(1),
+        }
+    }
+
+    fn build_request(&self, submitter_did: &str, operation: Operation) -> VcxCoreResult<Request> {
+        let req_id = self.next_req_id.fetch_add(1, Ordering::Relaxed);
+        let mut request = Request::new(submitter_did, req_id, operation);
+        request.taa_acceptance = self.taa_acceptance()?;
+        Ok(request)
+    }
+
+    /// The acceptance of the agreement set in the settings. It is only attached while the ledger
+    /// has an agreement, since the ledger rejects acceptances it does not expect.
+    fn taa_acceptance(&self) -> VcxCoreResult<Option<TaaAcceptance>> {
+        if self.ledger.acceptance_mechanisms()?.is_empty() {
+            return Ok(None);
+        }
+        Ok(get_txn_author_agreement()?.map(|taa| {
+            let digest = match (taa.taa_digest, taa.text, taa.version) {
+                (Some(digest), _, _) => digest,
+                (None, Some(text), Some(version)) => taa_digest(&text, &version),
+                _ => String::new(),
+            };
+            TaaAcceptance {
+                mechanism: taa.acceptance_mechanism_type,
+                taa_digest: digest,
+                time: taa.time_of_acceptance,
+            }
+        }))
+    }
+
+    async fn sign(&self, did: &str, request: &mut Request) -> VcxCoreResult<()> {
+        let verkey = self.wallet.key_for_local_did(did).await?;
+        let signature = self.wallet.sign(&verkey, &request.signing_payload()?).await?;
+        request.add_signature(did, bs58::encode(signature).into_string());
+        Ok(())
+    }
+
+    async fn sign_and_submit(&self, submitter_did: &str, mut request: Request) -> VcxCoreResult<String> {
+        self.sign(submitter_did, &mut request).await?;
+        let reply = self.ledger.submit(&request, self.wallet.as_ref()).await?;
+        Ok(reply.to_string())
+    }
+
+    async fn prepare_for_endorsement(
+        &self,
+        submitter_did: &str,
+        mut request: Request,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        request.endorser = Some(endorser_did.to_string());
+        self.sign(submitter_did, &mut request).await?;
+        Ok(serde_json::to_string(&request)?)
+    }
+
+    fn schema_request(&self, schema_json: &str, submitter_did: &str) -> VcxCoreResult<Request> {
+        let schema: Value = serde_json::from_str(schema_json).map_err(|err| invalid_json("schema", err))?;
+        let data = SchemaData {
+            name: string_field(&schema, "name")?,
+            version: string_field(&schema, "version")?,
+            attr_names: serde_json::from_value(schema["attrNames"].clone())
+                .map_err(|err| invalid_json("schema attributes", err))?,
+        };
+        self.build_request(submitter_did, Operation::Schema { data })
+    }
+
+    fn cred_def_request(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<Request> {
+        let cred_def: Value =
+            serde_json::from_str(cred_def_json).map_err(|err| invalid_json("credential definition", err))?;
+        let schema_ref = string_field(&cred_def, "schemaId")?.parse().map_err(|_| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                "Credential definition must refer to the sequence number of its schema",
+            )
+        })?;
+        let operation = Operation::CredDef {
+            schema_ref,
+            signature_type: string_field(&cred_def, "type")?,
+            tag: string_field(&cred_def, "tag")?,
+            data: cred_def["value"].clone(),
+        };
+        self.build_request(submitter_did, operation)
+    }
+
+    fn rev_reg_def_request(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<Request> {
+        let rev_reg_def: Value =
+            serde_json::from_str(rev_reg_def).map_err(|err| invalid_json("revocation registry definition", err))?;
+        let operation = Operation::RevRegDef {
+            id: string_field(&rev_reg_def, "id")?,
+            revoc_def_type: string_field(&rev_reg_def, "revocDefType")?,
+            tag: string_field(&rev_reg_def, "tag")?,
+            cred_def_id: string_field(&rev_reg_def, "credDefId")?,
+            value: rev_reg_def["value"].clone(),
+        };
+        self.build_request(submitter_did, operation)
+    }
+
+    fn rev_reg_delta_request(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<Request> {
+        let delta: Value =
+            serde_json::from_str(rev_reg_entry_json).map_err(|err| invalid_json("revocation registry delta", err))?;
+        let value: RevRegEntryValue = serde_json::from_value(delta["value"].clone())
+            .map_err(|err| invalid_json("revocation registry delta", err))?;
+        let operation = Operation::RevRegEntry {
+            revoc_reg_def_id: rev_reg_id.to_string(),
+            revoc_def_type: "CL_ACCUM".to_string(),
+            value,
+        };
+        self.build_request(submitter_did, operation)
+    }
+}
+
+impl Debug for InMemoryLedgerWrite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "InMemoryLedgerWrite instance")
+    }
+}
+
+#[async_trait]
+impl IndyLedgerWrite for InMemoryLedgerWrite {
+    async fn publish_nym(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let operation = Operation::Nym {
+            dest: target_did.to_string(),
+            verkey: verkey.map(String::from),
+            alias: data.map(String::from),
+            role: role.map(String::from),
+        };
+        let request = self.build_request(submitter_did, operation)?;
+        self.sign_and_submit(submitter_did, request).await
+    }
+
+    async fn set_endorser(&self, submitter_did: &str, request_json: &str, endorser: &str) -> VcxCoreResult<String> {
+        let mut request = Request::from_json(request_json)?;
+        request.endorser = Some(endorser.to_string());
+        request.signature = None;
+        request.signatures = None;
+        self.sign(submitter_did, &mut request).await?;
+        Ok(serde_json::to_string(&request)?)
+    }
+
+    async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
+        verify_transaction_can_be_endorsed(request_json, endorser_did)?;
+        let mut request = Request::from_json(request_json)?;
+        self.sign(endorser_did, &mut request).await?;
+        self.ledger.submit(&request, self.wallet.as_ref()).await.map(|_| ())
+    }
+
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        verify_transaction_can_be_endorsed(request_json, endorser_did)?;
+        let mut request = Request::from_json(request_json)?;
+        self.sign(endorser_did, &mut request).await?;
+        Ok(serde_json::to_string(&request)?)
+    }
+
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()> {
+        self.ledger
+            .submit_request(request_json, self.wallet.as_ref())
+            .await
+            .map(|_| ())
+    }
+
+    async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
+        let operation = Operation::Attrib {
+            dest: target_did.to_string(),
+            raw: attrib_json.to_string(),
+        };
+        let request = self.build_request(target_did, operation)?;
+        self.sign_and_submit(target_did, request).await
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerWrite for InMemoryLedgerWrite {
+    async fn publish_schema(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        match endorser_did {
+            Some(endorser_did) => {
+                let request = self
+                    .prepare_schema_for_endorsement(schema_json, submitter_did, &endorser_did)
+                    .await?;
+                self.endorse_transaction(&endorser_did, &request).await
+            }
+            None => {
+                let request = self.schema_request(schema_json, submitter_did)?;
+                self.sign_and_submit(submitter_did, request).await.map(|_| ())
+            }
+        }
+    }
+
+    async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let request = self.cred_def_request(cred_def_json, submitter_did)?;
+        self.sign_and_submit(submitter_did, request).await.map(|_| ())
+    }
+
+    async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let request = self.rev_reg_def_request(rev_reg_def, submitter_did)?;
+        self.sign_and_submit(submitter_did, request).await.map(|_| ())
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        let request = self.rev_reg_delta_request(rev_reg_id, rev_reg_entry_json, submitter_did)?;
+        self.sign_and_submit(submitter_did, request).await.map(|_| ())
+    }
+
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.schema_request(schema_json, submitter_did)?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.cred_def_request(cred_def_json, submitter_did)?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.rev_reg_def_request(rev_reg_def, submitter_did)?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.rev_reg_delta_request(rev_reg_id, rev_reg_entry_json, submitter_did)?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+}
+
+fn string_field(json: &Value, field: &str) -> VcxCoreResult<String> {
+    json[field].as_str().map(String::from).ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Missing string field {} in {}", field, json),
+        )
+    })
+}
+
+#[cfg(all(test, feature = "in_memory_wallet"))]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::errors::error::AriesVcxCoreErrorKind;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    const TRUSTEE_SEED: &str = "000000000000000000000000Trustee1";
+
+    struct TestLedger {
+        ledger: Arc<InMemoryLedger>,
+        writer: InMemoryLedgerWrite,
+        wallet: Arc<dyn BaseWallet>,
+        trustee_did: String,
+    }
+
+    impl TestLedger {
+        async fn new() -> Self {
+            let ledger = Arc::new(InMemoryLedger::new());
+            let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+            let (trustee_did, trustee_verkey) = wallet.create_and_store_my_did(Some(TRUSTEE_SEED), None).await.unwrap();
+            ledger
+                .add_genesis_nym(&trustee_did, &trustee_verkey, Some(LedgerRole::Trustee))
+                .unwrap();
+            let writer = InMemoryLedgerWrite::new(ledger.clone(), wallet.clone());
+            Self {
+                ledger,
+                writer,
+                wallet,
+                trustee_did,
+            }
+        }
+
+        async fn new_did(&self, role: Option<&str>) -> String {
+            let (did, verkey) = self.wallet.create_and_store_my_did(None, None).await.unwrap();
+            self.writer
+                .publish_nym(&self.trustee_did, &did, Some(&verkey), None, role)
+                .await
+                .unwrap();
+            did
+        }
+    }
+
+    fn schema_json(name: &str) -> String {
+        json!({ "ver": "1.0", "id": "", "name": name, "version": "1.0", "attrNames": ["age"], "seqNo": null })
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_nym_written_by_trustee_is_read_back() {
+        let ledger = TestLedger::new().await;
+        let did = ledger.new_did(Some("ENDORSER")).await;
+
+        let reply: Value = serde_json::from_str(&ledger.ledger.get_nym(&did).await.unwrap()).unwrap();
+        assert_eq!(reply["op"], "REPLY");
+        let data: Value = serde_json::from_str(reply["result"]["data"].as_str().unwrap()).unwrap();
+        assert_eq!(data["dest"], did);
+        assert_eq!(data["role"], "101");
+        assert_eq!(data["verkey"], ledger.wallet.key_for_local_did(&did).await.unwrap());
+
+        let reply: Value =
+            serde_json::from_str(&ledger.ledger.get_nym("Th7MpTaRZVRYnPiabds81Y").await.unwrap()).unwrap();
+        assert_eq!(reply["result"]["data"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_auth_rules_require_endorsement_of_schemas() {
+        let ledger = TestLedger::new().await;
+        let author_did = ledger.new_did(None).await;
+        let endorser_did = ledger.new_did(Some("ENDORSER")).await;
+
+        let err = ledger
+            .writer
+            .publish_schema(&schema_json("unendorsed"), &author_did, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+        let err = ledger
+            .writer
+            .publish_nym(&author_did, &endorser_did, None, None, Some(""))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+
+        ledger
+            .writer
+            .publish_schema(&schema_json("endorsed"), &author_did, Some(endorser_did))
+            .await
+            .unwrap();
+        let schema_id = format!("{}:2:endorsed:1.0", author_did);
+        let schema: Value = serde_json::from_str(&ledger.ledger.get_schema(&schema_id, None).await.unwrap()).unwrap();
+        assert_eq!(schema["attrNames"], json!(["age"]));
+
+        ledger
+            .writer
+            .publish_schema(&schema_json("trusted"), &ledger.trustee_did, None)
+            .await
+            .unwrap();
+        let err = ledger
+            .writer
+            .publish_schema(&schema_json("trusted"), &ledger.trustee_did, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationSchema);
+    }
+
+    #[tokio::test]
+    async fn test_prepared_transactions_are_written_once_endorsed() {
+        let ledger = TestLedger::new().await;
+        let author_did = ledger.new_did(None).await;
+        let endorser_did = ledger.new_did(Some("ENDORSER")).await;
+
+        let request = ledger
+            .writer
+            .prepare_schema_for_endorsement(&schema_json("schema"), &author_did, &endorser_did)
+            .await
+            .unwrap();
+        let err = ledger
+            .writer
+            .sign_endorsed_transaction(&ledger.trustee_did, &request)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidJson);
+        let err = ledger.writer.submit_endorsed_transaction(&request).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+
+        let endorsed = ledger
+            .writer
+            .sign_endorsed_transaction(&endorser_did, &request)
+            .await
+            .unwrap();
+        ledger.writer.submit_endorsed_transaction(&endorsed).await.unwrap();
+        let schema: Value = serde_json::from_str(
+            &ledger
+                .ledger
+                .get_schema(&format!("{}:2:schema:1.0", author_did), None)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+
+        let schema_seq_no = schema["seqNo"].as_u64().unwrap();
+        let cred_def_id = format!("{}:3:CL:{}:tag", author_did, schema_seq_no);
+        let cred_def = json!({
+            "ver": "1.0", "id": cred_def_id, "schemaId": schema_seq_no.to_string(),
+            "type": "CL", "tag": "tag", "value": { "primary": {} }
+        });
+        let request = ledger
+            .writer
+            .prepare_cred_def_for_endorsement(&cred_def.to_string(), &author_did, &endorser_did)
+            .await
+            .unwrap();
+        ledger
+            .writer
+            .endorse_transaction(&endorser_did, &request)
+            .await
+            .unwrap();
+        ledger.ledger.get_cred_def(&cred_def_id, None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_signed_with_other_key_is_rejected() {
+        let ledger = TestLedger::new().await;
+        let did = ledger.new_did(None).await;
+
+        let operation = Operation::Attrib {
+            dest: did.clone(),
+            raw: json!({ "endpoint": "http://localhost" }).to_string(),
+        };
+        let mut request = Request::new(&did, 1, operation);
+        ledger.writer.sign(&ledger.trustee_did, &mut request).await.unwrap();
+        request.signature = request
+            .signatures
+            .take()
+            .and_then(|mut s| s.remove(&ledger.trustee_did));
+
+        let err = ledger
+            .ledger
+            .submit_request(&serde_json::to_string(&request).unwrap(), ledger.wallet.as_ref())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+
+        ledger
+            .writer
+            .add_attr(&did, &json!({ "endpoint": "http://localhost" }).to_string())
+            .await
+            .unwrap();
+        let reply: Value = serde_json::from_str(&ledger.ledger.get_attr(&did, "endpoint").await.unwrap()).unwrap();
+        assert_eq!(
+            reply["result"]["data"],
+            json!({ "endpoint": "http://localhost" }).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_txn_author_agreement_must_be_accepted() {
+        let ledger = TestLedger::new().await;
+        ledger
+            .ledger
+            .set_txn_author_agreement("agreement", "1.0", vec!["on_file".to_string()])
+            .unwrap();
+        let now = ledger.ledger.now().unwrap();
+
+        let submit = |acceptance: Option<TaaAcceptance>| {
+            let ledger = &ledger;
+            async move {
+                let (did, verkey) = ledger.wallet.create_and_store_my_did(None, None).await.unwrap();
+                let operation = Operation::Nym {
+                    dest: did,
+                    verkey: Some(verkey),
+                    alias: None,
+                    role: None,
+                };
+                let mut request = Request::new(&ledger.trustee_did, 1, operation);
+                request.taa_acceptance = acceptance;
+                ledger.writer.sign(&ledger.trustee_did, &mut request).await.unwrap();
+                ledger.ledger.submit(&request, ledger.wallet.as_ref()).await
+            }
+        };
+        let acceptance = |digest: String, time: u64| TaaAcceptance {
+            mechanism: "on_file".to_string(),
+            taa_digest: digest,
+            time,
+        };
+
+        assert!(submit(None).await.is_err());
+        assert!(submit(Some(acceptance("digest".to_string(), now))).await.is_err());
+        assert!(submit(Some(acceptance(taa_digest("agreement", "1.0"), now + 3600)))
+            .await
+            .is_err());
+        submit(Some(acceptance(taa_digest("agreement", "1.0"), now)))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rev_reg_delta_between_entries() {
+        let ledger = TestLedger::new().await;
+        let did = ledger.trustee_did.clone();
+        ledger.ledger.set_time(1000).unwrap();
+
+        ledger
+            .writer
+            .publish_schema(&schema_json("schema"), &did, None)
+            .await
+            .unwrap();
+        let schema: Value = serde_json::from_str(
+            &ledger
+                .ledger
+                .get_schema(&format!("{}:2:schema:1.0", did), None)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let schema_seq_no = schema["seqNo"].as_u64().unwrap();
+        let cred_def_id = format!("{}:3:CL:{}:tag", did, schema_seq_no);
+        let cred_def = json!({
+            "ver": "1.0", "id": cred_def_id, "schemaId": schema_seq_no.to_string(),
+            "type": "CL", "tag": "tag", "value": { "primary": {} }
+        });
+        ledger
+            .writer
+            .publish_cred_def(&cred_def.to_string(), &did)
+            .await
+            .unwrap();
+        let rev_reg_id = format!("{}:4:{}:CL_ACCUM:tag", did, cred_def_id);
+        let rev_reg_def = json!({
+            "ver": "1.0", "id": rev_reg_id, "revocDefType": "CL_ACCUM", "tag": "tag",
+            "credDefId": cred_def_id, "value": { "maxCredNum": 10 }
+        });
+        ledger
+            .writer
+            .publish_rev_reg_def(&rev_reg_def.to_string(), &did)
+            .await
+            .unwrap();
+
+        let entry = json!({ "ver": "1.0", "value": { "accum": "1", "issued": [1, 2, 3] } });
+        ledger
+            .writer
+            .publish_rev_reg_delta(&rev_reg_id, &entry.to_string(), &did)
+            .await
+            .unwrap();
+        ledger.ledger.advance_time(100).unwrap();
+        let entry = json!({ "ver": "1.0", "value": { "prevAccum": "1", "accum": "2", "revoked": [2] } });
+        ledger
+            .writer
+            .publish_rev_reg_delta(&rev_reg_id, &entry.to_string(), &did)
+            .await
+            .unwrap();
+        let stale = json!({ "ver": "1.0", "value": { "prevAccum": "1", "accum": "3", "revoked": [3] } });
+        let err = ledger
+            .writer
+            .publish_rev_reg_delta(&rev_reg_id, &stale.to_string(), &did)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+
+        let (_, delta, timestamp) = ledger
+            .ledger
+            .get_rev_reg_delta_json(&rev_reg_id, None, None)
+            .await
+            .unwrap();
+        let delta: Value = serde_json::from_str(&delta).unwrap();
+        assert_eq!(timestamp, 1100);
+        assert_eq!(
+            delta["value"],
+            json!({ "accum": "2", "issued": [1, 3], "revoked": [2] })
+        );
+
+        let (_, delta, _) = ledger
+            .ledger
+            .get_rev_reg_delta_json(&rev_reg_id, Some(1050), Some(1100))
+            .await
+            .unwrap();
+        let delta: Value = serde_json::from_str(&delta).unwrap();
+        assert_eq!(
+            delta["value"],
+            json!({ "prevAccum": "1", "accum": "2", "issued": [], "revoked": [2] })
+        );
+
+        let (_, rev_reg, timestamp) = ledger.ledger.get_rev_reg(&rev_reg_id, 1050).await.unwrap();
+        assert_eq!(timestamp, 1000);
+        assert_eq!(serde_json::from_str::<Value>(&rev_reg).unwrap()["value"]["accum"], "1");
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+pub(super) const PROTOCOL_VERSION: u8 = 2;
+
+/// A write request, in the JSON format of indy-node requests.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Request {
+    pub identifier: String,
+    pub req_id: u64,
+    pub protocol_version: u8,
+    pub operation: Operation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endorser: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taa_acceptance: Option<TaaAcceptance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub(super) enum Operation {
+    #[serde(rename = "1")]
+    Nym {
+        dest: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verkey: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        alias: Option<String>,
+        /// An empty role removes the role of the DID.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        role: Option<String>,
+    },
+    #[serde(rename = "100")]
+    Attrib { dest: String, raw: String },
+    #[serde(rename = "101")]
+    Schema { data: SchemaData },
+    #[serde(rename = "102")]
+    CredDef {
+        #[serde(rename = "ref")]
+        schema_ref: u64,
+        signature_type: String,
+        tag: String,
+        data: Value,
+    },
+    #[serde(rename = "113")]
+    RevRegDef {
+        id: String,
+        #[serde(rename = "revocDefType")]
+        revoc_def_type: String,
+        tag: String,
+        #[serde(rename = "credDefId")]
+        cred_def_id: String,
+        value: Value,
+    },
+    #[serde(rename = "114")]
+    RevRegEntry {
+        #[serde(rename = "revocRegDefId")]
+        revoc_reg_def_id: String,
+        #[serde(rename = "revocDefType")]
+        revoc_def_type: String,
+        value: RevRegEntryValue,
+    },
+}
+
+impl Operation {
+    pub fn txn_type(&self) -> &'static str {
+        match self {
+            Self::Nym { .. } => "1",
+            Self::Attrib { .. } => "100",
+            Self::Schema { .. } => "101",
+            Self::CredDef { .. } => "102",
+            Self::RevRegDef { .. } => "113",
+            Self::RevRegEntry { .. } => "114",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub(super) struct SchemaData {
+    pub name: String,
+    pub version: String,
+    pub attr_names: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RevRegEntryValue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_accum: Option<String>,
+    pub accum: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued: Option<Vec<u32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<Vec<u32>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TaaAcceptance {
+    pub mechanism: String,
+    pub taa_digest: String,
+    pub time: u64,
+}
+
+impl Request {
+    pub fn new(identifier: &str, req_id: u64, operation: Operation) -> Self {
+        Self {
+            identifier: identifier.to_string(),
+            req_id,
+            protocol_version: PROTOCOL_VERSION,
+            operation,
+            endorser: None,
+            taa_acceptance: None,
+            signature: None,
+            signatures: None,
+        }
+    }
+
+    pub fn from_json(request_json: &str) -> VcxCoreResult<Self> {
+        serde_json::from_str(request_json).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Invalid ledger request: {}", err),
+            )
+        })
+    }
+
+    /// The bytes signed by the author and endorser: the request without its signatures, as JSON
+    /// with sorted keys.
+    pub fn signing_payload(&self) -> VcxCoreResult<Vec<u8>> {
+        let mut request = serde_json::to_value(self)?;
+        if let Value::Object(fields) = &mut request {
+            fields.remove("signature");
+            fields.remove("signatures");
+        }
+        let mut payload = String::new();
+        write_canonical(&request, &mut payload);
+        Ok(payload.into_bytes())
+    }
+
+    /// Requests with an endorser are signed by both, through `signatures`.
+    pub fn add_signature(&mut self, did: &str, signature: String) {
+        if self.endorser.is_some() || self.signatures.is_some() {
+            let mut signatures = self.signatures.take().unwrap_or_default();
+            if let Some(author_signature) = self.signature.take() {
+                signatures.insert(self.identifier.clone(), author_signature);
+            }
+            signatures.insert(did.to_string(), signature);
+            self.signatures = Some(signatures);
+        } else {
+            self.signature = Some(signature);
+        }
+    }
+
+    pub fn signature_of(&self, did: &str) -> Option<&str> {
+        match &self.signatures {
+            Some(signatures) => signatures.get(did).map(String::as_str),
+            None if did == self.identifier => self.signature.as_deref(),
+            None => None,
+        }
+    }
+
+    /// The DIDs which must have signed the request.
+    pub fn signers(&self) -> Vec<&str> {
+        let mut signers = vec![self.identifier.as_str()];
+        if let Some(endorser) = &self.endorser {
+            if endorser != &self.identifier {
+                signers.push(endorser);
+            }
+        }
+        signers
+    }
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(fields) => {
+            let sorted: BTreeMap<_, _> = fields.iter().collect();
+            out.push('{');
+            for (i, (name, value)) in sorted.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(name.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        value => out.push_str(&value.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema_request() -> Request {
+        let operation = Operation::Schema {
+            data: SchemaData {
+                name: "name".to_string(),
+                version: "1.0".to_string(),
+                attr_names: vec!["age".to_string()],
+            },
+        };
+        Request::new("V4SGRU86Z58d6TV7PBUe6f", 1, operation)
+    }
+
+    #[test]
+    fn test_request_serializes_as_indy_request() {
+        let request = schema_request();
+        let expected = json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "reqId": 1,
+            "protocolVersion": 2,
+            "operation": {
+                "type": "101",
+                "data": { "name": "name", "version": "1.0", "attr_names": ["age"] }
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&request).unwrap(), expected);
+        let parsed = Request::from_json(&expected.to_string()).unwrap();
+        assert_eq!(parsed.operation, request.operation);
+    }
+
+    #[test]
+    fn test_signing_payload_ignores_signatures_and_key_order() {
+        let mut request = schema_request();
+        let payload = request.signing_payload().unwrap();
+        assert_eq!(
+            String::from_utf8(payload.clone()).unwrap(),
+            r#"{"identifier":"V4SGRU86Z58d6TV7PBUe6f","operation":{"data":{"attr_names":["age"],"name":"name","version":"1.0"},"type":"101"},"protocolVersion":2,"reqId":1}"#
+        );
+
+        request.add_signature("V4SGRU86Z58d6TV7PBUe6f", "signature".to_string());
+        assert_eq!(request.signing_payload().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_endorsed_request_collects_signatures() {
+        let mut request = schema_request();
+        request.add_signature("V4SGRU86Z58d6TV7PBUe6f", "author".to_string());
+        request.endorser = Some("Th7MpTaRZVRYnPiabds81Y".to_string());
+        request.add_signature("Th7MpTaRZVRYnPiabds81Y", "endorser".to_string());
+
+        assert_eq!(request.signature, None);
+        assert_eq!(request.signature_of("V4SGRU86Z58d6TV7PBUe6f"), Some("author"));
+        assert_eq!(request.signature_of("Th7MpTaRZVRYnPiabds81Y"), Some("endorser"));
+        assert_eq!(
+            request.signers(),
+            vec!["V4SGRU86Z58d6TV7PBUe6f", "Th7MpTaRZVRYnPiabds81Y"]
+        );
+    }
+}
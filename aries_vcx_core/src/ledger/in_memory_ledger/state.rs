@@ -0,0 +1,735 @@
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::request::{Operation, Request, RevRegEntryValue, SchemaData};
+
+/// How far in the future a TAA acceptance may be dated, as indy-node allows for clock skew.
+const TAA_ACCEPTANCE_SKEW: u64 = 120;
+
+/// The roles of DIDs on an indy ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedgerRole {
+    Trustee,
+    Steward,
+    Endorser,
+    NetworkMonitor,
+}
+
+impl LedgerRole {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Trustee => "0",
+            Self::Steward => "2",
+            Self::Endorser => "101",
+            Self::NetworkMonitor => "201",
+        }
+    }
+
+    /// Whether DIDs of the role may write public data, such as schemas and DIDs, without endorsement.
+    fn may_write(role: Option<Self>) -> bool {
+        matches!(role, Some(Self::Trustee | Self::Steward | Self::Endorser))
+    }
+
+    /// Whether DIDs of the role may give `role` to a DID, or take it away from it.
+    fn may_assign(by: Option<Self>, role: Option<Self>) -> bool {
+        matches!(
+            (by, role),
+            (Some(Self::Trustee), _)
+                | (Some(Self::Steward), None | Some(Self::Endorser | Self::NetworkMonitor))
+                | (Some(Self::Endorser), None)
+        )
+    }
+}
+
+impl FromStr for LedgerRole {
+    type Err = AriesVcxCoreError;
+
+    fn from_str(role: &str) -> Result<Self, Self::Err> {
+        match role {
+            "TRUSTEE" | "0" => Ok(Self::Trustee),
+            "STEWARD" | "2" => Ok(Self::Steward),
+            "ENDORSER" | "TRUST_ANCHOR" | "101" => Ok(Self::Endorser),
+            "NETWORK_MONITOR" | "201" => Ok(Self::NetworkMonitor),
+            _ => Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Unknown ledger role {}", role),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Nym {
+    verkey: Option<String>,
+    role: Option<LedgerRole>,
+    alias: Option<String>,
+    /// The DID which wrote the NYM, owning it while it has no verkey.
+    creator: String,
+    seq_no: u64,
+    txn_time: u64,
+}
+
+#[derive(Clone, Debug)]
+struct LedgerObject {
+    json: Value,
+    owner: String,
+    seq_no: u64,
+    txn_time: u64,
+}
+
+/// The state of a revocation registry after one of its entries.
+#[derive(Clone, Debug)]
+struct RevRegState {
+    accum: String,
+    issued: BTreeSet<u32>,
+    revoked: BTreeSet<u32>,
+    txn_time: u64,
+}
+
+#[derive(Clone, Debug)]
+struct TxnAuthorAgreement {
+    text: String,
+    version: String,
+    digest: String,
+    mechanisms: Vec<String>,
+    ratification_ts: u64,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct LedgerState {
+    txns: Vec<Value>,
+    nyms: HashMap<String, Nym>,
+    attribs: HashMap<(String, String), LedgerObject>,
+    schemas: HashMap<String, LedgerObject>,
+    schema_ids: HashMap<u64, String>,
+    cred_defs: HashMap<String, LedgerObject>,
+    rev_reg_defs: HashMap<String, LedgerObject>,
+    rev_reg_states: HashMap<String, Vec<RevRegState>>,
+    taa: Option<TxnAuthorAgreement>,
+    clock: Option<u64>,
+}
+
+pub(super) fn reject(reason: impl Into<String>) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidLedgerResponse,
+        format!("Ledger rejected request: {}", reason.into()),
+    )
+}
+
+fn not_found(item: &str, id: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::LedgerItemNotFound,
+        format!("{} {} not found on the ledger", item, id),
+    )
+}
+
+/// The digest identifying a TAA, as computed by indy-node.
+pub(super) fn taa_digest(text: &str, version: &str) -> String {
+    Sha256::digest(format!("{}{}", version, text).as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The full verkey of a DID, given the verkey written for it, which may be abbreviated.
+fn full_verkey(did: &str, verkey: &str) -> VcxCoreResult<String> {
+    match verkey.strip_prefix('~') {
+        None => Ok(verkey.to_string()),
+        Some(abbreviated) => {
+            let mut key = bs58::decode(did).into_vec().map_err(|_| reject("DID is not base58"))?;
+            key.extend(
+                bs58::decode(abbreviated)
+                    .into_vec()
+                    .map_err(|_| reject("Abbreviated verkey is not base58"))?,
+            );
+            Ok(bs58::encode(key).into_string())
+        }
+    }
+}
+
+fn reply(result: Value) -> Value {
+    json!({ "op": "REPLY", "result": result })
+}
+
+/// The `data` of read replies, which indy-node serializes into a string.
+fn reply_data(data: Option<Value>) -> Value {
+    data.map(|data| Value::String(data.to_string())).unwrap_or(Value::Null)
+}
+
+impl LedgerState {
+    pub fn now(&self) -> u64 {
+        self.clock.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn set_clock(&mut self, time: Option<u64>) {
+        self.clock = time;
+    }
+
+    pub fn advance_clock(&mut self, seconds: u64) {
+        self.clock = Some(self.now() + seconds);
+    }
+
+    /// The full verkey a DID signs its requests with.
+    pub fn verkey(&self, did: &str) -> VcxCoreResult<Option<String>> {
+        match self.nyms.get(did).and_then(|nym| nym.verkey.as_deref()) {
+            Some(verkey) => full_verkey(did, verkey).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn role(&self, did: &str) -> Option<LedgerRole> {
+        self.nyms.get(did).and_then(|nym| nym.role)
+    }
+
+    /// The DID which may change the NYM and its attributes: the DID itself, or its creator when
+    /// it has no verkey yet.
+    fn nym_owner<'a>(&'a self, did: &'a str) -> Option<&'a str> {
+        self.nyms.get(did).map(|nym| match nym.verkey {
+            Some(_) => did,
+            None => nym.creator.as_str(),
+        })
+    }
+
+    pub fn add_genesis_nym(&mut self, did: &str, verkey: &str, role: Option<LedgerRole>) -> VcxCoreResult<()> {
+        if self.nyms.contains_key(did) {
+            return Err(reject(format!("DID {} is already on the ledger", did)));
+        }
+        let operation = Operation::Nym {
+            dest: did.to_string(),
+            verkey: Some(verkey.to_string()),
+            alias: None,
+            role: role.map(|role| role.code().to_string()),
+        };
+        let request = Request::new(did, 0, operation);
+        let now = self.now();
+        self.apply_nym(&request, did, Some(verkey), None, role.map(Some), now);
+        Ok(())
+    }
+
+    pub fn set_txn_author_agreement(&mut self, text: &str, version: &str, mechanisms: Vec<String>) {
+        self.taa = Some(TxnAuthorAgreement {
+            text: text.to_string(),
+            version: version.to_string(),
+            digest: taa_digest(text, version),
+            mechanisms,
+            ratification_ts: self.now(),
+        });
+    }
+
+    /// Checks the request may be written, the signatures being verified already, and writes it.
+    pub fn apply(&mut self, request: &Request) -> VcxCoreResult<Value> {
+        let now = self.now();
+        self.check_taa(request, now)?;
+
+        let author_role = self.role(&request.identifier);
+        if let Some(endorser) = &request.endorser {
+            if !LedgerRole::may_write(self.role(endorser)) {
+                return Err(reject(format!("{} is not an endorser", endorser)));
+            }
+        }
+        let may_write = LedgerRole::may_write(author_role) || request.endorser.is_some();
+
+        match &request.operation {
+            Operation::Nym {
+                dest,
+                verkey,
+                alias,
+                role,
+            } => {
+                let role = match role.as_deref() {
+                    None => None,
+                    Some("") => Some(None),
+                    Some(role) => Some(Some(role.parse()?)),
+                };
+                match self.nyms.get(dest) {
+                    None => {
+                        if !may_write {
+                            return Err(reject(format!(
+                                "{} may not write DIDs without endorsement",
+                                request.identifier
+                            )));
+                        }
+                        if !LedgerRole::may_assign(author_role, role.flatten()) {
+                            return Err(reject(format!(
+                                "{} may not create DIDs of this role",
+                                request.identifier
+                            )));
+                        }
+                    }
+                    Some(nym) => {
+                        let owner = self.nym_owner(dest) == Some(request.identifier.as_str());
+                        if (verkey.is_some() || alias.is_some()) && !owner {
+                            return Err(reject(format!("Only the owner of {} may change its verkey", dest)));
+                        }
+                        if let Some(role) = role.filter(|role| *role != nym.role) {
+                            if !LedgerRole::may_assign(author_role, role)
+                                || !LedgerRole::may_assign(author_role, nym.role)
+                            {
+                                return Err(reject(format!(
+                                    "{} may not change the role of {}",
+                                    request.identifier, dest
+                                )));
+                            }
+                        }
+                    }
+                }
+                Ok(self.apply_nym(request, dest, verkey.as_deref(), alias.as_deref(), role, now))
+            }
+            Operation::Attrib { dest, raw } => {
+                if self.nym_owner(dest) != Some(request.identifier.as_str()) {
+                    return Err(reject(format!("Only the owner of {} may set its attributes", dest)));
+                }
+                let attributes: Map<String, Value> =
+                    serde_json::from_str(raw).map_err(|_| reject("Attribute must be a JSON object"))?;
+                if attributes.len() != 1 {
+                    return Err(reject("Attribute must set exactly one attribute"));
+                }
+                let seq_no = self.next_seq_no();
+                for (name, value) in attributes {
+                    let attribute = LedgerObject {
+                        json: json!({ name.clone(): value }),
+                        owner: request.identifier.clone(),
+                        seq_no,
+                        txn_time: now,
+                    };
+                    self.attribs.insert((dest.clone(), name), attribute);
+                }
+                Ok(self.record(request, now))
+            }
+            Operation::Schema { data } => {
+                if !may_write {
+                    return Err(reject(format!(
+                        "{} may not write schemas without endorsement",
+                        request.identifier
+                    )));
+                }
+                let id = format!("{}:2:{}:{}", request.identifier, data.name, data.version);
+                if self.schemas.contains_key(&id) {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::DuplicationSchema,
+                        format!("Schema {} is already on the ledger", id),
+                    ));
+                }
+                let seq_no = self.next_seq_no();
+                let SchemaData {
+                    name,
+                    version,
+                    attr_names,
+                } = data;
+                let schema = json!({
+                    "ver": "1.0",
+                    "id": id,
+                    "name": name,
+                    "version": version,
+                    "attrNames": attr_names,
+                    "seqNo": seq_no,
+                });
+                self.insert_object(Kind::Schema, &id, schema, request, seq_no, now);
+                self.schema_ids.insert(seq_no, id);
+                Ok(self.record(request, now))
+            }
+            Operation::CredDef {
+                schema_ref,
+                signature_type,
+                tag,
+                data,
+            } => {
+                if !may_write {
+                    return Err(reject(format!(
+                        "{} may not write credential definitions without endorsement",
+                        request.identifier
+                    )));
+                }
+                if !self.schema_ids.contains_key(schema_ref) {
+                    return Err(reject(format!("No schema was written in transaction {}", schema_ref)));
+                }
+                let id = format!("{}:3:{}:{}:{}", request.identifier, signature_type, schema_ref, tag);
+                if self.cred_defs.contains_key(&id) {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::CredDefAlreadyCreated,
+                        format!("Credential definition {} is already on the ledger", id),
+                    ));
+                }
+                let cred_def = json!({
+                    "ver": "1.0",
+                    "id": id,
+                    "schemaId": schema_ref.to_string(),
+                    "type": signature_type,
+                    "tag": tag,
+                    "value": data,
+                });
+                let seq_no = self.next_seq_no();
+                self.insert_object(Kind::CredDef, &id, cred_def, request, seq_no, now);
+                Ok(self.record(request, now))
+            }
+            Operation::RevRegDef {
+                id,
+                revoc_def_type,
+                tag,
+                cred_def_id,
+                value,
+            } => {
+                if !may_write {
+                    return Err(reject(format!(
+                        "{} may not write revocation registries without endorsement",
+                        request.identifier
+                    )));
+                }
+                match self.cred_defs.get(cred_def_id) {
+                    Some(cred_def) if cred_def.owner == request.identifier => {}
+                    Some(_) => return Err(reject(format!("{} does not own {}", request.identifier, cred_def_id))),
+                    None => {
+                        return Err(reject(format!(
+                            "Credential definition {} is not on the ledger",
+                            cred_def_id
+                        )))
+                    }
+                }
+                let expected_id = format!("{}:4:{}:{}:{}", request.identifier, cred_def_id, revoc_def_type, tag);
+                if *id != expected_id {
+                    return Err(reject(format!(
+                        "Revocation registry id {} should be {}",
+                        id, expected_id
+                    )));
+                }
+                if self.rev_reg_defs.contains_key(id) {
+                    return Err(reject(format!("Revocation registry {} is already on the ledger", id)));
+                }
+                let rev_reg_def = json!({
+                    "ver": "1.0",
+                    "id": id,
+                    "revocDefType": revoc_def_type,
+                    "tag": tag,
+                    "credDefId": cred_def_id,
+                    "value": value,
+                });
+                let seq_no = self.next_seq_no();
+                self.insert_object(Kind::RevRegDef, id, rev_reg_def, request, seq_no, now);
+                Ok(self.record(request, now))
+            }
+            Operation::RevRegEntry {
+                revoc_reg_def_id,
+                value,
+                ..
+            } => {
+                let rev_reg_def = self
+                    .rev_reg_defs
+                    .get(revoc_reg_def_id)
+                    .ok_or_else(|| reject(format!("Revocation registry {} is not on the ledger", revoc_reg_def_id)))?;
+                if rev_reg_def.owner != request.identifier {
+                    return Err(reject(format!(
+                        "{} does not own {}",
+                        request.identifier, revoc_reg_def_id
+                    )));
+                }
+                let max_cred_num = rev_reg_def.json["value"]["maxCredNum"].as_u64().unwrap_or(u64::MAX);
+                let state = self.next_rev_reg_state(revoc_reg_def_id, value, max_cred_num, now)?;
+                self.rev_reg_states
+                    .entry(revoc_reg_def_id.clone())
+                    .or_default()
+                    .push(state);
+                Ok(self.record(request, now))
+            }
+        }
+    }
+
+    fn check_taa(&self, request: &Request, now: u64) -> VcxCoreResult<()> {
+        match (&self.taa, &request.taa_acceptance) {
+            (None, None) => Ok(()),
+            (None, Some(_)) => Err(reject("Transaction author agreement accepted but not required")),
+            (Some(_), None) => Err(reject("Transaction author agreement not accepted")),
+            (Some(taa), Some(acceptance)) => {
+                if acceptance.taa_digest != taa.digest {
+                    return Err(reject(format!(
+                        "Accepted transaction author agreement {} is not the current one",
+                        acceptance.taa_digest
+                    )));
+                }
+                if !taa.mechanisms.contains(&acceptance.mechanism) {
+                    return Err(reject(format!("Unknown acceptance mechanism {}", acceptance.mechanism)));
+                }
+                if acceptance.time > now + TAA_ACCEPTANCE_SKEW {
+                    return Err(reject("Transaction author agreement accepted in the future"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn next_seq_no(&self) -> u64 {
+        self.txns.len() as u64 + 1
+    }
+
+    fn apply_nym(
+        &mut self,
+        request: &Request,
+        dest: &str,
+        verkey: Option<&str>,
+        alias: Option<&str>,
+        role: Option<Option<LedgerRole>>,
+        now: u64,
+    ) -> Value {
+        let seq_no = self.next_seq_no();
+        let nym = self.nyms.entry(dest.to_string()).or_insert_with(|| Nym {
+            verkey: None,
+            role: None,
+            alias: None,
+            creator: request.identifier.clone(),
+            seq_no,
+            txn_time: now,
+        });
+        if let Some(verkey) = verkey {
+            nym.verkey = Some(verkey.to_string());
+        }
+        if let Some(alias) = alias {
+            nym.alias = Some(alias.to_string());
+        }
+        if let Some(role) = role {
+            nym.role = role;
+        }
+        nym.seq_no = seq_no;
+        nym.txn_time = now;
+        self.record(request, now)
+    }
+
+    fn insert_object(&mut self, kind: Kind, id: &str, json: Value, request: &Request, seq_no: u64, now: u64) {
+        let object = LedgerObject {
+            json,
+            owner: request.identifier.clone(),
+            seq_no,
+            txn_time: now,
+        };
+        let objects = match kind {
+            Kind::Schema => &mut self.schemas,
+            Kind::CredDef => &mut self.cred_defs,
+            Kind::RevRegDef => &mut self.rev_reg_defs,
+        };
+        objects.insert(id.to_string(), object);
+    }
+
+    fn next_rev_reg_state(
+        &self,
+        rev_reg_id: &str,
+        value: &RevRegEntryValue,
+        max_cred_num: u64,
+        now: u64,
+    ) -> VcxCoreResult<RevRegState> {
+        let current = self.rev_reg_states.get(rev_reg_id).and_then(|states| states.last());
+        let (mut issued, mut revoked) = match current {
+            Some(current) => {
+                if value.prev_accum.as_ref() != Some(&current.accum) {
+                    return Err(reject(format!(
+                        "Previous accumulator of {} is {}, not {:?}",
+                        rev_reg_id, current.accum, value.prev_accum
+                    )));
+                }
+                (current.issued.clone(), current.revoked.clone())
+            }
+            None => (BTreeSet::new(), BTreeSet::new()),
+        };
+
+        let newly_issued = value.issued.clone().unwrap_or_default();
+        let newly_revoked = value.revoked.clone().unwrap_or_default();
+        if let Some(index) = newly_issued
+            .iter()
+            .chain(newly_revoked.iter())
+            .find(|index| **index == 0 || u64::from(**index) > max_cred_num)
+        {
+            return Err(reject(format!("Credential index {} is out of {}", index, rev_reg_id)));
+        }
+        for index in newly_issued {
+            revoked.remove(&index);
+            issued.insert(index);
+        }
+        for index in newly_revoked {
+            issued.remove(&index);
+            revoked.insert(index);
+        }
+
+        Ok(RevRegState {
+            accum: value.accum.clone(),
+            issued,
+            revoked,
+            txn_time: now,
+        })
+    }
+
+    /// Appends the request to the transaction log, returning the reply to it.
+    fn record(&mut self, request: &Request, now: u64) -> Value {
+        let seq_no = self.next_seq_no();
+        let mut data = serde_json::to_value(&request.operation).unwrap_or_default();
+        if let Value::Object(fields) = &mut data {
+            fields.remove("type");
+        }
+        let mut metadata = json!({ "from": request.identifier, "reqId": request.req_id });
+        if let Some(endorser) = &request.endorser {
+            metadata["endorser"] = json!(endorser);
+        }
+        if let Some(acceptance) = &request.taa_acceptance {
+            metadata["taaAcceptance"] = json!(acceptance);
+        }
+        let signature = match &request.signatures {
+            Some(signatures) => json!({
+                "type": "ED25519",
+                "values": signatures.iter().map(|(from, value)| json!({ "from": from, "value": value })).collect::<Vec<_>>(),
+            }),
+            None => json!({
+                "type": "ED25519",
+                "values": request.signature.iter().map(|value| json!({ "from": request.identifier, "value": value })).collect::<Vec<_>>(),
+            }),
+        };
+
+        let txn = json!({
+            "txn": {
+                "type": request.operation.txn_type(),
+                "data": data,
+                "metadata": metadata,
+                "protocolVersion": request.protocol_version,
+            },
+            "txnMetadata": { "seqNo": seq_no, "txnTime": now },
+            "reqSignature": signature,
+            "ver": "1",
+        });
+        self.txns.push(txn.clone());
+        reply(txn)
+    }
+
+    pub fn get_nym(&self, did: &str) -> Value {
+        let nym = self.nyms.get(did);
+        let data = nym.map(|nym| {
+            json!({
+                "dest": did,
+                "identifier": nym.creator,
+                "role": nym.role.map(|role| role.code()),
+                "verkey": nym.verkey,
+                "alias": nym.alias,
+                "seqNo": nym.seq_no,
+                "txnTime": nym.txn_time,
+            })
+        });
+        reply(json!({
+            "type": "105",
+            "dest": did,
+            "data": reply_data(data),
+            "seqNo": nym.map(|nym| nym.seq_no),
+            "txnTime": nym.map(|nym| nym.txn_time),
+        }))
+    }
+
+    pub fn get_attr(&self, did: &str, name: &str) -> Value {
+        let attribute = self.attribs.get(&(did.to_string(), name.to_string()));
+        reply(json!({
+            "type": "104",
+            "dest": did,
+            "raw": name,
+            "data": reply_data(attribute.map(|attribute| attribute.json.clone())),
+            "seqNo": attribute.map(|attribute| attribute.seq_no),
+            "txnTime": attribute.map(|attribute| attribute.txn_time),
+        }))
+    }
+
+    pub fn get_txn_author_agreement(&self) -> Value {
+        let data = self.taa.as_ref().map(|taa| {
+            json!({
+                "text": taa.text,
+                "version": taa.version,
+                "digest": taa.digest,
+                "ratification_ts": taa.ratification_ts,
+            })
+        });
+        reply(json!({ "type": "6", "data": data }))
+    }
+
+    /// The acceptance mechanisms of the current TAA, as returned by `GET_TXN_AUTHR_AGRMT_AML`.
+    pub fn acceptance_mechanisms(&self) -> Vec<String> {
+        self.taa.as_ref().map(|taa| taa.mechanisms.clone()).unwrap_or_default()
+    }
+
+    pub fn get_txn(&self, seq_no: i32) -> Value {
+        let txn = usize::try_from(seq_no)
+            .ok()
+            .and_then(|seq_no| seq_no.checked_sub(1))
+            .and_then(|index| self.txns.get(index));
+        reply(json!({ "type": "3", "seqNo": seq_no, "data": txn }))
+    }
+
+    pub fn get_schema(&self, id: &str) -> VcxCoreResult<Value> {
+        Self::get_object(&self.schemas, "Schema", id)
+    }
+
+    pub fn get_cred_def(&self, id: &str) -> VcxCoreResult<Value> {
+        Self::get_object(&self.cred_defs, "Credential definition", id)
+    }
+
+    pub fn get_rev_reg_def(&self, id: &str) -> VcxCoreResult<Value> {
+        Self::get_object(&self.rev_reg_defs, "Revocation registry definition", id)
+    }
+
+    fn get_object(objects: &HashMap<String, LedgerObject>, item: &str, id: &str) -> VcxCoreResult<Value> {
+        objects
+            .get(id)
+            .map(|object| object.json.clone())
+            .ok_or_else(|| not_found(item, id))
+    }
+
+    /// The last state of the registry written at or before the time.
+    fn rev_reg_state_at(&self, id: &str, timestamp: u64) -> Option<&RevRegState> {
+        self.rev_reg_states
+            .get(id)?
+            .iter()
+            .rev()
+            .find(|state| state.txn_time <= timestamp)
+    }
+
+    /// The delta between the states of the registry at `from` and `to`, as indy-node computes it,
+    /// and the time of the state at `to`.
+    pub fn get_rev_reg_delta(&self, id: &str, from: Option<u64>, to: u64) -> VcxCoreResult<(Value, u64)> {
+        let to_state = self
+            .rev_reg_state_at(id, to)
+            .ok_or_else(|| not_found("Revocation registry entry of", id))?;
+        let from_state = from.and_then(|from| self.rev_reg_state_at(id, from));
+
+        let value = match from_state {
+            Some(from_state) => json!({
+                "prevAccum": from_state.accum,
+                "accum": to_state.accum,
+                "issued": to_state.issued.difference(&from_state.issued).collect::<Vec<_>>(),
+                "revoked": to_state.revoked.difference(&from_state.revoked).collect::<Vec<_>>(),
+            }),
+            None => json!({
+                "accum": to_state.accum,
+                "issued": to_state.issued,
+                "revoked": to_state.revoked,
+            }),
+        };
+        Ok((json!({ "ver": "1.0", "value": value }), to_state.txn_time))
+    }
+
+    pub fn get_rev_reg(&self, id: &str, timestamp: u64) -> VcxCoreResult<(Value, u64)> {
+        let state = self
+            .rev_reg_state_at(id, timestamp)
+            .ok_or_else(|| not_found("Revocation registry entry of", id))?;
+        Ok((
+            json!({ "ver": "1.0", "value": { "accum": state.accum } }),
+            state.txn_time,
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Schema,
+    CredDef,
+    RevRegDef,
+}
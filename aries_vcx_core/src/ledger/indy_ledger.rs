@@ -98,6 +98,14 @@ impl IndyLedgerWrite for IndySdkLedgerWrite {
         .await
     }
 
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        indy::ledger::transactions::sign_endorsed_transaction(self.indy_wallet_handle, endorser_did, request_json).await
+    }
+
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()> {
+        indy::ledger::transactions::submit_endorsed_transaction(self.indy_pool_handle, request_json).await
+    }
+
     async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
         indy::ledger::transactions::add_attr(self.indy_wallet_handle, self.indy_pool_handle, target_did, attrib_json)
             .await
@@ -205,4 +213,47 @@ impl AnoncredsLedgerWrite for IndySdkLedgerWrite {
 
         Ok(())
     }
+
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = indy::ledger::transactions::build_schema_request(submitter_did, schema_json).await?;
+        self.set_endorser(submitter_did, &request, endorser_did).await
+    }
+
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = indy::ledger::transactions::build_cred_def_request(submitter_did, cred_def_json).await?;
+        self.set_endorser(submitter_did, &request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = indy::ledger::transactions::build_rev_reg_request(submitter_did, rev_reg_def).await?;
+        self.set_endorser(submitter_did, &request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request =
+            indy::ledger::transactions::build_rev_reg_delta_request(submitter_did, rev_reg_id, rev_reg_entry_json)
+                .await?;
+        self.set_endorser(submitter_did, &request, endorser_did).await
+    }
 }
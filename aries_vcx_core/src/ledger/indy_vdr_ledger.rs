@@ -18,14 +18,14 @@ use vdr::utils::did::DidValue;
 use vdr::utils::Qualifiable;
 
 use crate::common::ledger::transactions::verify_transaction_can_be_endorsed;
-use crate::errors::error::VcxCoreResult;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use crate::global::author_agreement::get_txn_author_agreement;
 use crate::global::settings;
 
 use super::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite};
 use super::request_signer::RequestSigner;
 use super::request_submitter::RequestSubmitter;
-use super::response_cacher::ResponseCacher;
+use super::response_cacher::{rev_reg_state_cache_id, CacheStatistics, LedgerObjectKind, ResponseCacher};
 
 pub struct IndyVdrLedgerReadConfig<T, V>
 where
@@ -84,18 +84,59 @@ where
         Ok(RequestBuilder::new(version))
     }
 
+    pub fn cache_statistics(&self) -> CacheStatistics {
+        self.response_cacher.statistics()
+    }
+
+    /// Fetches the objects into the cache ahead of their use, such as the schemas and credential
+    /// definitions an agent works with when it starts. The ids are the ones the objects are cached
+    /// under, as told apart by [`LedgerObjectKind::of`].
+    pub async fn prefetch(&self, ids: &[&str]) -> VcxCoreResult<()> {
+        for id in ids {
+            match LedgerObjectKind::of(id) {
+                LedgerObjectKind::Nym => self.get_nym(id).await.map(|_| ())?,
+                LedgerObjectKind::Schema => self.get_schema(id, None).await.map(|_| ())?,
+                LedgerObjectKind::CredDef => self.get_cred_def(id, None).await.map(|_| ())?,
+                LedgerObjectKind::RevRegDef => self.get_rev_reg_def_json(id).await.map(|_| ())?,
+                LedgerObjectKind::RevRegState => {
+                    let (rev_reg_id, timestamp) = id
+                        .rsplit_once('@')
+                        .and_then(|(rev_reg_id, timestamp)| Some((rev_reg_id, timestamp.parse().ok()?)))
+                        .ok_or_else(|| {
+                            AriesVcxCoreError::from_msg(
+                                AriesVcxCoreErrorKind::InvalidInput,
+                                format!("Invalid revocation registry state id: {id}"),
+                            )
+                        })?;
+                    self.get_rev_reg(rev_reg_id, timestamp).await.map(|_| ())?
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn submit_request_cached(&self, id: &str, request: PreparedRequest) -> VcxCoreResult<String> {
         match self.response_cacher.get(id, None).await? {
             Some(response) => Ok(response),
             None => {
                 let response = self.request_submitter.submit(request).await?;
-                self.response_cacher.put(id, response.clone()).await?;
+                if holds_ledger_object(&response) {
+                    self.response_cacher.put(id, response.clone()).await?;
+                }
                 Ok(response)
             }
         }
     }
 }
 
+/// Whether a reply to a GET request holds the object asked for. The replies telling the object is
+/// not on the ledger are not cached, as it can be written later on.
+fn holds_ledger_object(response: &str) -> bool {
+    serde_json::from_str::<Value>(response)
+        .map(|reply| reply["op"] == "REPLY" && !reply["result"]["data"].is_null())
+        .unwrap_or(false)
+}
+
 impl<T, U> IndyVdrLedgerWrite<T, U>
 where
     T: RequestSubmitter + Send + Sync,
@@ -120,6 +161,68 @@ where
         request.set_signature(&signature)?;
         self.request_submitter.submit(request).await
     }
+
+    async fn prepare_for_endorsement(
+        &self,
+        submitter_did: &str,
+        request: PreparedRequest,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let mut request = request;
+        request.set_endorser(&DidValue::from_str(endorser_did)?)?;
+        let signature_submitter = self.request_signer.sign(submitter_did, &request).await?;
+        request.set_multi_signature(&DidValue::from_str(submitter_did)?, &signature_submitter)?;
+        Ok(request.req_json.to_string())
+    }
+
+    async fn build_schema_request(&self, schema_json: &str, submitter_did: &str) -> VcxCoreResult<PreparedRequest> {
+        let identifier = DidValue::from_str(submitter_did)?;
+        let schema_data: SchemaV1 = serde_json::from_str(schema_json)?;
+        let request = self
+            .request_builder()?
+            .build_schema_request(&identifier, Schema::SchemaV1(schema_data))?;
+        _append_txn_author_agreement_to_request(request).await
+    }
+
+    async fn build_cred_def_request(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<PreparedRequest> {
+        let identifier = DidValue::from_str(submitter_did)?;
+        let cred_def_data: CredentialDefinitionV1 = serde_json::from_str(cred_def_json)?;
+        let request = self
+            .request_builder()?
+            .build_cred_def_request(&identifier, CredentialDefinition::CredentialDefinitionV1(cred_def_data))?;
+        _append_txn_author_agreement_to_request(request).await
+    }
+
+    async fn build_rev_reg_def_request(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<PreparedRequest> {
+        let identifier = DidValue::from_str(submitter_did)?;
+        let rev_reg_def_data: RevocationRegistryDefinitionV1 = serde_json::from_str(rev_reg_def)?;
+        let request = self.request_builder()?.build_revoc_reg_def_request(
+            &identifier,
+            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(rev_reg_def_data),
+        )?;
+        _append_txn_author_agreement_to_request(request).await
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<PreparedRequest> {
+        let identifier = DidValue::from_str(submitter_did)?;
+        let rev_reg_delta_data: RevocationRegistryDeltaV1 = serde_json::from_str(rev_reg_entry_json)?;
+        let request = self.request_builder()?.build_revoc_reg_entry_request(
+            &identifier,
+            &RevocationRegistryId::from_str(rev_reg_id)?,
+            &RegistryType::CL_ACCUM,
+            RevocationRegistryDelta::RevocationRegistryDeltaV1(rev_reg_delta_data),
+        )?;
+        _append_txn_author_agreement_to_request(request).await
+    }
 }
 
 impl<T, V> Debug for IndyVdrLedgerRead<T, V>
@@ -206,11 +309,8 @@ where
     }
 
     async fn set_endorser(&self, submitter_did: &str, request_json: &str, endorser: &str) -> VcxCoreResult<String> {
-        let mut request = PreparedRequest::from_request_json(request_json)?;
-        request.set_endorser(&DidValue::from_str(endorser)?)?;
-        let signature_submitter = self.request_signer.sign(submitter_did, &request).await?;
-        request.set_multi_signature(&DidValue::from_str(submitter_did)?, &signature_submitter)?;
-        Ok(request.req_json.to_string())
+        let request = PreparedRequest::from_request_json(request_json)?;
+        self.prepare_for_endorsement(submitter_did, request, endorser).await
     }
 
     async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
@@ -221,6 +321,19 @@ where
         self.request_submitter.submit(request).await.map(|_| ())
     }
 
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        let mut request = PreparedRequest::from_request_json(request_json)?;
+        verify_transaction_can_be_endorsed(request_json, endorser_did)?;
+        let signature_endorser = self.request_signer.sign(endorser_did, &request).await?;
+        request.set_multi_signature(&DidValue::from_str(endorser_did)?, &signature_endorser)?;
+        Ok(request.req_json.to_string())
+    }
+
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()> {
+        let request = PreparedRequest::from_request_json(request_json)?;
+        self.request_submitter.submit(request).await.map(|_| ())
+    }
+
     async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
         let identifier = DidValue::from_str(target_did)?;
         let dest = DidValue::from_str(target_did)?;
@@ -277,7 +390,7 @@ where
         let request = self
             .request_builder()?
             .build_get_cred_def_request(identifier.as_ref(), &id)?;
-        let response = self.request_submitter.submit(request).await?;
+        let response = self.submit_request_cached(cred_def_id, request).await?;
         let cred_def = self.response_parser.parse_get_cred_def_response(&response, None)?;
         Ok(serde_json::to_string(&cred_def)?)
     }
@@ -328,7 +441,9 @@ where
             &revoc_reg_def_id,
             timestamp.try_into().unwrap(),
         )?;
-        let res = self.request_submitter.submit(request).await?;
+        let res = self
+            .submit_request_cached(&rev_reg_state_cache_id(rev_reg_id, timestamp), request)
+            .await?;
 
         let RevocationRegistryInfo {
             revoc_reg_def_id,
@@ -356,12 +471,7 @@ where
         submitter_did: &str,
         endorser_did: Option<String>,
     ) -> VcxCoreResult<()> {
-        let identifier = DidValue::from_str(submitter_did)?;
-        let schema_data: SchemaV1 = serde_json::from_str(schema_json)?;
-        let mut request = self
-            .request_builder()?
-            .build_schema_request(&identifier, Schema::SchemaV1(schema_data))?;
-        request = _append_txn_author_agreement_to_request(request).await?;
+        let request = self.build_schema_request(schema_json, submitter_did).await?;
         // if let Some(endorser_did) = endorser_did {
         //     request = PreparedRequest::from_request_json(
         //         self.set_endorser(submitter_did, &request.req_json.to_string(), &endorser_did)
@@ -372,23 +482,12 @@ where
     }
 
     async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
-        let identifier = DidValue::from_str(submitter_did)?;
-        let cred_def_data: CredentialDefinitionV1 = serde_json::from_str(cred_def_json)?;
-        let request = self
-            .request_builder()?
-            .build_cred_def_request(&identifier, CredentialDefinition::CredentialDefinitionV1(cred_def_data))?;
-        let request = _append_txn_author_agreement_to_request(request).await?;
+        let request = self.build_cred_def_request(cred_def_json, submitter_did).await?;
         self.sign_and_submit_request(submitter_did, request).await.map(|_| ())
     }
 
     async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
-        let identifier = DidValue::from_str(submitter_did)?;
-        let rev_reg_def_data: RevocationRegistryDefinitionV1 = serde_json::from_str(rev_reg_def)?;
-        let request = self.request_builder()?.build_revoc_reg_def_request(
-            &identifier,
-            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(rev_reg_def_data),
-        )?;
-        let request = _append_txn_author_agreement_to_request(request).await?;
+        let request = self.build_rev_reg_def_request(rev_reg_def, submitter_did).await?;
         self.sign_and_submit_request(submitter_did, request).await.map(|_| ())
     }
 
@@ -398,15 +497,69 @@ where
         rev_reg_entry_json: &str,
         submitter_did: &str,
     ) -> VcxCoreResult<()> {
-        let identifier = DidValue::from_str(submitter_did)?;
-        let rev_reg_delta_data: RevocationRegistryDeltaV1 = serde_json::from_str(rev_reg_entry_json)?;
-        let request = self.request_builder()?.build_revoc_reg_entry_request(
-            &identifier,
-            &RevocationRegistryId::from_str(rev_reg_id)?,
-            &RegistryType::CL_ACCUM,
-            RevocationRegistryDelta::RevocationRegistryDeltaV1(rev_reg_delta_data),
-        )?;
-        let request = _append_txn_author_agreement_to_request(request).await?;
+        let request = self
+            .build_rev_reg_delta_request(rev_reg_id, rev_reg_entry_json, submitter_did)
+            .await?;
         self.sign_and_submit_request(submitter_did, request).await.map(|_| ())
     }
+
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.build_schema_request(schema_json, submitter_did).await?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.build_cred_def_request(cred_def_json, submitter_did).await?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self.build_rev_reg_def_request(rev_reg_def, submitter_did).await?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let request = self
+            .build_rev_reg_delta_request(rev_reg_id, rev_reg_entry_json, submitter_did)
+            .await?;
+        self.prepare_for_endorsement(submitter_did, request, endorser_did).await
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_holds_ledger_object() {
+        assert!(holds_ledger_object(
+            r#"{"op": "REPLY", "result": {"type": "107", "data": {"attr_names": ["name"]}}}"#
+        ));
+        assert!(!holds_ledger_object(
+            r#"{"op": "REPLY", "result": {"type": "107", "data": null}}"#
+        ));
+        assert!(!holds_ledger_object(r#"{"op": "REQNACK", "reason": "invalid"}"#));
+        assert!(!holds_ledger_object("not json"));
+    }
 }
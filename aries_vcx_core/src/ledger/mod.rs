@@ -1,11 +1,16 @@
 pub mod base_ledger;
+#[cfg(feature = "in_memory_ledger")]
+pub mod in_memory_ledger;
 #[cfg(feature = "vdrtools")]
 pub mod indy_ledger;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 pub mod indy_vdr_ledger;
+pub mod multi_ledger;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 pub mod request_signer;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 pub mod request_submitter;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 pub mod response_cacher;
+#[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
+pub mod state_proof;
@@ -0,0 +1,664 @@
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead, IndyLedgerWrite};
+
+const DID_INDY_PREFIX: &str = "did:indy:";
+const OBJECT_PATH: &str = "/anoncreds/v0/";
+
+/// A ledger of a [`MultiLedgerRead`], serving the `did:indy` namespace, such as `sovrin` or
+/// `sovrin:staging`.
+#[derive(Clone)]
+pub struct NamespacedLedger {
+    pub namespace: String,
+    pub indy_ledger_read: Arc<dyn IndyLedgerRead>,
+    pub anoncreds_ledger_read: Arc<dyn AnoncredsLedgerRead>,
+}
+
+impl NamespacedLedger {
+    pub fn new<T>(namespace: &str, ledger_read: Arc<T>) -> Self
+    where
+        T: IndyLedgerRead + AnoncredsLedgerRead + 'static,
+    {
+        Self {
+            namespace: namespace.to_string(),
+            indy_ledger_read: ledger_read.clone(),
+            anoncreds_ledger_read: ledger_read,
+        }
+    }
+}
+
+impl Debug for NamespacedLedger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NamespacedLedger")
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+/// Reads from several ledgers, routing every read to the ledger of the namespace of the DID or
+/// object ID read.
+///
+/// `did:indy` DIDs and object IDs, such as `did:indy:sovrin:<did>/anoncreds/v0/SCHEMA/<name>/<version>`,
+/// are read from the ledger of their namespace, after being converted to the unqualified IDs the
+/// ledgers know. Unqualified DIDs and IDs are looked up on every ledger, in the order the ledgers
+/// were given, until one of them has the object. Reads which are not about a DID, such as the
+/// transaction author agreement, go to the first ledger.
+#[derive(Debug)]
+pub struct MultiLedgerRead {
+    ledgers: Vec<NamespacedLedger>,
+}
+
+impl MultiLedgerRead {
+    pub fn new(ledgers: Vec<NamespacedLedger>) -> VcxCoreResult<Self> {
+        if ledgers.is_empty() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                "At least one ledger is required",
+            ));
+        }
+        for (i, ledger) in ledgers.iter().enumerate() {
+            if ledgers[..i].iter().any(|other| other.namespace == ledger.namespace) {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidConfiguration,
+                    format!("Ledger of namespace {} given twice", ledger.namespace),
+                ));
+            }
+        }
+        Ok(Self { ledgers })
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.ledgers.iter().map(|ledger| ledger.namespace.as_str())
+    }
+
+    fn ledger(&self, namespace: &str) -> VcxCoreResult<&NamespacedLedger> {
+        self.ledgers
+            .iter()
+            .find(|ledger| ledger.namespace == namespace)
+            .ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidLedgerResponse,
+                    format!("No ledger configured for did:indy namespace {}", namespace),
+                )
+            })
+    }
+
+    fn default_ledger(&self) -> &NamespacedLedger {
+        &self.ledgers[0]
+    }
+
+    /// The ledger the ID is expected on: the ledger of its namespace, or the first ledger when it
+    /// is unqualified, with the ID the ledger knows it by.
+    fn home_ledger<'a>(&'a self, id: &str) -> VcxCoreResult<(&'a NamespacedLedger, String)> {
+        match parse_qualified(id)? {
+            Some((namespace, unqualified)) => Ok((self.ledger(namespace)?, unqualified)),
+            None => Ok((self.default_ledger(), id.to_string())),
+        }
+    }
+
+    /// Reads the ID from the ledger of its namespace, or from the first ledger having it when it
+    /// is unqualified.
+    async fn route<'a, T, F, Fut>(&'a self, id: &'a str, read: F) -> VcxCoreResult<T>
+    where
+        F: Fn(&'a NamespacedLedger, String) -> Fut,
+        Fut: Future<Output = VcxCoreResult<Option<T>>>,
+    {
+        match parse_qualified(id)? {
+            Some(_) => {
+                let (ledger, unqualified) = self.home_ledger(id)?;
+                read(ledger, unqualified).await?.ok_or_else(|| not_found(id))
+            }
+            None => {
+                let mut last_err = None;
+                for ledger in &self.ledgers {
+                    match read(ledger, id.to_string()).await {
+                        Ok(Some(found)) => return Ok(found),
+                        Ok(None) => continue,
+                        Err(err) => {
+                            debug!(
+                                "MultiLedgerRead::route >> reading {} from ledger {} failed: {}",
+                                id, ledger.namespace, err
+                            );
+                            last_err = Some(err);
+                        }
+                    }
+                }
+                Err(last_err.unwrap_or_else(|| not_found(id)))
+            }
+        }
+    }
+}
+
+fn not_found(id: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::LedgerItemNotFound,
+        format!("{} not found on any ledger", id),
+    )
+}
+
+fn invalid_id(id: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidInput,
+        format!("Invalid did:indy identifier {}", id),
+    )
+}
+
+/// Splits a `did:indy` DID or object ID into its namespace and the unqualified ID the ledger of
+/// the namespace knows it by. Returns `None` for IDs which are not `did:indy` ones.
+pub fn parse_qualified(id: &str) -> VcxCoreResult<Option<(&str, String)>> {
+    let qualified = match id.strip_prefix(DID_INDY_PREFIX) {
+        Some(qualified) => qualified,
+        None => return Ok(None),
+    };
+    let (did, object) = match qualified.split_once(OBJECT_PATH) {
+        Some((did, object)) => (did, Some(object)),
+        None => (qualified, None),
+    };
+    let (namespace, did) = did.rsplit_once(':').ok_or_else(|| invalid_id(id))?;
+    if namespace.is_empty() || did.is_empty() || namespace.split(':').count() > 2 {
+        return Err(invalid_id(id));
+    }
+
+    let unqualified = match object.map(|object| object.split('/').collect::<Vec<_>>()).as_deref() {
+        None => did.to_string(),
+        Some(["SCHEMA", name, version]) => format!("{}:2:{}:{}", did, name, version),
+        Some(["CLAIM_DEF", schema_seq_no, tag]) => format!("{}:3:CL:{}:{}", did, schema_seq_no, tag),
+        Some(["REV_REG_DEF", schema_seq_no, cred_def_tag, tag]) => format!(
+            "{}:4:{}:3:CL:{}:{}:CL_ACCUM:{}",
+            did, did, schema_seq_no, cred_def_tag, tag
+        ),
+        Some(_) => return Err(invalid_id(id)),
+    };
+    Ok(Some((namespace, unqualified)))
+}
+
+/// Whether the reply of a `GET_NYM` or `GET_ATTRIB` has the data asked for.
+fn reply_has_data(reply: &str) -> bool {
+    serde_json::from_str::<Value>(reply)
+        .map(|reply| !reply["result"]["data"].is_null())
+        .unwrap_or(true)
+}
+
+/// Turns the "not found" errors of anoncreds reads into `None`, to look further.
+fn found<T>(result: VcxCoreResult<T>) -> VcxCoreResult<Option<T>> {
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::LedgerItemNotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[async_trait]
+impl IndyLedgerRead for MultiLedgerRead {
+    async fn get_attr(&self, target_did: &str, attr_name: &str) -> VcxCoreResult<String> {
+        let routed = self
+            .route(target_did, |ledger, did| async move {
+                let reply = ledger.indy_ledger_read.get_attr(&did, attr_name).await?;
+                Ok(Some(reply).filter(|reply| reply_has_data(reply)))
+            })
+            .await;
+        match routed {
+            // the reply without data, as a single ledger gives it
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::LedgerItemNotFound => {
+                let (ledger, did) = self.home_ledger(target_did)?;
+                ledger.indy_ledger_read.get_attr(&did, attr_name).await
+            }
+            routed => routed,
+        }
+    }
+
+    async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+        let routed = self
+            .route(did, |ledger, did| async move {
+                let reply = ledger.indy_ledger_read.get_nym(&did).await?;
+                Ok(Some(reply).filter(|reply| reply_has_data(reply)))
+            })
+            .await;
+        match routed {
+            // the reply without data, as a single ledger gives it
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::LedgerItemNotFound => {
+                let (ledger, did) = self.home_ledger(did)?;
+                ledger.indy_ledger_read.get_nym(&did).await
+            }
+            routed => routed,
+        }
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+        self.default_ledger().indy_ledger_read.get_txn_author_agreement().await
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.default_ledger()
+            .indy_ledger_read
+            .get_ledger_txn(seq_no, submitter_did)
+            .await
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerRead for MultiLedgerRead {
+    async fn get_schema(&self, schema_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.route(schema_id, |ledger, id| async move {
+            found(ledger.anoncreds_ledger_read.get_schema(&id, submitter_did).await)
+        })
+        .await
+    }
+
+    async fn get_cred_def(&self, cred_def_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.route(cred_def_id, |ledger, id| async move {
+            found(ledger.anoncreds_ledger_read.get_cred_def(&id, submitter_did).await)
+        })
+        .await
+    }
+
+    async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+        self.route(rev_reg_id, |ledger, id| async move {
+            found(ledger.anoncreds_ledger_read.get_rev_reg_def_json(&id).await)
+        })
+        .await
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, String, u64)> {
+        self.route(rev_reg_id, |ledger, id| async move {
+            found(ledger.anoncreds_ledger_read.get_rev_reg_delta_json(&id, from, to).await)
+        })
+        .await
+    }
+
+    async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+        self.route(rev_reg_id, |ledger, id| async move {
+            found(ledger.anoncreds_ledger_read.get_rev_reg(&id, timestamp).await)
+        })
+        .await
+    }
+}
+
+/// Writes to the ledger of a single `did:indy` namespace, the first one of a [`MultiLedgerRead`].
+///
+/// `did:indy` DIDs and object IDs of the namespace are converted to the unqualified IDs the ledger
+/// knows, while the ones of other namespaces are rejected rather than written to the wrong ledger.
+/// Unqualified DIDs and IDs are written as they are.
+pub struct MultiLedgerWrite {
+    namespace: String,
+    indy_ledger_write: Arc<dyn IndyLedgerWrite>,
+    anoncreds_ledger_write: Arc<dyn AnoncredsLedgerWrite>,
+}
+
+impl MultiLedgerWrite {
+    pub fn new<T>(namespace: &str, ledger_write: Arc<T>) -> Self
+    where
+        T: IndyLedgerWrite + AnoncredsLedgerWrite + 'static,
+    {
+        Self {
+            namespace: namespace.to_string(),
+            indy_ledger_write: ledger_write.clone(),
+            anoncreds_ledger_write: ledger_write,
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The ID the ledger knows the DID or object by, unless it is of another namespace.
+    fn local_id(&self, id: &str) -> VcxCoreResult<String> {
+        match parse_qualified(id)? {
+            None => Ok(id.to_string()),
+            Some((namespace, unqualified)) if namespace == self.namespace => Ok(unqualified),
+            Some((namespace, _)) => Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!(
+                    "{} is of did:indy namespace {}, writes go to the ledger of namespace {}",
+                    id, namespace, self.namespace
+                ),
+            )),
+        }
+    }
+
+    /// The object JSON with its `id` converted as by [`Self::local_id`].
+    fn local_object(&self, object_json: &str) -> VcxCoreResult<String> {
+        let mut object: Value = serde_json::from_str(object_json).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Unable to parse ledger object {}: {}", object_json, err),
+            )
+        })?;
+        let id = match object["id"].as_str() {
+            Some(id) if parse_qualified(id)?.is_some() => self.local_id(id)?,
+            _ => return Ok(object_json.to_string()),
+        };
+        object["id"] = Value::String(id);
+        Ok(object.to_string())
+    }
+}
+
+impl Debug for MultiLedgerWrite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiLedgerWrite")
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl IndyLedgerWrite for MultiLedgerWrite {
+    async fn publish_nym(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let (submitter_did, target_did) = (self.local_id(submitter_did)?, self.local_id(target_did)?);
+        self.indy_ledger_write
+            .publish_nym(&submitter_did, &target_did, verkey, data, role)
+            .await
+    }
+
+    async fn set_endorser(&self, submitter_did: &str, request: &str, endorser: &str) -> VcxCoreResult<String> {
+        let (submitter_did, endorser) = (self.local_id(submitter_did)?, self.local_id(endorser)?);
+        self.indy_ledger_write
+            .set_endorser(&submitter_did, request, &endorser)
+            .await
+    }
+
+    async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
+        let endorser_did = self.local_id(endorser_did)?;
+        self.indy_ledger_write
+            .endorse_transaction(&endorser_did, request_json)
+            .await
+    }
+
+    async fn sign_endorsed_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        let endorser_did = self.local_id(endorser_did)?;
+        self.indy_ledger_write
+            .sign_endorsed_transaction(&endorser_did, request_json)
+            .await
+    }
+
+    async fn submit_endorsed_transaction(&self, request_json: &str) -> VcxCoreResult<()> {
+        self.indy_ledger_write.submit_endorsed_transaction(request_json).await
+    }
+
+    async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
+        let target_did = self.local_id(target_did)?;
+        self.indy_ledger_write.add_attr(&target_did, attrib_json).await
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerWrite for MultiLedgerWrite {
+    async fn publish_schema(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        let (schema_json, submitter_did) = (self.local_object(schema_json)?, self.local_id(submitter_did)?);
+        let endorser_did = endorser_did.map(|did| self.local_id(&did)).transpose()?;
+        self.anoncreds_ledger_write
+            .publish_schema(&schema_json, &submitter_did, endorser_did)
+            .await
+    }
+
+    async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let (cred_def_json, submitter_did) = (self.local_object(cred_def_json)?, self.local_id(submitter_did)?);
+        self.anoncreds_ledger_write
+            .publish_cred_def(&cred_def_json, &submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let (rev_reg_def, submitter_did) = (self.local_object(rev_reg_def)?, self.local_id(submitter_did)?);
+        self.anoncreds_ledger_write
+            .publish_rev_reg_def(&rev_reg_def, &submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        let (rev_reg_id, submitter_did) = (self.local_id(rev_reg_id)?, self.local_id(submitter_did)?);
+        self.anoncreds_ledger_write
+            .publish_rev_reg_delta(&rev_reg_id, rev_reg_entry_json, &submitter_did)
+            .await
+    }
+
+    async fn prepare_schema_for_endorsement(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let (schema_json, submitter_did) = (self.local_object(schema_json)?, self.local_id(submitter_did)?);
+        let endorser_did = self.local_id(endorser_did)?;
+        self.anoncreds_ledger_write
+            .prepare_schema_for_endorsement(&schema_json, &submitter_did, &endorser_did)
+            .await
+    }
+
+    async fn prepare_cred_def_for_endorsement(
+        &self,
+        cred_def_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let (cred_def_json, submitter_did) = (self.local_object(cred_def_json)?, self.local_id(submitter_did)?);
+        let endorser_did = self.local_id(endorser_did)?;
+        self.anoncreds_ledger_write
+            .prepare_cred_def_for_endorsement(&cred_def_json, &submitter_did, &endorser_did)
+            .await
+    }
+
+    async fn prepare_rev_reg_def_for_endorsement(
+        &self,
+        rev_reg_def: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let (rev_reg_def, submitter_did) = (self.local_object(rev_reg_def)?, self.local_id(submitter_did)?);
+        let endorser_did = self.local_id(endorser_did)?;
+        self.anoncreds_ledger_write
+            .prepare_rev_reg_def_for_endorsement(&rev_reg_def, &submitter_did, &endorser_did)
+            .await
+    }
+
+    async fn prepare_rev_reg_delta_for_endorsement(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+        endorser_did: &str,
+    ) -> VcxCoreResult<String> {
+        let (rev_reg_id, submitter_did) = (self.local_id(rev_reg_id)?, self.local_id(submitter_did)?);
+        let endorser_did = self.local_id(endorser_did)?;
+        self.anoncreds_ledger_write
+            .prepare_rev_reg_delta_for_endorsement(&rev_reg_id, rev_reg_entry_json, &submitter_did, &endorser_did)
+            .await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_qualified_did() {
+        assert_eq!(
+            parse_qualified("did:indy:sovrin:F72i3Y3Q4i466efjYJYCHM").unwrap(),
+            Some(("sovrin", "F72i3Y3Q4i466efjYJYCHM".to_string()))
+        );
+        assert_eq!(
+            parse_qualified("did:indy:sovrin:staging:F72i3Y3Q4i466efjYJYCHM").unwrap(),
+            Some(("sovrin:staging", "F72i3Y3Q4i466efjYJYCHM".to_string()))
+        );
+        assert_eq!(parse_qualified("F72i3Y3Q4i466efjYJYCHM").unwrap(), None);
+        assert_eq!(parse_qualified("did:sov:F72i3Y3Q4i466efjYJYCHM").unwrap(), None);
+        assert!(parse_qualified("did:indy:F72i3Y3Q4i466efjYJYCHM").is_err());
+        assert!(parse_qualified("did:indy:a:b:c:F72i3Y3Q4i466efjYJYCHM").is_err());
+    }
+
+    #[test]
+    fn test_parse_qualified_object_ids() {
+        let did = "did:indy:bcovrin:test:F72i3Y3Q4i466efjYJYCHM";
+        assert_eq!(
+            parse_qualified(&format!("{}/anoncreds/v0/SCHEMA/npdb/4.3.4", did)).unwrap(),
+            Some(("bcovrin:test", "F72i3Y3Q4i466efjYJYCHM:2:npdb:4.3.4".to_string()))
+        );
+        assert_eq!(
+            parse_qualified(&format!("{}/anoncreds/v0/CLAIM_DEF/56495/npdb", did)).unwrap(),
+            Some(("bcovrin:test", "F72i3Y3Q4i466efjYJYCHM:3:CL:56495:npdb".to_string()))
+        );
+        assert_eq!(
+            parse_qualified(&format!("{}/anoncreds/v0/REV_REG_DEF/56495/npdb/TAG1", did)).unwrap(),
+            Some((
+                "bcovrin:test",
+                "F72i3Y3Q4i466efjYJYCHM:4:F72i3Y3Q4i466efjYJYCHM:3:CL:56495:npdb:CL_ACCUM:TAG1".to_string()
+            ))
+        );
+        assert!(parse_qualified(&format!("{}/anoncreds/v0/NYM/56495", did)).is_err());
+    }
+
+    #[cfg(all(feature = "in_memory_ledger", feature = "in_memory_wallet"))]
+    mod routing {
+        use serde_json::json;
+
+        use super::super::*;
+        use crate::ledger::base_ledger::{AnoncredsLedgerWrite, IndyLedgerWrite};
+        use crate::ledger::in_memory_ledger::{InMemoryLedger, InMemoryLedgerWrite, LedgerRole};
+        use crate::wallet::base_wallet::BaseWallet;
+        use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+        /// A ledger with a trustee, which wrote a schema of the given name, and a NYM without role.
+        async fn ledger_with_schema(
+            wallet: &Arc<dyn BaseWallet>,
+            seed: &str,
+            schema_name: &str,
+        ) -> (Arc<InMemoryLedger>, String) {
+            let ledger = Arc::new(InMemoryLedger::new());
+            let (did, verkey) = wallet.create_and_store_my_did(Some(seed), None).await.unwrap();
+            ledger
+                .add_genesis_nym(&did, &verkey, Some(LedgerRole::Trustee))
+                .unwrap();
+            let schema = json!({ "ver": "1.0", "id": "", "name": schema_name, "version": "1.0", "attrNames": ["age"] });
+            InMemoryLedgerWrite::new(ledger.clone(), wallet.clone())
+                .publish_schema(&schema.to_string(), &did, None)
+                .await
+                .unwrap();
+            (ledger, did)
+        }
+
+        #[tokio::test]
+        async fn test_reads_are_routed_by_namespace() {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+            let (sovrin, sovrin_did) = ledger_with_schema(&wallet, "000000000000000000000000Trustee1", "sovrin").await;
+            let (bcovrin, bcovrin_did) =
+                ledger_with_schema(&wallet, "000000000000000000000000Trustee2", "bcovrin").await;
+            let (other_did, other_verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            InMemoryLedgerWrite::new(bcovrin.clone(), wallet.clone())
+                .publish_nym(&bcovrin_did, &other_did, Some(&other_verkey), None, None)
+                .await
+                .unwrap();
+
+            let ledger = MultiLedgerRead::new(vec![
+                NamespacedLedger::new("sovrin", sovrin),
+                NamespacedLedger::new("bcovrin:test", bcovrin),
+            ])
+            .unwrap();
+
+            let schema_id = format!("did:indy:bcovrin:test:{}/anoncreds/v0/SCHEMA/bcovrin/1.0", bcovrin_did);
+            let schema: Value = serde_json::from_str(&ledger.get_schema(&schema_id, None).await.unwrap()).unwrap();
+            assert_eq!(schema["name"], "bcovrin");
+            let schema_id = format!("did:indy:sovrin:{}/anoncreds/v0/SCHEMA/bcovrin/1.0", bcovrin_did);
+            let err = ledger.get_schema(&schema_id, None).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxCoreErrorKind::LedgerItemNotFound);
+            let schema_id = format!("did:indy:indicio:{}/anoncreds/v0/SCHEMA/bcovrin/1.0", bcovrin_did);
+            assert!(ledger.get_schema(&schema_id, None).await.is_err());
+
+            let schema_id = format!("{}:2:bcovrin:1.0", bcovrin_did);
+            let schema: Value = serde_json::from_str(&ledger.get_schema(&schema_id, None).await.unwrap()).unwrap();
+            assert_eq!(schema["name"], "bcovrin");
+            let schema_id = format!("{}:2:sovrin:1.0", sovrin_did);
+            let schema: Value = serde_json::from_str(&ledger.get_schema(&schema_id, None).await.unwrap()).unwrap();
+            assert_eq!(schema["name"], "sovrin");
+
+            let nym: Value = serde_json::from_str(&ledger.get_nym(&other_did).await.unwrap()).unwrap();
+            assert!(!nym["result"]["data"].is_null());
+            let qualified_did = format!("did:indy:sovrin:{}", other_did);
+            let nym: Value = serde_json::from_str(&ledger.get_nym(&qualified_did).await.unwrap()).unwrap();
+            assert!(nym["result"]["data"].is_null());
+        }
+
+        #[tokio::test]
+        async fn test_writes_of_other_namespaces_are_rejected() {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+            let (sovrin, trustee_did) = ledger_with_schema(&wallet, "000000000000000000000000Trustee1", "sovrin").await;
+            let ledger_write = MultiLedgerWrite::new(
+                "sovrin",
+                Arc::new(InMemoryLedgerWrite::new(sovrin.clone(), wallet.clone())),
+            );
+            let ledger_read = MultiLedgerRead::new(vec![NamespacedLedger::new("sovrin", sovrin)]).unwrap();
+
+            let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let err = ledger_write
+                .publish_nym(
+                    &format!("did:indy:bcovrin:test:{}", trustee_did),
+                    &did,
+                    Some(&verkey),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+            let nym: Value = serde_json::from_str(&ledger_read.get_nym(&did).await.unwrap()).unwrap();
+            assert!(nym["result"]["data"].is_null());
+
+            ledger_write
+                .publish_nym(
+                    &format!("did:indy:sovrin:{}", trustee_did),
+                    &did,
+                    Some(&verkey),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            let nym: Value = serde_json::from_str(&ledger_read.get_nym(&did).await.unwrap()).unwrap();
+            assert!(!nym["result"]["data"].is_null());
+
+            let schema = json!({
+                "ver": "1.0",
+                "id": format!("did:indy:bcovrin:test:{}/anoncreds/v0/SCHEMA/other/1.0", trustee_did),
+                "name": "other",
+                "version": "1.0",
+                "attrNames": ["age"]
+            });
+            let err = ledger_write
+                .publish_schema(&schema.to_string(), &trustee_did, None)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+            let schema_id = format!("{}:2:other:1.0", trustee_did);
+            assert!(ledger_read.get_schema(&schema_id, None).await.is_err());
+        }
+    }
+}
@@ -3,6 +3,7 @@ use indy_vdr::pool::PreparedRequest;
 
 use crate::errors::error::VcxCoreResult;
 
+pub mod state_proof;
 #[cfg(feature = "modular_libs")]
 pub mod vdr_ledger;
 #[cfg(feature = "vdr_proxy_ledger")]
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use indy_vdr::pool::PreparedRequest;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::ledger::state_proof::{StateProofCheck, StateProofVerifier};
+
+use super::RequestSubmitter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateProofMode {
+    /// Replies to reads which can be proven are rejected unless their state proof is valid.
+    Strict,
+    /// Replies with an invalid state proof are rejected, while replies without one are passed on.
+    Lenient,
+}
+
+/// Submits the requests through another submitter, verifying the state proofs of the replies to
+/// reads, so they can be trusted even when the submitter is not, as with a remote proxy.
+pub struct StateProofSubmitter<T>
+where
+    T: RequestSubmitter + Send + Sync,
+{
+    request_submitter: Arc<T>,
+    verifier: StateProofVerifier,
+    mode: StateProofMode,
+}
+
+impl<T> StateProofSubmitter<T>
+where
+    T: RequestSubmitter + Send + Sync,
+{
+    pub fn new(request_submitter: Arc<T>, verifier: StateProofVerifier, mode: StateProofMode) -> Self {
+        Self {
+            request_submitter,
+            verifier,
+            mode,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> RequestSubmitter for StateProofSubmitter<T>
+where
+    T: RequestSubmitter + Send + Sync,
+{
+    async fn submit(&self, request: PreparedRequest) -> VcxCoreResult<String> {
+        let request_json = request.req_json.clone();
+        let reply = self.request_submitter.submit(request).await?;
+        match self.verifier.verify(&request_json, &reply)? {
+            StateProofCheck::Missing if self.mode == StateProofMode::Strict => Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidLedgerResponse,
+                "Reply to read has no state proof",
+            )),
+            StateProofCheck::Missing => {
+                warn!("Reply to read has no state proof, passing it on unverified");
+                Ok(reply)
+            }
+            StateProofCheck::Verified | StateProofCheck::NotApplicable => Ok(reply),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::collections::HashMap;
+
+    use indy_vdr::pool::ProtocolVersion;
+    use serde_json::Value;
+
+    use super::*;
+
+    // GET_SCHEMA read and its reply from a pool of the nodes below, which runs Node 1.3.
+    const GET_SCHEMA_REQUEST: &str = r#"{"reqId":1526055830436897605,"identifier":"2hoqvcwupRTUNkXn6ArYzs","operation":{"type":"107","dest":"2hoqvcwupRTUNkXn6ArYzs","data":{"name":"test-licence","version":"4.4.4"}},"protocolVersion":1}"#;
+    const GET_SCHEMA_REPLY: &str = r#"{"result":{"state_proof":{"multi_signature":{"value":{"state_root_hash":"81s3UgVN47doEdWzg897EssC5aCMZ5g2bHAFHXgJyUGe","pool_state_root_hash":"DuhjUiR6QDsT4X3KFTGHgPnaCCTTVMhmmA8uRwkkhDwA","txn_root_hash":"49dVmci81excka4Ff17uUPY6NyK5Fkyb7mxKQxHXGUXT","timestamp":1526055830,"ledger_id":1},"signature":"R5DPg3mctNrPwbzb7qCbo5LkTC2nnLj1jvuRSZDypD4FdFoH5eHp3v5vYvQaLkGfPnG9bmPwXgdhwrybsrTm9RvXj15MYxcfnBDACCjFCeAxjEKaWU6ebyJKi8UsGPiQzJVgVNaD6yvLNtvwzn6r9UhZ3wFVjWRu3M9sBfLZPCTE1m","participants":["Node1","Node4","Node3"]},"root_hash":"81s3UgVN47doEdWzg897EssC5aCMZ5g2bHAFHXgJyUGe","proof_nodes":"+QiL+FyFIC40LjS4VPhSuFB7ImxzbiI6MjQ3MSwibHV0IjoxNTI2MDU1ODMwLCJ2YWwiOnsiYXR0cl9uYW1lcyI6WyJhZ2UiLCJoZWlnaHQiLCJuYW1lIiwic2V4Il19feIWoKS+yLqQrrlh0AHdXeEgErYxfmhpVzaJ+U4XV9IhNgFz+DmXAIb3F2Y3d1cFJUVU5rWG42QXJZenM6Cg0ey22y1Sw5R0Wr517KDVTtxr1h+dgvtZsXyDnQ5IEjL5AbGgT71lC6jsMTHl3Pa1Rj1nmP8643p6YpwARnmr8pcYPVSg0mYAZNq3qZ1kBfzZ4IkJ9JPoSY9sNt5pSiPy1Sesoy6giTaZI9nDaWnk1Z5+KCJl+Go7TXmmgTHqURkcvCaGJvmAoNyumIZbkdqKaRnLJHMvQHk66iXOKc0eO9fAg0S6IweyoKOlTCQeHHpiiRIqOWf+ZiyoE1RzGLYwYv\/ZT92bj5TUoDYsQFMS2a6ItdDw7pD7FMbhAFy50WgvpLtc5N3kUoPnoEFpsFh4\/nnPQ9a7uJuO1aOF\/0eLZZPbk61GqZrhuH1goPfPWQgWLjM3nhcoyL5\/2KsHiwGTaXLKfB7UvL5Yk3u9oAoTTzqKqJFLJXTlBzO3RoFrsMHHRV7XiX\/ZNGqkeZLdoP2ODKKueuDUgH+081Su3N5ByHvgMGaU9dg+JxJCH351oL8QgC6a78sPnRhjdbUStwqlPP4TOGrPyYC4FAYtN\/A9gKBH\/5JTxilhi+ZrkUrfkkO2KYas7GIQW+HUecA6Ng0sH4Cggh7qzc1C\/0n1Os53lZb9INU33xFXaUSGn9E8NKkFUoyA+FGAgKA8XWevrjMeCJVdfpqrFMz3kn4gCHY9zstqAaYG7QRex6Cug+aEkajVK5kcv41wZi\/8t5dMKsZLmiDeQEkYUiForYCAgICAgICAgICAgID5AXGgdHHcBnO5LERbEZl44uTuxdCRy3YCHcyvM\/iroJzNdACgz7b3Y6RqprH64dbOJiWxR13LjcOakD6+ZdicWWEXM8yg\/6deXgumdcqhse0H2bS5U411JnaRZBYIEMPp1xkFGtKgaNnmuK+WwSL9pcmIZFJI5osNECREaxf7K1eOEXc0RqKgTnhZ9UgjsuSSGru0N3GIAy8BMivi0gX\/AWH8qQDpstCgbNiTG4lUqf96EyWojSovUtrz+XUpH+8N2+QFCrKpIOygv2R1F2JXyEqh2AXalY2VldRciIPsr7EWFAabOG4RKzugyDzJ72wkDeDMP19UfN\/lfpDFo9XnSIV4iHHjgz3QU4igGQ0k1eP\/uLZvThw6V0SemmquhaNp77pRQoQwMEtkdVqgqy7KI1QOQoBZgJjBqJ\/i56QfzUF1gKUu6voQvyQf3fegLUg5S0QHBY4ldJxdLdWpVfksMNnDVoeIOk4YaoY9TsiAgICAgIDkggA6oEwv9bhu5R8nrnUt4Yx3+oB0SXXXuZBO3b28qAICCOSd7owXN0LWxpY2VuY2U6OgOFNinrJ5T+l1MMwSVWJf5WOoYjFNf\/XZ3zVKmxgUnHP4sYCAgKC0dldFCLyH+sCHRrFr5phR9NjdPw6AKv0zlZKTn3x2OaDTgNSzkxwEcmAnqqgFicXYnk+U1UR9Zr5GW\/kNcr\/QpaBSnbcCh57QsvePUsmWURFq3G2WYfLinvZvbN7djK1eJaAZ1CRb5ndo+3SSaqOyg0hBd1UVFWwvDv9WxWwXaoEz4qBt6QgRVzUf5A9QoRzs\/BqQN3HRYS9mbvGzr8i8gQBsvoCAgICAgICAgPhRgICAgICAoMmjsuJicY9FzZqlzDRPdp3\/d6e2ete+uTUuGlLZrfEkgICAgICAgKA+q5fnel\/\/ZFjKfWOn3PScO8k6Qdki79w+y2ZACYUTZoCA+FGAgICAgKC4hIGO4DPcaKYmRpzNYUAXu57xExF9yyxyGD03dg3nCoCAgKBrY\/1SacMf6LNQ\/VBpr9p\/hAkk1AY6XrpoAxZW4aaOrYCAgICAgID4kYCgj6RlWm2C5oUBN0vbyaI+Hxh7AzbmI1SYINZ8bGj7FHygSwYrTkPDB\/SU3Yi8+UJW60leF09Xf4NDHIxnkH+XR8egHPodFdkcnKxOiLXtlpOuzM\/THPYfTQ9xWanloSw8uU6g1iRB91Rq8zT7dTIl1FIPXg0ovtW\/gktKvMVp\/XNxoOOAgICAgICAgICAgID5AhGgZBqeju\/DK9w6gsSRyeuChMfPiz3O5CJOt05Cwx6+Zq6g\/qAj00WnUVvBv214PQY\/n6Hz3ge+kdXc7bRq2zC31cCgK9Hxi\/K6rqzGHb2hV1UhJwZV9RZh9kW\/ROGpoNzP26qgWiSIFqr4VJmXkDDFgkHrnTBOnefFNXuDTl9ug1V3K86gsnM3Tnk5\/vzBW5+41I7cwvW1\/77F2bdmGlnc0xmx4TegMZvW8Kl\/KJS0Z3NItqp8zhKrxs+VwSHasFQG6ySr\/ROgPZToxrJxpSH9egAT4t\/cIXgXmeM9Lgqnk7ZWXWPcbN+g0kXS+Xw39zJ5DUOqKiMIKl3hcaVTIgnAEmke90PmIo+gMKg2q8Gla7GKLXYh5ZBAvGaZM0zm8zZEsu1WRLYePSSgqvwhtqLvuNKkBdPMSDAO28IarXiYXDhae80T+EiDK32gmO6lIx+XA5ydY7qyl\/c41qlWvyLmJIyhd71wOGS2xhmgwDrBtPzkPBdieH2gS50vDXWH7zUgksxbCX4lBbnlJV6gS9n9ZPT3Q7ICigKtf7\/6y383dDp\/junzRl9OfxsgYEKgtc5LYJgXPhA6bX7ma\/iOGn3C6DOpahza9mEQxCb5APCgUKah9cJa5x9dtgVS6rymjOoNTbrSuw+AxvrqhZgYgI6gE+UVSRl8WVc4BZyM+zE9W5OEO58lpO97\/pTn2PDkiB+A"},"type":"107","reqId":1526055830436897605,"seqNo":2471,"data":{"version":"4.4.4","attr_names":["age","height","name","sex"],"name":"test-licence"},"txnTime":1526055830,"dest":"2hoqvcwupRTUNkXn6ArYzs","identifier":"2hoqvcwupRTUNkXn6ArYzs"},"op":"REPLY"}"#;
+
+    struct ReplySubmitter(String);
+
+    #[async_trait]
+    impl RequestSubmitter for ReplySubmitter {
+        async fn submit(&self, _request: PreparedRequest) -> VcxCoreResult<String> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn _state_proof_submitter(reply: String) -> StateProofSubmitter<ReplySubmitter> {
+        let node_bls_keys = HashMap::from([
+            ("Node1", "4N8aUNHSgjQVgkpm8nhNEfDf6txHznoYREg9kirmJrkivgL4oSEimFF6nsQ6M41QvhM2Z33nves5vfSn9n1UwNFJBYtWVnHYMATn76vLuL3zU88KyeAYcHfsih3He6UHcXDxcaecHVz6jhCYz1P2UZn2bDVruL5wXpehgBfBaLKm3Ba"),
+            ("Node2", "37rAPpXVoxzKhz7d9gkUe52XuXryuLXoM6P6LbWDB7LSbG62Lsb33sfG7zqS8TK1MXwuCHj1FKNzVpsnafmqLG1vXN88rt38mNFs9TENzm4QHdBzsvCuoBnPH7rpYYDo9DZNJePaDvRvqJKByCabubJz3XXKbEeshzpz4Ma5QYpJqjk"),
+            ("Node3", "3WFpdbg7C5cnLYZwFZevJqhubkFALBfCBBok15GdrKMUhUjGsk3jV6QKj6MZgEubF7oqCafxNdkm7eswgA4sdKTRc82tLGzZBd6vNqU8dupzup6uYUf32KTHTPQbuUM8Yk4QFXjEf2Usu2TJcNkdgpyeUSX42u5LqdDDpNSWUK5deC5"),
+            ("Node4", "2zN3bHM1m4rLz54MJHYSwvqzPchYp8jkHswveCLAEJVcX6Mm1wHQD1SkPYMzUDTZvWvhuE6VNAkK3KxVeEmsanSmvjVkReDeBEMxeDaayjcZjFGPydyey1qxBHmTvAnBKoPydvuTAqx5f7YNNRAdeLmUi99gERUU7TD8KfAa6MpQ9bw"),
+        ])
+        .into_iter()
+        .map(|(alias, bls_key)| (alias.to_string(), bls_key.to_string()))
+        .collect();
+        let verifier = StateProofVerifier::new(node_bls_keys)
+            .unwrap()
+            .with_protocol_version(ProtocolVersion::Node1_3);
+        StateProofSubmitter::new(Arc::new(ReplySubmitter(reply)), verifier, StateProofMode::Strict)
+    }
+
+    fn _tampered_reply(tamper: impl FnOnce(&mut Value)) -> String {
+        let mut reply: Value = serde_json::from_str(GET_SCHEMA_REPLY).unwrap();
+        tamper(&mut reply["result"]);
+        reply.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_submit_passes_on_proven_reply() {
+        let submitter = _state_proof_submitter(GET_SCHEMA_REPLY.to_string());
+        let request = PreparedRequest::from_request_json(GET_SCHEMA_REQUEST).unwrap();
+        assert_eq!(submitter.submit(request).await.unwrap(), GET_SCHEMA_REPLY);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_tampered_reply() {
+        let tampered_replies = [
+            _tampered_reply(|result| result["data"]["attr_names"] = json!(["age", "height", "name"])),
+            _tampered_reply(|result| result["seqNo"] = json!(2470)),
+            _tampered_reply(|result| {
+                result["state_proof"]["multi_signature"]["value"]["timestamp"] = json!(1526055831)
+            }),
+        ];
+        for reply in tampered_replies {
+            let submitter = _state_proof_submitter(reply);
+            let request = PreparedRequest::from_request_json(GET_SCHEMA_REQUEST).unwrap();
+            let err = submitter.submit(request).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_reply_without_state_proof() {
+        let submitter = _state_proof_submitter(_tampered_reply(|result| result["state_proof"] = Value::Null));
+        let request = PreparedRequest::from_request_json(GET_SCHEMA_REQUEST).unwrap();
+        let err = submitter.submit(request).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidLedgerResponse);
+    }
+}
@@ -14,11 +14,12 @@ use tokio::sync::Mutex;
 
 use crate::errors::error::VcxCoreResult;
 
-use super::ResponseCacher;
+use super::{CacheCounters, CacheStatistics, ResponseCacher};
 
 pub struct InMemoryResponseCacher {
     cache: Arc<Mutex<LruCache<String, (String, Instant)>>>,
     config: InMemoryResponseCacherConfig,
+    counters: CacheCounters,
 }
 
 impl InMemoryResponseCacher {
@@ -26,6 +27,7 @@ impl InMemoryResponseCacher {
         Self {
             cache: Arc::new(Mutex::new(LruCache::new(config.capacity()))),
             config,
+            counters: CacheCounters::default(),
         }
     }
 }
@@ -44,6 +46,7 @@ impl ResponseCacher for InMemoryResponseCacher {
 
         let mut cache = self.cache.lock().await;
         cache.put(id, (obj, Instant::now()));
+        self.counters.put();
         Ok(())
     }
 
@@ -65,15 +68,24 @@ impl ResponseCacher for InMemoryResponseCacher {
             Some((obj, timestamp)) => {
                 if timestamp.elapsed() > ttl {
                     cache.pop(&id);
+                    self.counters.expire();
                     Ok(None)
                 } else {
                     let obj: T = serde_json::from_str(obj)?;
+                    self.counters.hit();
                     Ok(Some(obj))
                 }
             }
-            None => Ok(None),
+            None => {
+                self.counters.miss();
+                Ok(None)
+            }
         }
     }
+
+    fn statistics(&self) -> CacheStatistics {
+        self.counters.statistics()
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +170,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_statistics() -> VcxCoreResult<()> {
+        let cacher = InMemoryResponseCacher::new(_cacher_config(Duration::from_millis(1)));
+
+        cacher.put("id1", _test_object()).await?;
+        let _: Option<TestStruct> = cacher.get("id1", Some(_cacher_options(Duration::from_secs(1)))).await?;
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        let _: Option<TestStruct> = cacher.get("id1", None).await?;
+        let _: Option<TestStruct> = cacher.get("id2", None).await?;
+
+        let statistics = cacher.statistics();
+        assert_eq!(statistics.puts, 1);
+        assert_eq!(statistics.hits, 1);
+        assert_eq!(statistics.misses, 2);
+        assert_eq!(statistics.expired, 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_options_ttl_override_global_config_ttl() -> VcxCoreResult<()> {
         let cacher = InMemoryResponseCacher::new(_cacher_config(Duration::from_millis(1)));
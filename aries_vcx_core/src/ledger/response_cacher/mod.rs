@@ -1,5 +1,8 @@
 pub mod in_memory;
 pub mod noop;
+pub mod persistent;
+
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -19,4 +22,106 @@ pub trait ResponseCacher: Send + Sync {
     where
         S: ToString + Send,
         T: Serialize + for<'de> Deserialize<'de> + Send;
+
+    fn statistics(&self) -> CacheStatistics;
+}
+
+/// The kind of ledger object a response is cached for, told apart by the id it is cached under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LedgerObjectKind {
+    Nym,
+    Schema,
+    CredDef,
+    RevRegDef,
+    RevRegState,
+}
+
+impl LedgerObjectKind {
+    /// Schemas, credential definitions and revocation registry definitions are cached under their
+    /// ids, nyms under their DIDs and the states of revocation registries under the ids built by
+    /// [`rev_reg_state_cache_id`].
+    pub fn of(id: &str) -> Self {
+        if id.contains('@') {
+            return Self::RevRegState;
+        }
+        match id.split(':').nth(1) {
+            Some("2") => Self::Schema,
+            Some("3") => Self::CredDef,
+            Some("4") => Self::RevRegDef,
+            _ => Self::Nym,
+        }
+    }
+}
+
+pub fn rev_reg_state_cache_id(rev_reg_id: &str, timestamp: u64) -> String {
+    format!("{rev_reg_id}@{timestamp}")
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStatistics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Lookups of responses found past their time to live, which count as misses too.
+    pub expired: u64,
+    pub puts: u64,
+}
+
+#[derive(Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    expired: AtomicU64,
+    puts: AtomicU64,
+}
+
+impl CacheCounters {
+    fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn expire(&self) {
+        self.expired.fetch_add(1, Ordering::Relaxed);
+        self.miss();
+    }
+
+    fn put(&self) {
+        self.puts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn statistics(&self) -> CacheStatistics {
+        CacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            puts: self.puts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_object_kind_of_id() {
+        assert_eq!(LedgerObjectKind::of("V4SGRU86Z58d6TV7PBUe6f"), LedgerObjectKind::Nym);
+        assert_eq!(
+            LedgerObjectKind::of("V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0"),
+            LedgerObjectKind::Schema
+        );
+        assert_eq!(
+            LedgerObjectKind::of("V4SGRU86Z58d6TV7PBUe6f:3:CL:8:tag"),
+            LedgerObjectKind::CredDef
+        );
+        let rev_reg_id = "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:8:tag:CL_ACCUM:tag";
+        assert_eq!(LedgerObjectKind::of(rev_reg_id), LedgerObjectKind::RevRegDef);
+        assert_eq!(
+            LedgerObjectKind::of(&rev_reg_state_cache_id(rev_reg_id, 1000)),
+            LedgerObjectKind::RevRegState
+        );
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::errors::error::VcxCoreResult;
 
-use super::ResponseCacher;
+use super::{CacheStatistics, ResponseCacher};
 
 pub struct NoopResponseCacher {}
 
@@ -32,4 +32,8 @@ impl ResponseCacher for NoopResponseCacher {
     {
         Ok(None)
     }
+
+    fn statistics(&self) -> CacheStatistics {
+        CacheStatistics::default()
+    }
 }
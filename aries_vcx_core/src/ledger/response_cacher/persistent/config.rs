@@ -0,0 +1,89 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::ledger::response_cacher::LedgerObjectKind;
+
+/// How long the responses for each kind of ledger object stay valid, `None` keeping them valid
+/// forever.
+///
+/// By default schemas, credential definitions and revocation registry definitions, which cannot
+/// change once written, never expire, while nyms expire after 5 minutes and the states of
+/// revocation registries after a minute.
+///
+/// The states of revocation registries are cached for each timestamp asked for, so at most
+/// `max_rev_reg_states` of them are kept, 1000 by default, the oldest being evicted first.
+///
+/// The responses of several ledgers can be cached in the same wallet, such as those of the ledgers
+/// of a [`MultiLedgerRead`](crate::ledger::multi_ledger::MultiLedgerRead), as long as the cacher
+/// of each ledger is given the `namespace` of its ledger.
+#[derive(Clone, Debug)]
+pub struct PersistentResponseCacherConfig {
+    ttls: HashMap<LedgerObjectKind, Option<Duration>>,
+    max_rev_reg_states: usize,
+    namespace: Option<String>,
+}
+
+impl Default for PersistentResponseCacherConfig {
+    fn default() -> Self {
+        let ttls = HashMap::from([
+            (LedgerObjectKind::Nym, Some(Duration::from_secs(300))),
+            (LedgerObjectKind::Schema, None),
+            (LedgerObjectKind::CredDef, None),
+            (LedgerObjectKind::RevRegDef, None),
+            (LedgerObjectKind::RevRegState, Some(Duration::from_secs(60))),
+        ]);
+        Self {
+            ttls,
+            max_rev_reg_states: 1000,
+            namespace: None,
+        }
+    }
+}
+
+impl PersistentResponseCacherConfig {
+    pub fn builder() -> PersistentResponseCacherConfigBuilder {
+        PersistentResponseCacherConfigBuilder::default()
+    }
+
+    pub fn ttl(&self, kind: LedgerObjectKind) -> Option<Duration> {
+        self.ttls.get(&kind).copied().flatten()
+    }
+
+    pub fn max_rev_reg_states(&self) -> usize {
+        self.max_rev_reg_states
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+}
+
+#[derive(Default)]
+pub struct PersistentResponseCacherConfigBuilder {
+    config: PersistentResponseCacherConfig,
+}
+
+impl PersistentResponseCacherConfigBuilder {
+    pub fn ttl(mut self, kind: LedgerObjectKind, ttl: Duration) -> Self {
+        self.config.ttls.insert(kind, Some(ttl));
+        self
+    }
+
+    pub fn no_expiry(mut self, kind: LedgerObjectKind) -> Self {
+        self.config.ttls.insert(kind, None);
+        self
+    }
+
+    pub fn max_rev_reg_states(mut self, max_rev_reg_states: usize) -> Self {
+        self.config.max_rev_reg_states = max_rev_reg_states;
+        self
+    }
+
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.config.namespace = Some(namespace.to_string());
+        self
+    }
+
+    pub fn build(self) -> PersistentResponseCacherConfig {
+        self.config
+    }
+}
@@ -0,0 +1,361 @@
+mod config;
+mod options;
+
+pub use config::*;
+pub use options::*;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error::{AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet};
+
+use super::{CacheCounters, CacheStatistics, LedgerObjectKind, ResponseCacher};
+
+const CACHE_RECORD_TYPE: &str = "VCX_LEDGER_RESPONSE_CACHE";
+const REV_REG_STATE_KIND: &str = "rev_reg_state";
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    response: String,
+    cached_at_ms: u64,
+}
+
+/// Caches the ledger responses as records of a wallet, so they outlive the process.
+///
+/// The responses are cached under their ids, prefixed with the namespace of the ledger if one is
+/// configured, so that the responses of several ledgers can be cached in the same wallet.
+pub struct PersistentResponseCacher {
+    wallet: Arc<dyn BaseWallet>,
+    config: PersistentResponseCacherConfig,
+    counters: CacheCounters,
+    // counted from the wallet on the first state cached
+    rev_reg_states: Mutex<Option<usize>>,
+}
+
+impl PersistentResponseCacher {
+    pub fn new(wallet: Arc<dyn BaseWallet>, config: PersistentResponseCacherConfig) -> Self {
+        Self {
+            wallet,
+            config,
+            counters: CacheCounters::default(),
+            rev_reg_states: Mutex::new(None),
+        }
+    }
+
+    /// Removes the response cached under the id, if any.
+    pub async fn remove(&self, id: &str) -> VcxCoreResult<()> {
+        let removed = self.delete_record(&self.cache_id(id)).await?;
+        if removed && LedgerObjectKind::of(id) == LedgerObjectKind::RevRegState {
+            if let Some(count) = self.lock_rev_reg_states().as_mut() {
+                *count = count.saturating_sub(1);
+            }
+        }
+        Ok(())
+    }
+
+    fn cache_id(&self, id: &str) -> String {
+        match self.config.namespace() {
+            Some(namespace) => format!("{}:{}", namespace, id),
+            None => id.to_string(),
+        }
+    }
+
+    fn rev_reg_state_tags(&self) -> String {
+        json!({ "kind": REV_REG_STATE_KIND, "namespace": self.config.namespace().unwrap_or_default() }).to_string()
+    }
+
+    async fn delete_record(&self, cache_id: &str) -> VcxCoreResult<bool> {
+        match self.wallet.delete_wallet_record(CACHE_RECORD_TYPE, cache_id).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn lock_rev_reg_states(&self) -> MutexGuard<'_, Option<usize>> {
+        self.rev_reg_states.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn rev_reg_state_records(&self) -> VcxCoreResult<Vec<CachedRecord>> {
+        let records = self
+            .wallet
+            .iterate_wallet_records(
+                CACHE_RECORD_TYPE,
+                &self.rev_reg_state_tags(),
+                r#"{"retrieveValue": true}"#,
+            )
+            .await?
+            .collect()
+            .await?;
+
+        records
+            .iter()
+            .map(|record| serde_json::from_str(record).map_err(From::from))
+            .collect()
+    }
+
+    /// Counts a newly cached state of a revocation registry, returning how many are cached.
+    async fn count_rev_reg_state(&self) -> VcxCoreResult<usize> {
+        let counted = *self.lock_rev_reg_states();
+        let count = match counted {
+            Some(count) => count + 1,
+            None => self.rev_reg_state_records().await?.len(),
+        };
+        *self.lock_rev_reg_states() = Some(count);
+        Ok(count)
+    }
+
+    /// Removes the expired states of revocation registries, then the oldest ones past the
+    /// configured maximum, along with a tenth of the maximum so that the states are not all
+    /// loaded again on the next ones cached.
+    async fn evict_rev_reg_states(&self) -> VcxCoreResult<()> {
+        let records = self.rev_reg_state_records().await?;
+
+        let mut states = Vec::with_capacity(records.len());
+        for record in records {
+            let cached: CachedResponse = serde_json::from_str(&record.value)?;
+            states.push((cached.cached_at_ms, record.id));
+        }
+        states.sort_unstable();
+
+        let expired = match self.config.ttl(LedgerObjectKind::RevRegState) {
+            Some(ttl) => {
+                let now = now_ms();
+                states.partition_point(|(cached_at_ms, _)| now.saturating_sub(*cached_at_ms) > ttl.as_millis() as u64)
+            }
+            None => 0,
+        };
+        let max_rev_reg_states = self.config.max_rev_reg_states();
+        let kept = max_rev_reg_states - max_rev_reg_states / 10;
+        let evicted = expired.max(states.len().saturating_sub(kept));
+        for (_, cache_id) in &states[..evicted] {
+            self.delete_record(cache_id).await?;
+        }
+        *self.lock_rev_reg_states() = Some(states.len() - evicted);
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct CachedRecord {
+    id: String,
+    value: String,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl ResponseCacher for PersistentResponseCacher {
+    type Options = PersistentResponseCacherOptions;
+
+    async fn put<S, T>(&self, id: S, obj: T) -> VcxCoreResult<()>
+    where
+        S: ToString + Send,
+        T: Serialize + for<'de> Deserialize<'de> + Send,
+    {
+        let id = id.to_string();
+        let cache_id = self.cache_id(&id);
+        let cached = CachedResponse {
+            response: serde_json::to_string(&obj)?,
+            cached_at_ms: now_ms(),
+        };
+        let value = serde_json::to_string(&cached)?;
+
+        let is_rev_reg_state = LedgerObjectKind::of(&id) == LedgerObjectKind::RevRegState;
+        let tags = is_rev_reg_state.then(|| self.rev_reg_state_tags());
+
+        let added = self
+            .wallet
+            .add_wallet_record(CACHE_RECORD_TYPE, &cache_id, &value, tags.as_deref())
+            .await;
+        let is_new = match added {
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => {
+                self.wallet
+                    .update_wallet_record_value(CACHE_RECORD_TYPE, &cache_id, &value)
+                    .await?;
+                false
+            }
+            result => result.map(|_| true)?,
+        };
+        self.counters.put();
+
+        if is_rev_reg_state && is_new && self.count_rev_reg_state().await? > self.config.max_rev_reg_states() {
+            self.evict_rev_reg_states().await?;
+        }
+        Ok(())
+    }
+
+    async fn get<S, T>(&self, id: S, opt: Option<Self::Options>) -> VcxCoreResult<Option<T>>
+    where
+        S: ToString + Send,
+        T: Serialize + for<'de> Deserialize<'de> + Send,
+    {
+        let id = id.to_string();
+
+        let ttl = match opt.and_then(|opt| opt.ttl()) {
+            Some(ttl) => Some(ttl),
+            None => self.config.ttl(LedgerObjectKind::of(&id)),
+        };
+
+        let value = match self
+            .wallet
+            .get_wallet_record_value(CACHE_RECORD_TYPE, &self.cache_id(&id))
+            .await
+        {
+            Ok(value) => value,
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => {
+                self.counters.miss();
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+        let cached: CachedResponse = serde_json::from_str(&value)?;
+
+        if let Some(ttl) = ttl {
+            if now_ms().saturating_sub(cached.cached_at_ms) > ttl.as_millis() as u64 {
+                self.remove(&id).await?;
+                self.counters.expire();
+                return Ok(None);
+            }
+        }
+
+        let obj: T = serde_json::from_str(&cached.response)?;
+        self.counters.hit();
+        Ok(Some(obj))
+    }
+
+    fn statistics(&self) -> CacheStatistics {
+        self.counters.statistics()
+    }
+}
+
+#[cfg(all(test, feature = "in_memory_wallet"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ledger::response_cacher::rev_reg_state_cache_id;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    const SCHEMA_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:2:schema:1.0";
+    const NYM: &str = "V4SGRU86Z58d6TV7PBUe6f";
+
+    fn _cacher(wallet: &Arc<dyn BaseWallet>, nym_ttl: Duration) -> PersistentResponseCacher {
+        let config = PersistentResponseCacherConfig::builder()
+            .ttl(LedgerObjectKind::Nym, nym_ttl)
+            .build();
+        PersistentResponseCacher::new(wallet.clone(), config)
+    }
+
+    #[tokio::test]
+    async fn test_responses_outlive_the_cacher() -> VcxCoreResult<()> {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+
+        let cacher = _cacher(&wallet, Duration::from_secs(1));
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+        cacher.put(SCHEMA_ID, "updated schema".to_string()).await?;
+        drop(cacher);
+
+        let cacher = _cacher(&wallet, Duration::from_secs(1));
+        let cached: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(cached, Some("updated schema".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ttl_of_object_kind() -> VcxCoreResult<()> {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let cacher = _cacher(&wallet, Duration::from_millis(1));
+
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+        cacher.put(NYM, "nym".to_string()).await?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let schema: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(schema, Some("schema".to_string()));
+        let options = PersistentResponseCacherOptions::builder()
+            .ttl(Duration::from_secs(60))
+            .build();
+        let nym: Option<String> = cacher.get(NYM, Some(options)).await?;
+        assert_eq!(nym, Some("nym".to_string()));
+        let nym: Option<String> = cacher.get(NYM, None).await?;
+        assert_eq!(nym, None);
+        let nym: Option<String> = cacher.get(NYM, None).await?;
+        assert_eq!(nym, None);
+
+        let statistics = cacher.statistics();
+        assert_eq!(statistics.puts, 2);
+        assert_eq!(statistics.hits, 2);
+        assert_eq!(statistics.misses, 2);
+        assert_eq!(statistics.expired, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rev_reg_states_are_bounded() -> VcxCoreResult<()> {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let config = PersistentResponseCacherConfig::builder().max_rev_reg_states(2).build();
+        let cacher = PersistentResponseCacher::new(wallet, config);
+
+        let rev_reg_id = "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:8:tag:CL_ACCUM:tag";
+        for timestamp in 1..=3 {
+            cacher
+                .put(rev_reg_state_cache_id(rev_reg_id, timestamp), timestamp.to_string())
+                .await?;
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        cacher.put(SCHEMA_ID, "schema".to_string()).await?;
+
+        let oldest: Option<String> = cacher.get(rev_reg_state_cache_id(rev_reg_id, 1), None).await?;
+        assert_eq!(oldest, None);
+        for timestamp in 2..=3 {
+            let state: Option<String> = cacher.get(rev_reg_state_cache_id(rev_reg_id, timestamp), None).await?;
+            assert_eq!(state, Some(timestamp.to_string()));
+        }
+        let schema: Option<String> = cacher.get(SCHEMA_ID, None).await?;
+        assert_eq!(schema, Some("schema".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_share_wallet() -> VcxCoreResult<()> {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let cacher = |namespace| {
+            let config = PersistentResponseCacherConfig::builder()
+                .namespace(namespace)
+                .max_rev_reg_states(1)
+                .build();
+            PersistentResponseCacher::new(wallet.clone(), config)
+        };
+        let (sovrin, indicio) = (cacher("sovrin"), cacher("indicio"));
+
+        sovrin.put(SCHEMA_ID, "sovrin schema".to_string()).await?;
+        indicio.put(SCHEMA_ID, "indicio schema".to_string()).await?;
+        let schema: Option<String> = sovrin.get(SCHEMA_ID, None).await?;
+        assert_eq!(schema, Some("sovrin schema".to_string()));
+
+        let rev_reg_state_id = rev_reg_state_cache_id("rev_reg_id", 1);
+        sovrin.put(&rev_reg_state_id, "sovrin state".to_string()).await?;
+        indicio.put(&rev_reg_state_id, "indicio state".to_string()).await?;
+        let state: Option<String> = sovrin.get(&rev_reg_state_id, None).await?;
+        assert_eq!(state, Some("sovrin state".to_string()));
+
+        indicio.remove(SCHEMA_ID).await?;
+        let schema: Option<String> = sovrin.get(SCHEMA_ID, None).await?;
+        assert_eq!(schema, Some("sovrin schema".to_string()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,552 @@
+mod node;
+
+use std::collections::HashMap;
+use std::fs;
+
+use indy_vdr::pool::ProtocolVersion;
+use rlp::UntrustedRlp;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use ursa::bls::{Bls, Generator, MultiSignature, VerKey};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use self::node::{Node, TrieDB};
+
+const GET_ATTR: &str = "104";
+const GET_NYM: &str = "105";
+const GET_SCHEMA: &str = "107";
+const GET_CRED_DEF: &str = "108";
+const GET_REVOC_REG_DEF: &str = "115";
+const GET_REVOC_REG: &str = "116";
+const GET_REVOC_REG_DELTA: &str = "117";
+
+const VERIFIABLE_READS: [&str; 7] = [
+    GET_ATTR,
+    GET_NYM,
+    GET_SCHEMA,
+    GET_CRED_DEF,
+    GET_REVOC_REG_DEF,
+    GET_REVOC_REG,
+    GET_REVOC_REG_DELTA,
+];
+
+// The generator the nodes make their BLS keys with, which is the same on all Indy ledgers.
+const BLS_GENERATOR: &str = "3LHpUjiyFC2q2hD7MnwwNmVXiuaFbQx2XkAFJWzswCjgN1utjsCeLzHsKk1nJvFEaS4fcrUmVAkdhtPCYbrVyATZcmzwJReTcJqwqBCPTmTQ9uWPwz6rEncKb2pYYYFcdHa8N17HzVyTqKfgPi4X9pMetfT3A5xCHq54R2pDNYWVLDX";
+
+/// Outcome of checking the state proof of a reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateProofCheck {
+    /// The reply is proven by a state proof signed by enough of the pool nodes.
+    Verified,
+    /// The reply is to a read which can be proven, but it carries no state proof.
+    Missing,
+    /// The request is not a read whose replies can be proven, or it was rejected.
+    NotApplicable,
+}
+
+/// Checks the state proofs of replies to `GET_NYM`, `GET_ATTR`, `GET_SCHEMA`, `GET_CRED_DEF` and
+/// `GET_REVOC_REG*` reads against the BLS keys of the pool nodes, so the replies are known to be
+/// the ledger state whichever way they were received.
+///
+/// The nodes are only known as of the genesis transactions the verifier is made from, so a pool
+/// whose validators have since changed needs fresh genesis transactions. Note the signed state
+/// is not checked for how recent it is.
+///
+/// The keys of the ledger state depend on the protocol version of the pool, Node 1.4 unless set
+/// with [`StateProofVerifier::with_protocol_version`].
+pub struct StateProofVerifier {
+    nodes: HashMap<String, Option<VerKey>>,
+    f: usize,
+    generator: Generator,
+    protocol_version: ProtocolVersion,
+}
+
+impl StateProofVerifier {
+    /// Verifier trusting the nodes of the given aliases and base58 encoded BLS keys.
+    pub fn new(node_bls_keys: HashMap<String, String>) -> VcxCoreResult<Self> {
+        let nodes = node_bls_keys
+            .into_iter()
+            .map(|(alias, bls_key)| Ok((alias, Some(parse_bls_key(&bls_key)?))))
+            .collect::<VcxCoreResult<_>>()?;
+        Self::from_nodes(nodes)
+    }
+
+    /// Verifier trusting the validator nodes of the pool, as given by its genesis transactions,
+    /// one JSON transaction per line.
+    pub fn from_genesis_transactions(genesis_transactions: &str) -> VcxCoreResult<Self> {
+        let mut nodes: HashMap<String, Option<VerKey>> = HashMap::new();
+        for line in genesis_transactions
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let txn: Value = serde_json::from_str(line)?;
+            let data = &txn["txn"]["data"]["data"];
+            let Some(alias) = data["alias"].as_str() else {
+                continue;
+            };
+            if let Some(services) = data["services"].as_array() {
+                if !services.iter().any(|service| service == "VALIDATOR") {
+                    nodes.remove(alias);
+                    continue;
+                }
+            }
+            let bls_key = data["blskey"].as_str().map(parse_bls_key).transpose()?;
+            let node = nodes.entry(alias.to_string()).or_insert(None);
+            if bls_key.is_some() {
+                *node = bls_key;
+            }
+        }
+        Self::from_nodes(nodes)
+    }
+
+    pub fn from_genesis_file(genesis_file_path: &str) -> VcxCoreResult<Self> {
+        let genesis_transactions = fs::read_to_string(genesis_file_path).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidGenesisTxnPath,
+                format!("Unable to read genesis transactions from {genesis_file_path}: {err}"),
+            )
+        })?;
+        Self::from_genesis_transactions(&genesis_transactions)
+    }
+
+    fn from_nodes(nodes: HashMap<String, Option<VerKey>>) -> VcxCoreResult<Self> {
+        if nodes.is_empty() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                "No validator nodes to verify state proofs with",
+            ));
+        }
+        let generator = bs58::decode(BLS_GENERATOR)
+            .into_vec()
+            .ok()
+            .and_then(|generator| Generator::from_bytes(&generator).ok())
+            .ok_or_else(|| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "Invalid BLS generator"))?;
+        Ok(Self {
+            f: (nodes.len() - 1) / 3,
+            nodes,
+            generator,
+            protocol_version: ProtocolVersion::Node1_4,
+        })
+    }
+
+    /// The protocol version of the pool, which should be the one its requests are made with.
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Checks the state proof of the reply to the request. A reply whose state proof is invalid,
+    /// or which does not match the request, is an error.
+    pub fn verify(&self, request: &Value, reply: &str) -> VcxCoreResult<StateProofCheck> {
+        let operation = &request["operation"];
+        let txn_type = operation["type"].as_str().unwrap_or_default();
+        if !VERIFIABLE_READS.contains(&txn_type) {
+            return Ok(StateProofCheck::NotApplicable);
+        }
+        let reply: Value = serde_json::from_str(reply)?;
+        if reply["op"].as_str() != Some("REPLY") {
+            return Ok(StateProofCheck::NotApplicable);
+        }
+        let result = &reply["result"];
+        if result["state_proof"].is_null() {
+            return Ok(StateProofCheck::Missing);
+        }
+        ensure_reply_is_to_request(operation, result)?;
+
+        let key = state_key(operation, txn_type, self.protocol_version)?;
+        let with_from = txn_type == GET_REVOC_REG_DELTA && !operation["from"].is_null();
+        for state_proof in parse_state_proofs(result, txn_type, key, with_from)? {
+            self.verify_signature(&state_proof.multi_signature, &state_proof.root_hash)?;
+            verify_trie(&state_proof)?;
+        }
+        Ok(StateProofCheck::Verified)
+    }
+
+    fn verify_signature(&self, multi_signature: &Value, root_hash: &str) -> VcxCoreResult<()> {
+        let signed = &multi_signature["value"];
+        if signed["state_root_hash"].as_str() != Some(root_hash) && signed["txn_root_hash"].as_str() != Some(root_hash)
+        {
+            return Err(invalid_proof(
+                "Multi-signature is not over the root hash of the state proof",
+            ));
+        }
+        let signature = multi_signature["signature"]
+            .as_str()
+            .and_then(|signature| bs58::decode(signature).into_vec().ok())
+            .and_then(|signature| MultiSignature::from_bytes(&signature).ok())
+            .ok_or_else(|| invalid_proof("State proof has an invalid multi-signature"))?;
+        let participants = multi_signature["participants"]
+            .as_array()
+            .and_then(|participants| participants.iter().map(Value::as_str).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| invalid_proof("State proof has invalid multi-signature participants"))?;
+        let signed = rmp_serde::to_vec_named(signed)
+            .map_err(|_| invalid_proof("Unable to serialize the value signed by the multi-signature"))?;
+
+        let bls_keys = self
+            .nodes
+            .iter()
+            .filter(|(alias, _)| participants.contains(&alias.as_str()))
+            .map(|(alias, bls_key)| {
+                bls_key
+                    .as_ref()
+                    .ok_or_else(|| invalid_proof(&format!("No BLS key of node {alias}")))
+            })
+            .collect::<VcxCoreResult<Vec<_>>>()?;
+        if bls_keys.len() < self.nodes.len() - self.f {
+            return Err(invalid_proof("State proof is signed by too few nodes"));
+        }
+        if !Bls::verify_multi_sig(&signature, &signed, &bls_keys, &self.generator).unwrap_or(false) {
+            return Err(invalid_proof("Multi-signature of state proof is invalid"));
+        }
+        Ok(())
+    }
+}
+
+struct ParsedStateProof {
+    root_hash: String,
+    proof_nodes: String,
+    multi_signature: Value,
+    key: Vec<u8>,
+    value: Option<String>,
+}
+
+fn invalid_proof(msg: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidLedgerResponse,
+        format!("State proof verification failed: {msg}"),
+    )
+}
+
+fn parse_bls_key(bls_key: &str) -> VcxCoreResult<VerKey> {
+    bs58::decode(bls_key)
+        .into_vec()
+        .ok()
+        .and_then(|bls_key| VerKey::from_bytes(&bls_key).ok())
+        .ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                format!("Invalid BLS key of node: {bls_key}"),
+            )
+        })
+}
+
+/// Nodes echo the operation of a read in its reply, which the reply is parsed from, so the
+/// echoed fields must be those of the operation the state key is made from.
+fn ensure_reply_is_to_request(operation: &Value, result: &Value) -> VcxCoreResult<()> {
+    let echoed = operation
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(field, _)| *field != "data")
+        .all(|(field, value)| &result[field] == value);
+    let schema_echoed = operation["type"] != GET_SCHEMA
+        || (result["data"]["name"] == operation["data"]["name"]
+            && result["data"]["version"] == operation["data"]["version"]);
+    if echoed && schema_echoed {
+        Ok(())
+    } else {
+        Err(invalid_proof("Reply is not to the request"))
+    }
+}
+
+/// Key the object read by the operation is stored under in the ledger state of a pool of the
+/// protocol version.
+fn state_key(operation: &Value, txn_type: &str, protocol_version: ProtocolVersion) -> VcxCoreResult<Vec<u8>> {
+    let node_1_3 = protocol_version == ProtocolVersion::Node1_3;
+    let marker = |marker: u8| {
+        if node_1_3 {
+            char::from(marker)
+        } else {
+            char::from(b'0' + marker)
+        }
+    };
+    let field = |name: &str| {
+        operation[name].as_str().ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Read of type {txn_type} has no {name}"),
+            )
+        })
+    };
+
+    let key = match txn_type {
+        GET_NYM => Sha256::digest(field("dest")?.as_bytes()).to_vec(),
+        GET_ATTR => {
+            let attr_name = field("raw").or_else(|_| field("enc")).or_else(|_| field("hash"))?;
+            let attr_hash = Sha256::digest(attr_name.as_bytes());
+            format!("{}:{}:{:x}", field("dest")?, marker(1), attr_hash).into_bytes()
+        }
+        GET_SCHEMA => {
+            let schema = &operation["data"];
+            match (schema["name"].as_str(), schema["version"].as_str()) {
+                (Some(name), Some(version)) => {
+                    format!("{}:{}:{name}:{version}", field("dest")?, marker(2)).into_bytes()
+                }
+                _ => {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::InvalidInput,
+                        "Read of type GET_SCHEMA has no schema name or version",
+                    ))
+                }
+            }
+        }
+        GET_CRED_DEF => {
+            let schema_seq_no = operation["ref"].as_u64().ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidInput,
+                    "Read of type GET_CRED_DEF has no schema sequence number",
+                )
+            })?;
+            let tag = match operation["tag"].as_str() {
+                Some(tag) if !node_1_3 => format!(":{tag}"),
+                _ => String::new(),
+            };
+            format!(
+                "{}:{}:{}:{schema_seq_no}{tag}",
+                field("origin")?,
+                marker(3),
+                field("signature_type")?
+            )
+            .into_bytes()
+        }
+        GET_REVOC_REG_DEF => field("id")?.as_bytes().to_vec(),
+        GET_REVOC_REG => format!("{}:{}", marker(6), field("revocRegDefId")?).into_bytes(),
+        GET_REVOC_REG_DELTA => {
+            let marker = if operation["from"].is_null() {
+                marker(5)
+            } else {
+                marker(6)
+            };
+            format!("{marker}:{}", field("revocRegDefId")?).into_bytes()
+        }
+        _ => {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Reads of type {txn_type} have no state proof"),
+            ))
+        }
+    };
+    Ok(key)
+}
+
+fn parse_state_proofs(
+    result: &Value,
+    txn_type: &str,
+    key: Vec<u8>,
+    with_from: bool,
+) -> VcxCoreResult<Vec<ParsedStateProof>> {
+    // Nodes return the data of some reads as JSON strings, whose exact text is what was hashed.
+    let (data, parsed_data) = match result["data"] {
+        Value::Null => (None, Value::Null),
+        Value::String(ref data) => (Some(data.to_owned()), serde_json::from_str(data)?),
+        ref data @ (Value::Object(_) | Value::Array(_)) => (Some(data.to_string()), data.clone()),
+        _ => return Err(invalid_proof("Reply data is of unexpected type")),
+    };
+    let proof_field = |state_proof: &Value, name: &str| {
+        state_proof[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| invalid_proof(&format!("State proof has no {name}")))
+    };
+
+    let state_proof = &result["state_proof"];
+    let mut state_proofs = vec![ParsedStateProof {
+        root_hash: proof_field(state_proof, "root_hash")?,
+        proof_nodes: proof_field(state_proof, "proof_nodes")?,
+        multi_signature: state_proof["multi_signature"].clone(),
+        key: key.clone(),
+        value: proof_value(result, data.as_deref(), &parsed_data, txn_type)?,
+    }];
+
+    // The delta of a revocation registry since a time also proves the accumulator at that time.
+    if with_from {
+        let state_proof = &parsed_data["stateProofFrom"];
+        let accum_from = &parsed_data["value"]["accum_from"];
+        let value = (!accum_from.is_null()).then(|| {
+            json!({
+                "lsn": accum_from["seqNo"],
+                "lut": accum_from["txnTime"],
+                "val": accum_from,
+            })
+            .to_string()
+        });
+        state_proofs.push(ParsedStateProof {
+            root_hash: proof_field(state_proof, "root_hash")?,
+            proof_nodes: proof_field(state_proof, "proof_nodes")?,
+            multi_signature: state_proof["multi_signature"].clone(),
+            key,
+            value,
+        });
+    }
+    Ok(state_proofs)
+}
+
+/// Value the reply claims is stored under the state key, as the ledger stores it.
+fn proof_value(
+    result: &Value,
+    data: Option<&str>,
+    parsed_data: &Value,
+    txn_type: &str,
+) -> VcxCoreResult<Option<String>> {
+    let Some(data) = data else {
+        return Ok(None);
+    };
+    let (seq_no, txn_time) = (result["seqNo"].clone(), result["txnTime"].clone());
+
+    let value = match txn_type {
+        GET_NYM => json!({
+            "seqNo": seq_no,
+            "txnTime": txn_time,
+            "identifier": parsed_data["identifier"],
+            "role": parsed_data["role"],
+            "verkey": parsed_data["verkey"],
+        }),
+        GET_ATTR => json!({
+            "lsn": seq_no,
+            "lut": txn_time,
+            "val": format!("{:x}", Sha256::digest(data.as_bytes())),
+        }),
+        GET_SCHEMA => {
+            let mut schema = parsed_data
+                .as_object()
+                .cloned()
+                .ok_or_else(|| invalid_proof("Reply data of GET_SCHEMA is not an object"))?;
+            schema.remove("name");
+            schema.remove("version");
+            // Reads of schemas not on the ledger are replied to with just their name and version
+            if schema.is_empty() {
+                return Ok(None);
+            }
+            json!({ "lsn": seq_no, "lut": txn_time, "val": schema })
+        }
+        GET_CRED_DEF | GET_REVOC_REG_DEF | GET_REVOC_REG => {
+            json!({ "lsn": seq_no, "lut": txn_time, "val": parsed_data })
+        }
+        GET_REVOC_REG_DELTA => {
+            let accum_to = &parsed_data["value"]["accum_to"];
+            if accum_to.is_null() {
+                return Ok(None);
+            }
+            json!({ "lsn": seq_no, "lut": txn_time, "val": accum_to })
+        }
+        _ => return Err(invalid_proof(&format!("Reads of type {txn_type} have no state proof"))),
+    };
+    Ok(Some(value.to_string()))
+}
+
+/// Checks the proof nodes make up a trie of the root hash, holding the value under the key.
+fn verify_trie(state_proof: &ParsedStateProof) -> VcxCoreResult<()> {
+    let proof_nodes =
+        base64::decode(&state_proof.proof_nodes).map_err(|_| invalid_proof("Proof nodes are not valid base64"))?;
+    let root_hash = bs58::decode(&state_proof.root_hash)
+        .into_vec()
+        .map_err(|_| invalid_proof("Root hash is not valid base58"))?;
+    let nodes: Vec<Node> = UntrustedRlp::new(&proof_nodes)
+        .as_list()
+        .map_err(|_| invalid_proof("Proof nodes are malformed"))?;
+    let db: TrieDB = nodes.iter().map(|node| (node.get_hash(), node)).collect();
+    let root = db
+        .get(&root_hash)
+        .ok_or_else(|| invalid_proof("Proof nodes do not include the root"))?;
+    if root.get_str_value(&db, &state_proof.key)? != state_proof.value {
+        return Err(invalid_proof("Reply does not match the ledger state"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    // Proof of a trie of '33' -> 'v1', '34' -> 'v2', '3C' -> 'v3', '4' -> 'v4', 'D' -> 'v5asdfasdf',
+    // 'E' -> 'v6fdsfdfs'
+    const PROOF_NODES: &str = "+MD3gICAoHYvxJZ8eS7z0i/v0/QyCeIYWyXpqXZA8Ju0thZX9nzzxiCEw4J2NICAgICAgICAgICAgPSAgIDdgICAxiCEw4J2McYghMOCdjKAgICAgICAgICAgIDGM4TDgnYzgICAgICAgICAgICA+FGAgICgCZ11Lx1aS5+fADRUAVPS0qfBTBEpDyfl2He1fIAYSMqgYmdkAIG+uMd/FPMMaPMGiK/D5dWjiBlMakL2mf42Gy+AgICAgICAgICAgIA=";
+    const ROOT_HASH: &str = "DaRuBYfV5yXih3jVZ6qayMiqUGKL1q8Z4SGU6ZsbmowV";
+
+    fn _state_proof(key: &str, value: Option<&str>) -> ParsedStateProof {
+        ParsedStateProof {
+            root_hash: ROOT_HASH.to_string(),
+            proof_nodes: PROOF_NODES.to_string(),
+            multi_signature: Value::Null,
+            key: key.as_bytes().to_vec(),
+            value: value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_verify_trie() {
+        verify_trie(&_state_proof("33", Some("v1"))).unwrap();
+        verify_trie(&_state_proof("3C", Some("v3"))).unwrap();
+        verify_trie(&_state_proof("4", Some("v4"))).unwrap();
+        verify_trie(&_state_proof("5", None)).unwrap();
+
+        assert!(verify_trie(&_state_proof("34", Some("v1"))).is_err());
+        assert!(verify_trie(&_state_proof("34", None)).is_err());
+        assert!(verify_trie(&_state_proof("5", Some("v5"))).is_err());
+    }
+
+    #[test]
+    fn test_verify_trie_fails_for_other_root() {
+        let mut state_proof = _state_proof("33", Some("v1"));
+        state_proof.root_hash = bs58::encode([0u8; 32]).into_string();
+        assert!(verify_trie(&state_proof).is_err());
+    }
+
+    #[test]
+    fn test_state_key() {
+        let operation = json!({ "type": GET_SCHEMA, "dest": "V4SGRU86Z58d6TV7PBUe6f", "data": { "name": "name", "version": "1.0" } });
+        assert_eq!(
+            state_key(&operation, GET_SCHEMA, ProtocolVersion::Node1_4).unwrap(),
+            b"V4SGRU86Z58d6TV7PBUe6f:2:name:1.0".to_vec()
+        );
+        assert_eq!(
+            state_key(&operation, GET_SCHEMA, ProtocolVersion::Node1_3).unwrap(),
+            b"V4SGRU86Z58d6TV7PBUe6f:\x02:name:1.0".to_vec()
+        );
+
+        let operation = json!({ "type": GET_CRED_DEF, "origin": "V4SGRU86Z58d6TV7PBUe6f", "ref": 10, "signature_type": "CL", "tag": "tag" });
+        assert_eq!(
+            state_key(&operation, GET_CRED_DEF, ProtocolVersion::Node1_4).unwrap(),
+            b"V4SGRU86Z58d6TV7PBUe6f:3:CL:10:tag".to_vec()
+        );
+        assert_eq!(
+            state_key(&operation, GET_CRED_DEF, ProtocolVersion::Node1_3).unwrap(),
+            b"V4SGRU86Z58d6TV7PBUe6f:\x03:CL:10".to_vec()
+        );
+
+        let operation = json!({ "type": GET_REVOC_REG_DELTA, "revocRegDefId": "id", "from": 1, "to": 2 });
+        assert_eq!(
+            state_key(&operation, GET_REVOC_REG_DELTA, ProtocolVersion::Node1_4).unwrap(),
+            b"6:id".to_vec()
+        );
+
+        let operation = json!({ "type": GET_NYM });
+        assert!(state_key(&operation, GET_NYM, ProtocolVersion::Node1_4).is_err());
+    }
+
+    #[test]
+    fn test_verify_without_state_proof() {
+        let verifier = StateProofVerifier {
+            nodes: HashMap::from([("Node1".to_string(), None)]),
+            f: 0,
+            generator: Generator::from_bytes(&bs58::decode(BLS_GENERATOR).into_vec().unwrap()).unwrap(),
+            protocol_version: ProtocolVersion::Node1_4,
+        };
+        let request = json!({ "operation": { "type": "3", "data": 1 } });
+        assert_eq!(verifier.verify(&request, "{}").unwrap(), StateProofCheck::NotApplicable);
+
+        let request = json!({ "operation": { "type": GET_NYM, "dest": "V4SGRU86Z58d6TV7PBUe6f" } });
+        let reply =
+            json!({ "op": "REPLY", "result": { "type": GET_NYM, "dest": "V4SGRU86Z58d6TV7PBUe6f", "data": null } });
+        assert_eq!(
+            verifier.verify(&request, &reply.to_string()).unwrap(),
+            StateProofCheck::Missing
+        );
+
+        let reply = json!({ "op": "REPLY", "result": { "type": GET_NYM, "dest": "LjgpST2rjsoxYegQDRm7EL", "data": null, "state_proof": {} } });
+        assert!(verifier.verify(&request, &reply.to_string()).is_err());
+    }
+}
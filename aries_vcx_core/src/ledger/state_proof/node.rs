@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use rlp::{DecoderError as RlpDecoderError, Prototype as RlpPrototype, RlpStream, UntrustedRlp};
+use sha3::{Digest, Sha3_256};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+/// Node of the Patricia Merkle Trie the ledger keeps its state in, as found in state proofs.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Node {
+    Leaf(Leaf),
+    Extension(Extension),
+    Full(FullNode),
+    Hash(Vec<u8>),
+    Blank,
+}
+
+impl Node {
+    const RADIX: usize = 16;
+    const FULL_SIZE: usize = Node::RADIX + 1;
+    const PAIR_SIZE: usize = 2;
+    const HASH_SIZE: usize = 32;
+    const EMPTY_SIZE: usize = 0;
+    const IS_LEAF_MASK: u8 = 0x20;
+    const IS_PATH_ODD_MASK: u8 = 0x10;
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct FullNode {
+    nodes: [Option<Box<Node>>; Node::RADIX],
+    value: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Leaf {
+    path: Vec<u8>,
+    value: Vec<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Extension {
+    path: Vec<u8>,
+    next: Box<Node>,
+}
+
+impl rlp::Encodable for Node {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        match *self {
+            Node::Hash(ref hash) => {
+                s.append_internal(&hash.as_slice());
+            }
+            Node::Leaf(ref pair) => {
+                s.begin_list(Node::PAIR_SIZE);
+                s.append(&pair.path);
+                s.append(&pair.value);
+            }
+            Node::Extension(ref ext) => {
+                s.begin_list(Node::PAIR_SIZE);
+                s.append(&ext.path);
+                s.append(ext.next.as_ref());
+            }
+            Node::Full(ref node) => {
+                s.begin_list(Node::FULL_SIZE);
+                for node in &node.nodes {
+                    if let Some(ref node) = *node {
+                        s.append(node.as_ref());
+                    } else {
+                        s.append_empty_data();
+                    }
+                }
+                if let Some(ref value) = node.value {
+                    s.append(value);
+                } else {
+                    s.append_empty_data();
+                }
+            }
+            Node::Blank => {
+                s.append_empty_data();
+            }
+        }
+    }
+}
+
+impl rlp::Decodable for Node {
+    fn decode(rlp: &UntrustedRlp) -> Result<Self, RlpDecoderError> {
+        match rlp.prototype()? {
+            RlpPrototype::List(Node::PAIR_SIZE) => {
+                let path: Vec<u8> = rlp.at(0)?.as_val()?;
+                match path.first() {
+                    Some(flags) if flags & Node::IS_LEAF_MASK == Node::IS_LEAF_MASK => Ok(Node::Leaf(Leaf {
+                        path,
+                        value: rlp.at(1)?.as_val()?,
+                    })),
+                    Some(_) => Ok(Node::Extension(Extension {
+                        path,
+                        next: Box::new(rlp.at(1)?.as_val()?),
+                    })),
+                    None => Err(RlpDecoderError::Custom("Path of Patricia Merkle Trie node is empty")),
+                }
+            }
+            RlpPrototype::List(Node::FULL_SIZE) => {
+                let mut nodes: [Option<Box<Node>>; Node::RADIX] = Default::default();
+                for (i, node) in nodes.iter_mut().enumerate() {
+                    let cur = rlp.at(i)?;
+                    if !matches!(cur.prototype()?, RlpPrototype::Data(0)) {
+                        *node = Some(Box::new(cur.as_val()?));
+                    }
+                }
+                let value: Option<Vec<u8>> = if !rlp.at(Node::RADIX)?.is_empty() {
+                    Some(rlp.at(Node::RADIX)?.as_val()?)
+                } else {
+                    None
+                };
+                Ok(Node::Full(FullNode { nodes, value }))
+            }
+            RlpPrototype::Data(Node::HASH_SIZE) => Ok(Node::Hash(rlp.as_val()?)),
+            RlpPrototype::Data(Node::EMPTY_SIZE) => Ok(Node::Blank),
+            _ => Err(RlpDecoderError::Custom("Unexpected data in Patricia Merkle Trie node")),
+        }
+    }
+}
+
+/// Nodes of a state proof by their hashes.
+pub type TrieDB<'a> = HashMap<Vec<u8>, &'a Node>;
+
+fn malformed(msg: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidLedgerResponse, msg)
+}
+
+impl Node {
+    pub fn get_hash(&self) -> Vec<u8> {
+        let encoded = rlp::encode(self);
+        Sha3_256::digest(&encoded[..]).to_vec()
+    }
+
+    /// Looks up the value stored under the key, in the trie under this node.
+    pub fn get_str_value(&self, db: &TrieDB, key: &[u8]) -> VcxCoreResult<Option<String>> {
+        match self.get_value(db, key)? {
+            Some(value) => String::from_utf8(value)
+                .map(Some)
+                .map_err(|_| malformed("Patricia Merkle Trie contains malformed utf8 string")),
+            None => Ok(None),
+        }
+    }
+
+    fn get_value(&self, db: &TrieDB, key: &[u8]) -> VcxCoreResult<Option<Vec<u8>>> {
+        let nibble_path = Node::path_to_nibbles(key);
+        let Some(raw) = self._get_value(db, &nibble_path)? else {
+            return Ok(None);
+        };
+        let mut values: Vec<Vec<u8>> = UntrustedRlp::new(raw).as_list().unwrap_or_default();
+        match (values.pop(), values.is_empty()) {
+            (Some(value), true) => Ok(Some(value)),
+            _ => Err(malformed("Unexpected data format of value in Patricia Merkle Trie")),
+        }
+    }
+
+    fn _get_value<'a>(&'a self, db: &'a TrieDB, path: &[u8]) -> VcxCoreResult<Option<&'a Vec<u8>>> {
+        match self._get_node(db, path)? {
+            Some(Node::Full(node)) => Ok(node.value.as_ref()),
+            Some(Node::Leaf(pair)) => Ok(Some(&pair.value)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Walks the trie along the path, down to the node holding its value, if any.
+    fn _get_node<'a>(&'a self, db: &'a TrieDB, path: &[u8]) -> VcxCoreResult<Option<&'a Node>> {
+        match *self {
+            Node::Full(ref node) => match path.split_first() {
+                None => Ok(Some(self)),
+                Some((nibble, rest)) => match node.nodes[*nibble as usize] {
+                    Some(ref next) => next._get_node(db, rest),
+                    None => Ok(None),
+                },
+            },
+            Node::Hash(ref hash) => match db.get(hash) {
+                Some(next) => next._get_node(db, path),
+                None => Err(malformed(
+                    "Incomplete key-value DB for Patricia Merkle Trie to get value by the key",
+                )),
+            },
+            Node::Leaf(ref pair) => {
+                let (is_leaf, pair_path) = Node::parse_path(&pair.path);
+                if !is_leaf {
+                    return Err(malformed(
+                        "Incorrect Patricia Merkle Trie: node marked as leaf but path contains extension flag",
+                    ));
+                }
+                if pair_path == path {
+                    Ok(Some(self))
+                } else {
+                    Ok(None)
+                }
+            }
+            Node::Extension(ref pair) => {
+                let (is_leaf, pair_path) = Node::parse_path(&pair.path);
+                if is_leaf {
+                    return Err(malformed(
+                        "Incorrect Patricia Merkle Trie: node marked as extension but path contains leaf flag",
+                    ));
+                }
+                if path.starts_with(&pair_path) {
+                    pair.next._get_node(db, &path[pair_path.len()..])
+                } else {
+                    Ok(None)
+                }
+            }
+            Node::Blank => Ok(None),
+        }
+    }
+
+    fn path_to_nibbles(path: &[u8]) -> Vec<u8> {
+        path.iter().flat_map(|byte| [byte >> 4, byte & 0x0F]).collect()
+    }
+
+    fn parse_path(path: &[u8]) -> (bool, Vec<u8>) {
+        let Some((flags, rest)) = path.split_first() else {
+            return (false, Vec::new());
+        };
+        let is_leaf = flags & Node::IS_LEAF_MASK == Node::IS_LEAF_MASK;
+        let is_odd = flags & Node::IS_PATH_ODD_MASK == Node::IS_PATH_ODD_MASK;
+        let mut nibbles = Node::path_to_nibbles(rest);
+        if is_odd {
+            nibbles.insert(0, flags & 0x0F);
+        }
+        (is_leaf, nibbles)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_node_rlp_roundtrip_for_empty() {
+        let encoded = base64::decode("wYA=").unwrap();
+        assert_eq!(
+            UntrustedRlp::new(&encoded).as_list::<Node>().unwrap(),
+            vec![Node::Blank]
+        );
+        let encoded = rlp::encode_list(&[Node::Blank]);
+        assert_eq!(base64::encode(&encoded[..]), "wYA=");
+    }
+}
@@ -93,6 +93,10 @@ impl BaseWallet for AgencyClientWallet {
         Err(unimplemented_agency_client_wallet_method("verify"))
     }
 
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        Err(unimplemented_agency_client_wallet_method("key_agreement"))
+    }
+
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(self.inner.pack_message(sender_vk, receiver_keys, msg).await?)
     }
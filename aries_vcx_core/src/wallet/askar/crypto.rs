@@ -0,0 +1,55 @@
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+pub(crate) use crate::wallet::indy_compat::keys::{random_bytes, Key, KEY_LENGTH};
+
+const NONCE_LENGTH: usize = 24;
+
+/// HMAC-SHA256 over the length prefixed parts, so that different splits of the same bytes differ.
+pub(crate) fn hmac(key: &Key, parts: &[&[u8]]) -> Key {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(&(part.len() as u32).to_be_bytes());
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn encrypt_with_nonce(key: &Key, nonce: &[u8], data: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    let ciphertext = XChaCha20Poly1305::new(key.into())
+        .encrypt(XNonce::from_slice(nonce), data)
+        .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::EncodeError, "Failed to encrypt data"))?;
+
+    let mut encrypted = nonce.to_vec();
+    encrypted.extend(ciphertext);
+    Ok(encrypted)
+}
+
+/// Encrypts the data under a random nonce, which is prepended to the ciphertext.
+pub(crate) fn encrypt(key: &Key, data: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    encrypt_with_nonce(key, &random_bytes::<NONCE_LENGTH>(), data)
+}
+
+/// Encrypts the data under a nonce derived from the data itself, so that equal plaintexts
+/// produce equal ciphertexts and can be looked up without decrypting the stored values.
+pub(crate) fn encrypt_searchable(key: &Key, hmac_key: &Key, data: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    let nonce = hmac(hmac_key, &[data]);
+    encrypt_with_nonce(key, &nonce[..NONCE_LENGTH], data)
+}
+
+pub(crate) fn decrypt(key: &Key, encrypted: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    if encrypted.len() < NONCE_LENGTH {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletAccessFailed,
+            "Encrypted data is too short",
+        ));
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletAccessFailed, "Failed to decrypt data"))
+}
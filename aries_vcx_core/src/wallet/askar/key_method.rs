@@ -0,0 +1,126 @@
+use std::fmt;
+use std::str::FromStr;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use super::crypto::{random_bytes, Key, KEY_LENGTH};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+pub(crate) const SALT_LENGTH: usize = 16;
+
+/// Cost of the Argon2i derivation of the store key from a passphrase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argon2Level {
+    /// 32 MiB of memory and 4 passes, equivalent to the libsodium interactive limits.
+    Interactive,
+    /// 128 MiB of memory and 6 passes.
+    Moderate,
+}
+
+impl Argon2Level {
+    fn params(&self) -> VcxCoreResult<Params> {
+        let (m_cost, t_cost) = match self {
+            Argon2Level::Interactive => (32768, 4),
+            Argon2Level::Moderate => (131072, 6),
+        };
+        Params::new(m_cost, t_cost, 1, Some(KEY_LENGTH)).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                format!("Invalid Argon2 parameters: {}", err),
+            )
+        })
+    }
+}
+
+/// Method of turning the wallet key passed by the user into the key protecting the store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyMethod {
+    /// The wallet key is a passphrase the store key is derived from.
+    DeriveKey(Argon2Level),
+    /// The wallet key is the base58 encoded store key itself, see [generate_raw_key].
+    RawKey,
+}
+
+impl Default for KeyMethod {
+    fn default() -> Self {
+        KeyMethod::DeriveKey(Argon2Level::Moderate)
+    }
+}
+
+impl KeyMethod {
+    pub(crate) fn derive_key(&self, wallet_key: &str, salt: Option<&[u8]>) -> VcxCoreResult<Key> {
+        let mut key = [0; KEY_LENGTH];
+        match self {
+            KeyMethod::DeriveKey(level) => {
+                let salt = salt.ok_or_else(|| {
+                    AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::InvalidState,
+                        "Key derivation requires the salt of the wallet",
+                    )
+                })?;
+                Argon2::new(Algorithm::Argon2i, Version::V0x13, level.params()?)
+                    .hash_password_into(wallet_key.as_bytes(), salt, &mut key)
+                    .map_err(|err| {
+                        AriesVcxCoreError::from_msg(
+                            AriesVcxCoreErrorKind::WalletAccessFailed,
+                            format!("Failed to derive wallet key: {}", err),
+                        )
+                    })?;
+            }
+            KeyMethod::RawKey => {
+                let decoded = bs58::decode(wallet_key).into_vec().map_err(|_| {
+                    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::NotBase58, "Raw wallet key is not base58")
+                })?;
+                if decoded.len() != KEY_LENGTH {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::InvalidInput,
+                        format!("Raw wallet key must be {} bytes long", KEY_LENGTH),
+                    ));
+                }
+                key.copy_from_slice(&decoded);
+            }
+        }
+        Ok(key)
+    }
+
+    pub(crate) fn generate_salt(&self) -> Option<[u8; SALT_LENGTH]> {
+        match self {
+            KeyMethod::DeriveKey(_) => Some(random_bytes()),
+            KeyMethod::RawKey => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = match self {
+            KeyMethod::DeriveKey(Argon2Level::Interactive) => "kdf:argon2i:int",
+            KeyMethod::DeriveKey(Argon2Level::Moderate) => "kdf:argon2i:mod",
+            KeyMethod::RawKey => "raw",
+        };
+        f.write_str(method)
+    }
+}
+
+/// Parses both the store notation (`kdf:argon2i:mod`, `raw`) and the indy wallet key derivation
+/// methods (`ARGON2I_MOD`, `ARGON2I_INT`, `RAW`).
+impl FromStr for KeyMethod {
+    type Err = AriesVcxCoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kdf:argon2i:int" | "ARGON2I_INT" => Ok(KeyMethod::DeriveKey(Argon2Level::Interactive)),
+            "kdf:argon2i:mod" | "kdf:argon2i" | "ARGON2I_MOD" => Ok(KeyMethod::DeriveKey(Argon2Level::Moderate)),
+            "raw" | "RAW" => Ok(KeyMethod::RawKey),
+            other => Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                format!("Unsupported wallet key derivation method: {}", other),
+            )),
+        }
+    }
+}
+
+/// Generates a random key to be used as the wallet key with [KeyMethod::RawKey].
+pub fn generate_raw_key() -> String {
+    bs58::encode(random_bytes::<KEY_LENGTH>()).into_string()
+}
@@ -0,0 +1,637 @@
+//! Pure-Rust wallet in the fashion of Aries Askar: records live in an SQLite database, with their
+//! categories, names and tags encrypted so that they can still be searched for, under keys specific
+//! to each profile of the store. DIDs and keys are kept in the record categories of the indy
+//! wallet, and messages are packed in the same envelope, so that it can stand in for
+//! [IndySdkWallet](super::indy_wallet::IndySdkWallet) without any vdrtools code.
+
+mod crypto;
+mod key_method;
+mod store;
+
+use std::fmt;
+
+use async_trait::async_trait;
+
+pub use self::key_method::{generate_raw_key, Argon2Level, KeyMethod};
+use self::store::{Profile, Store};
+use super::base_wallet::BaseWallet;
+use super::indy_compat::keys::{self, LocalDid, LocalKey, DID_CATEGORY, KEY_CATEGORY, TEMPORARY_DID_CATEGORY};
+use super::indy_compat::pack::{self, PackedMessage};
+use super::indy_compat::query::TagQuery;
+use super::indy_compat::records::{
+    check_record_type, merge_tags, parse_tag_names, parse_tags, record_json, Entry, RecordOptions,
+};
+use super::migration::{MigrationSource, MigrationTarget};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Clone, Debug, Builder)]
+#[builder(setter(into))]
+pub struct AskarWalletConfig {
+    /// SQLite database uri, such as `sqlite://path/to/wallet.db` or `sqlite::memory:`.
+    pub uri: String,
+    pub wallet_key: String,
+    #[builder(default)]
+    pub key_method: KeyMethod,
+    #[builder(default = "DEFAULT_PROFILE.to_string()")]
+    pub profile: String,
+}
+
+pub struct AskarWallet {
+    store: Store,
+    profile: Profile,
+    profile_name: String,
+}
+
+impl fmt::Debug for AskarWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AskarWallet")
+            .field("profile", &self.profile_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AskarWallet {
+    /// Provisions a new wallet, failing with [AriesVcxCoreErrorKind::DuplicationWallet] if there
+    /// already is one at the uri.
+    pub async fn create(config: &AskarWalletConfig) -> VcxCoreResult<Self> {
+        let store = Store::provision(&config.uri, config.key_method, &config.wallet_key).await?;
+        Self::open_profile(store, &config.profile).await
+    }
+
+    /// Opens an existing wallet, the key method being the one it was provisioned with.
+    pub async fn open(config: &AskarWalletConfig) -> VcxCoreResult<Self> {
+        let store = Store::open(&config.uri, &config.wallet_key).await?;
+        Self::open_profile(store, &config.profile).await
+    }
+
+    /// Removes the database of the wallet, returns whether there was one.
+    pub async fn delete(config: &AskarWalletConfig) -> VcxCoreResult<bool> {
+        Store::remove(&config.uri).await
+    }
+
+    pub async fn close(&self) {
+        self.store.close().await;
+    }
+
+    async fn open_profile(store: Store, profile_name: &str) -> VcxCoreResult<Self> {
+        let profile = store.profile(profile_name).await?;
+        Ok(Self {
+            store,
+            profile,
+            profile_name: profile_name.to_string(),
+        })
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, category: &str, name: &str) -> VcxCoreResult<T> {
+        let entry = self.profile.fetch(category, name).await?;
+        Ok(serde_json::from_str(&entry.value)?)
+    }
+
+    async fn fetch_key(&self, verkey: &str) -> VcxCoreResult<LocalKey> {
+        self.fetch_json(KEY_CATEGORY, verkey).await
+    }
+
+    async fn fetch_did(&self, did: &str) -> VcxCoreResult<LocalDid> {
+        self.fetch_json(DID_CATEGORY, did).await
+    }
+
+    async fn find_record(&self, category: &str, name: &str) -> VcxCoreResult<Option<Entry>> {
+        match self.profile.fetch(category, name).await {
+            Ok(entry) => Ok(Some(entry)),
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn scan(
+        &self,
+        category: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let options = RecordOptions::from_json(options)?;
+        let query = TagQuery::from_wql(query)?;
+
+        let records = match options.retrieve_records {
+            true => self
+                .profile
+                .scan(category, &query)
+                .await?
+                .into_iter()
+                .map(|entry| record_json(entry, &options))
+                .collect(),
+            false => Vec::new(),
+        };
+
+        Ok(Box::new(AskarRecordIterator {
+            records: records.into_iter(),
+        }))
+    }
+
+    /// Stores the key, a key already stored under the same verkey being the same key.
+    async fn store_key(&self, key: &LocalKey) -> VcxCoreResult<()> {
+        match self
+            .profile
+            .insert(KEY_CATEGORY, &key.verkey, &serde_json::to_string(key)?, &[])
+            .await
+        {
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => Ok(()),
+            res => res,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseWallet for AskarWallet {
+    async fn create_and_store_my_did(
+        &self,
+        seed: Option<&str>,
+        method_name: Option<&str>,
+    ) -> VcxCoreResult<(String, String)> {
+        let key = LocalKey::generate(seed)?;
+        let did = keys::did_from_verkey(&key.verkey, method_name)?;
+
+        if self.find_record(DID_CATEGORY, &did).await?.is_some() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationDid,
+                format!("DID {} already exists in the wallet", did),
+            ));
+        }
+
+        self.store_key(&key).await?;
+        let local_did = LocalDid {
+            did: did.clone(),
+            verkey: key.verkey.clone(),
+        };
+        self.profile
+            .insert(DID_CATEGORY, &did, &serde_json::to_string(&local_did)?, &[])
+            .await?;
+
+        Ok((did, key.verkey))
+    }
+
+    async fn key_for_local_did(&self, did: &str) -> VcxCoreResult<String> {
+        Ok(self.fetch_did(did).await?.verkey)
+    }
+
+    async fn replace_did_keys_start(&self, target_did: &str) -> VcxCoreResult<String> {
+        self.fetch_did(target_did).await?;
+
+        let key = LocalKey::generate(None)?;
+        self.store_key(&key).await?;
+
+        let temporary_did = serde_json::to_string(&LocalDid {
+            did: target_did.to_string(),
+            verkey: key.verkey.clone(),
+        })?;
+        match self.find_record(TEMPORARY_DID_CATEGORY, target_did).await? {
+            Some(_) => {
+                self.profile
+                    .update_value(TEMPORARY_DID_CATEGORY, target_did, &temporary_did)
+                    .await?
+            }
+            None => {
+                self.profile
+                    .insert(TEMPORARY_DID_CATEGORY, target_did, &temporary_did, &[])
+                    .await?
+            }
+        }
+
+        Ok(key.verkey)
+    }
+
+    async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()> {
+        let temporary_did: LocalDid = self.fetch_json(TEMPORARY_DID_CATEGORY, target_did).await?;
+
+        self.profile
+            .update_value(DID_CATEGORY, target_did, &serde_json::to_string(&temporary_did)?)
+            .await?;
+        self.profile.remove(TEMPORARY_DID_CATEGORY, target_did).await
+    }
+
+    async fn add_wallet_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tags = parse_tags(tags_json)?;
+        self.profile.insert(xtype, id, value, &tags).await
+    }
+
+    async fn get_wallet_record(&self, xtype: &str, id: &str, options_json: &str) -> VcxCoreResult<String> {
+        check_record_type(xtype)?;
+        let options = RecordOptions::from_json(options_json)?;
+        let entry = self.profile.fetch(xtype, id).await?;
+        Ok(record_json(entry, &options))
+    }
+
+    async fn get_wallet_record_value(&self, xtype: &str, id: &str) -> VcxCoreResult<String> {
+        check_record_type(xtype)?;
+        Ok(self.profile.fetch(xtype, id).await?.value)
+    }
+
+    async fn delete_wallet_record(&self, xtype: &str, id: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        self.profile.remove(xtype, id).await
+    }
+
+    async fn update_wallet_record_value(&self, xtype: &str, id: &str, value: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        self.profile.update_value(xtype, id, value).await
+    }
+
+    async fn add_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let mut tags = self.profile.fetch(xtype, id).await?.tags;
+        merge_tags(&mut tags, parse_tags(Some(tags_json))?);
+        self.profile.update_tags(xtype, id, &tags).await
+    }
+
+    async fn update_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tags = parse_tags(Some(tags_json))?;
+        self.profile.update_tags(xtype, id, &tags).await
+    }
+
+    async fn delete_wallet_record_tags(&self, xtype: &str, id: &str, tag_names: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tag_names = parse_tag_names(tag_names)?;
+        let mut tags = self.profile.fetch(xtype, id).await?.tags;
+        tags.retain(|tag| !tag_names.contains(&tag.name));
+        self.profile.update_tags(xtype, id, &tags).await
+    }
+
+    async fn iterate_wallet_records(
+        &self,
+        xtype: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        check_record_type(xtype)?;
+        self.scan(xtype, query, options).await
+    }
+
+    async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        self.fetch_key(my_vk).await?.sign(msg)
+    }
+
+    async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+        keys::verify(vk, msg, signature)
+    }
+
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        self.fetch_key(my_vk).await?.key_agreement(their_pk)
+    }
+
+    async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Receiver keys must be an array of verkeys: {}", err),
+            )
+        })?;
+        let sender = match sender_vk {
+            Some(sender_vk) => Some(self.fetch_key(sender_vk).await?),
+            None => None,
+        };
+
+        pack::pack(sender.as_ref(), &receiver_keys, msg)
+    }
+
+    async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let packed = PackedMessage::parse(msg)?;
+
+        for recipient_key in packed.recipient_keys() {
+            if let Some(entry) = self.find_record(KEY_CATEGORY, recipient_key).await? {
+                let key: LocalKey = serde_json::from_str(&entry.value)?;
+                return packed.unpack(&key);
+            }
+        }
+
+        Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            "None of the recipient keys of the message is in the wallet",
+        ))
+    }
+}
+
+/// The records matched by a search, which are all loaded when the search is opened.
+#[async_trait]
+impl MigrationSource for AskarWallet {
+    async fn iterate_migration_records(
+        &self,
+        xtype: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        self.scan(xtype, "{}", options).await
+    }
+}
+
+#[async_trait]
+impl MigrationTarget for AskarWallet {
+    async fn get_reserved_record(&self, xtype: &str, id: &str, options: &str) -> VcxCoreResult<String> {
+        let options = RecordOptions::from_json(options)?;
+        let entry = self.profile.fetch(xtype, id).await?;
+        Ok(record_json(entry, &options))
+    }
+
+    async fn import_reserved_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()> {
+        let tags = parse_tags(tags_json)?;
+        self.profile.insert(xtype, id, value, &tags).await
+    }
+}
+
+struct AskarRecordIterator {
+    records: std::vec::IntoIter<String>,
+}
+
+#[async_trait]
+impl AsyncFnIterator for AskarRecordIterator {
+    type Item = VcxCoreResult<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+
+    async fn create_wallet() -> AskarWallet {
+        let config = AskarWalletConfigBuilder::default()
+            .uri("sqlite::memory:")
+            .wallet_key(generate_raw_key())
+            .key_method(KeyMethod::RawKey)
+            .build()
+            .unwrap();
+        AskarWallet::create(&config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reopen_wallet() {
+        let path = std::env::temp_dir().join(format!(
+            "askar_wallet_{}.db",
+            bs58::encode(crypto::random_bytes::<8>()).into_string()
+        ));
+        let config = AskarWalletConfigBuilder::default()
+            .uri(format!("sqlite://{}", path.display()))
+            .wallet_key("passphrase")
+            .key_method(KeyMethod::DeriveKey(Argon2Level::Interactive))
+            .build()
+            .unwrap();
+
+        let wallet = AskarWallet::create(&config).await.unwrap();
+        wallet.add_wallet_record("type", "id", "value", None).await.unwrap();
+        wallet.close().await;
+        assert_eq!(
+            AskarWallet::create(&config).await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::DuplicationWallet
+        );
+
+        let wrong_key = AskarWalletConfig {
+            wallet_key: "wrong passphrase".into(),
+            ..config.clone()
+        };
+        assert!(AskarWallet::open(&wrong_key).await.is_err());
+
+        let wallet = AskarWallet::open(&config).await.unwrap();
+        assert_eq!(wallet.get_wallet_record_value("type", "id").await.unwrap(), "value");
+        wallet.close().await;
+
+        assert!(AskarWallet::delete(&config).await.unwrap());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_dids_and_keys() {
+        let wallet = create_wallet().await;
+
+        let (did, verkey) = wallet
+            .create_and_store_my_did(Some("000000000000000000000000Trustee1"), None)
+            .await
+            .unwrap();
+        assert_eq!(did, "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+        assert_eq!(
+            wallet
+                .create_and_store_my_did(Some("000000000000000000000000Trustee1"), None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::DuplicationDid
+        );
+
+        let signature = wallet.sign(&verkey, b"msg").await.unwrap();
+        assert!(wallet.verify(&verkey, b"msg", &signature).await.unwrap());
+
+        let new_verkey = wallet.replace_did_keys_start(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+        wallet.replace_did_keys_apply(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), new_verkey);
+        assert!(wallet.replace_did_keys_apply(&did).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reserved_records() {
+        let wallet = create_wallet().await;
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        assert_eq!(
+            wallet
+                .get_wallet_record_value(KEY_CATEGORY, &verkey)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert_eq!(
+            wallet
+                .get_wallet_record(DID_CATEGORY, &did, "{}")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert_eq!(
+            wallet
+                .update_wallet_record_value(KEY_CATEGORY, &verkey, "{}")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert!(wallet
+            .add_wallet_record(TEMPORARY_DID_CATEGORY, &did, "{}", None)
+            .await
+            .is_err());
+        assert!(wallet
+            .update_wallet_record_tags(DID_CATEGORY, &did, r#"{"a": "1"}"#)
+            .await
+            .is_err());
+        assert!(wallet.iterate_wallet_records(KEY_CATEGORY, "{}", "{}").await.is_err());
+        assert!(wallet.delete_wallet_record(KEY_CATEGORY, &verkey).await.is_err());
+
+        let signature = wallet.sign(&verkey, b"msg").await.unwrap();
+        assert!(wallet.verify(&verkey, b"msg", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_records() {
+        let wallet = create_wallet().await;
+
+        wallet
+            .add_wallet_record("type", "id", "value", Some(r#"{"a": "1", "~b": "2"}"#))
+            .await
+            .unwrap();
+        assert_eq!(
+            wallet
+                .add_wallet_record("type", "id", "value", None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::DuplicationWalletRecord
+        );
+
+        wallet.update_wallet_record_value("type", "id", "value2").await.unwrap();
+        wallet
+            .add_wallet_record_tags("type", "id", r#"{"a": "3", "c": "4"}"#)
+            .await
+            .unwrap();
+        wallet
+            .delete_wallet_record_tags("type", "id", r#"["~b"]"#)
+            .await
+            .unwrap();
+
+        let record: Value = serde_json::from_str(
+            &wallet
+                .get_wallet_record(
+                    "type",
+                    "id",
+                    r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#,
+                )
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            json!({"id": "id", "type": "type", "value": "value2", "tags": {"a": "3", "c": "4"}})
+        );
+
+        let record: Value = serde_json::from_str(&wallet.get_wallet_record("type", "id", "{}").await.unwrap()).unwrap();
+        assert_eq!(
+            record,
+            json!({"id": "id", "type": null, "value": "value2", "tags": null})
+        );
+
+        wallet
+            .update_wallet_record_tags("type", "id", r#"{"~d": "5"}"#)
+            .await
+            .unwrap();
+        wallet.delete_wallet_record("type", "id").await.unwrap();
+        assert_eq!(
+            wallet.get_wallet_record_value("type", "id").await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::WalletRecordNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_iterate_records() {
+        let wallet = create_wallet().await;
+        for i in 0..5 {
+            let tags = json!({"even": (i % 2 == 0).to_string(), "~index": i.to_string()}).to_string();
+            wallet
+                .add_wallet_record("type", &format!("id{}", i), "value", Some(&tags))
+                .await
+                .unwrap();
+        }
+        wallet.add_wallet_record("other", "id0", "value", None).await.unwrap();
+
+        let ids = |records: Vec<String>| {
+            let mut ids: Vec<String> = records
+                .iter()
+                .map(|record| {
+                    serde_json::from_str::<Value>(record).unwrap()["id"]
+                        .as_str()
+                        .unwrap()
+                        .to_string()
+                })
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let all = wallet
+            .iterate_wallet_records("type", "{}", "{}")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(all), ["id0", "id1", "id2", "id3", "id4"]);
+
+        let even = wallet
+            .iterate_wallet_records("type", r#"{"even": "true", "~index": {"$gt": "0"}}"#, "{}")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(even), ["id2", "id4"]);
+
+        let not_in = wallet
+            .iterate_wallet_records("type", r#"{"$not": {"~index": {"$in": ["0", "1", "2"]}}}"#, "{}")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(ids(not_in), ["id3", "id4"]);
+
+        assert!(wallet
+            .iterate_wallet_records("type", r#"{"even": {"$gt": "0"}}"#, "{}")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_message() {
+        let wallet = create_wallet().await;
+        let (_, sender_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let (_, receiver_vk) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let receiver_keys = json!([receiver_vk]).to_string();
+
+        let packed = wallet
+            .pack_message(Some(&sender_vk), &receiver_keys, b"hello")
+            .await
+            .unwrap();
+        let unpacked: Value = serde_json::from_slice(&wallet.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "hello", "sender_verkey": sender_vk, "recipient_verkey": receiver_vk})
+        );
+
+        let other_wallet = create_wallet().await;
+        assert_eq!(
+            other_wallet.unpack_message(&packed).await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::WalletRecordNotFound
+        );
+    }
+}
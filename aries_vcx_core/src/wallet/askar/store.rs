@@ -0,0 +1,597 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::{Executor, Row, Sqlite, Transaction};
+
+use super::crypto::{decrypt, encrypt, encrypt_searchable, hmac, random_bytes, Key, KEY_LENGTH};
+use super::key_method::KeyMethod;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::wallet::indy_compat::query::{TagName, TagQuery};
+use crate::wallet::indy_compat::records::{Entry, EntryTag};
+
+const MAX_CONNECTIONS: u32 = 10;
+const IN_MEMORY_URI: &str = ":memory:";
+
+const CONFIG_KEY_METHOD: &str = "key_method";
+const CONFIG_SALT: &str = "salt";
+const CONFIG_VERSION: &str = "version";
+const STORE_VERSION: &str = "1";
+
+const SCHEMA: &str = r#"
+CREATE TABLE config (
+    name TEXT NOT NULL PRIMARY KEY,
+    value TEXT
+);
+CREATE TABLE profiles (
+    id INTEGER NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    profile_key BLOB NOT NULL
+);
+CREATE TABLE items (
+    id INTEGER NOT NULL PRIMARY KEY,
+    profile_id INTEGER NOT NULL REFERENCES profiles (id) ON DELETE CASCADE,
+    category BLOB NOT NULL,
+    name BLOB NOT NULL,
+    value BLOB NOT NULL,
+    UNIQUE (profile_id, category, name)
+);
+CREATE TABLE items_tags (
+    id INTEGER NOT NULL PRIMARY KEY,
+    item_id INTEGER NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+    name BLOB NOT NULL,
+    value BLOB NOT NULL,
+    plaintext BOOLEAN NOT NULL
+);
+CREATE INDEX ix_items_tags_item_id ON items_tags (item_id);
+CREATE INDEX ix_items_tags_name_value ON items_tags (name, value, plaintext);
+"#;
+
+/// SQLite database holding the profiles, each with its own keys encrypted by the store key.
+pub(crate) struct Store {
+    pool: SqlitePool,
+    store_key: Key,
+}
+
+async fn connect(uri: &str, create: bool) -> VcxCoreResult<SqlitePool> {
+    let options = SqliteConnectOptions::from_str(uri)
+        .map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                format!("Invalid wallet uri {}: {}", uri, err),
+            )
+        })?
+        .create_if_missing(create)
+        .foreign_keys(true);
+
+    // every connection to an in-memory database opens a new database
+    let pool_options = if uri.contains(IN_MEMORY_URI) {
+        SqlitePoolOptions::new()
+            .min_connections(1)
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        SqlitePoolOptions::new().max_connections(MAX_CONNECTIONS)
+    };
+
+    pool_options.connect_with(options).await.map_err(|err| {
+        let kind = match create {
+            true => AriesVcxCoreErrorKind::WalletCreate,
+            false => AriesVcxCoreErrorKind::WalletNotFound,
+        };
+        AriesVcxCoreError::from_msg(kind, format!("Failed to open wallet database {}: {}", uri, err))
+    })
+}
+
+/// Path of the database file of an `sqlite:` uri, in the forms `SqliteConnectOptions` accepts.
+fn database_path(uri: &str) -> VcxCoreResult<&Path> {
+    let path = uri
+        .strip_prefix("sqlite://")
+        .or_else(|| uri.strip_prefix("sqlite:"))
+        .ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                format!("Invalid wallet uri {}, expected an sqlite: uri", uri),
+            )
+        })?;
+    Ok(Path::new(path.split('?').next().unwrap_or(path)))
+}
+
+impl Store {
+    pub async fn provision(uri: &str, key_method: KeyMethod, wallet_key: &str) -> VcxCoreResult<Self> {
+        let pool = connect(uri, true).await?;
+
+        let salt = key_method.generate_salt();
+        let store_key = key_method.derive_key(wallet_key, salt.as_ref().map(|salt| &salt[..]))?;
+
+        let mut tx = pool.begin().await?;
+        let exists = sqlx::query("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'config'")
+            .fetch_optional(&mut tx)
+            .await?
+            .is_some();
+        if exists {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationWallet,
+                format!("Wallet already exists: {}", uri),
+            ));
+        }
+
+        (&mut tx).execute(SCHEMA).await?;
+        let config = [
+            (CONFIG_VERSION, Some(STORE_VERSION.to_string())),
+            (CONFIG_KEY_METHOD, Some(key_method.to_string())),
+            (CONFIG_SALT, salt.map(|salt| bs58::encode(salt).into_string())),
+        ];
+        for (name, value) in config {
+            sqlx::query("INSERT INTO config (name, value) VALUES (?1, ?2)")
+                .bind(name)
+                .bind(value)
+                .execute(&mut tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(Self { pool, store_key })
+    }
+
+    pub async fn open(uri: &str, wallet_key: &str) -> VcxCoreResult<Self> {
+        let pool = connect(uri, false).await?;
+
+        let config: Vec<(String, Option<String>)> = sqlx::query_as("SELECT name, value FROM config")
+            .fetch_all(&pool)
+            .await
+            .map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::WalletNotFound,
+                    format!("Database {} is not a wallet: {}", uri, err),
+                )
+            })?;
+        let config_value = |name: &str| {
+            config
+                .iter()
+                .find(|(config_name, _)| config_name == name)
+                .and_then(|(_, value)| value.as_deref())
+        };
+
+        let key_method = config_value(CONFIG_KEY_METHOD)
+            .ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidState,
+                    "Wallet has no key derivation method",
+                )
+            })?
+            .parse::<KeyMethod>()?;
+        let salt = config_value(CONFIG_SALT)
+            .map(|salt| bs58::decode(salt).into_vec())
+            .transpose()
+            .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::NotBase58, "Wallet salt is not base58"))?;
+        let store_key = key_method.derive_key(wallet_key, salt.as_deref())?;
+
+        // any profile key tells whether the wallet key is the right one
+        let profile_key: Option<Vec<u8>> = sqlx::query_scalar("SELECT profile_key FROM profiles LIMIT 1")
+            .fetch_optional(&pool)
+            .await?;
+        if let Some(profile_key) = profile_key {
+            decrypt(&store_key, &profile_key)
+                .map_err(|err| err.extend("Invalid wallet key, failed to decrypt the wallet profile"))?;
+        }
+
+        Ok(Self { pool, store_key })
+    }
+
+    /// Removes the database file of the store, returns whether there was one.
+    pub async fn remove(uri: &str) -> VcxCoreResult<bool> {
+        if uri.contains(IN_MEMORY_URI) {
+            return Ok(false);
+        }
+        let path = database_path(uri)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        std::fs::remove_file(path).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::IOError,
+                format!("Failed to remove wallet {}: {}", path.display(), err),
+            )
+        })?;
+        for suffix in ["-wal", "-shm"] {
+            let mut journal = path.as_os_str().to_owned();
+            journal.push(suffix);
+            std::fs::remove_file(journal).ok();
+        }
+        Ok(true)
+    }
+
+    /// Opens the profile of the given name, creating it with fresh keys if it does not exist yet.
+    pub async fn profile(&self, name: &str) -> VcxCoreResult<Profile> {
+        let existing: Option<(i64, Vec<u8>)> = sqlx::query_as("SELECT id, profile_key FROM profiles WHERE name = ?1")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let (id, key) = match existing {
+            Some((id, encrypted_key)) => (id, ProfileKey::from_bytes(&decrypt(&self.store_key, &encrypted_key)?)?),
+            None => {
+                let key = ProfileKey::generate();
+                let id = sqlx::query("INSERT INTO profiles (name, profile_key) VALUES (?1, ?2)")
+                    .bind(name)
+                    .bind(encrypt(&self.store_key, &key.to_bytes())?)
+                    .execute(&self.pool)
+                    .await?
+                    .last_insert_rowid();
+                (id, key)
+            }
+        };
+
+        Ok(Profile {
+            pool: self.pool.clone(),
+            id,
+            key,
+        })
+    }
+
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+/// Keys of a profile: names and tags are encrypted deterministically, so that they can be
+/// searched for, values under a key derived for each item.
+struct ProfileKey {
+    category_key: Key,
+    name_key: Key,
+    item_hmac_key: Key,
+    value_key: Key,
+    tag_name_key: Key,
+    tag_value_key: Key,
+    tags_hmac_key: Key,
+}
+
+impl ProfileKey {
+    const PARTS: usize = 7;
+
+    fn generate() -> Self {
+        Self {
+            category_key: random_bytes(),
+            name_key: random_bytes(),
+            item_hmac_key: random_bytes(),
+            value_key: random_bytes(),
+            tag_name_key: random_bytes(),
+            tag_value_key: random_bytes(),
+            tags_hmac_key: random_bytes(),
+        }
+    }
+
+    fn keys(&self) -> [&Key; Self::PARTS] {
+        [
+            &self.category_key,
+            &self.name_key,
+            &self.item_hmac_key,
+            &self.value_key,
+            &self.tag_name_key,
+            &self.tag_value_key,
+            &self.tags_hmac_key,
+        ]
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.keys().iter().flat_map(|key| key.iter().copied()).collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> VcxCoreResult<Self> {
+        if bytes.len() != Self::PARTS * KEY_LENGTH {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletAccessFailed,
+                "Invalid profile key",
+            ));
+        }
+        let mut keys = bytes
+            .chunks(KEY_LENGTH)
+            .map(|key| key.try_into().expect("chunks have the key length"));
+        let mut next = || keys.next().expect("number of keys is checked");
+
+        Ok(Self {
+            category_key: next(),
+            name_key: next(),
+            item_hmac_key: next(),
+            value_key: next(),
+            tag_name_key: next(),
+            tag_value_key: next(),
+            tags_hmac_key: next(),
+        })
+    }
+
+    fn encrypt_category(&self, category: &str) -> VcxCoreResult<Vec<u8>> {
+        encrypt_searchable(&self.category_key, &self.item_hmac_key, category.as_bytes())
+    }
+
+    fn encrypt_name(&self, name: &str) -> VcxCoreResult<Vec<u8>> {
+        encrypt_searchable(&self.name_key, &self.item_hmac_key, name.as_bytes())
+    }
+
+    fn item_value_key(&self, category: &[u8], name: &[u8]) -> Key {
+        hmac(&self.value_key, &[category, name])
+    }
+
+    fn encrypt_value(&self, category: &[u8], name: &[u8], value: &str) -> VcxCoreResult<Vec<u8>> {
+        encrypt(&self.item_value_key(category, name), value.as_bytes())
+    }
+
+    fn decrypt_value(&self, category: &[u8], name: &[u8], value: &[u8]) -> VcxCoreResult<String> {
+        to_string(decrypt(&self.item_value_key(category, name), value)?)
+    }
+
+    /// Encrypted tag name and whether the tag values are stored unencrypted.
+    fn encrypt_tag_name(&self, name: &TagName) -> VcxCoreResult<(Vec<u8>, bool)> {
+        let (name, plaintext) = match name {
+            TagName::Encrypted(name) => (name, false),
+            TagName::Plaintext(name) => (name, true),
+        };
+        let name = encrypt_searchable(&self.tag_name_key, &self.tags_hmac_key, name.as_bytes())?;
+        Ok((name, plaintext))
+    }
+
+    fn encrypt_tag_value(&self, name: &TagName, value: &str) -> VcxCoreResult<Vec<u8>> {
+        match name {
+            TagName::Encrypted(_) => encrypt_searchable(&self.tag_value_key, &self.tags_hmac_key, value.as_bytes()),
+            TagName::Plaintext(_) => Ok(value.as_bytes().to_vec()),
+        }
+    }
+
+    fn decrypt_tag(&self, name: &[u8], value: &[u8], plaintext: bool) -> VcxCoreResult<EntryTag> {
+        let name = to_string(decrypt(&self.tag_name_key, name)?)?;
+        Ok(match plaintext {
+            true => EntryTag {
+                name: TagName::Plaintext(name),
+                value: to_string(value.to_vec())?,
+            },
+            false => EntryTag {
+                name: TagName::Encrypted(name),
+                value: to_string(decrypt(&self.tag_value_key, value)?)?,
+            },
+        })
+    }
+}
+
+fn to_string(bytes: Vec<u8>) -> VcxCoreResult<String> {
+    String::from_utf8(bytes)
+        .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "Wallet item is not valid UTF-8"))
+}
+
+fn record_not_found(category: &str, name: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletRecordNotFound,
+        format!("Wallet record {} of type {} not found", name, category),
+    )
+}
+
+/// A named partition of the store, holding records identified by their category and name.
+pub(crate) struct Profile {
+    pool: SqlitePool,
+    id: i64,
+    key: ProfileKey,
+}
+
+impl Profile {
+    pub async fn insert(&self, category: &str, name: &str, value: &str, tags: &[EntryTag]) -> VcxCoreResult<()> {
+        let enc_category = self.key.encrypt_category(category)?;
+        let enc_name = self.key.encrypt_name(name)?;
+        let enc_value = self.key.encrypt_value(&enc_category, &enc_name, value)?;
+
+        let mut tx = self.pool.begin().await?;
+        let item_id = sqlx::query("INSERT INTO items (profile_id, category, name, value) VALUES (?1, ?2, ?3, ?4)")
+            .bind(self.id)
+            .bind(&enc_category)
+            .bind(&enc_name)
+            .bind(enc_value)
+            .execute(&mut tx)
+            .await
+            .map_err(|err| match AriesVcxCoreError::from(err) {
+                err if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::DuplicationWalletRecord,
+                    format!("Wallet record {} of type {} already exists", name, category),
+                ),
+                err => err,
+            })?
+            .last_insert_rowid();
+        self.insert_tags(&mut tx, item_id, tags).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn fetch(&self, category: &str, name: &str) -> VcxCoreResult<Entry> {
+        let enc_category = self.key.encrypt_category(category)?;
+        let enc_name = self.key.encrypt_name(name)?;
+
+        let (item_id, enc_value): (i64, Vec<u8>) =
+            sqlx::query_as("SELECT id, value FROM items WHERE profile_id = ?1 AND category = ?2 AND name = ?3")
+                .bind(self.id)
+                .bind(&enc_category)
+                .bind(&enc_name)
+                .fetch_optional(&self.pool)
+                .await?
+                .ok_or_else(|| record_not_found(category, name))?;
+
+        Ok(Entry {
+            category: category.to_string(),
+            name: name.to_string(),
+            value: self.key.decrypt_value(&enc_category, &enc_name, &enc_value)?,
+            tags: self.load_tags(item_id).await?,
+        })
+    }
+
+    pub async fn update_value(&self, category: &str, name: &str, value: &str) -> VcxCoreResult<()> {
+        let enc_category = self.key.encrypt_category(category)?;
+        let enc_name = self.key.encrypt_name(name)?;
+        let enc_value = self.key.encrypt_value(&enc_category, &enc_name, value)?;
+
+        let updated = sqlx::query("UPDATE items SET value = ?1 WHERE profile_id = ?2 AND category = ?3 AND name = ?4")
+            .bind(enc_value)
+            .bind(self.id)
+            .bind(&enc_category)
+            .bind(&enc_name)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        match updated {
+            0 => Err(record_not_found(category, name)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Replaces all the tags of the record.
+    pub async fn update_tags(&self, category: &str, name: &str, tags: &[EntryTag]) -> VcxCoreResult<()> {
+        let enc_category = self.key.encrypt_category(category)?;
+        let enc_name = self.key.encrypt_name(name)?;
+
+        let mut tx = self.pool.begin().await?;
+        let item_id: i64 =
+            sqlx::query_scalar("SELECT id FROM items WHERE profile_id = ?1 AND category = ?2 AND name = ?3")
+                .bind(self.id)
+                .bind(&enc_category)
+                .bind(&enc_name)
+                .fetch_optional(&mut tx)
+                .await?
+                .ok_or_else(|| record_not_found(category, name))?;
+
+        sqlx::query("DELETE FROM items_tags WHERE item_id = ?1")
+            .bind(item_id)
+            .execute(&mut tx)
+            .await?;
+        self.insert_tags(&mut tx, item_id, tags).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn remove(&self, category: &str, name: &str) -> VcxCoreResult<()> {
+        let removed = sqlx::query("DELETE FROM items WHERE profile_id = ?1 AND category = ?2 AND name = ?3")
+            .bind(self.id)
+            .bind(self.key.encrypt_category(category)?)
+            .bind(self.key.encrypt_name(name)?)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        match removed {
+            0 => Err(record_not_found(category, name)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fetches all the records of the category matching the query.
+    pub async fn scan(&self, category: &str, query: &TagQuery) -> VcxCoreResult<Vec<Entry>> {
+        let enc_category = self.key.encrypt_category(category)?;
+        let mut params = Vec::new();
+        let condition = self.query_sql(query, &mut params)?;
+
+        let sql = format!(
+            "SELECT i.id, i.name, i.value FROM items i WHERE i.profile_id = ? AND i.category = ? AND ({})",
+            condition
+        );
+        let mut statement = sqlx::query(&sql).bind(self.id).bind(&enc_category);
+        for param in params {
+            statement = statement.bind(param);
+        }
+        let rows = statement.fetch_all(&self.pool).await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let enc_name: Vec<u8> = row.try_get(1)?;
+            let enc_value: Vec<u8> = row.try_get(2)?;
+            entries.push(Entry {
+                category: category.to_string(),
+                name: to_string(decrypt(&self.key.name_key, &enc_name)?)?,
+                value: self.key.decrypt_value(&enc_category, &enc_name, &enc_value)?,
+                tags: self.load_tags(row.try_get(0)?).await?,
+            });
+        }
+        Ok(entries)
+    }
+
+    async fn insert_tags(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        item_id: i64,
+        tags: &[EntryTag],
+    ) -> VcxCoreResult<()> {
+        for tag in tags {
+            let (name, plaintext) = self.key.encrypt_tag_name(&tag.name)?;
+            sqlx::query("INSERT INTO items_tags (item_id, name, value, plaintext) VALUES (?1, ?2, ?3, ?4)")
+                .bind(item_id)
+                .bind(name)
+                .bind(self.key.encrypt_tag_value(&tag.name, &tag.value)?)
+                .bind(plaintext)
+                .execute(&mut *tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn load_tags(&self, item_id: i64) -> VcxCoreResult<Vec<EntryTag>> {
+        let tags: Vec<(Vec<u8>, Vec<u8>, bool)> =
+            sqlx::query_as("SELECT name, value, plaintext FROM items_tags WHERE item_id = ?1 ORDER BY id")
+                .bind(item_id)
+                .fetch_all(&self.pool)
+                .await?;
+
+        tags.iter()
+            .map(|(name, value, plaintext)| self.key.decrypt_tag(name, value, *plaintext))
+            .collect()
+    }
+
+    /// Translates the query into an SQL condition on the items, pushing its parameters.
+    fn query_sql(&self, query: &TagQuery, params: &mut Vec<Vec<u8>>) -> VcxCoreResult<String> {
+        let join = |queries: &[TagQuery], separator: &str, empty: &str, params: &mut Vec<Vec<u8>>| {
+            if queries.is_empty() {
+                return Ok(empty.to_string());
+            }
+            let conditions = queries
+                .iter()
+                .map(|query| Ok(format!("({})", self.query_sql(query, params)?)))
+                .collect::<VcxCoreResult<Vec<_>>>()?;
+            Ok(conditions.join(separator))
+        };
+
+        match query {
+            TagQuery::And(queries) => join(queries, " AND ", "1", params),
+            TagQuery::Or(queries) => join(queries, " OR ", "0", params),
+            TagQuery::Not(query) => Ok(format!("NOT ({})", self.query_sql(query, params)?)),
+            TagQuery::Eq(name, value) => self.tag_condition(name, "= ?", &[value], params),
+            TagQuery::Neq(name, value) => self.tag_condition(name, "!= ?", &[value], params),
+            TagQuery::In(_, values) if values.is_empty() => Ok("0".to_string()),
+            TagQuery::In(name, values) => {
+                let placeholders = vec!["?"; values.len()].join(", ");
+                let values = values.iter().collect::<Vec<_>>();
+                self.tag_condition(name, &format!("IN ({})", placeholders), &values, params)
+            }
+            TagQuery::Gt(name, value) => self.tag_condition(name, "> ?", &[value], params),
+            TagQuery::Gte(name, value) => self.tag_condition(name, ">= ?", &[value], params),
+            TagQuery::Lt(name, value) => self.tag_condition(name, "< ?", &[value], params),
+            TagQuery::Lte(name, value) => self.tag_condition(name, "<= ?", &[value], params),
+            TagQuery::Like(name, value) => self.tag_condition(name, "LIKE ?", &[value], params),
+        }
+    }
+
+    fn tag_condition(
+        &self,
+        name: &TagName,
+        comparison: &str,
+        values: &[&String],
+        params: &mut Vec<Vec<u8>>,
+    ) -> VcxCoreResult<String> {
+        let (enc_name, plaintext) = self.key.encrypt_tag_name(name)?;
+        params.push(enc_name);
+        for value in values {
+            params.push(self.key.encrypt_tag_value(name, value)?);
+        }
+        Ok(format!(
+            "i.id IN (SELECT item_id FROM items_tags WHERE name = ? AND plaintext = {} AND value {})",
+            plaintext as u8, comparison
+        ))
+    }
+}
@@ -56,6 +56,10 @@ pub trait BaseWallet: std::fmt::Debug + Send + Sync {
 
     async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool>;
 
+    /// Computes the X25519 shared secret of `their_pk`, a raw X25519 public key, and the X25519 key
+    /// converted from our Ed25519 key `my_vk`, as used by DIDComm v2 key agreement.
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>>;
+
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
 
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::base_wallet::BaseWallet;
+use super::indy_compat::keys::{self, LocalDid, LocalKey, DID_CATEGORY, KEY_CATEGORY, TEMPORARY_DID_CATEGORY};
+use super::indy_compat::pack::{self, PackedMessage};
+use super::indy_compat::query::TagQuery;
+use super::indy_compat::records::{
+    check_record_type, merge_tags, parse_tag_names, parse_tags, record_json, Entry, EntryTag, RecordOptions,
+};
+use super::migration::{MigrationSource, MigrationTarget};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+type Records = HashMap<(String, String), Entry>;
+
+/// Wallet keeping its keys and records in memory only, for tests and short-lived agents.
+/// Keys, records and packed messages are in the formats of the indy wallet.
+#[derive(Debug, Default)]
+pub struct InMemoryWallet {
+    records: RwLock<Records>,
+}
+
+fn record_not_found(category: &str, name: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletRecordNotFound,
+        format!("Wallet record {} of type {} not found", name, category),
+    )
+}
+
+fn record_key(category: &str, name: &str) -> (String, String) {
+    (category.to_string(), name.to_string())
+}
+
+impl InMemoryWallet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> VcxCoreResult<RwLockReadGuard<'_, Records>> {
+        self.records.read().map_err(|_| {
+            AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "In-memory wallet lock is poisoned")
+        })
+    }
+
+    fn write(&self) -> VcxCoreResult<RwLockWriteGuard<'_, Records>> {
+        self.records.write().map_err(|_| {
+            AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "In-memory wallet lock is poisoned")
+        })
+    }
+
+    fn fetch(&self, category: &str, name: &str) -> VcxCoreResult<Entry> {
+        self.read()?
+            .get(&record_key(category, name))
+            .cloned()
+            .ok_or_else(|| record_not_found(category, name))
+    }
+
+    fn fetch_json<T: DeserializeOwned>(&self, category: &str, name: &str) -> VcxCoreResult<T> {
+        Ok(serde_json::from_str(&self.fetch(category, name)?.value)?)
+    }
+
+    fn insert(&self, category: &str, name: &str, value: String, tags: Vec<EntryTag>) -> VcxCoreResult<()> {
+        let mut records = self.write()?;
+        let key = record_key(category, name);
+        if records.contains_key(&key) {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationWalletRecord,
+                format!("Wallet record {} of type {} already exists", name, category),
+            ));
+        }
+
+        let entry = Entry {
+            category: category.to_string(),
+            name: name.to_string(),
+            value,
+            tags,
+        };
+        records.insert(key, entry);
+        Ok(())
+    }
+
+    fn update(&self, category: &str, name: &str, update: impl FnOnce(&mut Entry)) -> VcxCoreResult<()> {
+        let mut records = self.write()?;
+        let entry = records
+            .get_mut(&record_key(category, name))
+            .ok_or_else(|| record_not_found(category, name))?;
+        update(entry);
+        Ok(())
+    }
+
+    fn remove(&self, category: &str, name: &str) -> VcxCoreResult<Entry> {
+        self.write()?
+            .remove(&record_key(category, name))
+            .ok_or_else(|| record_not_found(category, name))
+    }
+
+    fn scan(
+        &self,
+        category: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let options = RecordOptions::from_json(options)?;
+        let query = TagQuery::from_wql(query)?;
+
+        let records = match options.retrieve_records {
+            true => self
+                .read()?
+                .values()
+                .filter(|entry| entry.category == category && query.matches(&entry.tags))
+                .map(|entry| record_json(entry.clone(), &options))
+                .collect(),
+            false => Vec::new(),
+        };
+
+        Ok(Box::new(InMemoryRecordIterator {
+            records: records.into_iter(),
+        }))
+    }
+
+    fn store_json<T: Serialize>(&self, category: &str, name: &str, value: &T) -> VcxCoreResult<()> {
+        let value = serde_json::to_string(value)?;
+        self.write()?.insert(
+            record_key(category, name),
+            Entry {
+                category: category.to_string(),
+                name: name.to_string(),
+                value,
+                tags: Vec::new(),
+            },
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseWallet for InMemoryWallet {
+    async fn create_and_store_my_did(
+        &self,
+        seed: Option<&str>,
+        method_name: Option<&str>,
+    ) -> VcxCoreResult<(String, String)> {
+        let key = LocalKey::generate(seed)?;
+        let did = keys::did_from_verkey(&key.verkey, method_name)?;
+        let local_did = LocalDid {
+            did: did.clone(),
+            verkey: key.verkey.clone(),
+        };
+
+        self.insert(DID_CATEGORY, &did, serde_json::to_string(&local_did)?, Vec::new())
+            .map_err(|err| match err.kind() {
+                AriesVcxCoreErrorKind::DuplicationWalletRecord => AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::DuplicationDid,
+                    format!("DID {} already exists in the wallet", did),
+                ),
+                _ => err,
+            })?;
+        self.store_json(KEY_CATEGORY, &key.verkey, &key)?;
+
+        Ok((did, key.verkey))
+    }
+
+    async fn key_for_local_did(&self, did: &str) -> VcxCoreResult<String> {
+        Ok(self.fetch_json::<LocalDid>(DID_CATEGORY, did)?.verkey)
+    }
+
+    async fn replace_did_keys_start(&self, target_did: &str) -> VcxCoreResult<String> {
+        self.fetch(DID_CATEGORY, target_did)?;
+
+        let key = LocalKey::generate(None)?;
+        self.store_json(KEY_CATEGORY, &key.verkey, &key)?;
+        self.store_json(
+            TEMPORARY_DID_CATEGORY,
+            target_did,
+            &LocalDid {
+                did: target_did.to_string(),
+                verkey: key.verkey.clone(),
+            },
+        )?;
+
+        Ok(key.verkey)
+    }
+
+    async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()> {
+        let temporary_did = self.fetch(TEMPORARY_DID_CATEGORY, target_did)?;
+        self.update(DID_CATEGORY, target_did, |entry| entry.value = temporary_did.value)?;
+        self.remove(TEMPORARY_DID_CATEGORY, target_did)?;
+        Ok(())
+    }
+
+    async fn add_wallet_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tags = parse_tags(tags_json)?;
+        self.insert(xtype, id, value.to_string(), tags)
+    }
+
+    async fn get_wallet_record(&self, xtype: &str, id: &str, options_json: &str) -> VcxCoreResult<String> {
+        check_record_type(xtype)?;
+        let options = RecordOptions::from_json(options_json)?;
+        Ok(record_json(self.fetch(xtype, id)?, &options))
+    }
+
+    async fn get_wallet_record_value(&self, xtype: &str, id: &str) -> VcxCoreResult<String> {
+        check_record_type(xtype)?;
+        Ok(self.fetch(xtype, id)?.value)
+    }
+
+    async fn delete_wallet_record(&self, xtype: &str, id: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        self.remove(xtype, id)?;
+        Ok(())
+    }
+
+    async fn update_wallet_record_value(&self, xtype: &str, id: &str, value: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        self.update(xtype, id, |entry| entry.value = value.to_string())
+    }
+
+    async fn add_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tags = parse_tags(Some(tags_json))?;
+        self.update(xtype, id, |entry| merge_tags(&mut entry.tags, tags))
+    }
+
+    async fn update_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tags = parse_tags(Some(tags_json))?;
+        self.update(xtype, id, |entry| entry.tags = tags)
+    }
+
+    async fn delete_wallet_record_tags(&self, xtype: &str, id: &str, tag_names: &str) -> VcxCoreResult<()> {
+        check_record_type(xtype)?;
+        let tag_names = parse_tag_names(tag_names)?;
+        self.update(xtype, id, |entry| {
+            entry.tags.retain(|tag| !tag_names.contains(&tag.name))
+        })
+    }
+
+    async fn iterate_wallet_records(
+        &self,
+        xtype: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        check_record_type(xtype)?;
+        self.scan(xtype, query, options)
+    }
+
+    async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        self.fetch_json::<LocalKey>(KEY_CATEGORY, my_vk)?.sign(msg)
+    }
+
+    async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+        keys::verify(vk, msg, signature)
+    }
+
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        self.fetch_json::<LocalKey>(KEY_CATEGORY, my_vk)?
+            .key_agreement(their_pk)
+    }
+
+    async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Receiver keys must be an array of verkeys: {}", err),
+            )
+        })?;
+        let sender = sender_vk
+            .map(|sender_vk| self.fetch_json::<LocalKey>(KEY_CATEGORY, sender_vk))
+            .transpose()?;
+
+        pack::pack(sender.as_ref(), &receiver_keys, msg)
+    }
+
+    async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let packed = PackedMessage::parse(msg)?;
+
+        let key = packed
+            .recipient_keys()
+            .find_map(|recipient_key| self.fetch_json::<LocalKey>(KEY_CATEGORY, recipient_key).ok())
+            .ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::WalletRecordNotFound,
+                    "None of the recipient keys of the message is in the wallet",
+                )
+            })?;
+        packed.unpack(&key)
+    }
+}
+
+#[async_trait]
+impl MigrationSource for InMemoryWallet {
+    async fn iterate_migration_records(
+        &self,
+        xtype: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        self.scan(xtype, "{}", options)
+    }
+}
+
+#[async_trait]
+impl MigrationTarget for InMemoryWallet {
+    async fn get_reserved_record(&self, xtype: &str, id: &str, options: &str) -> VcxCoreResult<String> {
+        let options = RecordOptions::from_json(options)?;
+        Ok(record_json(self.fetch(xtype, id)?, &options))
+    }
+
+    async fn import_reserved_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()> {
+        let tags = parse_tags(tags_json)?;
+        self.insert(xtype, id, value.to_string(), tags)
+    }
+}
+
+struct InMemoryRecordIterator {
+    records: std::vec::IntoIter<String>,
+}
+
+#[async_trait]
+impl AsyncFnIterator for InMemoryRecordIterator {
+    type Item = VcxCoreResult<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+
+    #[tokio::test]
+    async fn test_dids_and_keys() {
+        let wallet = InMemoryWallet::new();
+
+        let (did, verkey) = wallet
+            .create_and_store_my_did(Some("000000000000000000000000Trustee1"), Some("sov"))
+            .await
+            .unwrap();
+        assert_eq!(did, "did:sov:V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+        assert_eq!(
+            wallet
+                .create_and_store_my_did(Some("000000000000000000000000Trustee1"), Some("sov"))
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::DuplicationDid
+        );
+
+        let new_verkey = wallet.replace_did_keys_start(&did).await.unwrap();
+        wallet.replace_did_keys_apply(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), new_verkey);
+
+        let signature = wallet.sign(&new_verkey, b"msg").await.unwrap();
+        assert!(wallet.verify(&new_verkey, b"msg", &signature).await.unwrap());
+        assert!(!wallet.verify(&verkey, b"msg", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_records() {
+        let wallet = InMemoryWallet::new();
+        wallet
+            .add_wallet_record("type", "id", "value", Some(r#"{"a": "1", "~b": "2"}"#))
+            .await
+            .unwrap();
+        wallet
+            .add_wallet_record_tags("type", "id", r#"{"a": "3"}"#)
+            .await
+            .unwrap();
+        wallet
+            .delete_wallet_record_tags("type", "id", r#"["~b"]"#)
+            .await
+            .unwrap();
+        wallet.update_wallet_record_value("type", "id", "value2").await.unwrap();
+
+        let record: Value = serde_json::from_str(
+            &wallet
+                .get_wallet_record("type", "id", r#"{"retrieveType": true, "retrieveTags": true}"#)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            json!({"id": "id", "type": "type", "value": "value2", "tags": {"a": "3"}})
+        );
+
+        wallet.delete_wallet_record("type", "id").await.unwrap();
+        assert_eq!(
+            wallet.delete_wallet_record("type", "id").await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::WalletRecordNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reserved_records() {
+        let wallet = InMemoryWallet::new();
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        assert_eq!(
+            wallet
+                .get_wallet_record_value(KEY_CATEGORY, &verkey)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert_eq!(
+            wallet
+                .update_wallet_record_value(DID_CATEGORY, &did, "{}")
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert_eq!(
+            wallet
+                .add_wallet_record(TEMPORARY_DID_CATEGORY, &did, "{}", None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert!(wallet.iterate_wallet_records(KEY_CATEGORY, "{}", "{}").await.is_err());
+        assert!(wallet.delete_wallet_record(DID_CATEGORY, &did).await.is_err());
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+    }
+
+    #[tokio::test]
+    async fn test_iterate_records() {
+        let wallet = InMemoryWallet::new();
+        for i in 0..4 {
+            let tags = json!({"even": (i % 2 == 0).to_string(), "~index": i.to_string()}).to_string();
+            wallet
+                .add_wallet_record("type", &format!("id{}", i), "value", Some(&tags))
+                .await
+                .unwrap();
+        }
+
+        let mut ids: Vec<String> = wallet
+            .iterate_wallet_records("type", r#"{"even": "true", "~index": {"$gt": "0"}}"#, "{}")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap()
+            .iter()
+            .map(|record| {
+                serde_json::from_str::<Value>(record).unwrap()["id"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["id2"]);
+
+        assert!(wallet
+            .iterate_wallet_records("type", r#"{"even": {"$gt": "0"}}"#, "{}")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_message() {
+        let alice = InMemoryWallet::new();
+        let bob = InMemoryWallet::new();
+        let (_, alice_vk) = alice.create_and_store_my_did(None, None).await.unwrap();
+        let (_, bob_vk) = bob.create_and_store_my_did(None, None).await.unwrap();
+
+        let packed = alice
+            .pack_message(Some(&alice_vk), &json!([bob_vk]).to_string(), b"hello")
+            .await
+            .unwrap();
+        let unpacked: Value = serde_json::from_slice(&bob.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "hello", "sender_verkey": alice_vk, "recipient_verkey": bob_vk})
+        );
+        assert!(alice.unpack_message(&packed).await.is_err());
+
+        let anoncrypted = alice
+            .pack_message(None, &json!([bob_vk]).to_string(), b"hello")
+            .await
+            .unwrap();
+        let unpacked: Value = serde_json::from_slice(&bob.unpack_message(&anoncrypted).await.unwrap()).unwrap();
+        assert_eq!(unpacked, json!({"message": "hello", "recipient_verkey": bob_vk}));
+    }
+}
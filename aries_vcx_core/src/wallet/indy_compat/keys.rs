@@ -0,0 +1,220 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+use rand::RngCore;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+pub(crate) const KEY_LENGTH: usize = 32;
+
+pub(crate) type Key = [u8; KEY_LENGTH];
+
+// Record categories of the indy wallet, kept so that wallets can be migrated between the backends
+pub(crate) const KEY_CATEGORY: &str = "Indy::Key";
+pub(crate) const DID_CATEGORY: &str = "Indy::Did";
+pub(crate) const TEMPORARY_DID_CATEGORY: &str = "Indy::TemporaryDid";
+
+const VERKEY_SUFFIX: &str = ":ed25519";
+
+pub(crate) fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Ed25519 key pair, stored under its verkey in the [KEY_CATEGORY] records.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct LocalKey {
+    pub verkey: String,
+    pub signkey: String,
+}
+
+/// DID with its current verkey, stored under the DID in the [DID_CATEGORY] records.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct LocalDid {
+    pub did: String,
+    pub verkey: String,
+}
+
+impl LocalKey {
+    pub fn generate(seed: Option<&str>) -> VcxCoreResult<Self> {
+        let seed = match seed {
+            Some(seed) => convert_seed(seed)?,
+            None => random_bytes(),
+        };
+        let secret = SecretKey::from_bytes(&seed).map_err(invalid_key)?;
+        let keypair = Keypair {
+            public: PublicKey::from(&secret),
+            secret,
+        };
+
+        Ok(Self {
+            verkey: bs58::encode(keypair.public.as_bytes()).into_string(),
+            signkey: bs58::encode(keypair.to_bytes()).into_string(),
+        })
+    }
+
+    fn keypair(&self) -> VcxCoreResult<Keypair> {
+        let bytes = bs58::decode(&self.signkey).into_vec().map_err(|_| {
+            AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::NotBase58, "Stored signing key is not base58")
+        })?;
+        Keypair::from_bytes(&bytes).map_err(invalid_key)
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        Ok(self.keypair()?.sign(msg).to_bytes().to_vec())
+    }
+
+    /// The X25519 secret key corresponding to the Ed25519 key, as libsodium's
+    /// `crypto_sign_ed25519_sk_to_curve25519` derives it.
+    pub fn x25519_secret(&self) -> VcxCoreResult<Key> {
+        let hash = Sha512::digest(self.keypair()?.secret.as_bytes());
+        let mut secret = [0; KEY_LENGTH];
+        secret.copy_from_slice(&hash[..KEY_LENGTH]);
+        Ok(secret)
+    }
+
+    /// The X25519 shared secret of the key, converted with [LocalKey::x25519_secret], and a raw X25519 public key.
+    pub fn key_agreement(&self, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let their_pk: Key = their_pk.try_into().map_err(|_| {
+            AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, "Invalid X25519 public key")
+        })?;
+        let shared = x25519_dalek::x25519(self.x25519_secret()?, their_pk);
+        if shared == [0; KEY_LENGTH] {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                "X25519 public key is of low order",
+            ));
+        }
+        Ok(shared.to_vec())
+    }
+}
+
+fn invalid_key(err: ed25519_dalek::SignatureError) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidVerkey,
+        format!("Invalid Ed25519 key: {}", err),
+    )
+}
+
+/// Accepts the seed formats of the indy wallet: 32 characters, base64 or 64 hex characters.
+fn convert_seed(seed: &str) -> VcxCoreResult<Key> {
+    let invalid_seed = || {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!(
+                "Seed must be a {} bytes string, base64 string or {} bytes hex string",
+                KEY_LENGTH,
+                KEY_LENGTH * 2
+            ),
+        )
+    };
+
+    let bytes = if seed.len() == KEY_LENGTH {
+        seed.as_bytes().to_vec()
+    } else if seed.ends_with('=') {
+        base64::decode(seed).map_err(|_| invalid_seed())?
+    } else if seed.len() == KEY_LENGTH * 2 {
+        (0..seed.len())
+            .step_by(2)
+            .map(|i| seed.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid_seed)?
+    } else {
+        return Err(invalid_seed());
+    };
+
+    bytes.try_into().map_err(|_| invalid_seed())
+}
+
+pub(crate) fn decode_verkey(verkey: &str) -> VcxCoreResult<PublicKey> {
+    let verkey = verkey.strip_suffix(VERKEY_SUFFIX).unwrap_or(verkey);
+    let bytes = bs58::decode(verkey).into_vec().map_err(|_| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::NotBase58,
+            format!("Verkey is not base58: {}", verkey),
+        )
+    })?;
+    PublicKey::from_bytes(&bytes).map_err(invalid_key)
+}
+
+/// The X25519 public key corresponding to the Ed25519 verkey.
+pub(crate) fn x25519_public(verkey: &str) -> VcxCoreResult<Key> {
+    CompressedEdwardsY(decode_verkey(verkey)?.to_bytes())
+        .decompress()
+        .map(|point| point.to_montgomery().to_bytes())
+        .ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidVerkey,
+                format!("Verkey is not a valid Ed25519 point: {}", verkey),
+            )
+        })
+}
+
+pub(crate) fn verify(verkey: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+    let signature = Signature::try_from(signature)
+        .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, "Invalid Ed25519 signature"))?;
+    Ok(decode_verkey(verkey)?.verify(msg, &signature).is_ok())
+}
+
+/// Unqualified DIDs are the first 16 bytes of the verkey, qualified by the method if one is given.
+pub(crate) fn did_from_verkey(verkey: &str, method_name: Option<&str>) -> VcxCoreResult<String> {
+    let id = bs58::encode(&decode_verkey(verkey)?.as_bytes()[..16]).into_string();
+    Ok(match method_name {
+        Some(method) => format!("did:{}:{}", method, id),
+        None => id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_seed() {
+        let seed = "000000000000000000000000Trustee1";
+        let hex_seed: String = seed.bytes().map(|byte| format!("{:02x}", byte)).collect();
+        let base64_seed = base64::encode(seed);
+
+        assert_eq!(&convert_seed(seed).unwrap(), seed.as_bytes());
+        assert_eq!(&convert_seed(&hex_seed).unwrap(), seed.as_bytes());
+        assert_eq!(&convert_seed(&base64_seed).unwrap(), seed.as_bytes());
+        assert!(convert_seed("too short").is_err());
+    }
+
+    #[test]
+    fn test_did_from_seed() {
+        let key = LocalKey::generate(Some("000000000000000000000000Trustee1")).unwrap();
+        assert_eq!(key.verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+        assert_eq!(did_from_verkey(&key.verkey, None).unwrap(), "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(
+            did_from_verkey(&key.verkey, Some("sov")).unwrap(),
+            "did:sov:V4SGRU86Z58d6TV7PBUe6f"
+        );
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let key = LocalKey::generate(None).unwrap();
+        let signature = key.sign(b"hello").unwrap();
+
+        assert!(verify(&key.verkey, b"hello", &signature).unwrap());
+        assert!(!verify(&key.verkey, b"hello!", &signature).unwrap());
+        assert!(verify(&key.verkey, b"hello", b"short").is_err());
+    }
+
+    #[test]
+    fn test_key_agreement() {
+        let alice = LocalKey::generate(None).unwrap();
+        let bob = LocalKey::generate(None).unwrap();
+
+        assert_eq!(
+            alice.key_agreement(&x25519_public(&bob.verkey).unwrap()).unwrap(),
+            bob.key_agreement(&x25519_public(&alice.verkey).unwrap()).unwrap()
+        );
+        assert!(alice.key_agreement(&[0; KEY_LENGTH]).is_err());
+        assert!(alice.key_agreement(b"short").is_err());
+    }
+}
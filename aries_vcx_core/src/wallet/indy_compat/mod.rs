@@ -0,0 +1,7 @@
+//! Keys, records and message envelopes in the formats of the indy wallet, shared by the wallets
+//! implemented without vdrtools.
+
+pub(crate) mod keys;
+pub(crate) mod pack;
+pub(crate) mod query;
+pub(crate) mod records;
@@ -0,0 +1,417 @@
+use blake2::digest::consts::U24;
+use blake2::{Blake2b, Digest};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+use poly1305::universal_hash::{NewUniversalHash, Output};
+use poly1305::Poly1305;
+use salsa20::cipher::{NewCipher, StreamCipher};
+use salsa20::{hsalsa20, XSalsa20};
+use serde::{Deserialize, Serialize};
+use x25519_dalek::{x25519, X25519_BASEPOINT_BYTES};
+
+use super::keys::{random_bytes, x25519_public, Key, LocalKey, KEY_LENGTH};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+// The envelope of Aries RFC 0019, in the exact shape the indy wallet produces it
+const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
+const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
+const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
+const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
+
+const BOX_NONCE_LENGTH: usize = 24;
+const TAG_LENGTH: usize = 16;
+const IETF_NONCE_LENGTH: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+struct Jwe {
+    protected: String,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Protected {
+    enc: String,
+    typ: String,
+    alg: String,
+    recipients: Vec<Recipient>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Recipient {
+    encrypted_key: String,
+    header: Header,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    kid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iv: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UnpackedMessage {
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sender_verkey: Option<String>,
+    recipient_verkey: String,
+}
+
+fn encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE)
+}
+
+fn decode(data: &str) -> VcxCoreResult<Vec<u8>> {
+    base64::decode_config(data, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| invalid_message("Packed message contains invalid base64url data"))
+}
+
+fn invalid_message(msg: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidMessageFormat, msg.to_string())
+}
+
+fn to_key(bytes: &[u8]) -> VcxCoreResult<Key> {
+    bytes
+        .try_into()
+        .map_err(|_| invalid_message("Packed message contains a key of invalid length"))
+}
+
+/// Encrypts the message with a fresh content encryption key, which is in turn encrypted for
+/// each of the receivers: with `crypto_box` from the sender if one is given (authcrypt),
+/// otherwise with `crypto_box_seal` (anoncrypt).
+pub(crate) fn pack(sender: Option<&LocalKey>, receiver_keys: &[String], msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    if receiver_keys.is_empty() {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            "No receiver keys found",
+        ));
+    }
+
+    let cek: Key = random_bytes();
+    let recipients = receiver_keys
+        .iter()
+        .map(|receiver_key| {
+            let their_pk = x25519_public(receiver_key)?;
+            let recipient = match sender {
+                Some(sender) => {
+                    let nonce = random_bytes::<BOX_NONCE_LENGTH>();
+                    Recipient {
+                        encrypted_key: encode(&crypto_box(&cek, &nonce, &their_pk, &sender.x25519_secret()?)),
+                        header: Header {
+                            kid: receiver_key.clone(),
+                            sender: Some(encode(&crypto_box_seal(sender.verkey.as_bytes(), &their_pk))),
+                            iv: Some(encode(&nonce)),
+                        },
+                    }
+                }
+                None => Recipient {
+                    encrypted_key: encode(&crypto_box_seal(&cek, &their_pk)),
+                    header: Header {
+                        kid: receiver_key.clone(),
+                        sender: None,
+                        iv: None,
+                    },
+                },
+            };
+            Ok(recipient)
+        })
+        .collect::<VcxCoreResult<Vec<_>>>()?;
+
+    let alg = match sender {
+        Some(_) => PROTECTED_HEADER_ALG_AUTH,
+        None => PROTECTED_HEADER_ALG_ANON,
+    };
+    let protected = Protected {
+        enc: PROTECTED_HEADER_ENC.to_string(),
+        typ: PROTECTED_HEADER_TYP.to_string(),
+        alg: alg.to_string(),
+        recipients,
+    };
+    let protected = encode(serde_json::to_string(&protected)?.as_bytes());
+
+    let iv = random_bytes::<IETF_NONCE_LENGTH>();
+    let payload = Payload {
+        msg,
+        aad: protected.as_bytes(),
+    };
+    let mut ciphertext = ChaCha20Poly1305::new(&cek.into())
+        .encrypt(&iv.into(), payload)
+        .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::EncodeError, "Failed to encrypt message"))?;
+    let tag = ciphertext.split_off(ciphertext.len() - TAG_LENGTH);
+
+    let jwe = Jwe {
+        protected,
+        iv: encode(&iv),
+        ciphertext: encode(&ciphertext),
+        tag: encode(&tag),
+    };
+    Ok(serde_json::to_vec(&jwe)?)
+}
+
+/// A message packed by [pack], with its protected header decoded.
+pub(crate) struct PackedMessage {
+    jwe: Jwe,
+    protected: Protected,
+}
+
+impl PackedMessage {
+    pub fn parse(msg: &[u8]) -> VcxCoreResult<Self> {
+        let jwe: Jwe = serde_json::from_slice(msg).map_err(|_| invalid_message("Message is not a packed message"))?;
+        let protected = serde_json::from_slice(&decode(&jwe.protected)?)
+            .map_err(|_| invalid_message("Packed message has invalid protected header"))?;
+        Ok(Self { jwe, protected })
+    }
+
+    pub fn recipient_keys(&self) -> impl Iterator<Item = &str> {
+        self.protected
+            .recipients
+            .iter()
+            .map(|recipient| recipient.header.kid.as_str())
+    }
+
+    /// Decrypts the message as the recipient owning the key, producing the JSON output of
+    /// the indy `unpack_message`: the message, the recipient verkey and the sender verkey, if known.
+    pub fn unpack(&self, key: &LocalKey) -> VcxCoreResult<Vec<u8>> {
+        let recipient = self
+            .protected
+            .recipients
+            .iter()
+            .find(|recipient| recipient.header.kid == key.verkey)
+            .ok_or_else(|| invalid_message("Packed message is not addressed to the key"))?;
+
+        let my_sk = key.x25519_secret()?;
+        let my_pk = x25519_public(&key.verkey)?;
+        let encrypted_key = decode(&recipient.encrypted_key)?;
+
+        let (sender_verkey, cek) = match (&recipient.header.sender, &recipient.header.iv) {
+            (Some(sender), Some(iv)) => {
+                let sender_verkey = String::from_utf8(crypto_box_seal_open(&decode(sender)?, &my_pk, &my_sk)?)
+                    .map_err(|_| invalid_message("Packed message has invalid sender"))?;
+                let nonce: [u8; BOX_NONCE_LENGTH] = decode(iv)?
+                    .try_into()
+                    .map_err(|_| invalid_message("Packed message has invalid nonce"))?;
+                let cek = crypto_box_open(&encrypted_key, &nonce, &x25519_public(&sender_verkey)?, &my_sk)?;
+                (Some(sender_verkey), cek)
+            }
+            (None, None) => (None, crypto_box_seal_open(&encrypted_key, &my_pk, &my_sk)?),
+            _ => return Err(invalid_message("Packed message has incomplete sender header")),
+        };
+        let cek = to_key(&cek)?;
+
+        let iv = decode(&self.jwe.iv)?;
+        let mut ciphertext = decode(&self.jwe.ciphertext)?;
+        ciphertext.extend(decode(&self.jwe.tag)?);
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: self.jwe.protected.as_bytes(),
+        };
+        // the indy wallet encrypts with ChaCha20Poly1305, others use XChaCha20Poly1305 as `enc` says
+        let message = match iv.len() {
+            IETF_NONCE_LENGTH => ChaCha20Poly1305::new(&cek.into()).decrypt(iv.as_slice().into(), payload),
+            BOX_NONCE_LENGTH => XChaCha20Poly1305::new(&cek.into()).decrypt(iv.as_slice().into(), payload),
+            _ => return Err(invalid_message("Packed message has invalid nonce")),
+        }
+        .map_err(|_| invalid_message("Failed to decrypt packed message"))?;
+
+        let unpacked = UnpackedMessage {
+            message: String::from_utf8(message).map_err(|_| invalid_message("Packed message is not UTF-8"))?,
+            sender_verkey,
+            recipient_verkey: key.verkey.clone(),
+        };
+        Ok(serde_json::to_vec(&unpacked)?)
+    }
+}
+
+// NaCl `crypto_box`: XSalsa20Poly1305 keyed by the X25519 shared secret passed through HSalsa20
+
+fn box_key(their_pk: &Key, my_sk: &Key) -> salsa20::Key {
+    let shared = x25519(*my_sk, *their_pk);
+    hsalsa20(&shared.into(), &Default::default())
+}
+
+fn box_cipher(their_pk: &Key, my_sk: &Key, nonce: &[u8; BOX_NONCE_LENGTH]) -> (XSalsa20, Poly1305) {
+    let mut cipher = XSalsa20::new(&box_key(their_pk, my_sk), nonce.into());
+    let mut mac_key = [0; KEY_LENGTH];
+    cipher.apply_keystream(&mut mac_key);
+    (cipher, Poly1305::new(&mac_key.into()))
+}
+
+fn crypto_box(msg: &[u8], nonce: &[u8; BOX_NONCE_LENGTH], their_pk: &Key, my_sk: &Key) -> Vec<u8> {
+    let (mut cipher, mac) = box_cipher(their_pk, my_sk, nonce);
+    let mut ciphertext = msg.to_vec();
+    cipher.apply_keystream(&mut ciphertext);
+
+    let mut boxed = mac.compute_unpadded(&ciphertext).into_bytes().to_vec();
+    boxed.extend(ciphertext);
+    boxed
+}
+
+fn crypto_box_open(
+    boxed: &[u8],
+    nonce: &[u8; BOX_NONCE_LENGTH],
+    their_pk: &Key,
+    my_sk: &Key,
+) -> VcxCoreResult<Vec<u8>> {
+    if boxed.len() < TAG_LENGTH {
+        return Err(invalid_message("Encrypted key is too short"));
+    }
+    let (tag, ciphertext) = boxed.split_at(TAG_LENGTH);
+
+    let (mut cipher, mac) = box_cipher(their_pk, my_sk, nonce);
+    if mac.compute_unpadded(ciphertext) != Output::new(*poly1305::Block::from_slice(tag)) {
+        return Err(invalid_message("Failed to decrypt encrypted key"));
+    }
+    let mut msg = ciphertext.to_vec();
+    cipher.apply_keystream(&mut msg);
+    Ok(msg)
+}
+
+fn seal_nonce(ephemeral_pk: &Key, their_pk: &Key) -> [u8; BOX_NONCE_LENGTH] {
+    Blake2b::<U24>::new()
+        .chain_update(ephemeral_pk)
+        .chain_update(their_pk)
+        .finalize()
+        .into()
+}
+
+fn crypto_box_seal(msg: &[u8], their_pk: &Key) -> Vec<u8> {
+    let ephemeral_sk: Key = random_bytes();
+    let ephemeral_pk = x25519(ephemeral_sk, X25519_BASEPOINT_BYTES);
+
+    let mut sealed = ephemeral_pk.to_vec();
+    sealed.extend(crypto_box(
+        msg,
+        &seal_nonce(&ephemeral_pk, their_pk),
+        their_pk,
+        &ephemeral_sk,
+    ));
+    sealed
+}
+
+fn crypto_box_seal_open(sealed: &[u8], my_pk: &Key, my_sk: &Key) -> VcxCoreResult<Vec<u8>> {
+    if sealed.len() < KEY_LENGTH {
+        return Err(invalid_message("Sealed box is too short"));
+    }
+    let (ephemeral_pk, boxed) = sealed.split_at(KEY_LENGTH);
+    let ephemeral_pk = to_key(ephemeral_pk)?;
+    crypto_box_open(boxed, &seal_nonce(&ephemeral_pk, my_pk), &ephemeral_pk, my_sk)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_crypto_box_libsodium_compatible() {
+        // secret keys and nonce of the NaCl `crypto_box` test vectors, output of libsodium `crypto_box_easy`
+        let alice_sk: Key = [
+            0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45, 0xdf, 0x4c,
+            0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+        ];
+        let bob_sk: Key = [
+            0x5d, 0xab, 0x08, 0x7e, 0x62, 0x4a, 0x8a, 0x4b, 0x79, 0xe1, 0x7f, 0x8b, 0x83, 0x80, 0x0e, 0xe6, 0x6f, 0x3b,
+            0xb1, 0x29, 0x26, 0x18, 0xb6, 0xfd, 0x1c, 0x2f, 0x8b, 0x27, 0xff, 0x88, 0xe0, 0xeb,
+        ];
+        let nonce = [
+            0x69, 0x69, 0x6e, 0xe9, 0x55, 0xb6, 0x2b, 0x73, 0xcd, 0x62, 0xbd, 0xa8, 0x75, 0xfc, 0x73, 0xd6, 0x82, 0x19,
+            0xe0, 0x03, 0x6b, 0x7a, 0x0b, 0x37,
+        ];
+        let boxed = [
+            0x0b, 0xbe, 0xaa, 0x76, 0x68, 0xcb, 0x16, 0x21, 0xc4, 0x2f, 0xe4, 0xd4, 0xe5, 0xa4, 0xaf, 0xcb, 0x58, 0xfb,
+            0x08, 0x36, 0x1b, 0xc9, 0x97, 0xc9, 0x7f, 0xee, 0x27,
+        ];
+        let alice_pk = x25519(alice_sk, X25519_BASEPOINT_BYTES);
+        let bob_pk = x25519(bob_sk, X25519_BASEPOINT_BYTES);
+
+        assert_eq!(crypto_box(b"hello world", &nonce, &bob_pk, &alice_sk), boxed);
+        assert_eq!(
+            crypto_box_open(&boxed, &nonce, &alice_pk, &bob_sk).unwrap(),
+            b"hello world"
+        );
+        assert!(crypto_box_open(&boxed[1..], &nonce, &alice_pk, &bob_sk).is_err());
+    }
+
+    #[test]
+    fn test_crypto_box_seal_open_libsodium() {
+        // libsodium `crypto_box_seal` of "hello" for the X25519 key converted from the Ed25519 key
+        let sealed =
+            base64::decode("nspggkOs+5+l25cl2uY/lEyG2eUjiXyhflAMxz5pNhwJFNONku+imGRdJPRqm5fYDvJ1OG0=").unwrap();
+        let key = LocalKey::generate(Some("000000000000000000000000Trustee1")).unwrap();
+
+        let opened = crypto_box_seal_open(
+            &sealed,
+            &x25519_public(&key.verkey).unwrap(),
+            &key.x25519_secret().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(opened, b"hello");
+    }
+
+    #[test]
+    fn test_crypto_box_seal_roundtrip() {
+        let key = LocalKey::generate(None).unwrap();
+        let my_pk = x25519_public(&key.verkey).unwrap();
+        let my_sk = key.x25519_secret().unwrap();
+
+        let mut sealed = crypto_box_seal(b"secret", &my_pk);
+        assert_eq!(crypto_box_seal_open(&sealed, &my_pk, &my_sk).unwrap(), b"secret");
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(crypto_box_seal_open(&sealed, &my_pk, &my_sk).is_err());
+    }
+
+    #[test]
+    fn test_pack_unpack_authcrypt() {
+        let sender = LocalKey::generate(None).unwrap();
+        let receiver = LocalKey::generate(None).unwrap();
+        let other = LocalKey::generate(None).unwrap();
+
+        let packed = pack(
+            Some(&sender),
+            &[other.verkey.clone(), receiver.verkey.clone()],
+            b"hello",
+        )
+        .unwrap();
+        let packed = PackedMessage::parse(&packed).unwrap();
+        assert_eq!(
+            packed.recipient_keys().collect::<Vec<_>>(),
+            [other.verkey.as_str(), receiver.verkey.as_str()]
+        );
+
+        let unpacked: Value = serde_json::from_slice(&packed.unpack(&receiver).unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({
+                "message": "hello",
+                "sender_verkey": sender.verkey,
+                "recipient_verkey": receiver.verkey,
+            })
+        );
+        assert!(packed.unpack(&sender).is_err());
+    }
+
+    #[test]
+    fn test_pack_unpack_anoncrypt() {
+        let receiver = LocalKey::generate(None).unwrap();
+
+        let packed = pack(None, std::slice::from_ref(&receiver.verkey), b"hello").unwrap();
+        let unpacked: Value =
+            serde_json::from_slice(&PackedMessage::parse(&packed).unwrap().unpack(&receiver).unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({
+                "message": "hello",
+                "recipient_verkey": receiver.verkey,
+            })
+        );
+
+        assert!(pack(None, &[], b"hello").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+#[cfg(feature = "in_memory_wallet")]
+use super::records::EntryTag;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+/// Prefix of the names of tags stored unencrypted, which can be compared in other ways than by equality.
+pub(crate) const PLAINTEXT_TAG_PREFIX: char = '~';
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TagName {
+    Encrypted(String),
+    Plaintext(String),
+}
+
+impl TagName {
+    pub fn parse(name: &str) -> Self {
+        match name.strip_prefix(PLAINTEXT_TAG_PREFIX) {
+            Some(name) => TagName::Plaintext(name.to_string()),
+            None => TagName::Encrypted(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagName::Encrypted(name) => f.write_str(name),
+            TagName::Plaintext(name) => write!(f, "{}{}", PLAINTEXT_TAG_PREFIX, name),
+        }
+    }
+}
+
+/// Record search query, parsed from the Wallet Query Language of the indy wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TagQuery {
+    And(Vec<TagQuery>),
+    Or(Vec<TagQuery>),
+    Not(Box<TagQuery>),
+    Eq(TagName, String),
+    Neq(TagName, String),
+    Gt(TagName, String),
+    Gte(TagName, String),
+    Lt(TagName, String),
+    Lte(TagName, String),
+    Like(TagName, String),
+    In(TagName, Vec<String>),
+}
+
+fn invalid_query<D: fmt::Display>(msg: D) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidInput,
+        format!("Invalid wallet query: {}", msg),
+    )
+}
+
+impl TagQuery {
+    pub fn from_wql(query: &str) -> VcxCoreResult<Self> {
+        let query: Value = serde_json::from_str(query).map_err(invalid_query)?;
+        match query {
+            Value::Object(query) => Self::parse_object(query),
+            other => Err(invalid_query(format!("query must be an object: {}", other))),
+        }
+    }
+
+    fn parse_object(query: Map<String, Value>) -> VcxCoreResult<Self> {
+        let mut operators = query
+            .into_iter()
+            .map(|(key, value)| Self::parse_operator(key, value))
+            .collect::<VcxCoreResult<Vec<_>>>()?;
+
+        match operators.len() {
+            1 => Ok(operators.remove(0)),
+            _ => Ok(TagQuery::And(operators)),
+        }
+    }
+
+    fn parse_list(values: Value) -> VcxCoreResult<Vec<Self>> {
+        match values {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Object(query) => Self::parse_object(query),
+                    other => Err(invalid_query(format!("expected an object: {}", other))),
+                })
+                .collect(),
+            other => Err(invalid_query(format!("expected an array: {}", other))),
+        }
+    }
+
+    fn parse_operator(key: String, value: Value) -> VcxCoreResult<Self> {
+        match (key.as_str(), value) {
+            ("$and", values) => Ok(TagQuery::And(Self::parse_list(values)?)),
+            ("$or", values) => Ok(TagQuery::Or(Self::parse_list(values)?)),
+            ("$not", Value::Object(query)) => Ok(TagQuery::Not(Box::new(Self::parse_object(query)?))),
+            ("$not", other) => Err(invalid_query(format!("$not expects an object: {}", other))),
+            (_, Value::String(value)) => Ok(TagQuery::Eq(TagName::parse(&key), value)),
+            (_, Value::Object(comparison)) => Self::parse_comparison(TagName::parse(&key), comparison),
+            (_, other) => Err(invalid_query(format!("unsupported value of tag {}: {}", key, other))),
+        }
+    }
+
+    fn parse_comparison(name: TagName, comparison: Map<String, Value>) -> VcxCoreResult<Self> {
+        let mut comparison = comparison.into_iter();
+        let (operator, value) = match (comparison.next(), comparison.next()) {
+            (Some(operator), None) => operator,
+            _ => return Err(invalid_query("tag comparison must have a single operator")),
+        };
+
+        let query = match (operator.as_str(), value) {
+            ("$in", Value::Array(values)) => TagQuery::In(
+                name,
+                values
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::String(value) => Ok(value),
+                        other => Err(invalid_query(format!("$in expects strings: {}", other))),
+                    })
+                    .collect::<VcxCoreResult<_>>()?,
+            ),
+            ("$eq", Value::String(value)) => TagQuery::Eq(name, value),
+            ("$neq", Value::String(value)) => TagQuery::Neq(name, value),
+            ("$gt" | "$gte" | "$lt" | "$lte" | "$like", _) if matches!(name, TagName::Encrypted(_)) => {
+                return Err(invalid_query(format!(
+                    "only equality can be queried on encrypted tag {}, prefix it with {} to store it unencrypted",
+                    name, PLAINTEXT_TAG_PREFIX
+                )))
+            }
+            ("$gt", Value::String(value)) => TagQuery::Gt(name, value),
+            ("$gte", Value::String(value)) => TagQuery::Gte(name, value),
+            ("$lt", Value::String(value)) => TagQuery::Lt(name, value),
+            ("$lte", Value::String(value)) => TagQuery::Lte(name, value),
+            ("$like", Value::String(value)) => TagQuery::Like(name, value),
+            (operator, value) => {
+                return Err(invalid_query(format!(
+                    "unsupported operator {} with value {}",
+                    operator, value
+                )))
+            }
+        };
+        Ok(query)
+    }
+
+    #[cfg(feature = "in_memory_wallet")]
+    /// Whether a record with the given tags matches the query, for the backends which cannot
+    /// translate it into a query of their own.
+    pub fn matches(&self, tags: &[EntryTag]) -> bool {
+        let tag_value = |name: &TagName| tags.iter().find(|tag| &tag.name == name).map(|tag| tag.value.as_str());
+        let compare =
+            |name: &TagName, predicate: &dyn Fn(&str) -> bool| matches!(tag_value(name), Some(tag) if predicate(tag));
+
+        match self {
+            TagQuery::And(queries) => queries.iter().all(|query| query.matches(tags)),
+            TagQuery::Or(queries) => queries.iter().any(|query| query.matches(tags)),
+            TagQuery::Not(query) => !query.matches(tags),
+            TagQuery::Eq(name, value) => compare(name, &|tag| tag == value),
+            TagQuery::Neq(name, value) => compare(name, &|tag| tag != value),
+            TagQuery::Gt(name, value) => compare(name, &|tag| tag > value.as_str()),
+            TagQuery::Gte(name, value) => compare(name, &|tag| tag >= value.as_str()),
+            TagQuery::Lt(name, value) => compare(name, &|tag| tag < value.as_str()),
+            TagQuery::Lte(name, value) => compare(name, &|tag| tag <= value.as_str()),
+            TagQuery::Like(name, pattern) => compare(name, &|tag| like(tag.as_bytes(), pattern.as_bytes())),
+            TagQuery::In(name, values) => compare(name, &|tag| values.iter().any(|value| tag == value)),
+        }
+    }
+}
+
+#[cfg(feature = "in_memory_wallet")]
+/// SQL `LIKE`, as SQLite evaluates it: `%` matches any sequence, `_` any single character, and
+/// ASCII letters match regardless of case.
+fn like(value: &[u8], pattern: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((b'%', rest)) => (0..=value.len()).any(|skip| like(&value[skip..], rest)),
+        Some((b'_', rest)) => match std::str::from_utf8(value).ok().and_then(|value| value.chars().next()) {
+            Some(first) => like(&value[first.len_utf8()..], rest),
+            None => false,
+        },
+        Some((expected, rest)) => match value.split_first() {
+            Some((actual, value_rest)) => actual.eq_ignore_ascii_case(expected) && like(value_rest, rest),
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wql() {
+        let query = TagQuery::from_wql(
+            r#"{
+                "sender": "alice",
+                "~time": { "$gt": "10" },
+                "$or": [{ "kind": { "$in": ["a", "b"] } }, { "$not": { "kind": { "$neq": "c" } } }]
+            }"#,
+        )
+        .unwrap();
+
+        let mut operators = match query {
+            TagQuery::And(operators) => operators,
+            other => panic!("expected $and, got {:?}", other),
+        };
+        operators.sort_by_key(|operator| format!("{:?}", operator));
+        assert_eq!(
+            operators,
+            [
+                TagQuery::Eq(TagName::Encrypted("sender".into()), "alice".into()),
+                TagQuery::Gt(TagName::Plaintext("time".into()), "10".into()),
+                TagQuery::Or(vec![
+                    TagQuery::In(TagName::Encrypted("kind".into()), vec!["a".into(), "b".into()]),
+                    TagQuery::Not(Box::new(TagQuery::Neq(TagName::Encrypted("kind".into()), "c".into()))),
+                ]),
+            ]
+        );
+
+        assert_eq!(TagQuery::from_wql("{}").unwrap(), TagQuery::And(vec![]));
+    }
+
+    #[test]
+    fn test_parse_invalid_wql() {
+        assert!(TagQuery::from_wql("[]").is_err());
+        assert!(TagQuery::from_wql(r#"{"tag": 1}"#).is_err());
+        assert!(TagQuery::from_wql(r#"{"tag": {"$in": "a"}}"#).is_err());
+        assert!(TagQuery::from_wql(r#"{"tag": {"$gt": "a", "$lt": "b"}}"#).is_err());
+        assert!(TagQuery::from_wql(r#"{"$not": []}"#).is_err());
+        assert!(TagQuery::from_wql(r#"{"tag": {"$gt": "a"}}"#).is_err());
+    }
+
+    #[cfg(feature = "in_memory_wallet")]
+    #[test]
+    fn test_matches() {
+        let tags = [
+            EntryTag {
+                name: TagName::parse("kind"),
+                value: "invitation".into(),
+            },
+            EntryTag {
+                name: TagName::parse("~time"),
+                value: "0020".into(),
+            },
+        ];
+        let matches = |query: &str| TagQuery::from_wql(query).unwrap().matches(&tags);
+
+        assert!(matches("{}"));
+        assert!(matches(r#"{"kind": "invitation", "~time": {"$gte": "0020"}}"#));
+        assert!(!matches(r#"{"kind": "invitation", "~time": {"$gt": "0020"}}"#));
+        assert!(matches(r#"{"$or": [{"kind": "request"}, {"~time": {"$lt": "0100"}}]}"#));
+        assert!(matches(r#"{"kind": {"$in": ["request", "invitation"]}}"#));
+        assert!(!matches(r#"{"kind": {"$neq": "invitation"}}"#));
+        assert!(!matches(r#"{"other": {"$neq": "invitation"}}"#));
+        assert!(matches(r#"{"$not": {"other": "invitation"}}"#));
+        assert!(matches(r#"{"~time": {"$like": "%2_"}}"#));
+        assert!(!matches(r#"{"~time": {"$like": "2%"}}"#));
+    }
+
+    #[cfg(feature = "in_memory_wallet")]
+    #[test]
+    fn test_like() {
+        assert!(like(b"Invitation", b"invit%"));
+        assert!(like(b"abc", b"a_c"));
+        assert!(like("aéc".as_bytes(), b"a_c"));
+        assert!(!like(b"abc", b"a_"));
+        assert!(like(b"", b"%"));
+    }
+}
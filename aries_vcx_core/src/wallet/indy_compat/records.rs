@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::query::TagName;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::wallet::migration::is_reserved_category;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EntryTag {
+    pub name: TagName,
+    pub value: String,
+}
+
+/// Record as the wallet backends store it, identified by its category and name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub category: String,
+    pub name: String,
+    pub value: String,
+    pub tags: Vec<EntryTag>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Options of record retrieval and search, as the indy wallet accepts them.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordOptions {
+    #[serde(default = "default_true")]
+    pub retrieve_records: bool,
+    #[serde(default)]
+    pub retrieve_type: bool,
+    #[serde(default = "default_true")]
+    pub retrieve_value: bool,
+    #[serde(default)]
+    pub retrieve_tags: bool,
+}
+
+impl RecordOptions {
+    pub fn from_json(options_json: &str) -> VcxCoreResult<Self> {
+        match options_json.trim() {
+            "" => Ok(serde_json::from_str("{}")?),
+            options_json => serde_json::from_str(options_json).map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidJson,
+                    format!("Invalid record options {}: {}", options_json, err),
+                )
+            }),
+        }
+    }
+}
+
+/// Keeps the records of the DIDs and keys, with their private parts, out of reach of the record
+/// API, as the indy wallet does.
+pub(crate) fn check_record_type(xtype: &str) -> VcxCoreResult<()> {
+    if is_reserved_category(xtype) {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!(
+                "Wallet records of type {} are reserved for the DIDs and keys of the wallet",
+                xtype
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Parses tags given as a JSON object of string values, names prefixed with `~` being unencrypted.
+pub(crate) fn parse_tags(tags_json: Option<&str>) -> VcxCoreResult<Vec<EntryTag>> {
+    let tags: HashMap<String, String> = match tags_json {
+        None => return Ok(Vec::new()),
+        Some(tags_json) => serde_json::from_str(tags_json).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Tags must be an object of strings, got {}: {}", tags_json, err),
+            )
+        })?,
+    };
+
+    Ok(tags
+        .into_iter()
+        .map(|(name, value)| EntryTag {
+            name: TagName::parse(&name),
+            value,
+        })
+        .collect())
+}
+
+pub(crate) fn parse_tag_names(tag_names_json: &str) -> VcxCoreResult<Vec<TagName>> {
+    let names: Vec<String> = serde_json::from_str(tag_names_json).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Tag names must be an array of strings, got {}: {}", tag_names_json, err),
+        )
+    })?;
+    Ok(names.iter().map(|name| TagName::parse(name)).collect())
+}
+
+/// Replaces the tags of the same names and adds the others.
+pub(crate) fn merge_tags(tags: &mut Vec<EntryTag>, new_tags: Vec<EntryTag>) {
+    tags.retain(|tag| !new_tags.iter().any(|new_tag| new_tag.name == tag.name));
+    tags.extend(new_tags);
+}
+
+/// JSON of the record in the format of the indy wallet, the fields not asked for being `null`.
+pub(crate) fn record_json(entry: Entry, options: &RecordOptions) -> String {
+    let tags = options.retrieve_tags.then(|| {
+        entry
+            .tags
+            .into_iter()
+            .map(|tag| (tag.name.to_string(), Value::String(tag.value)))
+            .collect::<Map<_, _>>()
+    });
+
+    json!({
+        "id": entry.name,
+        "type": options.retrieve_type.then_some(entry.category),
+        "value": options.retrieve_value.then_some(entry.value),
+        "tags": tags,
+    })
+    .to_string()
+}
@@ -13,6 +13,7 @@ use crate::{
 use crate::{SearchHandle, WalletHandle};
 
 use super::base_wallet::BaseWallet;
+use super::migration::MigrationSource;
 
 #[derive(Debug)]
 pub struct IndySdkWallet {
@@ -23,6 +24,34 @@ impl IndySdkWallet {
     pub fn new(wallet_handle: WalletHandle) -> Self {
         IndySdkWallet { wallet_handle }
     }
+
+    /// Reads out all the records of the wallet for a [WalletMigrator](super::migration::WalletMigrator),
+    /// including the DIDs, the keys with their private parts and the anoncreds objects which vdrtools
+    /// keeps out of [BaseWallet::iterate_wallet_records]. Only for migrating the wallet.
+    pub fn migration_source(&self) -> IndySdkMigrationSource {
+        IndySdkMigrationSource {
+            wallet_handle: self.wallet_handle,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IndySdkMigrationSource {
+    wallet_handle: WalletHandle,
+}
+
+#[async_trait]
+impl MigrationSource for IndySdkMigrationSource {
+    async fn iterate_migration_records(
+        &self,
+        xtype: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let search = indy::wallet::open_migration_search_wallet(self.wallet_handle, xtype, "{}", options).await?;
+        let iter = IndyWalletRecordIterator::new(self.wallet_handle, search);
+
+        Ok(Box::new(iter))
+    }
 }
 
 #[allow(unused_variables)]
@@ -119,6 +148,10 @@ impl BaseWallet for IndySdkWallet {
         indy::signing::verify(vk, msg, signature).await
     }
 
+    async fn key_agreement(&self, my_vk: &str, their_pk: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        indy::signing::key_agreement(self.wallet_handle, my_vk, their_pk).await
+    }
+
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         indy::signing::pack_message(self.wallet_handle, sender_vk, receiver_keys, msg).await
     }
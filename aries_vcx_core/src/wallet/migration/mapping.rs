@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::anoncreds::credx_categories::{
+    CATEGORY_CREDENTIAL, CATEGORY_CRED_DEF, CATEGORY_CRED_DEF_PRIV, CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+    CATEGORY_CRED_MAP_SCHEMA_ID, CATEGORY_CRED_SCHEMA, CATEGORY_LINK_SECRET, CATEGORY_REV_REG, CATEGORY_REV_REG_DEF,
+    CATEGORY_REV_REG_DEF_PRIV, CATEGORY_REV_REG_DELTA, CATEGORY_REV_REG_INFO,
+};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+// Record types of the objects vdrtools stores in its wallet
+pub const VDRTOOLS_CATEGORY_DID: &str = "Indy::Did";
+pub const VDRTOOLS_CATEGORY_TEMPORARY_DID: &str = "Indy::TemporaryDid";
+pub const VDRTOOLS_CATEGORY_THEIR_DID: &str = "Indy::TheirDid";
+pub const VDRTOOLS_CATEGORY_DID_METADATA: &str = "Indy::DidMetadata";
+pub const VDRTOOLS_CATEGORY_KEY: &str = "Indy::Key";
+pub const VDRTOOLS_CATEGORY_MASTER_SECRET: &str = "Indy::MasterSecret";
+pub const VDRTOOLS_CATEGORY_CREDENTIAL: &str = "Indy::Credential";
+pub const VDRTOOLS_CATEGORY_CRED_DEF: &str = "Indy::CredentialDefinition";
+pub const VDRTOOLS_CATEGORY_CRED_DEF_PRIV: &str = "Indy::CredentialDefinitionPrivateKey";
+pub const VDRTOOLS_CATEGORY_CRED_KEY_CORRECTNESS_PROOF: &str = "Indy::CredentialDefinitionCorrectnessProof";
+pub const VDRTOOLS_CATEGORY_SCHEMA: &str = "Indy::Schema";
+pub const VDRTOOLS_CATEGORY_SCHEMA_ID: &str = "Indy::SchemaId";
+pub const VDRTOOLS_CATEGORY_REV_REG: &str = "Indy::RevocationRegistry";
+pub const VDRTOOLS_CATEGORY_REV_REG_DELTA: &str = "Indy::RevocationRegistryDelta";
+pub const VDRTOOLS_CATEGORY_REV_REG_INFO: &str = "Indy::RevocationRegistryInfo";
+pub const VDRTOOLS_CATEGORY_REV_REG_DEF: &str = "Indy::RevocationRegistryDefinition";
+pub const VDRTOOLS_CATEGORY_REV_REG_DEF_PRIV: &str = "Indy::RevocationRegistryDefinitionPrivate";
+
+/// A wallet record as it is carried from one wallet to the other.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct MigrationRecord {
+    #[serde(skip)]
+    pub category: String,
+    pub id: String,
+    pub value: String,
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    pub tags: HashMap<String, String>,
+}
+
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<HashMap<String, String>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+impl MigrationRecord {
+    /// Parses a record as [BaseWallet](crate::wallet::base_wallet::BaseWallet) returns it,
+    /// retrieved with its value and tags.
+    pub(super) fn from_record_json(category: &str, record_json: &str) -> VcxCoreResult<Self> {
+        let mut record: Self = serde_json::from_str(record_json).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Failed to parse wallet record of type {}: {}", category, err),
+            )
+        })?;
+        record.category = category.to_string();
+        Ok(record)
+    }
+}
+
+/// Converts a record whose value or tags are not in the format the target wallet's users expect.
+pub type RecordConverter = fn(MigrationRecord) -> VcxCoreResult<MigrationRecord>;
+
+/// Where the records of a category of the source wallet go in the target wallet, and how they
+/// are converted on the way.
+#[derive(Clone, Debug)]
+pub struct CategoryMapping {
+    pub source: String,
+    pub target: String,
+    converter: Option<RecordConverter>,
+}
+
+impl CategoryMapping {
+    pub fn unchanged(category: &str) -> Self {
+        Self::renamed(category, category)
+    }
+
+    pub fn renamed(source: &str, target: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+            converter: None,
+        }
+    }
+
+    pub fn converted(source: &str, target: &str, converter: RecordConverter) -> Self {
+        Self {
+            converter: Some(converter),
+            ..Self::renamed(source, target)
+        }
+    }
+
+    pub(super) fn apply(&self, record: MigrationRecord) -> VcxCoreResult<MigrationRecord> {
+        let record = MigrationRecord {
+            category: self.target.clone(),
+            ..record
+        };
+        match self.converter {
+            Some(converter) => converter(record),
+            None => Ok(record),
+        }
+    }
+}
+
+/// The categories of a wallet of vdrtools, mapped to the ones of the wallets used along with
+/// [IndyCredxAnonCreds](crate::anoncreds::credx_anoncreds::IndyCredxAnonCreds). DIDs and keys keep
+/// their categories, which the vdrtools-free wallets share. Categories of non-secret records the
+/// application stores itself are to be added to these.
+pub fn vdrtools_to_credx() -> Vec<CategoryMapping> {
+    vec![
+        CategoryMapping::unchanged(VDRTOOLS_CATEGORY_DID),
+        CategoryMapping::unchanged(VDRTOOLS_CATEGORY_TEMPORARY_DID),
+        CategoryMapping::unchanged(VDRTOOLS_CATEGORY_THEIR_DID),
+        CategoryMapping::unchanged(VDRTOOLS_CATEGORY_DID_METADATA),
+        CategoryMapping::unchanged(VDRTOOLS_CATEGORY_KEY),
+        CategoryMapping::converted(
+            VDRTOOLS_CATEGORY_MASTER_SECRET,
+            CATEGORY_LINK_SECRET,
+            convert_master_secret,
+        ),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_CREDENTIAL, CATEGORY_CREDENTIAL),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_CRED_DEF, CATEGORY_CRED_DEF),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_CRED_DEF_PRIV, CATEGORY_CRED_DEF_PRIV),
+        CategoryMapping::renamed(
+            VDRTOOLS_CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+            CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+        ),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_SCHEMA, CATEGORY_CRED_SCHEMA),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_SCHEMA_ID, CATEGORY_CRED_MAP_SCHEMA_ID),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_REV_REG, CATEGORY_REV_REG),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_REV_REG_DELTA, CATEGORY_REV_REG_DELTA),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_REV_REG_INFO, CATEGORY_REV_REG_INFO),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_REV_REG_DEF, CATEGORY_REV_REG_DEF),
+        CategoryMapping::renamed(VDRTOOLS_CATEGORY_REV_REG_DEF_PRIV, CATEGORY_REV_REG_DEF_PRIV),
+    ]
+}
+
+/// vdrtools stores the master secret as the JSON `{"value": {"ms": "<decimal>"}}`, credx as the
+/// decimal itself.
+fn convert_master_secret(record: MigrationRecord) -> VcxCoreResult<MigrationRecord> {
+    let master_secret: Value = serde_json::from_str(&record.value)?;
+    let decimal = master_secret["value"]["ms"].as_str().ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Master secret {} has no decimal value", record.id),
+        )
+    })?;
+
+    Ok(MigrationRecord {
+        value: decimal.to_string(),
+        ..record
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_master_secret() {
+        let mapping = vdrtools_to_credx()
+            .into_iter()
+            .find(|mapping| mapping.source == VDRTOOLS_CATEGORY_MASTER_SECRET)
+            .unwrap();
+        let record = MigrationRecord::from_record_json(
+            VDRTOOLS_CATEGORY_MASTER_SECRET,
+            r#"{"id": "main", "type": null, "value": "{\"value\":{\"ms\":\"1234\"}}", "tags": null}"#,
+        )
+        .unwrap();
+
+        let converted = mapping.apply(record).unwrap();
+        assert_eq!(converted.category, CATEGORY_LINK_SECRET);
+        assert_eq!(converted.id, "main");
+        assert_eq!(converted.value, "1234");
+        assert!(converted.tags.is_empty());
+
+        let invalid = MigrationRecord {
+            value: "{}".into(),
+            ..converted
+        };
+        assert!(mapping.apply(invalid).is_err());
+    }
+}
@@ -0,0 +1,533 @@
+//! Copies the records of a wallet into a wallet of another backend, category by category. The
+//! records are read from a [MigrationSource] and written to a [MigrationTarget]. Records already in
+//! the target wallet are skipped, so that an interrupted migration can be run again until it
+//! completes.
+
+pub mod mapping;
+
+use async_trait::async_trait;
+
+use self::mapping::{
+    CategoryMapping, MigrationRecord, VDRTOOLS_CATEGORY_DID, VDRTOOLS_CATEGORY_KEY, VDRTOOLS_CATEGORY_TEMPORARY_DID,
+};
+use super::base_wallet::BaseWallet;
+use crate::errors::error::{AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+const RECORD_OPTIONS: &str = r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"#;
+
+/// Record types of the DIDs and keys of a wallet, which its record API does not give access to.
+pub const RESERVED_CATEGORIES: [&str; 3] = [
+    VDRTOOLS_CATEGORY_KEY,
+    VDRTOOLS_CATEGORY_DID,
+    VDRTOOLS_CATEGORY_TEMPORARY_DID,
+];
+
+pub fn is_reserved_category(xtype: &str) -> bool {
+    RESERVED_CATEGORIES.contains(&xtype)
+}
+
+/// Where the records to migrate are read from, including the DIDs and keys which wallets keep out
+/// of their record API. A vdrtools wallet, which keeps its anoncreds objects out of searches as
+/// well, provides one with
+/// [IndySdkWallet::migration_source](crate::wallet::indy_wallet::IndySdkWallet::migration_source).
+#[async_trait]
+pub trait MigrationSource: Send + Sync {
+    /// Iterates over all the records of the type, as [BaseWallet::iterate_wallet_records] does.
+    async fn iterate_migration_records(
+        &self,
+        xtype: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>>;
+}
+
+/// Where the migrated records are written. The records of the [RESERVED_CATEGORIES] are imported
+/// through this trait, the others are added through [BaseWallet]. vdrtools wallets are only
+/// migrated from, so they are no target.
+#[async_trait]
+pub trait MigrationTarget: BaseWallet {
+    /// Retrieves a record of a reserved category, as [BaseWallet::get_wallet_record] does.
+    async fn get_reserved_record(&self, xtype: &str, id: &str, options: &str) -> VcxCoreResult<String>;
+
+    /// Imports a DID or key record of a reserved category, as [BaseWallet::add_wallet_record] does.
+    async fn import_reserved_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordFailure {
+    pub id: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CategoryReport {
+    pub source: String,
+    pub target: String,
+    /// Records written to the target wallet, or which would be on a dry run.
+    pub migrated: usize,
+    /// Records already in the target wallet, as a previous run left them.
+    pub skipped: usize,
+    pub failed: Vec<RecordFailure>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    pub categories: Vec<CategoryReport>,
+}
+
+impl MigrationReport {
+    pub fn migrated(&self) -> usize {
+        self.categories.iter().map(|category| category.migrated).sum()
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.categories.iter().map(|category| category.skipped).sum()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.categories.iter().map(|category| category.failed.len()).sum()
+    }
+
+    /// Whether all the records are in the target wallet, or would be on a dry run.
+    pub fn is_complete(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CategoryVerification {
+    pub source: String,
+    pub target: String,
+    pub matching: usize,
+    /// Ids of the records which are not in the target wallet.
+    pub missing: Vec<String>,
+    /// Ids of the records whose value or tags differ in the target wallet.
+    pub mismatched: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub categories: Vec<CategoryVerification>,
+}
+
+impl VerificationReport {
+    pub fn matching(&self) -> usize {
+        self.categories.iter().map(|category| category.matching).sum()
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.categories
+            .iter()
+            .all(|category| category.missing.is_empty() && category.mismatched.is_empty())
+    }
+}
+
+enum TargetRecord {
+    Missing,
+    Matching,
+    Mismatched,
+}
+
+pub struct WalletMigrator<'a> {
+    source: &'a dyn MigrationSource,
+    target: &'a dyn MigrationTarget,
+    mappings: Vec<CategoryMapping>,
+}
+
+impl<'a> WalletMigrator<'a> {
+    /// Migrates the records of the categories of the mappings, see [mapping::vdrtools_to_credx]
+    /// for moving off a vdrtools wallet.
+    pub fn new(
+        source: &'a dyn MigrationSource,
+        target: &'a dyn MigrationTarget,
+        mappings: Vec<CategoryMapping>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            mappings,
+        }
+    }
+
+    pub async fn migrate(&self) -> VcxCoreResult<MigrationReport> {
+        self.run(false).await
+    }
+
+    /// Converts the records and counts the ones to be migrated, without writing to the target wallet.
+    pub async fn dry_run(&self) -> VcxCoreResult<MigrationReport> {
+        self.run(true).await
+    }
+
+    /// Checks that all the records of the source wallet are in the target wallet, as converted.
+    pub async fn verify(&self) -> VcxCoreResult<VerificationReport> {
+        let mut report = VerificationReport::default();
+
+        for mapping in &self.mappings {
+            let mut verification = CategoryVerification {
+                source: mapping.source.clone(),
+                target: mapping.target.clone(),
+                ..Default::default()
+            };
+
+            let mut records = self.source_records(mapping).await?;
+            while let Some(record) = records.next().await {
+                let record = MigrationRecord::from_record_json(&mapping.source, &record?)?;
+                let record = mapping.apply(record)?;
+                match self.find_target_record(&record).await? {
+                    TargetRecord::Matching => verification.matching += 1,
+                    TargetRecord::Missing => verification.missing.push(record.id),
+                    TargetRecord::Mismatched => verification.mismatched.push(record.id),
+                }
+            }
+            report.categories.push(verification);
+        }
+
+        Ok(report)
+    }
+
+    async fn run(&self, dry_run: bool) -> VcxCoreResult<MigrationReport> {
+        let mut report = MigrationReport {
+            dry_run,
+            categories: Vec::new(),
+        };
+
+        for mapping in &self.mappings {
+            let mut category_report = CategoryReport {
+                source: mapping.source.clone(),
+                target: mapping.target.clone(),
+                ..Default::default()
+            };
+
+            let mut records = self.source_records(mapping).await?;
+            while let Some(record) = records.next().await {
+                let record = record.and_then(|record| MigrationRecord::from_record_json(&mapping.source, &record));
+                match self.migrate_record(mapping, record, dry_run).await {
+                    Ok(true) => category_report.migrated += 1,
+                    Ok(false) => category_report.skipped += 1,
+                    Err(failure) => {
+                        warn!(
+                            "Failed to migrate wallet record {} of type {}: {}",
+                            failure.id, mapping.source, failure.reason
+                        );
+                        category_report.failed.push(failure)
+                    }
+                }
+            }
+
+            info!(
+                "Migrated wallet records of type {} to {}: {} migrated, {} skipped, {} failed",
+                mapping.source,
+                mapping.target,
+                category_report.migrated,
+                category_report.skipped,
+                category_report.failed.len()
+            );
+            report.categories.push(category_report);
+        }
+
+        Ok(report)
+    }
+
+    /// Returns whether the record was migrated, rather than found already in the target wallet.
+    async fn migrate_record(
+        &self,
+        mapping: &CategoryMapping,
+        record: VcxCoreResult<MigrationRecord>,
+        dry_run: bool,
+    ) -> Result<bool, RecordFailure> {
+        let failure = |id: &str, reason: String| RecordFailure {
+            id: id.to_string(),
+            reason,
+        };
+
+        let record = record.map_err(|err| failure("", err.to_string()))?;
+        let id = record.id.clone();
+        let record = mapping.apply(record).map_err(|err| failure(&id, err.to_string()))?;
+
+        match self.find_target_record(&record).await {
+            Ok(TargetRecord::Matching) => return Ok(false),
+            Ok(TargetRecord::Mismatched) => {
+                return Err(failure(
+                    &id,
+                    "a different record of the same id is already in the target wallet".to_string(),
+                ))
+            }
+            Ok(TargetRecord::Missing) => {}
+            Err(err) => return Err(failure(&id, err.to_string())),
+        }
+
+        if !dry_run {
+            let tags_json = match record.tags.is_empty() {
+                true => None,
+                false => Some(serde_json::to_string(&record.tags).map_err(|err| failure(&id, err.to_string()))?),
+            };
+            let added = match is_reserved_category(&record.category) {
+                true => {
+                    self.target
+                        .import_reserved_record(&record.category, &record.id, &record.value, tags_json.as_deref())
+                        .await
+                }
+                false => {
+                    self.target
+                        .add_wallet_record(&record.category, &record.id, &record.value, tags_json.as_deref())
+                        .await
+                }
+            };
+            added.map_err(|err| failure(&id, err.to_string()))?;
+        }
+        Ok(true)
+    }
+
+    async fn source_records(
+        &self,
+        mapping: &CategoryMapping,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        self.source
+            .iterate_migration_records(&mapping.source, RECORD_OPTIONS)
+            .await
+    }
+
+    async fn find_target_record(&self, record: &MigrationRecord) -> VcxCoreResult<TargetRecord> {
+        let existing = match is_reserved_category(&record.category) {
+            true => {
+                self.target
+                    .get_reserved_record(&record.category, &record.id, RECORD_OPTIONS)
+                    .await
+            }
+            false => {
+                self.target
+                    .get_wallet_record(&record.category, &record.id, RECORD_OPTIONS)
+                    .await
+            }
+        };
+        let existing = match existing {
+            Ok(existing) => MigrationRecord::from_record_json(&record.category, &existing)?,
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => return Ok(TargetRecord::Missing),
+            Err(err) => return Err(err),
+        };
+
+        match existing.value == record.value && existing.tags == record.tags {
+            true => Ok(TargetRecord::Matching),
+            false => Ok(TargetRecord::Mismatched),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "in_memory_wallet"))]
+mod tests {
+    #[cfg(feature = "vdrtools")]
+    use serde_json::json;
+
+    use super::mapping::{
+        vdrtools_to_credx, VDRTOOLS_CATEGORY_CREDENTIAL, VDRTOOLS_CATEGORY_KEY, VDRTOOLS_CATEGORY_MASTER_SECRET,
+    };
+    use super::*;
+    use crate::anoncreds::credx_categories::{CATEGORY_CREDENTIAL, CATEGORY_LINK_SECRET};
+    use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    async fn source_wallet() -> (InMemoryWallet, String, String) {
+        let source = InMemoryWallet::new();
+        let (did, verkey) = source.create_and_store_my_did(None, None).await.unwrap();
+        source
+            .add_wallet_record(
+                VDRTOOLS_CATEGORY_MASTER_SECRET,
+                "main",
+                r#"{"value":{"ms":"1234"}}"#,
+                None,
+            )
+            .await
+            .unwrap();
+        for i in 0..3 {
+            source
+                .add_wallet_record(
+                    VDRTOOLS_CATEGORY_CREDENTIAL,
+                    &format!("cred{}", i),
+                    "{}",
+                    Some(r#"{"schema_name": "degree", "attr::name::marker": "1"}"#),
+                )
+                .await
+                .unwrap();
+        }
+        source.add_wallet_record("cache", "rev_reg", "{}", None).await.unwrap();
+        (source, did, verkey)
+    }
+
+    fn mappings() -> Vec<CategoryMapping> {
+        let mut mappings = vdrtools_to_credx();
+        mappings.push(CategoryMapping::unchanged("cache"));
+        mappings
+    }
+
+    #[tokio::test]
+    async fn test_migrate_wallet() {
+        let (source, did, verkey) = source_wallet().await;
+        let target = InMemoryWallet::new();
+        let migrator = WalletMigrator::new(&source, &target, mappings());
+
+        let dry_run = migrator.dry_run().await.unwrap();
+        assert!(dry_run.dry_run && dry_run.is_complete());
+        assert_eq!(dry_run.migrated(), 7);
+        assert!(!migrator.verify().await.unwrap().is_verified());
+
+        let report = migrator.migrate().await.unwrap();
+        assert_eq!((report.migrated(), report.skipped(), report.failed()), (7, 0, 0));
+
+        let verification = migrator.verify().await.unwrap();
+        assert!(verification.is_verified());
+        assert_eq!(verification.matching(), 7);
+
+        assert_eq!(target.key_for_local_did(&did).await.unwrap(), verkey);
+        let signature = target.sign(&verkey, b"msg").await.unwrap();
+        assert!(target.verify(&verkey, b"msg", &signature).await.unwrap());
+        // the key was imported, as the record API of the target wallet keeps keys out of reach
+        assert_eq!(
+            target
+                .get_wallet_record_value(VDRTOOLS_CATEGORY_KEY, &verkey)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidInput
+        );
+        assert_eq!(
+            target
+                .get_wallet_record_value(CATEGORY_LINK_SECRET, "main")
+                .await
+                .unwrap(),
+            "1234"
+        );
+        let credentials = target
+            .iterate_wallet_records(CATEGORY_CREDENTIAL, r#"{"schema_name": "degree"}"#, "{}")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(credentials.len(), 3);
+    }
+
+    #[cfg(feature = "vdrtools")]
+    #[tokio::test]
+    async fn test_migrate_indy_wallet() {
+        use crate::anoncreds::base_anoncreds::BaseAnonCreds;
+        use crate::anoncreds::indy_anoncreds::IndySdkAnonCreds;
+        use crate::indy::utils::test_setup::with_wallet;
+        use crate::wallet::indy_wallet::IndySdkWallet;
+
+        with_wallet(|wallet_handle| async move {
+            let source = IndySdkWallet::new(wallet_handle);
+            let anoncreds = IndySdkAnonCreds::new(wallet_handle);
+            let (did, verkey) = source.create_and_store_my_did(None, None).await.unwrap();
+
+            let (_, schema_json) = anoncreds
+                .issuer_create_schema(&did, "degree", "1.0", r#"["name"]"#)
+                .await
+                .unwrap();
+            let (cred_def_id, cred_def_json) = anoncreds
+                .issuer_create_and_store_credential_def(
+                    &did,
+                    &schema_json,
+                    "tag",
+                    None,
+                    r#"{"support_revocation": false}"#,
+                )
+                .await
+                .unwrap();
+            anoncreds.prover_create_link_secret("main").await.unwrap();
+            let offer = anoncreds.issuer_create_credential_offer(&cred_def_id).await.unwrap();
+            let (request, request_metadata) = anoncreds
+                .prover_create_credential_req(&did, &offer, &cred_def_json, "main")
+                .await
+                .unwrap();
+            let (credential, _, _) = anoncreds
+                .issuer_create_credential(
+                    &offer,
+                    &request,
+                    r#"{"name": {"raw": "alice", "encoded": "1139481716457488690172217916278103335"}}"#,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            anoncreds
+                .prover_store_credential(Some("cred"), &request_metadata, &credential, &cred_def_json, None)
+                .await
+                .unwrap();
+
+            // the records vdrtools keeps for itself are not in the searches of the wallet
+            assert!(source
+                .iterate_wallet_records(VDRTOOLS_CATEGORY_KEY, "{}", "{}")
+                .await
+                .is_err());
+
+            let target = InMemoryWallet::new();
+            let source = source.migration_source();
+            let migrator = WalletMigrator::new(&source, &target, vdrtools_to_credx());
+            let report = migrator.migrate().await.unwrap();
+            assert!(report.is_complete());
+            assert!(migrator.verify().await.unwrap().is_verified());
+
+            assert_eq!(target.key_for_local_did(&did).await.unwrap(), verkey);
+            let signature = target.sign(&verkey, b"msg").await.unwrap();
+            assert!(target.verify(&verkey, b"msg", &signature).await.unwrap());
+            let link_secret = target
+                .get_wallet_record_value(CATEGORY_LINK_SECRET, "main")
+                .await
+                .unwrap();
+            assert!(link_secret.chars().all(|c| c.is_ascii_digit()));
+            let credentials = target
+                .iterate_wallet_records(
+                    CATEGORY_CREDENTIAL,
+                    &json!({ "cred_def_id": cred_def_id, "attr::name::value": "alice" }).to_string(),
+                    "{}",
+                )
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            assert_eq!(credentials.len(), 1);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_resume_migration() {
+        let (source, _, _) = source_wallet().await;
+        let target = InMemoryWallet::new();
+        target
+            .add_wallet_record(CATEGORY_LINK_SECRET, "main", "1234", None)
+            .await
+            .unwrap();
+        target
+            .add_wallet_record(CATEGORY_CREDENTIAL, "cred0", "{\"other\": true}", None)
+            .await
+            .unwrap();
+
+        let migrator = WalletMigrator::new(&source, &target, mappings());
+        let report = migrator.migrate().await.unwrap();
+        assert_eq!((report.migrated(), report.skipped(), report.failed()), (5, 1, 1));
+        assert!(!report.is_complete());
+
+        let credentials = report
+            .categories
+            .iter()
+            .find(|category| category.target == CATEGORY_CREDENTIAL)
+            .unwrap();
+        assert_eq!(credentials.failed[0].id, "cred0");
+
+        let verification = migrator.verify().await.unwrap();
+        assert_eq!(verification.matching(), 6);
+        assert!(!verification.is_verified());
+
+        let report = migrator.migrate().await.unwrap();
+        assert_eq!((report.migrated(), report.skipped(), report.failed()), (0, 6, 1));
+    }
+}
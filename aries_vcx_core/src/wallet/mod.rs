@@ -1,4 +1,11 @@
 pub mod agency_client_wallet;
+#[cfg(feature = "askar_wallet")]
+pub mod askar;
 pub mod base_wallet;
+#[cfg(feature = "in_memory_wallet")]
+pub mod in_memory_wallet;
+#[cfg(any(feature = "askar_wallet", feature = "in_memory_wallet"))]
+mod indy_compat;
 #[cfg(feature = "vdrtools")]
 pub mod indy_wallet;
+pub mod migration;
@@ -0,0 +1,248 @@
+use std::io::Cursor;
+
+use did_doc::schema::{
+    did_doc::DidDocument, service::Service, verification_method::VerificationMethod,
+    verification_method::VerificationMethodKind,
+};
+use did_parser::DidUrl;
+use percent_encoding::percent_decode_str;
+use url::Url;
+
+use super::{
+    dereferencing_error::DidDereferencingError, dereferencing_metadata::DidDereferencingMetadata,
+    dereferencing_output::DidDereferencingOutput,
+};
+use crate::{error::GenericError, traits::resolvable::resolution_output::DidResolutionOutput};
+
+pub const CONTENT_TYPE_DID_JSON: &str = "application/did+json";
+pub const CONTENT_TYPE_URI_LIST: &str = "text/uri-list";
+
+const SERVICE_QUERY: &str = "service";
+const RELATIVE_REF_QUERY: &str = "relativeRef";
+
+fn fragment_matches(id: &str, did_url: &DidUrl, fragment: &str) -> bool {
+    id == did_url.did_url()
+        || id
+            .strip_suffix(fragment)
+            .and_then(|id| id.strip_suffix('#'))
+            .is_some()
+}
+
+fn service_by_fragment<'a>(
+    did_document: &'a DidDocument,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Option<&'a Service> {
+    did_document
+        .service()
+        .iter()
+        .find(|service| fragment_matches(&service.id().to_string(), did_url, fragment))
+}
+
+fn verification_method_by_fragment<'a>(
+    did_document: &'a DidDocument,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Option<&'a VerificationMethod> {
+    let embedded = [
+        did_document.authentication(),
+        did_document.assertion_method(),
+        did_document.key_agreement(),
+        did_document.capability_invocation(),
+        did_document.capability_delegation(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|method| match method {
+        VerificationMethodKind::Resolved(method) => Some(method),
+        VerificationMethodKind::Resolvable(_) => None,
+    });
+
+    did_document
+        .verification_method()
+        .iter()
+        .chain(embedded)
+        .find(|method| fragment_matches(method.id().did_url(), did_url, fragment))
+}
+
+/// Selects the service named by the `service` query parameter and builds the URL it redirects to,
+/// resolving the `relativeRef` query parameter, if any, against the service endpoint.
+fn service_endpoint_url(
+    did_document: &DidDocument,
+    did_url: &DidUrl,
+    service: &str,
+) -> Result<String, DidDereferencingError> {
+    let service = service_by_fragment(did_document, did_url, service)
+        .ok_or(DidDereferencingError::NotFound)?;
+
+    let relative_ref = match did_url.queries().get(RELATIVE_REF_QUERY) {
+        Some(relative_ref) => relative_ref.to_owned(),
+        None => return Ok(service.service_endpoint().to_owned()),
+    };
+    let relative_ref = percent_decode_str(&relative_ref)
+        .decode_utf8()
+        .map_err(|_| DidDereferencingError::InvalidDid)?;
+
+    Url::parse(service.service_endpoint())
+        .and_then(|endpoint| endpoint.join(&relative_ref))
+        .map(|url| url.to_string())
+        .map_err(|_| DidDereferencingError::InvalidDid)
+}
+
+/// Dereferences a DID URL against the DID document it was resolved to.
+///
+/// A `service` query parameter dereferences to the endpoint URL of the selected service,
+/// combined with the `relativeRef` query parameter if present. Otherwise, a fragment dereferences
+/// to the verification method or service it identifies, and a plain DID to the whole document.
+/// Paths are method specific and are not handled here.
+pub fn dereference_did_document(
+    resolution_output: &DidResolutionOutput,
+    did_url: &DidUrl,
+) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+    let did_document = resolution_output.did_document();
+
+    let (content_type, content) = match (did_url.queries().get(SERVICE_QUERY), did_url.fragment()) {
+        (Some(service), _) => (
+            CONTENT_TYPE_URI_LIST,
+            service_endpoint_url(did_document, did_url, service)?.into_bytes(),
+        ),
+        (None, Some(fragment)) => {
+            let content = match (
+                service_by_fragment(did_document, did_url, fragment),
+                verification_method_by_fragment(did_document, did_url, fragment),
+            ) {
+                (Some(service), None) => serde_json::to_vec(service)?,
+                (None, Some(method)) => serde_json::to_vec(method)?,
+                (None, None) => return Err(Box::new(DidDereferencingError::NotFound)),
+                (Some(_), Some(_)) => return Err(Box::new(DidDereferencingError::InvalidDid)),
+            };
+            (CONTENT_TYPE_DID_JSON, content)
+        }
+        (None, None) if did_url.path().is_none() => {
+            (CONTENT_TYPE_DID_JSON, serde_json::to_vec(did_document)?)
+        }
+        (None, None) => return Err(Box::new(DidDereferencingError::NotFound)),
+    };
+
+    let dereferencing_metadata = DidDereferencingMetadata::builder()
+        .content_type(content_type.to_string())
+        .build();
+
+    Ok(DidDereferencingOutput::builder(Cursor::new(content))
+        .content_metadata(resolution_output.did_document_metadata().clone())
+        .dereferencing_metadata(dereferencing_metadata)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use did_doc::schema::{
+        did_doc::DidDocumentBuilder,
+        service::ServiceBuilder,
+        types::{uri::Uri, url::Url as ServiceUrl},
+    };
+    use did_parser::Did;
+    use serde_json::Value;
+
+    use super::*;
+
+    const DID: &str = "did:example:123456789abcdefghi";
+
+    fn resolution_output() -> DidResolutionOutput {
+        let did = Did::parse(DID.to_string()).unwrap();
+        let verification_method = VerificationMethod::builder(
+            DidUrl::parse(format!("{}#key-1", DID)).unwrap(),
+            did.clone(),
+            "Ed25519VerificationKey2018".to_string(),
+        )
+        .add_extra_field(
+            "publicKeyBase58".to_string(),
+            Value::String("H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV".to_string()),
+        )
+        .build();
+        let service = ServiceBuilder::new(
+            Uri::new(&format!("{}#files", DID)).unwrap(),
+            ServiceUrl::new("https://example.com/files/").unwrap(),
+        )
+        .unwrap()
+        .add_service_type("LinkedDomains".to_string())
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let did_document = DidDocumentBuilder::new(did)
+            .add_verification_method(verification_method)
+            .add_service(service)
+            .build();
+        DidResolutionOutput::builder(did_document).build()
+    }
+
+    fn dereference(did_url: &str) -> Result<(String, Vec<u8>), GenericError> {
+        let output =
+            dereference_did_document(&resolution_output(), &DidUrl::parse(did_url.to_string())?)?;
+        Ok((
+            output
+                .dereferencing_metadata()
+                .content_type()
+                .unwrap()
+                .to_owned(),
+            output.content_stream().get_ref().to_owned(),
+        ))
+    }
+
+    #[test]
+    fn test_dereference_did() {
+        let (content_type, content) = dereference(DID).unwrap();
+        assert_eq!(content_type, CONTENT_TYPE_DID_JSON);
+        let did_document: DidDocument = serde_json::from_slice(&content).unwrap();
+        assert_eq!(&did_document, resolution_output().did_document());
+    }
+
+    #[test]
+    fn test_dereference_fragment() {
+        let (content_type, content) = dereference(&format!("{}#key-1", DID)).unwrap();
+        assert_eq!(content_type, CONTENT_TYPE_DID_JSON);
+        let method: VerificationMethod = serde_json::from_slice(&content).unwrap();
+        assert_eq!(method.id().to_string(), format!("{}#key-1", DID));
+
+        let (_, content) = dereference(&format!("{}#files", DID)).unwrap();
+        let service: Service = serde_json::from_slice(&content).unwrap();
+        assert_eq!(service.service_endpoint(), "https://example.com/files/");
+
+        let err = dereference(&format!("{}#key-2", DID)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_dereference_service() {
+        let (content_type, content) = dereference(&format!("{}?service=files", DID)).unwrap();
+        assert_eq!(content_type, CONTENT_TYPE_URI_LIST);
+        assert_eq!(content, b"https://example.com/files/");
+
+        let (_, content) =
+            dereference(&format!("{}?service=files&relativeRef=%2Fresume.pdf", DID)).unwrap();
+        assert_eq!(content, b"https://example.com/resume.pdf");
+
+        let (_, content) =
+            dereference(&format!("{}?service=files&relativeRef=2023%2Fcv.pdf", DID)).unwrap();
+        assert_eq!(content, b"https://example.com/files/2023/cv.pdf");
+
+        let err = dereference(&format!("{}?service=unknown", DID)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_dereference_path() {
+        let err = dereference(&format!("{}/some/path", DID)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::DidUrl,
+    error::GenericError,
+    traits::{
+        dereferenceable::{
+            dereferencing_metadata::DidDereferencingMetadata,
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput, DidDereferenceable,
+        },
+        resolvable::{resolution_options::DidResolutionOptions, DidResolvable},
+    },
+};
+
+use crate::{error::DidKeyError, resolution::DidKeyResolver};
+
+#[async_trait]
+impl DidDereferenceable for DidKeyResolver {
+    type Output = Cursor<Vec<u8>>;
+
+    /// Dereferences a `did:key` URL to its DID document or, given a fragment,
+    /// to the verification method it identifies.
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+        let mut resolution_options = DidResolutionOptions::default();
+        if let Some(accept) = options.accept() {
+            resolution_options = resolution_options.set_accept(accept.clone());
+        }
+        let resolution_output = self
+            .resolve(&did_url.try_into()?, &resolution_options)
+            .await?;
+        let did_document = resolution_output.did_document();
+
+        let content = match did_url.fragment() {
+            None => serde_json::to_vec(did_document)?,
+            Some(fragment) => {
+                let fragment = format!("#{}", fragment);
+                let verification_method = did_document
+                    .verification_method()
+                    .iter()
+                    .find(|method| method.id().did_url().ends_with(&fragment))
+                    .ok_or_else(|| {
+                        DidKeyError::NotFound(format!(
+                            "Fragment '{}' not found in the DID document",
+                            fragment
+                        ))
+                    })?;
+                serde_json::to_vec(verification_method)?
+            }
+        };
+
+        let dereferencing_metadata = DidDereferencingMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+
+        Ok(DidDereferencingOutput::builder(Cursor::new(content))
+            .content_metadata(resolution_output.did_document_metadata().clone())
+            .dereferencing_metadata(dereferencing_metadata)
+            .build())
+    }
+}
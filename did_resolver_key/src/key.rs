@@ -0,0 +1,270 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use did_resolver::did_doc::schema::types::jsonwebkey::JsonWebKey;
+use multibase::Base;
+use serde_json::json;
+
+use crate::error::DidKeyError;
+
+// Unsigned varint encoded multicodec prefixes
+// https://github.com/multiformats/multicodec/blob/master/table.csv
+const ED25519_PUB_MULTICODEC: [u8; 2] = [0xed, 0x01];
+const X25519_PUB_MULTICODEC: [u8; 2] = [0xec, 0x01];
+const P256_PUB_MULTICODEC: [u8; 2] = [0x80, 0x24];
+const SECP256K1_PUB_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+
+const KEY_TYPES: [KeyType; 4] = [
+    KeyType::Ed25519,
+    KeyType::X25519,
+    KeyType::P256,
+    KeyType::Secp256k1,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    Ed25519,
+    X25519,
+    P256,
+    Secp256k1,
+}
+
+impl KeyType {
+    fn multicodec_prefix(&self) -> [u8; 2] {
+        match self {
+            KeyType::Ed25519 => ED25519_PUB_MULTICODEC,
+            KeyType::X25519 => X25519_PUB_MULTICODEC,
+            KeyType::P256 => P256_PUB_MULTICODEC,
+            KeyType::Secp256k1 => SECP256K1_PUB_MULTICODEC,
+        }
+    }
+
+    /// Length of the public key, compressed in case of the elliptic curve keys.
+    fn key_length(&self) -> usize {
+        match self {
+            KeyType::Ed25519 | KeyType::X25519 => 32,
+            KeyType::P256 | KeyType::Secp256k1 => 33,
+        }
+    }
+
+    pub fn verification_method_type(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "Ed25519VerificationKey2020",
+            KeyType::X25519 => "X25519KeyAgreementKey2020",
+            KeyType::P256 | KeyType::Secp256k1 => "JsonWebKey2020",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    key_type: KeyType,
+    public_key: Vec<u8>,
+}
+
+impl Key {
+    pub fn new(key_type: KeyType, public_key: Vec<u8>) -> Result<Self, DidKeyError> {
+        if public_key.len() != key_type.key_length() {
+            return Err(DidKeyError::InvalidKey(format!(
+                "Expected {} bytes long {:?} key, got {} bytes",
+                key_type.key_length(),
+                key_type,
+                public_key.len()
+            )));
+        }
+        Ok(Self {
+            key_type,
+            public_key,
+        })
+    }
+
+    /// Creates a key from its multibase encoded multicodec representation,
+    /// which is the method specific identifier of a `did:key`.
+    pub fn from_multibase(multibase: &str) -> Result<Self, DidKeyError> {
+        let (_, bytes) = multibase::decode(multibase)?;
+
+        KEY_TYPES
+            .into_iter()
+            .find_map(|key_type| {
+                bytes
+                    .strip_prefix(&key_type.multicodec_prefix())
+                    .map(|public_key| Self::new(key_type, public_key.to_vec()))
+            })
+            .unwrap_or_else(|| {
+                Err(DidKeyError::InvalidKey(format!(
+                    "Unsupported multicodec key: {}",
+                    multibase
+                )))
+            })
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn to_base58(&self) -> String {
+        Base::Base58Btc.encode(&self.public_key)
+    }
+
+    pub fn to_multibase(&self) -> String {
+        let mut bytes = self.key_type.multicodec_prefix().to_vec();
+        bytes.extend_from_slice(&self.public_key);
+        multibase::encode(Base::Base58Btc, bytes)
+    }
+
+    /// Converts an Ed25519 key to the X25519 key agreement key derived from it.
+    pub fn to_x25519(&self) -> Result<Key, DidKeyError> {
+        match self.key_type {
+            KeyType::X25519 => Ok(self.clone()),
+            KeyType::Ed25519 => {
+                let point = CompressedEdwardsY::from_slice(&self.public_key)
+                    .decompress()
+                    .ok_or_else(|| {
+                        DidKeyError::InvalidKey(format!(
+                            "Not a valid Ed25519 key: {}",
+                            self.to_base58()
+                        ))
+                    })?;
+                Key::new(KeyType::X25519, point.to_montgomery().to_bytes().to_vec())
+            }
+            other => Err(DidKeyError::InvalidKey(format!(
+                "{:?} key can't be converted to X25519",
+                other
+            ))),
+        }
+    }
+
+    /// Expresses an elliptic curve key as a JWK, decompressing its point.
+    pub fn to_jwk(&self) -> Result<JsonWebKey, DidKeyError> {
+        let (curve, x, y) = match self.key_type {
+            KeyType::P256 => {
+                use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+                let point = p256::PublicKey::from_sec1_bytes(&self.public_key)
+                    .map_err(|err| DidKeyError::InvalidKey(err.to_string()))?
+                    .to_encoded_point(false);
+                (
+                    "P-256",
+                    point.x().map(|x| x.to_vec()),
+                    point.y().map(|y| y.to_vec()),
+                )
+            }
+            KeyType::Secp256k1 => {
+                use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+                let point = k256::PublicKey::from_sec1_bytes(&self.public_key)
+                    .map_err(|err| DidKeyError::InvalidKey(err.to_string()))?
+                    .to_encoded_point(false);
+                (
+                    "secp256k1",
+                    point.x().map(|x| x.to_vec()),
+                    point.y().map(|y| y.to_vec()),
+                )
+            }
+            other => {
+                return Err(DidKeyError::InvalidKey(format!(
+                    "{:?} key is not an elliptic curve key",
+                    other
+                )))
+            }
+        };
+
+        let (x, y) = x.zip(y).ok_or_else(|| {
+            DidKeyError::InvalidKey(format!(
+                "Key is the identity point: {}",
+                self.to_multibase()
+            ))
+        })?;
+        let jwk = json!({
+            "kty": "EC",
+            "crv": curve,
+            "x": Base::Base64Url.encode(x),
+            "y": Base::Base64Url.encode(y),
+        });
+
+        Ok(JsonWebKey::new(&jwk.to_string())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_MULTIBASE: &str = "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    const X25519_MULTIBASE: &str = "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+    const P256_MULTIBASE: &str = "zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+    const SECP256K1_MULTIBASE: &str = "zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+
+    #[test]
+    fn test_key_multibase_roundtrip() {
+        for (multibase, key_type) in [
+            (ED25519_MULTIBASE, KeyType::Ed25519),
+            (X25519_MULTIBASE, KeyType::X25519),
+            (P256_MULTIBASE, KeyType::P256),
+            (SECP256K1_MULTIBASE, KeyType::Secp256k1),
+        ] {
+            let key = Key::from_multibase(multibase).unwrap();
+            assert_eq!(key.key_type(), key_type);
+            assert_eq!(key.to_multibase(), multibase);
+        }
+    }
+
+    #[test]
+    fn test_key_to_x25519() {
+        let key = Key::from_multibase(ED25519_MULTIBASE).unwrap();
+        assert_eq!(key.to_x25519().unwrap().to_multibase(), X25519_MULTIBASE);
+
+        let key = Key::from_multibase(P256_MULTIBASE).unwrap();
+        assert!(key.to_x25519().is_err());
+    }
+
+    #[test]
+    fn test_key_to_jwk() {
+        let jwk = Key::from_multibase(P256_MULTIBASE)
+            .unwrap()
+            .to_jwk()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(jwk).unwrap(),
+            json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "x": "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI",
+                "y": "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU"
+            })
+        );
+
+        let jwk = Key::from_multibase(SECP256K1_MULTIBASE)
+            .unwrap()
+            .to_jwk()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(jwk).unwrap(),
+            json!({
+                "kty": "EC",
+                "crv": "secp256k1",
+                "x": "h0wVx_2iDlOcblulc8E5iEw1EYh5n1RYtLQfeSTyNc0",
+                "y": "O2EATIGbu6DezKFptj5scAIRntgfecanVNXxat1rnwE"
+            })
+        );
+
+        assert!(Key::from_multibase(ED25519_MULTIBASE)
+            .unwrap()
+            .to_jwk()
+            .is_err());
+    }
+
+    #[test]
+    fn test_key_invalid() {
+        assert!(Key::new(KeyType::Ed25519, vec![0; 33]).is_err());
+        assert!(Key::new(KeyType::P256, vec![0; 32]).is_err());
+        assert!(Key::from_multibase("invalidmultibasekey").is_err());
+        // BLS12-381 G2 public key
+        assert!(Key::from_multibase(
+            "zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb"
+        )
+        .is_err());
+    }
+}
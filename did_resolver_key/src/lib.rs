@@ -0,0 +1,6 @@
+pub extern crate did_resolver;
+
+pub mod dereferencing;
+pub mod error;
+pub mod key;
+pub mod resolution;
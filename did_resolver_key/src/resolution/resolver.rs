@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::{DidDocument, DidDocumentBuilder},
+        types::multibase::Multibase,
+        verification_method::VerificationMethod,
+    },
+    did_parser::{Did, DidUrl},
+    error::GenericError,
+    shared_types::{did_document_metadata::DidDocumentMetadata, media_type::MediaType},
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_options::DidResolutionOptions,
+        resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+
+use crate::{
+    error::DidKeyError,
+    key::{Key, KeyType},
+};
+
+/// Resolves `did:key` DIDs by expanding the key they encode, without any network access.
+#[derive(Debug, Default, Clone)]
+pub struct DidKeyResolver;
+
+impl DidKeyResolver {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn verification_method(did: &Did, key: &Key) -> Result<VerificationMethod, DidKeyError> {
+        let id = DidUrl::parse(format!("{}#{}", did, key.to_multibase()))?;
+        let builder = VerificationMethod::builder(
+            id,
+            did.clone(),
+            key.key_type().verification_method_type().to_string(),
+        );
+
+        let verification_method = match key.key_type() {
+            KeyType::Ed25519 | KeyType::X25519 => builder
+                .add_public_key_multibase(Multibase::new(key.to_multibase())?)
+                .build(),
+            KeyType::P256 | KeyType::Secp256k1 => builder.add_public_key_jwk(key.to_jwk()?).build(),
+        };
+
+        Ok(verification_method)
+    }
+
+    fn did_document(did: &Did) -> Result<DidDocument, DidKeyError> {
+        if !did.id().starts_with(multibase::Base::Base58Btc.code()) {
+            return Err(DidKeyError::InvalidDid(format!(
+                "Key of {} is not base58btc encoded",
+                did
+            )));
+        }
+        let key = Key::from_multibase(did.id())?;
+
+        let verification_method = Self::verification_method(did, &key)?;
+        let key_id = verification_method.id().clone();
+        let builder =
+            DidDocumentBuilder::new(did.clone()).add_verification_method(verification_method);
+
+        let builder = match key.key_type() {
+            KeyType::X25519 => builder.add_key_agreement_refrence(key_id),
+            key_type => {
+                let builder = builder
+                    .add_authentication_reference(key_id.clone())
+                    .add_assertion_method_reference(key_id.clone())
+                    .add_capability_invocation_refrence(key_id.clone())
+                    .add_capability_delegation_refrence(key_id.clone());
+
+                if key_type == KeyType::Ed25519 {
+                    let agreement_method = Self::verification_method(did, &key.to_x25519()?)?;
+                    let agreement_key_id = agreement_method.id().clone();
+                    builder
+                        .add_verification_method(agreement_method)
+                        .add_key_agreement_refrence(agreement_key_id)
+                } else {
+                    builder.add_key_agreement_refrence(key_id)
+                }
+            }
+        };
+
+        Ok(builder.build())
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidKeyResolver {
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        if did.method() != "key" {
+            return Err(Box::new(DidKeyError::MethodNotSupported(
+                did.method().to_string(),
+            )));
+        }
+
+        if let Some(accept) = options.accept() {
+            if accept != &MediaType::DidJson {
+                return Err(Box::new(DidKeyError::RepresentationNotSupported(
+                    accept.to_string(),
+                )));
+            }
+        }
+
+        let did_resolution_output = DidResolutionOutput::builder(Self::did_document(did)?)
+            .did_resolution_metadata(DidResolutionMetadata::default())
+            .did_document_metadata(DidDocumentMetadata::default())
+            .build();
+
+        Ok(did_resolution_output)
+    }
+}
@@ -0,0 +1,141 @@
+use did_resolver::did_doc::schema::verification_method::{
+    VerificationMethod, VerificationMethodKind,
+};
+use did_resolver::did_parser::{Did, DidUrl};
+use did_resolver::traits::dereferenceable::{
+    dereferencing_options::DidDereferencingOptions, DidDereferenceable,
+};
+use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_key::{error::DidKeyError, resolution::DidKeyResolver};
+
+const ED25519_DID: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+const X25519_KEY_ID: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+const P256_DID: &str = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+const SECP256K1_DID: &str = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+
+fn reference(kind: &VerificationMethodKind) -> String {
+    match kind {
+        VerificationMethodKind::Resolvable(reference) => reference.to_string(),
+        VerificationMethodKind::Resolved(method) => method.id().to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_resolve_ed25519() {
+    let did = Did::parse(ED25519_DID.to_string()).unwrap();
+    let output = DidKeyResolver::new()
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+    let doc = output.did_document();
+
+    assert_eq!(doc.id(), &did);
+    assert_eq!(doc.verification_method().len(), 2);
+
+    let key_id = format!(
+        "{}#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        ED25519_DID
+    );
+    let method = &doc.verification_method()[0];
+    assert_eq!(method.id().to_string(), key_id);
+    assert_eq!(method.controller(), &did);
+    assert_eq!(
+        method.verification_method_type(),
+        "Ed25519VerificationKey2020"
+    );
+    assert_eq!(
+        serde_json::to_value(method).unwrap()["publicKeyMultibase"],
+        "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+    );
+
+    assert_eq!(reference(&doc.authentication()[0]), key_id);
+    assert_eq!(reference(&doc.assertion_method()[0]), key_id);
+    assert_eq!(reference(&doc.capability_invocation()[0]), key_id);
+    assert_eq!(reference(&doc.capability_delegation()[0]), key_id);
+
+    let agreement_method = &doc.verification_method()[1];
+    assert_eq!(agreement_method.id().to_string(), X25519_KEY_ID);
+    assert_eq!(
+        agreement_method.verification_method_type(),
+        "X25519KeyAgreementKey2020"
+    );
+    assert_eq!(doc.key_agreement().len(), 1);
+    assert_eq!(reference(&doc.key_agreement()[0]), X25519_KEY_ID);
+}
+
+#[tokio::test]
+async fn test_resolve_elliptic_curve_keys() {
+    for (did, curve) in [(P256_DID, "P-256"), (SECP256K1_DID, "secp256k1")] {
+        let did = Did::parse(did.to_string()).unwrap();
+        let output = DidKeyResolver::new()
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        let doc = output.did_document();
+
+        assert_eq!(doc.verification_method().len(), 1);
+        let method = &doc.verification_method()[0];
+        assert_eq!(method.verification_method_type(), "JsonWebKey2020");
+        let jwk = serde_json::to_value(method.public_key_jwk().unwrap()).unwrap();
+        assert_eq!(jwk["crv"], curve);
+        assert_eq!(reference(&doc.key_agreement()[0]), method.id().to_string());
+        assert_eq!(reference(&doc.authentication()[0]), method.id().to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_resolve_invalid() {
+    let resolver = DidKeyResolver::new();
+
+    let did = Did::parse("did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_string())
+        .unwrap();
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidKeyError>(),
+        Some(DidKeyError::MethodNotSupported(_))
+    ));
+
+    // Base58 without the multibase prefix
+    let did =
+        Did::parse("did:key:6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_string()).unwrap();
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidKeyError>(),
+        Some(DidKeyError::InvalidDid(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_dereference_fragment() {
+    let resolver = DidKeyResolver::new();
+
+    let did_url = DidUrl::parse(X25519_KEY_ID.to_string()).unwrap();
+    let output = resolver
+        .dereference(&did_url, &DidDereferencingOptions::default())
+        .await
+        .unwrap();
+    let method: VerificationMethod =
+        serde_json::from_slice(output.content_stream().get_ref()).unwrap();
+    assert_eq!(method.id(), &did_url);
+    assert_eq!(
+        method.verification_method_type(),
+        "X25519KeyAgreementKey2020"
+    );
+
+    let did_url = DidUrl::parse(format!("{}#unknown", ED25519_DID)).unwrap();
+    let err = resolver
+        .dereference(&did_url, &DidDereferencingOptions::default())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.downcast_ref::<DidKeyError>(),
+        Some(DidKeyError::NotFound(_))
+    ));
+}
@@ -0,0 +1,33 @@
+pub mod parsing;
+
+use did_resolver::{did_doc::error::DidDocumentBuilderError, did_parser::ParseError};
+use thiserror::Error;
+
+use self::parsing::ParsingErrorSource;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidPeerError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Representation not supported: {0}")]
+    RepresentationNotSupported(String),
+    #[error("Numalgo not supported: {0}")]
+    NumalgoNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Invalid service: {0}")]
+    InvalidService(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("DID parsing error: {0}")]
+    DidParsingError(#[from] ParseError),
+    #[error("DID Document Builder Error: {0}")]
+    DidDocumentBuilderError(#[from] DidDocumentBuilderError),
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use super::DidPeerError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Multibase decoding error: {0}")]
+    MultibaseError(#[from] multibase::Error),
+}
+
+impl From<serde_json::Error> for DidPeerError {
+    fn from(error: serde_json::Error) -> Self {
+        DidPeerError::ParsingError(ParsingErrorSource::JsonError(error))
+    }
+}
+
+impl From<multibase::Error> for DidPeerError {
+    fn from(error: multibase::Error) -> Self {
+        DidPeerError::ParsingError(ParsingErrorSource::MultibaseError(error))
+    }
+}
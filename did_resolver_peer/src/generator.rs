@@ -0,0 +1,144 @@
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser::Did};
+
+use crate::{
+    error::DidPeerError,
+    key::{Key, KeyPurpose},
+    numalgos::{did_doc_json, numalgo0, numalgo2, numalgo4},
+    service::PeerService,
+};
+
+/// Builds the DID document of a set of keys and services, along with the peer DID identifying it.
+#[derive(Clone, Debug, Default)]
+pub struct PeerDidGenerator {
+    keys: Vec<(KeyPurpose, Key)>,
+    services: Vec<PeerService>,
+}
+
+impl PeerDidGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_key(mut self, purpose: KeyPurpose, key: Key) -> Self {
+        self.keys.push((purpose, key));
+        self
+    }
+
+    pub fn add_service(mut self, service: PeerService) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    /// Generates a numalgo 0 peer DID, which requires a single inception key and no services.
+    pub fn generate_numalgo0(&self) -> Result<(Did, DidDocument), DidPeerError> {
+        let key = match (self.keys.as_slice(), self.services.is_empty()) {
+            ([(_, key)], true) => key,
+            _ => {
+                return Err(DidPeerError::InvalidInput(
+                    "Numalgo 0 requires exactly one key and no services".to_string(),
+                ))
+            }
+        };
+        let did = numalgo0::encode(key)?;
+        let doc = numalgo0::resolve(&did)?;
+        Ok((did, doc))
+    }
+
+    pub fn generate_numalgo2(&self) -> Result<(Did, DidDocument), DidPeerError> {
+        self.check_not_empty()?;
+        let did = numalgo2::encode(&self.keys, &self.services)?;
+        let doc = numalgo2::resolve(&did)?;
+        Ok((did, doc))
+    }
+
+    /// Generates the long form numalgo 4 peer DID and its document,
+    /// which lists the short form of the DID in `alsoKnownAs`.
+    pub fn generate_numalgo4(&self) -> Result<(Did, DidDocument), DidPeerError> {
+        self.check_not_empty()?;
+        let did = numalgo4::encode(&did_doc_json(&self.keys, &self.services))?;
+        let doc = numalgo4::resolve_long_form(&did)?;
+        Ok((did, doc))
+    }
+
+    fn check_not_empty(&self) -> Result<(), DidPeerError> {
+        if self.keys.is_empty() {
+            return Err(DidPeerError::InvalidInput(
+                "Peer DID requires at least one key".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::KeyType;
+
+    fn verification_key() -> Key {
+        Key::from_multibase("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH").unwrap()
+    }
+
+    fn generator() -> PeerDidGenerator {
+        PeerDidGenerator::new()
+            .add_key(KeyPurpose::Verification, verification_key())
+            .add_key(
+                KeyPurpose::Encryption,
+                verification_key().to_x25519().unwrap(),
+            )
+            .add_service(PeerService::didcomm_messaging(
+                "https://example.com/endpoint".to_string(),
+            ))
+    }
+
+    #[test]
+    fn test_generate_numalgo0() {
+        let (did, doc) = PeerDidGenerator::new()
+            .add_key(KeyPurpose::Verification, verification_key())
+            .generate_numalgo0()
+            .unwrap();
+        assert_eq!(
+            did.did(),
+            "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+        );
+        assert_eq!(doc.id(), &did);
+
+        assert!(generator().generate_numalgo0().is_err());
+        assert!(PeerDidGenerator::new().generate_numalgo0().is_err());
+    }
+
+    #[test]
+    fn test_generate_numalgo2() {
+        let (did, doc) = generator().generate_numalgo2().unwrap();
+        assert!(did.did().starts_with("did:peer:2.Vz6Mk"));
+        assert_eq!(doc.id(), &did);
+        assert_eq!(doc.verification_method().len(), 2);
+        assert_eq!(
+            doc.verification_method()[1].verification_method_type(),
+            KeyType::X25519.verification_method_type()
+        );
+        assert_eq!(doc.service().len(), 1);
+    }
+
+    #[test]
+    fn test_generate_numalgo4() {
+        let (did, doc) = generator().generate_numalgo4().unwrap();
+        assert!(did.did().starts_with("did:peer:4"));
+        assert_eq!(doc.id(), &did);
+        assert_eq!(
+            doc.also_known_as()[0].to_string(),
+            numalgo4::short_form(&did).unwrap().did()
+        );
+        assert_eq!(doc.verification_method().len(), 2);
+        assert_eq!(doc.service().len(), 1);
+    }
+
+    #[test]
+    fn test_generate_without_keys() {
+        let generator = PeerDidGenerator::new().add_service(PeerService::didcomm_messaging(
+            "https://example.com/endpoint".to_string(),
+        ));
+        assert!(generator.generate_numalgo2().is_err());
+        assert!(generator.generate_numalgo4().is_err());
+    }
+}
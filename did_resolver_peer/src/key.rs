@@ -0,0 +1,201 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use multibase::Base;
+
+use crate::error::DidPeerError;
+
+const KEY_LENGTH: usize = 32;
+
+// Unsigned varint encoded multicodec prefixes
+// https://github.com/multiformats/multicodec/blob/master/table.csv
+const ED25519_PUB_MULTICODEC: [u8; 2] = [0xed, 0x01];
+const X25519_PUB_MULTICODEC: [u8; 2] = [0xec, 0x01];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    Ed25519,
+    X25519,
+}
+
+impl KeyType {
+    fn multicodec_prefix(&self) -> [u8; 2] {
+        match self {
+            KeyType::Ed25519 => ED25519_PUB_MULTICODEC,
+            KeyType::X25519 => X25519_PUB_MULTICODEC,
+        }
+    }
+
+    pub fn verification_method_type(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "Ed25519VerificationKey2020",
+            KeyType::X25519 => "X25519KeyAgreementKey2020",
+        }
+    }
+}
+
+/// Purpose of a key within a peer DID, as encoded by the numalgo 2 purpose codes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyPurpose {
+    Assertion,
+    Encryption,
+    Verification,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+impl KeyPurpose {
+    pub fn code(&self) -> char {
+        match self {
+            KeyPurpose::Assertion => 'A',
+            KeyPurpose::Encryption => 'E',
+            KeyPurpose::Verification => 'V',
+            KeyPurpose::CapabilityInvocation => 'I',
+            KeyPurpose::CapabilityDelegation => 'D',
+        }
+    }
+
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'A' => Some(KeyPurpose::Assertion),
+            'E' => Some(KeyPurpose::Encryption),
+            'V' => Some(KeyPurpose::Verification),
+            'I' => Some(KeyPurpose::CapabilityInvocation),
+            'D' => Some(KeyPurpose::CapabilityDelegation),
+            _ => None,
+        }
+    }
+
+    /// Name of the DID document verification relationship the purpose maps to.
+    pub fn relationship(&self) -> &'static str {
+        match self {
+            KeyPurpose::Assertion => "assertionMethod",
+            KeyPurpose::Encryption => "keyAgreement",
+            KeyPurpose::Verification => "authentication",
+            KeyPurpose::CapabilityInvocation => "capabilityInvocation",
+            KeyPurpose::CapabilityDelegation => "capabilityDelegation",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    key_type: KeyType,
+    public_key: Vec<u8>,
+}
+
+impl Key {
+    pub fn new(key_type: KeyType, public_key: Vec<u8>) -> Result<Self, DidPeerError> {
+        if public_key.len() != KEY_LENGTH {
+            return Err(DidPeerError::InvalidKey(format!(
+                "Expected {} bytes long key, got {} bytes",
+                KEY_LENGTH,
+                public_key.len()
+            )));
+        }
+        Ok(Self {
+            key_type,
+            public_key,
+        })
+    }
+
+    /// Creates a key from its base58 encoding, such as an Indy verkey.
+    pub fn from_base58(key_type: KeyType, base58: &str) -> Result<Self, DidPeerError> {
+        Self::new(key_type, Base::Base58Btc.decode(base58)?)
+    }
+
+    /// Creates a key from its multibase encoded multicodec representation,
+    /// as used by `did:key` and peer DIDs.
+    pub fn from_multibase(multibase: &str) -> Result<Self, DidPeerError> {
+        let (_, bytes) = multibase::decode(multibase)?;
+
+        [KeyType::Ed25519, KeyType::X25519]
+            .into_iter()
+            .find_map(|key_type| {
+                bytes
+                    .strip_prefix(&key_type.multicodec_prefix())
+                    .map(|public_key| Self::new(key_type, public_key.to_vec()))
+            })
+            .unwrap_or_else(|| {
+                Err(DidPeerError::InvalidKey(format!(
+                    "Unsupported multicodec key: {}",
+                    multibase
+                )))
+            })
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn to_base58(&self) -> String {
+        Base::Base58Btc.encode(&self.public_key)
+    }
+
+    pub fn to_multibase(&self) -> String {
+        let mut bytes = self.key_type.multicodec_prefix().to_vec();
+        bytes.extend_from_slice(&self.public_key);
+        multibase::encode(Base::Base58Btc, bytes)
+    }
+
+    /// Converts an Ed25519 key to the X25519 key agreement key derived from it.
+    /// X25519 keys are returned as they are.
+    pub fn to_x25519(&self) -> Result<Key, DidPeerError> {
+        match self.key_type {
+            KeyType::X25519 => Ok(self.clone()),
+            KeyType::Ed25519 => {
+                let point = CompressedEdwardsY::from_slice(&self.public_key)
+                    .decompress()
+                    .ok_or_else(|| {
+                        DidPeerError::InvalidKey(format!(
+                            "Not a valid Ed25519 key: {}",
+                            self.to_base58()
+                        ))
+                    })?;
+                Key::new(KeyType::X25519, point.to_montgomery().to_bytes().to_vec())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_MULTIBASE: &str = "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    const X25519_MULTIBASE: &str = "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc";
+
+    #[test]
+    fn test_key_multibase_roundtrip() {
+        let key = Key::from_multibase(ED25519_MULTIBASE).unwrap();
+        assert_eq!(key.key_type(), KeyType::Ed25519);
+        assert_eq!(key.to_multibase(), ED25519_MULTIBASE);
+
+        let key = Key::from_multibase(X25519_MULTIBASE).unwrap();
+        assert_eq!(key.key_type(), KeyType::X25519);
+        assert_eq!(key.to_multibase(), X25519_MULTIBASE);
+    }
+
+    #[test]
+    fn test_key_base58_roundtrip() {
+        let key = Key::from_multibase(ED25519_MULTIBASE).unwrap();
+        let from_base58 = Key::from_base58(KeyType::Ed25519, &key.to_base58()).unwrap();
+        assert_eq!(from_base58, key);
+    }
+
+    #[test]
+    fn test_key_to_x25519() {
+        let key = Key::from_multibase(ED25519_MULTIBASE).unwrap();
+        assert_eq!(key.to_x25519().unwrap().to_multibase(), X25519_MULTIBASE);
+    }
+
+    #[test]
+    fn test_key_invalid() {
+        assert!(Key::new(KeyType::Ed25519, vec![0; 31]).is_err());
+        assert!(Key::from_multibase("invalidmultibasekey").is_err());
+        // secp256k1 public key
+        assert!(Key::from_multibase("zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme").is_err());
+    }
+}
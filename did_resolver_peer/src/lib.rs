@@ -0,0 +1,8 @@
+pub extern crate did_resolver;
+
+pub mod error;
+pub mod generator;
+pub mod key;
+pub mod numalgos;
+pub mod resolution;
+pub mod service;
@@ -0,0 +1,224 @@
+pub mod numalgo0;
+pub mod numalgo2;
+pub mod numalgo4;
+
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser::Did};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    error::DidPeerError,
+    key::{Key, KeyPurpose},
+    service::PeerService,
+};
+
+const RELATIONSHIPS: [&str; 5] = [
+    "authentication",
+    "assertionMethod",
+    "keyAgreement",
+    "capabilityInvocation",
+    "capabilityDelegation",
+];
+
+/// The peer DID generation methods, identified by the character following `did:peer:`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Numalgo {
+    InceptionKeyWithoutDoc,
+    MultipleInceptionKeys,
+    ShortFormAndLongForm,
+}
+
+impl Numalgo {
+    pub fn from_did(did: &Did) -> Result<Self, DidPeerError> {
+        if did.method() != "peer" {
+            return Err(DidPeerError::MethodNotSupported(did.method().to_string()));
+        }
+        match did.id().chars().next() {
+            Some('0') => Ok(Numalgo::InceptionKeyWithoutDoc),
+            Some('2') => Ok(Numalgo::MultipleInceptionKeys),
+            Some('4') => Ok(Numalgo::ShortFormAndLongForm),
+            Some(other) => Err(DidPeerError::NumalgoNotSupported(other.to_string())),
+            None => Err(DidPeerError::InvalidDid(did.did().to_string())),
+        }
+    }
+
+    pub fn code(&self) -> char {
+        match self {
+            Numalgo::InceptionKeyWithoutDoc => '0',
+            Numalgo::MultipleInceptionKeys => '2',
+            Numalgo::ShortFormAndLongForm => '4',
+        }
+    }
+}
+
+pub(crate) fn verification_method_json(id: &str, key: &Key) -> Value {
+    json!({
+        "id": id,
+        "type": key.key_type().verification_method_type(),
+        "publicKeyMultibase": key.to_multibase(),
+    })
+}
+
+/// Builds a DID document without an `id`, referring to its keys and services by relative DID URLs:
+/// keys as `#key-1`, `#key-2`, ... in order, services as `#service`, `#service-1`, ...
+pub(crate) fn did_doc_json(keys: &[(KeyPurpose, Key)], services: &[PeerService]) -> Value {
+    let mut doc = Map::new();
+
+    let verification_methods = keys
+        .iter()
+        .enumerate()
+        .map(|(index, (_, key))| verification_method_json(&format!("#key-{}", index + 1), key))
+        .collect();
+    doc.insert(
+        "verificationMethod".to_string(),
+        Value::Array(verification_methods),
+    );
+
+    for (index, (purpose, _)) in keys.iter().enumerate() {
+        let relationship = doc
+            .entry(purpose.relationship())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(references) = relationship {
+            references.push(json!(format!("#key-{}", index + 1)));
+        }
+    }
+
+    if !services.is_empty() {
+        let services = services
+            .iter()
+            .enumerate()
+            .map(|(index, service)| match index {
+                0 => service.to_json("#service"),
+                _ => service.to_json(&format!("#service-{}", index)),
+            })
+            .collect();
+        doc.insert("service".to_string(), Value::Array(services));
+    }
+
+    Value::Object(doc)
+}
+
+/// Turns a DID document with relative DID URLs into the document of the DID,
+/// making its identifiers absolute and setting the verification methods' controller.
+pub(crate) fn contextualize(
+    doc: Value,
+    did: &str,
+    also_known_as: Option<&str>,
+) -> Result<DidDocument, DidPeerError> {
+    let mut doc = match doc {
+        Value::Object(doc) => doc,
+        _ => {
+            return Err(DidPeerError::InvalidInput(format!(
+                "DID document must be a JSON object: {}",
+                doc
+            )))
+        }
+    };
+
+    let make_absolute = |value: &mut Value| {
+        if let Value::String(id) = value {
+            if id.starts_with('#') {
+                *id = format!("{}{}", did, id);
+            }
+        }
+    };
+    let contextualize_method = |method: &mut Value| {
+        if let Value::Object(method) = method {
+            if let Some(id) = method.get_mut("id") {
+                make_absolute(id);
+            }
+            method
+                .entry("controller")
+                .or_insert_with(|| Value::String(did.to_string()));
+        }
+    };
+
+    if let Some(Value::Array(methods)) = doc.get_mut("verificationMethod") {
+        methods.iter_mut().for_each(contextualize_method);
+    }
+    for relationship in RELATIONSHIPS {
+        if let Some(Value::Array(entries)) = doc.get_mut(relationship) {
+            for entry in entries.iter_mut() {
+                match entry {
+                    Value::String(_) => make_absolute(entry),
+                    _ => contextualize_method(entry),
+                }
+            }
+        }
+    }
+    if let Some(Value::Array(services)) = doc.get_mut("service") {
+        for service in services.iter_mut() {
+            if let Some(id) = service.get_mut("id") {
+                make_absolute(id);
+            }
+        }
+    }
+
+    doc.insert("id".to_string(), json!(did));
+    if let Some(also_known_as) = also_known_as {
+        doc.insert("alsoKnownAs".to_string(), json!([also_known_as]));
+    }
+
+    Ok(serde_json::from_value(Value::Object(doc))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numalgo_from_did() {
+        let did =
+            Did::parse("did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string())
+                .unwrap();
+        assert_eq!(
+            Numalgo::from_did(&did).unwrap(),
+            Numalgo::InceptionKeyWithoutDoc
+        );
+
+        let did =
+            Did::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa".to_string())
+                .unwrap();
+        assert!(matches!(
+            Numalgo::from_did(&did),
+            Err(DidPeerError::NumalgoNotSupported(_))
+        ));
+
+        let did =
+            Did::parse("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string())
+                .unwrap();
+        assert!(matches!(
+            Numalgo::from_did(&did),
+            Err(DidPeerError::MethodNotSupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_contextualize() {
+        let doc = json!({
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "Ed25519VerificationKey2020",
+                "publicKeyMultibase": "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+            }],
+            "authentication": ["#key-1"],
+            "service": [{
+                "id": "#service",
+                "type": "DIDCommMessaging",
+                "serviceEndpoint": "https://example.com/endpoint"
+            }]
+        });
+
+        let doc = contextualize(doc, "did:peer:4abc", Some("did:peer:4abc:def")).unwrap();
+        assert_eq!(doc.id().did(), "did:peer:4abc");
+        assert_eq!(doc.also_known_as()[0].to_string(), "did:peer:4abc:def");
+        assert_eq!(
+            doc.verification_method()[0].id().to_string(),
+            "did:peer:4abc#key-1"
+        );
+        assert_eq!(
+            doc.verification_method()[0].controller().did(),
+            "did:peer:4abc"
+        );
+        assert_eq!(doc.service()[0].id().to_string(), "did:peer:4abc#service");
+    }
+}
@@ -0,0 +1,97 @@
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser::Did};
+use serde_json::json;
+
+use super::{contextualize, verification_method_json, Numalgo};
+use crate::{
+    error::DidPeerError,
+    key::{Key, KeyType},
+};
+
+/// Creates the numalgo 0 peer DID of an inception key.
+pub fn encode(key: &Key) -> Result<Did, DidPeerError> {
+    Ok(Did::parse(format!(
+        "did:peer:{}{}",
+        Numalgo::InceptionKeyWithoutDoc.code(),
+        key.to_multibase()
+    ))?)
+}
+
+pub fn decode(did: &Did) -> Result<Key, DidPeerError> {
+    match did
+        .id()
+        .strip_prefix(Numalgo::InceptionKeyWithoutDoc.code())
+    {
+        Some(key) => Key::from_multibase(key),
+        None => Err(DidPeerError::InvalidDid(did.did().to_string())),
+    }
+}
+
+/// Resolves a numalgo 0 peer DID the same way its inception key would be resolved as a `did:key`.
+/// The X25519 key agreement key derived from an Ed25519 inception key is included.
+pub fn resolve(did: &Did) -> Result<DidDocument, DidPeerError> {
+    let key = decode(did)?;
+    let key_id = format!("#{}", key.to_multibase());
+
+    let doc = match key.key_type() {
+        KeyType::Ed25519 => {
+            let agreement_key = key.to_x25519()?;
+            let agreement_key_id = format!("#{}", agreement_key.to_multibase());
+            json!({
+                "verificationMethod": [
+                    verification_method_json(&key_id, &key),
+                    verification_method_json(&agreement_key_id, &agreement_key),
+                ],
+                "authentication": [key_id],
+                "assertionMethod": [key_id],
+                "capabilityInvocation": [key_id],
+                "capabilityDelegation": [key_id],
+                "keyAgreement": [agreement_key_id],
+            })
+        }
+        KeyType::X25519 => json!({
+            "verificationMethod": [verification_method_json(&key_id, &key)],
+            "keyAgreement": [key_id],
+        }),
+    };
+
+    contextualize(doc, did.did(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    #[test]
+    fn test_numalgo0_encode_decode() {
+        let did = Did::parse(DID.to_string()).unwrap();
+        let key = decode(&did).unwrap();
+        assert_eq!(key.key_type(), KeyType::Ed25519);
+        assert_eq!(encode(&key).unwrap(), did);
+    }
+
+    #[test]
+    fn test_numalgo0_resolve() {
+        let doc = resolve(&Did::parse(DID.to_string()).unwrap()).unwrap();
+
+        assert_eq!(doc.id().did(), DID);
+        assert_eq!(doc.verification_method().len(), 2);
+        assert_eq!(
+            doc.verification_method()[0].id().to_string(),
+            format!("{}#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH", DID)
+        );
+        assert_eq!(
+            doc.verification_method()[1].id().to_string(),
+            format!("{}#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc", DID)
+        );
+        assert_eq!(doc.authentication().len(), 1);
+        assert_eq!(doc.key_agreement().len(), 1);
+    }
+
+    #[test]
+    fn test_numalgo0_decode_invalid() {
+        let did = Did::parse("did:peer:0z6Mkinvalid".to_string()).unwrap();
+        assert!(resolve(&did).is_err());
+    }
+}
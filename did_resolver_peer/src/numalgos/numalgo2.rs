@@ -0,0 +1,142 @@
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser::Did};
+
+use super::{contextualize, did_doc_json, Numalgo};
+use crate::{
+    error::DidPeerError,
+    key::{Key, KeyPurpose},
+    service::PeerService,
+};
+
+const SERVICE_PURPOSE_CODE: char = 'S';
+
+/// Keys, along with their purposes, and services encoded in a numalgo 2 peer DID.
+pub type Elements = (Vec<(KeyPurpose, Key)>, Vec<PeerService>);
+
+/// Creates the numalgo 2 peer DID of the keys and services, encoding the elements in the given order.
+pub fn encode(keys: &[(KeyPurpose, Key)], services: &[PeerService]) -> Result<Did, DidPeerError> {
+    let mut did = format!("did:peer:{}", Numalgo::MultipleInceptionKeys.code());
+
+    for (purpose, key) in keys {
+        did.push('.');
+        did.push(purpose.code());
+        did.push_str(&key.to_multibase());
+    }
+    for service in services {
+        did.push('.');
+        did.push(SERVICE_PURPOSE_CODE);
+        did.push_str(&service.encode_abbreviated()?);
+    }
+
+    Ok(Did::parse(did)?)
+}
+
+pub fn decode(did: &Did) -> Result<Elements, DidPeerError> {
+    let elements = did
+        .id()
+        .strip_prefix(Numalgo::MultipleInceptionKeys.code())
+        .and_then(|elements| elements.strip_prefix('.'))
+        .ok_or_else(|| DidPeerError::InvalidDid(did.did().to_string()))?;
+
+    let mut keys = Vec::new();
+    let mut services = Vec::new();
+
+    for element in elements.split('.') {
+        let mut chars = element.chars();
+        let (code, value) = match chars.next() {
+            Some(code) => (code, chars.as_str()),
+            None => return Err(DidPeerError::InvalidDid(did.did().to_string())),
+        };
+
+        if code == SERVICE_PURPOSE_CODE {
+            services.push(PeerService::decode_abbreviated(value)?);
+        } else {
+            let purpose = KeyPurpose::from_code(code).ok_or_else(|| {
+                DidPeerError::InvalidDid(format!("Unknown purpose code {} in {}", code, did.did()))
+            })?;
+            keys.push((purpose, Key::from_multibase(value)?));
+        }
+    }
+
+    Ok((keys, services))
+}
+
+pub fn resolve(did: &Did) -> Result<DidDocument, DidPeerError> {
+    let (keys, services) = decode(did)?;
+    contextualize(did_doc_json(&keys, &services), did.did(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key::KeyType, service::SERVICE_TYPE_DIDCOMM_MESSAGING};
+
+    fn keys() -> Vec<(KeyPurpose, Key)> {
+        let verification_key =
+            Key::from_multibase("z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V").unwrap();
+        let agreement_key = verification_key.to_x25519().unwrap();
+        vec![
+            (KeyPurpose::Encryption, agreement_key),
+            (KeyPurpose::Verification, verification_key),
+        ]
+    }
+
+    fn services() -> Vec<PeerService> {
+        vec![
+            PeerService::didcomm_messaging("https://example.com/endpoint".to_string())
+                .add_routing_key("did:example:somemediator#somekey".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_numalgo2_encode_decode() {
+        let did = encode(&keys(), &services()).unwrap();
+        assert!(did.did().starts_with("did:peer:2.Ez6LS"));
+
+        let (keys, services) = decode(&did).unwrap();
+        assert_eq!(keys, self::keys());
+        assert_eq!(services, self::services());
+    }
+
+    #[test]
+    fn test_numalgo2_resolve() {
+        let did = encode(&keys(), &services()).unwrap();
+        let doc = resolve(&did).unwrap();
+
+        assert_eq!(doc.id(), &did);
+        assert_eq!(doc.verification_method().len(), 2);
+        assert_eq!(
+            doc.verification_method()[0].id().to_string(),
+            format!("{}#key-1", did)
+        );
+        assert_eq!(
+            doc.verification_method()[0].verification_method_type(),
+            KeyType::X25519.verification_method_type()
+        );
+        assert_eq!(doc.key_agreement().len(), 1);
+        assert_eq!(doc.authentication().len(), 1);
+        assert!(doc.assertion_method().is_empty());
+
+        let service = &doc.service()[0];
+        assert_eq!(service.id().to_string(), format!("{}#service", did));
+        assert_eq!(service.service_endpoint(), "https://example.com/endpoint");
+        assert_eq!(
+            service.extra_field("routingKeys").unwrap(),
+            &serde_json::json!(["did:example:somemediator#somekey"])
+        );
+        assert_eq!(
+            serde_json::to_value(service.service_type()).unwrap(),
+            serde_json::json!(SERVICE_TYPE_DIDCOMM_MESSAGING)
+        );
+    }
+
+    #[test]
+    fn test_numalgo2_decode_invalid() {
+        let did = Did::parse("did:peer:2".to_string()).unwrap();
+        assert!(decode(&did).is_err());
+
+        let did =
+            Did::parse("did:peer:2.Xz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V".to_string())
+                .unwrap();
+        assert!(decode(&did).is_err());
+    }
+}
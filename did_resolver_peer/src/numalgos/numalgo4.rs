@@ -0,0 +1,187 @@
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser::Did};
+use multibase::Base;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{contextualize, Numalgo};
+use crate::error::DidPeerError;
+
+// Unsigned varint encoded multicodec prefix of JSON
+const JSON_MULTICODEC: [u8; 2] = [0x80, 0x04];
+// Multihash prefix of a SHA2-256 digest
+const SHA2_256_MULTIHASH: [u8; 2] = [0x12, 0x20];
+
+fn hash(encoded_document: &str) -> String {
+    let mut multihash = SHA2_256_MULTIHASH.to_vec();
+    multihash.extend_from_slice(&Sha256::digest(encoded_document.as_bytes()));
+    multibase::encode(Base::Base58Btc, multihash)
+}
+
+/// Splits a numalgo 4 peer DID into its hash and, for the long form, its encoded document.
+fn split(did: &Did) -> Result<(&str, Option<&str>), DidPeerError> {
+    let id = did
+        .id()
+        .strip_prefix(Numalgo::ShortFormAndLongForm.code())
+        .ok_or_else(|| DidPeerError::InvalidDid(did.did().to_string()))?;
+
+    match id.split_once(':') {
+        Some((hash, encoded_document)) => Ok((hash, Some(encoded_document))),
+        None => Ok((id, None)),
+    }
+}
+
+/// Creates the long form numalgo 4 peer DID of a DID document lacking an `id`,
+/// whose identifiers are relative DID URLs.
+pub fn encode(input_document: &Value) -> Result<Did, DidPeerError> {
+    if input_document.get("id").is_some() {
+        return Err(DidPeerError::InvalidInput(
+            "Input document of a numalgo 4 peer DID must not have an id".to_string(),
+        ));
+    }
+
+    let mut bytes = JSON_MULTICODEC.to_vec();
+    bytes.extend(serde_json::to_vec(input_document)?);
+    let encoded_document = multibase::encode(Base::Base58Btc, bytes);
+
+    Ok(Did::parse(format!(
+        "did:peer:{}{}:{}",
+        Numalgo::ShortFormAndLongForm.code(),
+        hash(&encoded_document),
+        encoded_document
+    ))?)
+}
+
+/// Decodes the input document of a long form DID, verifying it matches the DID's hash.
+pub fn decode(did: &Did) -> Result<Value, DidPeerError> {
+    let (hash, encoded_document) = split(did)?;
+    let encoded_document = encoded_document.ok_or_else(|| {
+        DidPeerError::InvalidInput(format!("Not a long form peer DID: {}", did.did()))
+    })?;
+
+    if self::hash(encoded_document) != hash {
+        return Err(DidPeerError::InvalidDid(format!(
+            "Hash does not match the encoded document: {}",
+            did.did()
+        )));
+    }
+
+    let (_, bytes) = multibase::decode(encoded_document)?;
+    let json = bytes.strip_prefix(&JSON_MULTICODEC).ok_or_else(|| {
+        DidPeerError::InvalidDid(format!("Encoded document is not JSON: {}", did.did()))
+    })?;
+
+    Ok(serde_json::from_slice(json)?)
+}
+
+pub fn is_short_form(did: &Did) -> Result<bool, DidPeerError> {
+    Ok(split(did)?.1.is_none())
+}
+
+pub fn short_form(did: &Did) -> Result<Did, DidPeerError> {
+    let (hash, _) = split(did)?;
+    Ok(Did::parse(format!(
+        "did:peer:{}{}",
+        Numalgo::ShortFormAndLongForm.code(),
+        hash
+    ))?)
+}
+
+/// Resolves a long form DID to its document, known by its short form as well.
+pub fn resolve_long_form(did: &Did) -> Result<DidDocument, DidPeerError> {
+    let short_form = short_form(did)?;
+    contextualize(decode(did)?, did.did(), Some(short_form.did()))
+}
+
+/// Resolves a short form DID, using the long form DID it was created from.
+pub fn resolve_short_form(did: &Did, long_form: &Did) -> Result<DidDocument, DidPeerError> {
+    if &short_form(long_form)? != did {
+        return Err(DidPeerError::InvalidInput(format!(
+            "{} is not the long form of {}",
+            long_form.did(),
+            did.did()
+        )));
+    }
+    contextualize(decode(long_form)?, did.did(), Some(long_form.did()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn input_document() -> Value {
+        json!({
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "Ed25519VerificationKey2020",
+                "publicKeyMultibase": "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+            }],
+            "authentication": ["#key-1"]
+        })
+    }
+
+    #[test]
+    fn test_numalgo4_encode_decode() {
+        let did = encode(&input_document()).unwrap();
+        assert!(did.did().starts_with("did:peer:4zQm"));
+        assert!(!is_short_form(&did).unwrap());
+        assert_eq!(decode(&did).unwrap(), input_document());
+
+        let short_form = short_form(&did).unwrap();
+        assert!(is_short_form(&short_form).unwrap());
+        assert!(did.did().starts_with(short_form.did()));
+    }
+
+    #[test]
+    fn test_numalgo4_resolve() {
+        let long_form = encode(&input_document()).unwrap();
+        let short_form = short_form(&long_form).unwrap();
+
+        let doc = resolve_long_form(&long_form).unwrap();
+        assert_eq!(doc.id(), &long_form);
+        assert_eq!(doc.also_known_as()[0].to_string(), short_form.did());
+        assert_eq!(
+            doc.verification_method()[0].id().to_string(),
+            format!("{}#key-1", long_form)
+        );
+
+        let doc = resolve_short_form(&short_form, &long_form).unwrap();
+        assert_eq!(doc.id(), &short_form);
+        assert_eq!(doc.also_known_as()[0].to_string(), long_form.did());
+        assert_eq!(
+            doc.verification_method()[0].id().to_string(),
+            format!("{}#key-1", short_form)
+        );
+    }
+
+    #[test]
+    fn test_numalgo4_tampered_document() {
+        let did = encode(&input_document()).unwrap();
+        let (hash, _) = split(&did).unwrap();
+
+        let mut other_document = input_document();
+        other_document["authentication"] = json!([]);
+        let other_did = encode(&other_document).unwrap();
+        let (_, other_encoded_document) = split(&other_did).unwrap();
+
+        let tampered = Did::parse(format!(
+            "did:peer:4{}:{}",
+            hash,
+            other_encoded_document.unwrap()
+        ))
+        .unwrap();
+        assert!(matches!(
+            decode(&tampered),
+            Err(DidPeerError::InvalidDid(_))
+        ));
+        assert!(resolve_short_form(&short_form(&did).unwrap(), &other_did).is_err());
+    }
+
+    #[test]
+    fn test_numalgo4_encode_with_id() {
+        let mut input_document = input_document();
+        input_document["id"] = json!("did:example:123");
+        assert!(encode(&input_document).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
+    did_parser::Did,
+    error::GenericError,
+    shared_types::{did_document_metadata::DidDocumentMetadata, media_type::MediaType},
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_options::DidResolutionOptions,
+        resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+
+use crate::{
+    error::DidPeerError,
+    numalgos::{numalgo0, numalgo2, numalgo4, Numalgo},
+};
+
+/// Resolves numalgo 0, 2 and 4 peer DIDs.
+///
+/// A short form numalgo 4 DID carries only the hash of its document, so it can only be resolved
+/// once the resolver has seen the corresponding long form DID.
+#[derive(Debug, Default)]
+pub struct DidPeerResolver {
+    long_forms: RwLock<HashMap<String, Did>>,
+}
+
+impl DidPeerResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn resolve_numalgo4(&self, did: &Did) -> Result<DidDocument, DidPeerError> {
+        if numalgo4::is_short_form(did)? {
+            let long_form = self
+                .long_forms
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(did.did())
+                .cloned()
+                .ok_or_else(|| {
+                    DidPeerError::NotFound(format!("Long form of {} is not known", did.did()))
+                })?;
+            numalgo4::resolve_short_form(did, &long_form)
+        } else {
+            let doc = numalgo4::resolve_long_form(did)?;
+            let short_form = numalgo4::short_form(did)?;
+            self.long_forms
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(short_form.did().to_string(), did.clone());
+            Ok(doc)
+        }
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidPeerResolver {
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        if let Some(accept) = options.accept() {
+            if accept != &MediaType::DidJson {
+                return Err(Box::new(DidPeerError::RepresentationNotSupported(
+                    accept.to_string(),
+                )));
+            }
+        }
+
+        let did_document = match Numalgo::from_did(did)? {
+            Numalgo::InceptionKeyWithoutDoc => numalgo0::resolve(did)?,
+            Numalgo::MultipleInceptionKeys => numalgo2::resolve(did)?,
+            Numalgo::ShortFormAndLongForm => self.resolve_numalgo4(did)?,
+        };
+
+        let did_resolution_output = DidResolutionOutput::builder(did_document)
+            .did_resolution_metadata(DidResolutionMetadata::default())
+            .did_document_metadata(DidDocumentMetadata::default())
+            .build();
+
+        Ok(did_resolution_output)
+    }
+}
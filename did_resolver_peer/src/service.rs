@@ -0,0 +1,205 @@
+use multibase::Base;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::DidPeerError;
+
+pub const SERVICE_TYPE_DIDCOMM_MESSAGING: &str = "DIDCommMessaging";
+
+const SERVICE_TYPE_DIDCOMM_MESSAGING_ABBREVIATION: &str = "dm";
+
+/// A service of a peer DID, which numalgo 2 encodes in its abbreviated form.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerService {
+    service_type: String,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+    accept: Vec<String>,
+}
+
+impl PeerService {
+    pub fn new(service_type: String, service_endpoint: String) -> Self {
+        Self {
+            service_type,
+            service_endpoint,
+            routing_keys: Vec::new(),
+            accept: Vec::new(),
+        }
+    }
+
+    pub fn didcomm_messaging(service_endpoint: String) -> Self {
+        Self::new(SERVICE_TYPE_DIDCOMM_MESSAGING.to_string(), service_endpoint)
+    }
+
+    pub fn add_routing_key(mut self, routing_key: String) -> Self {
+        self.routing_keys.push(routing_key);
+        self
+    }
+
+    pub fn add_accept(mut self, accept: String) -> Self {
+        self.accept.push(accept);
+        self
+    }
+
+    pub fn service_type(&self) -> &str {
+        self.service_type.as_ref()
+    }
+
+    pub fn service_endpoint(&self) -> &str {
+        self.service_endpoint.as_ref()
+    }
+
+    pub fn routing_keys(&self) -> &[String] {
+        self.routing_keys.as_ref()
+    }
+
+    pub fn accept(&self) -> &[String] {
+        self.accept.as_ref()
+    }
+
+    /// Encodes the service as the base64url encoded JSON of its abbreviated form.
+    pub fn encode_abbreviated(&self) -> Result<String, DidPeerError> {
+        let service_type = match self.service_type.as_str() {
+            SERVICE_TYPE_DIDCOMM_MESSAGING => SERVICE_TYPE_DIDCOMM_MESSAGING_ABBREVIATION,
+            other => other,
+        };
+        let abbreviated = AbbreviatedService {
+            service_type: service_type.to_string(),
+            service_endpoint: AbbreviatedEndpoint::Uri(self.service_endpoint.clone()),
+            routing_keys: self.routing_keys.clone(),
+            accept: self.accept.clone(),
+        };
+        Ok(Base::Base64Url.encode(serde_json::to_vec(&abbreviated)?))
+    }
+
+    /// Decodes a service from the base64url encoded JSON of its abbreviated form.
+    /// Both the flat form and the form nesting routing keys and accepted media types
+    /// in the endpoint object are supported.
+    pub fn decode_abbreviated(encoded: &str) -> Result<Self, DidPeerError> {
+        let json = Base::Base64Url.decode(encoded.trim_end_matches('='))?;
+        let abbreviated: AbbreviatedService = serde_json::from_slice(&json)?;
+
+        let service_type = match abbreviated.service_type.as_str() {
+            SERVICE_TYPE_DIDCOMM_MESSAGING_ABBREVIATION => SERVICE_TYPE_DIDCOMM_MESSAGING,
+            other => other,
+        };
+        let mut service = Self::new(service_type.to_string(), String::new());
+        service.routing_keys = abbreviated.routing_keys;
+        service.accept = abbreviated.accept;
+
+        match abbreviated.service_endpoint {
+            AbbreviatedEndpoint::Uri(uri) => service.service_endpoint = uri,
+            AbbreviatedEndpoint::Object {
+                uri,
+                routing_keys,
+                accept,
+            } => {
+                service.service_endpoint = uri;
+                service.routing_keys.extend(routing_keys);
+                service.accept.extend(accept);
+            }
+        }
+
+        if service.service_endpoint.is_empty() {
+            return Err(DidPeerError::InvalidService(format!(
+                "Service has no endpoint: {}",
+                encoded
+            )));
+        }
+        Ok(service)
+    }
+
+    /// JSON representation of the service within a DID document.
+    pub(crate) fn to_json(&self, id: &str) -> Value {
+        let mut service = Map::new();
+        service.insert("id".to_string(), json!(id));
+        service.insert("type".to_string(), json!(self.service_type));
+        service.insert("serviceEndpoint".to_string(), json!(self.service_endpoint));
+        if !self.routing_keys.is_empty() {
+            service.insert("routingKeys".to_string(), json!(self.routing_keys));
+        }
+        if !self.accept.is_empty() {
+            service.insert("accept".to_string(), json!(self.accept));
+        }
+        Value::Object(service)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AbbreviatedService {
+    #[serde(rename = "t")]
+    service_type: String,
+    #[serde(rename = "s")]
+    service_endpoint: AbbreviatedEndpoint,
+    #[serde(rename = "r", default, skip_serializing_if = "Vec::is_empty")]
+    routing_keys: Vec<String>,
+    #[serde(rename = "a", default, skip_serializing_if = "Vec::is_empty")]
+    accept: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum AbbreviatedEndpoint {
+    Uri(String),
+    Object {
+        uri: String,
+        #[serde(rename = "r", default)]
+        routing_keys: Vec<String>,
+        #[serde(rename = "a", default)]
+        accept: Vec<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_abbreviation_roundtrip() {
+        let service = PeerService::didcomm_messaging("https://example.com/endpoint".to_string())
+            .add_routing_key("did:example:somemediator#somekey".to_string())
+            .add_accept("didcomm/v2".to_string());
+
+        let encoded = service.encode_abbreviated().unwrap();
+        let json: Value =
+            serde_json::from_slice(&Base::Base64Url.decode(&encoded).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "t": "dm",
+                "s": "https://example.com/endpoint",
+                "r": ["did:example:somemediator#somekey"],
+                "a": ["didcomm/v2"]
+            })
+        );
+
+        assert_eq!(PeerService::decode_abbreviated(&encoded).unwrap(), service);
+    }
+
+    #[test]
+    fn test_service_decode_nested_endpoint() {
+        let json = json!({
+            "t": "dm",
+            "s": {
+                "uri": "https://example.com/endpoint",
+                "r": ["did:example:somemediator#somekey"],
+                "a": ["didcomm/v2"]
+            }
+        });
+        let encoded = Base::Base64Url.encode(serde_json::to_vec(&json).unwrap());
+
+        let service = PeerService::decode_abbreviated(&encoded).unwrap();
+        assert_eq!(service.service_type(), SERVICE_TYPE_DIDCOMM_MESSAGING);
+        assert_eq!(service.service_endpoint(), "https://example.com/endpoint");
+        assert_eq!(service.routing_keys(), ["did:example:somemediator#somekey"]);
+        assert_eq!(service.accept(), ["didcomm/v2"]);
+    }
+
+    #[test]
+    fn test_service_decode_invalid() {
+        assert!(PeerService::decode_abbreviated("not base64url!").is_err());
+
+        let encoded = Base::Base64Url.encode(br#"{"t":"dm"}"#);
+        assert!(PeerService::decode_abbreviated(&encoded).is_err());
+    }
+}
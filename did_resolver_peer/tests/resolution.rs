@@ -0,0 +1,110 @@
+use did_resolver::did_parser::Did;
+use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_peer::{
+    error::DidPeerError,
+    generator::PeerDidGenerator,
+    key::{Key, KeyPurpose},
+    numalgos::numalgo4,
+    resolution::resolver::DidPeerResolver,
+    service::PeerService,
+};
+
+fn generator() -> PeerDidGenerator {
+    let key = Key::from_multibase("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH").unwrap();
+    PeerDidGenerator::new()
+        .add_key(KeyPurpose::Verification, key.clone())
+        .add_key(KeyPurpose::Encryption, key.to_x25519().unwrap())
+        .add_service(
+            PeerService::didcomm_messaging("https://example.com/endpoint".to_string())
+                .add_routing_key("did:example:somemediator#somekey".to_string()),
+        )
+}
+
+#[tokio::test]
+async fn test_resolve_numalgo0() {
+    let key = Key::from_multibase("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH").unwrap();
+    let (did, doc) = PeerDidGenerator::new()
+        .add_key(KeyPurpose::Verification, key)
+        .generate_numalgo0()
+        .unwrap();
+
+    let output = DidPeerResolver::new()
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document(), &doc);
+}
+
+#[tokio::test]
+async fn test_resolve_numalgo2() {
+    let (did, doc) = generator().generate_numalgo2().unwrap();
+
+    let output = DidPeerResolver::new()
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document(), &doc);
+}
+
+#[tokio::test]
+async fn test_resolve_numalgo4() {
+    let (long_form, doc) = generator().generate_numalgo4().unwrap();
+    let short_form = numalgo4::short_form(&long_form).unwrap();
+    let resolver = DidPeerResolver::new();
+
+    let err = resolver
+        .resolve(&short_form, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidPeerError>(),
+        Some(DidPeerError::NotFound(_))
+    ));
+
+    let output = resolver
+        .resolve(&long_form, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document(), &doc);
+
+    let output = resolver
+        .resolve(&short_form, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document().id(), &short_form);
+    assert_eq!(
+        output.did_document().also_known_as()[0].to_string(),
+        long_form.did()
+    );
+    assert_eq!(
+        output.did_document().verification_method().len(),
+        doc.verification_method().len()
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_unsupported() {
+    let resolver = DidPeerResolver::new();
+
+    let did =
+        Did::parse("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()).unwrap();
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidPeerError>(),
+        Some(DidPeerError::MethodNotSupported(_))
+    ));
+
+    let did = Did::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa".to_string())
+        .unwrap();
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidPeerError>(),
+        Some(DidPeerError::NumalgoNotSupported(_))
+    ));
+}
@@ -0,0 +1,88 @@
+use std::{collections::HashMap, error::Error, num::NonZeroUsize, time::Duration};
+
+use did_resolver::traits::resolvable::resolution_error::DidResolutionError;
+
+/// Decides whether a resolution error means the DID does not exist.
+pub type NotFoundPredicate = fn(&(dyn Error + Send + Sync + 'static)) -> bool;
+
+fn is_resolution_not_found(err: &(dyn Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<DidResolutionError>(),
+        Some(DidResolutionError::NotFound)
+    )
+}
+
+pub struct ResolutionCacheConfig {
+    ttl: Duration,
+    capacity: NonZeroUsize,
+    method_ttls: HashMap<String, Duration>,
+    not_found_ttl: Option<Duration>,
+    is_not_found: NotFoundPredicate,
+}
+
+impl ResolutionCacheConfig {
+    pub fn builder(ttl: Duration, capacity: NonZeroUsize) -> ResolutionCacheConfigBuilder {
+        ResolutionCacheConfigBuilder {
+            ttl,
+            capacity,
+            method_ttls: HashMap::new(),
+            not_found_ttl: None,
+            is_not_found: is_resolution_not_found,
+        }
+    }
+
+    pub fn ttl(&self, method: &str) -> Duration {
+        self.method_ttls.get(method).copied().unwrap_or(self.ttl)
+    }
+
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    pub fn not_found_ttl(&self) -> Option<Duration> {
+        self.not_found_ttl
+    }
+
+    pub fn is_not_found(&self, err: &(dyn Error + Send + Sync + 'static)) -> bool {
+        (self.is_not_found)(err)
+    }
+}
+
+pub struct ResolutionCacheConfigBuilder {
+    ttl: Duration,
+    capacity: NonZeroUsize,
+    method_ttls: HashMap<String, Duration>,
+    not_found_ttl: Option<Duration>,
+    is_not_found: NotFoundPredicate,
+}
+
+impl ResolutionCacheConfigBuilder {
+    /// Overrides the TTL of the results of a DID method.
+    pub fn method_ttl(mut self, method: String, ttl: Duration) -> Self {
+        self.method_ttls.insert(method, ttl);
+        self
+    }
+
+    /// Enables caching of DIDs found not to exist for the given TTL.
+    pub fn not_found_ttl(mut self, ttl: Duration) -> Self {
+        self.not_found_ttl = Some(ttl);
+        self
+    }
+
+    /// Sets how resolver errors meaning the DID does not exist are recognized.
+    /// By default, only `DidResolutionError::NotFound` is.
+    pub fn not_found_predicate(mut self, is_not_found: NotFoundPredicate) -> Self {
+        self.is_not_found = is_not_found;
+        self
+    }
+
+    pub fn build(self) -> ResolutionCacheConfig {
+        ResolutionCacheConfig {
+            ttl: self.ttl,
+            capacity: self.capacity,
+            method_ttls: self.method_ttls,
+            not_found_ttl: self.not_found_ttl,
+            is_not_found: self.is_not_found,
+        }
+    }
+}
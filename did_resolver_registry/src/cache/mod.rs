@@ -0,0 +1,541 @@
+mod config;
+
+pub use config::*;
+
+use std::{
+    io::Cursor,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use did_resolver::{
+    did_parser::{Did, DidUrl},
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::{
+        dereferenceable::{
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput, utils::dereference_did_document,
+        },
+        resolvable::{
+            resolution_error::DidResolutionError, resolution_options::DidResolutionOptions,
+            resolution_output::DidResolutionOutput,
+        },
+    },
+};
+use lru::LruCache;
+
+use crate::{error::DidResolverRegistryError, ResolverRegistry};
+
+// DID and the requested representation
+type CacheKey = (String, Option<String>);
+
+// DID parameters selecting a version of the document other than the latest
+const VERSION_PARAMETERS: [&str; 2] = ["versionId", "versionTime"];
+
+enum CachedResult {
+    Found(Box<DidResolutionOutput>),
+    NotFound,
+}
+
+struct CacheEntry {
+    result: CachedResult,
+    expires_at: Instant,
+}
+
+/// [`ResolverRegistry`] decorator keeping resolution results in an in-memory LRU cache.
+///
+/// The document metadata returned by resolvers is taken into account:
+/// * results are not kept past their `nextUpdate`, unless the DID is `deactivated`,
+/// * results with a `nextVersionId` are not cached, as a newer version of the document exists,
+/// * a result is never replaced by one `updated` earlier, and a result of the same `versionId`
+///   only refreshes the cached one.
+///
+/// Resolutions requested with [`DidResolutionOptions::no_cache`] skip the cache lookup, but their
+/// results are still cached.
+pub struct CachingResolverRegistry {
+    registry: ResolverRegistry,
+    cache: Mutex<LruCache<CacheKey, CacheEntry>>,
+    config: ResolutionCacheConfig,
+    clock: Box<dyn Fn() -> Instant + Send + Sync>,
+}
+
+impl CachingResolverRegistry {
+    pub fn new(registry: ResolverRegistry, config: ResolutionCacheConfig) -> Self {
+        Self {
+            registry,
+            cache: Mutex::new(LruCache::new(config.capacity())),
+            config,
+            clock: Box::new(Instant::now),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn registry(&self) -> &ResolverRegistry {
+        &self.registry
+    }
+
+    pub fn registry_mut(&mut self) -> &mut ResolverRegistry {
+        &mut self.registry
+    }
+
+    /// Drops every cached result of the DID.
+    pub fn invalidate(&self, did: &Did) {
+        let mut cache = self.lock();
+        let keys: Vec<CacheKey> = cache
+            .iter()
+            .filter(|((cached_did, _), _)| cached_did == did.did())
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            cache.pop(&key);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let key = (
+            did.did().to_string(),
+            options.accept().map(|accept| accept.to_string()),
+        );
+
+        if !options.no_cache() {
+            match self.cached(&key) {
+                Some(CachedResult::Found(output)) => return Ok(*output),
+                Some(CachedResult::NotFound) => return Err(Box::new(DidResolutionError::NotFound)),
+                None => {}
+            }
+        }
+
+        match self.registry.resolve(did, options).await {
+            Ok(output) => {
+                self.store_found(key, did.method(), &output);
+                Ok(output)
+            }
+            Err(err) => {
+                if let Some(ttl) = self.config.not_found_ttl() {
+                    if self.config.is_not_found(err.as_ref()) {
+                        self.lock().put(
+                            key,
+                            CacheEntry {
+                                result: CachedResult::NotFound,
+                                expires_at: self.now() + ttl,
+                            },
+                        );
+                    }
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Dereferences a DID URL like [`ResolverRegistry::dereference`], resolving DIDs
+    /// through the cache unless their method is registered with its own dereferencer,
+    /// or the URL asks for a version of the document by `versionId` or `versionTime`.
+    pub async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let method = did_url
+            .method()
+            .ok_or(DidResolverRegistryError::RelativeDidUrl)?;
+        if self.registry.dereferencers.contains_key(method) || requests_version(did_url) {
+            return self.registry.dereference(did_url, options).await;
+        }
+
+        let mut resolution_options = DidResolutionOptions::default();
+        if let Some(accept) = options.accept() {
+            resolution_options = resolution_options.set_accept(accept.clone());
+        }
+        let resolution_output = self
+            .resolve(&did_url.try_into()?, &resolution_options)
+            .await?;
+
+        dereference_did_document(&resolution_output, did_url)
+    }
+
+    fn now(&self) -> Instant {
+        (self.clock)()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<CacheKey, CacheEntry>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached(&self, key: &CacheKey) -> Option<CachedResult> {
+        let mut cache = self.lock();
+        let entry = cache.get(key)?;
+
+        if entry.expires_at <= self.now() {
+            cache.pop(key);
+            return None;
+        }
+        match &entry.result {
+            CachedResult::Found(output) => Some(CachedResult::Found(output.clone())),
+            CachedResult::NotFound => Some(CachedResult::NotFound),
+        }
+    }
+
+    fn expires_at(&self, method: &str, metadata: &DidDocumentMetadata) -> Option<Instant> {
+        let ttl = self.config.ttl(method);
+
+        if metadata.deactivated() == Some(true) {
+            return Some(self.now() + ttl);
+        }
+        if metadata.next_version_id().is_some() {
+            return None;
+        }
+
+        let ttl = match metadata.next_update() {
+            Some(next_update) => {
+                let until_next_update = (next_update - Utc::now()).to_std().unwrap_or_default();
+                ttl.min(until_next_update)
+            }
+            None => ttl,
+        };
+        if ttl == Duration::ZERO {
+            None
+        } else {
+            Some(self.now() + ttl)
+        }
+    }
+
+    fn store_found(&self, key: CacheKey, method: &str, output: &DidResolutionOutput) {
+        let metadata = output.did_document_metadata();
+        let expires_at = match self.expires_at(method, metadata) {
+            Some(expires_at) => expires_at,
+            None => return,
+        };
+
+        let mut cache = self.lock();
+        if let Some(CacheEntry {
+            result: CachedResult::Found(cached),
+            expires_at: cached_expires_at,
+        }) = cache.get_mut(&key)
+        {
+            let cached_metadata = cached.did_document_metadata();
+            if let (Some(cached_updated), Some(updated)) =
+                (cached_metadata.updated(), metadata.updated())
+            {
+                if updated < cached_updated {
+                    return;
+                }
+            }
+            if cached_metadata.version_id().is_some()
+                && cached_metadata.version_id() == metadata.version_id()
+            {
+                *cached_expires_at = expires_at;
+                return;
+            }
+        }
+
+        cache.put(
+            key,
+            CacheEntry {
+                result: CachedResult::Found(Box::new(output.clone())),
+                expires_at,
+            },
+        );
+    }
+}
+
+fn requests_version(did_url: &DidUrl) -> bool {
+    let (queries, params) = (did_url.queries(), did_url.params());
+    VERSION_PARAMETERS
+        .iter()
+        .any(|param| queries.contains_key(*param) || params.contains_key(*param))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use async_trait::async_trait;
+    use chrono::Duration as ChronoDuration;
+    use did_resolver::{
+        did_doc::schema::did_doc::DidDocumentBuilder, traits::resolvable::DidResolvable,
+    };
+
+    use super::*;
+
+    type Respond = fn(&Did, usize) -> Result<DidResolutionOutput, GenericError>;
+
+    struct CountingResolver {
+        calls: Arc<AtomicUsize>,
+        respond: Respond,
+    }
+
+    #[async_trait]
+    impl DidResolvable for CountingResolver {
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            (self.respond)(did, call)
+        }
+    }
+
+    fn output(did: &Did, metadata: DidDocumentMetadata) -> DidResolutionOutput {
+        DidResolutionOutput::builder(DidDocumentBuilder::new(did.clone()).build())
+            .did_document_metadata(metadata)
+            .build()
+    }
+
+    fn found(did: &Did, _call: usize) -> Result<DidResolutionOutput, GenericError> {
+        Ok(output(did, DidDocumentMetadata::default()))
+    }
+
+    fn not_found(_did: &Did, _call: usize) -> Result<DidResolutionOutput, GenericError> {
+        Err(Box::new(DidResolutionError::NotFound))
+    }
+
+    fn caching_registry(
+        respond: Respond,
+        config: ResolutionCacheConfigBuilder,
+    ) -> (CachingResolverRegistry, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = ResolverRegistry::new();
+        for method in ["example", "other"] {
+            registry.register_resolver(
+                method.to_string(),
+                Box::new(CountingResolver {
+                    calls: calls.clone(),
+                    respond,
+                }),
+            );
+        }
+        (
+            CachingResolverRegistry::new(registry, config.build()),
+            calls,
+        )
+    }
+
+    fn config(capacity: usize) -> ResolutionCacheConfigBuilder {
+        ResolutionCacheConfig::builder(
+            Duration::from_secs(60),
+            NonZeroUsize::new(capacity).unwrap(),
+        )
+    }
+
+    fn did(did: &str) -> Did {
+        Did::parse(did.to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_cached() {
+        let (registry, calls) = caching_registry(found, config(10));
+        let did = did("did:example:123");
+
+        let first = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        let second = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        registry
+            .resolve(&did, &DidResolutionOptions::default().set_no_cache(true))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate(&did);
+        registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_method_ttl() {
+        let config = config(10).method_ttl("example".to_string(), Duration::from_secs(1));
+        let (registry, calls) = caching_registry(found, config);
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock = now.clone();
+        let registry = registry.with_clock(move || *clock.lock().unwrap());
+        let expiring = did("did:example:123");
+        let other = did("did:other:123");
+
+        for did in [&expiring, &other] {
+            registry
+                .resolve(did, &DidResolutionOptions::default())
+                .await
+                .unwrap();
+        }
+        *now.lock().unwrap() += Duration::from_secs(2);
+        for did in [&expiring, &other] {
+            registry
+                .resolve(did, &DidResolutionOptions::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_capacity() {
+        let (registry, calls) = caching_registry(found, config(1));
+
+        for did in ["did:example:1", "did:example:2", "did:example:1"] {
+            registry
+                .resolve(&self::did(did), &DidResolutionOptions::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_not_found() {
+        let did = did("did:example:123");
+
+        let (registry, calls) = caching_registry(not_found, config(10));
+        for _ in 0..2 {
+            assert!(registry
+                .resolve(&did, &DidResolutionOptions::default())
+                .await
+                .is_err());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let config = config(10).not_found_ttl(Duration::from_secs(60));
+        let (registry, calls) = caching_registry(not_found, config);
+        for _ in 0..2 {
+            let err = registry
+                .resolve(&did, &DidResolutionOptions::default())
+                .await
+                .unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<DidResolutionError>(),
+                Some(&DidResolutionError::NotFound)
+            );
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_metadata() {
+        fn respond(did: &Did, _call: usize) -> Result<DidResolutionOutput, GenericError> {
+            let past = Utc::now() - ChronoDuration::hours(1);
+            let metadata = match did.id() {
+                "outdated" => DidDocumentMetadata::builder().next_update(past).build(),
+                "superseded" => DidDocumentMetadata::builder()
+                    .next_version_id("2".to_string())
+                    .build(),
+                "deactivated" => DidDocumentMetadata::builder()
+                    .next_update(past)
+                    .deactivated(true)
+                    .build(),
+                _ => DidDocumentMetadata::default(),
+            };
+            Ok(output(did, metadata))
+        }
+        let (registry, calls) = caching_registry(respond, config(10));
+
+        for did in ["did:example:outdated", "did:example:superseded"] {
+            for _ in 0..2 {
+                registry
+                    .resolve(&self::did(did), &DidResolutionOptions::default())
+                    .await
+                    .unwrap();
+            }
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        for _ in 0..2 {
+            registry
+                .resolve(
+                    &did("did:example:deactivated"),
+                    &DidResolutionOptions::default(),
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version_skips_cache() {
+        let (registry, calls) = caching_registry(found, config(10));
+        registry
+            .resolve(&did("did:example:123"), &DidResolutionOptions::default())
+            .await
+            .unwrap();
+
+        let url = DidUrl::parse("did:example:123".to_string()).unwrap();
+        registry
+            .dereference(&url, &DidDereferencingOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        for url in [
+            "did:example:123?versionId=1",
+            "did:example:123?versionTime=2023-01-01T00:00:00Z",
+        ] {
+            let url = DidUrl::parse(url.to_string()).unwrap();
+            registry
+                .dereference(&url, &DidDereferencingOptions::default())
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_keeps_latest_update() {
+        fn respond(did: &Did, call: usize) -> Result<DidResolutionOutput, GenericError> {
+            let updated = Utc::now() - ChronoDuration::hours(call as i64);
+            let metadata = DidDocumentMetadata::builder()
+                .updated(updated)
+                .version_id(call.to_string())
+                .build();
+            Ok(output(did, metadata))
+        }
+        let (registry, _) = caching_registry(respond, config(10));
+        let did = did("did:example:123");
+
+        let latest = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        let stale = registry
+            .resolve(&did, &DidResolutionOptions::default().set_no_cache(true))
+            .await
+            .unwrap();
+        assert_ne!(latest, stale);
+
+        let cached = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(cached, latest);
+    }
+}
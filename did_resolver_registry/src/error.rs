@@ -3,12 +3,16 @@ use std::error::Error;
 #[derive(Debug)]
 pub enum DidResolverRegistryError {
     UnsupportedMethod,
+    RelativeDidUrl,
 }
 
 impl std::fmt::Display for DidResolverRegistryError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             DidResolverRegistryError::UnsupportedMethod => write!(f, "Unsupported DID method"),
+            DidResolverRegistryError::RelativeDidUrl => {
+                write!(f, "Relative DID URL can't be dereferenced")
+            }
         }
     }
 }
@@ -1,25 +1,37 @@
+pub mod cache;
 pub mod error;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Cursor};
 
 use did_resolver::{
-    did_parser::Did,
+    did_parser::{Did, DidUrl},
     error::GenericError,
-    traits::resolvable::{
-        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
-        DidResolvable,
+    traits::{
+        dereferenceable::{
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput, utils::dereference_did_document,
+            DidDereferenceable,
+        },
+        resolvable::{
+            resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
+            DidResolvable,
+        },
     },
 };
 use error::DidResolverRegistryError;
 
+pub type BoxedDereferencer = Box<dyn DidDereferenceable<Output = Cursor<Vec<u8>>>>;
+
 pub struct ResolverRegistry {
     resolvers: HashMap<String, Box<dyn DidResolvable>>,
+    dereferencers: HashMap<String, BoxedDereferencer>,
 }
 
 impl ResolverRegistry {
     pub fn new() -> Self {
         ResolverRegistry {
             resolvers: HashMap::new(),
+            dereferencers: HashMap::new(),
         }
     }
 
@@ -27,8 +39,16 @@ impl ResolverRegistry {
         self.resolvers.insert(method, resolver);
     }
 
+    /// Registers a resolver with its own DID URL dereferencing, used both to resolve DIDs
+    /// of the method and to dereference their DID URLs.
+    pub fn register_dereferencer(&mut self, method: String, dereferencer: BoxedDereferencer) {
+        self.resolvers.remove(&method);
+        self.dereferencers.insert(method, dereferencer);
+    }
+
     pub fn unregister_resolver(&mut self, method: &str) {
         self.resolvers.remove(method);
+        self.dereferencers.remove(method);
     }
 
     pub async fn resolve(
@@ -37,11 +57,40 @@ impl ResolverRegistry {
         options: &DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
         let method = did.method();
+        if let Some(dereferencer) = self.dereferencers.get(method) {
+            return dereferencer.resolve(did, options).await;
+        }
         match self.resolvers.get(method) {
             Some(resolver) => resolver.resolve(did, options).await,
             None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod)),
         }
     }
+
+    /// Dereferences a DID URL of any registered method. Methods registered with a plain resolver
+    /// are dereferenced against the resolved DID document, supporting fragments and the `service`
+    /// and `relativeRef` query parameters.
+    pub async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let method = did_url
+            .method()
+            .ok_or(DidResolverRegistryError::RelativeDidUrl)?;
+        if let Some(dereferencer) = self.dereferencers.get(method) {
+            return dereferencer.dereference(did_url, options).await;
+        }
+
+        let mut resolution_options = DidResolutionOptions::default();
+        if let Some(accept) = options.accept() {
+            resolution_options = resolution_options.set_accept(accept.clone());
+        }
+        let resolution_output = self
+            .resolve(&did_url.try_into()?, &resolution_options)
+            .await?;
+
+        dereference_did_document(&resolution_output, did_url)
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +272,122 @@ mod tests {
             .await;
         assert!(result_after.is_ok());
     }
+
+    struct DummyDereferencer;
+
+    #[async_trait]
+    impl DidResolvable for DummyDereferencer {
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            Ok(DidResolutionOutput::builder(
+                DidDocumentBuilder::new(Did::parse(did.did().to_string()).unwrap()).build(),
+            )
+            .build())
+        }
+    }
+
+    #[async_trait]
+    impl DidDereferenceable for DummyDereferencer {
+        type Output = Cursor<Vec<u8>>;
+
+        async fn dereference(
+            &self,
+            _did_url: &DidUrl,
+            _options: &DidDereferencingOptions,
+        ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+            Ok(DidDereferencingOutput::builder(Cursor::new(b"dereferenced".to_vec())).build())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dereference_with_resolver() {
+        let did = "did:example:1234";
+        let parsed_did = Did::parse(did.to_string()).unwrap();
+        let method = parsed_did.method().to_string();
+
+        let mut mock_resolver = MockDummyDidResolver::new();
+        mock_resolver
+            .expect_resolve()
+            .with(eq(parsed_did.clone()), eq(DidResolutionOptions::default()))
+            .times(1)
+            .return_once(move |_, _| {
+                let future = async move {
+                    Ok::<DidResolutionOutput, GenericError>(
+                        DidResolutionOutput::builder(
+                            DidDocumentBuilder::new(Did::parse(did.to_string()).unwrap()).build(),
+                        )
+                        .build(),
+                    )
+                };
+                Pin::from(Box::new(future))
+            });
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver(method, Box::new(mock_resolver));
+
+        let output = registry
+            .dereference(
+                &DidUrl::parse(did.to_string()).unwrap(),
+                &DidDereferencingOptions::default(),
+            )
+            .await
+            .unwrap();
+        let did_document: serde_json::Value =
+            serde_json::from_slice(output.content_stream().get_ref()).unwrap();
+        assert_eq!(did_document["id"], did);
+    }
+
+    #[tokio::test]
+    async fn test_dereference_with_dereferencer() {
+        let did_url = DidUrl::parse("did:example:1234/some/path".to_string()).unwrap();
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_dereferencer("example".to_string(), Box::new(DummyDereferencer));
+
+        let output = registry
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(output.content_stream().get_ref(), b"dereferenced");
+
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let output = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(output.did_document().id(), &did);
+
+        registry.unregister_resolver("example");
+        let result = registry
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await;
+        assert!(matches!(
+            result
+                .err()
+                .unwrap()
+                .downcast_ref::<DidResolverRegistryError>(),
+            Some(DidResolverRegistryError::UnsupportedMethod)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dereference_relative_did_url() {
+        let registry = ResolverRegistry::new();
+        let result = registry
+            .dereference(
+                &DidUrl::parse("#key-1".to_string()).unwrap(),
+                &DidDereferencingOptions::default(),
+            )
+            .await;
+        assert!(matches!(
+            result
+                .err()
+                .unwrap()
+                .downcast_ref::<DidResolverRegistryError>(),
+            Some(DidResolverRegistryError::RelativeDidUrl)
+        ));
+    }
 }
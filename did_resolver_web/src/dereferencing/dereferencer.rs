@@ -0,0 +1,59 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::{Did, DidUrl},
+    error::GenericError,
+    traits::{
+        dereferenceable::{
+            dereferencing_metadata::DidDereferencingMetadata,
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput,
+            utils::{dereference_did_document, CONTENT_TYPE_URI_LIST},
+            DidDereferenceable,
+        },
+        resolvable::{resolution_options::DidResolutionOptions, DidResolvable},
+    },
+};
+use hyper::client::connect::Connect;
+
+use crate::resolution::resolver::DidWebResolver;
+
+#[async_trait]
+impl<C> DidDereferenceable for DidWebResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    type Output = Cursor<Vec<u8>>;
+
+    /// Dereferences a `did:web` URL. A path redirects to the resource at the same path relative
+    /// to the location the DID is hosted at, anything else is dereferenced against the resolved
+    /// DID document.
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+        let did: Did = did_url.try_into()?;
+
+        if let (Some(path), None) = (did_url.path(), did_url.queries().get("service")) {
+            let url = self.did_web_url(&did, path)?;
+            let dereferencing_metadata = DidDereferencingMetadata::builder()
+                .content_type(CONTENT_TYPE_URI_LIST.to_string())
+                .build();
+            return Ok(
+                DidDereferencingOutput::builder(Cursor::new(url.to_string().into_bytes()))
+                    .dereferencing_metadata(dereferencing_metadata)
+                    .build(),
+            );
+        }
+
+        let mut resolution_options = DidResolutionOptions::default();
+        if let Some(accept) = options.accept() {
+            resolution_options = resolution_options.set_accept(accept.clone());
+        }
+        let resolution_output = self.resolve(&did, &resolution_options).await?;
+
+        dereference_did_document(&resolution_output, did_url)
+    }
+}
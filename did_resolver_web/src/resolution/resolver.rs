@@ -47,6 +47,29 @@ impl<C> DidWebResolver<C>
 where
     C: Connect + Send + Sync + Clone + 'static,
 {
+    /// Builds the URL of a resource at the path relative to the location of a `did:web` DID.
+    pub(crate) fn did_web_url(&self, did: &Did, path: &str) -> Result<Uri, DidWebError> {
+        let did_parts: Vec<&str> = did.id().split(':').collect();
+
+        if did_parts.is_empty() {
+            return Err(DidWebError::InvalidDid(did.id().to_string()));
+        }
+
+        let domain = did_parts[0].replace("%3A", ":");
+
+        let path_and_query = did_parts[1..]
+            .iter()
+            .fold(String::new(), |acc, part| format!("{}/{}", acc, part))
+            + "/"
+            + path.trim_start_matches('/');
+        uri::Builder::new()
+            .scheme(self.scheme.clone())
+            .authority(domain.as_str())
+            .path_and_query(path_and_query.as_str())
+            .build()
+            .map_err(|err| DidWebError::InvalidDid(format!("{}: {}", did, err)))
+    }
+
     async fn fetch_did_document(&self, url: Uri) -> Result<String, DidWebError> {
         let res = self.client.get(url).await?;
 
@@ -76,26 +99,12 @@ where
             }
         }
 
-        let did_parts: Vec<&str> = did.id().split(':').collect();
-
-        if did_parts.is_empty() {
-            return Err(Box::new(DidWebError::InvalidDid(did.id().to_string())));
-        }
-
-        let domain = did_parts[0].replace("%3A", ":");
-
-        let path_parts = &did_parts[1..];
-        let path_and_query = if path_parts.is_empty() {
-            "/.well-known/did.json".to_string()
+        let did_document_path = if did.id().contains(':') {
+            "did.json"
         } else {
-            let path = path_parts.join("/");
-            format!("/{}/did.json", path)
+            ".well-known/did.json"
         };
-        let url = uri::Builder::new()
-            .scheme(self.scheme.clone())
-            .authority(domain.as_str())
-            .path_and_query(path_and_query.as_str())
-            .build()?;
+        let url = self.did_web_url(did, did_document_path)?;
 
         let did_document = serde_json::from_str(&self.fetch_did_document(url).await?)?;
 
@@ -1,5 +1,7 @@
 use did_resolver::did_doc::schema::did_doc::DidDocument;
-use did_resolver::did_parser::Did;
+use did_resolver::did_doc::schema::verification_method::VerificationMethod;
+use did_resolver::did_parser::{Did, DidUrl};
+use did_resolver::traits::dereferenceable::{dereferencing_options::DidDereferencingOptions, DidDereferenceable};
 use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
 use did_resolver_web::resolution::resolver::DidWebResolver;
 use hyper::{
@@ -118,3 +120,32 @@ async fn test_did_web_resolver() {
     );
     verify_did_document(result_2.did_document());
 }
+
+#[tokio::test]
+async fn test_did_web_dereferencer() {
+    let port = 3001;
+    let host = create_mock_server(port).await;
+
+    let did_web_resolver = DidWebResolver::http();
+
+    let did_url = DidUrl::parse(format!("did:web:{}%3A{}#key-1", host, port)).unwrap();
+    let output = assert_ok!(
+        did_web_resolver
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+    );
+    let verification_method: VerificationMethod = serde_json::from_slice(output.content_stream().get_ref()).unwrap();
+    assert_eq!(verification_method.id().to_string(), "did:web:example.com#key-1");
+
+    let did_url = DidUrl::parse(format!("did:web:{}%3A{}:user:alice/photos/1.png", host, port)).unwrap();
+    let output = assert_ok!(
+        did_web_resolver
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+    );
+    assert_eq!(output.dereferencing_metadata().content_type().unwrap(), "text/uri-list");
+    assert_eq!(
+        output.content_stream().get_ref(),
+        format!("http://{}:{}/user/alice/photos/1.png", host, port).as_bytes()
+    );
+}
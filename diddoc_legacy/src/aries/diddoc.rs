@@ -153,6 +153,13 @@ impl AriesDidDoc {
         service.routing_keys.to_vec()
     }
 
+    pub fn accept(&self) -> Vec<String> {
+        match self.service.get(0) {
+            Some(service) => service.accept.clone(),
+            None => Vec::new(),
+        }
+    }
+
     pub fn get_endpoint(&self) -> Option<Url> {
         self.service.get(0).map(|s| s.service_endpoint.clone())
     }
@@ -21,6 +21,9 @@ pub struct AriesService {
     pub routing_keys: Vec<String>,
     #[serde(rename = "serviceEndpoint")]
     pub service_endpoint: Url,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub accept: Vec<String>,
 }
 
 impl AriesService {
@@ -42,6 +45,11 @@ impl AriesService {
         self.recipient_keys = recipient_keys;
         self
     }
+
+    pub fn set_accept(mut self, accept: Vec<String>) -> Self {
+        self.accept = accept;
+        self
+    }
 }
 
 impl Default for AriesService {
@@ -55,6 +63,7 @@ impl Default for AriesService {
                 .expect("dummy url should get parsed"),
             recipient_keys: Vec::new(),
             routing_keys: Vec::new(),
+            accept: Vec::new(),
         }
     }
 }
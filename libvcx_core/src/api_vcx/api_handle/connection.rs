@@ -211,6 +211,8 @@ pub fn get_state(handle: u32) -> LibvcxResult<u32> {
     let state_id = match state {
         ThinState::Invitee(s) => s as u32,
         ThinState::Inviter(s) => s as u32,
+        ThinState::Requester(s) => s as u32,
+        ThinState::Responder(s) => s as u32,
     };
 
     Ok(state_id)
@@ -330,6 +332,14 @@ pub fn process_problem_report(handle: u32, _problem_report: &str) -> LibvcxResul
             handle,
             Connection::new_inviter("".to_owned(), con.pairwise_info().to_owned()),
         ),
+        ThinState::Requester(_) => insert_connection(
+            handle,
+            Connection::new_requester("".to_owned(), con.pairwise_info().to_owned()),
+        ),
+        ThinState::Responder(_) => insert_connection(
+            handle,
+            Connection::new_responder("".to_owned(), con.pairwise_info().to_owned()),
+        ),
     }
 }
 
@@ -2,6 +2,7 @@ extern crate sodiumoxide;
 
 use self::sodiumoxide::crypto::box_;
 use indy_api_types::errors::prelude::*;
+use libc::c_int;
 
 pub const NONCEBYTES: usize = box_::curve25519xsalsa20poly1305::NONCEBYTES;
 pub const PUBLICKEYBYTES: usize = box_::curve25519xsalsa20poly1305::PUBLICKEYBYTES;
@@ -11,6 +12,19 @@ sodium_type!(Nonce, box_::Nonce, NONCEBYTES);
 sodium_type!(PublicKey, box_::PublicKey, PUBLICKEYBYTES);
 sodium_type!(SecretKey, box_::SecretKey, SECRETKEYBYTES);
 
+pub const SHAREDKEYBYTES: usize = 32;
+
+extern "C" {
+    // TODO: fix hack:
+    // this functions isn't included to sodiumoxide rust wrappers,
+    // temporary local binding is used to call libsodium-sys function
+    pub fn crypto_scalarmult_curve25519(
+        q: *mut [u8; SHAREDKEYBYTES],
+        n: *const [u8; SECRETKEYBYTES],
+        p: *const [u8; PUBLICKEYBYTES],
+    ) -> c_int;
+}
+
 pub fn encrypt(
     secret_key: &SecretKey,
     public_key: &PublicKey,
@@ -38,6 +52,19 @@ pub fn gen_nonce() -> Nonce {
     Nonce(box_::gen_nonce())
 }
 
+pub fn key_agreement(secret_key: &SecretKey, public_key: &PublicKey) -> Result<Vec<u8>, IndyError> {
+    let mut shared: [u8; SHAREDKEYBYTES] = [0; SHAREDKEYBYTES];
+    let res =
+        unsafe { crypto_scalarmult_curve25519(&mut shared, &(secret_key.0).0, &(public_key.0).0) };
+    if res != 0 {
+        return Err(IndyError::from_msg(
+            IndyErrorKind::InvalidStructure,
+            "Public key is of low order",
+        ));
+    }
+    Ok(shared.to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +96,25 @@ mod tests {
         assert!(alice_decrypted_text.is_ok());
         assert_eq!(text, alice_decrypted_text.unwrap());
     }
+
+    #[test]
+    fn key_agreement_works() {
+        let (alice_ver_key, alice_sign_key) =
+            ed25519_sign::create_key_pair_for_signature(None).unwrap();
+        let (bob_ver_key, bob_sign_key) =
+            ed25519_sign::create_key_pair_for_signature(None).unwrap();
+
+        let alice_shared = key_agreement(
+            &ed25519_sign::sk_to_curve25519(&alice_sign_key).unwrap(),
+            &ed25519_sign::vk_to_curve25519(&bob_ver_key).unwrap(),
+        )
+        .unwrap();
+        let bob_shared = key_agreement(
+            &ed25519_sign::sk_to_curve25519(&bob_sign_key).unwrap(),
+            &ed25519_sign::vk_to_curve25519(&alice_ver_key).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(alice_shared, bob_shared);
+    }
 }
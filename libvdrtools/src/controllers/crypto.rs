@@ -163,6 +163,48 @@ impl CryptoController {
         Ok(res)
     }
 
+    /// Computes the X25519 shared secret of a key in the wallet, converted to X25519, and a X25519 public key.
+    ///
+    /// #Params
+
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// my_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// their_pk: raw X25519 public key of the other party
+    ///
+    /// #Returns
+    /// the raw shared secret
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    pub async fn crypto_key_agreement(
+        &self,
+        wallet_handle: WalletHandle,
+        my_vk: &str,
+        their_pk: &[u8],
+    ) -> IndyResult<Vec<u8>> {
+        trace!(
+            "crypto_key_agreement >>> wallet_handle: {:?}, my_vk: {:?}, their_pk: {:?}",
+            wallet_handle,
+            my_vk,
+            their_pk
+        );
+
+        self.crypto_service.validate_key(my_vk).await?;
+
+        let key: Key = self
+            .wallet_service
+            .get_indy_object(wallet_handle, &my_vk, &RecordOptions::id_value())
+            .await?;
+
+        let res = self.crypto_service.key_agreement(&key, their_pk).await?;
+
+        trace!("crypto_key_agreement <<<");
+
+        Ok(res)
+    }
+
     /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
     ///
     /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
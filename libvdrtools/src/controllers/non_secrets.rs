@@ -374,14 +374,52 @@ impl NonSecretsController {
 
         self._check_type(&type_)?;
 
-        serde_json::from_str::<SearchOptions>(&options_json).to_indy(
+        self._open_search(wallet_handle, &type_, &query_json, &options_json)
+            .await
+    }
+
+    /// Search for wallet records of any type, including the types of the records
+    /// vdrtools keeps for itself (DIDs, keys with their private parts, anoncreds objects)
+    /// which `open_search` refuses. Only for reading all the records of a wallet in order
+    /// to migrate them to another storage; these records can still only be modified by
+    /// vdrtools.
+    ///
+    /// #Params and #Returns as for `open_search`
+    pub async fn open_migration_search(
+        &self,
+        wallet_handle: WalletHandle,
+        type_: String,
+        query_json: String,
+        options_json: String,
+    ) -> IndyResult<SearchHandle> {
+        trace!(
+            "open_migration_search > wallet_handle {:?} type_ {:?} \
+                query_json {:?} options_json {:?}",
+            wallet_handle,
+            type_,
+            query_json,
+            options_json
+        );
+
+        self._open_search(wallet_handle, &type_, &query_json, &options_json)
+            .await
+    }
+
+    async fn _open_search(
+        &self,
+        wallet_handle: WalletHandle,
+        type_: &str,
+        query_json: &str,
+        options_json: &str,
+    ) -> IndyResult<SearchHandle> {
+        serde_json::from_str::<SearchOptions>(options_json).to_indy(
             IndyErrorKind::InvalidStructure,
             "Cannot deserialize options",
         )?;
 
         let search = self
             .wallet_service
-            .search_records(wallet_handle, &type_, &query_json, &options_json)
+            .search_records(wallet_handle, type_, query_json, options_json)
             .await?;
 
         let search_handle = next_search_handle();
@@ -45,6 +45,14 @@ impl CryptoType for ED25519CryptoType {
         ed25519_box::gen_nonce()
     }
 
+    fn key_agreement(
+        &self,
+        sk: &ed25519_sign::SecretKey,
+        their_pk: &ed25519_box::PublicKey,
+    ) -> Result<Vec<u8>, IndyError> {
+        ed25519_box::key_agreement(&ed25519_sign::sk_to_curve25519(sk)?, their_pk)
+    }
+
     fn create_key(
         &self,
         seed: Option<&ed25519_sign::Seed>,
@@ -46,6 +46,12 @@ trait CryptoType: Send + Sync {
 
     fn gen_nonce(&self) -> ed25519_box::Nonce;
 
+    fn key_agreement(
+        &self,
+        sk: &ed25519_sign::SecretKey,
+        their_pk: &ed25519_box::PublicKey,
+    ) -> IndyResult<Vec<u8>>;
+
     fn create_key(
         &self,
         seed: Option<&ed25519_sign::Seed>,
@@ -235,6 +241,37 @@ impl CryptoService {
         res
     }
 
+    pub(crate) async fn key_agreement(&self, my_key: &Key, their_pk: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!(
+            "key_agreement > my_key {:?} their_pk {:?}",
+            my_key,
+            their_pk
+        );
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+        let crypto_types = self.crypto_types.read().await;
+
+        let crypto_type = crypto_types.get(crypto_type_name).ok_or_else(|| {
+            err_msg(
+                IndyErrorKind::UnknownCrypto,
+                format!(
+                    "Trying to agree on key with unknown crypto: {}",
+                    crypto_type_name
+                ),
+            )
+        })?;
+
+        let my_sk = ed25519_sign::SecretKey::from_slice(
+            &my_key.signkey.as_str().from_base58()?.as_slice(),
+        )?;
+        let their_pk = ed25519_box::PublicKey::from_slice(their_pk)?;
+
+        let shared = crypto_type.key_agreement(&my_sk, &their_pk)?;
+
+        trace!("key_agreement <");
+        Ok(shared)
+    }
+
     pub(crate) async fn verify(
         &self,
         their_vk: &str,
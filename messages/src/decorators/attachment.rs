@@ -42,9 +42,8 @@ impl Attachment {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AttachmentData {
-    // There probably is a better type for this???
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub jws: Option<String>,
+    pub jws: Option<AttachmentJws>,
     // Better type for this as well?
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sha256: Option<String>,
@@ -62,6 +61,36 @@ impl AttachmentData {
     }
 }
 
+/// Detached JWS over the attachment content, used to prove that the attachment was signed by a certain key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentJws {
+    pub header: AttachmentJwsHeader,
+    pub protected: String,
+    pub signature: String,
+}
+
+impl AttachmentJws {
+    pub fn new(header: AttachmentJwsHeader, protected: String, signature: String) -> Self {
+        Self {
+            header,
+            protected,
+            signature,
+        }
+    }
+}
+
+/// Unprotected header of an [`AttachmentJws`], identifying the signing key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentJwsHeader {
+    pub kid: String,
+}
+
+impl AttachmentJwsHeader {
+    pub fn new(kid: String) -> Self {
+        Self { kid }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum AttachmentType {
@@ -165,7 +194,11 @@ pub mod tests {
 
     #[test]
     fn test_extended_attach_data() {
-        let jws = "test_jws".to_owned();
+        let jws = AttachmentJws::new(
+            AttachmentJwsHeader::new("test_kid".to_owned()),
+            "test_protected".to_owned(),
+            "test_signature".to_owned(),
+        );
         let sha256 = "test_sha256".to_owned();
 
         let data = json!({
@@ -174,7 +207,13 @@ pub mod tests {
 
         let expected = json!({
             "json": data,
-            "jws": jws,
+            "jws": {
+                "header": {
+                    "kid": "test_kid"
+                },
+                "protected": "test_protected",
+                "signature": "test_signature"
+            },
             "sha256": sha256
         });
 
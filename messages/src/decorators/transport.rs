@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// Struct representing the `~transport` decorator from its [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0092-transport-return-route/README.md>).
+///
+/// Senders which cannot be reached at an endpoint, such as mobile agents, use it to have the
+/// messages meant for them sent back over the connection their message came on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transport {
+    pub return_route: ReturnRoute,
+    /// The thread whose messages are returned, when `return_route` is [`ReturnRoute::Thread`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_route_thread: Option<String>,
+}
+
+impl Transport {
+    pub fn new(return_route: ReturnRoute) -> Self {
+        Self {
+            return_route,
+            return_route_thread: None,
+        }
+    }
+
+    /// Messages of the thread only are to be returned.
+    pub fn return_thread(thread_id: String) -> Self {
+        Self {
+            return_route: ReturnRoute::Thread,
+            return_route_thread: Some(thread_id),
+        }
+    }
+
+    /// Whether the messages of the thread are to be returned over the connection the decorated
+    /// message came on.
+    pub fn returns_thread(&self, thread_id: &str) -> bool {
+        match self.return_route {
+            ReturnRoute::None => false,
+            ReturnRoute::All => true,
+            ReturnRoute::Thread => self.return_route_thread.as_deref() == Some(thread_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReturnRoute {
+    /// Messages are sent to the sender's endpoint, as if the decorator was missing.
+    None,
+    /// All messages for the sender are returned, for as long as the connection stays open.
+    All,
+    /// Only messages of the thread given by `return_route_thread` are returned.
+    Thread,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    pub fn make_minimal_transport() -> Transport {
+        Transport::new(ReturnRoute::All)
+    }
+
+    pub fn make_extended_transport() -> Transport {
+        Transport::return_thread("test_thread_id".to_owned())
+    }
+
+    #[test]
+    fn test_minimal_transport() {
+        let transport = make_minimal_transport();
+        let expected = json!({ "return_route": "all" });
+
+        test_utils::test_serde(transport, expected);
+    }
+
+    #[test]
+    fn test_extended_transport() {
+        let transport = make_extended_transport();
+        let expected = json!({
+            "return_route": "thread",
+            "return_route_thread": "test_thread_id"
+        });
+
+        test_utils::test_serde(transport, expected);
+    }
+
+    #[test]
+    fn test_transport_returns_thread() {
+        assert!(make_minimal_transport().returns_thread("any_thread_id"));
+        assert!(make_extended_transport().returns_thread("test_thread_id"));
+        assert!(!make_extended_transport().returns_thread("other_thread_id"));
+        assert!(!Transport::new(ReturnRoute::None).returns_thread("test_thread_id"));
+    }
+}
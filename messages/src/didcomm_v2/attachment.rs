@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::decorators::attachment::AttachmentType;
+
+/// Struct representing a DIDComm v2 attachment, as defined in the [spec](<https://identity.foundation/didcomm-messaging/spec/v2.0/#attachments>).
+///
+/// Unlike the `~attach` decorator, the media type is a free form string
+/// and the timestamps are expressed in UTC epoch seconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentV2 {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
+    pub data: AttachmentDataV2,
+}
+
+impl AttachmentV2 {
+    pub fn new(data: AttachmentDataV2) -> Self {
+        Self {
+            id: None,
+            description: None,
+            filename: None,
+            media_type: None,
+            format: None,
+            lastmod_time: None,
+            byte_count: None,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentDataV2 {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jws: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(flatten)]
+    pub content: AttachmentType,
+}
+
+impl AttachmentDataV2 {
+    pub fn new(content: AttachmentType) -> Self {
+        Self {
+            jws: None,
+            hash: None,
+            content,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    pub fn make_extended_attachment_v2() -> AttachmentV2 {
+        let content = AttachmentType::Json(json!({ "field": "test_json_data" }));
+        let mut attachment = AttachmentV2::new(AttachmentDataV2::new(content));
+
+        attachment.id = Some("test_id".to_owned());
+        attachment.description = Some("test_description".to_owned());
+        attachment.filename = Some("test_filename".to_owned());
+        attachment.media_type = Some("application/json".to_owned());
+        attachment.format = Some("test_format".to_owned());
+        attachment.lastmod_time = Some(1_600_000_000);
+        attachment.byte_count = Some(64);
+
+        attachment
+    }
+
+    #[test]
+    fn test_minimal_attachment_v2() {
+        let content = AttachmentType::Base64("test_base64_str".to_owned());
+        let attachment = AttachmentV2::new(AttachmentDataV2::new(content));
+
+        let expected = json!({
+            "data": {
+                "base64": "test_base64_str"
+            }
+        });
+
+        test_utils::test_serde(attachment, expected);
+    }
+
+    #[test]
+    fn test_extended_attachment_v2() {
+        let attachment = make_extended_attachment_v2();
+
+        let expected = json!({
+            "id": attachment.id,
+            "description": attachment.description,
+            "filename": attachment.filename,
+            "media_type": attachment.media_type,
+            "format": attachment.format,
+            "lastmod_time": attachment.lastmod_time,
+            "byte_count": attachment.byte_count,
+            "data": {
+                "json": { "field": "test_json_data" }
+            }
+        });
+
+        test_utils::test_serde(attachment, expected);
+    }
+}
@@ -0,0 +1,203 @@
+use serde::{de::Error, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::plaintext::PlaintextMessage;
+
+/// Struct representing a DIDComm v2 encrypted message, which is a JWE in
+/// [general JSON serialization](<https://datatracker.ietf.org/doc/html/rfc7516#section-7.2.1>).
+///
+/// All fields are base64url encoded (without padding).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Jwe {
+    pub protected: String,
+    pub recipients: Vec<JweRecipient>,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JweRecipient {
+    pub header: JweRecipientHeader,
+    pub encrypted_key: String,
+}
+
+impl JweRecipient {
+    pub fn new(kid: String, encrypted_key: String) -> Self {
+        Self {
+            header: JweRecipientHeader { kid },
+            encrypted_key,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JweRecipientHeader {
+    pub kid: String,
+}
+
+/// Struct representing a DIDComm v2 signed message, which is a JWS in
+/// [general JSON serialization](<https://datatracker.ietf.org/doc/html/rfc7515#section-7.2.1>).
+///
+/// All fields are base64url encoded (without padding).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Jws {
+    pub payload: String,
+    pub signatures: Vec<JwsSignature>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JwsSignature {
+    pub protected: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<JwsSignatureHeader>,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JwsSignatureHeader {
+    pub kid: String,
+}
+
+/// The three shapes a DIDComm v2 message can have on the wire.
+///
+/// The shapes are told apart by their mandatory members,
+/// as none of them share these with the others.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DidCommV2Envelope {
+    Plaintext(PlaintextMessage),
+    Signed(Jws),
+    Encrypted(Jwe),
+}
+
+impl DidCommV2Envelope {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        let value: Value = serde_json::from_slice(bytes)?;
+        Self::from_value(value)
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, serde_json::Error> {
+        let Some(obj) = value.as_object() else {
+            return Err(serde_json::Error::custom("DIDComm v2 message is not a JSON object"));
+        };
+
+        if obj.contains_key("ciphertext") {
+            serde_json::from_value(value).map(Self::Encrypted)
+        } else if obj.contains_key("signatures") {
+            serde_json::from_value(value).map(Self::Signed)
+        } else if obj.contains_key("body") {
+            serde_json::from_value(value).map(Self::Plaintext)
+        } else {
+            Err(serde_json::Error::custom("unknown DIDComm v2 message structure"))
+        }
+    }
+}
+
+impl Serialize for DidCommV2Envelope {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Plaintext(v) => v.serialize(serializer),
+            Self::Signed(v) => v.serialize(serializer),
+            Self::Encrypted(v) => v.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use serde_json::{json, Map};
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    fn make_jwe() -> Jwe {
+        Jwe {
+            protected: "test_protected".to_owned(),
+            recipients: vec![JweRecipient::new(
+                "did:example:bob#key-1".to_owned(),
+                "test_encrypted_key".to_owned(),
+            )],
+            iv: "test_iv".to_owned(),
+            ciphertext: "test_ciphertext".to_owned(),
+            tag: "test_tag".to_owned(),
+        }
+    }
+
+    fn make_jws() -> Jws {
+        Jws {
+            payload: "test_payload".to_owned(),
+            signatures: vec![JwsSignature {
+                protected: "test_protected".to_owned(),
+                header: Some(JwsSignatureHeader {
+                    kid: "did:example:alice#key-1".to_owned(),
+                }),
+                signature: "test_signature".to_owned(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_jwe() {
+        let expected = json!({
+            "protected": "test_protected",
+            "recipients": [
+                {
+                    "header": { "kid": "did:example:bob#key-1" },
+                    "encrypted_key": "test_encrypted_key"
+                }
+            ],
+            "iv": "test_iv",
+            "ciphertext": "test_ciphertext",
+            "tag": "test_tag"
+        });
+
+        test_utils::test_serde(make_jwe(), expected);
+    }
+
+    #[test]
+    fn test_jws() {
+        let expected = json!({
+            "payload": "test_payload",
+            "signatures": [
+                {
+                    "protected": "test_protected",
+                    "header": { "kid": "did:example:alice#key-1" },
+                    "signature": "test_signature"
+                }
+            ]
+        });
+
+        test_utils::test_serde(make_jws(), expected);
+    }
+
+    #[test]
+    fn test_envelope_detection() {
+        let jwe = make_jwe();
+        let jws = make_jws();
+        let plaintext = PlaintextMessage::new(
+            "test_id".to_owned(),
+            "https://didcomm.org/trust_ping/1.0/ping".to_owned(),
+            Map::new(),
+        );
+
+        let envelope = DidCommV2Envelope::from_value(serde_json::to_value(&jwe).unwrap()).unwrap();
+        assert_eq!(envelope, DidCommV2Envelope::Encrypted(jwe));
+
+        let envelope = DidCommV2Envelope::from_value(serde_json::to_value(&jws).unwrap()).unwrap();
+        assert_eq!(envelope, DidCommV2Envelope::Signed(jws));
+
+        let bytes = serde_json::to_vec(&plaintext).unwrap();
+        let envelope = DidCommV2Envelope::from_slice(&bytes).unwrap();
+        assert_eq!(envelope, DidCommV2Envelope::Plaintext(plaintext));
+    }
+
+    #[test]
+    fn test_envelope_detection_fails_for_unknown() {
+        assert!(DidCommV2Envelope::from_value(json!({ "field": "value" })).is_err());
+        assert!(DidCommV2Envelope::from_value(json!("value")).is_err());
+    }
+}
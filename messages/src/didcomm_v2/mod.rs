@@ -0,0 +1,24 @@
+//! Module containing the DIDComm v2 message representations, as defined in the [spec](<https://identity.foundation/didcomm-messaging/spec/v2.0/>).
+//!
+//! DIDComm v2 uses a different message structure than the Aries (DIDComm v1) messages
+//! represented by [`crate::AriesMessage`]: the `@type` and `@id` fields become `type` and `id`,
+//! the `~thread` decorator is replaced by the top level `thid` and `pthid` fields
+//! and the protocol specific fields are nested in a `body` object.
+//!
+//! The protocol content, however, is the same, so [`PlaintextMessage`] can be converted
+//! to and from an [`crate::AriesMessage`] to reuse the protocol content types.
+
+pub mod attachment;
+pub mod envelope;
+pub mod plaintext;
+
+pub use attachment::{AttachmentDataV2, AttachmentV2};
+pub use envelope::{DidCommV2Envelope, Jwe, JweRecipient, JweRecipientHeader, Jws, JwsSignature, JwsSignatureHeader};
+pub use plaintext::PlaintextMessage;
+
+/// Media type of a DIDComm v2 plaintext message.
+pub const PLAINTEXT_MEDIA_TYPE: &str = "application/didcomm-plain+json";
+/// Media type of a DIDComm v2 signed message.
+pub const SIGNED_MEDIA_TYPE: &str = "application/didcomm-signed+json";
+/// Media type of a DIDComm v2 encrypted message.
+pub const ENCRYPTED_MEDIA_TYPE: &str = "application/didcomm-encrypted+json";
@@ -0,0 +1,306 @@
+use chrono::{TimeZone, Utc};
+use serde::{de::Error, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{attachment::AttachmentV2, PLAINTEXT_MEDIA_TYPE};
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    AriesMessage,
+};
+
+const V1_TYPE: &str = "@type";
+const V1_ID: &str = "@id";
+const V1_THREAD: &str = "~thread";
+const V1_TIMING: &str = "~timing";
+
+/// Struct representing a DIDComm v2 plaintext message, as defined in the [spec](<https://identity.foundation/didcomm-messaging/spec/v2.0/#plaintext-message-structure>).
+///
+/// The protocol specific fields live in the `body`, which means the message
+/// can be converted to and from an [`AriesMessage`]:
+/// - `type` and `id` map to `@type` and `@id`
+/// - `thid` and `pthid` map to the `~thread` decorator
+/// - `created_time` and `expires_time` map to the `out_time` and `expires_time` of the `~timing` decorator
+///
+/// Everything else in the `~thread` and `~timing` decorators has no DIDComm v2 equivalent
+/// and is dropped when converting. The top level `attachments` of a DIDComm v2 message are not
+/// part of any DIDComm v1 message, so they're not carried over to the [`AriesMessage`] either.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PlaintextMessage {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pthid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_time: Option<u64>,
+    #[serde(default)]
+    pub body: Map<String, Value>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentV2>,
+}
+
+impl PlaintextMessage {
+    pub fn new(id: String, msg_type: String, body: Map<String, Value>) -> Self {
+        Self {
+            id,
+            msg_type,
+            typ: Some(PLAINTEXT_MEDIA_TYPE.to_owned()),
+            thid: None,
+            pthid: None,
+            from: None,
+            to: None,
+            created_time: None,
+            expires_time: None,
+            body,
+            attachments: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<&AriesMessage> for PlaintextMessage {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &AriesMessage) -> Result<Self, Self::Error> {
+        let Value::Object(mut body) = serde_json::to_value(value)? else {
+            return Err(serde_json::Error::custom("message did not serialize to a JSON object"));
+        };
+
+        let msg_type = take_string(&mut body, V1_TYPE)?;
+        let id = take_string(&mut body, V1_ID)?;
+
+        let thread = body
+            .remove(V1_THREAD)
+            .map(serde_json::from_value::<Thread>)
+            .transpose()?;
+
+        let timing = body
+            .remove(V1_TIMING)
+            .map(serde_json::from_value::<Timing>)
+            .transpose()?;
+
+        let (thid, pthid) = match thread {
+            Some(thread) => (Some(thread.thid), thread.pthid),
+            None => (None, None),
+        };
+
+        let (created_time, expires_time) = match timing {
+            Some(timing) => (
+                timing.out_time.map(|t| t.timestamp() as u64),
+                timing.expires_time.map(|t| t.timestamp() as u64),
+            ),
+            None => (None, None),
+        };
+
+        let mut msg = Self::new(id, msg_type, body);
+        msg.thid = thid;
+        msg.pthid = pthid;
+        msg.created_time = created_time;
+        msg.expires_time = expires_time;
+
+        Ok(msg)
+    }
+}
+
+impl TryFrom<PlaintextMessage> for AriesMessage {
+    type Error = serde_json::Error;
+
+    fn try_from(value: PlaintextMessage) -> Result<Self, Self::Error> {
+        let PlaintextMessage {
+            id,
+            msg_type,
+            thid,
+            pthid,
+            created_time,
+            expires_time,
+            mut body,
+            ..
+        } = value;
+
+        if thid.is_some() || pthid.is_some() {
+            // DIDComm v1 requires a `thid` in the `~thread` decorator.
+            // The thread id of the first message in a thread is the message id.
+            let mut thread = Thread::new(thid.unwrap_or_else(|| id.clone()));
+            thread.pthid = pthid;
+            body.insert(V1_THREAD.to_owned(), serde_json::to_value(thread)?);
+        }
+
+        if created_time.is_some() || expires_time.is_some() {
+            let timing = Timing {
+                out_time: created_time.map(epoch_to_datetime).transpose()?,
+                expires_time: expires_time.map(epoch_to_datetime).transpose()?,
+                ..Default::default()
+            };
+            body.insert(V1_TIMING.to_owned(), serde_json::to_value(timing)?);
+        }
+
+        body.insert(V1_TYPE.to_owned(), Value::String(msg_type));
+        body.insert(V1_ID.to_owned(), Value::String(id));
+
+        serde_json::from_value(Value::Object(body))
+    }
+}
+
+fn take_string(map: &mut Map<String, Value>, key: &str) -> Result<String, serde_json::Error> {
+    match map.remove(key) {
+        Some(Value::String(s)) => Ok(s),
+        _ => Err(serde_json::Error::custom(format!("missing or invalid field: {key}"))),
+    }
+}
+
+fn epoch_to_datetime(secs: u64) -> Result<chrono::DateTime<Utc>, serde_json::Error> {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .ok_or_else(|| serde_json::Error::custom(format!("invalid epoch timestamp: {secs}")))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        didcomm_v2::attachment::tests::make_extended_attachment_v2,
+        misc::test_utils,
+        msg_fields::protocols::{
+            basic_message::{BasicMessage, BasicMessageContent, BasicMessageDecorators},
+            trust_ping::{
+                ping::{Ping, PingContent, PingDecorators},
+                TrustPing,
+            },
+        },
+    };
+
+    #[test]
+    fn test_minimal_plaintext() {
+        let msg = PlaintextMessage::new(
+            "test_id".to_owned(),
+            "https://didcomm.org/trust_ping/1.0/ping".to_owned(),
+            Map::new(),
+        );
+
+        let expected = json!({
+            "id": "test_id",
+            "type": "https://didcomm.org/trust_ping/1.0/ping",
+            "typ": PLAINTEXT_MEDIA_TYPE,
+            "body": {}
+        });
+
+        test_utils::test_serde(msg, expected);
+    }
+
+    #[test]
+    fn test_extended_plaintext() {
+        let mut body = Map::new();
+        body.insert("response_requested".to_owned(), json!(true));
+
+        let mut msg = PlaintextMessage::new(
+            "test_id".to_owned(),
+            "https://didcomm.org/trust_ping/1.0/ping".to_owned(),
+            body,
+        );
+        msg.thid = Some("test_thid".to_owned());
+        msg.pthid = Some("test_pthid".to_owned());
+        msg.from = Some("did:example:alice".to_owned());
+        msg.to = Some(vec!["did:example:bob".to_owned()]);
+        msg.created_time = Some(1_600_000_000);
+        msg.expires_time = Some(1_600_000_100);
+        msg.attachments = vec![make_extended_attachment_v2()];
+
+        let expected = json!({
+            "id": "test_id",
+            "type": "https://didcomm.org/trust_ping/1.0/ping",
+            "typ": PLAINTEXT_MEDIA_TYPE,
+            "thid": "test_thid",
+            "pthid": "test_pthid",
+            "from": "did:example:alice",
+            "to": ["did:example:bob"],
+            "created_time": 1_600_000_000,
+            "expires_time": 1_600_000_100,
+            "body": {
+                "response_requested": true
+            },
+            "attachments": msg.attachments
+        });
+
+        test_utils::test_serde(msg, expected);
+    }
+
+    #[test]
+    fn test_aries_msg_to_plaintext() {
+        let mut content = PingContent::default();
+        content.response_requested = true;
+
+        let mut decorators = PingDecorators::default();
+        let mut thread = Thread::new("test_thid".to_owned());
+        thread.pthid = Some("test_pthid".to_owned());
+        decorators.thread = Some(thread);
+
+        let msg = AriesMessage::from(TrustPing::Ping(Ping::with_decorators(
+            "test_id".to_owned(),
+            content,
+            decorators,
+        )));
+
+        let plaintext = PlaintextMessage::try_from(&msg).unwrap();
+
+        assert_eq!(plaintext.id, "test_id");
+        assert_eq!(plaintext.msg_type, "https://didcomm.org/trust_ping/1.0/ping");
+        assert_eq!(plaintext.thid.as_deref(), Some("test_thid"));
+        assert_eq!(plaintext.pthid.as_deref(), Some("test_pthid"));
+        assert_eq!(plaintext.body.get("response_requested"), Some(&json!(true)));
+        assert!(plaintext.body.get(V1_THREAD).is_none());
+    }
+
+    #[test]
+    fn test_plaintext_aries_msg_roundtrip() {
+        let content = BasicMessageContent::new("test_content".to_owned(), Utc.timestamp_opt(0, 0).unwrap());
+        let mut decorators = BasicMessageDecorators::default();
+        decorators.thread = Some(Thread::new("test_thid".to_owned()));
+
+        let msg = AriesMessage::from(BasicMessage::with_decorators("test_id".to_owned(), content, decorators));
+
+        let plaintext = PlaintextMessage::try_from(&msg).unwrap();
+        let converted = AriesMessage::try_from(plaintext).unwrap();
+
+        assert_eq!(msg, converted);
+    }
+
+    #[test]
+    fn test_plaintext_thid_defaults_to_id() {
+        let mut msg = PlaintextMessage::new(
+            "test_id".to_owned(),
+            "https://didcomm.org/trust_ping/1.0/ping".to_owned(),
+            Map::new(),
+        );
+        msg.pthid = Some("test_pthid".to_owned());
+
+        let value = serde_json::to_value(AriesMessage::try_from(msg).unwrap()).unwrap();
+
+        assert_eq!(value["~thread"]["thid"], json!("test_id"));
+        assert_eq!(value["~thread"]["pthid"], json!("test_pthid"));
+    }
+
+    #[test]
+    fn test_plaintext_unknown_type_fails() {
+        let msg = PlaintextMessage::new(
+            "test_id".to_owned(),
+            "https://didcomm.org/unknown/1.0/ping".to_owned(),
+            Map::new(),
+        );
+
+        assert!(AriesMessage::try_from(msg).is_err());
+    }
+}
@@ -7,6 +7,7 @@
 #![deny(clippy::unwrap_used)]
 
 pub mod decorators;
+pub mod didcomm_v2;
 mod error;
 pub mod misc;
 pub mod msg_fields;
@@ -23,9 +24,11 @@ use crate::{
     misc::utils::MSG_TYPE,
     msg_fields::{
         protocols::{
-            basic_message::BasicMessage, connection::Connection, cred_issuance::CredentialIssuance,
-            discover_features::DiscoverFeatures, notification::Notification, out_of_band::OutOfBand,
-            present_proof::PresentProof, report_problem::ProblemReport, revocation::Revocation, routing::Forward,
+            basic_message::BasicMessage, connection::Connection, coordinate_mediation::CoordinateMediation,
+            cred_issuance::CredentialIssuance, cred_issuance_v2::CredentialIssuanceV2, did_exchange::DidExchange,
+            discover_features::DiscoverFeatures, discover_features_v2::DiscoverFeaturesV2, notification::Notification,
+            out_of_band::OutOfBand, pickup::Pickup, present_proof::PresentProof, present_proof_v2::PresentProofV2,
+            report_problem::ProblemReport, revocation::Revocation, routing::Forward, transactions::Transactions,
             trust_ping::TrustPing,
         },
         traits::DelayedSerde,
@@ -34,6 +37,9 @@ use crate::{
         basic_message::BasicMessageTypeV1_0,
         protocols::{
             basic_message::{BasicMessageType, BasicMessageTypeV1},
+            cred_issuance::CredentialIssuanceType,
+            discover_features::DiscoverFeaturesType,
+            present_proof::PresentProofType,
             report_problem::{ReportProblemType, ReportProblemTypeV1},
             routing::{RoutingType, RoutingTypeV1},
         },
@@ -54,13 +60,20 @@ pub enum AriesMessage {
     Connection(Connection),
     Revocation(Revocation),
     CredentialIssuance(CredentialIssuance),
+    CredentialIssuanceV2(CredentialIssuanceV2),
     ReportProblem(ProblemReport),
     PresentProof(PresentProof),
+    PresentProofV2(PresentProofV2),
     TrustPing(TrustPing),
     DiscoverFeatures(DiscoverFeatures),
+    DiscoverFeaturesV2(DiscoverFeaturesV2),
     BasicMessage(BasicMessage),
     OutOfBand(OutOfBand),
     Notification(Notification),
+    DidExchange(DidExchange),
+    CoordinateMediation(CoordinateMediation),
+    Pickup(Pickup),
+    Transactions(Transactions),
 }
 
 impl DelayedSerde for AriesMessage {
@@ -99,9 +112,12 @@ impl DelayedSerde for AriesMessage {
             Protocol::RevocationType(msg_type) => {
                 Revocation::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
-            Protocol::CredentialIssuanceType(msg_type) => {
+            Protocol::CredentialIssuanceType(CredentialIssuanceType::V1(msg_type)) => {
                 CredentialIssuance::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::CredentialIssuanceType(CredentialIssuanceType::V2(msg_type)) => {
+                CredentialIssuanceV2::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
             Protocol::ReportProblemType(msg_type) => {
                 let kind = match msg_type {
                     ReportProblemType::V1(ReportProblemTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
@@ -111,15 +127,21 @@ impl DelayedSerde for AriesMessage {
                     ReportProblemTypeV1_0::ProblemReport => ProblemReport::deserialize(deserializer).map(From::from),
                 }
             }
-            Protocol::PresentProofType(msg_type) => {
+            Protocol::PresentProofType(PresentProofType::V1(msg_type)) => {
                 PresentProof::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::PresentProofType(PresentProofType::V2(msg_type)) => {
+                PresentProofV2::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
             Protocol::TrustPingType(msg_type) => {
                 TrustPing::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
-            Protocol::DiscoverFeaturesType(msg_type) => {
+            Protocol::DiscoverFeaturesType(DiscoverFeaturesType::V1(msg_type)) => {
                 DiscoverFeatures::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::DiscoverFeaturesType(DiscoverFeaturesType::V2(msg_type)) => {
+                DiscoverFeaturesV2::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
             Protocol::BasicMessageType(msg_type) => {
                 let kind = match msg_type {
                     BasicMessageType::V1(BasicMessageTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
@@ -135,6 +157,18 @@ impl DelayedSerde for AriesMessage {
             Protocol::NotificationType(msg_type) => {
                 Notification::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::DidExchangeType(msg_type) => {
+                DidExchange::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::CoordinateMediationType(msg_type) => {
+                CoordinateMediation::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::PickupType(msg_type) => {
+                Pickup::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::TransactionsType(msg_type) => {
+                Transactions::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
         }
     }
 
@@ -147,13 +181,20 @@ impl DelayedSerde for AriesMessage {
             Self::Connection(v) => v.delayed_serialize(serializer),
             Self::Revocation(v) => v.delayed_serialize(serializer),
             Self::CredentialIssuance(v) => v.delayed_serialize(serializer),
+            Self::CredentialIssuanceV2(v) => v.delayed_serialize(serializer),
             Self::ReportProblem(v) => MsgWithType::from(v).serialize(serializer),
             Self::PresentProof(v) => v.delayed_serialize(serializer),
+            Self::PresentProofV2(v) => v.delayed_serialize(serializer),
             Self::TrustPing(v) => v.delayed_serialize(serializer),
             Self::DiscoverFeatures(v) => v.delayed_serialize(serializer),
+            Self::DiscoverFeaturesV2(v) => v.delayed_serialize(serializer),
             Self::BasicMessage(v) => MsgWithType::from(v).serialize(serializer),
             Self::OutOfBand(v) => v.delayed_serialize(serializer),
             Self::Notification(v) => v.delayed_serialize(serializer),
+            Self::DidExchange(v) => v.delayed_serialize(serializer),
+            Self::CoordinateMediation(v) => v.delayed_serialize(serializer),
+            Self::Pickup(v) => v.delayed_serialize(serializer),
+            Self::Transactions(v) => v.delayed_serialize(serializer),
         }
     }
 }
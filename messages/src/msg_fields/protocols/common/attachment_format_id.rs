@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+/// Struct binding an attachment to its format, used by protocols that
+/// support multiple attachment formats (e.g: `issue-credential/2.0`, `present-proof/2.0`).
+///
+/// The `attach_id` is the `@id` of the attachment in the attachments array of the message,
+/// while the `format` is the identifier of the attachment format.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AttachmentFormatSpecifier<F> {
+    pub attach_id: String,
+    pub format: MaybeKnown<F>,
+}
+
+impl<F> AttachmentFormatSpecifier<F> {
+    pub fn new(attach_id: String, format: MaybeKnown<F>) -> Self {
+        Self { attach_id, format }
+    }
+}
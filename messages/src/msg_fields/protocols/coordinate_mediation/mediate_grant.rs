@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type MediateGrant = MsgParts<MediateGrantContent, MediateGrantDecorators>;
+
+/// The routing information the mediator grants to the recipient.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MediateGrantContent {
+    pub endpoint: Url,
+    pub routing_keys: Vec<String>,
+}
+
+impl MediateGrantContent {
+    pub fn new(endpoint: Url, routing_keys: Vec<String>) -> Self {
+        Self { endpoint, routing_keys }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MediateGrantDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl MediateGrantDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::coordinate_mediation::CoordinateMediationTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_mediate_grant() {
+        let content = MediateGrantContent::new(
+            "https://mediator.example.com/".parse().unwrap(),
+            vec!["did:key:z6MkfvYpQgPXmPwSnoXf4S7hLHHsXR6hdEqvgzWeuEJYDFgE".to_owned()],
+        );
+
+        let decorators = MediateGrantDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "endpoint": content.endpoint,
+            "routing_keys": content.routing_keys,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, CoordinateMediationTypeV1_0::MediateGrant, expected);
+    }
+
+    #[test]
+    fn test_extended_mediate_grant() {
+        let content = MediateGrantContent::new("https://mediator.example.com/".parse().unwrap(), Vec::new());
+
+        let mut decorators = MediateGrantDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "endpoint": content.endpoint,
+            "routing_keys": content.routing_keys,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, CoordinateMediationTypeV1_0::MediateGrant, expected);
+    }
+}
@@ -48,7 +48,7 @@ pub enum CredentialIssuance {
 }
 
 impl DelayedSerde for CredentialIssuance {
-    type MsgType<'a> = (CredentialIssuanceKind, &'a str);
+    type MsgType<'a> = (CredentialIssuanceTypeV1, &'a str);
 
     fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
     where
@@ -56,7 +56,7 @@ impl DelayedSerde for CredentialIssuance {
     {
         let (protocol, kind_str) = msg_type;
         let kind = match protocol {
-            CredentialIssuanceKind::V1(CredentialIssuanceTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+            CredentialIssuanceTypeV1::V1_0(kind) => kind.kind_from_str(kind_str),
         };
 
         match kind.map_err(D::Error::custom)? {
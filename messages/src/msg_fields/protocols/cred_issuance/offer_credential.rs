@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use super::CredentialPreview;
 use crate::{
-    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    decorators::{attachment::Attachment, please_ack::PleaseAck, thread::Thread, timing::Timing},
     msg_parts::MsgParts,
 };
 
@@ -32,6 +32,9 @@ pub struct OfferCredentialDecorators {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "~thread")]
     pub thread: Option<Thread>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
     #[serde(rename = "~timing")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timing: Option<Timing>,
@@ -46,8 +49,8 @@ mod tests {
     use super::*;
     use crate::{
         decorators::{
-            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
-            timing::tests::make_extended_timing,
+            attachment::tests::make_extended_attachment, please_ack::tests::make_minimal_please_ack,
+            thread::tests::make_extended_thread, timing::tests::make_extended_timing,
         },
         misc::test_utils,
         msg_fields::protocols::cred_issuance::CredentialAttr,
@@ -84,6 +87,7 @@ mod tests {
 
         let mut decorators = OfferCredentialDecorators::default();
         decorators.thread = Some(make_extended_thread());
+        decorators.please_ack = Some(make_minimal_please_ack());
         decorators.timing = Some(make_extended_timing());
 
         let expected = json!({
@@ -91,6 +95,7 @@ mod tests {
             "credential_preview": content.credential_preview,
             "comment": content.comment,
             "~thread": decorators.thread,
+            "~please_ack": decorators.please_ack,
             "~timing": decorators.timing
         });
 
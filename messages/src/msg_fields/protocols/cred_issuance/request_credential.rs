@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    decorators::{attachment::Attachment, please_ack::PleaseAck, thread::Thread, timing::Timing},
     msg_parts::MsgParts,
 };
 
@@ -29,6 +29,9 @@ pub struct RequestCredentialDecorators {
     #[serde(rename = "~thread")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thread: Option<Thread>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
     #[serde(rename = "~timing")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timing: Option<Timing>,
@@ -42,7 +45,10 @@ mod tests {
 
     use super::*;
     use crate::{
-        decorators::{attachment::tests::make_extended_attachment, thread::tests::make_extended_thread},
+        decorators::{
+            attachment::tests::make_extended_attachment, please_ack::tests::make_minimal_please_ack,
+            thread::tests::make_extended_thread,
+        },
         misc::test_utils,
         msg_types::cred_issuance::CredentialIssuanceTypeV1_0,
     };
@@ -72,11 +78,13 @@ mod tests {
 
         let mut decorators = RequestCredentialDecorators::default();
         decorators.thread = Some(make_extended_thread());
+        decorators.please_ack = Some(make_minimal_please_ack());
 
         let expected = json!({
             "requests~attach": content.requests_attach,
             "comment": content.comment,
-            "~thread": decorators.thread
+            "~thread": decorators.thread,
+            "~please_ack": decorators.please_ack
         });
 
         test_utils::test_msg(
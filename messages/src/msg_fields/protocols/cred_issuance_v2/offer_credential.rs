@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use super::CredentialPreviewV2;
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_fields::protocols::common::attachment_format_id::AttachmentFormatSpecifier,
+    msg_parts::MsgParts,
+};
+
+pub type OfferCredentialV2 = MsgParts<OfferCredentialV2Content, OfferCredentialV2Decorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct OfferCredentialV2Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_id: Option<String>,
+    pub credential_preview: CredentialPreviewV2,
+    pub formats: Vec<AttachmentFormatSpecifier<OfferCredentialAttachmentFormatType>>,
+    #[serde(rename = "offers~attach")]
+    pub offers_attach: Vec<Attachment>,
+}
+
+impl OfferCredentialV2Content {
+    pub fn new(
+        credential_preview: CredentialPreviewV2,
+        formats: Vec<AttachmentFormatSpecifier<OfferCredentialAttachmentFormatType>>,
+        offers_attach: Vec<Attachment>,
+    ) -> Self {
+        Self {
+            goal_code: None,
+            comment: None,
+            replacement_id: None,
+            credential_preview,
+            formats,
+            offers_attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct OfferCredentialV2Decorators {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "~thread")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+/// Format types derived from Aries RFC Registry:
+/// <https://github.com/hyperledger/aries-rfcs/tree/main/features/0453-issue-credential-v2#offer-attachment-registry>
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum OfferCredentialAttachmentFormatType {
+    #[serde(rename = "dif/credential-manifest@v1.0")]
+    DifCredentialManifest1_0,
+    #[serde(rename = "hlindy/cred-abstract@v2.0")]
+    HyperledgerIndyCredentialAbstract2_0,
+    #[serde(rename = "anoncreds/credential-offer@v1.0")]
+    AnoncredsCredentialOffer1_0,
+    #[serde(rename = "aries/ld-proof-vc-detail@v1.0")]
+    AriesLdProofVcDetail1_0,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+    use shared_vcx::maybe_known::MaybeKnown;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_fields::protocols::cred_issuance::CredentialAttr,
+        msg_types::cred_issuance::CredentialIssuanceTypeV2_0,
+    };
+
+    fn make_content() -> OfferCredentialV2Content {
+        let attribute = CredentialAttr::new("test_attribute_name".to_owned(), "test_attribute_value".to_owned());
+        let preview = CredentialPreviewV2::new(vec![attribute]);
+
+        OfferCredentialV2Content::new(
+            preview,
+            vec![AttachmentFormatSpecifier::new(
+                "test_attach_id".to_owned(),
+                MaybeKnown::Known(OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0),
+            )],
+            vec![make_extended_attachment()],
+        )
+    }
+
+    #[test]
+    fn test_minimal_offer_cred_v2() {
+        let content = make_content();
+        let decorators = OfferCredentialV2Decorators::default();
+
+        let expected = json!({
+            "credential_preview": content.credential_preview,
+            "formats": [{
+                "attach_id": "test_attach_id",
+                "format": "hlindy/cred-abstract@v2.0"
+            }],
+            "offers~attach": content.offers_attach,
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::OfferCredential,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_extended_offer_cred_v2() {
+        let mut content = make_content();
+        content.goal_code = Some("test_goal_code".to_owned());
+        content.comment = Some("test_comment".to_owned());
+        content.replacement_id = Some("test_replacement_id".to_owned());
+
+        let mut decorators = OfferCredentialV2Decorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "goal_code": content.goal_code,
+            "comment": content.comment,
+            "replacement_id": content.replacement_id,
+            "credential_preview": content.credential_preview,
+            "formats": [{
+                "attach_id": "test_attach_id",
+                "format": "hlindy/cred-abstract@v2.0"
+            }],
+            "offers~attach": content.offers_attach,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::OfferCredential,
+            expected,
+        );
+    }
+}
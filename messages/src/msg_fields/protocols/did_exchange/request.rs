@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_fields::protocols::out_of_band::OobGoalCode,
+    msg_parts::MsgParts,
+};
+
+pub type Request = MsgParts<RequestContent, RequestDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestContent {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<MaybeKnown<OobGoalCode>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    pub did: String,
+    #[serde(rename = "did_doc~attach")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_doc: Option<Attachment>,
+}
+
+impl RequestContent {
+    pub fn new(label: String, did: String) -> Self {
+        Self {
+            label,
+            goal_code: None,
+            goal: None,
+            did,
+            did_doc: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct RequestDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::did_exchange::DidExchangeTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_didexchange_request() {
+        let content = RequestContent::new("test_request_label".to_owned(), "test_did".to_owned());
+
+        let decorators = RequestDecorators::default();
+
+        let expected = json!({
+            "label": content.label,
+            "did": content.did
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Request, expected);
+    }
+
+    #[test]
+    fn test_extended_didexchange_request() {
+        let mut content = RequestContent::new("test_request_label".to_owned(), "test_did".to_owned());
+        content.goal_code = Some(MaybeKnown::Known(OobGoalCode::P2PMessaging));
+        content.goal = Some("test_goal".to_owned());
+        content.did_doc = Some(make_extended_attachment());
+
+        let mut decorators = RequestDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "label": content.label,
+            "goal_code": content.goal_code,
+            "goal": content.goal,
+            "did": content.did,
+            "did_doc~attach": content.did_doc,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Request, expected);
+    }
+}
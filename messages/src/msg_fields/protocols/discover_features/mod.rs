@@ -15,9 +15,8 @@ use crate::{
     misc::utils::{into_msg_with_type, transit_to_aries_msg},
     msg_fields::traits::DelayedSerde,
     msg_types::{
-        protocols::discover_features::{
-            DiscoverFeaturesType as DiscoverFeaturesKind, DiscoverFeaturesTypeV1, DiscoverFeaturesTypeV1_0,
-        },
+        protocols::discover_features::{DiscoverFeaturesTypeV1, DiscoverFeaturesTypeV1_0},
+        registry::PROTOCOL_REGISTRY,
         MsgWithType, Protocol, Role,
     },
 };
@@ -29,7 +28,7 @@ pub enum DiscoverFeatures {
 }
 
 impl DelayedSerde for DiscoverFeatures {
-    type MsgType<'a> = (DiscoverFeaturesKind, &'a str);
+    type MsgType<'a> = (DiscoverFeaturesTypeV1, &'a str);
 
     fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
     where
@@ -38,7 +37,7 @@ impl DelayedSerde for DiscoverFeatures {
         let (protocol, kind_str) = msg_type;
 
         let kind = match protocol {
-            DiscoverFeaturesKind::V1(DiscoverFeaturesTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+            DiscoverFeaturesTypeV1::V1_0(kind) => kind.kind_from_str(kind_str),
         };
 
         match kind.map_err(D::Error::custom)? {
@@ -71,8 +70,128 @@ impl ProtocolDescriptor {
     }
 }
 
+/// Looks up into the [`PROTOCOL_REGISTRY`] and returns a [`Vec<ProtocolDescriptor>`] of the protocols whose
+/// *pid* matches the query, sorted by *pid*. Queries using the legacy `did:sov` prefix are matched as well.
+///
+/// If `roles` are provided, only these are disclosed and protocols in which none of them
+/// can be played are left out.
+pub fn lookup_protocols(query: &str, roles: Option<&[Role]>) -> Vec<ProtocolDescriptor> {
+    let query = match query.strip_prefix(Protocol::DID_SOV_PREFIX) {
+        Some(rest) => format!("{}{}", Protocol::DID_COM_ORG_PREFIX, rest),
+        None => query.to_owned(),
+    };
+
+    let mut entries: Vec<_> = PROTOCOL_REGISTRY
+        .values()
+        .flatten()
+        .filter(|entry| matches_query(&query, &entry.str_pid))
+        .collect();
+    entries.sort_by(|a, b| a.str_pid.cmp(&b.str_pid));
+
+    let mut protocols = Vec::new();
+
+    for entry in entries {
+        let entry_roles = match roles {
+            None => entry.roles.clone(),
+            Some(roles) => {
+                let entry_roles: Vec<_> = entry
+                    .roles
+                    .iter()
+                    .filter(|role| matches!(role, MaybeKnown::Known(role) if roles.contains(role)))
+                    .cloned()
+                    .collect();
+
+                if entry_roles.is_empty() {
+                    continue;
+                }
+                entry_roles
+            }
+        };
+
+        let mut pd = ProtocolDescriptor::new(MaybeKnown::Known(entry.protocol));
+        pd.roles = Some(entry_roles);
+        protocols.push(pd);
+    }
+
+    protocols
+}
+
+/// Matches a value against a discover features query, where `*` stands for any sequence of characters.
+/// A query without wildcards only matches the exact value.
+pub fn matches_query(query: &str, value: &str) -> bool {
+    let mut parts = query.split('*');
+    let prefix = parts.next().unwrap_or_default();
+
+    let Some(mut rest) = value.strip_prefix(prefix) else {
+        return false;
+    };
+
+    let mut parts: Vec<_> = parts.collect();
+    let Some(suffix) = parts.pop() else {
+        return rest.is_empty();
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= suffix.len() && rest.ends_with(suffix)
+}
+
 transit_to_aries_msg!(QueryContent: QueryDecorators, DiscoverFeatures);
 transit_to_aries_msg!(DiscloseContent: DiscloseDecorators, DiscoverFeatures);
 
 into_msg_with_type!(Query, DiscoverFeaturesTypeV1_0, Query);
 into_msg_with_type!(Disclose, DiscoverFeaturesTypeV1_0, Disclose);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg_types::{protocols::connection::ConnectionTypeV1, traits::ProtocolVersion};
+
+    #[test]
+    fn test_matches_query() {
+        let pid = "https://didcomm.org/connections/1.0";
+
+        assert!(matches_query("*", pid));
+        assert!(matches_query(pid, pid));
+        assert!(matches_query("https://didcomm.org/connections/*", pid));
+        assert!(matches_query("https://didcomm.org/*/1.0", pid));
+        assert!(matches_query("*connections*", pid));
+        assert!(!matches_query("https://didcomm.org/connections/", pid));
+        assert!(!matches_query("https://didcomm.org/connections/2.*", pid));
+        assert!(!matches_query("*/2.0", pid));
+    }
+
+    #[test]
+    fn test_lookup_legacy_prefix() {
+        let query = format!("{}/connections/*", Protocol::DID_SOV_PREFIX);
+        let protocols = lookup_protocols(&query, None);
+
+        let pid = ConnectionTypeV1::new_v1_0();
+        let mut pd = ProtocolDescriptor::new(MaybeKnown::Known(pid.into()));
+        pd.roles = Some(pid.roles());
+
+        assert_eq!(protocols, vec![pd]);
+    }
+
+    #[test]
+    fn test_lookup_roles() {
+        let protocols = lookup_protocols(
+            "https://didcomm.org/connections/*",
+            Some(&[Role::Invitee, Role::Holder]),
+        );
+
+        let pid = ConnectionTypeV1::new_v1_0();
+        let mut pd = ProtocolDescriptor::new(MaybeKnown::Known(pid.into()));
+        pd.roles = Some(vec![MaybeKnown::Known(Role::Invitee)]);
+
+        assert_eq!(protocols, vec![pd]);
+
+        let protocols = lookup_protocols("https://didcomm.org/connections/*", Some(&[Role::Holder]));
+        assert!(protocols.is_empty());
+    }
+}
@@ -1,8 +1,7 @@
 use serde::{Deserialize, Serialize};
-use shared_vcx::maybe_known::MaybeKnown;
 
-use super::ProtocolDescriptor;
-use crate::{decorators::timing::Timing, msg_parts::MsgParts, msg_types::registry::PROTOCOL_REGISTRY};
+use super::{lookup_protocols, ProtocolDescriptor};
+use crate::{decorators::timing::Timing, msg_parts::MsgParts, msg_types::Role};
 
 pub type Query = MsgParts<QueryContent, QueryDecorators>;
 
@@ -18,27 +17,14 @@ impl QueryContent {
         Self { query, comment: None }
     }
 
-    /// Looks up into the [`PROTOCOL_REGISTRY`] and returns a [`Vec<ProtocolDescriptor`] matching the inner query.
+    /// Looks up into the [`PROTOCOL_REGISTRY`](crate::msg_types::registry::PROTOCOL_REGISTRY) and returns a [`Vec<ProtocolDescriptor>`] matching the inner query.
     pub fn lookup(&self) -> Vec<ProtocolDescriptor> {
-        let mut protocols = Vec::new();
-        let query = self
-            .query
-            .split('*')
-            .next()
-            .expect("query must have at least an empty string before *");
-
-        for entries in PROTOCOL_REGISTRY.values() {
-            for entry in entries {
-                if entry.str_pid.starts_with(query) {
-                    let pid = MaybeKnown::Known(entry.protocol);
-                    let mut pd = ProtocolDescriptor::new(pid);
-                    pd.roles = Some(entry.roles.clone());
-                    protocols.push(pd);
-                }
-            }
-        }
+        lookup_protocols(&self.query, None)
+    }
 
-        protocols
+    /// Same as [`QueryContent::lookup`], but only discloses the given roles.
+    pub fn lookup_for_roles(&self, roles: &[Role]) -> Vec<ProtocolDescriptor> {
+        lookup_protocols(&self.query, Some(roles))
     }
 }
 
@@ -54,6 +40,7 @@ pub struct QueryDecorators {
 #[allow(clippy::field_reassign_with_default)]
 mod tests {
     use serde_json::json;
+    use shared_vcx::maybe_known::MaybeKnown;
 
     use super::*;
     use crate::{
@@ -61,7 +48,7 @@ mod tests {
         misc::test_utils,
         msg_types::{
             discover_features::DiscoverFeaturesTypeV1_0, protocols::connection::ConnectionTypeV1,
-            traits::ProtocolVersion,
+            registry::PROTOCOL_REGISTRY, traits::ProtocolVersion,
         },
     };
 
@@ -99,15 +86,16 @@ mod tests {
     fn test_lookup_match_all() {
         let matched_all = QueryContent::new("*".to_owned()).lookup();
 
+        let mut entries: Vec<_> = PROTOCOL_REGISTRY.values().flatten().collect();
+        entries.sort_by(|a, b| a.str_pid.cmp(&b.str_pid));
+
         let mut protocols = Vec::new();
 
-        for entries in PROTOCOL_REGISTRY.values() {
-            for entry in entries {
-                let pid = MaybeKnown::Known(entry.protocol);
-                let mut pd = ProtocolDescriptor::new(pid);
-                pd.roles = Some(entry.roles.clone());
-                protocols.push(pd);
-            }
+        for entry in entries {
+            let pid = MaybeKnown::Known(entry.protocol);
+            let mut pd = ProtocolDescriptor::new(pid);
+            pd.roles = Some(entry.roles.clone());
+            protocols.push(pd);
         }
 
         assert_eq!(protocols, matched_all);
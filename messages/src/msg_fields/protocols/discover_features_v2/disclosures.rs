@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::FeatureType;
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::discover_features::ProtocolDescriptor,
+    msg_parts::MsgParts,
+    msg_types::Role,
+};
+
+pub type Disclosures = MsgParts<DisclosuresContent, DisclosuresDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DisclosuresContent {
+    pub disclosures: Vec<Disclosure>,
+}
+
+impl DisclosuresContent {
+    pub fn new(disclosures: Vec<Disclosure>) -> Self {
+        Self { disclosures }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Disclosure {
+    #[serde(rename = "feature-type")]
+    pub feature_type: MaybeKnown<FeatureType>,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<MaybeKnown<Role>>>,
+}
+
+impl Disclosure {
+    pub fn new(feature_type: FeatureType, id: String) -> Self {
+        Self {
+            feature_type: MaybeKnown::Known(feature_type),
+            id,
+            roles: None,
+        }
+    }
+}
+
+impl From<ProtocolDescriptor> for Disclosure {
+    fn from(value: ProtocolDescriptor) -> Self {
+        let id = match value.pid {
+            MaybeKnown::Known(protocol) => protocol.to_string(),
+            MaybeKnown::Unknown(pid) => pid,
+        };
+
+        Self {
+            feature_type: MaybeKnown::Known(FeatureType::Protocol),
+            id,
+            roles: value.roles,
+        }
+    }
+}
+
+/// Disclosures can be sent without being queried, in which case they are not threaded.
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct DisclosuresDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::{
+            discover_features::DiscoverFeaturesTypeV2_0, protocols::connection::ConnectionTypeV1,
+            traits::ProtocolVersion,
+        },
+    };
+
+    #[test]
+    fn test_minimal_disclosures() {
+        let content = DisclosuresContent::new(vec![Disclosure::new(
+            FeatureType::GoalCode,
+            "aries.vc.issue".to_owned(),
+        )]);
+
+        let decorators = DisclosuresDecorators::default();
+
+        let expected = json!({
+            "disclosures": [{
+                "feature-type": "goal-code",
+                "id": "aries.vc.issue"
+            }]
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Disclosures, expected);
+    }
+
+    #[test]
+    fn test_extended_disclosures() {
+        let pid = ConnectionTypeV1::new_v1_0();
+        let mut pd = ProtocolDescriptor::new(MaybeKnown::Known(pid.into()));
+        pd.roles = Some(pid.roles());
+
+        let content = DisclosuresContent::new(vec![
+            pd.into(),
+            Disclosure::new(FeatureType::DidcommVersion, "didcomm/aip2;env=rfc19".to_owned()),
+        ]);
+
+        let mut decorators = DisclosuresDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "disclosures": [
+                {
+                    "feature-type": "protocol",
+                    "id": "https://didcomm.org/connections/1.0",
+                    "roles": ["inviter", "invitee"]
+                },
+                {
+                    "feature-type": "didcomm-version",
+                    "id": "didcomm/aip2;env=rfc19"
+                }
+            ],
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Disclosures, expected);
+    }
+}
@@ -0,0 +1,74 @@
+//! Module containing the `discover features` protocol messages, version 2.0, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0557-discover-features-v2/README.md>).
+//!
+//! Unlike version 1.0, several queries can be sent at once and features other than protocols,
+//! such as goal codes and DIDComm versions, can be discovered.
+
+pub mod disclosures;
+pub mod queries;
+
+use derive_more::From;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use self::{
+    disclosures::{Disclosures, DisclosuresContent, DisclosuresDecorators},
+    queries::{Queries, QueriesContent, QueriesDecorators},
+};
+use crate::{
+    misc::utils::{into_msg_with_type, transit_to_aries_msg},
+    msg_fields::traits::DelayedSerde,
+    msg_types::{
+        protocols::discover_features::{DiscoverFeaturesTypeV2, DiscoverFeaturesTypeV2_0},
+        MsgWithType,
+    },
+};
+
+#[derive(Clone, Debug, From, PartialEq)]
+pub enum DiscoverFeaturesV2 {
+    Queries(Queries),
+    Disclosures(Disclosures),
+}
+
+impl DelayedSerde for DiscoverFeaturesV2 {
+    type MsgType<'a> = (DiscoverFeaturesTypeV2, &'a str);
+
+    fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (protocol, kind_str) = msg_type;
+
+        let kind = match protocol {
+            DiscoverFeaturesTypeV2::V2_0(kind) => kind.kind_from_str(kind_str),
+        };
+
+        match kind.map_err(D::Error::custom)? {
+            DiscoverFeaturesTypeV2_0::Queries => Queries::deserialize(deserializer).map(From::from),
+            DiscoverFeaturesTypeV2_0::Disclosures => Disclosures::deserialize(deserializer).map(From::from),
+        }
+    }
+
+    fn delayed_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Queries(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Disclosures(v) => MsgWithType::from(v).serialize(serializer),
+        }
+    }
+}
+
+/// The kinds of features that can be queried and disclosed.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeatureType {
+    Protocol,
+    GoalCode,
+    DidcommVersion,
+}
+
+transit_to_aries_msg!(QueriesContent: QueriesDecorators, DiscoverFeaturesV2);
+transit_to_aries_msg!(DisclosuresContent: DisclosuresDecorators, DiscoverFeaturesV2);
+
+into_msg_with_type!(Queries, DiscoverFeaturesTypeV2_0, Queries);
+into_msg_with_type!(Disclosures, DiscoverFeaturesTypeV2_0, Disclosures);
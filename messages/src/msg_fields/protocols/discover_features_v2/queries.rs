@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::FeatureType;
+use crate::{decorators::timing::Timing, msg_fields::protocols::discover_features::matches_query, msg_parts::MsgParts};
+
+pub type Queries = MsgParts<QueriesContent, QueriesDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct QueriesContent {
+    pub queries: Vec<FeatureQuery>,
+}
+
+impl QueriesContent {
+    pub fn new(queries: Vec<FeatureQuery>) -> Self {
+        Self { queries }
+    }
+}
+
+/// A query for the features of a type whose identifier matches a pattern, where `*` stands
+/// for any sequence of characters.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FeatureQuery {
+    #[serde(rename = "feature-type")]
+    pub feature_type: MaybeKnown<FeatureType>,
+    #[serde(rename = "match")]
+    pub pattern: String,
+}
+
+impl FeatureQuery {
+    pub fn new(feature_type: FeatureType, pattern: String) -> Self {
+        Self {
+            feature_type: MaybeKnown::Known(feature_type),
+            pattern,
+        }
+    }
+
+    /// Tells whether a feature of the given type and identifier is queried.
+    pub fn matches(&self, feature_type: FeatureType, id: &str) -> bool {
+        self.feature_type == MaybeKnown::Known(feature_type) && matches_query(&self.pattern, id)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct QueriesDecorators {
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::timing::tests::make_extended_timing, misc::test_utils,
+        msg_types::discover_features::DiscoverFeaturesTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_queries() {
+        let content = QueriesContent::new(vec![FeatureQuery::new(FeatureType::Protocol, "*".to_owned())]);
+
+        let decorators = QueriesDecorators::default();
+
+        let expected = json!({
+            "queries": [{
+                "feature-type": "protocol",
+                "match": "*"
+            }]
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Queries, expected);
+    }
+
+    #[test]
+    fn test_extended_queries() {
+        let content = QueriesContent::new(vec![
+            FeatureQuery::new(FeatureType::Protocol, "https://didcomm.org/connections/1.*".to_owned()),
+            FeatureQuery::new(FeatureType::GoalCode, "aries.*".to_owned()),
+            FeatureQuery::new(FeatureType::DidcommVersion, "*".to_owned()),
+        ]);
+
+        let mut decorators = QueriesDecorators::default();
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "queries": [
+                { "feature-type": "protocol", "match": "https://didcomm.org/connections/1.*" },
+                { "feature-type": "goal-code", "match": "aries.*" },
+                { "feature-type": "didcomm-version", "match": "*" }
+            ],
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Queries, expected);
+    }
+
+    #[test]
+    fn test_unknown_feature_type() {
+        let query: FeatureQuery = serde_json::from_value(json!({
+            "feature-type": "gov-fw",
+            "match": "*"
+        }))
+        .unwrap();
+
+        assert_eq!(query.feature_type, MaybeKnown::Unknown("gov-fw".to_owned()));
+        assert!(!query.matches(FeatureType::Protocol, "https://didcomm.org/connections/1.0"));
+    }
+}
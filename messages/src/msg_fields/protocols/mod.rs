@@ -1,11 +1,19 @@
 pub mod basic_message;
+pub mod common;
 pub mod connection;
+pub mod coordinate_mediation;
 pub mod cred_issuance;
+pub mod cred_issuance_v2;
+pub mod did_exchange;
 pub mod discover_features;
+pub mod discover_features_v2;
 pub mod notification;
 pub mod out_of_band;
+pub mod pickup;
 pub mod present_proof;
+pub mod present_proof_v2;
 pub mod report_problem;
 pub mod revocation;
 pub mod routing;
+pub mod transactions;
 pub mod trust_ping;
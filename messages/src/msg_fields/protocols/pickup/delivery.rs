@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Delivery = MsgParts<DeliveryContent, DeliveryDecorators>;
+
+/// Batch of queued messages. Every attachment holds a packed message
+/// and its ID is the one to acknowledge through a `messages-received` message.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DeliveryContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_key: Option<String>,
+    #[serde(rename = "~attach")]
+    pub attach: Vec<Attachment>,
+}
+
+impl DeliveryContent {
+    pub fn new(attach: Vec<Attachment>) -> Self {
+        Self {
+            recipient_key: None,
+            attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct DeliveryDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::pickup::PickupTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_delivery() {
+        let content = DeliveryContent::new(vec![make_extended_attachment()]);
+
+        let decorators = DeliveryDecorators::default();
+
+        let expected = json!({
+            "~attach": content.attach
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::Delivery, expected);
+    }
+
+    #[test]
+    fn test_extended_delivery() {
+        let mut content = DeliveryContent::new(vec![make_extended_attachment()]);
+        content.recipient_key = Some("test_recipient_key".to_owned());
+
+        let mut decorators = DeliveryDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "recipient_key": content.recipient_key,
+            "~attach": content.attach,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::Delivery, expected);
+    }
+}
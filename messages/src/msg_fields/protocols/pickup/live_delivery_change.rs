@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing, transport::Transport},
+    msg_parts::MsgParts,
+};
+
+pub type LiveDeliveryChange = MsgParts<LiveDeliveryChangeContent, LiveDeliveryChangeDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct LiveDeliveryChangeContent {
+    pub live_delivery: bool,
+}
+
+impl LiveDeliveryChangeContent {
+    pub fn new(live_delivery: bool) -> Self {
+        Self { live_delivery }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct LiveDeliveryChangeDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~transport")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            thread::tests::make_extended_thread, timing::tests::make_extended_timing,
+            transport::tests::make_extended_transport,
+        },
+        misc::test_utils,
+        msg_types::pickup::PickupTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_live_delivery_change() {
+        let content = LiveDeliveryChangeContent::new(true);
+
+        let decorators = LiveDeliveryChangeDecorators::default();
+
+        let expected = json!({
+            "live_delivery": true
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::LiveDeliveryChange, expected);
+    }
+
+    #[test]
+    fn test_extended_live_delivery_change() {
+        let content = LiveDeliveryChangeContent::new(false);
+
+        let mut decorators = LiveDeliveryChangeDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+        decorators.transport = Some(make_extended_transport());
+
+        let expected = json!({
+            "live_delivery": false,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing,
+            "~transport": decorators.transport
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::LiveDeliveryChange, expected);
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    decorators::{attachment::Attachment, please_ack::PleaseAck, thread::Thread, timing::Timing},
     msg_parts::MsgParts,
 };
 
@@ -29,6 +29,9 @@ pub struct RequestPresentationDecorators {
     #[serde(rename = "~thread")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thread: Option<Thread>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
     #[serde(rename = "~timing")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timing: Option<Timing>,
@@ -43,8 +46,8 @@ mod tests {
     use super::*;
     use crate::{
         decorators::{
-            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
-            timing::tests::make_extended_timing,
+            attachment::tests::make_extended_attachment, please_ack::tests::make_minimal_please_ack,
+            thread::tests::make_extended_thread, timing::tests::make_extended_timing,
         },
         misc::test_utils,
         msg_types::present_proof::PresentProofTypeV1_0,
@@ -70,12 +73,14 @@ mod tests {
 
         let mut decorators = RequestPresentationDecorators::default();
         decorators.thread = Some(make_extended_thread());
+        decorators.please_ack = Some(make_minimal_please_ack());
         decorators.timing = Some(make_extended_timing());
 
         let expected = json!({
             "request_presentations~attach": content.request_presentations_attach,
             "comment": content.comment,
             "~thread": decorators.thread,
+            "~please_ack": decorators.please_ack,
             "~timing": decorators.timing
         });
 
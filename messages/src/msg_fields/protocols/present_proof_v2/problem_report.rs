@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    msg_fields::protocols::report_problem::{ProblemReportContent, ProblemReportDecorators},
+    msg_parts::MsgParts,
+};
+
+pub type PresentProofV2ProblemReport = MsgParts<PresentProofV2ProblemReportContent, ProblemReportDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct PresentProofV2ProblemReportContent(pub ProblemReportContent);
+
+impl PresentProofV2ProblemReportContent {
+    pub fn new(code: String) -> Self {
+        Self(ProblemReportContent::new(code))
+    }
+}
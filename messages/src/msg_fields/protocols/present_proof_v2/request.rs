@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_fields::protocols::common::attachment_format_id::AttachmentFormatSpecifier,
+    msg_parts::MsgParts,
+};
+
+pub type RequestPresentationV2 = MsgParts<RequestPresentationV2Content, RequestPresentationV2Decorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestPresentationV2Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub will_confirm: Option<bool>,
+    pub formats: Vec<AttachmentFormatSpecifier<RequestPresentationAttachmentFormatType>>,
+    #[serde(rename = "request_presentations~attach")]
+    pub request_presentations_attach: Vec<Attachment>,
+}
+
+impl RequestPresentationV2Content {
+    pub fn new(
+        formats: Vec<AttachmentFormatSpecifier<RequestPresentationAttachmentFormatType>>,
+        request_presentations_attach: Vec<Attachment>,
+    ) -> Self {
+        Self {
+            goal_code: None,
+            comment: None,
+            will_confirm: None,
+            formats,
+            request_presentations_attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct RequestPresentationV2Decorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum RequestPresentationAttachmentFormatType {
+    #[serde(rename = "hlindy/proof-req@v2.0")]
+    HyperledgerIndyProofRequest2_0,
+    #[serde(rename = "anoncreds/proof-request@v1.0")]
+    AnoncredsProofRequest1_0,
+    #[serde(rename = "dif/presentation-exchange/definitions@v1.0")]
+    DifPresentationExchangeDefinitions1_0,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+    use shared_vcx::maybe_known::MaybeKnown;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::present_proof::PresentProofTypeV2_0,
+    };
+
+    fn make_content() -> RequestPresentationV2Content {
+        RequestPresentationV2Content::new(
+            vec![AttachmentFormatSpecifier::new(
+                "test_attach_id".to_owned(),
+                MaybeKnown::Known(RequestPresentationAttachmentFormatType::DifPresentationExchangeDefinitions1_0),
+            )],
+            vec![make_extended_attachment()],
+        )
+    }
+
+    #[test]
+    fn test_minimal_request_presentation_v2() {
+        let content = make_content();
+        let decorators = RequestPresentationV2Decorators::default();
+
+        let expected = json!({
+            "formats": [{
+                "attach_id": "test_attach_id",
+                "format": "dif/presentation-exchange/definitions@v1.0"
+            }],
+            "request_presentations~attach": content.request_presentations_attach,
+        });
+
+        test_utils::test_msg(content, decorators, PresentProofTypeV2_0::RequestPresentation, expected);
+    }
+
+    #[test]
+    fn test_extended_request_presentation_v2() {
+        let mut content = make_content();
+        content.goal_code = Some("test_goal_code".to_owned());
+        content.comment = Some("test_comment".to_owned());
+        content.will_confirm = Some(true);
+
+        let mut decorators = RequestPresentationV2Decorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "goal_code": content.goal_code,
+            "comment": content.comment,
+            "will_confirm": content.will_confirm,
+            "formats": [{
+                "attach_id": "test_attach_id",
+                "format": "dif/presentation-exchange/definitions@v1.0"
+            }],
+            "request_presentations~attach": content.request_presentations_attach,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, PresentProofTypeV2_0::RequestPresentation, expected);
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Endorse = MsgParts<EndorseContent, EndorseDecorators>;
+
+/// The answer of an endorser which endorsed the transaction. It carries the endorsed transaction
+/// when the author writes it, and nothing when the endorser already wrote it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct EndorseContent {
+    pub endorser_did: String,
+    #[serde(rename = "messages~attach")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages_attach: Vec<Attachment>,
+}
+
+impl EndorseContent {
+    pub fn new(endorser_did: String) -> Self {
+        Self {
+            endorser_did,
+            messages_attach: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct EndorseDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl EndorseDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::transactions::TransactionsTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_endorse() {
+        let content = EndorseContent::new("V4SGRU86Z58d6TV7PBUe6f".to_owned());
+
+        let decorators = EndorseDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "endorser_did": content.endorser_did,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Endorse, expected);
+    }
+
+    #[test]
+    fn test_extended_endorse() {
+        let mut content = EndorseContent::new("V4SGRU86Z58d6TV7PBUe6f".to_owned());
+        content.messages_attach = vec![make_extended_attachment()];
+
+        let mut decorators = EndorseDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "endorser_did": content.endorser_did,
+            "messages~attach": content.messages_attach,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Endorse, expected);
+    }
+}
@@ -0,0 +1,71 @@
+//! Module containing the `vcx-transactions` protocol messages, with which authors without the right
+//! to write to a ledger have their transactions endorsed. The protocol is not interoperable with the
+//! endorser protocol of ACA-Py.
+
+pub mod endorse;
+pub mod refuse;
+pub mod request;
+
+use derive_more::From;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use self::{
+    endorse::{Endorse, EndorseContent, EndorseDecorators},
+    refuse::{Refuse, RefuseContent, RefuseDecorators},
+    request::{Request, RequestContent, RequestDecorators},
+};
+use crate::{
+    misc::utils::{into_msg_with_type, transit_to_aries_msg},
+    msg_fields::traits::DelayedSerde,
+    msg_types::{
+        protocols::transactions::{TransactionsType as TransactionsKind, TransactionsTypeV1, TransactionsTypeV1_0},
+        MsgWithType,
+    },
+};
+
+#[derive(Clone, Debug, From, PartialEq)]
+pub enum Transactions {
+    Request(Request),
+    Endorse(Endorse),
+    Refuse(Refuse),
+}
+
+impl DelayedSerde for Transactions {
+    type MsgType<'a> = (TransactionsKind, &'a str);
+
+    fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (protocol, kind_str) = msg_type;
+
+        let kind = match protocol {
+            TransactionsKind::V1(TransactionsTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+        };
+
+        match kind.map_err(D::Error::custom)? {
+            TransactionsTypeV1_0::Request => Request::deserialize(deserializer).map(From::from),
+            TransactionsTypeV1_0::Endorse => Endorse::deserialize(deserializer).map(From::from),
+            TransactionsTypeV1_0::Refuse => Refuse::deserialize(deserializer).map(From::from),
+        }
+    }
+
+    fn delayed_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Request(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Endorse(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Refuse(v) => MsgWithType::from(v).serialize(serializer),
+        }
+    }
+}
+
+transit_to_aries_msg!(RequestContent: RequestDecorators, Transactions);
+transit_to_aries_msg!(EndorseContent: EndorseDecorators, Transactions);
+transit_to_aries_msg!(RefuseContent: RefuseDecorators, Transactions);
+
+into_msg_with_type!(Request, TransactionsTypeV1_0, Request);
+into_msg_with_type!(Endorse, TransactionsTypeV1_0, Endorse);
+into_msg_with_type!(Refuse, TransactionsTypeV1_0, Refuse);
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Refuse = MsgParts<RefuseContent, RefuseDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RefuseContent {
+    pub endorser_did: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+impl RefuseContent {
+    pub fn new(endorser_did: String) -> Self {
+        Self {
+            endorser_did,
+            explanation: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RefuseDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl RefuseDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::transactions::TransactionsTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_refuse() {
+        let content = RefuseContent::new("V4SGRU86Z58d6TV7PBUe6f".to_owned());
+
+        let decorators = RefuseDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "endorser_did": content.endorser_did,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Refuse, expected);
+    }
+
+    #[test]
+    fn test_extended_refuse() {
+        let mut content = RefuseContent::new("V4SGRU86Z58d6TV7PBUe6f".to_owned());
+        content.explanation = Some("Schemas of this author are not endorsed".to_owned());
+
+        let mut decorators = RefuseDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "endorser_did": content.endorser_did,
+            "explanation": content.explanation,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Refuse, expected);
+    }
+}
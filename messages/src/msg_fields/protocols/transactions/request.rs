@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Request = MsgParts<RequestContent, RequestDecorators>;
+
+/// The transaction an author asks an endorser to endorse, prepared for endorsement and signed by
+/// the author.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(rename = "messages~attach")]
+    pub messages_attach: Vec<Attachment>,
+    /// Whether the endorser writes the transaction to the ledger, rather than returning it
+    /// endorsed for the author to write.
+    pub endorser_write_txn: bool,
+}
+
+impl RequestContent {
+    pub fn new(messages_attach: Vec<Attachment>, endorser_write_txn: bool) -> Self {
+        Self {
+            comment: None,
+            messages_attach,
+            endorser_write_txn,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct RequestDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::transactions::TransactionsTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_request() {
+        let content = RequestContent::new(vec![make_extended_attachment()], false);
+
+        let decorators = RequestDecorators::default();
+
+        let expected = json!({
+            "messages~attach": content.messages_attach,
+            "endorser_write_txn": false
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Request, expected);
+    }
+
+    #[test]
+    fn test_extended_request() {
+        let mut content = RequestContent::new(vec![make_extended_attachment()], true);
+        content.comment = Some("test_comment".to_owned());
+
+        let mut decorators = RequestDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "comment": content.comment,
+            "messages~attach": content.messages_attach,
+            "endorser_write_txn": true,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, TransactionsTypeV1_0::Request, expected);
+    }
+}
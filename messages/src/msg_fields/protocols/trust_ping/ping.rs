@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    decorators::{thread::Thread, timing::Timing},
+    decorators::{thread::Thread, timing::Timing, transport::Transport},
     msg_parts::MsgParts,
 };
 
@@ -23,6 +23,9 @@ pub struct PingDecorators {
     #[serde(rename = "~timing")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timing: Option<Timing>,
+    #[serde(rename = "~transport")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
 }
 
 #[cfg(test)]
@@ -33,7 +36,9 @@ mod tests {
 
     use super::*;
     use crate::{
-        decorators::thread::tests::make_extended_thread, misc::test_utils, msg_types::trust_ping::TrustPingTypeV1_0,
+        decorators::{thread::tests::make_extended_thread, transport::tests::make_extended_transport},
+        misc::test_utils,
+        msg_types::trust_ping::TrustPingTypeV1_0,
     };
 
     #[test]
@@ -56,11 +61,13 @@ mod tests {
 
         let mut decorators = PingDecorators::default();
         decorators.thread = Some(make_extended_thread());
+        decorators.transport = Some(make_extended_transport());
 
         let expected = json!({
             "response_requested": false,
             "comment": content.comment,
-            "~thread": decorators.thread
+            "~thread": decorators.thread,
+            "~transport": decorators.transport
         });
 
         test_utils::test_msg(content, decorators, TrustPingTypeV1_0::Ping, expected);
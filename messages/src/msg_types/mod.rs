@@ -14,8 +14,8 @@ use std::{marker::PhantomData, str::FromStr};
 use serde::Serialize;
 
 pub use protocols::{
-    basic_message, connection, cred_issuance, discover_features, notification, out_of_band, present_proof,
-    report_problem, revocation, routing, trust_ping, Protocol,
+    basic_message, connection, coordinate_mediation, cred_issuance, did_exchange, discover_features, notification,
+    out_of_band, pickup, present_proof, report_problem, revocation, routing, transactions, trust_ping, Protocol,
 };
 pub use role::Role;
 
@@ -41,8 +41,8 @@ impl<'a> TryFrom<&'a str> for MessageType<'a> {
         // The first element will be the string repr of the protocol
         // while the second will be the message kind.
         let Some((protocol_str, kind)) = msg_type_str.rsplit_once('/') else {
-                return Err(format!("Invalid message type: {msg_type_str}"));
-            };
+            return Err(format!("Invalid message type: {msg_type_str}"));
+        };
 
         // Parse the Protocol instance
         let protocol = match Protocol::from_str(protocol_str) {
@@ -10,6 +10,7 @@ use crate::msg_types::{role::Role, MsgKindType};
 #[msg_type(protocol = "present-proof")]
 pub enum PresentProofType {
     V1(PresentProofTypeV1),
+    V2(PresentProofTypeV2),
 }
 
 #[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
@@ -31,6 +32,24 @@ pub enum PresentProofTypeV1_0 {
     ProblemReport,
 }
 
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(PresentProofType, Protocol))]
+#[msg_type(major = 2)]
+pub enum PresentProofTypeV2 {
+    #[msg_type(minor = 0, roles = "Role::Prover, Role::Verifier")]
+    V2_0(MsgKindType<PresentProofTypeV2_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PresentProofTypeV2_0 {
+    ProposePresentation,
+    RequestPresentation,
+    Presentation,
+    Ack,
+    ProblemReport,
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -59,7 +78,7 @@ mod tests {
     fn test_unsupported_version_present_proof() {
         test_utils::test_serde(
             Protocol::from(PresentProofTypeV1::new_v1_0()),
-            json!("https://didcomm.org/present-proof/2.0"),
+            json!("https://didcomm.org/present-proof/3.0"),
         )
     }
 
@@ -107,4 +126,56 @@ mod tests {
             PresentProofTypeV1::new_v1_0(),
         )
     }
+
+    #[test]
+    fn test_protocol_present_proof_v2() {
+        test_utils::test_serde(
+            Protocol::from(PresentProofTypeV2::new_v2_0()),
+            json!("https://didcomm.org/present-proof/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_present_proof_v2() {
+        test_utils::test_msg_type_resolution(
+            "https://didcomm.org/present-proof/2.255",
+            PresentProofTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_propose_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/present-proof/2.0",
+            "propose-presentation",
+            PresentProofTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_request_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/present-proof/2.0",
+            "request-presentation",
+            PresentProofTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_presentation_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/present-proof/2.0",
+            "presentation",
+            PresentProofTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_ack_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/present-proof/2.0",
+            "ack",
+            PresentProofTypeV2::new_v2_0(),
+        )
+    }
 }
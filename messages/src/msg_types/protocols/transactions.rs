@@ -0,0 +1,92 @@
+use derive_more::{From, TryInto};
+use messages_macros::MessageType;
+use strum_macros::{AsRefStr, EnumString};
+use transitive::TransitiveFrom;
+
+use super::Protocol;
+use crate::msg_types::{role::Role, MsgKindType};
+
+/// The transactions protocol of aries-vcx. ACA-Py's endorser protocol names its messages and
+/// shapes their contents differently under `https://didcomm.org/transactions/1.0`, so this one
+/// goes by its own name rather than being mistaken for it.
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, MessageType)]
+#[msg_type(protocol = "vcx-transactions")]
+pub enum TransactionsType {
+    V1(TransactionsTypeV1),
+}
+
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(TransactionsType, Protocol))]
+#[msg_type(major = 1)]
+pub enum TransactionsTypeV1 {
+    #[msg_type(minor = 0, roles = "Role::Author, Role::Endorser")]
+    V1_0(MsgKindType<TransactionsTypeV1_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum TransactionsTypeV1_0 {
+    Request,
+    Endorse,
+    Refuse,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    #[test]
+    fn test_protocol_transactions() {
+        test_utils::test_serde(
+            Protocol::from(TransactionsTypeV1::new_v1_0()),
+            json!("https://didcomm.org/vcx-transactions/1.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_transactions() {
+        test_utils::test_msg_type_resolution(
+            "https://didcomm.org/vcx-transactions/1.255",
+            TransactionsTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsupported_version_transactions() {
+        test_utils::test_serde(
+            Protocol::from(TransactionsTypeV1::new_v1_0()),
+            json!("https://didcomm.org/vcx-transactions/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_request() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/vcx-transactions/1.0",
+            "request",
+            TransactionsTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_endorse() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/vcx-transactions/1.0",
+            "endorse",
+            TransactionsTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_refuse() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/vcx-transactions/1.0",
+            "refuse",
+            TransactionsTypeV1::new_v1_0(),
+        )
+    }
+}
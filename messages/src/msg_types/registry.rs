@@ -5,10 +5,22 @@ use shared_vcx::maybe_known::MaybeKnown;
 
 use super::{role::Role, Protocol};
 use crate::msg_types::protocols::{
-    basic_message::BasicMessageTypeV1, connection::ConnectionTypeV1, cred_issuance::CredentialIssuanceTypeV1,
-    discover_features::DiscoverFeaturesTypeV1, notification::NotificationTypeV1, out_of_band::OutOfBandTypeV1,
-    present_proof::PresentProofTypeV1, report_problem::ReportProblemTypeV1, revocation::RevocationTypeV2,
-    routing::RoutingTypeV1, signature::SignatureTypeV1, trust_ping::TrustPingTypeV1,
+    basic_message::BasicMessageTypeV1,
+    connection::ConnectionTypeV1,
+    coordinate_mediation::CoordinateMediationTypeV1,
+    cred_issuance::{CredentialIssuanceTypeV1, CredentialIssuanceTypeV2},
+    did_exchange::DidExchangeTypeV1,
+    discover_features::{DiscoverFeaturesTypeV1, DiscoverFeaturesTypeV2},
+    notification::NotificationTypeV1,
+    out_of_band::OutOfBandTypeV1,
+    pickup::PickupTypeV2,
+    present_proof::{PresentProofTypeV1, PresentProofTypeV2},
+    report_problem::ReportProblemTypeV1,
+    revocation::RevocationTypeV2,
+    routing::RoutingTypeV1,
+    signature::SignatureTypeV1,
+    transactions::TransactionsTypeV1,
+    trust_ping::TrustPingTypeV1,
 };
 type RegistryMap = HashMap<(&'static str, u8), Vec<RegistryEntry>>;
 
@@ -60,14 +72,21 @@ lazy_static! {
         map_insert(&mut m, extract_parts!(RoutingTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(BasicMessageTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(ConnectionTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(CoordinateMediationTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(SignatureTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(CredentialIssuanceTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(CredentialIssuanceTypeV2::new_v2_0()));
+        map_insert(&mut m, extract_parts!(DidExchangeTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(DiscoverFeaturesTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(DiscoverFeaturesTypeV2::new_v2_0()));
         map_insert(&mut m, extract_parts!(NotificationTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(OutOfBandTypeV1::new_v1_1()));
+        map_insert(&mut m, extract_parts!(PickupTypeV2::new_v2_0()));
         map_insert(&mut m, extract_parts!(PresentProofTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(PresentProofTypeV2::new_v2_0()));
         map_insert(&mut m, extract_parts!(ReportProblemTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(RevocationTypeV2::new_v2_0()));
+        map_insert(&mut m, extract_parts!(TransactionsTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(TrustPingTypeV1::new_v1_0()));
         m
     };
@@ -18,4 +18,7 @@ pub enum Role {
     Notified,
     Notifier,
     Mediator,
+    Recipient,
+    Author,
+    Endorser,
 }
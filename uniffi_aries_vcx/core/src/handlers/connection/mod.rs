@@ -11,6 +11,8 @@ pub struct ConnectionState {
 pub enum ConnectionRole {
     Invitee,
     Inviter,
+    Requester,
+    Responder,
 }
 
 pub enum ConnectionProtocolState {
@@ -32,6 +34,14 @@ impl From<ThinState> for ConnectionState {
                 role: ConnectionRole::Invitee,
                 protocol_state: ConnectionProtocolState::from(state),
             },
+            ThinState::Requester(state) => ConnectionState {
+                role: ConnectionRole::Requester,
+                protocol_state: ConnectionProtocolState::from(state),
+            },
+            ThinState::Responder(state) => ConnectionState {
+                role: ConnectionRole::Responder,
+                protocol_state: ConnectionProtocolState::from(state),
+            },
         }
     }
 }